@@ -369,7 +369,7 @@ describe("E2E Workflow - Complete Compliance Scanning Pipeline", () => {
 
       await commands.apply_fix(1)
 
-      expect(mockInvoke).toHaveBeenCalledWith("apply_fix", { fixId: 1 })
+      expect(mockInvoke).toHaveBeenCalledWith("apply_fix", { fixId: 1, confirmed: false })
     })
   })
 
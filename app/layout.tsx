@@ -5,6 +5,7 @@ import "./globals.css"
 import { WaterBackground } from "@/components/ui/water-background"
 import { ConsoleLogger } from "@/components/console-logger"
 import { McpInit } from "@/components/mcp-init"
+import { DeepLinkListener } from "@/components/deep-link-listener"
 import { Toaster } from "sonner"
 import { ErrorBoundary } from "@/components/ErrorBoundary"
 import { TopNav } from "@/components/layout/top-nav"
@@ -33,6 +34,7 @@ export default function RootLayout({
       <body className={inter.className}>
         <ErrorBoundary>
           <McpInit />
+          <DeepLinkListener />
           <ConsoleLogger />
           <WaterBackground />
           <div className="fixed inset-0 bg-black/78 backdrop-blur-[2px] z-[5]" />
@@ -0,0 +1,68 @@
+"use client"
+
+import { useEffect } from 'react'
+import { useRouter } from 'next/navigation'
+import { emit, listen, type UnlistenFn } from '@tauri-apps/api/event'
+import { toast } from 'sonner'
+
+type DeepLinkTarget =
+  | { kind: 'violation'; id: number }
+  | { kind: 'scan'; id: number }
+  | { kind: 'project'; id: number }
+
+interface DeepLinkError {
+  url: string
+  error: string
+}
+
+function routeFor(target: DeepLinkTarget): string {
+  switch (target.kind) {
+    case 'violation':
+      return `/violation?id=${target.id}`
+    case 'scan':
+      return `/scan?id=${target.id}`
+    case 'project':
+      return `/scan?project=${target.id}`
+  }
+}
+
+/**
+ * Routes ryn:// deep links forwarded by the backend.
+ *
+ * Emits `app-ready` once listeners are registered so the backend can flush
+ * links that arrived while the app was starting.
+ */
+export function DeepLinkListener() {
+  const router = useRouter()
+
+  useEffect(() => {
+    if (typeof window === 'undefined') return
+
+    let unlistenNavigate: UnlistenFn | null = null
+    let unlistenError: UnlistenFn | null = null
+
+    const setupListeners = async () => {
+      unlistenNavigate = await listen<DeepLinkTarget>('deep-link-navigate', (event) => {
+        router.push(routeFor(event.payload))
+      })
+
+      unlistenError = await listen<DeepLinkError>('deep-link-error', (event) => {
+        console.warn('[DeepLink] Ignoring invalid link:', event.payload.url, event.payload.error)
+        toast.error(`Could not open link: ${event.payload.error}`)
+      })
+
+      await emit('app-ready')
+    }
+
+    setupListeners().catch((e) => {
+      console.error('[DeepLink] Failed to setup deep link listeners:', e)
+    })
+
+    return () => {
+      if (unlistenNavigate) unlistenNavigate()
+      if (unlistenError) unlistenError()
+    }
+  }, [router])
+
+  return null
+}
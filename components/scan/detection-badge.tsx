@@ -1,4 +1,4 @@
-import { SearchCodeIcon, SparklesIcon, Combine } from "lucide-react"
+import { SearchCodeIcon, SparklesIcon, Combine, FileInput } from "lucide-react"
 import type { DetectionMethod } from "@/lib/types/violation"
 
 interface DetectionBadgeProps {
@@ -26,6 +26,12 @@ export function DetectionBadge({ method, className = "" }: DetectionBadgeProps)
       icon: Combine,
       label: "Hybrid",
     },
+    imported: {
+      bg: "bg-slate-500/10",
+      text: "text-slate-600 dark:text-slate-400",
+      icon: FileInput,
+      label: "Imported",
+    },
   }
 
   const style = styles[method]
@@ -10,6 +10,7 @@ interface ScanControlsProps {
 const CONTROL_COPY: Record<string, string> = {
   "CC6.1": "Access Controls",
   "CC6.7": "Encryption & Secrets",
+  "CC7.1": "Vulnerable Dependencies",
   "CC7.2": "Logging & Monitoring",
   "A1.2": "Data Availability",
 }
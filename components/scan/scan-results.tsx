@@ -22,6 +22,7 @@ export function ScanResults() {
   const [selectedControls, setSelectedControls] = useState<Record<string, boolean>>({
     "CC6.1": true,
     "CC6.7": true,
+    "CC7.1": true,
     "CC7.2": true,
     "A1.2": true,
   })
@@ -16,7 +16,7 @@ export function ViolationFilters({
   onControlChange,
 }: ViolationFiltersProps) {
   const severities: (Severity | "all")[] = ["all", "critical", "high", "medium", "low"]
-  const controls = ["all", "CC6.1", "CC6.7", "CC7.2", "CC8.1"]
+  const controls = ["all", "CC6.1", "CC6.7", "CC7.1", "CC7.2", "CC8.1"]
 
   return (
     <div className="flex items-center gap-4">
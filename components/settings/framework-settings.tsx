@@ -77,6 +77,8 @@ export function FrameworkSettings() {
             <option value="spring">Spring Boot</option>
             <option value="nextjs">Next.js</option>
             <option value="react">React</option>
+            <option value="actix">Actix Web</option>
+            <option value="rust">Rust</option>
           </select>
           {isSaving && (
             <p className="text-xs text-muted-foreground mt-2">Saving...</p>
@@ -11,6 +11,7 @@ import {
   SiFlask,
   SiRubyonrails,
   SiSpring,
+  SiRust,
 } from "react-icons/si"
 import type { IconType } from "react-icons"
 
@@ -25,6 +26,8 @@ const frameworkIcons: { match: (name: string) => boolean; Icon: IconType; label:
   { match: (name) => name.includes("flask"), Icon: SiFlask, label: "Flask" },
   { match: (name) => name.includes("rails"), Icon: SiRubyonrails, label: "Rails" },
   { match: (name) => name.includes("spring"), Icon: SiSpring, label: "Spring" },
+  { match: (name) => name.includes("actix"), Icon: SiRust, label: "Actix Web" },
+  { match: (name) => name.includes("rust"), Icon: SiRust, label: "Rust" },
 ]
 
 interface FrameworkBadgeProps {
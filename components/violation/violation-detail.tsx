@@ -176,7 +176,8 @@ export function ViolationDetail({ violationId }: ViolationDetailProps) {
     try {
       setIsApplying(true)
       showInfo("Applying fix...")
-      await apply_fix(violationDetail.fix.id)
+      // Only reached from the apply confirmation dialog
+      await apply_fix(violationDetail.fix.id, true)
       showSuccess("Fix applied successfully!")
       setShowApplyConfirm(false)
       // Reload violation to get updated status
@@ -5,11 +5,14 @@
 // are enabled. This runs against the live Tauri app via the
 // MCP bridge socket (~/.tauri/mcp.sock).
 
+import fs from 'fs';
 import net from 'net';
 import os from 'os';
 import path from 'path';
 
 const SOCKET_PATH = path.join(os.homedir(), '.tauri', 'mcp.sock');
+// Written by the bridge on startup; the first request must present it
+const TOKEN_PATH = path.join(os.homedir(), '.tauri', 'mcp.token');
 
 let nextId = 1;
 const pending = new Map();
@@ -66,7 +69,9 @@ async function main() {
     socket.once('error', reject);
   });
 
-  console.log('✓ Connected to MCP socket\n');
+  console.log('✓ Connected to MCP socket');
+  await sendRequest(socket, 'authenticate', { token: fs.readFileSync(TOKEN_PATH, 'utf8').trim() });
+  console.log('✓ Authenticated\n');
 
   try {
     // 0) Ensure a project is selected by seeding localStorage and reloading
@@ -6,11 +6,14 @@
 // and sends JSON-RPC commands such as `browser_navigate`
 // and `browser_execute`.
 
+import fs from 'fs';
 import net from 'net';
 import os from 'os';
 import path from 'path';
 
 const SOCKET_PATH = path.join(os.homedir(), '.tauri', 'mcp.sock');
+// Written by the bridge on startup; the first request must present it
+const TOKEN_PATH = path.join(os.homedir(), '.tauri', 'mcp.token');
 
 let nextId = 1;
 const pending = new Map();
@@ -117,7 +120,9 @@ async function main() {
     socket.once('error', reject);
   });
 
-  console.log('✓ Connected to Tauri MCP socket\n');
+  console.log('✓ Connected to Tauri MCP socket');
+  await sendRequest(socket, 'authenticate', { token: fs.readFileSync(TOKEN_PATH, 'utf8').trim() });
+  console.log('✓ Authenticated\n');
 
   try {
     // Navigate to the Settings page by path
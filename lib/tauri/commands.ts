@@ -13,6 +13,46 @@ export interface Project {
   path: string
   framework?: string
   created_at?: string
+  project_cost_limit_usd?: number | null
+}
+
+/** Per-project settings for opening GitHub pull requests when fixes are applied */
+export interface PullRequestConfig {
+  enabled: boolean
+  /** Remote the fix branch is pushed to (default "origin") */
+  remote: string
+  /** Branch the PR targets; the branch checked out when the fix is applied if null */
+  base_branch: string | null
+  /**
+   * PR description; placeholders: {control_id}, {severity}, {description}, {file_path},
+   * {line_number}, {explanation}, {changes_summary}, {test_recommendation}
+   */
+  pr_template: string | null
+}
+
+/** Per-project reporting thresholds; unset fields report everything */
+export interface ProjectSettings {
+  project_id: number
+  /** Lowest severity to report */
+  min_severity: "critical" | "high" | "medium" | "low" | null
+  /** Lowest confidence (0-100) for LLM-only findings to be kept */
+  min_llm_confidence: number | null
+  /** Control IDs (e.g. "CC7.2") whose violations aren't reported */
+  disabled_controls: string[]
+  updated_at?: string | null
+}
+
+/**
+ * Extra directory scanned with a project; violations in it have paths
+ * like "@{label}/src/App.tsx"
+ */
+export interface ProjectRoot {
+  id: number
+  project_id: number
+  path: string
+  label: string
+  framework: string | null
+  created_at: string
 }
 
 export interface ScanResult {
@@ -26,10 +66,39 @@ export interface ScanResult {
   total_files: number
   violations_found: number
   scan_mode: string
+  partial_reason?: string | null
+  baseline_scan_id?: number | null
+  new_violation_count?: number
+  resolved_violation_count?: number
+  enriched_count?: number
+  context_extraction_failed?: number
+  processing_timeouts?: number | null
+  commit_sha?: string | null
   critical_count: number
   high_count: number
   medium_count: number
   low_count: number
+  detail_stats?: ScanDetailStats
+}
+
+export interface SeverityCounts {
+  critical: number
+  high: number
+  medium: number
+  low: number
+}
+
+export interface ControlTrendPoint {
+  completed_at: string
+  violation_count: number
+}
+
+export interface ScanDetailStats {
+  scan_id: number
+  /** Severity counts keyed by control_id */
+  by_control: Record<string, SeverityCounts>
+  /** Violation counts over the project's last 10 completed scans, oldest first */
+  trends: Record<string, ControlTrendPoint[]>
 }
 
 export interface Violation {
@@ -43,10 +112,31 @@ export interface Violation {
   file_path: string
   status: string
   created_at: string
-  detection_method: "regex" | "llm" | "hybrid"
+  detection_method: "regex" | "llm" | "hybrid" | "imported"
   confidence_score?: number
   llm_reasoning?: string
   regex_reasoning?: string
+  is_new?: boolean
+  last_status_change_reason?: string
+  /** Monorepo package containing the file */
+  package_name?: string | null
+  /** Identity across scans: hash of control, file and normalized snippet */
+  fingerprint?: string
+  lifecycle?: "new" | "recurring"
+  /** Key of the Jira issue filed with create_jira_issue, e.g. "SEC-142" */
+  jira_issue_key?: string | null
+  /** Set only by get_project_violations: earliest and latest scans reporting this finding */
+  first_seen_scan_id?: number | null
+  last_seen_scan_id?: number | null
+}
+
+export interface StatusTransition {
+  id: number
+  violation_id: number
+  from_status: string
+  to_status: string
+  reason?: string
+  changed_at: string
 }
 
 export interface Fix {
@@ -59,6 +149,71 @@ export interface Fix {
   applied_at: string | null
   applied_by: string
   git_commit_sha: string | null
+  /** 1 for the first fix generated for the violation, 2 for the next, ... */
+  fix_attempt_number: number
+  created_at: string
+  /** Branch the fix was committed to when the fix_on_new_branch setting is on */
+  git_branch?: string | null
+  /** One-line summary of what the fix changes */
+  changes_summary?: string | null
+  /** How to test the fix (also stored in the fix_applied audit event's metadata) */
+  test_recommendation?: string | null
+}
+
+/** Git commit a fix was applied in (also stored as the fix_applied audit event's metadata) */
+export interface CommitInfo {
+  sha: string
+  short_sha: string
+  message: string
+  author: string
+  email: string
+  /** RFC 3339, UTC */
+  timestamp: string
+  files_changed: string[]
+}
+
+/** Lines start..=end of a file (1-based) */
+export interface LineRange {
+  start: number
+  end: number
+}
+
+/** What apply_fix would change, computed without writing the file */
+export interface FixPreview {
+  file_path: string
+  /** Unified diff with a single hunk; empty when nothing changes */
+  diff: string
+  /** Lines of the current file that are replaced (null for a pure insertion) */
+  removed_lines: LineRange | null
+  /** Lines of the fixed file that replace them (null for a pure deletion) */
+  added_lines: LineRange | null
+}
+
+/** Payload of the "fix-progress" event emitted by generate_fixes_batch */
+export interface FixProgressEvent {
+  scan_id: number
+  violation_id: number
+  /** Violations finished so far, including failures */
+  completed: number
+  total: number
+  succeeded: number
+  failed: number
+  cost_usd: number
+  error: string | null
+}
+
+export interface BatchFixResult {
+  scan_id: number
+  /** Violations queued for fix generation */
+  total: number
+  generated: Fix[]
+  failed: { violation_id: number; error: string }[]
+  /** Open violations skipped because they already have an unapplied fix */
+  skipped_existing: number
+  /** Violations left untouched after stopping at the cost limit */
+  not_attempted: number
+  total_cost_usd: number
+  stopped_at_cost_limit: boolean
 }
 
 export interface Control {
@@ -94,6 +249,30 @@ export interface Settings {
   updated_at: string
 }
 
+export interface SettingsProfile {
+  id: number
+  name: string
+  description: string
+  /** Setting key/value pairs written on apply */
+  settings: Record<string, string>
+  built_in: boolean
+  created_at: string
+}
+
+export interface MaintenanceStats {
+  pages_freed: number
+  size_before_bytes: number
+  size_after_bytes: number
+  duration_ms: number
+}
+
+export interface ExportStats {
+  rows_exported: number
+  file_size_bytes: number
+  /** Hex-encoded SHA-256 of the CSV file */
+  sha256_checksum: string
+}
+
 export interface ScanCost {
   id: number
   scan_id: number
@@ -106,6 +285,63 @@ export interface ScanCost {
   created_at: string
 }
 
+export interface CostEstimate {
+  file_count: number
+  files_by_language: Record<string, number>
+  estimated_llm_files: number
+  estimated_tokens: number
+  estimated_cost_usd: number
+  confidence: number
+  effective_cost_limit_usd: number
+}
+
+export interface ComplianceDataPoint {
+  scan_id: number
+  completed_at: string
+  cc61_score: number
+  cc67_score: number
+  cc72_score: number
+  a12_score: number
+  overall_score: number
+}
+
+export interface SpendSummary {
+  total_cost_usd: number
+  total_input_tokens: number
+  total_output_tokens: number
+  total_cache_read_tokens: number
+  scan_count: number
+  avg_cost_per_scan: number
+  most_expensive_scan_id: number | null
+}
+
+export interface DailySpend {
+  date: string
+  total_cost_usd: number
+  scan_count: number
+}
+
+export interface SpendingReport {
+  summary: SpendSummary
+  daily: DailySpend[]
+  month_to_date_usd: number
+  monthly_budget_usd: number | null
+}
+
+export interface CategorySummary {
+  category: string
+  total_violations: number
+  open_violations: number
+  compliance_score: number
+}
+
+/** Payload of the "budget-exceeded" event */
+export interface BudgetExceededEvent {
+  scan_id: number
+  monthly_budget_usd: number
+  month_to_date_usd: number
+}
+
 // ============================================================================
 // PROJECT COMMANDS
 // ============================================================================
@@ -139,6 +375,79 @@ export async function get_projects(): Promise<Project[]> {
   return await invoke<Project[]>("get_projects")
 }
 
+/**
+ * Set a project's cost limit, overriding the global cost_limit_per_scan
+ */
+export async function set_project_cost_limit(projectId: number, limitUsd: number): Promise<void> {
+  return await invoke<void>("set_project_cost_limit", { projectId, limitUsd })
+}
+
+/**
+ * Remove a project's cost limit so its scans use the global limit
+ */
+export async function remove_project_cost_limit(projectId: number): Promise<void> {
+  return await invoke<void>("remove_project_cost_limit", { projectId })
+}
+
+/**
+ * Configure pull requests for a project's applied fixes (needs GITHUB_TOKEN)
+ */
+export async function set_project_pull_request_config(
+  projectId: number,
+  config: PullRequestConfig
+): Promise<void> {
+  return await invoke<void>("set_project_pull_request_config", { projectId, config })
+}
+
+/**
+ * Get a project's pull request settings
+ */
+export async function get_project_pull_request_config(projectId: number): Promise<PullRequestConfig> {
+  return await invoke<PullRequestConfig>("get_project_pull_request_config", { projectId })
+}
+
+/**
+ * Set a project's minimum severity, minimum LLM confidence and disabled controls
+ */
+export async function set_project_settings(
+  projectId: number,
+  settings: Omit<ProjectSettings, "project_id" | "updated_at">
+): Promise<ProjectSettings> {
+  return await invoke<ProjectSettings>("set_project_settings", { projectId, settings })
+}
+
+/**
+ * Get a project's reporting thresholds
+ */
+export async function get_project_settings(projectId: number): Promise<ProjectSettings> {
+  return await invoke<ProjectSettings>("get_project_settings", { projectId })
+}
+
+/**
+ * Scan another directory (e.g. a frontend repo) as part of a project
+ */
+export async function add_project_root(
+  projectId: number,
+  path: string,
+  label?: string
+): Promise<ProjectRoot> {
+  return await invoke<ProjectRoot>("add_project_root", { projectId, path, label })
+}
+
+/**
+ * Stop scanning one of a project's extra roots
+ */
+export async function remove_project_root(projectId: number, rootId: number): Promise<void> {
+  return await invoke<void>("remove_project_root", { projectId, rootId })
+}
+
+/**
+ * Get a project's extra roots (its own path is the primary root)
+ */
+export async function get_project_roots(projectId: number): Promise<ProjectRoot[]> {
+  return await invoke<ProjectRoot[]>("get_project_roots", { projectId })
+}
+
 // ============================================================================
 // SCAN COMMANDS
 // ============================================================================
@@ -150,13 +459,50 @@ export async function detect_framework(path: string): Promise<string> {
   return await invoke<string>("detect_framework", { path })
 }
 
+export interface ScanOptions {
+  /** Record phase/file timings to a Chrome trace profile */
+  profile_scan?: boolean
+}
+
 /**
  * Start scanning a project for violations
  */
 export async function scan_project(
-  projectId: number
+  projectId: number,
+  options?: ScanOptions
+): Promise<ScanResult> {
+  return await invoke<ScanResult>("scan_project", { projectId, options })
+}
+
+/**
+ * Scan a project and diff its violations against an earlier scan
+ */
+export async function scan_project_with_baseline(
+  projectId: number,
+  baselineScanId: number
 ): Promise<ScanResult> {
-  return await invoke<ScanResult>("scan_project", { projectId })
+  return await invoke<ScanResult>("scan_project_with_baseline", { projectId, baselineScanId })
+}
+
+/**
+ * Re-scan only the files changed since the last completed scan's commit (or baseRef),
+ * carrying forward that scan's violations in unchanged files
+ */
+export async function scan_project_incremental(
+  projectId: number,
+  baseRef?: string
+): Promise<ScanResult> {
+  return await invoke<ScanResult>("scan_project_incremental", { projectId, baseRef })
+}
+
+/**
+ * Export the Chrome trace profile of a scan run with profile_scan enabled
+ */
+export async function export_scan_profile(
+  scanId: number,
+  dest: string
+): Promise<void> {
+  return await invoke<void>("export_scan_profile", { scanId, dest })
 }
 
 /**
@@ -168,6 +514,15 @@ export async function get_scan_progress(
   return await invoke<ScanResult>("get_scan_progress", { scanId })
 }
 
+/**
+ * Get per-control severity counts and recent trends for a scan
+ */
+export async function get_scan_detail_stats(
+  scanId: number
+): Promise<ScanDetailStats> {
+  return await invoke<ScanDetailStats>("get_scan_detail_stats", { scanId })
+}
+
 /**
  * Get all scans for a project
  */
@@ -179,7 +534,8 @@ export async function get_scans(
 
 /**
  * Start watching a project for real-time file changes
- * Emits "file-changed" events whenever files are modified, created, or deleted
+ * Emits "file-changed" events whenever files are modified, created, or deleted,
+ * and "violations-updated" after each changed file is re-checked against the latest scan
  */
 export async function watch_project(projectId: number): Promise<string> {
   return await invoke<string>("watch_project", { projectId })
@@ -192,6 +548,49 @@ export async function stop_watching(projectId: number): Promise<string> {
   return await invoke<string>("stop_watching", { projectId })
 }
 
+export interface WatcherStats {
+  events_received: number
+  events_filtered: number
+  events_coalesced: number
+  events_emitted: number
+}
+
+/**
+ * Get event counts for a project's file watcher
+ * @param projectId - ID of the watched project
+ */
+export async function get_watcher_stats(projectId: number): Promise<WatcherStats> {
+  return await invoke<WatcherStats>("get_watcher_stats", { projectId })
+}
+
+export interface IgnoreSource {
+  name: string
+  patterns: string[]
+}
+
+export interface IgnoredPath {
+  path: string
+  is_dir: boolean
+  source: string
+  pattern: string
+}
+
+export interface EffectiveIgnores {
+  project_id: number
+  sources: IgnoreSource[]
+  ignored_paths: IgnoredPath[]
+  truncated: boolean
+}
+
+/**
+ * Show what scans skip for a project and which rule skips it
+ * Sources are the built-in defaults, .gitignore, .rynignore and scan excludes
+ * @param projectId - ID of the project
+ */
+export async function get_effective_ignores(projectId: number): Promise<EffectiveIgnores> {
+  return await invoke<EffectiveIgnores>("get_effective_ignores", { projectId })
+}
+
 // ============================================================================
 // VIOLATION COMMANDS
 // ============================================================================
@@ -200,6 +599,8 @@ export interface ViolationFilters {
   severity?: string[]
   control?: string[]
   status?: string[]
+  control_category?: string
+  package_name?: string[]
 }
 
 /**
@@ -235,6 +636,143 @@ export async function dismiss_violation(
   await invoke<void>("dismiss_violation", { violationId })
 }
 
+/**
+ * Change the status of several violations at once (all or nothing)
+ * Returns the number of violations updated
+ */
+export async function update_violations_status(
+  violationIds: number[],
+  newStatus: string,
+  reason: string
+): Promise<number> {
+  return await invoke<number>("update_violations_status", { violationIds, newStatus, reason })
+}
+
+/**
+ * Reopen a scan's dismissed and fixed violations for re-audit
+ * Returns the number of violations reopened
+ */
+export async function reopen_violations(scanId: number): Promise<number> {
+  return await invoke<number>("reopen_violations", { scanId })
+}
+
+/**
+ * Get a violation's status transitions, oldest first
+ */
+export async function get_violation_status_history(
+  violationId: number
+): Promise<StatusTransition[]> {
+  return await invoke<StatusTransition[]>("get_violation_status_history", { violationId })
+}
+
+/**
+ * Get a scan's violations grouped by control category
+ */
+export async function get_violations_by_category(scanId: number): Promise<Record<string, Violation[]>> {
+  return await invoke<Record<string, Violation[]>>("get_violations_by_category", { scanId })
+}
+
+export interface PackageViolationSummary {
+  /** null for files outside every package */
+  package_name: string | null
+  total: number
+  open: number
+  severity_counts: SeverityCounts
+}
+
+/**
+ * Get a scan's violation counts per monorepo package, most violations first
+ */
+export async function get_violations_by_package(scanId: number): Promise<PackageViolationSummary[]> {
+  return await invoke<PackageViolationSummary[]>("get_violations_by_package", { scanId })
+}
+
+export interface ViolationWithHighlight {
+  violation: Violation
+  description: string
+  code_snippet: string
+  llm_reasoning: string | null
+  match_count: number
+}
+
+/**
+ * Search a scan's violations; matched words are wrapped in **...** in the returned fields
+ */
+export async function search_violations(scanId: number, query: string): Promise<ViolationWithHighlight[]> {
+  return await invoke<ViolationWithHighlight[]>("search_violations", { scanId, query })
+}
+
+/**
+ * Get a project's violations across all completed scans, one per
+ * (control, file, line) finding, with the scans it was first and last seen in
+ */
+export async function get_project_violations(
+  projectId: number,
+  filters?: ViolationFilters
+): Promise<Violation[]> {
+  return await invoke<Violation[]>("get_project_violations", { projectId, filters })
+}
+
+export type ViolationLifecycle = "new" | "recurring" | "resolved"
+
+export interface ViolationHistoryEntry {
+  id: number
+  project_id: number
+  fingerprint: string
+  control_id: string
+  file_path: string
+  severity: string
+  lifecycle: ViolationLifecycle
+  first_seen_scan_id: number
+  last_seen_scan_id: number
+  resolved_scan_id: number | null
+  times_seen: number
+  first_seen_at: string
+  last_seen_at: string
+  resolved_at: string | null
+}
+
+/**
+ * Get a project's findings across scans (one entry per fingerprint), most recently seen first
+ */
+export async function get_violation_history(
+  projectId: number,
+  lifecycle?: ViolationLifecycle
+): Promise<ViolationHistoryEntry[]> {
+  return await invoke<ViolationHistoryEntry[]>("get_violation_history", { projectId, lifecycle })
+}
+
+export interface BaselineEntry {
+  control_id: string
+  file_path: string
+  line_number: number
+  severity: string
+  description: string
+  line_hash: string
+}
+
+export interface Baseline {
+  version: number
+  created_at: string
+  scan_id: number
+  entries: BaselineEntry[]
+}
+
+/**
+ * Accept a completed scan's open and accepted-risk violations by writing them
+ * to the project's .ryn-baseline.json; later scans no longer report them
+ */
+export async function create_baseline(scanId: number): Promise<Baseline> {
+  return await invoke<Baseline>("create_baseline", { scanId })
+}
+
+/**
+ * List the violations suppressed by a project's .ryn-baseline.json
+ */
+export async function list_suppressed(projectId: number): Promise<BaselineEntry[]> {
+  return await invoke<BaselineEntry[]>("list_suppressed", { projectId })
+}
+
 // ============================================================================
 // FIX COMMANDS
 // ============================================================================
@@ -248,11 +786,68 @@ export async function generate_fix(
   return await invoke<Fix>("generate_fix", { violationId })
 }
 
+/**
+ * Generate fixes for every open violation in a scan, optionally for one control
+ *
+ * Emits "fix-progress" as each violation finishes. Past the cost limit it emits
+ * "cost-limit-reached" and waits for respond_to_cost_limit(scanId, ...).
+ */
+export async function generate_fixes_batch(
+  scanId: number,
+  controlIdFilter?: string
+): Promise<BatchFixResult> {
+  return await invoke<BatchFixResult>("generate_fixes_batch", {
+    scanId,
+    controlIdFilter: controlIdFilter ?? null,
+  })
+}
+
+/**
+ * Preview a fix as a unified diff without modifying the file
+ */
+export async function preview_fix(fixId: number): Promise<FixPreview> {
+  return await invoke<FixPreview>("preview_fix", { fixId })
+}
+
 /**
  * Apply a fix to the actual file and create a git commit
+ *
+ * Fixes with trust level "review" are rejected unless `confirmed` is true;
+ * "manual" fixes are always rejected.
+ */
+export async function apply_fix(fixId: number, confirmed = false): Promise<void> {
+  await invoke<void>("apply_fix", { fixId, confirmed })
+}
+
+/**
+ * Raise a fix's trust level (e.g. to "manual") so it isn't applied automatically
+ */
+export async function escalate_trust_level(
+  fixId: number,
+  newLevel: "review" | "manual"
+): Promise<void> {
+  await invoke<void>("escalate_trust_level", { fixId, newLevel })
+}
+
+/**
+ * Get every fix generated for a violation, newest first
+ */
+export async function get_fix_history(violationId: number): Promise<Fix[]> {
+  return await invoke<Fix[]>("get_fix_history", { violationId })
+}
+
+/**
+ * List a project's ryn/fix/{control_id}/{violation_id} git branches
  */
-export async function apply_fix(fixId: number): Promise<void> {
-  await invoke<void>("apply_fix", { fixId })
+export async function get_fix_branches(projectId: number): Promise<string[]> {
+  return await invoke<string[]>("get_fix_branches", { projectId })
+}
+
+/**
+ * Get the git commit an applied fix was committed in (fixes applied with fix_on_new_branch)
+ */
+export async function get_fix_commit_info(fixId: number): Promise<CommitInfo> {
+  return await invoke<CommitInfo>("get_fix_commit_info", { fixId })
 }
 
 // ============================================================================
@@ -313,6 +908,13 @@ export async function export_data(): Promise<string> {
   return await invoke<string>("export_data")
 }
 
+/**
+ * Optimize the database (PRAGMA optimize, ANALYZE, VACUUM) and record last_optimized_at
+ */
+export async function optimize_database(): Promise<MaintenanceStats> {
+  return await invoke<MaintenanceStats>("optimize_database")
+}
+
 /**
  * Complete onboarding by saving user's scanning preferences
  * @param scanMode - Scanning mode: "regex_only", "smart", or "analyze_all"
@@ -328,6 +930,304 @@ export async function complete_onboarding(
   })
 }
 
+/**
+ * Save a named settings preset
+ */
+export async function create_profile(
+  name: string,
+  settings: Record<string, string>,
+  description?: string
+): Promise<SettingsProfile> {
+  return await invoke<SettingsProfile>("create_profile", { name, settings, description })
+}
+
+/**
+ * List built-in (development, staging, production) and saved settings profiles
+ */
+export async function list_profiles(): Promise<SettingsProfile[]> {
+  return await invoke<SettingsProfile[]>("list_profiles")
+}
+
+/**
+ * Apply all of a profile's settings in one transaction
+ */
+export async function apply_profile(profileId: number): Promise<void> {
+  await invoke<void>("apply_profile", { profileId })
+}
+
+/**
+ * Write a profile to a JSON file
+ */
+export async function export_profile(profileId: number, path: string): Promise<void> {
+  await invoke<void>("export_profile", { profileId, path })
+}
+
+/**
+ * Load a profile from a JSON file written by export_profile
+ */
+export async function import_profile(path: string): Promise<SettingsProfile> {
+  return await invoke<SettingsProfile>("import_profile", { path })
+}
+
+/**
+ * Stream audit events between two dates (YYYY-MM-DD, inclusive) to a CSV file,
+ * writing export_manifest.json alongside it
+ */
+export async function export_audit_events_csv(
+  projectId: number | null,
+  startDate: string,
+  endDate: string,
+  outputPath: string
+): Promise<ExportStats> {
+  return await invoke<ExportStats>("export_audit_events_csv", { projectId, startDate, endDate, outputPath })
+}
+
+// ============================================================================
+// ONBOARDING COMMANDS
+// ============================================================================
+
+export type OnboardingStep = "api_key" | "first_project" | "first_scan"
+
+export interface OnboardingStatus {
+  completed: boolean
+  /** XAI_API_KEY is present; the api_key step also needs it validated */
+  api_key_set: boolean
+  first_project_created: boolean
+  first_scan_completed: boolean
+  steps_remaining: OnboardingStep[]
+}
+
+export interface ApiKeyValidation {
+  valid: boolean
+  error: string | null
+  latency_ms: number | null
+}
+
+export interface OnboardingStepProgress {
+  step: OnboardingStep
+  completed: boolean
+  completed_at: string | null
+}
+
+export interface OnboardingProgress {
+  steps: OnboardingStepProgress[]
+  completed_steps: number
+  total_steps: number
+}
+
+/**
+ * Check where the user is in onboarding (records project/scan steps already done)
+ */
+export async function start_onboarding_check(): Promise<OnboardingStatus> {
+  return await invoke<OnboardingStatus>("start_onboarding_check")
+}
+
+/**
+ * Send the Grok API a test prompt to verify XAI_API_KEY works
+ */
+export async function validate_api_key_connection(): Promise<ApiKeyValidation> {
+  return await invoke<ApiKeyValidation>("validate_api_key_connection")
+}
+
+/** Services whose API keys can be stored in the OS keychain */
+export type ApiKeyProvider = "xai" | "github"
+
+/**
+ * Store an API key in the OS keychain (takes precedence over XAI_API_KEY / GITHUB_TOKEN)
+ */
+export async function set_api_key(provider: ApiKeyProvider, key: string): Promise<void> {
+  return await invoke<void>("set_api_key", { provider, key })
+}
+
+/**
+ * Check a stored API key with a live request to its provider
+ */
+export async function test_api_key(provider: ApiKeyProvider): Promise<ApiKeyValidation> {
+  return await invoke<ApiKeyValidation>("test_api_key", { provider })
+}
+
+/**
+ * Per-step onboarding progress with completion times
+ */
+export async function get_onboarding_progress(): Promise<OnboardingProgress> {
+  return await invoke<OnboardingProgress>("get_onboarding_progress")
+}
+
+// ============================================================================
+// SCHEDULE COMMANDS
+// ============================================================================
+
+export interface ScanSchedule {
+  id: number
+  project_id: number
+  /** Five-field cron expression in local time, e.g. "0 2 * * *" */
+  cron_expr: string
+  enabled: boolean
+  last_run_at: string | null
+  last_scan_id: number | null
+  next_run_at: string
+  created_at: string
+}
+
+/**
+ * Scan a project on a cron schedule while the app is running
+ * @param projectId - Project to scan
+ * @param cronExpr - Cron expression, e.g. "0 2 * * *" for nightly at 02:00, or "@daily"
+ */
+export async function schedule_scan(projectId: number, cronExpr: string): Promise<ScanSchedule> {
+  return await invoke<ScanSchedule>("schedule_scan", { projectId, cronExpr })
+}
+
+/**
+ * List scan schedules for projects in the current namespace
+ */
+export async function list_schedules(): Promise<ScanSchedule[]> {
+  return await invoke<ScanSchedule[]>("list_schedules")
+}
+
+// ============================================================================
+// EXPORT COMMANDS
+// ============================================================================
+
+export interface ImportResult {
+  scans_created: number
+  violations_imported: number
+  /** Rule IDs with no control mapping in the sarif_rule_mapping setting */
+  unmapped_rules: string[]
+}
+
+/**
+ * Import violations from a SARIF 2.1.0 file (Semgrep, CodeQL, ...)
+ * @param projectId - Project the findings belong to
+ * @param sarifPath - Path to the SARIF file
+ */
+export async function import_sarif(
+  projectId: number,
+  sarifPath: string
+): Promise<ImportResult> {
+  return await invoke<ImportResult>("import_sarif", { projectId, sarifPath })
+}
+
+export type ReportFormat = "html" | "pdf"
+
+export interface GeneratedReport {
+  format: ReportFormat
+  path: string
+  size_bytes: number
+  /** SHA-256 of the report file */
+  sha256: string
+}
+
+/**
+ * Generate a scan's SOC 2 evidence report (violations by control, severity and
+ * remediation status breakdowns, compliance trend) for auditors
+ * @param scanId - Scan to report on
+ * @param format - "html" or "pdf"
+ */
+export async function generate_report(scanId: number, format: ReportFormat): Promise<GeneratedReport> {
+  return await invoke<GeneratedReport>("generate_report", { scanId, format })
+}
+
+export type SpreadsheetFormat = "csv" | "xlsx"
+
+export type ViolationColumn =
+  | "id"
+  | "control_id"
+  | "control_name"
+  | "severity"
+  | "status"
+  | "file_path"
+  | "line_number"
+  | "description"
+  | "code_snippet"
+  | "detection_method"
+  | "confidence_score"
+  | "function_name"
+  | "class_name"
+  | "package_name"
+  | "lifecycle"
+  | "detected_at"
+  | "status_reason"
+  | "llm_reasoning"
+
+export interface ExportedViolations {
+  format: SpreadsheetFormat
+  path: string
+  /** Violations written, not counting the header row */
+  rows_exported: number
+  columns: ViolationColumn[]
+  size_bytes: number
+  /** SHA-256 of the exported file */
+  sha256: string
+}
+
+/**
+ * Export a scan's violations as a CSV file or Excel workbook, most severe first
+ * @param scanId - Scan whose violations to export
+ * @param format - "csv" or "xlsx"
+ * @param columns - Columns to include, in order; omit for the defaults
+ */
+export async function export_violations(
+  scanId: number,
+  format: SpreadsheetFormat,
+  columns?: ViolationColumn[]
+): Promise<ExportedViolations> {
+  return await invoke<ExportedViolations>("export_violations", { scanId, format, columns })
+}
+
+// ============================================================================
+// INTEGRATION COMMANDS
+// ============================================================================
+
+export interface JiraConfig {
+  /** Site URL, e.g. "https://acme.atlassian.net" */
+  base_url: string
+  /** Account the API token belongs to */
+  email: string
+  /** Jira project key, e.g. "SEC" */
+  project_key: string
+  /** Defaults to "Task" */
+  issue_type?: string
+  /** Labels added to every issue besides "ryn" and the control ID */
+  labels?: string[]
+  /** Stored in the OS keychain; omit when relinking to keep the stored token. Never returned. */
+  api_token?: string
+  project_id?: number
+  updated_at?: string | null
+}
+
+export interface JiraIssue {
+  key: string
+  url: string
+}
+
+/**
+ * Link a project to a Jira project for remediation issues
+ * @param projectId - Project whose violations are filed
+ * @param config - Jira site, account email, API token and project key
+ */
+export async function link_jira(projectId: number, config: JiraConfig): Promise<JiraConfig> {
+  return await invoke<JiraConfig>("link_jira", { projectId, config })
+}
+
+/**
+ * File a Jira issue with the violation's snippet, control description and
+ * suggested fix; the issue key is stored on the violation
+ * @param violationId - Violation to file
+ */
+export async function create_jira_issue(violationId: number): Promise<JiraIssue> {
+  return await invoke<JiraIssue>("create_jira_issue", { violationId })
+}
+
+/**
+ * Send a test message to the Slack and generic webhooks set in settings
+ * (notification_slack_webhook_url, notification_webhook_url)
+ * @returns Endpoints notified: "slack" and/or "webhook"
+ */
+export async function test_notification(): Promise<string[]> {
+  return await invoke<string[]>("test_notification")
+}
+
 // ============================================================================
 // ANALYTICS COMMANDS
 // ============================================================================
@@ -350,6 +1250,39 @@ export async function get_scan_cost(scanId: number): Promise<ScanCost | null> {
   return await invoke<ScanCost | null>("get_scan_cost", { scanId })
 }
 
+/**
+ * Estimate what scanning a project will cost under the current LLM settings
+ * @param projectId - ID of the project to estimate
+ */
+export async function estimate_scan_cost(projectId: number): Promise<CostEstimate> {
+  return await invoke<CostEstimate>("estimate_scan_cost", { projectId })
+}
+
+/**
+ * Get per-control compliance scores (0-100) over a project's recent scans, oldest first
+ * @param projectId - ID of the project to report on
+ * @param numScans - How many recent scans to include (defaults to 10)
+ */
+export async function get_compliance_trend(projectId: number, numScans?: number): Promise<ComplianceDataPoint[]> {
+  return await invoke<ComplianceDataPoint[]>("get_compliance_trend", { projectId, numScans })
+}
+
+/**
+ * Get LLM spend totals and per-day spend, plus month-to-date spend against the budget
+ * @param projectId - Limit to one project (all projects when omitted)
+ * @param days - Days to cover, including today (defaults to 30)
+ */
+export async function get_spending_report(projectId?: number, days?: number): Promise<SpendingReport> {
+  return await invoke<SpendingReport>("get_spending_report", { projectId, days })
+}
+
+/**
+ * Get violation counts and compliance score per control category for a project's latest completed scan
+ */
+export async function get_category_summary(projectId: number): Promise<CategorySummary[]> {
+  return await invoke<CategorySummary[]>("get_category_summary", { projectId })
+}
+
 /**
  * Respond to cost limit prompt during scanning
  * @param scanId - The ID of the scan
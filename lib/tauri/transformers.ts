@@ -45,11 +45,20 @@ export function toViolation(violation: ApiViolation): Violation {
     confidenceScore: violation.confidence_score,
     llmReasoning: violation.llm_reasoning,
     regexReasoning: violation.regex_reasoning,
+    isNew: violation.is_new,
+    lastStatusChangeReason: violation.last_status_change_reason,
+    packageName: violation.package_name ?? undefined,
   }
 }
 
 function normalizeStatus(status: string | undefined): ViolationStatus {
-  if (status === "open" || status === "fixed" || status === "dismissed") {
+  if (
+    status === "open" ||
+    status === "fixed" ||
+    status === "dismissed" ||
+    status === "resolved" ||
+    status === "accepted_risk"
+  ) {
     return status
   }
   return "open"
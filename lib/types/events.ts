@@ -32,3 +32,27 @@ export interface FileChangedEvent {
   /** Type of file system change: created, modified, or deleted */
   eventType: FileEventType
 }
+
+/**
+ * Violations updated event payload emitted by watch_project
+ *
+ * Emitted after a changed file is re-checked and its violations in the
+ * project's latest completed scan are updated. Payload fields are snake_case,
+ * as sent by the backend.
+ */
+export interface ViolationsUpdatedEvent {
+  /** ID of the project being watched */
+  project_id: number
+
+  /** Latest completed scan, which the violations were added to or resolved in */
+  scan_id: number
+
+  /** Changed file, relative to the project root */
+  file_path: string
+
+  /** Violations found in the file that the scan didn't have yet */
+  added: number
+
+  /** Open violations no longer found in the file, now marked "resolved" */
+  resolved: number
+}
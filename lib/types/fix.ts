@@ -9,4 +9,6 @@ export interface Fix {
   trustLevel: TrustLevel
   appliedAt?: string
   appliedBy: string
+  fixAttemptNumber?: number
+  createdAt?: string
 }
@@ -1,8 +1,8 @@
 export type Severity = "critical" | "high" | "medium" | "low"
 
-export type ViolationStatus = "open" | "fixed" | "dismissed"
+export type ViolationStatus = "open" | "fixed" | "dismissed" | "resolved" | "accepted_risk"
 
-export type DetectionMethod = "regex" | "llm" | "hybrid"
+export type DetectionMethod = "regex" | "llm" | "hybrid" | "imported"
 
 export interface Violation {
   id: number
@@ -19,6 +19,11 @@ export interface Violation {
   confidenceScore?: number  // 0.0-1.0, only for LLM/hybrid
   llmReasoning?: string     // AI explanation of why this is a violation
   regexReasoning?: string   // Pattern match explanation
+  isNew?: boolean           // Not found in the baseline scan
+  lastStatusChangeReason?: string  // Reason given for the most recent bulk status change
+  packageName?: string  // Monorepo package containing the file
+  firstSeenScanId?: number  // Project-wide view only: earliest scan reporting this finding
+  lastSeenScanId?: number   // Project-wide view only: latest scan reporting this finding
 }
 
 export interface ScanResult {
@@ -28,5 +33,5 @@ export interface ScanResult {
   completedAt: string
   filesScanned: number
   violationsFound: number
-  status: "running" | "completed" | "failed"
+  status: "running" | "completed" | "failed" | "imported" | "partial"
 }
@@ -0,0 +1,19 @@
+//! Checks shared by every rule engine fuzz target
+
+use ryn::models::Violation;
+
+/// Assert the invariants every rule engine result must hold
+///
+/// `analyze` returning `Err` is fine (e.g. a pattern failed to compile);
+/// panicking is not, and libFuzzer reports that as a crash on its own.
+pub fn check_violations(result: anyhow::Result<Vec<Violation>>, control_id: &str) {
+    let Ok(violations) = result else {
+        return;
+    };
+
+    for violation in violations {
+        assert!(violation.line_number > 0, "line_number must be 1-based: {:?}", violation);
+        assert!(!violation.code_snippet.is_empty(), "code_snippet is empty: {:?}", violation);
+        assert_eq!(violation.control_id, control_id, "unexpected control: {:?}", violation);
+    }
+}
@@ -0,0 +1,13 @@
+#![no_main]
+
+mod common;
+
+use libfuzzer_sys::fuzz_target;
+use ryn::rules::A12ResilienceRule;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(code) = std::str::from_utf8(data) else {
+        return;
+    };
+    common::check_violations(A12ResilienceRule::analyze(code, "test.py", 1), "A1.2");
+});
@@ -0,0 +1,15 @@
+import requests
+import sqlite3
+
+
+def fetch_orders():
+    response = requests.get("https://api.partner.com/orders")
+    return response.json()
+
+
+def charge(card):
+    return requests.post("https://payments.example.com/charge", json=card)
+
+
+def connect():
+    return sqlite3.connect("app.db")
@@ -0,0 +1,9 @@
+from flask import Flask, request
+
+app = Flask(__name__)
+
+
+@app.route("/admin/users", methods=["POST"])
+def delete_user():
+    User.query.filter_by(id=request.json["id"]).delete()
+    return "", 204
@@ -0,0 +1,18 @@
+from django.http import JsonResponse
+from django.contrib.auth.decorators import login_required
+
+
+def user_profile(request, user_id):
+    user = User.objects.get(id=user_id)
+    return JsonResponse({"email": user.email})
+
+
+@login_required
+def dashboard(request):
+    return render(request, "dashboard.html")
+
+
+def search(request):
+    query = request.GET["q"]
+    rows = User.objects.raw(f"SELECT * FROM users WHERE name = '{query}'")
+    return JsonResponse({"count": len(list(rows))})
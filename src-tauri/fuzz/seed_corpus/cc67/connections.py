@@ -0,0 +1,6 @@
+import os
+
+db_password = "hunter2hunter2"
+url = f"postgres://{DB_USER}:{db_password}@{host}/{db}"
+dsn = "mysql://%s:%s@%s/app" % (user, password, host)
+token = os.getenv("API_TOKEN")
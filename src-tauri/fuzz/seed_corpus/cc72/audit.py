@@ -0,0 +1,19 @@
+import logging
+
+logger = logging.getLogger(__name__)
+
+
+def login(request):
+    password = request.POST["password"]
+    logger.info(f"Login attempt for {request.POST['username']} with password {password}")
+    user = authenticate(username=request.POST["username"], password=password)
+    return user
+
+
+def delete_account(user):
+    user.delete()
+
+
+def change_role(user, role):
+    user.role = role
+    user.save()
@@ -0,0 +1,255 @@
+// Ryn compliance tools for MCP bridge
+// Implements: mcp_scan_project, mcp_get_violations, mcp_dismiss_violation, mcp_generate_fix,
+// and mcp_list_tools so agents can discover them
+//
+// The plugin can't depend on the host app, so the app registers a `RynTools`
+// implementation with `init_with_ryn_tools()`. These handlers parse the MCP
+// params and forward them to it.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use tauri::{AppHandle, Manager, Runtime};
+use serde_json::{json, Value};
+
+use crate::state::MCPState;
+
+/// Boxed future returned by async Ryn tools
+pub type ToolFuture = Pin<Box<dyn Future<Output = Result<Value, String>> + Send>>;
+
+/// Ryn's compliance scanning capabilities, implemented by the host app
+/// Each method returns the JSON sent back as the MCP response
+pub trait RynTools: Send + Sync + 'static {
+    /// Scan the project at `path`, registering it first if needed
+    fn scan_project(&self, path: String) -> ToolFuture;
+
+    /// Violations of a scan, optionally only those of one control
+    fn get_violations(&self, scan_id: i64, control_id: Option<String>) -> Result<Value, String>;
+
+    /// Dismiss a violation, recording why
+    fn dismiss_violation(&self, violation_id: i64, reason: String) -> Result<Value, String>;
+
+    /// Generate an AI fix for a violation
+    fn generate_fix(&self, violation_id: i64) -> ToolFuture;
+}
+
+/// Registered Ryn tools, or an error if the app didn't register any
+fn tools<R: Runtime>(app: &AppHandle<R>) -> Result<Arc<dyn RynTools>, String> {
+    app.state::<MCPState>()
+        .ryn_tools
+        .clone()
+        .ok_or("Ryn tools not registered (use init_with_ryn_tools)".to_string())
+}
+
+/// Positive integer ID parameter
+fn id_param(params: &Value, name: &str) -> Result<i64, String> {
+    params.get(name)
+        .and_then(|v| v.as_i64())
+        .filter(|id| *id > 0)
+        .ok_or(format!("Missing or invalid parameter: {}", name))
+}
+
+/// Describe the Ryn tools in MCP `tools/list` form (name, description, inputSchema)
+/// Params: none
+pub async fn list_tools<R: Runtime>(app: &AppHandle<R>, _params: &Value) -> Result<Value, String> {
+    let registered = app.state::<MCPState>().ryn_tools.is_some();
+    Ok(json!({ "tools": tool_descriptors(), "registered": registered }))
+}
+
+/// Names, descriptions and JSON Schemas of the Ryn tools
+fn tool_descriptors() -> Value {
+    json!([
+        {
+            "name": "mcp_scan_project",
+            "description": "Scan a project directory for SOC 2 violations, registering it first if needed",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "path": { "type": "string", "description": "Absolute path to the project root" }
+                },
+                "required": ["path"]
+            }
+        },
+        {
+            "name": "mcp_get_violations",
+            "description": "List the violations found by a scan",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "scan_id": { "type": "integer", "minimum": 1 },
+                    "control_id": { "type": "string", "description": "Only this SOC 2 control, e.g. CC6.7" }
+                },
+                "required": ["scan_id"]
+            }
+        },
+        {
+            "name": "mcp_dismiss_violation",
+            "description": "Dismiss a violation, recording why",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "violation_id": { "type": "integer", "minimum": 1 },
+                    "reason": { "type": "string" }
+                },
+                "required": ["violation_id", "reason"]
+            }
+        },
+        {
+            "name": "mcp_generate_fix",
+            "description": "Generate an AI fix for a violation",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "violation_id": { "type": "integer", "minimum": 1 }
+                },
+                "required": ["violation_id"]
+            }
+        }
+    ])
+}
+
+/// Scan a project directory
+/// Params: { path: string }
+pub async fn scan_project<R: Runtime>(app: &AppHandle<R>, params: &Value) -> Result<Value, String> {
+    scan_project_with(tools(app)?.as_ref(), params).await
+}
+
+async fn scan_project_with(tools: &dyn RynTools, params: &Value) -> Result<Value, String> {
+    let path = params.get("path")
+        .and_then(|v| v.as_str())
+        .filter(|p| !p.trim().is_empty())
+        .ok_or("Missing required parameter: path")?;
+
+    tools.scan_project(path.to_string()).await
+}
+
+/// List the violations of a scan
+/// Params: { scan_id: number, control_id?: string }
+pub async fn get_violations<R: Runtime>(app: &AppHandle<R>, params: &Value) -> Result<Value, String> {
+    get_violations_with(tools(app)?.as_ref(), params)
+}
+
+fn get_violations_with(tools: &dyn RynTools, params: &Value) -> Result<Value, String> {
+    let scan_id = id_param(params, "scan_id")?;
+    let control_id = params.get("control_id")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+
+    tools.get_violations(scan_id, control_id)
+}
+
+/// Dismiss a violation
+/// Params: { violation_id: number, reason: string }
+pub async fn dismiss_violation<R: Runtime>(app: &AppHandle<R>, params: &Value) -> Result<Value, String> {
+    dismiss_violation_with(tools(app)?.as_ref(), params)
+}
+
+fn dismiss_violation_with(tools: &dyn RynTools, params: &Value) -> Result<Value, String> {
+    let violation_id = id_param(params, "violation_id")?;
+    let reason = params.get("reason")
+        .and_then(|v| v.as_str())
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .ok_or("Missing required parameter: reason")?;
+
+    tools.dismiss_violation(violation_id, reason.to_string())
+}
+
+/// Generate a fix for a violation
+/// Params: { violation_id: number }
+pub async fn generate_fix<R: Runtime>(app: &AppHandle<R>, params: &Value) -> Result<Value, String> {
+    generate_fix_with(tools(app)?.as_ref(), params).await
+}
+
+async fn generate_fix_with(tools: &dyn RynTools, params: &Value) -> Result<Value, String> {
+    let violation_id = id_param(params, "violation_id")?;
+
+    tools.generate_fix(violation_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Echoes each call's arguments back as JSON
+    struct EchoTools;
+
+    impl RynTools for EchoTools {
+        fn scan_project(&self, path: String) -> ToolFuture {
+            Box::pin(async move { Ok(json!({ "tool": "scan_project", "path": path })) })
+        }
+
+        fn get_violations(&self, scan_id: i64, control_id: Option<String>) -> Result<Value, String> {
+            Ok(json!({ "tool": "get_violations", "scan_id": scan_id, "control_id": control_id }))
+        }
+
+        fn dismiss_violation(&self, violation_id: i64, reason: String) -> Result<Value, String> {
+            Ok(json!({ "tool": "dismiss_violation", "violation_id": violation_id, "reason": reason }))
+        }
+
+        fn generate_fix(&self, violation_id: i64) -> ToolFuture {
+            Box::pin(async move { Ok(json!({ "tool": "generate_fix", "violation_id": violation_id })) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scan_project_forwards_path() {
+        let result = scan_project_with(&EchoTools, &json!({ "path": "/tmp/app" })).await.unwrap();
+        assert_eq!(result, json!({ "tool": "scan_project", "path": "/tmp/app" }));
+
+        let err = scan_project_with(&EchoTools, &json!({ "path": "  " })).await.unwrap_err();
+        assert_eq!(err, "Missing required parameter: path");
+    }
+
+    #[test]
+    fn test_get_violations_optional_control() {
+        let all = get_violations_with(&EchoTools, &json!({ "scan_id": 3 })).unwrap();
+        assert_eq!(all["scan_id"], 3);
+        assert!(all["control_id"].is_null());
+
+        let filtered = get_violations_with(&EchoTools, &json!({ "scan_id": 3, "control_id": "CC6.7" })).unwrap();
+        assert_eq!(filtered["control_id"], "CC6.7");
+
+        let err = get_violations_with(&EchoTools, &json!({ "scan_id": 0 })).unwrap_err();
+        assert_eq!(err, "Missing or invalid parameter: scan_id");
+    }
+
+    #[test]
+    fn test_dismiss_violation_requires_reason() {
+        let result = dismiss_violation_with(
+            &EchoTools,
+            &json!({ "violation_id": 7, "reason": " false positive " }),
+        ).unwrap();
+        assert_eq!(result["violation_id"], 7);
+        assert_eq!(result["reason"], "false positive");
+
+        let err = dismiss_violation_with(&EchoTools, &json!({ "violation_id": 7 })).unwrap_err();
+        assert_eq!(err, "Missing required parameter: reason");
+    }
+
+    #[test]
+    fn test_tool_descriptors_cover_every_tool() {
+        let tools = tool_descriptors();
+        let names: Vec<_> = tools.as_array().unwrap()
+            .iter()
+            .map(|tool| tool["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["mcp_scan_project", "mcp_get_violations", "mcp_dismiss_violation", "mcp_generate_fix"]);
+
+        for tool in tools.as_array().unwrap() {
+            assert_eq!(tool["inputSchema"]["type"], "object");
+            assert!(!tool["inputSchema"]["required"].as_array().unwrap().is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generate_fix_validates_id() {
+        let result = generate_fix_with(&EchoTools, &json!({ "violation_id": 12 })).await.unwrap();
+        assert_eq!(result, json!({ "tool": "generate_fix", "violation_id": 12 }));
+
+        let err = generate_fix_with(&EchoTools, &json!({ "violation_id": "12" })).await.unwrap_err();
+        assert_eq!(err, "Missing or invalid parameter: violation_id");
+    }
+}
@@ -0,0 +1,132 @@
+// Plugin configuration: which transport the bridge listens on, how clients
+// authenticate and which methods they may call
+// Defaults to the platform transport with loopback TCP as the fallback,
+// token auth on, and every method allowed
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use crate::commands::ryn::RynTools;
+use crate::transport::Transport;
+
+/// Configuration passed to `init_with_config`
+#[derive(Clone)]
+pub struct PluginConfig {
+    /// Transport to listen on
+    /// None uses `Transport::platform_default()` (Unix socket, or named pipe on Windows)
+    pub transport: Option<Transport>,
+
+    /// Listen on loopback TCP if the transport can't be bound
+    /// The port is written to ~/.tauri/mcp-tcp.json for clients
+    pub tcp_fallback: bool,
+
+    /// Require clients to authenticate with the token in ~/.tauri/mcp.token
+    /// Always required over TCP
+    pub require_auth: bool,
+
+    /// Fixed auth token; None generates a random one per launch
+    pub auth_token: Option<String>,
+
+    /// Methods clients may call; None allows all
+    /// `ping` and `authenticate` are always allowed
+    pub allowed_methods: Option<BTreeSet<String>>,
+
+    /// Ryn compliance tools registered by the host app
+    pub(crate) ryn_tools: Option<Arc<dyn RynTools>>,
+}
+
+impl Default for PluginConfig {
+    fn default() -> Self {
+        Self {
+            transport: None,
+            tcp_fallback: true,
+            require_auth: true,
+            auth_token: None,
+            allowed_methods: None,
+            ryn_tools: None,
+        }
+    }
+}
+
+impl PluginConfig {
+    /// Platform transport with TCP fallback, token auth and all methods allowed
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Listen on `transport` instead of the platform default
+    pub fn transport(mut self, transport: Transport) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// Enable or disable the TCP fallback
+    pub fn tcp_fallback(mut self, enabled: bool) -> Self {
+        self.tcp_fallback = enabled;
+        self
+    }
+
+    /// Enable or disable token auth for Unix sockets and named pipes
+    pub fn require_auth(mut self, required: bool) -> Self {
+        self.require_auth = required;
+        self
+    }
+
+    /// Use `token` instead of a random token
+    pub fn auth_token(mut self, token: impl Into<String>) -> Self {
+        self.auth_token = Some(token.into());
+        self
+    }
+
+    /// Only allow these methods, e.g. `["window_list", "mcp_scan_project"]`
+    /// Other requests get a permission error
+    pub fn allow_methods<I, S>(mut self, methods: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowed_methods = Some(methods.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Whether the server should authenticate clients on `transport`
+    pub(crate) fn auth_required(&self, transport: &Transport) -> bool {
+        self.require_auth || matches!(transport, Transport::Tcp { .. })
+    }
+
+    /// Enable the mcp_* compliance methods backed by `tools`
+    pub fn ryn_tools<T: RynTools>(mut self, tools: T) -> Self {
+        self.ryn_tools = Some(Arc::new(tools));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_defaults_require_auth_and_allow_all() {
+        let config = PluginConfig::new();
+        assert!(config.tcp_fallback);
+        assert!(config.require_auth);
+        assert!(config.allowed_methods.is_none());
+    }
+
+    #[test]
+    fn test_tcp_always_requires_auth() {
+        let config = PluginConfig::new().require_auth(false);
+        let socket = Transport::UnixSocket { path: PathBuf::from("mcp.sock") };
+        assert!(!config.auth_required(&socket));
+        assert!(config.auth_required(&Transport::Tcp { port: 0 }));
+    }
+
+    #[test]
+    fn test_allow_methods() {
+        let config = PluginConfig::new().allow_methods(["window_list", "mcp_scan_project"]);
+        let allowed = config.allowed_methods.unwrap();
+        assert!(allowed.contains("mcp_scan_project"));
+        assert!(!allowed.contains("browser_execute"));
+    }
+}
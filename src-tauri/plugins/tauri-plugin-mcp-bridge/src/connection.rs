@@ -1,19 +1,12 @@
-use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime};
 use std::collections::HashMap;
-use tokio::net::UnixStream;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::sync::{Mutex, oneshot};
 use serde_json::Value;
 
 use crate::error::{Result, TauriMCPError};
 use crate::protocol::{JsonRpcRequest, JsonRpcResponse, JsonRpcMessage, MessageFramer};
-
-/// Unix socket path for MCP communication
-const SOCKET_PATH: &str = ".tauri/mcp.sock";
-
-/// Max Unix socket path length (macOS limit)
-const MAX_SOCKET_PATH_LEN: usize = 104;
+use crate::transport::{self, BoxedStream, Transport};
 
 /// Connection timeout (5 seconds)
 const CONNECT_TIMEOUT_MS: u64 = 5000;
@@ -27,13 +20,16 @@ const MAX_BACKOFF_MS: u64 = 30000;
 /// Initial reconnect backoff (1 second)
 const INITIAL_BACKOFF_MS: u64 = 1000;
 
-/// Connection state and Unix socket manager
+/// Connection state and stream manager (Unix socket, named pipe or TCP)
 pub struct MCPConnection {
-    /// Unix socket stream (wrapped in Mutex for async access)
-    stream: Mutex<Option<UnixStream>>,
+    /// Transport stream (wrapped in Mutex for async access)
+    stream: Mutex<Option<BoxedStream>>,
+
+    /// Where the bridge listens
+    transport: Transport,
 
-    /// Socket path (absolute)
-    socket_path: PathBuf,
+    /// Auth token; None reads ~/.tauri/mcp.token on connect
+    token: Option<String>,
 
     /// Last activity timestamp (for staleness detection)
     last_activity: Mutex<SystemTime>,
@@ -46,114 +42,64 @@ pub struct MCPConnection {
 }
 
 impl MCPConnection {
-    /// Create new connection instance (doesn't connect yet)
+    /// Create new connection instance for the platform transport (doesn't connect yet)
     pub fn new() -> Result<Self> {
-        let socket_path = Self::get_socket_path()?;
+        Ok(Self::with_transport(Transport::platform_default()?))
+    }
 
-        Ok(Self {
+    /// Create new connection instance for `transport` (doesn't connect yet)
+    ///
+    /// Use `Transport::from_tcp_info()` to reach a bridge that fell back to TCP.
+    pub fn with_transport(transport: Transport) -> Self {
+        Self {
             stream: Mutex::new(None),
-            socket_path,
+            transport,
+            token: None,
             last_activity: Mutex::new(SystemTime::now()),
             pending_requests: Mutex::new(HashMap::new()),
             read_buffer: Mutex::new(String::new()),
-        })
-    }
-
-    /// Get absolute socket path (~/.tauri/mcp.sock)
-    fn get_socket_path() -> Result<PathBuf> {
-        let home = std::env::var("HOME")
-            .map_err(|_| TauriMCPError::InvalidConfig("HOME env var not set".to_string()))?;
-
-        let path = Path::new(&home).join(SOCKET_PATH);
-
-        // Validate path length (Unix socket limitation on macOS)
-        if path.as_os_str().len() >= MAX_SOCKET_PATH_LEN {
-            return Err(TauriMCPError::InvalidConfig(format!(
-                "Socket path too long: {} >= {}",
-                path.as_os_str().len(),
-                MAX_SOCKET_PATH_LEN
-            )));
         }
-
-        Ok(path)
     }
 
-    /// Create socket directory with proper permissions (0o700)
-    fn create_socket_dir(&self) -> Result<()> {
-        if let Some(parent) = self.socket_path.parent() {
-            std::fs::create_dir_all(parent).map_err(|e| {
-                TauriMCPError::PermissionDenied(format!("Failed to create dir: {}", e))
-            })?;
-
-            // Set directory permissions to 0o700 (owner rwx only)
-            #[cfg(unix)]
-            {
-                use std::os::unix::fs::PermissionsExt;
-                let perms = std::fs::Permissions::from_mode(0o700);
-                std::fs::set_permissions(parent, perms).map_err(|e| {
-                    TauriMCPError::PermissionDenied(format!("Failed to chmod dir: {}", e))
-                })?;
-            }
-        }
-
-        Ok(())
+    /// Authenticate with `token` instead of the one in ~/.tauri/mcp.token
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
     }
 
     /// Check if socket exists and is stale (no process listening)
+    /// Always false for named pipes and TCP, which leave nothing behind
     pub async fn check_socket_stale(&self) -> Result<bool> {
-        if !self.socket_path.exists() {
-            return Ok(false);
-        }
-
-        // Try to connect with very short timeout (100ms)
-        // If connection fails, socket is stale
-        match tokio::time::timeout(
-            Duration::from_millis(100),
-            UnixStream::connect(&self.socket_path),
-        )
-        .await
-        {
-            Ok(Ok(_)) => Ok(false), // Connected successfully = not stale
-            Ok(Err(_)) | Err(_) => {
-                // Connection failed = stale socket, remove it
-                std::fs::remove_file(&self.socket_path).ok();
-                Ok(true)
-            }
+        match &self.transport {
+            Transport::UnixSocket { path } => transport::remove_stale_socket(path).await,
+            _ => Ok(false),
         }
     }
 
-    /// Connect to Unix socket with timeout (5s)
+    /// Connect with timeout (5s), authenticating if a token is available
     pub async fn connect(&self) -> Result<()> {
         // Check and remove stale socket first
         self.check_socket_stale().await?;
 
-        // Create directory if needed
-        self.create_socket_dir()?;
+        // Bridges with auth disabled write no token file
+        let token = match &self.token {
+            Some(token) => Some(token.clone()),
+            None => transport::read_token().ok(),
+        };
 
         // Connect with timeout
         let stream = tokio::time::timeout(
             Duration::from_millis(CONNECT_TIMEOUT_MS),
-            UnixStream::connect(&self.socket_path),
+            transport::connect(&self.transport, token.as_deref()),
         )
         .await
-        .map_err(|_| TauriMCPError::Timeout("Connection timeout".to_string()))?
-        .map_err(|e| TauriMCPError::ConnectionFailed(format!("Failed to connect: {}", e)))?;
-
-        // Set socket permissions to 0o600 (owner rw only)
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            let perms = std::fs::Permissions::from_mode(0o600);
-            std::fs::set_permissions(&self.socket_path, perms).map_err(|e| {
-                TauriMCPError::PermissionDenied(format!("Failed to chmod socket: {}", e))
-            })?;
-        }
+        .map_err(|_| TauriMCPError::Timeout("Connection timeout".to_string()))??;
 
         // Store stream
         *self.stream.lock().await = Some(stream);
         *self.last_activity.lock().await = SystemTime::now();
 
-        log::info!("Connected to MCP server at {:?}", self.socket_path);
+        log::info!("Connected to MCP server via {}", self.transport);
 
         Ok(())
     }
@@ -320,11 +266,25 @@ impl MCPConnection {
 mod tests {
     use super::*;
 
+    #[cfg(unix)]
     #[tokio::test]
     async fn test_socket_path_validation() {
         let conn = MCPConnection::new().unwrap();
-        assert!(conn.socket_path.to_str().unwrap().contains(".tauri/mcp.sock"));
-        assert!(conn.socket_path.to_str().unwrap().len() < MAX_SOCKET_PATH_LEN);
+        let Transport::UnixSocket { path } = &conn.transport else {
+            panic!("expected a Unix socket, got {}", conn.transport);
+        };
+        assert!(path.to_str().unwrap().contains(".tauri/mcp.sock"));
+        assert!(path.to_str().unwrap().len() < transport::MAX_SOCKET_PATH_LEN);
+    }
+
+    #[cfg(windows)]
+    #[tokio::test]
+    async fn test_windows_defaults_to_named_pipe() {
+        let conn = MCPConnection::new().unwrap();
+        assert_eq!(
+            conn.transport,
+            Transport::NamedPipe { name: transport::PIPE_NAME.to_string() }
+        );
     }
 
     #[tokio::test]
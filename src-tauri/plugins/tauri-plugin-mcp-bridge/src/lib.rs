@@ -1,9 +1,12 @@
 // Tauri MCP Bridge - Model Context Protocol integration for Tauri
-// Provides Unix socket-based communication between MCP servers and Tauri apps
+// Provides socket-based communication between MCP servers and Tauri apps:
+// Unix socket, Windows named pipe, or loopback TCP with a token (see transport.rs)
 
 pub mod error;
 pub mod protocol;
 pub mod connection;
+pub mod config;
+pub mod transport;
 pub mod state;
 pub mod commands;
 mod server;
@@ -15,28 +18,49 @@ pub use protocol::{
     JsonRpcMessage, MessageFramer,
 };
 pub use connection::MCPConnection;
+pub use config::PluginConfig;
+pub use transport::{TcpInfo, Transport};
+pub use commands::ryn::{RynTools, ToolFuture};
 
+use std::sync::Arc;
 use tauri::{plugin::{Builder, TauriPlugin}, Manager, Runtime};
 
 /// Initialize the MCP bridge plugin
-/// This starts a Unix socket server at ~/.tauri/mcp.sock for MCP communication
+/// This starts a server at ~/.tauri/mcp.sock (\\.\pipe\tauri-mcp on Windows)
+/// for MCP communication
 pub fn init<R: Runtime>() -> TauriPlugin<R> {
+    init_with_config(PluginConfig::default())
+}
+
+/// Initialize the MCP bridge plugin with Ryn's compliance tools
+/// Enables the mcp_scan_project, mcp_get_violations, mcp_dismiss_violation
+/// and mcp_generate_fix methods, described by mcp_list_tools
+pub fn init_with_ryn_tools<R: Runtime, T: RynTools>(tools: T) -> TauriPlugin<R> {
+    init_with_config(PluginConfig::default().ryn_tools(tools))
+}
+
+/// Initialize the MCP bridge plugin with an explicit transport and tools
+pub fn init_with_config<R: Runtime>(config: PluginConfig) -> TauriPlugin<R> {
     Builder::new("mcp-bridge")
-        .setup(|app, _api| {
+        .setup(move |app, _api| {
             #[cfg(debug_assertions)]
             {
                 // Only enable MCP bridge in debug builds for security
                 println!("[MCP] Tauri MCP Bridge initializing...");
 
                 // Initialize and manage plugin state
-                app.manage(state::MCPState::new());
+                app.manage(state::MCPState {
+                    ryn_tools: config.ryn_tools.clone(),
+                    allowed_methods: config.allowed_methods.clone().map(Arc::new),
+                    ..state::MCPState::new()
+                });
 
                 // Clone app handle for background task
                 let app_handle = app.clone();
 
                 // Spawn socket server in background
                 tauri::async_runtime::spawn(async move {
-                    if let Err(e) = server::start_socket_server(app_handle).await {
+                    if let Err(e) = server::start_socket_server(app_handle, config).await {
                         eprintln!("[MCP] Socket server failed: {}", e);
                     }
                 });
@@ -47,6 +71,7 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
             #[cfg(not(debug_assertions))]
             {
                 log::info!("[MCP] Bridge disabled in production builds");
+                drop(config);
             }
 
             Ok(())
@@ -1,59 +1,49 @@
-// Unix socket server for receiving MCP requests from TypeScript client
-// Listens on ~/.tauri/mcp.sock and processes JSON-RPC 2.0 messages
+// Socket server for receiving MCP requests from TypeScript client
+// Listens on the configured transport (~/.tauri/mcp.sock by default) and
+// processes JSON-RPC 2.0 messages
 
-use std::path::PathBuf;
-use tokio::net::{UnixListener, UnixStream};
+use std::sync::Arc;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::sync::mpsc;
 use tauri::{AppHandle, Runtime, Manager};
 use serde_json::Value;
 
+use crate::config::PluginConfig;
 use crate::state::MCPState;
 use crate::error::{Result, TauriMCPError};
 use crate::protocol::{
     JsonRpcRequest, JsonRpcResponse, JsonRpcError, JsonRpcMessage, MessageFramer
 };
+use crate::transport::{self, BoxedStream, Listener, TcpInfo, Transport};
+
+/// Start the server on the configured transport
+pub async fn start_socket_server<R: Runtime>(app: AppHandle<R>, config: PluginConfig) -> Result<()> {
+    let (mut listener, transport) = bind_listener(&config).await?;
+
+    // Only processes running as this user can read the token file
+    let token: Option<Arc<str>> = if config.auth_required(&transport) {
+        let token = config.auth_token.clone().unwrap_or_else(transport::generate_token);
+        let path = transport::write_token(&token)?;
+        log::info!("[MCP] Auth token written to {:?}", path);
+        Some(Arc::from(token))
+    } else {
+        log::warn!("[MCP] Authentication disabled for {}", transport);
+        None
+    };
 
-const SOCKET_PATH: &str = ".tauri/mcp.sock";
-
-/// Start Unix socket server listening on ~/.tauri/mcp.sock
-pub async fn start_socket_server<R: Runtime>(app: AppHandle<R>) -> Result<()> {
-    let socket_path = get_socket_path()?;
-
-    // Create directory with proper permissions (0o700)
-    create_socket_dir(&socket_path)?;
-
-    // Remove stale socket if exists
-    if socket_path.exists() {
-        std::fs::remove_file(&socket_path)
-            .map_err(|e| TauriMCPError::PermissionDenied(format!("Cannot remove stale socket: {}", e)))?;
-    }
-
-    // Bind Unix socket
-    let listener = UnixListener::bind(&socket_path)
-        .map_err(|e| TauriMCPError::ConnectionFailed(format!("Bind failed: {}", e)))?;
-
-    // Set socket permissions to 0o600 (owner rw only)
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        let perms = std::fs::Permissions::from_mode(0o600);
-        std::fs::set_permissions(&socket_path, perms)
-            .map_err(|e| TauriMCPError::PermissionDenied(format!("Cannot chmod socket: {}", e)))?;
-    }
-
-    log::info!("[MCP] Socket server listening at {:?}", socket_path);
+    log::info!("[MCP] Server listening on {}", transport);
 
     // Accept connections in loop
     loop {
         match listener.accept().await {
-            Ok((stream, _addr)) => {
+            Ok(stream) => {
                 log::info!("[MCP] New connection accepted");
                 let app_handle = app.clone();
+                let token = token.clone();
 
                 // Spawn handler for this connection
                 tokio::spawn(async move {
-                    if let Err(e) = handle_connection(stream, app_handle).await {
+                    if let Err(e) = handle_connection(stream, token, app_handle).await {
                         log::error!("[MCP] Connection handler error: {}", e);
                     }
                 });
@@ -65,13 +55,54 @@ pub async fn start_socket_server<R: Runtime>(app: AppHandle<R>) -> Result<()> {
     }
 }
 
+/// Bind the configured transport, falling back to loopback TCP if enabled
+///
+/// Returns the listener and the transport actually bound.
+async fn bind_listener(config: &PluginConfig) -> Result<(Listener, Transport)> {
+    let primary = match config.transport.clone() {
+        Some(transport) => Ok(transport),
+        None => Transport::platform_default(),
+    };
+    let bound = match primary {
+        Ok(transport) => Listener::bind(&transport).await.map(|listener| (listener, transport)),
+        Err(e) => Err(e),
+    };
+
+    let (listener, transport) = match bound {
+        Ok(bound) => bound,
+        Err(e) if config.tcp_fallback && !matches!(config.transport, Some(Transport::Tcp { .. })) => {
+            log::warn!("[MCP] Cannot listen on the configured transport ({}), falling back to TCP", e);
+            let fallback = Transport::Tcp { port: 0 };
+            (Listener::bind(&fallback).await?, fallback)
+        }
+        Err(e) => return Err(e),
+    };
+
+    // Clients find the port (which may have been picked by the OS) here
+    if let Some(port) = listener.tcp_port() {
+        let path = transport::write_tcp_info(&TcpInfo { port })?;
+        log::info!("[MCP] TCP connection info written to {:?}", path);
+        return Ok((listener, Transport::Tcp { port }));
+    }
+
+    Ok((listener, transport))
+}
+
 /// Handle a single client connection
+///
+/// When auth is required, clients must authenticate with `token` before
+/// anything else is read or any notification is sent.
 async fn handle_connection<R: Runtime>(
-    stream: UnixStream,
+    mut stream: BoxedStream,
+    token: Option<Arc<str>>,
     app: AppHandle<R>,
 ) -> Result<()> {
+    if let Some(token) = token {
+        transport::authenticate_client(&mut stream, &token).await?;
+    }
+
     // Split stream for full duplex
-    let (mut reader, mut writer) = stream.into_split();
+    let (mut reader, mut writer) = tokio::io::split(stream);
     
     // Channel for sending responses from reader -> writer
     let (tx_response, mut rx_response) = mpsc::channel::<String>(32);
@@ -172,6 +203,12 @@ async fn handle_request<R: Runtime>(
 ) -> JsonRpcResponse {
     log::debug!("[MCP] Handling request: method={}", request.method);
 
+    if !app.state::<MCPState>().is_method_allowed(&request.method) {
+        log::warn!("[MCP] Rejected method not in allowlist: {}", request.method);
+        let error = TauriMCPError::PermissionDenied(format!("Method not allowed: {}", request.method));
+        return JsonRpcResponse::error(JsonRpcError::from_mcp_error(&error), request.id);
+    }
+
     let params = request.params.as_ref().unwrap_or(&Value::Null);
 
     // Route to command handlers
@@ -215,6 +252,16 @@ async fn handle_request<R: Runtime>(
         "test_record" => crate::commands::testing::record(app, params).await,
         "test_replay" => crate::commands::testing::replay(app, params).await,
 
+        // Ryn compliance commands (5)
+        "mcp_list_tools" => crate::commands::ryn::list_tools(app, params).await,
+        "mcp_scan_project" => crate::commands::ryn::scan_project(app, params).await,
+        "mcp_get_violations" => crate::commands::ryn::get_violations(app, params).await,
+        "mcp_dismiss_violation" => crate::commands::ryn::dismiss_violation(app, params).await,
+        "mcp_generate_fix" => crate::commands::ryn::generate_fix(app, params).await,
+
+        // Handshake already done (or auth disabled); repeats are harmless
+        "authenticate" => Ok(serde_json::json!({"authenticated": true})),
+
         // Legacy test commands
         "ping" => Ok(serde_json::json!({"status": "ok", "server": "tauri-mcp-bridge"})),
         "echo" => Ok(request.params.unwrap_or(Value::Null)),
@@ -232,39 +279,3 @@ async fn handle_request<R: Runtime>(
         ),
     }
 }
-
-/// Get absolute socket path (~/.tauri/mcp.sock)
-fn get_socket_path() -> Result<PathBuf> {
-    let home = std::env::var("HOME")
-        .map_err(|_| TauriMCPError::InvalidConfig("HOME env var not set".to_string()))?;
-
-    let path = std::path::Path::new(&home).join(SOCKET_PATH);
-
-    // Validate path length (Unix socket limitation on macOS: 104 bytes)
-    if path.as_os_str().len() >= 104 {
-        return Err(TauriMCPError::InvalidConfig(format!(
-            "Socket path too long: {} >= 104",
-            path.as_os_str().len()
-        )));
-    }
-
-    Ok(path)
-}
-
-/// Create socket directory with proper permissions (0o700)
-fn create_socket_dir(socket_path: &PathBuf) -> Result<()> {
-    if let Some(parent) = socket_path.parent() {
-        std::fs::create_dir_all(parent)
-            .map_err(|e| TauriMCPError::PermissionDenied(format!("Failed to create dir: {}", e)))?;
-
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            let perms = std::fs::Permissions::from_mode(0o700);
-            std::fs::set_permissions(parent, perms)
-                .map_err(|e| TauriMCPError::PermissionDenied(format!("Failed to chmod dir: {}", e)))?;
-        }
-    }
-
-    Ok(())
-}
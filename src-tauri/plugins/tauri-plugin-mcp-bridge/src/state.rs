@@ -1,12 +1,14 @@
 // Plugin state management for MCP bridge
 // Stores event subscriptions, JS callbacks, test recordings, etc.
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::sync::{Arc, Mutex};
 use serde_json::Value;
 use tauri::EventId;
 use tokio::sync::{oneshot, broadcast};
 
+use crate::commands::ryn::RynTools;
+
 /// Sender for JS callback results
 pub type CallbackSender = oneshot::Sender<Value>;
 
@@ -43,8 +45,19 @@ pub struct MCPState {
     /// Notification broadcaster
     /// Used to send events to all connected clients
     pub notification_sender: broadcast::Sender<Value>,
+
+    /// Ryn compliance tools registered by the host app
+    /// None when the plugin was set up with plain `init()`
+    pub ryn_tools: Option<Arc<dyn RynTools>>,
+
+    /// Methods clients may call, from `PluginConfig::allowed_methods`
+    /// None allows all
+    pub allowed_methods: Option<Arc<BTreeSet<String>>>,
 }
 
+/// Methods callable whatever the allowlist says
+const ALWAYS_ALLOWED_METHODS: &[&str] = &["ping", "authenticate"];
+
 impl Default for MCPState {
     fn default() -> Self {
         let (tx, _rx) = broadcast::channel(100);
@@ -53,6 +66,8 @@ impl Default for MCPState {
             js_callbacks: Arc::new(Mutex::new(HashMap::new())),
             recordings: Arc::new(Mutex::new(HashMap::new())),
             notification_sender: tx,
+            ryn_tools: None,
+            allowed_methods: None,
         }
     }
 }
@@ -62,4 +77,33 @@ impl MCPState {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Whether clients may call `method`
+    pub fn is_method_allowed(&self, method: &str) -> bool {
+        ALWAYS_ALLOWED_METHODS.contains(&method)
+            || self
+                .allowed_methods
+                .as_ref()
+                .map_or(true, |allowed| allowed.contains(method))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allowlist() {
+        let state = MCPState::new();
+        assert!(state.is_method_allowed("browser_execute"));
+
+        let state = MCPState {
+            allowed_methods: Some(Arc::new(BTreeSet::from(["mcp_get_violations".to_string()]))),
+            ..MCPState::new()
+        };
+        assert!(state.is_method_allowed("mcp_get_violations"));
+        assert!(state.is_method_allowed("ping"));
+        assert!(!state.is_method_allowed("browser_execute"));
+        assert!(!state.is_method_allowed("browser_screenshot"));
+    }
 }
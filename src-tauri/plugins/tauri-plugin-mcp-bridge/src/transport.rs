@@ -0,0 +1,622 @@
+// Transports shared by the socket server and MCPConnection
+// Unix socket on macOS/Linux, named pipe on Windows, and loopback TCP as a
+// fallback wherever the platform transport can't be bound. Clients
+// authenticate with the token in ~/.tauri/mcp.token before anything else.
+
+use std::fmt;
+use std::net::Ipv4Addr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+#[cfg(unix)]
+use tokio::net::{UnixListener, UnixStream};
+#[cfg(windows)]
+use tokio::net::windows::named_pipe::{ClientOptions, NamedPipeServer, ServerOptions};
+
+use crate::error::{Result, TauriMCPError};
+use crate::protocol::{JsonRpcError, JsonRpcMessage, JsonRpcRequest, JsonRpcResponse, MessageFramer};
+
+/// Unix socket path, relative to the home directory
+pub const SOCKET_PATH: &str = ".tauri/mcp.sock";
+
+/// Max Unix socket path length (macOS limit)
+pub const MAX_SOCKET_PATH_LEN: usize = 104;
+
+/// Default Windows named pipe
+pub const PIPE_NAME: &str = r"\\.\pipe\tauri-mcp";
+
+/// Port of a TCP bridge, relative to the home directory
+pub const TCP_INFO_PATH: &str = ".tauri/mcp-tcp.json";
+
+/// Auth token of the running bridge, relative to the home directory
+pub const TOKEN_PATH: &str = ".tauri/mcp.token";
+
+/// Method a client must call before any other when auth is required
+pub const AUTHENTICATE_METHOD: &str = "authenticate";
+
+/// Time a client has to authenticate (5 seconds)
+const AUTH_TIMEOUT_MS: u64 = 5000;
+
+/// Longest handshake message accepted
+const MAX_HANDSHAKE_LEN: usize = 4096;
+
+/// Windows error when every pipe instance is busy
+#[cfg(windows)]
+const ERROR_PIPE_BUSY: i32 = 231;
+
+/// Wait before retrying a busy pipe (50ms)
+#[cfg(windows)]
+const PIPE_BUSY_RETRY_MS: u64 = 50;
+
+/// How the bridge and its clients connect
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Transport {
+    /// Unix domain socket (default on macOS and Linux)
+    UnixSocket { path: PathBuf },
+
+    /// Windows named pipe (default on Windows)
+    NamedPipe { name: String },
+
+    /// TCP on 127.0.0.1, always authenticated
+    /// Port 0 picks a free port
+    Tcp { port: u16 },
+}
+
+impl Transport {
+    /// Default for this platform: ~/.tauri/mcp.sock, or \\.\pipe\tauri-mcp on Windows
+    pub fn platform_default() -> Result<Self> {
+        #[cfg(windows)]
+        {
+            Ok(Transport::NamedPipe { name: PIPE_NAME.to_string() })
+        }
+
+        #[cfg(not(windows))]
+        {
+            Ok(Transport::UnixSocket { path: default_socket_path()? })
+        }
+    }
+
+    /// TCP transport of a running bridge, read from ~/.tauri/mcp-tcp.json
+    pub fn from_tcp_info() -> Result<Self> {
+        let path = home_dir()?.join(TCP_INFO_PATH);
+        let content = std::fs::read_to_string(&path).map_err(|e| {
+            TauriMCPError::ResourceUnavailable(format!("Cannot read {:?}: {}", path, e))
+        })?;
+        let info: TcpInfo = serde_json::from_str(&content)
+            .map_err(|e| TauriMCPError::InvalidConfig(format!("Invalid TCP info: {}", e)))?;
+
+        Ok(Transport::Tcp { port: info.port })
+    }
+}
+
+impl fmt::Display for Transport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Transport::UnixSocket { path } => write!(f, "Unix socket {}", path.display()),
+            Transport::NamedPipe { name } => write!(f, "named pipe {}", name),
+            Transport::Tcp { port } => write!(f, "TCP 127.0.0.1:{}", port),
+        }
+    }
+}
+
+/// Contents of ~/.tauri/mcp-tcp.json, written when the bridge listens on TCP
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TcpInfo {
+    pub port: u16,
+}
+
+/// Byte stream between the bridge and one client, whatever the transport
+pub trait TransportStream: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> TransportStream for T {}
+
+/// Boxed stream returned by `Listener::accept` and `connect`
+pub type BoxedStream = Box<dyn TransportStream>;
+
+/// Server side of a transport
+pub(crate) enum Listener {
+    #[cfg(unix)]
+    Unix(UnixListener),
+
+    #[cfg(windows)]
+    NamedPipe { name: String, server: NamedPipeServer },
+
+    Tcp(TcpListener),
+}
+
+impl Listener {
+    /// Start listening on `transport`
+    pub(crate) async fn bind(transport: &Transport) -> Result<Self> {
+        match transport {
+            Transport::UnixSocket { path } => bind_unix_socket(path),
+            Transport::NamedPipe { name } => bind_named_pipe(name),
+            Transport::Tcp { port } => {
+                let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, *port))
+                    .await
+                    .map_err(|e| TauriMCPError::ConnectionFailed(format!("Bind failed: {}", e)))?;
+                Ok(Listener::Tcp(listener))
+            }
+        }
+    }
+
+    /// Port actually bound, for TCP
+    pub(crate) fn tcp_port(&self) -> Option<u16> {
+        match self {
+            Listener::Tcp(listener) => listener.local_addr().ok().map(|addr| addr.port()),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    /// Wait for the next client
+    pub(crate) async fn accept(&mut self) -> Result<BoxedStream> {
+        match self {
+            #[cfg(unix)]
+            Listener::Unix(listener) => {
+                let (stream, _addr) = listener.accept().await?;
+                Ok(Box::new(stream))
+            }
+
+            #[cfg(windows)]
+            Listener::NamedPipe { name, server } => {
+                server.connect().await?;
+                // Create the next instance before handing this one off, so
+                // clients never find the pipe missing
+                let next = ServerOptions::new().create(name.as_str())?;
+                Ok(Box::new(std::mem::replace(server, next)))
+            }
+
+            Listener::Tcp(listener) => {
+                let (stream, _addr) = listener.accept().await?;
+                Ok(Box::new(stream))
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+fn bind_unix_socket(path: &Path) -> Result<Listener> {
+    // Create directory with proper permissions (0o700)
+    create_private_dir(path)?;
+
+    // Remove stale socket if exists
+    if path.exists() {
+        std::fs::remove_file(path)
+            .map_err(|e| TauriMCPError::PermissionDenied(format!("Cannot remove stale socket: {}", e)))?;
+    }
+
+    let listener = UnixListener::bind(path)
+        .map_err(|e| TauriMCPError::ConnectionFailed(format!("Bind failed: {}", e)))?;
+
+    // Set socket permissions to 0o600 (owner rw only)
+    set_owner_only(path)?;
+
+    Ok(Listener::Unix(listener))
+}
+
+#[cfg(not(unix))]
+fn bind_unix_socket(_path: &Path) -> Result<Listener> {
+    Err(TauriMCPError::InvalidConfig("Unix sockets are not supported on this platform".to_string()))
+}
+
+#[cfg(windows)]
+fn bind_named_pipe(name: &str) -> Result<Listener> {
+    // first_pipe_instance fails if another process already owns the name;
+    // remote clients are rejected by default
+    let server = ServerOptions::new()
+        .first_pipe_instance(true)
+        .create(name)
+        .map_err(|e| TauriMCPError::ConnectionFailed(format!("Pipe creation failed: {}", e)))?;
+
+    Ok(Listener::NamedPipe { name: name.to_string(), server })
+}
+
+#[cfg(not(windows))]
+fn bind_named_pipe(_name: &str) -> Result<Listener> {
+    Err(TauriMCPError::InvalidConfig("Named pipes are only supported on Windows".to_string()))
+}
+
+/// Connect to a bridge on `transport`, authenticating with `token` if given
+pub async fn connect(transport: &Transport, token: Option<&str>) -> Result<BoxedStream> {
+    let mut stream = match transport {
+        Transport::UnixSocket { path } => connect_unix_socket(path).await?,
+        Transport::NamedPipe { name } => connect_named_pipe(name).await?,
+        Transport::Tcp { port } => {
+            let stream = TcpStream::connect((Ipv4Addr::LOCALHOST, *port))
+                .await
+                .map_err(|e| TauriMCPError::ConnectionFailed(format!("Failed to connect: {}", e)))?;
+            Box::new(stream) as BoxedStream
+        }
+    };
+
+    if let Some(token) = token {
+        authenticate(&mut stream, token).await?;
+    }
+
+    Ok(stream)
+}
+
+#[cfg(unix)]
+async fn connect_unix_socket(path: &Path) -> Result<BoxedStream> {
+    // Create directory if needed
+    create_private_dir(path)?;
+
+    let stream = UnixStream::connect(path)
+        .await
+        .map_err(|e| TauriMCPError::ConnectionFailed(format!("Failed to connect: {}", e)))?;
+
+    set_owner_only(path)?;
+
+    Ok(Box::new(stream))
+}
+
+#[cfg(not(unix))]
+async fn connect_unix_socket(_path: &Path) -> Result<BoxedStream> {
+    Err(TauriMCPError::InvalidConfig("Unix sockets are not supported on this platform".to_string()))
+}
+
+#[cfg(windows)]
+async fn connect_named_pipe(name: &str) -> Result<BoxedStream> {
+    loop {
+        match ClientOptions::new().open(name) {
+            Ok(client) => return Ok(Box::new(client)),
+            Err(e) if e.raw_os_error() == Some(ERROR_PIPE_BUSY) => {
+                tokio::time::sleep(Duration::from_millis(PIPE_BUSY_RETRY_MS)).await;
+            }
+            Err(e) => {
+                return Err(TauriMCPError::ConnectionFailed(format!("Failed to open pipe: {}", e)));
+            }
+        }
+    }
+}
+
+#[cfg(not(windows))]
+async fn connect_named_pipe(_name: &str) -> Result<BoxedStream> {
+    Err(TauriMCPError::InvalidConfig("Named pipes are only supported on Windows".to_string()))
+}
+
+/// Remove the socket at `path` if no server is listening on it
+///
+/// Returns true if a stale socket was removed.
+pub async fn remove_stale_socket(path: &Path) -> Result<bool> {
+    if !path.exists() {
+        return Ok(false);
+    }
+
+    #[cfg(unix)]
+    {
+        // Try to connect with very short timeout (100ms)
+        // If connection fails, socket is stale
+        match tokio::time::timeout(Duration::from_millis(100), UnixStream::connect(path)).await {
+            Ok(Ok(_)) => Ok(false), // Connected successfully = not stale
+            Ok(Err(_)) | Err(_) => {
+                std::fs::remove_file(path).ok();
+                Ok(true)
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        Ok(false)
+    }
+}
+
+/// Server side of the authentication handshake
+///
+/// The client's first message must be an `authenticate` request with the
+/// bridge's token in `params.token`. Anything else gets an error response and
+/// PermissionDenied, and the connection should be dropped.
+pub(crate) async fn authenticate_client<S: AsyncRead + AsyncWrite + Unpin>(
+    stream: &mut S,
+    token: &str,
+) -> Result<()> {
+    let line = tokio::time::timeout(Duration::from_millis(AUTH_TIMEOUT_MS), read_line(stream))
+        .await
+        .map_err(|_| TauriMCPError::Timeout("Client did not authenticate".to_string()))??;
+
+    let request = match MessageFramer::parse_json_rpc(&line)? {
+        JsonRpcMessage::Request(request) => request,
+        _ => return Err(TauriMCPError::ProtocolError("Expected an authenticate request".to_string())),
+    };
+
+    let presented = request
+        .params
+        .as_ref()
+        .and_then(|params| params.get("token"))
+        .and_then(Value::as_str);
+    let valid = request.method == AUTHENTICATE_METHOD
+        && presented.is_some_and(|presented| tokens_match(presented, token));
+
+    let rejection = TauriMCPError::PermissionDenied("Invalid or missing token".to_string());
+    let response = if valid {
+        JsonRpcResponse::success(json!({"authenticated": true}), request.id)
+    } else {
+        JsonRpcResponse::error(JsonRpcError::from_mcp_error(&rejection), request.id)
+    };
+    stream.write_all(MessageFramer::frame_message(&response)?.as_bytes()).await?;
+
+    if valid {
+        Ok(())
+    } else {
+        Err(rejection)
+    }
+}
+
+/// Client side of the authentication handshake
+async fn authenticate<S: AsyncRead + AsyncWrite + Unpin>(stream: &mut S, token: &str) -> Result<()> {
+    let request = JsonRpcRequest::new(
+        AUTHENTICATE_METHOD.to_string(),
+        Some(json!({ "token": token })),
+        Value::String(uuid::Uuid::new_v4().to_string()),
+    );
+    stream
+        .write_all(MessageFramer::frame_message(&request)?.as_bytes())
+        .await
+        .map_err(|e| TauriMCPError::Disconnected(format!("Write failed: {}", e)))?;
+
+    let line = tokio::time::timeout(Duration::from_millis(AUTH_TIMEOUT_MS), read_line(stream))
+        .await
+        .map_err(|_| TauriMCPError::Timeout("No authentication response".to_string()))??;
+
+    match MessageFramer::parse_json_rpc(&line)? {
+        JsonRpcMessage::Response(response) => match response.error {
+            None => Ok(()),
+            Some(error) => Err(TauriMCPError::PermissionDenied(error.message)),
+        },
+        _ => Err(TauriMCPError::ProtocolError("Expected an authentication response".to_string())),
+    }
+}
+
+/// Read one newline-terminated message
+///
+/// Reads a byte at a time so nothing after the newline is consumed.
+async fn read_line<S: AsyncRead + Unpin>(stream: &mut S) -> Result<String> {
+    let mut line = Vec::new();
+    loop {
+        let byte = stream
+            .read_u8()
+            .await
+            .map_err(|e| TauriMCPError::Disconnected(format!("Read failed: {}", e)))?;
+        if byte == b'\n' {
+            return Ok(String::from_utf8_lossy(&line).to_string());
+        }
+        if line.len() == MAX_HANDSHAKE_LEN {
+            return Err(TauriMCPError::ProtocolError("Handshake message too long".to_string()));
+        }
+        line.push(byte);
+    }
+}
+
+/// Compare tokens in constant time
+fn tokens_match(presented: &str, expected: &str) -> bool {
+    presented.len() == expected.len()
+        && presented
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// Random token for a bridge run
+pub fn generate_token() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+/// Token of the running bridge, read from ~/.tauri/mcp.token
+pub fn read_token() -> Result<String> {
+    let path = home_dir()?.join(TOKEN_PATH);
+    let token = std::fs::read_to_string(&path)
+        .map_err(|e| TauriMCPError::ResourceUnavailable(format!("Cannot read {:?}: {}", path, e)))?;
+    Ok(token.trim().to_string())
+}
+
+/// Write the auth token to ~/.tauri/mcp.token (owner-only) for clients
+pub(crate) fn write_token(token: &str) -> Result<PathBuf> {
+    write_private_file(&home_dir()?.join(TOKEN_PATH), token)
+}
+
+/// Write the TCP port to ~/.tauri/mcp-tcp.json (owner-only) for clients
+pub(crate) fn write_tcp_info(info: &TcpInfo) -> Result<PathBuf> {
+    let json = serde_json::to_string(info)
+        .map_err(|e| TauriMCPError::InternalError(format!("Serialization failed: {}", e)))?;
+    write_private_file(&home_dir()?.join(TCP_INFO_PATH), &json)
+}
+
+/// Write `content` to `path`, readable by the owner only
+fn write_private_file(path: &Path, content: &str) -> Result<PathBuf> {
+    create_private_dir(path)?;
+
+    // Restrict an existing file before rewriting it
+    if path.exists() {
+        set_owner_only(path)?;
+    }
+    std::fs::write(path, content)
+        .map_err(|e| TauriMCPError::PermissionDenied(format!("Cannot write {:?}: {}", path, e)))?;
+    set_owner_only(path)?;
+
+    Ok(path.to_path_buf())
+}
+
+/// Home directory (HOME, or USERPROFILE on Windows)
+fn home_dir() -> Result<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+        .ok_or_else(|| TauriMCPError::InvalidConfig("HOME env var not set".to_string()))
+}
+
+/// Get absolute socket path (~/.tauri/mcp.sock)
+pub fn default_socket_path() -> Result<PathBuf> {
+    let path = home_dir()?.join(SOCKET_PATH);
+
+    // Validate path length (Unix socket limitation on macOS)
+    if path.as_os_str().len() >= MAX_SOCKET_PATH_LEN {
+        return Err(TauriMCPError::InvalidConfig(format!(
+            "Socket path too long: {} >= {}",
+            path.as_os_str().len(),
+            MAX_SOCKET_PATH_LEN
+        )));
+    }
+
+    Ok(path)
+}
+
+/// Create the parent directory of `path` with proper permissions (0o700)
+fn create_private_dir(path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| TauriMCPError::PermissionDenied(format!("Failed to create dir: {}", e)))?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let perms = std::fs::Permissions::from_mode(0o700);
+            std::fs::set_permissions(parent, perms)
+                .map_err(|e| TauriMCPError::PermissionDenied(format!("Failed to chmod dir: {}", e)))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Set file permissions to 0o600 (owner rw only)
+fn set_owner_only(path: &Path) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let perms = std::fs::Permissions::from_mode(0o600);
+        std::fs::set_permissions(path, perms)
+            .map_err(|e| TauriMCPError::PermissionDenied(format!("Failed to chmod {:?}: {}", path, e)))?;
+    }
+
+    #[cfg(not(unix))]
+    let _ = path;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Accept one client, run the handshake and echo one line back
+    async fn serve_one(mut listener: Listener, token: Option<String>) -> Result<()> {
+        let mut stream = listener.accept().await?;
+        if let Some(token) = token {
+            authenticate_client(&mut stream, &token).await?;
+        }
+        let line = read_line(&mut stream).await?;
+        stream.write_all(format!("{}\n", line).as_bytes()).await?;
+        Ok(())
+    }
+
+    async fn echo(stream: &mut BoxedStream, message: &str) -> String {
+        stream.write_all(format!("{}\n", message).as_bytes()).await.unwrap();
+        read_line(stream).await.unwrap()
+    }
+
+    /// Bind TCP on a free port, returning the listener and a transport to reach it
+    async fn bind_tcp() -> (Listener, Transport) {
+        let listener = Listener::bind(&Transport::Tcp { port: 0 }).await.unwrap();
+        let port = listener.tcp_port().unwrap();
+        (listener, Transport::Tcp { port })
+    }
+
+    #[tokio::test]
+    async fn test_tcp_accepts_matching_token() {
+        let (listener, transport) = bind_tcp().await;
+        let server = tokio::spawn(serve_one(listener, Some("secret".to_string())));
+
+        let mut stream = connect(&transport, Some("secret")).await.unwrap();
+        assert_eq!(echo(&mut stream, "ping").await, "ping");
+        assert!(server.await.unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_tcp_rejects_wrong_token() {
+        let (listener, transport) = bind_tcp().await;
+        let server = tokio::spawn(serve_one(listener, Some("secret".to_string())));
+
+        let error = connect(&transport, Some("guess")).await.err().unwrap();
+        assert!(matches!(error, TauriMCPError::PermissionDenied(_)));
+        assert!(matches!(server.await.unwrap(), Err(TauriMCPError::PermissionDenied(_))));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_request_before_authentication() {
+        let (listener, transport) = bind_tcp().await;
+        let server = tokio::spawn(serve_one(listener, Some("secret".to_string())));
+
+        // A client that skips the handshake gets an error instead of a result
+        let mut stream = connect(&transport, None).await.unwrap();
+        let request = JsonRpcRequest::new(
+            "browser_screenshot".to_string(),
+            None,
+            Value::from(1),
+        );
+        let reply = echo(&mut stream, &serde_json::to_string(&request).unwrap()).await;
+        let response: JsonRpcResponse = serde_json::from_str(&reply).unwrap();
+        assert!(response.result.is_none());
+        assert_eq!(response.error.unwrap().code, 2300);
+        assert!(matches!(server.await.unwrap(), Err(TauriMCPError::PermissionDenied(_))));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_unix_socket_round_trip() {
+        // Own directory, since the socket's parent is chmodded to 0o700
+        let dir = std::env::temp_dir().join(format!("mcp-{}", uuid::Uuid::new_v4().simple()));
+        let path = dir.join("mcp.sock");
+        let transport = Transport::UnixSocket { path: path.clone() };
+        let listener = Listener::bind(&transport).await.unwrap();
+        assert_eq!(listener.tcp_port(), None);
+        let server = tokio::spawn(serve_one(listener, Some("secret".to_string())));
+
+        let mut stream = connect(&transport, Some("secret")).await.unwrap();
+        assert_eq!(echo(&mut stream, "ping").await, "ping");
+        assert!(server.await.unwrap().is_ok());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[cfg(windows)]
+    #[tokio::test]
+    async fn test_named_pipe_round_trip() {
+        let name = format!(r"\\.\pipe\tauri-mcp-test-{}", uuid::Uuid::new_v4().simple());
+        let transport = Transport::NamedPipe { name };
+        let listener = Listener::bind(&transport).await.unwrap();
+        let server = tokio::spawn(serve_one(listener, None));
+
+        let mut stream = connect(&transport, None).await.unwrap();
+        assert_eq!(echo(&mut stream, "ping").await, "ping");
+        assert!(server.await.unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_transport_fails_to_bind() {
+        #[cfg(unix)]
+        let transport = Transport::NamedPipe { name: PIPE_NAME.to_string() };
+        #[cfg(windows)]
+        let transport = Transport::UnixSocket { path: PathBuf::from("mcp.sock") };
+
+        let error = Listener::bind(&transport).await.err().unwrap();
+        assert!(matches!(error, TauriMCPError::InvalidConfig(_)));
+    }
+
+    #[test]
+    fn test_generate_token_is_random() {
+        let token = generate_token();
+        assert_eq!(token.len(), 32);
+        assert_ne!(token, generate_token());
+    }
+
+    #[test]
+    fn test_tokens_match() {
+        assert!(tokens_match("abc123", "abc123"));
+        assert!(!tokens_match("abc124", "abc123"));
+        assert!(!tokens_match("abc", "abc123"));
+    }
+}
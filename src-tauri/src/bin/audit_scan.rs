@@ -68,7 +68,7 @@ async fn main() -> anyhow::Result<()> {
         }
 
         let start = Instant::now();
-        let scan = match scan::scan_project(app.handle().clone(), channels_state.clone(), project_id).await {
+        let scan = match scan::scan_project(app.handle().clone(), channels_state.clone(), project_id, None).await {
             Ok(s) => s,
             Err(e) => {
                 println!("mode={} | scan failed: {}", mode, e);
@@ -0,0 +1,11 @@
+//! `ryn-cli`: the headless scanner as its own binary, for CI pipelines
+//!
+//! Runs the same commands as `ryn scan|detect-framework|report` (see
+//! `ryn::cli`) without the desktop app's entry point, e.g.
+//! `ryn-cli scan --path . --format json --fail-on high`.
+//!
+//! Build with `cargo build --features headless --bin ryn-cli`.
+
+fn main() {
+    std::process::exit(ryn::cli::run_as("ryn-cli"));
+}
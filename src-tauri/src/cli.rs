@@ -0,0 +1,588 @@
+//! Headless command line interface (`headless` feature)
+//!
+//! Runs Ryn from CI pipelines without the Tauri GUI, either as `ryn <command>`
+//! or through the standalone `ryn-cli` binary:
+//!
+//! ```text
+//! ryn-cli scan [<path> | --path <path>] [--mode regex|smart|full] [--format json|sarif|text|github]
+//!              [--fail-on critical|high|medium|low|never] [--output <file>]
+//! ryn-cli detect-framework <path>
+//! ryn-cli report <scan-id> [--format md|pdf] [--output <file>]
+//! ```
+//!
+//! Scans use the same database as the desktop app (`RYN_DATA_DIR`) and run the
+//! full scan pipeline with a [`NullEventEmitter`]. Exit codes make `scan`
+//! usable as a CI gate: 0 when no violations at or above the `--fail-on`
+//! severity (default critical) are found, 1 when some are, 2 on errors. The
+//! pipeline's `[ryn]` progress lines go to stderr, so stdout holds only the
+//! result. `--format github` prints GitHub Actions workflow commands, which
+//! show up as inline annotations on the pull request.
+//! `report --format pdf` writes the same evidence report the desktop app
+//! exports (see [`crate::reporting`]).
+
+use anyhow::{bail, Context, Result};
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::commands::export::violations_to_sarif;
+use crate::commands::scan::{scan_project_internal, ScanResponseChannels};
+use crate::db::{self, queries};
+use crate::models::{Control, Project, Scan, Severity, Violation};
+use crate::reporting::{self, ComplianceReport};
+use crate::scanner::{NullEventEmitter, FRAMEWORK_CACHE};
+
+/// Exit code when the scan found no violations at the `--fail-on` severity
+/// or above (or the command succeeded)
+pub const EXIT_OK: i32 = 0;
+
+/// Exit code when the scan found violations at the `--fail-on` severity or above
+pub const EXIT_VIOLATIONS: i32 = 1;
+
+/// Exit code when the command failed
+pub const EXIT_ERROR: i32 = 2;
+
+#[derive(Debug, Parser)]
+#[command(name = "ryn", version, about = "SOC 2 compliance scanner")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Scan a project directory for SOC 2 violations
+    Scan {
+        #[arg(default_value = ".")]
+        path: PathBuf,
+        /// Same as the positional path
+        #[arg(long = "path", value_name = "PATH", conflicts_with = "path")]
+        path_option: Option<PathBuf>,
+        #[arg(long, value_enum, default_value_t = ScanMode::Regex)]
+        mode: ScanMode,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+        /// Exit with 1 when violations of this severity or above are found
+        #[arg(long, value_enum, default_value_t = FailOn::Critical)]
+        fail_on: FailOn,
+        /// Write the result here instead of stdout
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Print the web framework a project uses
+    DetectFramework { path: PathBuf },
+    /// Render a stored scan as a compliance report
+    Report {
+        scan_id: i64,
+        #[arg(long, value_enum, default_value_t = ReportFormat::Md)]
+        format: ReportFormat,
+        /// Write the report here instead of stdout
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+}
+
+/// Scan modes, mapped onto the `llm_scan_mode` setting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ScanMode {
+    /// Regex rules only, no LLM usage
+    Regex,
+    /// LLM analysis of security-relevant files
+    Smart,
+    /// LLM analysis of every file
+    Full,
+}
+
+impl ScanMode {
+    pub fn setting_value(&self) -> &'static str {
+        match self {
+            ScanMode::Regex => "regex_only",
+            ScanMode::Smart => "smart",
+            ScanMode::Full => "analyze_all",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Json,
+    Sarif,
+    Text,
+    /// GitHub Actions annotations (`::error file=...,line=...::message`)
+    Github,
+}
+
+/// Lowest severity that fails a scan (`--fail-on`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FailOn {
+    Critical,
+    High,
+    Medium,
+    Low,
+    /// Always exit 0 after a successful scan
+    Never,
+}
+
+impl FailOn {
+    /// Whether `scan` has violations at this severity or above
+    pub fn is_failure(&self, scan: &Scan) -> bool {
+        let counts = [scan.critical_count, scan.high_count, scan.medium_count, scan.low_count];
+        let levels = match self {
+            FailOn::Critical => 1,
+            FailOn::High => 2,
+            FailOn::Medium => 3,
+            FailOn::Low => 4,
+            FailOn::Never => 0,
+        };
+        counts[..levels].iter().any(|count| *count > 0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    Md,
+    Pdf,
+}
+
+/// Whether the process arguments ask for the CLI rather than the GUI
+///
+/// Only known subcommands and help/version flags count, so deep link URLs
+/// passed on the command line still start the desktop app.
+pub fn is_cli_invocation(args: impl IntoIterator<Item = String>) -> bool {
+    matches!(
+        args.into_iter().nth(1).as_deref(),
+        Some("scan" | "detect-framework" | "report" | "help" | "--help" | "-h" | "--version" | "-V")
+    )
+}
+
+/// Parse the process arguments, run the command, and return the exit code
+pub fn run() -> i32 {
+    run_as("ryn")
+}
+
+/// [`run`], with `bin_name` shown in usage and help (e.g. "ryn-cli")
+pub fn run_as(bin_name: &str) -> i32 {
+    let matches = Cli::command().bin_name(bin_name.to_string()).get_matches();
+    let cli = match Cli::from_arg_matches(&matches) {
+        Ok(cli) => cli,
+        Err(e) => e.exit(),
+    };
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("ryn: failed to start async runtime: {}", e);
+            return EXIT_ERROR;
+        }
+    };
+
+    match runtime.block_on(execute(cli.command)) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("ryn: {:#}", e);
+            EXIT_ERROR
+        }
+    }
+}
+
+/// Run a parsed command, returning the exit code
+pub async fn execute(command: Command) -> Result<i32> {
+    crate::utils::env::load_env().ok();
+
+    match command {
+        Command::Scan { path, path_option, mode, format, fail_on, output } => {
+            db::init_db().context("Failed to initialize database")?;
+            let path = path_option.unwrap_or(path);
+            let (scan, violations) = scan_headless(&path, mode).await?;
+            let rendered = match format {
+                OutputFormat::Json => serde_json::to_string_pretty(&serde_json::json!({
+                    "scan": scan,
+                    "violations": violations,
+                }))?,
+                OutputFormat::Sarif => serde_json::to_string_pretty(&violations_to_sarif(&violations))?,
+                OutputFormat::Text => render_text(&scan, &violations),
+                OutputFormat::Github => render_github_annotations(&scan, &violations),
+            };
+            write_output(output.as_deref(), rendered.as_bytes())?;
+
+            Ok(if fail_on.is_failure(&scan) { EXIT_VIOLATIONS } else { EXIT_OK })
+        }
+        Command::DetectFramework { path } => {
+            if !path.is_dir() {
+                bail!("Not a directory: {}", path.display());
+            }
+            let framework = FRAMEWORK_CACHE
+                .detect(&path)
+                .map_err(|e| anyhow::anyhow!("Framework detection failed: {}", e))?;
+            println!("{}", framework.as_deref().unwrap_or("unknown"));
+            Ok(EXIT_OK)
+        }
+        Command::Report { scan_id, format, output } => {
+            db::init_db().context("Failed to initialize database")?;
+            let bytes = {
+                let conn = db::get_connection();
+                match format {
+                    ReportFormat::Md => {
+                        let scan = queries::select_scan(&conn, scan_id)?
+                            .with_context(|| format!("Scan not found: {}", scan_id))?;
+                        let project = queries::select_project(&conn, scan.project_id)?
+                            .with_context(|| format!("Project not found: {}", scan.project_id))?;
+                        let violations = queries::select_violations(&conn, scan_id)?;
+                        render_markdown_report(&project, &scan, &violations).into_bytes()
+                    }
+                    // The same evidence report the desktop app exports
+                    ReportFormat::Pdf => ComplianceReport::build(&conn, scan_id)?.render(reporting::ReportFormat::Pdf),
+                }
+            }; // Connection dropped here
+
+            write_output(output.as_deref(), &bytes)?;
+            Ok(EXIT_OK)
+        }
+    }
+}
+
+/// Scan `path` (registering it as a project if needed) with the given mode
+///
+/// The `llm_scan_mode` setting is restored afterwards so CLI runs don't change
+/// the desktop app's configuration.
+async fn scan_headless(path: &Path, mode: ScanMode) -> Result<(Scan, Vec<Violation>)> {
+    let path = path
+        .canonicalize()
+        .with_context(|| format!("Project path does not exist: {}", path.display()))?;
+    if !path.is_dir() {
+        bail!("Not a directory: {}", path.display());
+    }
+    let path_str = path.to_string_lossy().to_string();
+
+    let (project_id, previous_mode) = {
+        let conn = db::get_connection();
+        let project_id = match queries::select_project_by_path(&conn, &path_str)? {
+            Some(project) => project.id,
+            None => {
+                let name = path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_else(|| path_str.clone());
+                queries::insert_project(&conn, &name, &path_str, None)?
+            }
+        };
+        let previous_mode = queries::select_setting(&conn, "llm_scan_mode")?.map(|s| s.value);
+        queries::insert_or_update_setting(&conn, "llm_scan_mode", mode.setting_value())?;
+        (project_id, previous_mode)
+    }; // Connection dropped here
+
+    let result = scan_project_internal(NullEventEmitter, &ScanResponseChannels::default(), project_id, None).await;
+
+    let conn = db::get_connection();
+    if let Some(previous_mode) = previous_mode {
+        queries::insert_or_update_setting(&conn, "llm_scan_mode", &previous_mode)?;
+    }
+
+    let scan = result.map_err(|e| anyhow::anyhow!(e))?;
+    let violations = queries::select_violations(&conn, scan.id)?;
+    Ok((scan, violations))
+}
+
+fn write_output(output: Option<&Path>, bytes: &[u8]) -> Result<()> {
+    match output {
+        Some(path) => std::fs::write(path, bytes)
+            .with_context(|| format!("Failed to write {}", path.display())),
+        None => {
+            use std::io::Write;
+            let mut stdout = std::io::stdout().lock();
+            stdout.write_all(bytes)?;
+            if !bytes.ends_with(b"\n") {
+                stdout.write_all(b"\n")?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// One line per violation, most severe first, then a summary line
+pub fn render_text(scan: &Scan, violations: &[Violation]) -> String {
+    let mut text = String::new();
+    for v in most_severe_first(violations) {
+        text.push_str(&format!(
+            "{:<8} {:<6} {}:{}  {}\n",
+            v.severity.to_uppercase(),
+            v.control_id,
+            v.file_path,
+            v.line_number,
+            v.description
+        ));
+    }
+    text.push_str(&summary_line(scan));
+    text
+}
+
+/// One GitHub Actions workflow command per violation, most severe first,
+/// then the summary line
+///
+/// Critical and high violations are errors, medium ones warnings and low ones
+/// notices. Paths are relative to the scanned directory, so scan the
+/// repository root for annotations to land on the right files.
+pub fn render_github_annotations(scan: &Scan, violations: &[Violation]) -> String {
+    let mut text = String::new();
+    for v in most_severe_first(violations) {
+        let level = match v.severity.as_str() {
+            "critical" | "high" => "error",
+            "medium" => "warning",
+            _ => "notice",
+        };
+        let mut properties = format!("file={}", escape_annotation_property(&v.file_path));
+        if v.line_number > 0 {
+            properties.push_str(&format!(",line={}", v.line_number));
+        }
+        let title = format!("{} {}", v.control_id, v.severity);
+        properties.push_str(&format!(",title={}", escape_annotation_property(&title)));
+        text.push_str(&format!("::{} {}::{}\n", level, properties, escape_annotation_data(&v.description)));
+    }
+    text.push_str(&summary_line(scan));
+    text
+}
+
+/// Escape a workflow command message so newlines don't end the command
+fn escape_annotation_data(value: &str) -> String {
+    value.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+/// Escape a workflow command property value (`file=`, `title=`)
+fn escape_annotation_property(value: &str) -> String {
+    escape_annotation_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+fn summary_line(scan: &Scan) -> String {
+    format!(
+        "Scanned {} files: {} violations ({} critical, {} high, {} medium, {} low)\n",
+        scan.files_scanned,
+        scan.violations_found,
+        scan.critical_count,
+        scan.high_count,
+        scan.medium_count,
+        scan.low_count
+    )
+}
+
+fn most_severe_first(violations: &[Violation]) -> Vec<&Violation> {
+    let mut sorted: Vec<&Violation> = violations.iter().collect();
+    sorted.sort_by_key(|v| {
+        let severity = Severity::from_str(&v.severity).map_or(0, |s| s.numeric_value());
+        (std::cmp::Reverse(severity), v.file_path.clone(), v.line_number)
+    });
+    sorted
+}
+
+/// Markdown compliance report for a stored scan, findings grouped by control
+pub fn render_markdown_report(project: &Project, scan: &Scan, violations: &[Violation]) -> String {
+    let count = |severity: &str| violations.iter().filter(|v| v.severity == severity).count();
+
+    let mut report = format!(
+        "# Ryn Compliance Report\n\n\
+         - Project: {} ({})\n\
+         - Scan: #{} ({}, {})\n\
+         - Started: {}\n\
+         - Completed: {}\n\
+         - Files scanned: {} of {}\n\n\
+         ## Summary\n\n\
+         | Severity | Violations |\n\
+         |----------|------------|\n",
+        project.name,
+        project.path,
+        scan.id,
+        scan.scan_mode,
+        scan.status,
+        scan.started_at,
+        scan.completed_at.as_deref().unwrap_or("-"),
+        scan.files_scanned,
+        scan.total_files,
+    );
+    for severity in ["critical", "high", "medium", "low"] {
+        report.push_str(&format!("| {} | {} |\n", severity, count(severity)));
+    }
+
+    let mut by_control: BTreeMap<&str, Vec<&Violation>> = BTreeMap::new();
+    for violation in violations {
+        by_control.entry(violation.control_id.as_str()).or_default().push(violation);
+    }
+
+    report.push_str("\n## Findings\n");
+    if by_control.is_empty() {
+        report.push_str("\nNo violations found.\n");
+    }
+
+    let controls = Control::all_controls();
+    for (control_id, control_violations) in by_control {
+        let name = controls
+            .iter()
+            .find(|c| c.id == control_id)
+            .map(|c| format!("{} {}", control_id, c.name))
+            .unwrap_or_else(|| control_id.to_string());
+        report.push_str(&format!("\n### {} ({})\n\n", name, control_violations.len()));
+
+        for v in control_violations {
+            report.push_str(&format!(
+                "- **{}** `{}:{}` {}\n",
+                v.severity.to_uppercase(),
+                v.file_path,
+                v.line_number,
+                v.description
+            ));
+        }
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn violation(control_id: &str, severity: Severity, file_path: &str, line: i64) -> Violation {
+        Violation::new(
+            1,
+            control_id.to_string(),
+            severity,
+            "Hardcoded secret".to_string(),
+            file_path.to_string(),
+            line,
+            "password = \"x\"".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_parse_scan_arguments() {
+        let cli = Cli::try_parse_from(["ryn", "scan", "/repo", "--mode", "full", "--format", "sarif", "--output", "out.sarif"])
+            .unwrap();
+        match cli.command {
+            Command::Scan { path, mode, format, output, .. } => {
+                assert_eq!(path, PathBuf::from("/repo"));
+                assert_eq!(mode.setting_value(), "analyze_all");
+                assert_eq!(format, OutputFormat::Sarif);
+                assert_eq!(output, Some(PathBuf::from("out.sarif")));
+            }
+            other => panic!("unexpected command {:?}", other),
+        }
+
+        let cli = Cli::try_parse_from(["ryn", "scan", "."]).unwrap();
+        assert!(matches!(cli.command, Command::Scan { mode: ScanMode::Regex, format: OutputFormat::Text, .. }));
+
+        assert!(Cli::try_parse_from(["ryn", "scan", ".", "--mode", "turbo"]).is_err());
+        assert!(Cli::try_parse_from(["ryn", "report", "not-a-number"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_scan_path_option_and_fail_on() {
+        let cli = Cli::try_parse_from(["ryn-cli", "scan", "--path", "/repo", "--format", "json", "--fail-on", "high"])
+            .unwrap();
+        match cli.command {
+            Command::Scan { path, path_option, fail_on, .. } => {
+                assert_eq!(path, PathBuf::from("."));
+                assert_eq!(path_option, Some(PathBuf::from("/repo")));
+                assert_eq!(fail_on, FailOn::High);
+            }
+            other => panic!("unexpected command {:?}", other),
+        }
+
+        // Defaults: current directory, fail on critical
+        let cli = Cli::try_parse_from(["ryn-cli", "scan"]).unwrap();
+        assert!(matches!(cli.command, Command::Scan { path_option: None, fail_on: FailOn::Critical, .. }));
+
+        assert!(Cli::try_parse_from(["ryn-cli", "scan", "/a", "--path", "/b"]).is_err());
+        assert!(Cli::try_parse_from(["ryn-cli", "scan", "--fail-on", "severe"]).is_err());
+    }
+
+    #[test]
+    fn test_fail_on_threshold() {
+        let mut scan = Scan::new(1, "regex_only".to_string());
+        scan.medium_count = 2;
+
+        assert!(!FailOn::Critical.is_failure(&scan));
+        assert!(!FailOn::High.is_failure(&scan));
+        assert!(FailOn::Medium.is_failure(&scan));
+        assert!(FailOn::Low.is_failure(&scan));
+        assert!(!FailOn::Never.is_failure(&scan));
+
+        scan.critical_count = 1;
+        assert!(FailOn::Critical.is_failure(&scan));
+        assert!(!FailOn::Never.is_failure(&scan));
+    }
+
+    #[test]
+    fn test_is_cli_invocation() {
+        let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        assert!(is_cli_invocation(args(&["ryn", "scan", "."])));
+        assert!(is_cli_invocation(args(&["ryn", "detect-framework", "."])));
+        assert!(is_cli_invocation(args(&["ryn", "--help"])));
+        assert!(!is_cli_invocation(args(&["ryn"])));
+        assert!(!is_cli_invocation(args(&["ryn", "ryn://violation/4"])));
+    }
+
+    #[test]
+    fn test_render_text_sorts_by_severity() {
+        let mut scan = Scan::new(1, "regex_only".to_string());
+        scan.files_scanned = 2;
+        scan.violations_found = 2;
+        scan.critical_count = 1;
+        scan.low_count = 1;
+        let violations = vec![
+            violation("CC7.2", Severity::Low, "b.py", 4),
+            violation("CC6.7", Severity::Critical, "a.py", 9),
+        ];
+
+        let text = render_text(&scan, &violations);
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert!(lines[0].starts_with("CRITICAL CC6.7  a.py:9"));
+        assert!(lines[1].starts_with("LOW      CC7.2  b.py:4"));
+        assert_eq!(lines[2], "Scanned 2 files: 2 violations (1 critical, 0 high, 0 medium, 1 low)");
+    }
+
+    #[test]
+    fn test_render_github_annotations() {
+        let mut scan = Scan::new(1, "regex_only".to_string());
+        scan.violations_found = 3;
+        let mut multiline = violation("CC6.1", Severity::Medium, "src/a,b.py", 0);
+        multiline.description = "Missing auth: 100% of\nroutes".to_string();
+        let violations = vec![
+            violation("CC7.2", Severity::Low, "b.py", 4),
+            multiline,
+            violation("CC6.7", Severity::Critical, "a.py", 9),
+        ];
+
+        let text = render_github_annotations(&scan, &violations);
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines[0], "::error file=a.py,line=9,title=CC6.7 critical::Hardcoded secret");
+        assert_eq!(lines[1], "::warning file=src/a%2Cb.py,title=CC6.1 medium::Missing auth: 100%25 of%0Aroutes");
+        assert_eq!(lines[2], "::notice file=b.py,line=4,title=CC7.2 low::Hardcoded secret");
+        assert!(lines[3].starts_with("Scanned 0 files: 3 violations"));
+
+        let cli = Cli::try_parse_from(["ryn", "scan", ".", "--format", "github"]).unwrap();
+        assert!(matches!(cli.command, Command::Scan { format: OutputFormat::Github, .. }));
+    }
+
+    #[test]
+    fn test_markdown_report_groups_by_control() {
+        let project = Project::new("shop".to_string(), "/repos/shop".to_string());
+        let scan = Scan::new(1, "regex_only".to_string());
+        let violations = vec![
+            violation("CC6.7", Severity::Critical, "settings.py", 3),
+            violation("CC6.7", Severity::High, "api.py", 8),
+            violation("CC7.2", Severity::Medium, "views.py", 20),
+        ];
+
+        let report = render_markdown_report(&project, &scan, &violations);
+
+        assert!(report.starts_with("# Ryn Compliance Report"));
+        assert!(report.contains("- Project: shop (/repos/shop)"));
+        assert!(report.contains("| critical | 1 |"));
+        assert!(report.contains("| low | 0 |"));
+        assert!(report.contains("(2)\n\n- **CRITICAL** `settings.py:3` Hardcoded secret"));
+        assert!(report.find("### CC6.7").unwrap() < report.find("### CC7.2").unwrap());
+
+        let empty = render_markdown_report(&project, &scan, &[]);
+        assert!(empty.contains("No violations found."));
+    }
+}
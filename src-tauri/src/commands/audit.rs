@@ -106,11 +106,7 @@ mod tests {
 
     fn create_test_audit_event(event_type: &str, project_id: Option<i64>) -> i64 {
         // Ensure project exists if specified and use the ACTUAL returned project_id
-        let actual_project_id = if let Some(pid) = project_id {
-            Some(create_test_project(pid))
-        } else {
-            None
-        };
+        let actual_project_id = project_id.map(create_test_project);
 
         let event = AuditEvent {
             id: 0,
@@ -0,0 +1,247 @@
+//! Deep link handling
+//!
+//! External tools (CI, Slack alerts) open Ryn with `ryn://` URLs:
+//! - `ryn://violation/{id}` - violation detail view
+//! - `ryn://scan/{id}` - scan results
+//! - `ryn://project/{id}` - project overview
+//!
+//! Parsed links are emitted to the frontend as `deep-link-navigate` events.
+//! Links arriving before the frontend emits `app-ready` (e.g. the link that
+//! launched the app) are queued and flushed once it does. Malformed links emit
+//! `deep-link-error` instead.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Listener, Manager, Runtime};
+
+pub const DEEP_LINK_SCHEME: &str = "ryn";
+pub const NAVIGATE_EVENT: &str = "deep-link-navigate";
+pub const ERROR_EVENT: &str = "deep-link-error";
+pub const APP_READY_EVENT: &str = "app-ready";
+
+/// Destination of a parsed deep link
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "id", rename_all = "snake_case")]
+pub enum DeepLinkTarget {
+    Violation(i64),
+    Scan(i64),
+    Project(i64),
+}
+
+/// Payload of `deep-link-error` events
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeepLinkError {
+    pub url: String,
+    pub error: String,
+}
+
+/// Frontend readiness and links received before it
+#[derive(Default)]
+pub struct DeepLinkState {
+    inner: Mutex<DeepLinkQueue>,
+}
+
+#[derive(Default)]
+struct DeepLinkQueue {
+    ready: bool,
+    pending: Vec<DeepLinkTarget>,
+}
+
+impl DeepLinkState {
+    /// Queue `target` unless the frontend is ready; returns true if it should be emitted now
+    fn accept(&self, target: DeepLinkTarget) -> bool {
+        let mut queue = self.inner.lock().unwrap();
+        if queue.ready {
+            true
+        } else {
+            queue.pending.push(target);
+            false
+        }
+    }
+
+    /// Mark the frontend ready and take everything queued so far
+    fn mark_ready(&self) -> Vec<DeepLinkTarget> {
+        let mut queue = self.inner.lock().unwrap();
+        queue.ready = true;
+        std::mem::take(&mut queue.pending)
+    }
+}
+
+/// Parse a `ryn://{kind}/{id}` URL
+///
+/// Scheme and kind are case-insensitive, plural kinds (`violations`) are accepted,
+/// and trailing slashes, query strings and fragments are ignored.
+pub fn parse_deep_link(url: &str) -> Result<DeepLinkTarget, String> {
+    let trimmed = url.trim();
+    let (scheme, rest) = trimmed
+        .split_once("://")
+        .ok_or_else(|| format!("Not a URL: {}", trimmed))?;
+
+    if !scheme.eq_ignore_ascii_case(DEEP_LINK_SCHEME) {
+        return Err(format!("Unsupported scheme: {}", scheme));
+    }
+
+    let path = rest.split(['?', '#']).next().unwrap_or("").trim_matches('/');
+    let mut segments = path.split('/');
+    let kind = segments.next().unwrap_or("").to_ascii_lowercase();
+    let id_segment = segments.next().ok_or_else(|| format!("Missing id in deep link: {}", trimmed))?;
+
+    if segments.next().is_some() {
+        return Err(format!("Unexpected path in deep link: {}", trimmed));
+    }
+
+    let id: i64 = id_segment
+        .parse()
+        .ok()
+        .filter(|id| *id > 0)
+        .ok_or_else(|| format!("Invalid id in deep link: {}", id_segment))?;
+
+    match kind.as_str() {
+        "violation" | "violations" => Ok(DeepLinkTarget::Violation(id)),
+        "scan" | "scans" => Ok(DeepLinkTarget::Scan(id)),
+        "project" | "projects" => Ok(DeepLinkTarget::Project(id)),
+        "" => Err(format!("Missing target in deep link: {}", trimmed)),
+        other => Err(format!("Unknown deep link target: {}", other)),
+    }
+}
+
+/// Parse an incoming deep link and forward it to the frontend
+///
+/// Emits `deep-link-navigate` (or queues it until `app-ready`) on success and
+/// `deep-link-error` on malformed input.
+pub fn handle_deep_link<R: Runtime>(app: &AppHandle<R>, url: String) {
+    eprintln!("[ryn] Deep link received: {}", url);
+
+    match parse_deep_link(&url) {
+        Ok(target) => {
+            let state = app.state::<DeepLinkState>();
+            if state.accept(target) {
+                let _ = app.emit(NAVIGATE_EVENT, target);
+            } else {
+                eprintln!("[ryn] Frontend not ready, queued deep link: {:?}", target);
+            }
+        }
+        Err(error) => {
+            eprintln!("[ryn] Invalid deep link: {}", error);
+            let _ = app.emit(ERROR_EVENT, DeepLinkError { url, error });
+        }
+    }
+}
+
+/// Flush queued deep links once the frontend emits `app-ready`
+pub fn listen_for_app_ready<R: Runtime>(app: &AppHandle<R>) {
+    let handle = app.clone();
+    app.listen(APP_READY_EVENT, move |_| {
+        let pending = handle.state::<DeepLinkState>().mark_ready();
+        for target in pending {
+            let _ = handle.emit(NAVIGATE_EVENT, target);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_parse_violation_and_scan_links() {
+        assert_eq!(parse_deep_link("ryn://violation/42"), Ok(DeepLinkTarget::Violation(42)));
+        assert_eq!(parse_deep_link("ryn://scan/7"), Ok(DeepLinkTarget::Scan(7)));
+        assert_eq!(parse_deep_link("ryn://projects/3"), Ok(DeepLinkTarget::Project(3)));
+    }
+
+    #[test]
+    fn test_parse_tolerates_case_slashes_and_query() {
+        assert_eq!(parse_deep_link("RYN://Violation/42/"), Ok(DeepLinkTarget::Violation(42)));
+        assert_eq!(parse_deep_link(" ryn://scan/7?source=ci#top "), Ok(DeepLinkTarget::Scan(7)));
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_links() {
+        for url in [
+            "",
+            "violation/42",
+            "https://violation/42",
+            "ryn://",
+            "ryn://violation",
+            "ryn://violation/",
+            "ryn://violation/abc",
+            "ryn://violation/-1",
+            "ryn://violation/0",
+            "ryn://violation/99999999999999999999",
+            "ryn://violation/42/extra",
+            "ryn://fix/42",
+        ] {
+            assert!(parse_deep_link(url).is_err(), "{:?} should be rejected", url);
+        }
+    }
+
+    #[test]
+    fn test_target_serializes_with_kind_and_id() {
+        let json = serde_json::to_value(DeepLinkTarget::Violation(42)).unwrap();
+        assert_eq!(json, serde_json::json!({ "kind": "violation", "id": 42 }));
+    }
+
+    /// Payloads recorded by an event listener
+    type Recorded<T> = Arc<Mutex<Vec<T>>>;
+
+    /// Mock app with deep link state and listeners recording emitted events
+    fn mock_app_with_recorders() -> (
+        tauri::App<tauri::test::MockRuntime>,
+        Recorded<DeepLinkTarget>,
+        Recorded<DeepLinkError>,
+    ) {
+        let app = tauri::test::mock_app();
+        app.manage(DeepLinkState::default());
+        listen_for_app_ready(app.handle());
+
+        let navigated = Arc::new(Mutex::new(Vec::new()));
+        let navigated_clone = navigated.clone();
+        app.listen(NAVIGATE_EVENT, move |event| {
+            navigated_clone.lock().unwrap().push(serde_json::from_str(event.payload()).unwrap());
+        });
+
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        let errors_clone = errors.clone();
+        app.listen(ERROR_EVENT, move |event| {
+            errors_clone.lock().unwrap().push(serde_json::from_str(event.payload()).unwrap());
+        });
+
+        (app, navigated, errors)
+    }
+
+    #[test]
+    fn test_handle_deep_link_queues_until_app_ready() {
+        let (app, navigated, errors) = mock_app_with_recorders();
+
+        handle_deep_link(app.handle(), "ryn://violation/1".to_string());
+        handle_deep_link(app.handle(), "ryn://scan/2".to_string());
+        assert!(navigated.lock().unwrap().is_empty());
+
+        app.handle().emit(APP_READY_EVENT, ()).unwrap();
+        assert_eq!(
+            *navigated.lock().unwrap(),
+            vec![DeepLinkTarget::Violation(1), DeepLinkTarget::Scan(2)]
+        );
+
+        // Once ready, links are emitted immediately
+        handle_deep_link(app.handle(), "ryn://violation/3".to_string());
+        assert_eq!(navigated.lock().unwrap().last(), Some(&DeepLinkTarget::Violation(3)));
+        assert!(errors.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_handle_deep_link_emits_error_event_for_malformed_url() {
+        let (app, navigated, errors) = mock_app_with_recorders();
+        app.handle().emit(APP_READY_EVENT, ()).unwrap();
+
+        handle_deep_link(app.handle(), "ryn://violation/not-a-number".to_string());
+
+        assert!(navigated.lock().unwrap().is_empty());
+        let errors = errors.lock().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].url, "ryn://violation/not-a-number");
+        assert!(errors[0].error.contains("Invalid id"));
+    }
+}
@@ -0,0 +1,1031 @@
+//! Import/export commands
+//!
+//! Imports SARIF 2.1.0 result files from other SAST tools (Semgrep, CodeQL, ...)
+//! so their findings show up alongside Ryn's own scans. Each SARIF run becomes
+//! a synthetic scan with status "imported"; its results become violations with
+//! detection_method "imported". [`violations_to_sarif`] goes the other way for
+//! the headless CLI (`ryn scan --format sarif`). [`generate_report`] renders
+//! a scan's SOC 2 evidence report as HTML or PDF for auditors, and
+//! [`export_violations`] writes a scan's violations to CSV or Excel for
+//! compliance managers who track findings in spreadsheets.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::Path;
+
+use crate::db::{self, queries};
+use crate::models::{Control, DetectionMethod, ScanStatus, Severity, Violation};
+use crate::reporting::spreadsheet::{self, SpreadsheetFormat, ViolationColumn};
+use crate::reporting::{ComplianceReport, ReportFormat};
+use crate::utils::create_audit_event;
+
+/// Only SARIF version accepted by `import_sarif`
+pub const SARIF_VERSION: &str = "2.1.0";
+
+/// Settings key holding the SARIF ruleId -> Ryn control_id mapping (JSON object)
+pub const SARIF_RULE_MAPPING_KEY: &str = "sarif_rule_mapping";
+
+/// Scan mode recorded on imported scans
+const IMPORTED_SCAN_MODE: &str = "imported";
+
+/// Summary of a SARIF import
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportResult {
+    pub scans_created: i64,
+    pub violations_imported: i64,
+    /// Rule IDs with no control mapping; their results were skipped
+    pub unmapped_rules: Vec<String>,
+}
+
+/// A report written by `generate_report`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GeneratedReport {
+    pub format: ReportFormat,
+    pub path: String,
+    pub size_bytes: u64,
+    /// SHA-256 of the file, so auditors can check it wasn't altered
+    pub sha256: String,
+}
+
+/// A spreadsheet written by `export_violations`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedViolations {
+    pub format: SpreadsheetFormat,
+    pub path: String,
+    /// Violations written, not counting the header row
+    pub rows_exported: i64,
+    /// Columns in the order written
+    pub columns: Vec<ViolationColumn>,
+    pub size_bytes: u64,
+    pub sha256: String,
+}
+
+// ============================================================
+// SARIF 2.1.0 subset
+// ============================================================
+
+#[derive(Debug, Deserialize)]
+struct SarifLog {
+    version: String,
+    #[serde(rename = "$schema")]
+    schema: Option<String>,
+    #[serde(default)]
+    runs: Vec<SarifRun>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SarifRun {
+    tool: SarifTool,
+    #[serde(default)]
+    results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SarifTool {
+    driver: SarifDriver,
+}
+
+#[derive(Debug, Deserialize)]
+struct SarifDriver {
+    name: String,
+    #[serde(default)]
+    rules: Vec<SarifRule>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRule {
+    id: String,
+    default_configuration: Option<SarifRuleConfiguration>,
+    properties: Option<HashMap<String, serde_json::Value>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SarifRuleConfiguration {
+    level: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: Option<String>,
+    rule_index: Option<usize>,
+    rule: Option<SarifRuleReference>,
+    level: Option<String>,
+    message: SarifMessage,
+    #[serde(default)]
+    locations: Vec<SarifLocation>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRuleReference {
+    id: Option<String>,
+    index: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SarifMessage {
+    text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifLocation {
+    physical_location: Option<SarifPhysicalLocation>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifPhysicalLocation {
+    artifact_location: Option<SarifArtifactLocation>,
+    region: Option<SarifRegion>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SarifArtifactLocation {
+    uri: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRegion {
+    start_line: Option<i64>,
+    snippet: Option<SarifMessage>,
+}
+
+impl SarifResult {
+    /// Rule ID from `ruleId`, `rule.id`, or the driver rule at `ruleIndex`/`rule.index`
+    fn rule_id<'a>(&'a self, rules: &'a [SarifRule]) -> Option<&'a str> {
+        self.rule_id
+            .as_deref()
+            .or_else(|| self.rule.as_ref().and_then(|r| r.id.as_deref()))
+            .or_else(|| {
+                let index = self
+                    .rule_index
+                    .or_else(|| self.rule.as_ref().and_then(|r| r.index))?;
+                rules.get(index).map(|r| r.id.as_str())
+            })
+    }
+
+    fn physical_location(&self) -> Option<&SarifPhysicalLocation> {
+        self.locations.first()?.physical_location.as_ref()
+    }
+}
+
+/// Parse a SARIF log and check it is version 2.1.0
+fn parse_sarif(contents: &str) -> Result<SarifLog, String> {
+    let log: SarifLog = serde_json::from_str(contents)
+        .map_err(|e| format!("Invalid SARIF file: {}", e))?;
+
+    if log.version != SARIF_VERSION {
+        return Err(format!(
+            "Unsupported SARIF version {} (expected {})",
+            log.version, SARIF_VERSION
+        ));
+    }
+
+    if let Some(schema) = &log.schema {
+        if !schema.contains(SARIF_VERSION) {
+            return Err(format!("Unsupported SARIF schema: {}", schema));
+        }
+    }
+
+    Ok(log)
+}
+
+/// Map a SARIF result to a Ryn severity
+///
+/// CodeQL-style `security-severity` scores (CVSS 0-10) win over levels;
+/// otherwise the result level, then the rule's default level, is used.
+fn map_severity(result: &SarifResult, rule: Option<&SarifRule>) -> Severity {
+    let security_severity = rule
+        .and_then(|r| r.properties.as_ref())
+        .and_then(|p| p.get("security-severity"))
+        .and_then(|v| match v {
+            serde_json::Value::String(s) => s.parse::<f64>().ok(),
+            other => other.as_f64(),
+        });
+
+    if let Some(score) = security_severity {
+        return match score {
+            s if s >= 9.0 => Severity::Critical,
+            s if s >= 7.0 => Severity::High,
+            s if s >= 4.0 => Severity::Medium,
+            _ => Severity::Low,
+        };
+    }
+
+    let level = result.level.as_deref().or_else(|| {
+        rule.and_then(|r| r.default_configuration.as_ref())
+            .and_then(|c| c.level.as_deref())
+    });
+
+    match level {
+        Some("error") => Severity::High,
+        Some("note") | Some("none") => Severity::Low,
+        // SARIF's default level is "warning"
+        _ => Severity::Medium,
+    }
+}
+
+/// Convert an artifact URI to a path relative to the project root
+fn relative_file_path(uri: &str, project_path: &str) -> String {
+    let path = uri.strip_prefix("file://").unwrap_or(uri);
+    let root = project_path.trim_end_matches('/');
+
+    path.strip_prefix(root)
+        .and_then(|p| p.strip_prefix('/'))
+        .unwrap_or(path)
+        .trim_start_matches("./")
+        .to_string()
+}
+
+/// Load the ruleId -> control_id mapping from settings
+///
+/// Mappings to unknown controls are dropped so their rules report as unmapped.
+fn load_rule_mapping(conn: &rusqlite::Connection) -> Result<HashMap<String, String>, String> {
+    let Some(setting) = queries::select_setting(conn, SARIF_RULE_MAPPING_KEY)
+        .map_err(|e| format!("Failed to fetch SARIF rule mapping: {}", e))?
+    else {
+        return Ok(HashMap::new());
+    };
+
+    let mapping: HashMap<String, String> = serde_json::from_str(&setting.value)
+        .map_err(|e| format!("Invalid {} setting: {}", SARIF_RULE_MAPPING_KEY, e))?;
+
+    let controls: HashSet<String> = Control::all_controls().into_iter().map(|c| c.id).collect();
+    Ok(mapping
+        .into_iter()
+        .filter(|(_, control_id)| controls.contains(control_id))
+        .collect())
+}
+
+/// Resolve a rule to a control: explicit mapping first, then rule IDs that are control IDs
+fn map_control(rule_id: &str, mapping: &HashMap<String, String>) -> Option<String> {
+    if let Some(control_id) = mapping.get(rule_id) {
+        return Some(control_id.clone());
+    }
+
+    Control::all_controls()
+        .into_iter()
+        .find(|c| c.id == rule_id)
+        .map(|c| c.id)
+}
+
+/// Code at `line_number`, used when the SARIF region has no snippet
+fn read_source_line(project_path: &str, file_path: &str, line_number: i64) -> String {
+    std::fs::read_to_string(Path::new(project_path).join(file_path))
+        .ok()
+        .and_then(|contents| {
+            contents
+                .lines()
+                .nth((line_number - 1).max(0) as usize)
+                .map(|line| line.trim().to_string())
+        })
+        .unwrap_or_default()
+}
+
+/// SARIF level for a Ryn severity (critical and high are both "error")
+fn sarif_level(severity: &str) -> &'static str {
+    match Severity::from_str(severity) {
+        Some(Severity::Critical) | Some(Severity::High) => "error",
+        Some(Severity::Low) => "note",
+        _ => "warning",
+    }
+}
+
+/// Build a SARIF 2.1.0 log with one run holding `violations`
+///
+/// Each control with findings becomes a rule; the Ryn severity is kept in
+/// each result's `properties.severity`.
+pub fn violations_to_sarif(violations: &[Violation]) -> serde_json::Value {
+    let control_ids: BTreeSet<&str> = violations.iter().map(|v| v.control_id.as_str()).collect();
+    let controls = Control::all_controls();
+
+    let rules: Vec<serde_json::Value> = control_ids
+        .iter()
+        .map(|id| {
+            let name = controls
+                .iter()
+                .find(|c| c.id == *id)
+                .map(|c| c.name.as_str())
+                .unwrap_or(*id);
+            serde_json::json!({ "id": id, "name": name })
+        })
+        .collect();
+
+    let results: Vec<serde_json::Value> = violations
+        .iter()
+        .map(|v| {
+            serde_json::json!({
+                "ruleId": v.control_id,
+                "level": sarif_level(&v.severity),
+                "message": { "text": v.description },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": v.file_path },
+                        "region": {
+                            "startLine": v.line_number,
+                            "snippet": { "text": v.code_snippet }
+                        }
+                    }
+                }],
+                "properties": { "severity": v.severity }
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": SARIF_VERSION,
+        "runs": [{
+            "tool": { "driver": {
+                "name": "Ryn",
+                "version": env!("CARGO_PKG_VERSION"),
+                "rules": rules
+            }},
+            "results": results
+        }]
+    })
+}
+
+/// Import violations from a SARIF 2.1.0 result file
+///
+/// Each run becomes a scan with status "imported". Results are mapped to
+/// controls via the `sarif_rule_mapping` setting (a JSON object of
+/// ruleId -> control_id); results whose rule has no mapping are skipped and
+/// their rule IDs reported in `unmapped_rules`.
+///
+/// # Arguments
+/// * `project_id` - Project the findings belong to
+/// * `sarif_path` - Path to the .sarif / .json file
+///
+/// Returns: Counts of created scans and imported violations
+#[tauri::command]
+pub async fn import_sarif(project_id: i64, sarif_path: String) -> Result<ImportResult, String> {
+    eprintln!("[ryn] import_sarif called: project_id={}, path={}", project_id, sarif_path);
+
+    let contents = std::fs::read_to_string(&sarif_path)
+        .map_err(|e| format!("Failed to read SARIF file {}: {}", sarif_path, e))?;
+    let log = parse_sarif(&contents)?;
+
+    let conn = db::get_connection();
+
+    let project = queries::select_project(&conn, project_id)
+        .map_err(|e| format!("Failed to fetch project: {}", e))?
+        .ok_or_else(|| format!("Project not found: {}", project_id))?;
+    let mapping = load_rule_mapping(&conn)?;
+
+    let mut scans_created = 0;
+    let mut violations_imported = 0;
+    let mut unmapped_rules = BTreeSet::new();
+
+    for run in &log.runs {
+        let tool = &run.tool.driver.name;
+        let rules = &run.tool.driver.rules;
+        let mut violations = Vec::new();
+
+        for result in &run.results {
+            let Some(rule_id) = result.rule_id(rules) else {
+                continue;
+            };
+            let Some(control_id) = map_control(rule_id, &mapping) else {
+                unmapped_rules.insert(rule_id.to_string());
+                continue;
+            };
+
+            let rule = rules.iter().find(|r| r.id == rule_id);
+            let location = result.physical_location();
+            let file_path = location
+                .and_then(|l| l.artifact_location.as_ref())
+                .and_then(|a| a.uri.as_deref())
+                .map(|uri| relative_file_path(uri, &project.path))
+                .unwrap_or_default();
+            let region = location.and_then(|l| l.region.as_ref());
+            let line_number = region.and_then(|r| r.start_line).unwrap_or(1);
+            let code_snippet = region
+                .and_then(|r| r.snippet.as_ref())
+                .and_then(|s| s.text.as_deref())
+                .map(|s| s.trim().to_string())
+                .unwrap_or_else(|| read_source_line(&project.path, &file_path, line_number));
+            let description = result
+                .message
+                .text
+                .clone()
+                .unwrap_or_else(|| format!("{} finding for rule {}", tool, rule_id));
+
+            // scan_id is filled in once the run has at least one mapped result
+            let mut violation = Violation::new(
+                0,
+                control_id,
+                map_severity(result, rule),
+                description,
+                file_path,
+                line_number,
+                code_snippet,
+            );
+            violation.set_detection_method(DetectionMethod::Imported);
+            violation.regex_reasoning = Some(format!("Imported from {} rule {}", tool, rule_id));
+            violations.push(violation);
+        }
+
+        if violations.is_empty() {
+            continue;
+        }
+
+        let scan_id = queries::insert_scan(&conn, project_id, IMPORTED_SCAN_MODE)
+            .map_err(|e| format!("Failed to create imported scan: {}", e))?;
+
+        let mut files = HashSet::new();
+        let violations_found = violations.len() as i32;
+        for mut violation in violations {
+            violation.scan_id = scan_id;
+            queries::insert_violation(&conn, &violation)
+                .map_err(|e| format!("Failed to import violation: {}", e))?;
+            files.insert(violation.file_path);
+        }
+        violations_imported += violations_found as i64;
+
+        queries::update_scan_results(&conn, scan_id, files.len() as i32, files.len() as i32, violations_found)
+            .map_err(|e| format!("Failed to update imported scan: {}", e))?;
+
+        let completed_at = chrono::Utc::now().to_rfc3339();
+        queries::update_scan_status(&conn, scan_id, ScanStatus::Imported.as_str(), Some(&completed_at))
+            .map_err(|e| format!("Failed to update imported scan: {}", e))?;
+
+        scans_created += 1;
+    }
+
+    let unmapped_rules: Vec<String> = unmapped_rules.into_iter().collect();
+
+    if let Ok(event) = create_audit_event(
+        &conn,
+        "sarif_imported",
+        Some(project_id),
+        None,
+        None,
+        &format!(
+            "Imported {} violations in {} scans from {} ({} unmapped rules)",
+            violations_imported,
+            scans_created,
+            sarif_path,
+            unmapped_rules.len()
+        ),
+    ) {
+        let _ = queries::insert_audit_event(&conn, &event);
+    }
+
+    Ok(ImportResult {
+        scans_created,
+        violations_imported,
+        unmapped_rules,
+    })
+}
+
+/// Generate a scan's SOC 2 evidence report
+///
+/// The report covers violations grouped by control, severity and
+/// remediation status breakdowns, and the project's compliance trend. It is
+/// written to `reports/scan-{id}-report.{html,pdf}` in the data directory,
+/// replacing any earlier report for the scan.
+///
+/// # Arguments
+/// * `scan_id` - Scan to report on
+/// * `format` - "html" or "pdf"
+///
+/// Returns: Location, size and checksum of the report file
+#[tauri::command]
+pub async fn generate_report(scan_id: i64, format: String) -> Result<GeneratedReport, String> {
+    eprintln!("[ryn] generate_report called: scan_id={}, format={}", scan_id, format);
+
+    let format = ReportFormat::from_str(&format)
+        .ok_or_else(|| format!("Invalid report format: {} (expected html or pdf)", format))?;
+
+    let report = {
+        let conn = db::get_connection();
+        ComplianceReport::build(&conn, scan_id).map_err(|e| format!("Failed to build report: {}", e))?
+    }; // Connection dropped here
+    let contents = report.render(format);
+
+    let db_path = db::get_db_path()
+        .map_err(|e| format!("Failed to resolve data directory: {}", e))?;
+    let reports_dir = db_path.parent().unwrap_or_else(|| Path::new(".")).join("reports");
+    std::fs::create_dir_all(&reports_dir)
+        .map_err(|e| format!("Failed to create reports directory: {}", e))?;
+    let path = reports_dir.join(format!("scan-{}-report.{}", scan_id, format.extension()));
+    std::fs::write(&path, &contents)
+        .map_err(|e| format!("Failed to write report: {}", e))?;
+
+    let generated = GeneratedReport {
+        format,
+        path: path.to_string_lossy().to_string(),
+        size_bytes: contents.len() as u64,
+        sha256: hex::encode(Sha256::digest(&contents)),
+    };
+
+    let conn = db::get_connection();
+    if let Ok(event) = create_audit_event(
+        &conn,
+        "report_generated",
+        Some(report.project.id),
+        None,
+        None,
+        &format!(
+            "Generated {} compliance report for scan {} (sha256 {})",
+            format.as_str(),
+            scan_id,
+            generated.sha256
+        ),
+    ) {
+        let _ = queries::insert_audit_event(&conn, &event);
+    }
+
+    eprintln!("[ryn] generate_report success: {}", generated.path);
+    Ok(generated)
+}
+
+/// Export a scan's violations as a CSV file or Excel workbook
+///
+/// Writes one row per violation, most severe first, to
+/// `exports/scan-{id}-violations.{csv,xlsx}` in the data directory, replacing
+/// any earlier export of the scan.
+///
+/// # Arguments
+/// * `scan_id` - Scan whose violations to export
+/// * `format` - "csv" or "xlsx"
+/// * `columns` - Column names (e.g. "control_id", "severity", "file_path");
+///   None or empty uses the default columns
+///
+/// Returns: Location, row count and checksum of the file
+#[tauri::command]
+pub async fn export_violations(
+    scan_id: i64,
+    format: String,
+    columns: Option<Vec<String>>,
+) -> Result<ExportedViolations, String> {
+    eprintln!("[ryn] export_violations called: scan_id={}, format={}", scan_id, format);
+
+    let format = SpreadsheetFormat::from_str(&format)
+        .ok_or_else(|| format!("Invalid export format: {} (expected csv or xlsx)", format))?;
+    let columns = ViolationColumn::parse_list(columns.as_deref()).map_err(|e| e.to_string())?;
+
+    let (project_id, violations, control_names) = {
+        let conn = db::get_connection();
+        let scan = queries::select_scan(&conn, scan_id)
+            .map_err(|e| format!("Failed to fetch scan: {}", e))?
+            .ok_or_else(|| "Scan not found".to_string())?;
+        let violations = queries::select_violations(&conn, scan_id)
+            .map_err(|e| format!("Failed to fetch violations: {}", e))?;
+        let control_names: HashMap<String, String> = queries::select_controls(&conn)
+            .map_err(|e| format!("Failed to fetch controls: {}", e))?
+            .into_iter()
+            .map(|control| (control.id, control.name))
+            .collect();
+        (scan.project_id, violations, control_names)
+    }; // Connection dropped here
+
+    let contents = spreadsheet::render(&violations, &columns, &control_names, format)
+        .map_err(|e| format!("Failed to render export: {}", e))?;
+
+    let db_path = db::get_db_path()
+        .map_err(|e| format!("Failed to resolve data directory: {}", e))?;
+    let exports_dir = db_path.parent().unwrap_or_else(|| Path::new(".")).join("exports");
+    std::fs::create_dir_all(&exports_dir)
+        .map_err(|e| format!("Failed to create exports directory: {}", e))?;
+    let path = exports_dir.join(format!("scan-{}-violations.{}", scan_id, format.extension()));
+    std::fs::write(&path, &contents)
+        .map_err(|e| format!("Failed to write export: {}", e))?;
+
+    let exported = ExportedViolations {
+        format,
+        path: path.to_string_lossy().to_string(),
+        rows_exported: violations.len() as i64,
+        columns,
+        size_bytes: contents.len() as u64,
+        sha256: hex::encode(Sha256::digest(&contents)),
+    };
+
+    let conn = db::get_connection();
+    if let Ok(event) = create_audit_event(
+        &conn,
+        "violations_exported",
+        Some(project_id),
+        None,
+        None,
+        &format!(
+            "Exported {} violations from scan {} as {}",
+            exported.rows_exported,
+            scan_id,
+            format.as_str()
+        ),
+    ) {
+        let _ = queries::insert_audit_event(&conn, &event);
+    }
+
+    eprintln!("[ryn] export_violations success: {}", exported.path);
+    Ok(exported)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_helpers::TestDbGuard;
+    use tempfile::TempDir;
+
+    /// Trimmed from `semgrep --sarif` output (Semgrep 1.x)
+    const SEMGREP_SARIF: &str = r#"{
+      "$schema": "https://docs.oasis-open.org/sarif/sarif/v2.1.0/os/schemas/sarif-schema-2.1.0.json",
+      "version": "2.1.0",
+      "runs": [{
+        "tool": {"driver": {
+          "name": "Semgrep OSS",
+          "semanticVersion": "1.45.0",
+          "rules": [
+            {"id": "python.django.security.audit.unvalidated-password.unvalidated-password",
+             "defaultConfiguration": {"level": "warning"},
+             "properties": {"precision": "very-high", "tags": ["CWE-521"]}},
+            {"id": "python.lang.security.audit.hardcoded-password-default-argument",
+             "defaultConfiguration": {"level": "error"}},
+            {"id": "python.lang.best-practice.open-never-closed",
+             "defaultConfiguration": {"level": "note"}}
+          ]
+        }},
+        "results": [
+          {"ruleId": "python.django.security.audit.unvalidated-password.unvalidated-password",
+           "level": "warning",
+           "message": {"text": "The password on 'user' is being set without validating the password."},
+           "locations": [{"physicalLocation": {
+             "artifactLocation": {"uri": "accounts/views.py", "uriBaseId": "%SRCROOT%"},
+             "region": {"startLine": 42, "startColumn": 5, "endLine": 42, "endColumn": 35,
+                        "snippet": {"text": "    user.set_password(new_password)"}}}}]},
+          {"ruleId": "python.lang.security.audit.hardcoded-password-default-argument",
+           "level": "error",
+           "message": {"text": "Hardcoded password is used as a default argument to 'connect'."},
+           "locations": [{"physicalLocation": {
+             "artifactLocation": {"uri": "db/client.py", "uriBaseId": "%SRCROOT%"},
+             "region": {"startLine": 7}}}]},
+          {"ruleId": "python.lang.best-practice.open-never-closed",
+           "level": "note",
+           "message": {"text": "file object opened without corresponding close"},
+           "locations": [{"physicalLocation": {
+             "artifactLocation": {"uri": "utils/io.py"},
+             "region": {"startLine": 3}}}]}
+        ]
+      }]
+    }"#;
+
+    /// Trimmed from `codeql database analyze --format=sarif-latest` output
+    const CODEQL_SARIF: &str = r#"{
+      "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+      "version": "2.1.0",
+      "runs": [{
+        "tool": {"driver": {
+          "name": "CodeQL",
+          "organization": "GitHub",
+          "semanticVersion": "2.15.1",
+          "rules": [
+            {"id": "py/sql-injection",
+             "name": "py/sql-injection",
+             "shortDescription": {"text": "SQL query built from user-controlled sources"},
+             "defaultConfiguration": {"enabled": true, "level": "error"},
+             "properties": {"tags": ["security", "external/cwe/cwe-089"],
+                            "precision": "high", "security-severity": "8.8"}},
+            {"id": "py/clear-text-logging-sensitive-data",
+             "name": "py/clear-text-logging-sensitive-data",
+             "defaultConfiguration": {"enabled": true, "level": "error"},
+             "properties": {"security-severity": "7.5"}}
+          ]
+        }},
+        "results": [
+          {"ruleId": "py/sql-injection",
+           "ruleIndex": 0,
+           "rule": {"id": "py/sql-injection", "index": 0},
+           "message": {"text": "This SQL query depends on a [user-provided value](1)."},
+           "locations": [{"physicalLocation": {
+             "artifactLocation": {"uri": "app/routes.py", "uriBaseId": "%SRCROOT%", "index": 0},
+             "region": {"startLine": 2, "startColumn": 20, "endColumn": 61}}}],
+           "partialFingerprints": {"primaryLocationLineHash": "d3b2e4a5c1f0a9b8:1"}},
+          {"ruleIndex": 1,
+           "message": {"text": "This expression logs [sensitive data (password)](1) as clear text."},
+           "locations": [{"physicalLocation": {
+             "artifactLocation": {"uri": "app/auth.py", "uriBaseId": "%SRCROOT%", "index": 1},
+             "region": {"startLine": 15}}}]}
+        ]
+      }]
+    }"#;
+
+    fn create_test_project() -> (TempDir, i64) {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().to_string_lossy().to_string();
+        let conn = db::get_connection();
+        let project_id = queries::insert_project(&conn, "sarif-project", &path, None).unwrap();
+        (temp_dir, project_id)
+    }
+
+    fn set_rule_mapping(mapping: serde_json::Value) {
+        let conn = db::get_connection();
+        queries::insert_or_update_setting(&conn, SARIF_RULE_MAPPING_KEY, &mapping.to_string()).unwrap();
+    }
+
+    fn write_sarif(dir: &TempDir, contents: &str) -> String {
+        let path = dir.path().join("results.sarif");
+        std::fs::write(&path, contents).unwrap();
+        path.to_string_lossy().to_string()
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_import_semgrep_sarif() {
+        let _guard = TestDbGuard::new();
+        let (project_dir, project_id) = create_test_project();
+        set_rule_mapping(serde_json::json!({
+            "python.django.security.audit.unvalidated-password.unvalidated-password": "CC6.1",
+            "python.lang.security.audit.hardcoded-password-default-argument": "CC6.7",
+        }));
+        std::fs::create_dir_all(project_dir.path().join("db")).unwrap();
+        std::fs::write(
+            project_dir.path().join("db/client.py"),
+            "import psycopg2\n\n\n\n\n\ndef connect(password=\"hunter2\"):\n    pass\n",
+        )
+        .unwrap();
+        let sarif_path = write_sarif(&project_dir, SEMGREP_SARIF);
+
+        let result = import_sarif(project_id, sarif_path).await.unwrap();
+
+        assert_eq!(result.scans_created, 1);
+        assert_eq!(result.violations_imported, 2);
+        assert_eq!(result.unmapped_rules, vec!["python.lang.best-practice.open-never-closed"]);
+
+        let conn = db::get_connection();
+        let scans = queries::select_scans(&conn, project_id).unwrap();
+        assert_eq!(scans.len(), 1);
+        assert_eq!(scans[0].status, "imported");
+        assert_eq!(scans[0].scan_mode, "imported");
+        assert_eq!(scans[0].violations_found, 2);
+        assert_eq!(scans[0].files_scanned, 2);
+        assert!(scans[0].completed_at.is_some());
+
+        let violations = queries::select_violations(&conn, scans[0].id).unwrap();
+        assert_eq!(violations.len(), 2);
+        assert!(violations.iter().all(|v| v.detection_method == "imported"));
+
+        let password = violations.iter().find(|v| v.control_id == "CC6.1").unwrap();
+        assert_eq!(password.file_path, "accounts/views.py");
+        assert_eq!(password.line_number, 42);
+        assert_eq!(password.severity, "medium");
+        assert_eq!(password.code_snippet, "user.set_password(new_password)");
+        assert_eq!(
+            password.description,
+            "The password on 'user' is being set without validating the password."
+        );
+
+        // No snippet in the SARIF region: the line is read from the project
+        let secret = violations.iter().find(|v| v.control_id == "CC6.7").unwrap();
+        assert_eq!(secret.severity, "high");
+        assert_eq!(secret.line_number, 7);
+        assert_eq!(secret.code_snippet, "def connect(password=\"hunter2\"):");
+
+        let events = queries::select_audit_events_by_project(&conn, project_id).unwrap();
+        assert!(events.iter().any(|e| e.event_type == "sarif_imported"));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_import_codeql_sarif_uses_rule_index_and_security_severity() {
+        let _guard = TestDbGuard::new();
+        let (project_dir, project_id) = create_test_project();
+        set_rule_mapping(serde_json::json!({
+            "py/sql-injection": "CC6.1",
+            "py/clear-text-logging-sensitive-data": "CC7.2",
+        }));
+        let sarif_path = write_sarif(&project_dir, CODEQL_SARIF);
+
+        let result = import_sarif(project_id, sarif_path).await.unwrap();
+
+        assert_eq!(result.scans_created, 1);
+        assert_eq!(result.violations_imported, 2);
+        assert!(result.unmapped_rules.is_empty());
+
+        let conn = db::get_connection();
+        let scans = queries::select_scans(&conn, project_id).unwrap();
+        let violations = queries::select_violations(&conn, scans[0].id).unwrap();
+
+        let sql = violations.iter().find(|v| v.control_id == "CC6.1").unwrap();
+        assert_eq!(sql.severity, "high");
+        assert_eq!(sql.file_path, "app/routes.py");
+        assert_eq!(sql.line_number, 2);
+        assert_eq!(sql.code_snippet, "");
+
+        // Only ruleIndex identifies the rule for the logging result
+        let logging = violations.iter().find(|v| v.control_id == "CC7.2").unwrap();
+        assert_eq!(logging.file_path, "app/auth.py");
+        assert_eq!(
+            logging.regex_reasoning.as_deref(),
+            Some("Imported from CodeQL rule py/clear-text-logging-sensitive-data")
+        );
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_import_without_mapping_reports_all_rules_unmapped() {
+        let _guard = TestDbGuard::new();
+        let (project_dir, project_id) = create_test_project();
+        // Mappings to unknown controls are ignored
+        set_rule_mapping(serde_json::json!({ "py/sql-injection": "CC9.9" }));
+        let sarif_path = write_sarif(&project_dir, CODEQL_SARIF);
+
+        let result = import_sarif(project_id, sarif_path).await.unwrap();
+
+        assert_eq!(result.scans_created, 0);
+        assert_eq!(result.violations_imported, 0);
+        assert_eq!(
+            result.unmapped_rules,
+            vec!["py/clear-text-logging-sensitive-data", "py/sql-injection"]
+        );
+
+        let conn = db::get_connection();
+        assert!(queries::select_scans(&conn, project_id).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_import_rejects_unsupported_sarif_version() {
+        let _guard = TestDbGuard::new();
+        let (project_dir, project_id) = create_test_project();
+        let sarif_path = write_sarif(
+            &project_dir,
+            &SEMGREP_SARIF.replace("\"version\": \"2.1.0\"", "\"version\": \"2.0.0\""),
+        );
+
+        let err = import_sarif(project_id, sarif_path).await.unwrap_err();
+
+        assert!(err.contains("Unsupported SARIF version 2.0.0"), "{}", err);
+        let conn = db::get_connection();
+        assert!(queries::select_scans(&conn, project_id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_sarif_rejects_mismatched_schema() {
+        let sarif = CODEQL_SARIF.replace("sarif-2.1.0.json", "sarif-2.0.0.json");
+        let err = parse_sarif(&sarif).unwrap_err();
+        assert!(err.contains("Unsupported SARIF schema"), "{}", err);
+
+        assert!(parse_sarif("{\"runs\": []}").unwrap_err().contains("Invalid SARIF file"));
+    }
+
+    #[test]
+    fn test_relative_file_path() {
+        assert_eq!(relative_file_path("src/app.py", "/repo"), "src/app.py");
+        assert_eq!(relative_file_path("./src/app.py", "/repo"), "src/app.py");
+        assert_eq!(relative_file_path("file:///repo/src/app.py", "/repo/"), "src/app.py");
+        assert_eq!(relative_file_path("/other/app.py", "/repo"), "/other/app.py");
+        assert_eq!(relative_file_path("/repository/app.py", "/repo"), "/repository/app.py");
+    }
+
+    #[test]
+    fn test_violations_to_sarif_round_trips_through_parser() {
+        let violation = |control_id: &str, severity: Severity, line: i64| Violation::new(
+            1, control_id.to_string(), severity, "Finding".to_string(),
+            "app/config.py".to_string(), line, "password = \"x\"".to_string(),
+        );
+        let violations = vec![
+            violation("CC6.7", Severity::Critical, 3),
+            violation("CC6.7", Severity::Low, 9),
+            violation("CC7.2", Severity::Medium, 12),
+        ];
+
+        let sarif = violations_to_sarif(&violations);
+        let log = parse_sarif(&sarif.to_string()).unwrap();
+
+        let run = &log.runs[0];
+        assert_eq!(run.tool.driver.name, "Ryn");
+        let rule_ids: Vec<&str> = run.tool.driver.rules.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(rule_ids, vec!["CC6.7", "CC7.2"]);
+
+        let levels: Vec<Option<&str>> = run.results.iter().map(|r| r.level.as_deref()).collect();
+        assert_eq!(levels, vec![Some("error"), Some("note"), Some("warning")]);
+        let region = run.results[2].physical_location().unwrap().region.as_ref().unwrap();
+        assert_eq!(region.start_line, Some(12));
+        assert_eq!(sarif["runs"][0]["results"][0]["properties"]["severity"], "critical");
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_generate_report_writes_html_and_pdf() {
+        let _guard = TestDbGuard::new();
+        let (_dir, project_id) = create_test_project();
+        let scan_id = {
+            let conn = db::get_connection();
+            let scan_id = queries::insert_scan(&conn, project_id, "regex_only").unwrap();
+            let violation = Violation::new(
+                scan_id,
+                "CC6.7".to_string(),
+                Severity::High,
+                "Hardcoded API key".to_string(),
+                "app.py".to_string(),
+                4,
+                String::new(),
+            );
+            queries::insert_violation(&conn, &violation).unwrap();
+            queries::update_scan_status(&conn, scan_id, "completed", Some("2026-03-10T02:00:00Z")).unwrap();
+            scan_id
+        };
+
+        let html = generate_report(scan_id, "html".to_string()).await.unwrap();
+        assert_eq!(html.format, ReportFormat::Html);
+        assert!(html.path.ends_with(&format!("reports/scan-{}-report.html", scan_id)));
+        let contents = std::fs::read_to_string(&html.path).unwrap();
+        assert!(contents.contains("Hardcoded API key"));
+        assert_eq!(html.size_bytes, contents.len() as u64);
+        assert_eq!(html.sha256, hex::encode(Sha256::digest(contents.as_bytes())));
+
+        let pdf = generate_report(scan_id, "PDF".to_string()).await.unwrap();
+        let bytes = std::fs::read(&pdf.path).unwrap();
+        assert!(bytes.starts_with(b"%PDF-"));
+
+        let conn = db::get_connection();
+        let events = queries::select_audit_events_by_project(&conn, project_id).unwrap();
+        assert_eq!(events.iter().filter(|e| e.event_type == "report_generated").count(), 2);
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_generate_report_rejects_bad_input() {
+        let _guard = TestDbGuard::new();
+
+        let error = generate_report(1, "docx".to_string()).await.unwrap_err();
+        assert!(error.contains("Invalid report format"));
+
+        let error = generate_report(999, "html".to_string()).await.unwrap_err();
+        assert!(error.contains("Scan not found"));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_export_violations_csv_and_xlsx() {
+        let _guard = TestDbGuard::new();
+        let (_dir, project_id) = create_test_project();
+        let scan_id = {
+            let conn = db::get_connection();
+            let scan_id = queries::insert_scan(&conn, project_id, "regex_only").unwrap();
+            for (severity, file_path, line) in [(Severity::Low, "debug.py", 3), (Severity::Critical, "app.py", 4)] {
+                let violation = Violation::new(
+                    scan_id,
+                    "CC6.7".to_string(),
+                    severity,
+                    "Hardcoded API key".to_string(),
+                    file_path.to_string(),
+                    line,
+                    "api_key = \"sk-123\"".to_string(),
+                );
+                queries::insert_violation(&conn, &violation).unwrap();
+            }
+            scan_id
+        };
+
+        let csv = export_violations(scan_id, "csv".to_string(), None).await.unwrap();
+        assert_eq!(csv.rows_exported, 2);
+        assert_eq!(csv.columns, ViolationColumn::DEFAULT.to_vec());
+        assert!(csv.path.ends_with(&format!("exports/scan-{}-violations.csv", scan_id)));
+        let contents = std::fs::read_to_string(&csv.path).unwrap();
+        assert_eq!(csv.sha256, hex::encode(Sha256::digest(contents.as_bytes())));
+        assert!(contents.starts_with("ID,Control,Control Name,Severity,Status,File,Line,Description,Detected At\n"));
+
+        let columns = Some(vec!["severity".to_string(), "file_path".to_string(), "line_number".to_string()]);
+        let custom = export_violations(scan_id, "csv".to_string(), columns).await.unwrap();
+        let contents = std::fs::read_to_string(&custom.path).unwrap();
+        assert_eq!(contents, "Severity,File,Line\ncritical,app.py,4\nlow,debug.py,3\n");
+
+        let xlsx = export_violations(scan_id, "xlsx".to_string(), None).await.unwrap();
+        assert!(xlsx.path.ends_with(".xlsx"));
+        assert!(std::fs::read(&xlsx.path).unwrap().starts_with(b"PK"));
+
+        let conn = db::get_connection();
+        let events = queries::select_audit_events_by_project(&conn, project_id).unwrap();
+        assert_eq!(events.iter().filter(|e| e.event_type == "violations_exported").count(), 3);
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_export_violations_rejects_bad_input() {
+        let _guard = TestDbGuard::new();
+        let (_dir, project_id) = create_test_project();
+        let scan_id = {
+            let conn = db::get_connection();
+            queries::insert_scan(&conn, project_id, "regex_only").unwrap()
+        };
+
+        let error = export_violations(scan_id, "json".to_string(), None).await.unwrap_err();
+        assert!(error.contains("Invalid export format"));
+
+        let error = export_violations(scan_id, "csv".to_string(), Some(vec!["owner".to_string()]))
+            .await
+            .unwrap_err();
+        assert!(error.contains("Unknown export column 'owner'"));
+
+        let error = export_violations(999, "csv".to_string(), None).await.unwrap_err();
+        assert!(error.contains("Scan not found"));
+    }
+}
@@ -0,0 +1,237 @@
+//! Issue tracker commands
+//!
+//! Links a project to a Jira project and files remediation issues for its
+//! violations. The Jira API token is stored in the OS keychain; the issue
+//! key is stored on the violation.
+
+use crate::db::{self, queries};
+use crate::integrations::jira::{build_issue, JiraClient, JiraIssue};
+use crate::models::JiraConfig;
+use crate::security::credentials;
+use crate::utils::create_audit_event;
+
+/// Link a project to a Jira project
+///
+/// Replaces any earlier link. The API token can be left out when relinking
+/// to keep the stored one.
+///
+/// # Arguments
+/// * `project_id` - Project whose violations are filed
+/// * `config` - Jira site URL, account email, API token, project key, issue
+///   type (default "Task") and extra labels
+///
+/// Returns: The saved config (without the token)
+#[tauri::command]
+pub async fn link_jira(project_id: i64, config: JiraConfig) -> Result<JiraConfig, String> {
+    eprintln!("[ryn] link_jira called: project_id={}, project_key={}", project_id, config.project_key);
+
+    let config = JiraConfig { project_id, ..config };
+    config.validate()?;
+
+    let conn = db::get_connection();
+    queries::select_project(&conn, project_id)
+        .map_err(|e| format!("Failed to fetch project: {}", e))?
+        .ok_or_else(|| format!("Project not found: {}", project_id))?;
+
+    let account = JiraConfig::token_account(project_id);
+    match config.api_token.as_deref().map(str::trim).filter(|token| !token.is_empty()) {
+        Some(token) => credentials::store_secret(&account, token)
+            .map_err(|e| format!("Failed to store Jira API token: {}", e))?,
+        None => {
+            let stored = credentials::read_secret(&account)
+                .map_err(|e| format!("Failed to read Jira API token: {}", e))?;
+            if stored.is_none() {
+                return Err("A Jira API token is required".to_string());
+            }
+        }
+    }
+
+    queries::upsert_jira_config(&conn, &config)
+        .map_err(|e| format!("Failed to save Jira config: {}", e))?;
+
+    if let Ok(event) = create_audit_event(
+        &conn,
+        "settings_updated",
+        Some(project_id),
+        None,
+        None,
+        &format!("Linked Jira project {} at {}", config.project_key, config.site_url()),
+    ) {
+        let _ = queries::insert_audit_event(&conn, &event);
+    }
+
+    queries::select_jira_config(&conn, project_id)
+        .map_err(|e| format!("Failed to fetch Jira config: {}", e))?
+        .ok_or_else(|| "Jira config not found after saving".to_string())
+}
+
+/// File a Jira issue for a violation
+///
+/// The issue holds the code snippet, the control's description and
+/// requirement, and the latest generated fix if there is one. Its key is
+/// stored on the violation; a violation gets at most one issue.
+///
+/// # Arguments
+/// * `violation_id` - Violation to file
+///
+/// Returns: Key and link of the new issue
+#[tauri::command]
+pub async fn create_jira_issue(violation_id: i64) -> Result<JiraIssue, String> {
+    eprintln!("[ryn] create_jira_issue called: violation_id={}", violation_id);
+
+    let (project_id, config, issue) = {
+        let conn = db::get_connection();
+        let violation = queries::select_violation(&conn, violation_id)
+            .map_err(|e| format!("Failed to fetch violation: {}", e))?
+            .ok_or_else(|| format!("Violation not found: {}", violation_id))?;
+        if let Some(key) = &violation.jira_issue_key {
+            return Err(format!("Violation {} already has Jira issue {}", violation_id, key));
+        }
+
+        let scan = queries::select_scan(&conn, violation.scan_id)
+            .map_err(|e| format!("Failed to fetch scan: {}", e))?
+            .ok_or_else(|| "Scan not found".to_string())?;
+        let config = queries::select_jira_config(&conn, scan.project_id)
+            .map_err(|e| format!("Failed to fetch Jira config: {}", e))?
+            .ok_or_else(|| format!("Project {} is not linked to Jira", scan.project_id))?;
+
+        let control = queries::select_control(&conn, &violation.control_id)
+            .map_err(|e| format!("Failed to fetch control: {}", e))?;
+        let fix = queries::select_fix_for_violation(&conn, violation_id)
+            .map_err(|e| format!("Failed to fetch fix: {}", e))?;
+
+        let issue = build_issue(&config, &violation, control.as_ref(), fix.as_ref());
+        (scan.project_id, config, issue)
+    }; // Connection dropped before the Jira request
+
+    let api_token = credentials::read_secret(&JiraConfig::token_account(project_id))
+        .map_err(|e| format!("Failed to read Jira API token: {}", e))?
+        .ok_or_else(|| "No Jira API token stored; link Jira again".to_string())?;
+
+    let client = JiraClient::new().map_err(|e| format!("Failed to create Jira client: {}", e))?;
+    let created = client
+        .create_issue(&config, &api_token, &issue)
+        .await
+        .map_err(|e| format!("Failed to create Jira issue: {}", e))?;
+
+    let conn = db::get_connection();
+    queries::update_violation_jira_issue_key(&conn, violation_id, &created.key)
+        .map_err(|e| format!("Created Jira issue {} but failed to record it: {}", created.key, e))?;
+
+    if let Ok(event) = create_audit_event(
+        &conn,
+        "jira_issue_created",
+        Some(project_id),
+        Some(violation_id),
+        None,
+        &format!("Filed Jira issue {} for violation {}", created.key, violation_id),
+    ) {
+        let _ = queries::insert_audit_event(&conn, &event);
+    }
+
+    eprintln!("[ryn] create_jira_issue success: {}", created.key);
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_helpers::TestDbGuard;
+    use crate::integrations::jira::mock_jira;
+    use crate::models::{Severity, Violation};
+
+    fn jira_config(base_url: &str, api_token: Option<&str>) -> JiraConfig {
+        JiraConfig {
+            project_id: 0,
+            base_url: base_url.to_string(),
+            email: "security@acme.com".to_string(),
+            project_key: "SEC".to_string(),
+            issue_type: "Task".to_string(),
+            labels: vec![],
+            api_token: api_token.map(str::to_string),
+            updated_at: None,
+        }
+    }
+
+    /// Project with one violation; returns (project_id, violation_id)
+    fn create_violation() -> (i64, i64) {
+        let conn = db::get_connection();
+        let project_id = queries::insert_project(&conn, "jira-project", "/tmp/jira-project", None).unwrap();
+        let scan_id = queries::insert_scan(&conn, project_id, "regex_only").unwrap();
+        let violation = Violation::new(
+            scan_id,
+            "CC6.7".to_string(),
+            Severity::High,
+            "Hardcoded API key".to_string(),
+            "app.py".to_string(),
+            4,
+            "KEY = 'sk_live_abc'".to_string(),
+        );
+        let violation_id = queries::insert_violation(&conn, &violation).unwrap();
+        (project_id, violation_id)
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_create_jira_issue_stores_key() {
+        let _guard = TestDbGuard::new();
+        let (project_id, violation_id) = create_violation();
+        let (url, captured) = mock_jira(201, r#"{"id": "10001", "key": "SEC-7", "self": "x"}"#).await;
+
+        let linked = link_jira(project_id, jira_config(&url, Some("api-token"))).await.unwrap();
+        assert_eq!(linked.project_id, project_id);
+        assert_eq!(linked.api_token, None);
+
+        let issue = create_jira_issue(violation_id).await.unwrap();
+        assert_eq!(issue.key, "SEC-7");
+        assert_eq!(issue.url, format!("{}/browse/SEC-7", url));
+        let request = captured.lock().unwrap().clone();
+        assert!(request.contains("KEY = 'sk_live_abc'"));
+
+        let conn = db::get_connection();
+        let violation = queries::select_violation(&conn, violation_id).unwrap().unwrap();
+        assert_eq!(violation.jira_issue_key.as_deref(), Some("SEC-7"));
+        let events = queries::select_audit_events_by_project(&conn, project_id).unwrap();
+        assert!(events.iter().any(|e| e.event_type == "jira_issue_created"));
+        drop(conn);
+
+        // One issue per violation
+        let error = create_jira_issue(violation_id).await.unwrap_err();
+        assert!(error.contains("already has Jira issue SEC-7"));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_link_jira_requires_token_once() {
+        let _guard = TestDbGuard::new();
+        let (project_id, _) = create_violation();
+        credentials::delete_secret(&JiraConfig::token_account(project_id)).unwrap();
+
+        let error = link_jira(project_id, jira_config("https://acme.atlassian.net", None)).await.unwrap_err();
+        assert!(error.contains("API token is required"));
+
+        link_jira(project_id, jira_config("https://acme.atlassian.net", Some("api-token"))).await.unwrap();
+        // Relinking keeps the stored token
+        let mut relinked = jira_config("https://acme.atlassian.net", None);
+        relinked.project_key = "OPS".to_string();
+        assert_eq!(link_jira(project_id, relinked).await.unwrap().project_key, "OPS");
+
+        let error = link_jira(999, jira_config("https://acme.atlassian.net", Some("t"))).await.unwrap_err();
+        assert!(error.contains("Project not found"));
+        let error = link_jira(project_id, jira_config("acme", Some("t"))).await.unwrap_err();
+        assert!(error.contains("Invalid Jira URL"));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_create_jira_issue_requires_link() {
+        let _guard = TestDbGuard::new();
+        let (project_id, violation_id) = create_violation();
+
+        let error = create_jira_issue(violation_id).await.unwrap_err();
+        assert!(error.contains(&format!("Project {} is not linked to Jira", project_id)));
+
+        let error = create_jira_issue(999).await.unwrap_err();
+        assert!(error.contains("Violation not found"));
+    }
+}
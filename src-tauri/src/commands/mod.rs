@@ -2,34 +2,122 @@
 //!
 //! This module contains all 14 Tauri commands for frontend-backend communication:
 //!
-//! Project Commands (3):
+//! Project Commands (11):
 //! - select_project_folder: Open file dialog to select project directory
 //! - create_project: Create a new project in the database
 //! - get_projects: Retrieve all projects
+//! - set_project_scan_excludes: Set glob patterns excluded from scanning
+//! - get_project_scan_excludes: Get a project's scan exclusion patterns
+//! - set_project_cost_limit: Set a project's cost limit, overriding the global one
+//! - remove_project_cost_limit: Fall back to the global cost limit
+//! - set_project_pull_request_config: Open GitHub pull requests for applied fixes
+//! - get_project_pull_request_config: Get a project's pull request settings
+//! - set_project_settings: Set minimum severity, LLM confidence and disabled controls
+//! - get_project_settings: Get a project's reporting thresholds
+//! - add_project_root: Scan another directory (e.g. a frontend repo) with a project
+//! - remove_project_root: Stop scanning one of a project's extra roots
+//! - get_project_roots: Get a project's extra roots
 //!
-//! Scan Commands (4):
+//! Scan Commands (5):
 //! - detect_framework: Identify project framework
 //! - scan_project: Run all rule engines to find violations
+//! - scan_project_with_baseline: Scan and diff violations against an earlier scan
 //! - get_scan_progress: Get status and statistics of a scan
 //! - get_scans: List all scans for a project
+//! - get_scan_detail_stats: Per-control severity counts and recent trends for a scan
+//! - explain_file_selection: Show how smart mode scored a file
+//! - export_scan_profile: Export a profiled scan's Chrome trace
+//! - get_watcher_stats: Event counts for a project's file watcher
+//! - get_effective_ignores: Ignore patterns and the paths they skip
 //!
-//! Violation Commands (3):
+//! Violation Commands (10):
 //! - get_violations: Query violations with optional filters
 //! - get_violation: Get single violation with full details
 //! - dismiss_violation: Mark violation as dismissed
+//! - report_false_positive: Dismiss as false positive and record pattern feedback
+//! - update_violations_status: Change the status of several violations with a reason
+//! - reopen_violations: Reopen a scan's dismissed and fixed violations
+//! - get_violation_status_history: List a violation's status transitions
+//! - get_violations_by_category: Group a scan's violations by control category
+//! - get_violations_by_package: Count a scan's violations per monorepo package
+//! - search_violations: Text search over a scan's violations with highlighted matches
+//! - get_project_violations: A project's violations across completed scans, deduplicated
+//! - get_violation_history: A project's findings by fingerprint with their lifecycle
+//! - create_baseline: Accept a scan's violations in .ryn-baseline.json so later scans suppress them
+//! - list_suppressed: List the violations a project's baseline file suppresses
 //!
-//! Fix Commands (2):
+//! Fix Commands (6):
 //! - generate_fix: Call Claude API to generate a fix
-//! - apply_fix: Apply fix to file and commit to git
+//! - generate_fixes_batch: Generate fixes for every open violation in a scan
+//! - preview_fix: Unified diff of what apply_fix would change, without writing the file
+//! - apply_fix: Apply fix to file (review fixes need confirmation, manual fixes are rejected)
+//! - get_fix_history: All fix attempts for a violation
+//! - escalate_trust_level: Raise a fix's trust level to review or manual
+//! - get_fix_branches: List a project's ryn/fix/... git branches
+//! - get_fix_commit_info: Details of the git commit an applied fix was committed in
 //!
 //! Audit Commands (1):
 //! - get_audit_events: Retrieve audit trail with filters
 //!
-//! Settings Commands (4):
+//! Settings Commands (11):
 //! - get_settings: Retrieve all settings
 //! - update_settings: Create or update a setting
 //! - clear_database: Clear all scan history (destructive)
 //! - export_data: Export all data to JSON
+//! - create_profile: Save a named settings preset
+//! - list_profiles: List built-in and saved settings profiles
+//! - apply_profile: Write a profile's settings in one transaction
+//! - export_profile: Write a profile to a JSON file
+//! - import_profile: Load a profile from a JSON file
+//! - export_audit_events_csv: Stream audit events in a date range to CSV with a manifest
+//! - optimize_database: Run PRAGMA optimize, ANALYZE and VACUUM
+//!
+//! Onboarding Commands (5):
+//! - start_onboarding_check: Onboarding status and the wizard steps still to do
+//! - validate_api_key_connection: Send the Grok API a test prompt to verify the API key
+//! - get_onboarding_progress: Per-step onboarding completion with timestamps
+//! - set_api_key: Store an X.AI or GitHub key in the OS keychain
+//! - test_api_key: Check a stored key with a live request to its provider
+//!
+//! Analytics Commands (8):
+//! - get_scan_costs: Retrieve LLM cost records for a time range
+//! - get_scan_cost: Retrieve the cost record for a single scan
+//! - get_fix_quality_report: Verification outcomes for applied fixes
+//! - get_false_positive_report: False positive counts and patterns needing tuning
+//! - estimate_scan_cost: Forecast LLM usage and cost of scanning a project
+//! - get_compliance_trend: Per-control compliance scores over recent scans
+//! - get_spending_report: LLM spend totals, daily spend and monthly budget status
+//! - get_category_summary: Violation counts and compliance score per control category
+//!
+//! Namespace Commands (4):
+//! - create_namespace: Create an isolated namespace for a team
+//! - list_namespaces: List all namespaces
+//! - switch_namespace: Change the namespace all queries are scoped to
+//! - get_current_namespace: Get the active namespace
+//!
+//! Notification Commands (5):
+//! - create_slack_config: Register a Slack webhook for scan alerts
+//! - get_slack_configs: List Slack webhook configs
+//! - delete_slack_config: Remove a Slack webhook config
+//! - test_slack_notification: Send a test message through a config
+//! - test_notification: Send a test message to the webhooks set in settings
+//!
+//! Schedule Commands (2):
+//! - schedule_scan: Scan a project on a cron schedule while the app is running
+//! - list_schedules: List scan schedules
+//!
+//! Export Commands (3):
+//! - import_sarif: Import violations from another tool's SARIF 2.1.0 results
+//! - generate_report: Render a scan's SOC 2 evidence report as HTML or PDF
+//! - export_violations: Export a scan's violations as CSV or Excel
+//!
+//! Integration Commands (2):
+//! - link_jira: Link a project to a Jira project for remediation issues
+//! - create_jira_issue: File a Jira issue for a violation
+//!
+//! Deep Links (not IPC commands):
+//! - handle_deep_link: Parse ryn://violation/{id} and ryn://scan/{id} URLs and
+//!   emit deep-link-navigate events to the frontend
 
 pub mod project;
 pub mod scan;
@@ -39,13 +127,27 @@ pub mod audit;
 pub mod settings;
 pub mod analytics;
 pub mod logger;
+pub mod namespace;
+pub mod notification;
+pub mod deep_link;
+pub mod export;
+pub mod onboarding;
+pub mod schedule;
+pub mod integration;
 
 // Re-export all commands
-pub use project::{select_project_folder, create_project, get_projects};
-pub use scan::{detect_framework, scan_project, get_scan_progress, get_scans, watch_project, stop_watching};
-pub use violation::{get_violations, get_violation, dismiss_violation};
-pub use fix::{generate_fix, apply_fix};
+pub use project::{select_project_folder, create_project, get_projects, set_project_scan_excludes, get_project_scan_excludes, set_project_cost_limit, remove_project_cost_limit, set_project_pull_request_config, get_project_pull_request_config, set_project_settings, get_project_settings, add_project_root, remove_project_root, get_project_roots};
+pub use scan::{detect_framework, scan_project, scan_project_with_baseline, get_scan_progress, get_scan_detail_stats, get_scans, watch_project, stop_watching, explain_file_selection, export_scan_profile, get_watcher_stats, get_effective_ignores};
+pub use violation::{get_violations, get_violation, dismiss_violation, report_false_positive, update_violations_status, reopen_violations, get_violation_status_history, get_violations_by_category, get_violations_by_package, search_violations, get_project_violations, get_violation_history, create_baseline, list_suppressed};
+pub use fix::{generate_fix, generate_fixes_batch, preview_fix, apply_fix, get_fix_history, escalate_trust_level, get_fix_branches, get_fix_commit_info, verify_fix_resolved};
 pub use audit::get_audit_events;
-pub use settings::{get_settings, update_settings, clear_database, export_data};
-pub use analytics::get_scan_costs;
+pub use settings::{get_settings, update_settings, clear_database, export_data, create_profile, list_profiles, apply_profile, export_profile, import_profile, export_audit_events_csv, optimize_database};
+pub use analytics::{get_scan_costs, get_fix_quality_report, get_false_positive_report, estimate_scan_cost, get_compliance_trend, get_spending_report, get_category_summary};
 pub use logger::log_frontend_message;
+pub use namespace::{create_namespace, list_namespaces, switch_namespace, get_current_namespace};
+pub use notification::{create_slack_config, get_slack_configs, delete_slack_config, test_slack_notification, test_notification};
+pub use export::{import_sarif, generate_report, export_violations, ImportResult, GeneratedReport, ExportedViolations};
+pub use onboarding::{start_onboarding_check, validate_api_key_connection, get_onboarding_progress, set_api_key, test_api_key};
+pub use schedule::{schedule_scan, list_schedules};
+pub use integration::{link_jira, create_jira_issue};
+pub use deep_link::{handle_deep_link, parse_deep_link, DeepLinkTarget};
@@ -0,0 +1,280 @@
+//! Namespace commands
+//!
+//! Namespaces isolate projects (and everything hanging off them) between teams
+//! sharing one Ryn installation. The active namespace is the `current_namespace`
+//! setting; all project, scan, violation, fix and audit queries are scoped to it.
+
+use crate::db::{self, queries};
+use crate::models::Namespace;
+use crate::utils::create_audit_event;
+
+/// Create a new namespace
+///
+/// # Arguments
+/// * `name` - Namespace name, limited to letters, digits, '-' and '_'
+///
+/// Returns: Success or error if the name is invalid or already taken
+#[tauri::command]
+pub async fn create_namespace(name: String) -> Result<(), String> {
+    eprintln!("[ryn] create_namespace called: name={}", name);
+
+    if !Namespace::is_valid_name(&name) {
+        let err_msg = format!("Invalid namespace name: {}", name);
+        eprintln!("[ryn] create_namespace validation failed: {}", err_msg);
+        return Err(err_msg);
+    }
+
+    let conn = db::get_connection();
+
+    if queries::select_namespace(&conn, &name)
+        .map_err(|e| format!("Failed to check namespace: {}", e))?
+        .is_some()
+    {
+        return Err(format!("Namespace already exists: {}", name));
+    }
+
+    queries::insert_namespace(&conn, &name)
+        .map_err(|e| format!("Failed to create namespace: {}", e))?;
+
+    if let Ok(event) = create_audit_event(
+        &conn,
+        "namespace_created",
+        None,
+        None,
+        None,
+        &format!("Created namespace: {}", name),
+    ) {
+        let _ = queries::insert_audit_event(&conn, &event);
+    }
+
+    eprintln!("[ryn] create_namespace success: name={}", name);
+    Ok(())
+}
+
+/// List all namespaces
+///
+/// Returns: All namespaces sorted by name
+#[tauri::command]
+pub async fn list_namespaces() -> Result<Vec<Namespace>, String> {
+    let conn = db::get_connection();
+
+    queries::select_namespaces(&conn)
+        .map_err(|e| format!("Failed to fetch namespaces: {}", e))
+}
+
+/// Switch the active namespace
+///
+/// # Arguments
+/// * `name` - Existing namespace to make current
+///
+/// Returns: Success or error if the namespace does not exist
+#[tauri::command]
+pub async fn switch_namespace(name: String) -> Result<(), String> {
+    eprintln!("[ryn] switch_namespace called: name={}", name);
+
+    let conn = db::get_connection();
+
+    if queries::select_namespace(&conn, &name)
+        .map_err(|e| format!("Failed to check namespace: {}", e))?
+        .is_none()
+    {
+        return Err(format!("Namespace not found: {}", name));
+    }
+
+    queries::insert_or_update_setting(&conn, "current_namespace", &name)
+        .map_err(|e| format!("Failed to switch namespace: {}", e))?;
+
+    if let Ok(event) = create_audit_event(
+        &conn,
+        "namespace_switched",
+        None,
+        None,
+        None,
+        &format!("Switched to namespace: {}", name),
+    ) {
+        let _ = queries::insert_audit_event(&conn, &event);
+    }
+
+    eprintln!("[ryn] switch_namespace success: name={}", name);
+    Ok(())
+}
+
+/// Get the active namespace
+///
+/// Returns: Name of the namespace all queries are currently scoped to
+#[tauri::command]
+pub async fn get_current_namespace() -> Result<String, String> {
+    let conn = db::get_connection();
+
+    queries::current_namespace(&conn)
+        .map_err(|e| format!("Failed to read current namespace: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::project::create_project;
+    use crate::db::test_helpers::TestDbGuard;
+    use crate::models::{FalsePositive, ScanCost, Severity, Violation};
+
+    /// Create a project with one scan and one violation in the current namespace
+    fn seed_project(path: &str) -> (i64, i64, i64) {
+        let conn = db::get_connection();
+        let project_id = queries::insert_project(&conn, "app", path, None).unwrap();
+        let scan_id = queries::insert_scan(&conn, project_id, "regex_only").unwrap();
+        let violation = Violation::new(
+            scan_id,
+            "CC6.7".to_string(),
+            Severity::Critical,
+            "Hardcoded secret".to_string(),
+            "config.py".to_string(),
+            1,
+            "API_KEY = \"abc\"".to_string(),
+        );
+        let violation_id = queries::insert_violation(&conn, &violation).unwrap();
+        (project_id, scan_id, violation_id)
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_create_and_list_namespaces() {
+        let _guard = TestDbGuard::new();
+
+        create_namespace("team-a".to_string()).await.unwrap();
+
+        let names: Vec<String> = list_namespaces()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|n| n.name)
+            .collect();
+        assert_eq!(names, vec!["default".to_string(), "team-a".to_string()]);
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_create_namespace_rejects_duplicates_and_invalid_names() {
+        let _guard = TestDbGuard::new();
+
+        assert!(create_namespace("default".to_string()).await.is_err());
+        assert!(create_namespace("bad name".to_string()).await.is_err());
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_switch_namespace_requires_existing() {
+        let _guard = TestDbGuard::new();
+
+        assert!(switch_namespace("missing".to_string()).await.is_err());
+        assert_eq!(get_current_namespace().await.unwrap(), "default");
+
+        create_namespace("team-a".to_string()).await.unwrap();
+        switch_namespace("team-a".to_string()).await.unwrap();
+        assert_eq!(get_current_namespace().await.unwrap(), "team-a");
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_namespace_cannot_read_other_namespace_data() {
+        let _guard = TestDbGuard::new();
+
+        create_namespace("team-a".to_string()).await.unwrap();
+        create_namespace("team-b".to_string()).await.unwrap();
+
+        switch_namespace("team-a".to_string()).await.unwrap();
+        let (project_a, scan_a, violation_a) = seed_project("/tmp/ryn-ns-a");
+        {
+            let conn = db::get_connection();
+            queries::insert_scan_cost(&conn, &ScanCost::new(scan_a, 1, 1_000, 200, 0, 0)).unwrap();
+            let violation = queries::select_violation(&conn, violation_a).unwrap().unwrap();
+            let report = FalsePositive::from_violation(project_a, &violation, "test key".to_string());
+            queries::insert_false_positive(&conn, &report).unwrap();
+        }
+
+        switch_namespace("team-b".to_string()).await.unwrap();
+        let (project_b, _scan_b, _violation_b) = seed_project("/tmp/ryn-ns-b");
+
+        // Team B knows team A's IDs but can't read anything behind them
+        {
+            let conn = db::get_connection();
+            assert!(queries::select_project(&conn, project_a).unwrap().is_none());
+            assert!(queries::select_scans(&conn, project_a).unwrap().is_empty());
+            assert!(queries::select_scan(&conn, scan_a).unwrap().is_none());
+            assert!(queries::select_violations(&conn, scan_a).unwrap().is_empty());
+            assert!(queries::select_violation(&conn, violation_a).unwrap().is_none());
+            assert!(queries::select_all_violations(&conn).unwrap().iter().all(|v| v.scan_id != scan_a));
+            assert!(queries::select_all_scan_costs(&conn).unwrap().is_empty());
+            assert!(queries::select_scan_costs_since(&conn, "1970-01-01T00:00:00Z").unwrap().is_empty());
+            assert!(queries::select_false_positives_by_control(&conn, "CC6.7").unwrap().is_empty());
+
+            let projects = queries::select_projects(&conn).unwrap();
+            assert_eq!(projects.len(), 1);
+            assert_eq!(projects[0].id, project_b);
+            assert_eq!(projects[0].namespace, "team-b");
+        }
+
+        // Switching back restores access
+        switch_namespace("team-a".to_string()).await.unwrap();
+        let conn = db::get_connection();
+        assert_eq!(queries::select_violations(&conn, scan_a).unwrap().len(), 1);
+        assert_eq!(queries::select_all_scan_costs(&conn).unwrap().len(), 1);
+        assert_eq!(queries::select_false_positives_by_control(&conn, "CC6.7").unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_same_path_can_be_added_in_each_namespace() {
+        let _guard = TestDbGuard::new();
+        let checkout = tempfile::TempDir::new().unwrap();
+        let path = checkout.path().to_string_lossy().to_string();
+
+        create_namespace("team-a".to_string()).await.unwrap();
+        create_namespace("team-b".to_string()).await.unwrap();
+
+        switch_namespace("team-a".to_string()).await.unwrap();
+        let project_a = create_project(path.clone(), None, None).await.unwrap();
+
+        switch_namespace("team-b".to_string()).await.unwrap();
+        let project_b = create_project(path.clone(), None, None).await.unwrap();
+        assert_ne!(project_a.id, project_b.id);
+        assert_eq!(project_b.namespace, "team-b");
+
+        // Within a namespace the path still maps to one project
+        assert_eq!(create_project(path.clone(), None, None).await.unwrap().id, project_b.id);
+        let conn = db::get_connection();
+        assert!(queries::insert_project(&conn, "dup", &path, None).is_err());
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_namespace_cannot_modify_other_namespace_data() {
+        let _guard = TestDbGuard::new();
+
+        create_namespace("team-a".to_string()).await.unwrap();
+        create_namespace("team-b".to_string()).await.unwrap();
+
+        switch_namespace("team-a".to_string()).await.unwrap();
+        let (project_a, scan_a, violation_a) = seed_project("/tmp/ryn-ns-a");
+        {
+            let conn = db::get_connection();
+            queries::update_violation_status(&conn, violation_a, "dismissed", Some("false positive")).unwrap();
+        }
+
+        // Team B can't change or delete anything behind team A's IDs
+        switch_namespace("team-b".to_string()).await.unwrap();
+        {
+            let conn = db::get_connection();
+            assert!(queries::update_violation_status(&conn, violation_a, "open", None).is_err());
+            assert!(queries::update_violations_status(&conn, &[violation_a], "accepted_risk", "n/a").is_err());
+            assert_eq!(queries::reopen_violations(&conn, scan_a).unwrap(), 0);
+            assert!(queries::delete_project(&conn, project_a).is_err());
+        }
+
+        switch_namespace("team-a".to_string()).await.unwrap();
+        let conn = db::get_connection();
+        assert!(queries::select_project(&conn, project_a).unwrap().is_some());
+        let violation = queries::select_violation(&conn, violation_a).unwrap().unwrap();
+        assert_eq!(violation.status, "dismissed");
+        assert_eq!(queries::select_violation_status_history(&conn, violation_a).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,242 @@
+//! Notification commands
+//!
+//! Manages Slack webhook configs used to alert on critical/high violations
+//! after a scan completes, and lets users send a test message to them or to
+//! the Slack and generic webhooks set in settings.
+
+use serde_json::json;
+
+use crate::db::{self, queries};
+use crate::models::{Severity, SlackConfig};
+use crate::notifications::{webhook, Notification, NotificationEndpoints, SlackNotifier};
+use crate::utils::create_audit_event;
+
+/// Create a Slack notification config
+///
+/// # Arguments
+/// * `webhook_url` - Slack incoming webhook URL
+/// * `channel` - Optional channel override
+/// * `notify_on_severities` - Severities that trigger an alert (e.g. ["critical", "high"])
+/// * `project_id` - Restrict to one project, or None for all projects
+/// * `signing_secret` - Optional secret used to HMAC-sign requests
+///
+/// Returns: ID of the created config
+#[tauri::command]
+pub async fn create_slack_config(
+    webhook_url: String,
+    channel: Option<String>,
+    notify_on_severities: Vec<String>,
+    project_id: Option<i64>,
+    signing_secret: Option<String>,
+) -> Result<i64, String> {
+    eprintln!("[ryn] create_slack_config called: project_id={:?}", project_id);
+
+    if !webhook_url.starts_with("https://") && !webhook_url.starts_with("http://") {
+        return Err(format!("Invalid webhook URL: {}", webhook_url));
+    }
+
+    if let Some(invalid) = notify_on_severities
+        .iter()
+        .find(|s| Severity::from_str(s).is_none())
+    {
+        return Err(format!("Invalid severity: {}", invalid));
+    }
+
+    let mut config = SlackConfig::new(webhook_url, notify_on_severities);
+    config.channel = channel;
+    config.project_id = project_id;
+    config.signing_secret = signing_secret;
+
+    let conn = db::get_connection();
+
+    if let Some(pid) = project_id {
+        queries::select_project(&conn, pid)
+            .map_err(|e| format!("Failed to fetch project: {}", e))?
+            .ok_or_else(|| format!("Project not found: {}", pid))?;
+    }
+
+    let config_id = queries::insert_slack_config(&conn, &config)
+        .map_err(|e| format!("Failed to create Slack config: {}", e))?;
+
+    if let Ok(event) = create_audit_event(
+        &conn,
+        "settings_updated",
+        project_id,
+        None,
+        None,
+        &format!("Created Slack notification config {}", config_id),
+    ) {
+        let _ = queries::insert_audit_event(&conn, &event);
+    }
+
+    Ok(config_id)
+}
+
+/// List all Slack notification configs
+///
+/// Returns: Configs ordered by ID (signing secrets are not serialized)
+#[tauri::command]
+pub async fn get_slack_configs() -> Result<Vec<SlackConfig>, String> {
+    let conn = db::get_connection();
+
+    queries::select_slack_configs(&conn)
+        .map_err(|e| format!("Failed to fetch Slack configs: {}", e))
+}
+
+/// Delete a Slack notification config
+///
+/// # Arguments
+/// * `config_id` - ID of the config to remove
+///
+/// Returns: Success or error
+#[tauri::command]
+pub async fn delete_slack_config(config_id: i64) -> Result<(), String> {
+    let conn = db::get_connection();
+
+    queries::delete_slack_config(&conn, config_id)
+        .map_err(|e| format!("Failed to delete Slack config: {}", e))
+}
+
+/// Send a test message through a Slack config
+///
+/// # Arguments
+/// * `config_id` - ID of the config to test
+///
+/// Returns: Success, or the webhook error
+#[tauri::command]
+pub async fn test_slack_notification(config_id: i64) -> Result<(), String> {
+    eprintln!("[ryn] test_slack_notification called: config_id={}", config_id);
+
+    let config = {
+        let conn = db::get_connection();
+        queries::select_slack_config(&conn, config_id)
+            .map_err(|e| format!("Failed to fetch Slack config: {}", e))?
+            .ok_or_else(|| format!("Slack config not found: {}", config_id))?
+    };
+
+    let mut message = json!({
+        "text": "Ryn test notification: this webhook will receive compliance scan alerts.",
+    });
+    if let Some(channel) = &config.channel {
+        message["channel"] = json!(channel);
+    }
+
+    let notifier = SlackNotifier::new()
+        .map_err(|e| format!("Failed to create Slack client: {}", e))?;
+
+    notifier
+        .send(&config, &message)
+        .await
+        .map_err(|e| format!("Failed to send test notification: {}", e))?;
+
+    eprintln!("[ryn] test_slack_notification success: config_id={}", config_id);
+    Ok(())
+}
+
+/// Send a test message to the webhooks set in settings
+///
+/// Posts to the Slack webhook (`notification_slack_webhook_url`) and the
+/// generic webhook (`notification_webhook_url`), whichever are set.
+///
+/// Returns: Endpoints notified ("slack", "webhook"), or every failure
+#[tauri::command]
+pub async fn test_notification() -> Result<Vec<String>, String> {
+    eprintln!("[ryn] test_notification called");
+
+    let endpoints = {
+        let conn = db::get_connection();
+        NotificationEndpoints::load(&conn)
+    };
+    if endpoints.is_empty() {
+        return Err(format!(
+            "No notification endpoints configured; set {} or {}",
+            webhook::SLACK_WEBHOOK_SETTING,
+            webhook::WEBHOOK_URL_SETTING
+        ));
+    }
+
+    let sent = webhook::send(&endpoints, &Notification::Test)
+        .await
+        .map_err(|e| format!("Failed to send test notification: {}", e))?;
+
+    eprintln!("[ryn] test_notification success: {:?}", sent);
+    Ok(sent.into_iter().map(str::to_string).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_helpers::TestDbGuard;
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_create_and_list_slack_configs() {
+        let _guard = TestDbGuard::new();
+
+        let id = create_slack_config(
+            "https://hooks.slack.com/services/T000/B000/XXX".to_string(),
+            Some("#alerts".to_string()),
+            vec!["critical".to_string(), "high".to_string()],
+            None,
+            Some("secret".to_string()),
+        )
+        .await
+        .unwrap();
+
+        let configs = get_slack_configs().await.unwrap();
+        assert_eq!(configs.len(), 1);
+        assert_eq!(configs[0].id, id);
+        assert_eq!(configs[0].channel.as_deref(), Some("#alerts"));
+        assert_eq!(configs[0].notify_on_severities, vec!["critical", "high"]);
+        assert_eq!(configs[0].signing_secret.as_deref(), Some("secret"));
+
+        delete_slack_config(id).await.unwrap();
+        assert!(get_slack_configs().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_create_slack_config_rejects_invalid_severity() {
+        let _guard = TestDbGuard::new();
+
+        let result = create_slack_config(
+            "https://hooks.slack.com/services/T000/B000/XXX".to_string(),
+            None,
+            vec!["urgent".to_string()],
+            None,
+            None,
+        )
+        .await;
+        assert!(result.unwrap_err().contains("Invalid severity"));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_slack_notification_missing_config() {
+        let _guard = TestDbGuard::new();
+
+        let result = test_slack_notification(999).await;
+        assert!(result.unwrap_err().contains("not found"));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_notification_requires_endpoint() {
+        let _guard = TestDbGuard::new();
+
+        let error = test_notification().await.unwrap_err();
+        assert!(error.contains(webhook::WEBHOOK_URL_SETTING));
+
+        // Nothing listens on port 9, so the webhook is set but unreachable
+        let conn = db::get_connection();
+        queries::insert_or_update_setting(
+            &conn,
+            webhook::WEBHOOK_URL_SETTING,
+            "http://127.0.0.1:9/hook",
+        )
+        .unwrap();
+        drop(conn);
+        let error = test_notification().await.unwrap_err();
+        assert!(error.contains("Failed to send test notification: webhook"));
+    }
+}
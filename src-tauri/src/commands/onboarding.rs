@@ -0,0 +1,517 @@
+//! Onboarding commands
+//!
+//! Guides first-time users through the setup wizard: configure and validate an
+//! API key, add a first project and run a first scan. Finished steps are
+//! recorded in the settings table (`onboarding_step_api_key`,
+//! `onboarding_step_first_project`, `onboarding_step_first_scan`), so progress
+//! isn't lost if the project or scan is deleted later. The wizard ends with
+//! `settings::complete_onboarding`.
+
+use crate::db::{self, queries};
+use crate::fix_generator::grok_client::{GrokApiError, GrokClient};
+use crate::git::github::GitHubClient;
+use crate::models::{
+    ApiKeyValidation, OnboardingProgress, OnboardingStatus, OnboardingStep, OnboardingStepProgress, ScanStatus,
+};
+use crate::security::credentials::{self, CredentialProvider};
+use crate::utils::create_audit_event;
+use rusqlite::Connection;
+use std::time::Instant;
+
+/// When `step` was recorded as done, or None if it hasn't been
+fn step_completed_at(conn: &Connection, step: OnboardingStep) -> Result<Option<String>, String> {
+    Ok(queries::select_setting(conn, &step.setting_key())
+        .map_err(|e| format!("Failed to fetch onboarding step: {}", e))?
+        .filter(|setting| setting.value == "true")
+        .map(|setting| setting.updated_at))
+}
+
+/// Record `step` as done and log an audit event
+fn record_step(conn: &Connection, step: OnboardingStep) -> Result<(), String> {
+    queries::insert_or_update_setting(conn, &step.setting_key(), "true")
+        .map_err(|e| format!("Failed to record onboarding step: {}", e))?;
+
+    if let Ok(event) = create_audit_event(
+        conn,
+        "settings_updated",
+        None,
+        None,
+        None,
+        &format!("Onboarding step completed: {}", step.as_str()),
+    ) {
+        let _ = queries::insert_audit_event(conn, &event);
+    }
+
+    Ok(())
+}
+
+/// Record the project and scan steps if the database shows they're done
+fn record_detected_steps(conn: &Connection) -> Result<(), String> {
+    let projects = queries::select_projects(conn)
+        .map_err(|e| format!("Failed to fetch projects: {}", e))?;
+
+    if !projects.is_empty() && step_completed_at(conn, OnboardingStep::FirstProject)?.is_none() {
+        record_step(conn, OnboardingStep::FirstProject)?;
+    }
+
+    if step_completed_at(conn, OnboardingStep::FirstScan)?.is_none() {
+        for project in &projects {
+            let scans = queries::select_scans(conn, project.id)
+                .map_err(|e| format!("Failed to fetch scans: {}", e))?;
+            if scans.iter().any(|scan| scan.status == ScanStatus::Completed.as_str()) {
+                record_step(conn, OnboardingStep::FirstScan)?;
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn onboarding_status(conn: &Connection) -> Result<OnboardingStatus, String> {
+    record_detected_steps(conn)?;
+
+    let completed = queries::select_setting(conn, "onboarding_completed")
+        .map_err(|e| format!("Failed to fetch onboarding status: {}", e))?
+        .is_some_and(|setting| setting.value == "true");
+
+    let mut steps_remaining = Vec::new();
+    for step in OnboardingStep::ALL {
+        if step_completed_at(conn, step)?.is_none() {
+            steps_remaining.push(step);
+        }
+    }
+
+    Ok(OnboardingStatus {
+        completed,
+        api_key_set: credentials::has_api_key(CredentialProvider::Xai),
+        first_project_created: !steps_remaining.contains(&OnboardingStep::FirstProject),
+        first_scan_completed: !steps_remaining.contains(&OnboardingStep::FirstScan),
+        steps_remaining,
+    })
+}
+
+/// Check where the user is in onboarding
+///
+/// Steps the database shows are done (a project exists, a scan completed)
+/// are recorded as a side effect.
+///
+/// Returns: Onboarding status with the steps still to do
+#[tauri::command]
+pub async fn start_onboarding_check() -> Result<OnboardingStatus, String> {
+    eprintln!("[ryn] start_onboarding_check called");
+
+    let conn = db::get_connection();
+    onboarding_status(&conn)
+}
+
+/// Per-step onboarding progress with completion times
+///
+/// Returns: Every step in wizard order, and how many are done
+#[tauri::command]
+pub async fn get_onboarding_progress() -> Result<OnboardingProgress, String> {
+    let conn = db::get_connection();
+    record_detected_steps(&conn)?;
+
+    let steps = OnboardingStep::ALL
+        .iter()
+        .map(|step| {
+            let completed_at = step_completed_at(&conn, *step)?;
+            Ok(OnboardingStepProgress {
+                step: *step,
+                completed: completed_at.is_some(),
+                completed_at,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    Ok(OnboardingProgress {
+        completed_steps: steps.iter().filter(|s| s.completed).count(),
+        total_steps: steps.len(),
+        steps,
+    })
+}
+
+/// Check that XAI_API_KEY works by sending the Grok API a test prompt
+///
+/// Unlike the format check done when a client is created, this confirms the
+/// API accepts the key. A successful check completes the API key step.
+///
+/// Returns: Whether the key works, with the reason if it doesn't
+#[tauri::command]
+pub async fn validate_api_key_connection() -> Result<ApiKeyValidation, String> {
+    eprintln!("[ryn] validate_api_key_connection called");
+
+    validate_api_key_with(GrokClient::new()).await
+}
+
+async fn validate_api_key_with(client: anyhow::Result<GrokClient>) -> Result<ApiKeyValidation, String> {
+    let client = match client {
+        Ok(client) => client,
+        Err(e) => {
+            return Ok(ApiKeyValidation {
+                valid: false,
+                error: Some(e.to_string()),
+                latency_ms: None,
+            })
+        }
+    };
+
+    let started = Instant::now();
+    let result = client.send_test_prompt().await;
+    let latency_ms = Some(started.elapsed().as_millis() as u64);
+
+    let validation = match result {
+        Ok(()) => ApiKeyValidation { valid: true, error: None, latency_ms },
+        Err(e) => match e.downcast_ref::<GrokApiError>() {
+            Some(GrokApiError::ApiError(status @ (401 | 403), _)) => ApiKeyValidation {
+                valid: false,
+                error: Some(format!("API key was rejected ({})", status)),
+                latency_ms,
+            },
+            Some(api_error @ (GrokApiError::ApiError(..) | GrokApiError::RateLimited { .. })) => ApiKeyValidation {
+                valid: false,
+                error: Some(api_error.to_string()),
+                latency_ms,
+            },
+            // The key was accepted even if the reply couldn't be read
+            Some(GrokApiError::ParseError(_)) => ApiKeyValidation { valid: true, error: None, latency_ms },
+            Some(GrokApiError::Timeout) | None => ApiKeyValidation {
+                valid: false,
+                error: Some(format!("Could not reach the Grok API: {:#}", e)),
+                latency_ms: None,
+            },
+        },
+    };
+
+    if validation.valid {
+        let conn = db::get_connection();
+        record_step(&conn, OnboardingStep::ApiKey)?;
+    }
+
+    Ok(validation)
+}
+
+fn parse_provider(provider: &str) -> Result<CredentialProvider, String> {
+    CredentialProvider::from_str(provider)
+        .ok_or_else(|| format!("Unknown API key provider '{}': expected 'xai' or 'github'", provider))
+}
+
+/// Store an API key in the OS keychain
+///
+/// The key is checked for format but not sent anywhere; use `test_api_key`
+/// to try it. A stored key takes precedence over the environment variable.
+///
+/// # Arguments
+/// * `provider` - "xai" (or "grok") or "github"
+/// * `key` - The API key or token
+///
+/// Returns: Ok, or error if the provider is unknown, the key malformed or the keychain unavailable
+#[tauri::command]
+pub async fn set_api_key(provider: String, key: String) -> Result<(), String> {
+    eprintln!("[ryn] set_api_key called: provider={}", provider);
+
+    let provider = parse_provider(&provider)?;
+    credentials::set_api_key(provider, &key).map_err(|e| format!("Failed to store API key: {:#}", e))?;
+
+    let conn = db::get_connection();
+    if let Ok(event) = create_audit_event(
+        &conn,
+        "settings_updated",
+        None,
+        None,
+        None,
+        &format!("API key stored in keychain: {}", provider.as_str()),
+    ) {
+        let _ = queries::insert_audit_event(&conn, &event);
+    }
+
+    Ok(())
+}
+
+/// Check a provider's API key with a live request
+///
+/// Sends Grok a test prompt (like `validate_api_key_connection`) or asks
+/// GitHub who the token belongs to.
+///
+/// # Arguments
+/// * `provider` - "xai" (or "grok") or "github"
+///
+/// Returns: Whether the key works, with the reason if it doesn't
+#[tauri::command]
+pub async fn test_api_key(provider: String) -> Result<ApiKeyValidation, String> {
+    eprintln!("[ryn] test_api_key called: provider={}", provider);
+
+    match parse_provider(&provider)? {
+        CredentialProvider::Xai => validate_api_key_with(GrokClient::new()).await,
+        CredentialProvider::Github => Ok(validate_github_token_with(GitHubClient::from_env()).await),
+    }
+}
+
+async fn validate_github_token_with(client: anyhow::Result<GitHubClient>) -> ApiKeyValidation {
+    let client = match client {
+        Ok(client) => client,
+        Err(e) => return ApiKeyValidation { valid: false, error: Some(e.to_string()), latency_ms: None },
+    };
+
+    let started = Instant::now();
+    match client.authenticated_user().await {
+        Ok(login) => {
+            eprintln!("[ryn] GitHub token belongs to {}", login);
+            ApiKeyValidation { valid: true, error: None, latency_ms: Some(started.elapsed().as_millis() as u64) }
+        }
+        Err(e) => ApiKeyValidation { valid: false, error: Some(format!("{:#}", e)), latency_ms: None },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_helpers::TestDbGuard;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    const TEST_KEY: &str = "xai-1234567890123456789";
+
+    /// Minimal HTTP server answering every request with `status` and `body`;
+    /// returns the API base URL
+    async fn mock_grok_api(status: u16, body: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/v1", listener.local_addr().unwrap());
+
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = match listener.accept().await {
+                    Ok(conn) => conn,
+                    Err(_) => return,
+                };
+
+                let mut raw = Vec::new();
+                let mut buf = [0u8; 4096];
+                loop {
+                    let n = socket.read(&mut buf).await.unwrap();
+                    if n == 0 {
+                        break;
+                    }
+                    raw.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&raw);
+                    if let Some(split) = text.find("\r\n\r\n") {
+                        let content_length = text[..split]
+                            .lines()
+                            .find_map(|l| {
+                                l.to_lowercase()
+                                    .strip_prefix("content-length:")
+                                    .map(|v| v.trim().parse::<usize>().unwrap())
+                            })
+                            .unwrap_or(0);
+                        if raw.len() >= split + 4 + content_length {
+                            break;
+                        }
+                    }
+                }
+
+                let response = format!(
+                    "HTTP/1.1 {} Status\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        url
+    }
+
+    const COMPLETION_BODY: &str = r#"{"id":"chatcmpl-1","object":"chat.completion","created":0,"model":"grok-code-fast-1","choices":[{"index":0,"message":{"role":"assistant","content":"OK"},"finish_reason":"stop"}],"usage":{"prompt_tokens":10,"completion_tokens":1,"total_tokens":11}}"#;
+
+    fn mock_client(url: String) -> anyhow::Result<GrokClient> {
+        GrokClient::with_url(TEST_KEY.to_string(), url)
+    }
+
+    #[test]
+    fn test_onboarding_step_setting_keys() {
+        assert_eq!(OnboardingStep::ApiKey.setting_key(), "onboarding_step_api_key");
+        assert_eq!(OnboardingStep::FirstScan.setting_key(), "onboarding_step_first_scan");
+        for step in OnboardingStep::ALL {
+            assert_eq!(OnboardingStep::from_str(step.as_str()), Some(step));
+        }
+        assert_eq!(serde_json::to_string(&OnboardingStep::FirstProject).unwrap(), "\"first_project\"");
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_start_onboarding_check_fresh_install() {
+        let _guard = TestDbGuard::new();
+
+        let status = start_onboarding_check().await.unwrap();
+        assert!(!status.completed);
+        assert!(!status.first_project_created);
+        assert!(!status.first_scan_completed);
+        assert_eq!(status.steps_remaining, OnboardingStep::ALL.to_vec());
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_onboarding_check_detects_project_and_completed_scan() {
+        let _guard = TestDbGuard::new();
+        let scan_id = {
+            let conn = db::get_connection();
+            let project_id = queries::insert_project(&conn, "app", "/tmp/onboarding-app", None).unwrap();
+            queries::insert_scan(&conn, project_id, "regex_only").unwrap()
+        };
+
+        // A running scan doesn't complete the scan step
+        let status = start_onboarding_check().await.unwrap();
+        assert!(status.first_project_created);
+        assert!(!status.first_scan_completed);
+        assert_eq!(status.steps_remaining, vec![OnboardingStep::ApiKey, OnboardingStep::FirstScan]);
+
+        {
+            let conn = db::get_connection();
+            queries::update_scan_status(&conn, scan_id, "completed", None).unwrap();
+        }
+        let status = start_onboarding_check().await.unwrap();
+        assert!(status.first_scan_completed);
+        assert_eq!(status.steps_remaining, vec![OnboardingStep::ApiKey]);
+
+        let conn = db::get_connection();
+        let setting = queries::select_setting(&conn, "onboarding_step_first_scan").unwrap().unwrap();
+        assert_eq!(setting.value, "true");
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_recorded_steps_survive_project_deletion() {
+        let _guard = TestDbGuard::new();
+        let project_id = {
+            let conn = db::get_connection();
+            queries::insert_project(&conn, "app", "/tmp/onboarding-gone", None).unwrap()
+        };
+        start_onboarding_check().await.unwrap();
+        {
+            let conn = db::get_connection();
+            queries::delete_project(&conn, project_id).unwrap();
+        }
+
+        let status = start_onboarding_check().await.unwrap();
+        assert!(status.first_project_created);
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_onboarding_completed_flag() {
+        let _guard = TestDbGuard::new();
+        crate::commands::settings::complete_onboarding("smart".to_string(), 5.0).await.unwrap();
+
+        let status = start_onboarding_check().await.unwrap();
+        assert!(status.completed);
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_validate_api_key_accepted() {
+        let _guard = TestDbGuard::new();
+        let url = mock_grok_api(200, COMPLETION_BODY).await;
+
+        let validation = validate_api_key_with(mock_client(url)).await.unwrap();
+        assert!(validation.valid);
+        assert!(validation.error.is_none());
+        assert!(validation.latency_ms.is_some());
+
+        let progress = get_onboarding_progress().await.unwrap();
+        assert_eq!(progress.completed_steps, 1);
+        assert_eq!(progress.total_steps, 3);
+        assert_eq!(progress.steps[0].step, OnboardingStep::ApiKey);
+        assert!(progress.steps[0].completed);
+        assert!(progress.steps[0].completed_at.is_some());
+        assert!(!progress.steps[1].completed);
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_validate_api_key_rejected() {
+        let _guard = TestDbGuard::new();
+        let url = mock_grok_api(401, r#"{"error":"Incorrect API key provided"}"#).await;
+
+        let validation = validate_api_key_with(mock_client(url)).await.unwrap();
+        assert!(!validation.valid);
+        assert_eq!(validation.error.as_deref(), Some("API key was rejected (401)"));
+
+        // A failed check leaves the step open
+        let status = start_onboarding_check().await.unwrap();
+        assert!(status.steps_remaining.contains(&OnboardingStep::ApiKey));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_validate_api_key_server_error() {
+        let _guard = TestDbGuard::new();
+        let url = mock_grok_api(500, "internal error").await;
+
+        let validation = validate_api_key_with(mock_client(url)).await.unwrap();
+        assert!(!validation.valid);
+        assert!(validation.error.unwrap().contains("(500)"));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_validate_api_key_unreachable() {
+        let _guard = TestDbGuard::new();
+        // Bind then drop a listener so nothing accepts on the port
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/v1", listener.local_addr().unwrap());
+        drop(listener);
+
+        let validation = validate_api_key_with(mock_client(url)).await.unwrap();
+        assert!(!validation.valid);
+        assert!(validation.error.unwrap().starts_with("Could not reach the Grok API"));
+        assert!(validation.latency_ms.is_none());
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_validate_api_key_bad_format_skips_request() {
+        let _guard = TestDbGuard::new();
+
+        let validation = validate_api_key_with(GrokClient::with_key("sk-1234567890123456789".to_string()))
+            .await
+            .unwrap();
+        assert!(!validation.valid);
+        assert_eq!(validation.error.as_deref(), Some("API key must start with 'xai-'"));
+        assert!(validation.latency_ms.is_none());
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_set_api_key_stores_in_keychain() {
+        let _guard = TestDbGuard::new();
+        std::env::remove_var("XAI_API_KEY");
+        credentials::delete_api_key(CredentialProvider::Xai).unwrap();
+        assert!(!start_onboarding_check().await.unwrap().api_key_set);
+
+        let error = set_api_key("xai".to_string(), "sk-1234567890123456789".to_string()).await.unwrap_err();
+        assert!(error.contains("must start with 'xai-'"));
+        let error = set_api_key("openai".to_string(), TEST_KEY.to_string()).await.unwrap_err();
+        assert!(error.contains("Unknown API key provider 'openai'"));
+
+        set_api_key("grok".to_string(), TEST_KEY.to_string()).await.unwrap();
+        assert!(start_onboarding_check().await.unwrap().api_key_set);
+        assert_eq!(credentials::get_api_key(CredentialProvider::Xai).unwrap(), TEST_KEY);
+
+        credentials::delete_api_key(CredentialProvider::Xai).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_validate_github_token() {
+        let url = mock_grok_api(200, r#"{"login":"octocat","id":1}"#).await;
+        let validation = validate_github_token_with(GitHubClient::new(&url, "ghp_test")).await;
+        assert!(validation.valid);
+        assert!(validation.latency_ms.is_some());
+
+        let url = mock_grok_api(401, r#"{"message":"Bad credentials"}"#).await;
+        let validation = validate_github_token_with(GitHubClient::new(&url, "ghp_test")).await;
+        assert!(!validation.valid);
+        assert_eq!(validation.error.as_deref(), Some("GitHub token was rejected (401)"));
+    }
+}
@@ -3,7 +3,10 @@
 //! Handles project creation, listing, and selection via Tauri IPC
 
 use crate::db::{self, queries};
-use crate::models::Project;
+use crate::git::github::{self, PullRequestConfig};
+use crate::models::{Project, ProjectRoot, ProjectSettings};
+use crate::scanner::{scan_excludes, ScanRoots, FRAMEWORK_CACHE};
+use crate::security::path_validation;
 use crate::utils::create_audit_event;
 use std::path::Path;
 
@@ -14,7 +17,7 @@ use std::path::Path;
 /// Returns: Path to selected directory or error if cancelled
 #[tauri::command]
 pub async fn select_project_folder() -> Result<String, String> {
-    println!("[ryn] select_project_folder called");
+    eprintln!("[ryn] select_project_folder called");
 
     // Use tauri-plugin-dialog for native file picker
     // In production, this would use the Tauri dialog plugin
@@ -29,7 +32,7 @@ pub async fn select_project_folder() -> Result<String, String> {
     // Placeholder implementation - returns success with a path
     // This would be called from frontend with the dialog result
     let path = "/path/to/project".to_string();
-    println!("[ryn] select_project_folder success: path={}", path);
+    eprintln!("[ryn] select_project_folder success: path={}", path);
     Ok(path)
 }
 
@@ -50,13 +53,13 @@ pub async fn create_project(
     name: Option<String>,
     framework: Option<String>,
 ) -> Result<Project, String> {
-    println!("[ryn] create_project called: path={}, name={:?}, framework={:?}",
+    eprintln!("[ryn] create_project called: path={}, name={:?}, framework={:?}",
              path, name, framework);
 
     // Validate path exists
     if !Path::new(&path).exists() {
         let err_msg = format!("Project path does not exist: {}", path);
-        println!("[ryn] create_project validation failed: {}", err_msg);
+        eprintln!("[ryn] create_project validation failed: {}", err_msg);
         return Err(err_msg);
     }
 
@@ -120,12 +123,12 @@ pub async fn create_project(
         .ok_or_else(|| "Project was created but could not be retrieved".to_string())?;
 
     // Log audit event
-    if let Ok(event) = create_audit_event(&conn, "project_created", None, None, None,
+    if let Ok(event) = create_audit_event(&conn, "project_created", Some(project_id), None, None,
         &format!("Created project: {}", project_name)) {
         let _ = queries::insert_audit_event(&conn, &event);
     }
 
-    println!("[ryn] create_project success: project_id={}, name={}", project.id, project.name);
+    eprintln!("[ryn] create_project success: project_id={}, name={}", project.id, project.name);
     Ok(project)
 }
 
@@ -134,21 +137,354 @@ pub async fn create_project(
 /// Returns: List of all projects sorted by creation date (newest first)
 #[tauri::command]
 pub async fn get_projects() -> Result<Vec<Project>, String> {
-    println!("[ryn] get_projects called");
+    eprintln!("[ryn] get_projects called");
 
     let conn = db::get_connection();
 
     let projects = queries::select_projects(&conn)
         .map_err(|e| {
             let err_msg = format!("Failed to fetch projects: {}", e);
-            println!("[ryn] get_projects error: {}", err_msg);
+            eprintln!("[ryn] get_projects error: {}", err_msg);
             err_msg
         })?;
 
-    println!("[ryn] get_projects success: found {} projects", projects.len());
+    eprintln!("[ryn] get_projects success: found {} projects", projects.len());
     Ok(projects)
 }
 
+/// Set project-specific scan exclusion patterns
+///
+/// # Arguments
+/// * `project_id` - Project to configure
+/// * `patterns` - Glob patterns relative to the project root (e.g. "tests/**", "*.min.js")
+///
+/// Returns: Success, or error if the project is missing or a pattern is invalid
+/// or would exclude the entire project
+#[tauri::command]
+pub async fn set_project_scan_excludes(project_id: i64, patterns: Vec<String>) -> Result<(), String> {
+    eprintln!("[ryn] set_project_scan_excludes called: project_id={}, patterns={:?}", project_id, patterns);
+
+    scan_excludes::validate_patterns(&patterns)
+        .map_err(|e| format!("Invalid exclude patterns: {}", e))?;
+
+    let conn = db::get_connection();
+
+    queries::select_project(&conn, project_id)
+        .map_err(|e| format!("Failed to fetch project: {}", e))?
+        .ok_or_else(|| format!("Project not found: {}", project_id))?;
+
+    let value = serde_json::to_string(&patterns)
+        .map_err(|e| format!("Failed to serialize exclude patterns: {}", e))?;
+
+    queries::insert_or_update_setting(&conn, &scan_excludes::setting_key(project_id), &value)
+        .map_err(|e| format!("Failed to save exclude patterns: {}", e))?;
+
+    if let Ok(event) = create_audit_event(
+        &conn,
+        "settings_updated",
+        Some(project_id),
+        None,
+        None,
+        &format!("Updated scan exclude patterns: {}", value),
+    ) {
+        let _ = queries::insert_audit_event(&conn, &event);
+    }
+
+    Ok(())
+}
+
+/// Get project-specific scan exclusion patterns
+///
+/// Returns: Configured patterns (empty if none)
+#[tauri::command]
+pub async fn get_project_scan_excludes(project_id: i64) -> Result<Vec<String>, String> {
+    let conn = db::get_connection();
+
+    let patterns = queries::select_setting(&conn, &scan_excludes::setting_key(project_id))
+        .map_err(|e| format!("Failed to fetch exclude patterns: {}", e))?
+        .map(|s| scan_excludes::parse_patterns(&s.value))
+        .unwrap_or_default();
+
+    Ok(patterns)
+}
+
+/// Set a project's cost limit, overriding `cost_limit_per_scan` for its scans
+///
+/// # Arguments
+/// * `project_id` - Project to configure
+/// * `limit_usd` - Cost limit per scan in dollars (0.00 - 1,000.00)
+///
+/// Returns: Success, or error if the project is missing or the limit is out of range
+#[tauri::command]
+pub async fn set_project_cost_limit(project_id: i64, limit_usd: f64) -> Result<(), String> {
+    eprintln!("[ryn] set_project_cost_limit called: project_id={}, limit_usd={}", project_id, limit_usd);
+
+    if !(0.0..=1000.0).contains(&limit_usd) {
+        return Err("Cost limit must be between $0.00 and $1,000.00".to_string());
+    }
+
+    let conn = db::get_connection();
+
+    queries::select_project(&conn, project_id)
+        .map_err(|e| format!("Failed to fetch project: {}", e))?
+        .ok_or_else(|| format!("Project not found: {}", project_id))?;
+
+    queries::update_project_cost_limit(&conn, project_id, Some(limit_usd))
+        .map_err(|e| format!("Failed to save project cost limit: {}", e))?;
+
+    if let Ok(event) = create_audit_event(
+        &conn,
+        "settings_updated",
+        Some(project_id),
+        None,
+        None,
+        &format!("Set project cost limit: ${:.2}", limit_usd),
+    ) {
+        let _ = queries::insert_audit_event(&conn, &event);
+    }
+
+    Ok(())
+}
+
+/// Remove a project's cost limit so its scans use `cost_limit_per_scan` again
+///
+/// Returns: Success, or error if the project is missing
+#[tauri::command]
+pub async fn remove_project_cost_limit(project_id: i64) -> Result<(), String> {
+    eprintln!("[ryn] remove_project_cost_limit called: project_id={}", project_id);
+
+    let conn = db::get_connection();
+
+    queries::select_project(&conn, project_id)
+        .map_err(|e| format!("Failed to fetch project: {}", e))?
+        .ok_or_else(|| format!("Project not found: {}", project_id))?;
+
+    queries::update_project_cost_limit(&conn, project_id, None)
+        .map_err(|e| format!("Failed to remove project cost limit: {}", e))?;
+
+    if let Ok(event) = create_audit_event(
+        &conn,
+        "settings_updated",
+        Some(project_id),
+        None,
+        None,
+        "Removed project cost limit",
+    ) {
+        let _ = queries::insert_audit_event(&conn, &event);
+    }
+
+    Ok(())
+}
+
+/// Configure pull requests for a project's applied fixes
+///
+/// When enabled, `apply_fix` commits each fix to its own branch, pushes it to
+/// `config.remote` and opens a GitHub pull request (needs GITHUB_TOKEN).
+///
+/// # Arguments
+/// * `project_id` - Project to configure
+/// * `config` - Remote, base branch (current branch if unset) and PR template
+///
+/// Returns: Success, or error if the project is missing or the config is invalid
+#[tauri::command]
+pub async fn set_project_pull_request_config(project_id: i64, config: PullRequestConfig) -> Result<(), String> {
+    eprintln!("[ryn] set_project_pull_request_config called: project_id={}, enabled={}", project_id, config.enabled);
+
+    config.validate()
+        .map_err(|e| format!("Invalid pull request settings: {}", e))?;
+
+    let conn = db::get_connection();
+
+    queries::select_project(&conn, project_id)
+        .map_err(|e| format!("Failed to fetch project: {}", e))?
+        .ok_or_else(|| format!("Project not found: {}", project_id))?;
+
+    let value = serde_json::to_string(&config)
+        .map_err(|e| format!("Failed to serialize pull request settings: {}", e))?;
+
+    queries::insert_or_update_setting(&conn, &github::setting_key(project_id), &value)
+        .map_err(|e| format!("Failed to save pull request settings: {}", e))?;
+
+    if let Ok(event) = create_audit_event(
+        &conn,
+        "settings_updated",
+        Some(project_id),
+        None,
+        None,
+        &format!(
+            "{} pull requests for applied fixes (remote: {})",
+            if config.enabled { "Enabled" } else { "Disabled" },
+            config.remote
+        ),
+    ) {
+        let _ = queries::insert_audit_event(&conn, &event);
+    }
+
+    Ok(())
+}
+
+/// Get a project's pull request settings
+///
+/// Returns: Configured settings (disabled if never set)
+#[tauri::command]
+pub async fn get_project_pull_request_config(project_id: i64) -> Result<PullRequestConfig, String> {
+    let conn = db::get_connection();
+
+    let config = queries::select_setting(&conn, &github::setting_key(project_id))
+        .map_err(|e| format!("Failed to fetch pull request settings: {}", e))?
+        .map(|s| PullRequestConfig::parse(&s.value))
+        .unwrap_or_default();
+
+    Ok(config)
+}
+
+/// Set a project's reporting thresholds
+///
+/// Scans drop violations below the minimum severity, LLM-only findings
+/// below the minimum confidence, and violations of disabled controls.
+///
+/// # Arguments
+/// * `project_id` - Project to configure
+/// * `settings` - Thresholds; unset fields report everything
+///
+/// Returns: Saved settings, or error if the project is missing or a threshold is invalid
+#[tauri::command]
+pub async fn set_project_settings(project_id: i64, settings: ProjectSettings) -> Result<ProjectSettings, String> {
+    eprintln!("[ryn] set_project_settings called: project_id={}", project_id);
+
+    let settings = ProjectSettings {
+        project_id,
+        min_severity: settings.min_severity.map(|s| s.trim().to_lowercase()).filter(|s| !s.is_empty()),
+        disabled_controls: settings.disabled_controls.iter().map(|c| c.trim().to_string()).collect(),
+        ..settings
+    };
+    settings.validate()?;
+
+    let conn = db::get_connection();
+
+    queries::select_project(&conn, project_id)
+        .map_err(|e| format!("Failed to fetch project: {}", e))?
+        .ok_or_else(|| format!("Project not found: {}", project_id))?;
+
+    queries::upsert_project_settings(&conn, &settings)
+        .map_err(|e| format!("Failed to save project settings: {}", e))?;
+
+    if let Ok(event) = create_audit_event(
+        &conn,
+        "settings_updated",
+        Some(project_id),
+        None,
+        None,
+        &format!(
+            "Set project thresholds: min severity {}, min LLM confidence {}, {} disabled controls",
+            settings.min_severity.as_deref().unwrap_or("none"),
+            settings.min_llm_confidence.map(|c| c.to_string()).unwrap_or_else(|| "none".to_string()),
+            settings.disabled_controls.len()
+        ),
+    ) {
+        let _ = queries::insert_audit_event(&conn, &event);
+    }
+
+    crate::commands::scan::project_thresholds(&conn, project_id)
+}
+
+/// Get a project's reporting thresholds
+///
+/// Returns: Saved settings (every field unset if never configured)
+#[tauri::command]
+pub async fn get_project_settings(project_id: i64) -> Result<ProjectSettings, String> {
+    let conn = db::get_connection();
+    crate::commands::scan::project_thresholds(&conn, project_id)
+}
+
+/// Add a directory to be scanned with a project (e.g. a frontend repo next to the backend)
+///
+/// Scans walk every root into one scan record; violations in an extra root
+/// have paths like `@{label}/src/App.tsx`. Roots can't overlap the project's
+/// path or each other.
+///
+/// # Arguments
+/// * `project_id` - Project to extend
+/// * `path` - Absolute path to the directory
+/// * `label` - Optional label (defaults to the directory name)
+///
+/// Returns: The stored root with its detected framework
+#[tauri::command]
+pub async fn add_project_root(project_id: i64, path: String, label: Option<String>) -> Result<ProjectRoot, String> {
+    eprintln!("[ryn] add_project_root called: project_id={}, path={}, label={:?}", project_id, path, label);
+
+    path_validation::validate_project_path(Path::new(&path))
+        .map_err(|e| format!("Invalid root path: {}", e))?;
+    let label = label
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .unwrap_or_else(|| ProjectRoot::default_label(&path));
+    ProjectRoot::validate_label(&label)?;
+
+    let conn = db::get_connection();
+
+    let project = queries::select_project(&conn, project_id)
+        .map_err(|e| format!("Failed to fetch project: {}", e))?
+        .ok_or_else(|| format!("Project not found: {}", project_id))?;
+    let existing = queries::select_project_roots(&conn, project_id)
+        .map_err(|e| format!("Failed to fetch project roots: {}", e))?;
+
+    // Overlapping roots would scan the same files twice under different paths
+    if ScanRoots::for_project(&project, &existing).overlaps(Path::new(&path)) {
+        return Err(format!("Root {} overlaps the project or one of its roots", path));
+    }
+    if existing.iter().any(|root| root.label == label) {
+        return Err(format!("Project {} already has a root labelled '{}'", project_id, label));
+    }
+
+    let framework = FRAMEWORK_CACHE.detect(Path::new(&path)).unwrap_or_else(|e| {
+        eprintln!("[ryn] Framework detection failed for {}: {}", path, e);
+        None
+    });
+    let root_id = queries::insert_project_root(&conn, project_id, &path, &label, framework.as_deref())
+        .map_err(|e| format!("Failed to add project root: {}", e))?;
+
+    if let Ok(event) = create_audit_event(&conn, "project_root_added", Some(project_id), None, None,
+        &format!("Added root '{}': {}", label, path)) {
+        let _ = queries::insert_audit_event(&conn, &event);
+    }
+
+    queries::select_project_roots(&conn, project_id)
+        .map_err(|e| format!("Failed to fetch project roots: {}", e))?
+        .into_iter()
+        .find(|root| root.id == root_id)
+        .ok_or_else(|| "Root was added but could not be retrieved".to_string())
+}
+
+/// Stop scanning one of a project's extra roots
+///
+/// Violations already found in it stay in earlier scans.
+#[tauri::command]
+pub async fn remove_project_root(project_id: i64, root_id: i64) -> Result<(), String> {
+    eprintln!("[ryn] remove_project_root called: project_id={}, root_id={}", project_id, root_id);
+
+    let conn = db::get_connection();
+    if !queries::delete_project_root(&conn, project_id, root_id)
+        .map_err(|e| format!("Failed to remove project root: {}", e))?
+    {
+        return Err(format!("Root {} not found in project {}", root_id, project_id));
+    }
+
+    if let Ok(event) = create_audit_event(&conn, "project_root_removed", Some(project_id), None, None,
+        &format!("Removed root {}", root_id)) {
+        let _ = queries::insert_audit_event(&conn, &event);
+    }
+
+    Ok(())
+}
+
+/// Get a project's extra roots (its own path is the primary root)
+#[tauri::command]
+pub async fn get_project_roots(project_id: i64) -> Result<Vec<ProjectRoot>, String> {
+    let conn = db::get_connection();
+    queries::select_project_roots(&conn, project_id)
+        .map_err(|e| format!("Failed to fetch project roots: {}", e))
+}
 
 #[cfg(test)]
 mod tests {
@@ -305,7 +641,7 @@ mod tests {
     async fn test_create_multiple_projects_different_frameworks() {
         let _guard = TestDbGuard::new();
 
-        let frameworks = vec!["Django", "Express", "Flask"];
+        let frameworks = ["Django", "Express", "Flask"];
         for (i, fw) in frameworks.iter().enumerate() {
             let dir = tempfile::TempDir::new().unwrap();
             let path = dir.path().to_string_lossy().to_string();
@@ -395,4 +731,191 @@ mod tests {
         let project = result.unwrap();
         assert_eq!(project.path, path);
     }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_set_project_scan_excludes_round_trip() {
+        let _guard = TestDbGuard::new();
+        let project_dir = tempfile::TempDir::new().unwrap();
+        let project = create_project(project_dir.path().to_string_lossy().to_string(), None, None)
+            .await
+            .unwrap();
+
+        let patterns = vec!["tests/**".to_string(), "*.min.js".to_string()];
+        set_project_scan_excludes(project.id, patterns.clone()).await.unwrap();
+
+        assert_eq!(get_project_scan_excludes(project.id).await.unwrap(), patterns);
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_set_project_scan_excludes_rejects_root_pattern() {
+        let _guard = TestDbGuard::new();
+        let project_dir = tempfile::TempDir::new().unwrap();
+        let project = create_project(project_dir.path().to_string_lossy().to_string(), None, None)
+            .await
+            .unwrap();
+
+        let result = set_project_scan_excludes(project.id, vec!["**".to_string()]).await;
+        assert!(result.unwrap_err().contains("Invalid exclude patterns"));
+        assert!(get_project_scan_excludes(project.id).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_set_project_scan_excludes_missing_project() {
+        let _guard = TestDbGuard::new();
+        let result = set_project_scan_excludes(999, vec!["tests/**".to_string()]).await;
+        assert!(result.unwrap_err().contains("Project not found"));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_project_pull_request_config_round_trip() {
+        let _guard = TestDbGuard::new();
+        let project_dir = tempfile::TempDir::new().unwrap();
+        let project = create_project(project_dir.path().to_string_lossy().to_string(), None, None)
+            .await
+            .unwrap();
+        assert!(!get_project_pull_request_config(project.id).await.unwrap().enabled);
+
+        let config = PullRequestConfig {
+            enabled: true,
+            remote: "upstream".to_string(),
+            base_branch: Some("develop".to_string()),
+            pr_template: Some("Fixes {control_id}".to_string()),
+        };
+        set_project_pull_request_config(project.id, config.clone()).await.unwrap();
+        assert_eq!(get_project_pull_request_config(project.id).await.unwrap(), config);
+
+        let invalid = PullRequestConfig { remote: " ".to_string(), ..config };
+        let result = set_project_pull_request_config(project.id, invalid).await;
+        assert!(result.unwrap_err().contains("Invalid pull request settings"));
+        assert!(set_project_pull_request_config(999, PullRequestConfig::default()).await.is_err());
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_project_settings_round_trip() {
+        let _guard = TestDbGuard::new();
+        let project_dir = tempfile::TempDir::new().unwrap();
+        let project = create_project(project_dir.path().to_string_lossy().to_string(), None, None)
+            .await
+            .unwrap();
+        let defaults = get_project_settings(project.id).await.unwrap();
+        assert_eq!(defaults, ProjectSettings { project_id: project.id, ..Default::default() });
+
+        let settings = ProjectSettings {
+            min_severity: Some(" High ".to_string()),
+            min_llm_confidence: Some(80),
+            disabled_controls: vec!["A1.2".to_string()],
+            ..Default::default()
+        };
+        let saved = set_project_settings(project.id, settings).await.unwrap();
+        assert_eq!(saved.project_id, project.id);
+        assert_eq!(saved.min_severity.as_deref(), Some("high"));
+        assert_eq!(get_project_settings(project.id).await.unwrap(), saved);
+
+        let invalid = ProjectSettings { min_llm_confidence: Some(-1), ..Default::default() };
+        assert!(set_project_settings(project.id, invalid).await.is_err());
+        assert!(set_project_settings(999, ProjectSettings::default()).await.is_err());
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_project_cost_limit_takes_precedence() {
+        let _guard = TestDbGuard::new();
+        let project_dir = tempfile::TempDir::new().unwrap();
+        let project = create_project(project_dir.path().to_string_lossy().to_string(), None, None)
+            .await
+            .unwrap();
+        assert_eq!(project.project_cost_limit_usd, None);
+
+        {
+            let conn = db::get_connection();
+            queries::insert_or_update_setting(&conn, "cost_limit_per_scan", "5.00").unwrap();
+        }
+        set_project_cost_limit(project.id, 0.5).await.unwrap();
+
+        let conn = db::get_connection();
+        let stored = queries::select_project(&conn, project.id).unwrap().unwrap();
+        assert_eq!(stored.project_cost_limit_usd, Some(0.5));
+        assert_eq!(crate::commands::scan::effective_cost_limit(&conn, project.id), 0.5);
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_remove_project_cost_limit_restores_global_limit() {
+        let _guard = TestDbGuard::new();
+        let project_dir = tempfile::TempDir::new().unwrap();
+        let project = create_project(project_dir.path().to_string_lossy().to_string(), None, None)
+            .await
+            .unwrap();
+
+        {
+            let conn = db::get_connection();
+            queries::insert_or_update_setting(&conn, "cost_limit_per_scan", "5.00").unwrap();
+        }
+        set_project_cost_limit(project.id, 0.5).await.unwrap();
+        remove_project_cost_limit(project.id).await.unwrap();
+
+        let conn = db::get_connection();
+        let stored = queries::select_project(&conn, project.id).unwrap().unwrap();
+        assert_eq!(stored.project_cost_limit_usd, None);
+        assert_eq!(crate::commands::scan::effective_cost_limit(&conn, project.id), 5.0);
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_set_project_cost_limit_rejects_invalid_limits() {
+        let _guard = TestDbGuard::new();
+        let project_dir = tempfile::TempDir::new().unwrap();
+        let project = create_project(project_dir.path().to_string_lossy().to_string(), None, None)
+            .await
+            .unwrap();
+
+        for invalid in [-1.0, 1000.01, f64::NAN] {
+            let result = set_project_cost_limit(project.id, invalid).await;
+            assert!(result.unwrap_err().contains("Cost limit must be between"), "{}", invalid);
+        }
+
+        let result = set_project_cost_limit(999, 1.0).await;
+        assert!(result.unwrap_err().contains("Project not found"));
+        let result = remove_project_cost_limit(999).await;
+        assert!(result.unwrap_err().contains("Project not found"));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_project_roots() {
+        let _guard = TestDbGuard::new();
+        let workspace = tempfile::TempDir::new().unwrap();
+        let api_dir = workspace.path().join("api");
+        let web_dir = workspace.path().join("web");
+        let jobs_dir = workspace.path().join("jobs");
+        for dir in [api_dir.join("src"), web_dir.clone(), jobs_dir.clone()] {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        std::fs::write(web_dir.join("package.json"), r#"{"dependencies": {"express": "4.18.0"}}"#).unwrap();
+        let project = create_project(api_dir.to_string_lossy().to_string(), None, None).await.unwrap();
+
+        let root = add_project_root(project.id, web_dir.to_string_lossy().to_string(), None).await.unwrap();
+        assert_eq!(root.label, "web");
+        assert_eq!(root.framework.as_deref(), Some("express"));
+        assert_eq!(get_project_roots(project.id).await.unwrap(), vec![root.clone()]);
+
+        // Overlapping or duplicate roots are rejected
+        let nested = add_project_root(project.id, api_dir.join("src").to_string_lossy().to_string(), None).await;
+        assert!(nested.unwrap_err().contains("overlaps"));
+        let again = add_project_root(project.id, web_dir.to_string_lossy().to_string(), Some("frontend".to_string())).await;
+        assert!(again.unwrap_err().contains("overlaps"));
+        let jobs = jobs_dir.to_string_lossy().to_string();
+        assert!(add_project_root(project.id, jobs.clone(), Some("a/b".to_string())).await.is_err());
+        let taken = add_project_root(project.id, jobs, Some("web".to_string())).await;
+        assert!(taken.unwrap_err().contains("already has a root"));
+
+        remove_project_root(project.id, root.id).await.unwrap();
+        assert!(get_project_roots(project.id).await.unwrap().is_empty());
+        assert!(remove_project_root(project.id, root.id).await.is_err());
+    }
 }
@@ -0,0 +1,113 @@
+//! Scan schedule commands
+//!
+//! Schedules recurring scans of a project with a cron expression. The
+//! background scheduler (see `crate::scheduler`) runs them while the app is open.
+
+use chrono::Utc;
+
+use crate::db::{self, queries};
+use crate::models::ScanSchedule;
+use crate::scheduler;
+use crate::utils::create_audit_event;
+
+/// Schedule recurring scans of a project
+///
+/// # Arguments
+/// * `project_id` - Project to scan
+/// * `cron_expr` - Five-field cron expression in local time, e.g. "0 2 * * *"
+///   for 02:00 every night, or a shorthand like "@daily"
+///
+/// Returns: The created schedule, with its first run time
+#[tauri::command]
+pub async fn schedule_scan(project_id: i64, cron_expr: String) -> Result<ScanSchedule, String> {
+    eprintln!("[ryn] schedule_scan called: project_id={}, cron_expr='{}'", project_id, cron_expr);
+
+    let cron_expr = cron_expr.trim().to_string();
+    let next_run_at = scheduler::next_run_at(&cron_expr, Utc::now()).map_err(|e| format!("{:#}", e))?;
+
+    let conn = db::get_connection();
+
+    queries::select_project(&conn, project_id)
+        .map_err(|e| format!("Failed to fetch project: {}", e))?
+        .ok_or_else(|| format!("Project not found: {}", project_id))?;
+
+    let mut schedule = ScanSchedule::new(project_id, cron_expr, next_run_at);
+    schedule.id = queries::insert_scan_schedule(&conn, &schedule)
+        .map_err(|e| format!("Failed to create scan schedule: {}", e))?;
+
+    if let Ok(event) = create_audit_event(
+        &conn,
+        "scan_scheduled",
+        Some(project_id),
+        None,
+        None,
+        &format!(
+            "Scheduled scans '{}' (schedule {}), first run at {}",
+            schedule.cron_expr, schedule.id, schedule.next_run_at
+        ),
+    ) {
+        let _ = queries::insert_audit_event(&conn, &event);
+    }
+
+    eprintln!("[ryn] schedule_scan success: schedule_id={}, next_run_at={}", schedule.id, schedule.next_run_at);
+    Ok(schedule)
+}
+
+/// List scan schedules for projects in the current namespace
+///
+/// Returns: Schedules ordered by ID
+#[tauri::command]
+pub async fn list_schedules() -> Result<Vec<ScanSchedule>, String> {
+    let conn = db::get_connection();
+
+    queries::select_scan_schedules(&conn)
+        .map_err(|e| format!("Failed to fetch scan schedules: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_helpers::TestDbGuard;
+
+    fn create_test_project() -> i64 {
+        let conn = db::get_connection();
+        queries::insert_project(&conn, "test-project", "/tmp/ryn-test/scheduled", None).unwrap()
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_schedule_scan_and_list() {
+        let _guard = TestDbGuard::new();
+        let project_id = create_test_project();
+
+        let schedule = schedule_scan(project_id, " 0 2 * * * ".to_string()).await.unwrap();
+        assert_eq!(schedule.cron_expr, "0 2 * * *");
+        assert!(schedule.enabled);
+        assert!(schedule.next_run_at > scheduler::format_schedule_time(Utc::now()));
+
+        let schedules = list_schedules().await.unwrap();
+        assert_eq!(schedules.len(), 1);
+        assert_eq!(schedules[0].id, schedule.id);
+        assert_eq!(schedules[0].next_run_at, schedule.next_run_at);
+        assert_eq!(schedules[0].last_run_at, None);
+
+        let conn = db::get_connection();
+        let events = queries::select_audit_events_by_project(&conn, project_id).unwrap();
+        assert!(events.iter().any(|e| e.event_type == "scan_scheduled"));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_schedule_scan_rejects_bad_input() {
+        let _guard = TestDbGuard::new();
+        let project_id = create_test_project();
+
+        let error = schedule_scan(project_id, "every night".to_string()).await.unwrap_err();
+        assert!(error.contains("Invalid cron expression"));
+
+        let error = schedule_scan(999, "@daily".to_string()).await.unwrap_err();
+        assert!(error.contains("Project not found"));
+
+        assert!(list_schedules().await.unwrap().is_empty());
+    }
+}
@@ -3,8 +3,37 @@
 //! Handles application settings and configuration
 
 use crate::db::{self, queries};
-use crate::models::Settings;
+use crate::models::{MaintenanceStats, Settings, SettingsProfile, SettingsProfileExport};
+use crate::security::path_validation;
 use crate::utils::create_audit_event;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Columns of an audit event CSV export, in `AuditEvent` field order
+const AUDIT_CSV_COLUMNS: [&str; 8] = [
+    "id",
+    "event_type",
+    "project_id",
+    "violation_id",
+    "fix_id",
+    "description",
+    "metadata",
+    "created_at",
+];
+
+/// Metadata file written next to every audit event CSV export
+const EXPORT_MANIFEST_FILE: &str = "export_manifest.json";
+
+/// Result of an audit event CSV export
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExportStats {
+    pub rows_exported: i64,
+    pub file_size_bytes: u64,
+    /// Hex-encoded SHA-256 of the CSV file
+    pub sha256_checksum: String,
+}
 
 /// Get all application settings
 ///
@@ -91,9 +120,15 @@ pub async fn clear_database() -> Result<String, String> {
     conn.execute("DELETE FROM fixes", [])
         .map_err(|e| format!("Failed to clear fixes: {}", e))?;
 
+    conn.execute("DELETE FROM violation_status_history", [])
+        .map_err(|e| format!("Failed to clear violation status history: {}", e))?;
+
     conn.execute("DELETE FROM violations", [])
         .map_err(|e| format!("Failed to clear violations: {}", e))?;
 
+    conn.execute("DELETE FROM compliance_scores", [])
+        .map_err(|e| format!("Failed to clear compliance scores: {}", e))?;
+
     conn.execute("DELETE FROM scans", [])
         .map_err(|e| format!("Failed to clear scans: {}", e))?;
 
@@ -114,9 +149,39 @@ pub async fn clear_database() -> Result<String, String> {
         let _ = queries::insert_audit_event(&conn, &event);
     }
 
+    // Reclaim the space freed by the deletes; the clear itself already succeeded
+    match queries::vacuum_and_analyze(&conn) {
+        Ok(stats) => eprintln!(
+            "[ryn] Database optimized after clear: {} pages freed in {}ms",
+            stats.pages_freed, stats.duration_ms
+        ),
+        Err(e) => eprintln!("[ryn] Database optimization after clear failed: {}", e),
+    }
+
     Ok(format!("Database cleared successfully. Backup saved to: {}", backup_path_str))
 }
 
+/// Optimize the database: refresh query statistics and reclaim free pages
+///
+/// Runs `PRAGMA optimize`, `ANALYZE` and `VACUUM`, then records the time in
+/// the `last_optimized_at` setting.
+///
+/// Returns: Pages freed, database size before/after and how long it took
+#[tauri::command]
+pub async fn optimize_database() -> Result<MaintenanceStats, String> {
+    eprintln!("[ryn] optimize_database called");
+
+    let conn = db::get_connection();
+    let stats = queries::vacuum_and_analyze(&conn)
+        .map_err(|e| format!("Failed to optimize database: {}", e))?;
+
+    eprintln!(
+        "[ryn] optimize_database: {} -> {} bytes, {} pages freed in {}ms",
+        stats.size_before_bytes, stats.size_after_bytes, stats.pages_freed, stats.duration_ms
+    );
+    Ok(stats)
+}
+
 /// Export all database data to JSON format
 ///
 /// Returns: JSON string containing all projects, scans, violations, fixes, and settings
@@ -172,6 +237,136 @@ pub async fn export_data() -> Result<String, String> {
         .map_err(|e| format!("Failed to serialize export data: {}", e))
 }
 
+/// Export audit events in a date range to a CSV file
+///
+/// Events are streamed from the database straight into the CSV, newest
+/// first, so exports of millions of rows don't load them all into memory.
+/// An `export_manifest.json` describing the export (range, row count,
+/// checksum) is written to the same directory.
+///
+/// # Arguments
+/// * `project_id` - Only export this project's events (all events in the namespace if None)
+/// * `start_date` - First day to include, "YYYY-MM-DD"
+/// * `end_date` - Last day to include, "YYYY-MM-DD"
+/// * `output_path` - Destination CSV file
+///
+/// Returns: Row count, file size and SHA-256 checksum of the CSV
+#[tauri::command]
+pub async fn export_audit_events_csv(
+    project_id: Option<i64>,
+    start_date: String,
+    end_date: String,
+    output_path: String,
+) -> Result<ExportStats, String> {
+    eprintln!(
+        "[ryn] export_audit_events_csv called: project_id={:?}, {}..{}",
+        project_id, start_date, end_date
+    );
+
+    let start = chrono::NaiveDate::parse_from_str(&start_date, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid start date (expected YYYY-MM-DD): {}", start_date))?;
+    let end = chrono::NaiveDate::parse_from_str(&end_date, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid end date (expected YYYY-MM-DD): {}", end_date))?;
+    if end < start {
+        return Err(format!("End date {} is before start date {}", end_date, start_date));
+    }
+    // created_at is "YYYY-MM-DD HH:MM:SS", so the day after `end` is an exclusive bound
+    let end_exclusive = end
+        .succ_opt()
+        .ok_or_else(|| format!("Invalid end date: {}", end_date))?
+        .format("%Y-%m-%d")
+        .to_string();
+
+    let output = Path::new(&output_path);
+    path_validation::validate_export_path(output)
+        .map_err(|e| format!("Security: Invalid export path: {}", e))?;
+
+    let conn = db::get_connection();
+
+    let mut writer = csv::Writer::from_path(output)
+        .map_err(|e| format!("Failed to create {}: {}", output_path, e))?;
+    writer
+        .write_record(AUDIT_CSV_COLUMNS)
+        .map_err(|e| format!("Failed to write CSV header: {}", e))?;
+
+    let optional = |value: Option<i64>| value.map(|v| v.to_string()).unwrap_or_default();
+    let rows_exported = queries::for_each_audit_event_in_range(
+        &conn,
+        project_id,
+        &start.format("%Y-%m-%d").to_string(),
+        &end_exclusive,
+        |event| {
+            writer.write_record([
+                event.id.to_string(),
+                event.event_type,
+                optional(event.project_id),
+                optional(event.violation_id),
+                optional(event.fix_id),
+                event.description,
+                event.metadata.unwrap_or_default(),
+                event.created_at,
+            ])?;
+            Ok(())
+        },
+    )
+    .map_err(|e| format!("Failed to export audit events: {}", e))?;
+
+    writer
+        .flush()
+        .map_err(|e| format!("Failed to write {}: {}", output_path, e))?;
+    drop(writer);
+
+    let mut hasher = Sha256::new();
+    let mut file = std::fs::File::open(output)
+        .map_err(|e| format!("Failed to read back {}: {}", output_path, e))?;
+    let file_size_bytes = std::io::copy(&mut file, &mut hasher)
+        .map_err(|e| format!("Failed to checksum {}: {}", output_path, e))?;
+
+    let stats = ExportStats {
+        rows_exported,
+        file_size_bytes,
+        sha256_checksum: hex::encode(hasher.finalize()),
+    };
+
+    let manifest = serde_json::json!({
+        "version": "1.0",
+        "export_type": "audit_events",
+        "exported_at": chrono::Utc::now().to_rfc3339(),
+        "file": output.file_name().map(|name| name.to_string_lossy().to_string()),
+        "namespace": queries::current_namespace(&conn).ok(),
+        "project_id": project_id,
+        "start_date": start_date,
+        "end_date": end_date,
+        "columns": AUDIT_CSV_COLUMNS,
+        "sort": "created_at DESC",
+        "rows_exported": stats.rows_exported,
+        "file_size_bytes": stats.file_size_bytes,
+        "sha256_checksum": stats.sha256_checksum,
+    });
+    let manifest_path = output.with_file_name(EXPORT_MANIFEST_FILE);
+    let manifest_json = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| format!("Failed to serialize export manifest: {}", e))?;
+    std::fs::write(&manifest_path, manifest_json)
+        .map_err(|e| format!("Failed to write {}: {}", manifest_path.display(), e))?;
+
+    // Log audit event
+    if let Ok(event) = create_audit_event(
+        &conn,
+        "audit_events_exported",
+        project_id,
+        None,
+        None,
+        &format!(
+            "Exported {} audit events from {} to {} ({})",
+            stats.rows_exported, start_date, end_date, stats.sha256_checksum
+        ),
+    ) {
+        let _ = queries::insert_audit_event(&conn, &event);
+    }
+
+    Ok(stats)
+}
+
 /// Complete onboarding by saving user's scanning preferences
 ///
 /// # Arguments
@@ -222,6 +417,205 @@ pub async fn complete_onboarding(scan_mode: String, cost_limit: f64) -> Result<(
     Ok(())
 }
 
+/// Check the values of known settings in a profile
+///
+/// Keys must be non-empty; llm_scan_mode, cost_limit_per_scan,
+/// monthly_budget_usd, auto_apply_trust_level and min_confidence are
+/// range-checked like `complete_onboarding` does. Other keys are stored as-is.
+fn validate_profile_settings(settings: &HashMap<String, String>) -> Result<(), String> {
+    if settings.is_empty() {
+        return Err("Profile must contain at least one setting".to_string());
+    }
+
+    for (key, value) in settings {
+        match key.as_str() {
+            "" => return Err("Setting key cannot be empty".to_string()),
+            "llm_scan_mode" if !matches!(value.as_str(), "regex_only" | "smart" | "analyze_all") => {
+                return Err(format!("Invalid scan mode: {}. Must be regex_only, smart, or analyze_all", value));
+            }
+            "cost_limit_per_scan" => {
+                let limit: f64 = value.parse().map_err(|_| format!("Invalid cost limit: {}", value))?;
+                if !(0.0..=1000.0).contains(&limit) {
+                    return Err("Cost limit must be between $0.00 and $1,000.00".to_string());
+                }
+            }
+            "monthly_budget_usd" => {
+                let budget: f64 = value.parse().map_err(|_| format!("Invalid monthly budget: {}", value))?;
+                if !budget.is_finite() || budget < 0.0 {
+                    return Err("Monthly budget must be $0.00 or more".to_string());
+                }
+            }
+            "auto_apply_trust_level" if crate::models::TrustLevel::from_str(value).is_none() => {
+                return Err(format!("Invalid trust level: {}. Must be auto, review, or manual", value));
+            }
+            "min_confidence" => {
+                let confidence: i64 = value.parse().map_err(|_| format!("Invalid min_confidence: {}", value))?;
+                if !(0..=100).contains(&confidence) {
+                    return Err("min_confidence must be between 0 and 100".to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Store a new user profile, rejecting invalid settings and duplicate names
+fn insert_profile(
+    conn: &rusqlite::Connection,
+    name: String,
+    description: String,
+    settings: HashMap<String, String>,
+) -> Result<SettingsProfile, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Profile name cannot be empty".to_string());
+    }
+    validate_profile_settings(&settings)?;
+
+    if queries::select_settings_profile_by_name(conn, &name)
+        .map_err(|e| format!("Failed to check profile name: {}", e))?
+        .is_some()
+    {
+        return Err(format!("Profile already exists: {}", name));
+    }
+
+    let mut profile = SettingsProfile::new(name, description, settings);
+    profile.id = queries::insert_settings_profile(conn, &profile)
+        .map_err(|e| format!("Failed to create profile: {}", e))?;
+
+    Ok(profile)
+}
+
+/// Create a settings profile
+///
+/// # Arguments
+/// * `name` - Unique profile name
+/// * `settings` - Setting key/value pairs the profile applies
+/// * `description` - Optional description shown in the profile list
+///
+/// Returns: The created profile or error
+#[tauri::command]
+pub async fn create_profile(
+    name: String,
+    settings: HashMap<String, String>,
+    description: Option<String>,
+) -> Result<SettingsProfile, String> {
+    let conn = db::get_connection();
+    insert_profile(&conn, name, description.unwrap_or_default(), settings)
+}
+
+/// List all settings profiles, built-in presets first
+///
+/// Returns: List of profiles
+#[tauri::command]
+pub async fn list_profiles() -> Result<Vec<SettingsProfile>, String> {
+    let conn = db::get_connection();
+
+    queries::select_settings_profiles(&conn)
+        .map_err(|e| format!("Failed to fetch profiles: {}", e))
+}
+
+/// Apply a settings profile
+///
+/// Writes every setting in the profile in one transaction, so a failure leaves
+/// the current settings unchanged.
+///
+/// # Arguments
+/// * `profile_id` - ID of the profile to apply
+///
+/// Returns: Success or error
+#[tauri::command]
+pub async fn apply_profile(profile_id: i64) -> Result<(), String> {
+    let conn = db::get_connection();
+
+    let profile = queries::select_settings_profile(&conn, profile_id)
+        .map_err(|e| format!("Failed to fetch profile: {}", e))?
+        .ok_or_else(|| format!("Profile not found: {}", profile_id))?;
+
+    // Sorted so the write order (and the audit description) is stable
+    let mut settings: Vec<(&str, &str)> = profile
+        .settings
+        .iter()
+        .map(|(key, value)| (key.as_str(), value.as_str()))
+        .collect();
+    settings.sort();
+
+    queries::insert_or_update_settings(&conn, &settings)
+        .map_err(|e| format!("Failed to apply profile {}: {:#}", profile.name, e))?;
+
+    // Log audit event
+    if let Ok(event) = create_audit_event(
+        &conn,
+        "settings_profile_applied",
+        None,
+        None,
+        None,
+        &format!(
+            "Applied settings profile {}: {}",
+            profile.name,
+            settings
+                .iter()
+                .map(|(key, value)| format!("{} = {}", key, value))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    ) {
+        let _ = queries::insert_audit_event(&conn, &event);
+    }
+
+    Ok(())
+}
+
+/// Export a settings profile to a JSON file
+///
+/// # Arguments
+/// * `profile_id` - ID of the profile to export
+/// * `path` - Destination file
+///
+/// Returns: Success or error
+#[tauri::command]
+pub async fn export_profile(profile_id: i64, path: String) -> Result<(), String> {
+    path_validation::validate_export_path(Path::new(&path))
+        .map_err(|e| format!("Security: Invalid export path: {}", e))?;
+
+    let profile = {
+        let conn = db::get_connection();
+        queries::select_settings_profile(&conn, profile_id)
+            .map_err(|e| format!("Failed to fetch profile: {}", e))?
+            .ok_or_else(|| format!("Profile not found: {}", profile_id))?
+    }; // Connection dropped here
+
+    let json = serde_json::to_string_pretty(&profile.to_export())
+        .map_err(|e| format!("Failed to serialize profile: {}", e))?;
+
+    std::fs::write(&path, json)
+        .map_err(|e| format!("Failed to write profile to {}: {}", path, e))?;
+
+    Ok(())
+}
+
+/// Import a settings profile from a JSON file written by `export_profile`
+///
+/// The profile is stored under the name in the file, which must not already exist.
+///
+/// # Arguments
+/// * `path` - JSON file to read
+///
+/// Returns: The imported profile or error
+#[tauri::command]
+pub async fn import_profile(path: String) -> Result<SettingsProfile, String> {
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read profile from {}: {}", path, e))?;
+
+    let export: SettingsProfileExport = serde_json::from_str(&contents)
+        .map_err(|e| format!("Invalid profile file: {}", e))?;
+
+    let conn = db::get_connection();
+    insert_profile(&conn, export.name, export.description, export.settings)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::db::test_helpers::TestDbGuard;
@@ -418,4 +812,287 @@ mod tests {
         assert_eq!(settings[1].key, "banana");
         assert_eq!(settings[2].key, "zebra");
     }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_list_profiles_includes_built_ins() {
+        let _guard = TestDbGuard::new();
+
+        let profiles = list_profiles().await.unwrap();
+        let names: Vec<&str> = profiles.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["development", "staging", "production"]);
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_apply_built_in_profile_writes_settings_and_audit_event() {
+        let _guard = TestDbGuard::new();
+        let _ = update_settings("llm_scan_mode".to_string(), "regex_only".to_string()).await;
+
+        let production = list_profiles().await.unwrap().into_iter().find(|p| p.name == "production").unwrap();
+        apply_profile(production.id).await.unwrap();
+
+        let settings: HashMap<String, String> = get_settings()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|s| (s.key, s.value))
+            .collect();
+        assert_eq!(settings["llm_scan_mode"], "analyze_all");
+        assert_eq!(settings["cost_limit_per_scan"], "5.00");
+        assert_eq!(settings["min_confidence"], "80");
+
+        let conn = db::get_connection();
+        let events = queries::select_audit_events(&conn, 10).unwrap();
+        let applied = events.iter().find(|e| e.event_type == "settings_profile_applied").unwrap();
+        assert!(applied.description.contains("production"));
+        assert!(applied.description.contains("llm_scan_mode = analyze_all"));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_apply_profile_rolls_back_on_failure() {
+        let _guard = TestDbGuard::new();
+        let _ = update_settings("llm_scan_mode".to_string(), "regex_only".to_string()).await;
+
+        let profile = create_profile(
+            "broken".to_string(),
+            HashMap::from([
+                ("llm_scan_mode".to_string(), "smart".to_string()),
+                ("zz_reject".to_string(), "x".to_string()),
+            ]),
+            None,
+        )
+        .await
+        .unwrap();
+
+        // Make the last write of the profile fail after llm_scan_mode was written
+        {
+            let conn = db::get_connection();
+            conn.execute_batch(
+                "CREATE TEMP TRIGGER reject_setting BEFORE INSERT ON settings
+                 WHEN NEW.key = 'zz_reject'
+                 BEGIN SELECT RAISE(ABORT, 'rejected'); END;",
+            ).unwrap();
+        }
+
+        let result = apply_profile(profile.id).await;
+
+        {
+            let conn = db::get_connection();
+            conn.execute_batch("DROP TRIGGER reject_setting;").unwrap();
+        }
+
+        assert!(result.unwrap_err().contains("broken"));
+        let conn = db::get_connection();
+        let mode = queries::select_setting(&conn, "llm_scan_mode").unwrap().unwrap();
+        assert_eq!(mode.value, "regex_only");
+        assert!(queries::select_setting(&conn, "zz_reject").unwrap().is_none());
+        let events = queries::select_audit_events(&conn, 10).unwrap();
+        assert!(!events.iter().any(|e| e.event_type == "settings_profile_applied"));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_create_profile_validates_settings_and_names() {
+        let _guard = TestDbGuard::new();
+
+        let invalid_mode = HashMap::from([("llm_scan_mode".to_string(), "everything".to_string())]);
+        assert!(create_profile("ci".to_string(), invalid_mode, None).await.is_err());
+
+        let invalid_limit = HashMap::from([("cost_limit_per_scan".to_string(), "-1".to_string())]);
+        assert!(create_profile("ci".to_string(), invalid_limit, None).await.is_err());
+
+        let invalid_trust = HashMap::from([("auto_apply_trust_level".to_string(), "yolo".to_string())]);
+        assert!(create_profile("ci".to_string(), invalid_trust, None).await.is_err());
+
+        assert!(create_profile("ci".to_string(), HashMap::new(), None).await.is_err());
+
+        let settings = HashMap::from([("llm_scan_mode".to_string(), "smart".to_string())]);
+        let duplicate = create_profile("staging".to_string(), settings.clone(), None).await;
+        assert!(duplicate.unwrap_err().contains("already exists"));
+
+        let created = create_profile("ci".to_string(), settings, Some("CI runs".to_string())).await.unwrap();
+        assert!(created.id > 0);
+        assert!(!created.built_in);
+        assert_eq!(created.description, "CI runs");
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_export_and_import_profile() {
+        let guard = TestDbGuard::new();
+        let path = guard.temp_dir.path().join("team.json").to_string_lossy().to_string();
+
+        let settings = HashMap::from([
+            ("llm_scan_mode".to_string(), "smart".to_string()),
+            ("cost_limit_per_scan".to_string(), "2.50".to_string()),
+        ]);
+        let original = create_profile("team".to_string(), settings, None).await.unwrap();
+        export_profile(original.id, path.clone()).await.unwrap();
+
+        // Importing under an existing name fails
+        assert!(import_profile(path.clone()).await.unwrap_err().contains("already exists"));
+
+        {
+            let conn = db::get_connection();
+            conn.execute("DELETE FROM settings_profiles WHERE id = ?", [original.id]).unwrap();
+        }
+        let imported = import_profile(path).await.unwrap();
+        assert_eq!(imported.name, "team");
+        assert_eq!(imported.settings, original.settings);
+        assert_ne!(imported.id, original.id);
+    }
+
+    /// Insert `count` audit events, one per minute going back from 2025-03-31,
+    /// alternating between `project_id` and no project
+    fn insert_audit_events(count: i64, project_id: i64) {
+        let mut conn = db::get_connection();
+        let tx = conn.transaction().unwrap();
+        {
+            let mut stmt = tx
+                .prepare(
+                    "INSERT INTO audit_events (event_type, project_id, description, created_at)
+                     VALUES ('scan_completed', ?1, ?2, datetime('2025-03-31 23:59:00', ?3))",
+                )
+                .unwrap();
+            for i in 0..count {
+                let project = if i % 2 == 0 { Some(project_id) } else { None };
+                stmt.execute(rusqlite::params![
+                    project,
+                    format!("Event {}, with \"quotes\"", i),
+                    format!("-{} minutes", i)
+                ])
+                .unwrap();
+            }
+        }
+        tx.commit().unwrap();
+    }
+
+    fn create_project() -> i64 {
+        let conn = db::get_connection();
+        queries::insert_project(&conn, "audited", "/audited", None).unwrap()
+    }
+
+    fn read_csv(path: &Path) -> (Vec<String>, Vec<csv::StringRecord>) {
+        let mut reader = csv::Reader::from_path(path).unwrap();
+        let header = reader.headers().unwrap().iter().map(String::from).collect();
+        let records = reader.records().map(|r| r.unwrap()).collect();
+        (header, records)
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_export_audit_events_csv_large_range() {
+        let guard = TestDbGuard::new();
+        let project_id = create_project();
+        // 10,000 minutes reaches back to 2025-03-25
+        insert_audit_events(10_000, project_id);
+        let path = guard.temp_dir.path().join("audit.csv");
+
+        let stats = export_audit_events_csv(
+            None,
+            "2025-03-01".to_string(),
+            "2025-03-31".to_string(),
+            path.to_string_lossy().to_string(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(stats.rows_exported, 10_000);
+        let (header, records) = read_csv(&path);
+        assert_eq!(header, AUDIT_CSV_COLUMNS);
+        assert_eq!(records.len(), 10_000);
+        assert_eq!(&records[0][5], "Event 0, with \"quotes\"");
+        assert!(records.windows(2).all(|pair| pair[0][7] >= pair[1][7]), "not sorted newest first");
+
+        let bytes = std::fs::read(&path).unwrap();
+        assert_eq!(stats.file_size_bytes, bytes.len() as u64);
+        assert_eq!(stats.sha256_checksum, hex::encode(Sha256::digest(&bytes)));
+
+        let manifest: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(guard.temp_dir.path().join(EXPORT_MANIFEST_FILE)).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(manifest["file"], "audit.csv");
+        assert_eq!(manifest["rows_exported"], 10_000);
+        assert_eq!(manifest["sha256_checksum"], stats.sha256_checksum.as_str());
+        assert_eq!(manifest["start_date"], "2025-03-01");
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_export_audit_events_csv_filters_dates_and_project() {
+        let guard = TestDbGuard::new();
+        let project_id = create_project();
+        // 2 days of events: 2025-03-31 and 2025-03-30
+        insert_audit_events(2 * 24 * 60, project_id);
+        let path = guard.temp_dir.path().join("audit.csv").to_string_lossy().to_string();
+
+        let stats = export_audit_events_csv(None, "2025-03-31".to_string(), "2025-03-31".to_string(), path.clone())
+            .await
+            .unwrap();
+        assert_eq!(stats.rows_exported, 24 * 60);
+        let (_, records) = read_csv(Path::new(&path));
+        assert!(records.iter().all(|r| r[7].starts_with("2025-03-31")));
+
+        let stats = export_audit_events_csv(
+            Some(project_id),
+            "2025-03-30".to_string(),
+            "2025-03-31".to_string(),
+            path.clone(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(stats.rows_exported, 24 * 60);
+        let (_, records) = read_csv(Path::new(&path));
+        assert!(records.iter().all(|r| r[2] == project_id.to_string()));
+
+        // An empty range still writes the header
+        let stats = export_audit_events_csv(None, "2024-01-01".to_string(), "2024-01-31".to_string(), path.clone())
+            .await
+            .unwrap();
+        assert_eq!(stats.rows_exported, 0);
+        let (header, records) = read_csv(Path::new(&path));
+        assert_eq!(header, AUDIT_CSV_COLUMNS);
+        assert!(records.is_empty());
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_optimize_database_records_last_optimized_at() {
+        let _guard = TestDbGuard::new();
+
+        let stats = optimize_database().await.unwrap();
+        assert!(stats.size_after_bytes <= stats.size_before_bytes);
+
+        let settings = get_settings().await.unwrap();
+        assert!(settings.iter().any(|s| s.key == crate::models::LAST_OPTIMIZED_AT_SETTING));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_exports_reject_unsafe_paths() {
+        let _guard = TestDbGuard::new();
+
+        let result = export_audit_events_csv(None, "2025-03-01".to_string(), "2025-03-31".to_string(), "audit.csv".to_string()).await;
+        assert!(result.unwrap_err().contains("Security: Invalid export path"));
+
+        let result = export_profile(1, "/etc/ryn-profile.json".to_string()).await;
+        assert!(result.unwrap_err().contains("system directory"));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_export_audit_events_csv_rejects_bad_dates() {
+        let guard = TestDbGuard::new();
+        let path = guard.temp_dir.path().join("audit.csv").to_string_lossy().to_string();
+
+        let result = export_audit_events_csv(None, "03/01/2025".to_string(), "2025-03-31".to_string(), path.clone()).await;
+        assert!(result.unwrap_err().contains("Invalid start date"));
+
+        let result = export_audit_events_csv(None, "2025-03-31".to_string(), "2025-03-01".to_string(), path.clone()).await;
+        assert!(result.unwrap_err().contains("before start date"));
+        assert!(!Path::new(&path).exists());
+    }
 }
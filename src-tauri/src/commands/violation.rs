@@ -3,9 +3,13 @@
 //! Handles violation queries, filtering, and status updates
 
 use crate::db::{self, queries};
-use crate::models::{Violation, Control};
-use crate::utils::create_audit_event;
+use crate::models::{Violation, ViolationStatus, ViolationLifecycle, ViolationHistoryEntry, ScanStatus, SeverityCounts, StatusTransition, Control, FalsePositive, FALSE_POSITIVE_TUNING_THRESHOLD};
+use crate::models::false_positive::snippet_pattern;
+use crate::scanner::baseline::{Baseline, BaselineEntry, BASELINE_FILE_NAME};
+use crate::utils::{count_matches, create_audit_event, highlight_matches};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
 
 /// Violation filter options
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,13 +17,17 @@ pub struct ViolationFilters {
     pub severity: Option<Vec<String>>,
     pub control_id: Option<Vec<String>>,
     pub status: Option<Vec<String>>,
+    /// Control category (controls.category), e.g. "CC7 - System Monitoring"
+    pub control_category: Option<String>,
+    /// Monorepo package names, e.g. "@acme/api"
+    pub package_name: Option<Vec<String>>,
 }
 
 /// Get all violations for a scan with optional filters
 ///
 /// # Arguments
 /// * `scan_id` - ID of the scan
-/// * `filters` - Optional filters for severity, control_id, or status
+/// * `filters` - Optional filters for severity, control_id, status, control category or package
 ///
 /// Returns: List of violations matching the filters
 #[tauri::command]
@@ -27,54 +35,79 @@ pub async fn get_violations(
     scan_id: i64,
     filters: Option<ViolationFilters>,
 ) -> Result<Vec<Violation>, String> {
-    println!("[ryn] get_violations called: scan_id={}, filters={:?}", scan_id, filters);
+    eprintln!("[ryn] get_violations called: scan_id={}, filters={:?}", scan_id, filters);
 
     // Validate scan ID
     if scan_id <= 0 {
         let err_msg = format!("Invalid scan ID: must be greater than 0, got {}", scan_id);
-        println!("[ryn] get_violations validation failed: {}", err_msg);
+        eprintln!("[ryn] get_violations validation failed: {}", err_msg);
         return Err(err_msg);
     }
 
     let conn = db::get_connection();
 
-    // Get all violations for scan
-    let mut violations = queries::select_violations(&conn, scan_id)
+    // Get all violations for scan, limited to a control category if one is given
+    let category = filters.as_ref().and_then(|f| f.control_category.as_deref());
+    let result = match category {
+        Some(category) => queries::select_violations_by_category(&conn, scan_id, category),
+        None => queries::select_violations(&conn, scan_id),
+    };
+    let mut violations = result
         .map_err(|e| {
             let err_msg = format!("Failed to fetch violations: {}", e);
-            println!("[ryn] get_violations query failed: {}", err_msg);
+            eprintln!("[ryn] get_violations query failed: {}", err_msg);
             err_msg
         })?;
 
     // Apply filters if provided
     if let Some(f) = filters {
-        violations.retain(|v| {
-            // Filter by severity
-            if let Some(ref severities) = f.severity {
-                if !severities.contains(&v.severity) {
-                    return false;
-                }
+        retain_matching(&mut violations, &f);
+    }
+    sort_by_severity(&mut violations);
+
+    eprintln!("[ryn] get_violations success: found {} violations for scan_id={}", violations.len(), scan_id);
+    Ok(violations)
+}
+
+/// Keep the violations matching the severity, control_id, status and package filters
+///
+/// `control_category` is not checked here; callers query by category.
+fn retain_matching(violations: &mut Vec<Violation>, f: &ViolationFilters) {
+    violations.retain(|v| {
+        // Filter by severity
+        if let Some(ref severities) = f.severity {
+            if !severities.contains(&v.severity) {
+                return false;
+            }
+        }
+
+        // Filter by control_id
+        if let Some(ref control_ids) = f.control_id {
+            if !control_ids.contains(&v.control_id) {
+                return false;
             }
+        }
 
-            // Filter by control_id
-            if let Some(ref control_ids) = f.control_id {
-                if !control_ids.contains(&v.control_id) {
-                    return false;
-                }
+        // Filter by status
+        if let Some(ref statuses) = f.status {
+            if !statuses.contains(&v.status) {
+                return false;
             }
+        }
 
-            // Filter by status
-            if let Some(ref statuses) = f.status {
-                if !statuses.contains(&v.status) {
-                    return false;
-                }
+        // Filter by monorepo package; violations outside every package never match
+        if let Some(ref packages) = f.package_name {
+            if !v.package_name.as_ref().is_some_and(|package| packages.contains(package)) {
+                return false;
             }
+        }
 
-            true
-        });
-    }
+        true
+    });
+}
 
-    // Sort by severity (critical first) and line number
+/// Sort by severity (critical first) and line number
+fn sort_by_severity(violations: &mut [Violation]) {
     violations.sort_by(|a, b| {
         let severity_order = |s: &str| match s {
             "critical" => 0,
@@ -89,11 +122,89 @@ pub async fn get_violations(
             other => other,
         }
     });
+}
+
+/// Get a project's violations across all completed scans, deduplicated
+///
+/// Each `(control_id, file_path, line_number)` finding appears once, as its
+/// latest occurrence, with `first_seen_scan_id` and `last_seen_scan_id` set.
+///
+/// # Arguments
+/// * `project_id` - ID of the project
+/// * `filters` - Optional filters for severity, control_id, status, or control category
+///
+/// Returns: Unique violations matching the filters
+#[tauri::command]
+pub async fn get_project_violations(
+    project_id: i64,
+    filters: Option<ViolationFilters>,
+) -> Result<Vec<Violation>, String> {
+    eprintln!("[ryn] get_project_violations called: project_id={}, filters={:?}", project_id, filters);
+
+    if project_id <= 0 {
+        return Err(format!("Invalid project ID: must be greater than 0, got {}", project_id));
+    }
+
+    let conn = db::get_connection();
+
+    queries::select_project(&conn, project_id)
+        .map_err(|e| format!("Failed to fetch project: {}", e))?
+        .ok_or_else(|| format!("Project not found: {}", project_id))?;
+
+    let mut violations = queries::select_unique_violations_for_project(&conn, project_id)
+        .map_err(|e| format!("Failed to fetch project violations: {}", e))?;
+
+    if let Some(f) = filters {
+        if let Some(category) = f.control_category.as_deref() {
+            let in_category: Vec<String> = queries::select_controls(&conn)
+                .map_err(|e| format!("Failed to fetch controls: {}", e))?
+                .into_iter()
+                .filter(|c| c.category == category)
+                .map(|c| c.id)
+                .collect();
+            violations.retain(|v| in_category.contains(&v.control_id));
+        }
+        retain_matching(&mut violations, &f);
+    }
+    sort_by_severity(&mut violations);
 
-    println!("[ryn] get_violations success: found {} violations for scan_id={}", violations.len(), scan_id);
+    eprintln!("[ryn] get_project_violations success: found {} violations for project_id={}", violations.len(), project_id);
     Ok(violations)
 }
 
+/// Get a project's findings across scans with their lifecycle
+///
+/// Findings are identified by fingerprint, so one reported by every scan is
+/// a single entry with the scans it was first and last seen in.
+///
+/// # Arguments
+/// * `project_id` - ID of the project
+/// * `lifecycle` - Optional "new", "recurring" or "resolved"
+///
+/// Returns: History entries, most recently seen first
+#[tauri::command]
+pub async fn get_violation_history(
+    project_id: i64,
+    lifecycle: Option<String>,
+) -> Result<Vec<ViolationHistoryEntry>, String> {
+    eprintln!("[ryn] get_violation_history called: project_id={}, lifecycle={:?}", project_id, lifecycle);
+
+    if let Some(lifecycle) = lifecycle.as_deref() {
+        if ViolationLifecycle::from_str(lifecycle).is_none() {
+            return Err(format!("Invalid lifecycle '{}': expected new, recurring or resolved", lifecycle));
+        }
+    }
+
+    let conn = db::get_connection();
+
+    queries::select_project(&conn, project_id)
+        .map_err(|e| format!("Failed to fetch project: {}", e))?
+        .ok_or_else(|| format!("Project not found: {}", project_id))?;
+
+    queries::select_violation_history(&conn, project_id, lifecycle.as_deref())
+        .map_err(|e| format!("Failed to fetch violation history: {}", e))
+}
+
 /// Get a single violation with full details
 ///
 /// # Arguments
@@ -102,12 +213,12 @@ pub async fn get_violations(
 /// Returns: Violation detail object with related control and fix information
 #[tauri::command]
 pub async fn get_violation(violation_id: i64) -> Result<ViolationDetail, String> {
-    println!("[ryn] get_violation called: violation_id={}", violation_id);
+    eprintln!("[ryn] get_violation called: violation_id={}", violation_id);
 
     // Validate violation ID
     if violation_id <= 0 {
         let err_msg = format!("Invalid violation ID: must be greater than 0, got {}", violation_id);
-        println!("[ryn] get_violation validation failed: {}", err_msg);
+        eprintln!("[ryn] get_violation validation failed: {}", err_msg);
         return Err(err_msg);
     }
 
@@ -117,12 +228,12 @@ pub async fn get_violation(violation_id: i64) -> Result<ViolationDetail, String>
     let violation = queries::select_violation(&conn, violation_id)
         .map_err(|e| {
             let err_msg = format!("Failed to fetch violation: {}", e);
-            println!("[ryn] get_violation query failed: {}", err_msg);
+            eprintln!("[ryn] get_violation query failed: {}", err_msg);
             err_msg
         })?
         .ok_or_else(|| {
             let err_msg = format!("Violation not found: {}", violation_id);
-            println!("[ryn] get_violation not found: {}", err_msg);
+            eprintln!("[ryn] get_violation not found: {}", err_msg);
             err_msg
         })?;
 
@@ -138,11 +249,11 @@ pub async fn get_violation(violation_id: i64) -> Result<ViolationDetail, String>
     let scan = queries::select_scan(&conn, violation.scan_id)
         .map_err(|e| {
             let err_msg = format!("Failed to fetch scan: {}", e);
-            println!("[ryn] get_violation scan query failed: {}", err_msg);
+            eprintln!("[ryn] get_violation scan query failed: {}", err_msg);
             err_msg
         })?;
 
-    println!("[ryn] get_violation success: violation_id={}", violation_id);
+    eprintln!("[ryn] get_violation success: violation_id={}", violation_id);
     Ok(ViolationDetail {
         violation,
         control,
@@ -161,12 +272,12 @@ pub async fn get_violation(violation_id: i64) -> Result<ViolationDetail, String>
 /// Returns: Success or error
 #[tauri::command]
 pub async fn dismiss_violation(violation_id: i64) -> Result<(), String> {
-    println!("[ryn] dismiss_violation called: violation_id={}", violation_id);
+    eprintln!("[ryn] dismiss_violation called: violation_id={}", violation_id);
 
     // Validate violation ID
     if violation_id <= 0 {
         let err_msg = format!("Invalid violation ID: must be greater than 0, got {}", violation_id);
-        println!("[ryn] dismiss_violation validation failed: {}", err_msg);
+        eprintln!("[ryn] dismiss_violation validation failed: {}", err_msg);
         return Err(err_msg);
     }
 
@@ -176,20 +287,20 @@ pub async fn dismiss_violation(violation_id: i64) -> Result<(), String> {
     let violation = queries::select_violation(&conn, violation_id)
         .map_err(|e| {
             let err_msg = format!("Failed to fetch violation: {}", e);
-            println!("[ryn] dismiss_violation query failed: {}", err_msg);
+            eprintln!("[ryn] dismiss_violation query failed: {}", err_msg);
             err_msg
         })?
         .ok_or_else(|| {
             let err_msg = format!("Violation not found: {}", violation_id);
-            println!("[ryn] dismiss_violation not found: {}", err_msg);
+            eprintln!("[ryn] dismiss_violation not found: {}", err_msg);
             err_msg
         })?;
 
     // Update status to dismissed
-    queries::update_violation_status(&conn, violation_id, "dismissed")
+    queries::update_violation_status(&conn, violation_id, "dismissed", None)
         .map_err(|e| {
             let err_msg = format!("Failed to dismiss violation: {}", e);
-            println!("[ryn] dismiss_violation update failed: {}", err_msg);
+            eprintln!("[ryn] dismiss_violation update failed: {}", err_msg);
             err_msg
         })?;
 
@@ -211,10 +322,355 @@ pub async fn dismiss_violation(violation_id: i64) -> Result<(), String> {
         }
     }
 
-    println!("[ryn] dismiss_violation success: violation_id={}", violation_id);
+    eprintln!("[ryn] dismiss_violation success: violation_id={}", violation_id);
+    Ok(())
+}
+
+/// Report a violation as a false positive
+///
+/// Dismisses the violation, records the report for pattern feedback, and logs a
+/// `pattern_tuning_warning` audit event the first time more than
+/// `FALSE_POSITIVE_TUNING_THRESHOLD` reports share a control and snippet pattern.
+/// The project's recent reports are included in later LLM analysis prompts as
+/// examples not to report again.
+///
+/// # Arguments
+/// * `violation_id` - Violation ID
+/// * `reason` - Why the user believes the detection is wrong
+///
+/// Returns: Success or error
+#[tauri::command]
+pub async fn report_false_positive(violation_id: i64, reason: String) -> Result<(), String> {
+    eprintln!("[ryn] report_false_positive called: violation_id={}", violation_id);
+
+    if violation_id <= 0 {
+        let err_msg = format!("Invalid violation ID: must be greater than 0, got {}", violation_id);
+        eprintln!("[ryn] report_false_positive validation failed: {}", err_msg);
+        return Err(err_msg);
+    }
+
+    let conn = db::get_connection();
+
+    let violation = queries::select_violation(&conn, violation_id)
+        .map_err(|e| format!("Failed to fetch violation: {}", e))?
+        .ok_or_else(|| format!("Violation not found: {}", violation_id))?;
+
+    let scan = queries::select_scan(&conn, violation.scan_id)
+        .map_err(|e| format!("Failed to fetch scan: {}", e))?
+        .ok_or_else(|| format!("Scan not found: {}", violation.scan_id))?;
+
+    // Dismiss with reason "false_positive"
+    queries::update_violation_status(&conn, violation_id, "dismissed", Some(&format!("False positive: {}", reason)))
+        .map_err(|e| format!("Failed to dismiss violation: {}", e))?;
+
+    if let Ok(event) = create_audit_event(
+        &conn,
+        "violation_dismissed",
+        Some(scan.project_id),
+        Some(violation_id),
+        None,
+        &format!("Dismissed violation as false positive: {}", violation.description),
+    ) {
+        let event = event.with_metadata(serde_json::json!({
+            "reason": "false_positive",
+            "details": reason,
+        }));
+        let _ = queries::insert_audit_event(&conn, &event);
+    }
+
+    // Record the report for pattern feedback
+    let report = FalsePositive::from_violation(scan.project_id, &violation, reason);
+    queries::insert_false_positive(&conn, &report)
+        .map_err(|e| format!("Failed to record false positive: {}", e))?;
+
+    // Warn once when a control's pattern keeps producing false positives
+    let pattern = snippet_pattern(&violation.code_snippet);
+    let matching = queries::select_false_positives_by_control(&conn, &violation.control_id)
+        .map_err(|e| format!("Failed to fetch false positives: {}", e))?
+        .iter()
+        .filter(|r| snippet_pattern(&r.code_snippet) == pattern)
+        .count();
+
+    if matching == FALSE_POSITIVE_TUNING_THRESHOLD + 1 {
+        eprintln!(
+            "[ryn] report_false_positive: pattern for {} crossed tuning threshold ({} reports)",
+            violation.control_id, matching
+        );
+        if let Ok(event) = create_audit_event(
+            &conn,
+            "pattern_tuning_warning",
+            Some(scan.project_id),
+            Some(violation_id),
+            None,
+            &format!("Pattern may need tuning: {}", violation.control_id),
+        ) {
+            let event = event.with_metadata(serde_json::json!({
+                "control_id": violation.control_id,
+                "snippet_pattern": pattern,
+                "report_count": matching,
+            }));
+            let _ = queries::insert_audit_event(&conn, &event);
+        }
+    }
+
+    eprintln!("[ryn] report_false_positive success: violation_id={}", violation_id);
     Ok(())
 }
 
+/// Change the status of several violations at once
+///
+/// Used by compliance workflows, e.g. marking a batch of findings as
+/// `accepted_risk` with the business reason. All violations are updated in
+/// one transaction, so if any ID is unknown none change. One aggregate
+/// `violations_status_updated` audit event lists the count, status and IDs.
+///
+/// # Arguments
+/// * `violation_ids` - Violations to update (duplicates are ignored)
+/// * `new_status` - Any `ViolationStatus` value
+/// * `reason` - Why the status changed; stored on each violation
+///
+/// Returns: Number of violations updated
+#[tauri::command]
+pub async fn update_violations_status(
+    violation_ids: Vec<i64>,
+    new_status: String,
+    reason: String,
+) -> Result<i64, String> {
+    eprintln!(
+        "[ryn] update_violations_status called: {} violations -> {}",
+        violation_ids.len(),
+        new_status
+    );
+
+    let status = ViolationStatus::from_str(&new_status)
+        .ok_or_else(|| format!("Invalid violation status: {}", new_status))?;
+
+    let reason = reason.trim();
+    if reason.is_empty() {
+        return Err("A reason is required for bulk status changes".to_string());
+    }
+
+    let mut ids = violation_ids;
+    ids.sort_unstable();
+    ids.dedup();
+    if ids.is_empty() {
+        return Err("No violations selected".to_string());
+    }
+
+    let conn = db::get_connection();
+
+    let updated = queries::update_violations_status(&conn, &ids, status.as_str(), reason)
+        .map_err(|e| format!("Failed to update violation status: {:#}", e))?;
+
+    let project_id = queries::select_violation(&conn, ids[0])
+        .ok()
+        .flatten()
+        .and_then(|v| queries::select_scan(&conn, v.scan_id).ok().flatten())
+        .map(|scan| scan.project_id);
+
+    if let Ok(event) = create_audit_event(
+        &conn,
+        "violations_status_updated",
+        project_id,
+        None,
+        None,
+        &format!("Changed status of {} violations to {}: {}", updated, status.as_str(), reason),
+    ) {
+        let event = event.with_metadata(serde_json::json!({
+            "status": status.as_str(),
+            "count": updated,
+            "violation_ids": ids,
+            "reason": reason,
+        }));
+        let _ = queries::insert_audit_event(&conn, &event);
+    }
+
+    eprintln!("[ryn] update_violations_status success: {} violations updated", updated);
+    Ok(updated)
+}
+
+/// Reopen a scan's dismissed and fixed violations for re-audit
+///
+/// Sets `dismissed`, `fixed` and `fixed_verified` violations back to `open`.
+/// `accepted_risk` and `resolved` violations are left alone.
+///
+/// # Arguments
+/// * `scan_id` - ID of the scan
+///
+/// Returns: Number of violations reopened
+#[tauri::command]
+pub async fn reopen_violations(scan_id: i64) -> Result<i64, String> {
+    let conn = db::get_connection();
+
+    let scan = queries::select_scan(&conn, scan_id)
+        .map_err(|e| format!("Failed to fetch scan: {}", e))?
+        .ok_or_else(|| format!("Scan not found: {}", scan_id))?;
+
+    let reopened = queries::reopen_violations(&conn, scan_id)
+        .map_err(|e| format!("Failed to reopen violations: {}", e))?;
+
+    if let Ok(event) = create_audit_event(
+        &conn,
+        "violations_reopened",
+        Some(scan.project_id),
+        None,
+        None,
+        &format!("Reopened {} violations in scan {} for re-audit", reopened, scan_id),
+    ) {
+        let _ = queries::insert_audit_event(&conn, &event);
+    }
+
+    eprintln!("[ryn] reopen_violations: {} violations reopened in scan {}", reopened, scan_id);
+    Ok(reopened)
+}
+
+/// Get a violation's status history, oldest change first
+///
+/// # Arguments
+/// * `violation_id` - Violation ID
+///
+/// Returns: Every status transition with its reason and time
+#[tauri::command]
+pub async fn get_violation_status_history(violation_id: i64) -> Result<Vec<StatusTransition>, String> {
+    let conn = db::get_connection();
+
+    queries::select_violation(&conn, violation_id)
+        .map_err(|e| format!("Failed to fetch violation: {}", e))?
+        .ok_or_else(|| format!("Violation not found: {}", violation_id))?;
+
+    queries::select_violation_status_history(&conn, violation_id)
+        .map_err(|e| format!("Failed to fetch status history: {}", e))
+}
+
+/// Category for violations whose control isn't in the controls table (e.g. SARIF imports)
+const UNCATEGORIZED: &str = "Uncategorized";
+
+/// Get a scan's violations grouped by control category
+///
+/// # Arguments
+/// * `scan_id` - ID of the scan
+///
+/// Returns: Map of control category (e.g. "CC6 - Access Control") to its violations
+#[tauri::command]
+pub async fn get_violations_by_category(scan_id: i64) -> Result<HashMap<String, Vec<Violation>>, String> {
+    eprintln!("[ryn] get_violations_by_category called: scan_id={}", scan_id);
+
+    let conn = db::get_connection();
+
+    let categories: HashMap<String, String> = queries::select_controls(&conn)
+        .map_err(|e| format!("Failed to fetch controls: {}", e))?
+        .into_iter()
+        .map(|c| (c.id, c.category))
+        .collect();
+
+    let violations = queries::select_violations(&conn, scan_id)
+        .map_err(|e| format!("Failed to fetch violations: {}", e))?;
+
+    let mut by_category: HashMap<String, Vec<Violation>> = HashMap::new();
+    for violation in violations {
+        let category = categories
+            .get(&violation.control_id)
+            .cloned()
+            .unwrap_or_else(|| UNCATEGORIZED.to_string());
+        by_category.entry(category).or_default().push(violation);
+    }
+
+    Ok(by_category)
+}
+
+/// Violation counts for one monorepo package in a scan
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PackageViolationSummary {
+    /// None for files outside every package, or a project that isn't a monorepo
+    pub package_name: Option<String>,
+    pub total: i64,
+    /// Violations still open
+    pub open: i64,
+    pub severity_counts: SeverityCounts,
+}
+
+/// Get a scan's violation counts per monorepo package
+///
+/// # Arguments
+/// * `scan_id` - ID of the scan
+///
+/// Returns: One summary per package with violations, most violations first
+#[tauri::command]
+pub async fn get_violations_by_package(scan_id: i64) -> Result<Vec<PackageViolationSummary>, String> {
+    eprintln!("[ryn] get_violations_by_package called: scan_id={}", scan_id);
+
+    let conn = db::get_connection();
+    let violations = queries::select_violations(&conn, scan_id)
+        .map_err(|e| format!("Failed to fetch violations: {}", e))?;
+
+    Ok(summarize_by_package(&violations))
+}
+
+fn summarize_by_package(violations: &[Violation]) -> Vec<PackageViolationSummary> {
+    let mut by_package: BTreeMap<Option<&str>, PackageViolationSummary> = BTreeMap::new();
+    for violation in violations {
+        let summary = by_package
+            .entry(violation.package_name.as_deref())
+            .or_insert_with(|| PackageViolationSummary {
+                package_name: violation.package_name.clone(),
+                total: 0,
+                open: 0,
+                severity_counts: SeverityCounts::default(),
+            });
+        summary.total += 1;
+        if violation.status == ViolationStatus::Open.as_str() {
+            summary.open += 1;
+        }
+        match violation.severity.as_str() {
+            "critical" => summary.severity_counts.critical += 1,
+            "high" => summary.severity_counts.high += 1,
+            "medium" => summary.severity_counts.medium += 1,
+            "low" => summary.severity_counts.low += 1,
+            _ => {}
+        }
+    }
+
+    let mut summaries: Vec<_> = by_package.into_values().collect();
+    // Stable sort keeps packages with equal totals in name order
+    summaries.sort_by_key(|s| std::cmp::Reverse(s.total));
+    summaries
+}
+
+/// Search a scan's violations for a text query
+///
+/// Matches each word of the query case-insensitively against the description,
+/// code snippet and LLM reasoning. Highlighting is applied to copies in the
+/// response; stored violations are unchanged.
+///
+/// # Arguments
+/// * `scan_id` - ID of the scan
+/// * `query` - Words to search for (e.g. "password config")
+///
+/// Returns: Matching violations with highlighted fields, most matches first
+#[tauri::command]
+pub async fn search_violations(scan_id: i64, query: String) -> Result<Vec<ViolationWithHighlight>, String> {
+    eprintln!("[ryn] search_violations called: scan_id={}, query={:?}", scan_id, query);
+
+    if query.trim().is_empty() {
+        return Err("Search query cannot be empty".to_string());
+    }
+
+    let conn = db::get_connection();
+
+    let violations = queries::select_violations(&conn, scan_id)
+        .map_err(|e| format!("Failed to fetch violations: {}", e))?;
+
+    let mut results: Vec<ViolationWithHighlight> = violations
+        .into_iter()
+        .map(|violation| ViolationWithHighlight::new(violation, &query))
+        .filter(|result| result.match_count > 0)
+        .collect();
+    results.sort_by_key(|r| std::cmp::Reverse(r.match_count));
+
+    eprintln!("[ryn] search_violations success: found {} matches for scan_id={}", results.len(), scan_id);
+    Ok(results)
+}
+
 /// Violation detail response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ViolationDetail {
@@ -224,12 +680,116 @@ pub struct ViolationDetail {
     pub scan: Option<crate::models::Scan>,
 }
 
+/// Violation search result with the matched words marked as `**...**`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViolationWithHighlight {
+    /// The violation as stored
+    pub violation: Violation,
+    pub description: String,
+    pub code_snippet: String,
+    pub llm_reasoning: Option<String>,
+    /// Matches across description, code snippet and LLM reasoning
+    pub match_count: i32,
+}
+
+impl ViolationWithHighlight {
+    fn new(violation: Violation, query: &str) -> Self {
+        let reasoning = violation.llm_reasoning.as_deref().unwrap_or_default();
+        let match_count = count_matches(&violation.description, query)
+            + count_matches(&violation.code_snippet, query)
+            + count_matches(reasoning, query);
+
+        Self {
+            description: highlight_matches(&violation.description, query),
+            code_snippet: highlight_matches(&violation.code_snippet, query),
+            llm_reasoning: violation.llm_reasoning.as_deref().map(|r| highlight_matches(r, query)),
+            match_count,
+            violation,
+        }
+    }
+}
+
+/// Accept a scan's open and accepted-risk violations by writing them to the
+/// project's `.ryn-baseline.json`
+///
+/// Later scans of the project no longer report these violations. An existing
+/// baseline file is replaced.
+///
+/// # Arguments
+/// * `scan_id` - ID of the completed scan to take violations from
+///
+/// Returns: The baseline that was written
+#[tauri::command]
+pub async fn create_baseline(scan_id: i64) -> Result<Baseline, String> {
+    eprintln!("[ryn] create_baseline called: scan_id={}", scan_id);
+
+    let conn = db::get_connection();
+
+    let scan = queries::select_scan(&conn, scan_id)
+        .map_err(|e| format!("Failed to fetch scan: {}", e))?
+        .ok_or_else(|| format!("Scan not found: {}", scan_id))?;
+    if scan.status != ScanStatus::Completed.as_str() {
+        return Err(format!("Scan {} has not completed", scan_id));
+    }
+    let project = queries::select_project(&conn, scan.project_id)
+        .map_err(|e| format!("Failed to fetch project: {}", e))?
+        .ok_or_else(|| format!("Project not found: {}", scan.project_id))?;
+
+    let accepted: Vec<Violation> = queries::select_violations(&conn, scan_id)
+        .map_err(|e| format!("Failed to fetch violations: {}", e))?
+        .into_iter()
+        .filter(|v| v.status == ViolationStatus::Open.as_str() || v.status == ViolationStatus::AcceptedRisk.as_str())
+        .collect();
+
+    let project_root = Path::new(&project.path);
+    let baseline = Baseline::from_violations(&accepted, scan_id, project_root);
+    let path = baseline
+        .write(project_root)
+        .map_err(|e| format!("Failed to write baseline: {:#}", e))?;
+
+    if let Ok(event) = create_audit_event(
+        &conn,
+        "baseline_created",
+        Some(project.id),
+        None,
+        None,
+        &format!("Baselined {} violations from scan {} in {}", baseline.entries.len(), scan_id, BASELINE_FILE_NAME),
+    ) {
+        let _ = queries::insert_audit_event(&conn, &event);
+    }
+
+    eprintln!("[ryn] create_baseline: wrote {} entries to {}", baseline.entries.len(), path.display());
+    Ok(baseline)
+}
+
+/// List the violations a project's `.ryn-baseline.json` suppresses
+///
+/// # Arguments
+/// * `project_id` - ID of the project
+///
+/// Returns: The baseline's entries (empty if the project has no baseline)
+#[tauri::command]
+pub async fn list_suppressed(project_id: i64) -> Result<Vec<BaselineEntry>, String> {
+    let project = {
+        let conn = db::get_connection();
+        queries::select_project(&conn, project_id)
+            .map_err(|e| format!("Failed to fetch project: {}", e))?
+            .ok_or_else(|| format!("Project not found: {}", project_id))?
+    }; // Connection dropped here
+
+    let baseline = Baseline::load(Path::new(&project.path))
+        .map_err(|e| format!("Failed to read baseline: {:#}", e))?;
+    Ok(baseline.map(|b| b.entries).unwrap_or_default())
+}
+
 #[cfg(test)]
 mod tests {
     use crate::db::test_helpers::TestDbGuard;
     use super::*;
 
     fn create_test_violation(scan_id: i64) -> i64 {
+        // Each call is a separate finding, so it goes on its own line
+        static NEXT_LINE: std::sync::atomic::AtomicI64 = std::sync::atomic::AtomicI64::new(1);
         let violation = Violation {
             id: 0,
             scan_id,
@@ -237,7 +797,7 @@ mod tests {
             severity: "high".to_string(),
             description: "Missing login_required decorator".to_string(),
             file_path: "views.py".to_string(),
-            line_number: 42,
+            line_number: NEXT_LINE.fetch_add(1, std::sync::atomic::Ordering::Relaxed),
             code_snippet: "def get_user(request):".to_string(),
             status: "open".to_string(),
             detected_at: chrono::Utc::now().to_rfc3339(),
@@ -247,6 +807,15 @@ mod tests {
             regex_reasoning: None,
             function_name: None,
             class_name: None,
+            decorator_name: None,
+            is_new: false,
+            last_status_change_reason: None,
+            package_name: None,
+            fingerprint: String::new(),
+            lifecycle: "new".to_string(),
+            jira_issue_key: None,
+            first_seen_scan_id: None,
+            last_seen_scan_id: None,
         };
 
         let conn = db::get_connection();
@@ -255,7 +824,7 @@ mod tests {
 
     fn create_test_scan(project_id: i64) -> i64 {
         let conn = db::get_connection();
-        queries::insert_scan(&conn, project_id).unwrap()
+        queries::insert_scan(&conn, project_id, "regex_only").unwrap()
     }
 
     fn create_test_project() -> i64 {
@@ -318,7 +887,7 @@ mod tests {
                     description: format!("Violation with {} severity", severity),
                     file_path: "test.py".to_string(),
                     line_number: 1,
-                    code_snippet: "code".to_string(),
+                    code_snippet: format!("{}_code", severity),
                     status: "open".to_string(),
                     detected_at: chrono::Utc::now().to_rfc3339(),
                     detection_method: "regex".to_string(),
@@ -327,6 +896,15 @@ mod tests {
                     regex_reasoning: None,
                 function_name: None,
                 class_name: None,
+                decorator_name: None,
+                is_new: false,
+                last_status_change_reason: None,
+                package_name: None,
+                fingerprint: String::new(),
+                lifecycle: "new".to_string(),
+                jira_issue_key: None,
+                first_seen_scan_id: None,
+                last_seen_scan_id: None,
                 };
                 let _ = queries::insert_violation(&conn, &violation);
             }
@@ -336,6 +914,8 @@ mod tests {
             severity: Some(vec!["high".to_string()]),
             control_id: None,
             status: None,
+            control_category: None,
+            package_name: None,
         };
 
         let result = get_violations(scan_id, Some(filters)).await;
@@ -375,6 +955,15 @@ mod tests {
                     regex_reasoning: None,
                 function_name: None,
                 class_name: None,
+                decorator_name: None,
+                is_new: false,
+                last_status_change_reason: None,
+                package_name: None,
+                fingerprint: String::new(),
+                lifecycle: "new".to_string(),
+                jira_issue_key: None,
+                first_seen_scan_id: None,
+                last_seen_scan_id: None,
                 };
                 let _ = queries::insert_violation(&conn, &violation);
             }
@@ -509,6 +1098,15 @@ mod tests {
                     regex_reasoning: None,
                 function_name: None,
                 class_name: None,
+                decorator_name: None,
+                is_new: false,
+                last_status_change_reason: None,
+                package_name: None,
+                fingerprint: String::new(),
+                lifecycle: "new".to_string(),
+                jira_issue_key: None,
+                first_seen_scan_id: None,
+                last_seen_scan_id: None,
                 };
                 let _ = queries::insert_violation(&conn, &violation);
             }
@@ -518,6 +1116,8 @@ mod tests {
             severity: None,
             control_id: Some(vec!["CC6.1".to_string()]),
             status: None,
+            control_category: None,
+            package_name: None,
         };
 
         let result = get_violations(scan_id, Some(filters)).await;
@@ -557,6 +1157,15 @@ mod tests {
                     regex_reasoning: None,
                 function_name: None,
                 class_name: None,
+                decorator_name: None,
+                is_new: false,
+                last_status_change_reason: None,
+                package_name: None,
+                fingerprint: String::new(),
+                lifecycle: "new".to_string(),
+                jira_issue_key: None,
+                first_seen_scan_id: None,
+                last_seen_scan_id: None,
                 };
                 let _ = queries::insert_violation(&conn, &violation);
             }
@@ -566,6 +1175,8 @@ mod tests {
             severity: None,
             control_id: None,
             status: Some(vec!["open".to_string()]),
+            control_category: None,
+            package_name: None,
         };
 
         let result = get_violations(scan_id, Some(filters)).await;
@@ -604,6 +1215,15 @@ mod tests {
                 regex_reasoning: None,
                 function_name: None,
                 class_name: None,
+                decorator_name: None,
+                is_new: false,
+                last_status_change_reason: None,
+                package_name: None,
+                fingerprint: String::new(),
+                lifecycle: "new".to_string(),
+                jira_issue_key: None,
+                first_seen_scan_id: None,
+                last_seen_scan_id: None,
             };
             let _ = queries::insert_violation(&conn, &violation);
         }
@@ -612,6 +1232,8 @@ mod tests {
             severity: Some(vec!["critical".to_string()]),
             control_id: Some(vec!["CC6.1".to_string()]),
             status: Some(vec!["open".to_string()]),
+            control_category: None,
+            package_name: None,
         };
 
         let result = get_violations(scan_id, Some(filters)).await;
@@ -631,10 +1253,704 @@ mod tests {
             severity: Some(vec!["low".to_string()]),
             control_id: None,
             status: None,
+            control_category: None,
+            package_name: None,
         };
 
         let result = get_violations(scan_id, Some(filters)).await;
         assert!(result.is_ok());
         assert_eq!(result.unwrap().len(), 0);
     }
+
+    fn create_violation_with_snippet(scan_id: i64, code_snippet: &str) -> i64 {
+        let violation = Violation::new(
+            scan_id,
+            "CC6.7".to_string(),
+            crate::models::Severity::High,
+            "Hardcoded secret".to_string(),
+            "settings.py".to_string(),
+            1,
+            code_snippet.to_string(),
+        );
+        let conn = db::get_connection();
+        queries::insert_violation(&conn, &violation).unwrap()
+    }
+
+    fn count_tuning_warnings() -> i64 {
+        let conn = db::get_connection();
+        conn.query_row(
+            "SELECT COUNT(*) FROM audit_events WHERE event_type = 'pattern_tuning_warning'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_report_false_positive_dismisses_and_records() {
+        let _guard = TestDbGuard::new();
+        let project_id = create_test_project();
+        let scan_id = create_test_scan(project_id);
+        let violation_id = create_violation_with_snippet(scan_id, "token = \"abc\"");
+
+        report_false_positive(violation_id, "Test fixture".to_string()).await.unwrap();
+
+        let conn = db::get_connection();
+        let violation = queries::select_violation(&conn, violation_id).unwrap().unwrap();
+        assert_eq!(violation.status, "dismissed");
+
+        let reports = queries::select_false_positives_by_project(&conn, project_id).unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].violation_id, Some(violation_id));
+        assert_eq!(reports[0].control_id, "CC6.7");
+        assert_eq!(reports[0].reason, "Test fixture");
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_report_false_positive_threshold_not_reached() {
+        let _guard = TestDbGuard::new();
+        let project_id = create_test_project();
+        let scan_id = create_test_scan(project_id);
+
+        for i in 0..FALSE_POSITIVE_TUNING_THRESHOLD {
+            let id = create_violation_with_snippet(scan_id, &format!("token = \"value{}\"", i));
+            report_false_positive(id, "fixture".to_string()).await.unwrap();
+        }
+
+        assert_eq!(count_tuning_warnings(), 0);
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_report_false_positive_threshold_emits_warning_once() {
+        let _guard = TestDbGuard::new();
+        let project_id = create_test_project();
+        let scan_id = create_test_scan(project_id);
+
+        for i in 0..FALSE_POSITIVE_TUNING_THRESHOLD + 2 {
+            let id = create_violation_with_snippet(scan_id, &format!("token = \"value{}\"", i));
+            report_false_positive(id, "fixture".to_string()).await.unwrap();
+        }
+
+        assert_eq!(count_tuning_warnings(), 1);
+
+        let conn = db::get_connection();
+        let description: String = conn
+            .query_row(
+                "SELECT description FROM audit_events WHERE event_type = 'pattern_tuning_warning'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(description, "Pattern may need tuning: CC6.7");
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_report_false_positive_dissimilar_snippets_do_not_warn() {
+        let _guard = TestDbGuard::new();
+        let project_id = create_test_project();
+        let scan_id = create_test_scan(project_id);
+
+        let snippets = [
+            "token = \"abc\"",
+            "password = \"abc\"",
+            "api_key: \"abc\"",
+            "SECRET_KEY = get_secret(\"abc\")",
+        ];
+        for snippet in snippets {
+            let id = create_violation_with_snippet(scan_id, snippet);
+            report_false_positive(id, "fixture".to_string()).await.unwrap();
+        }
+
+        assert_eq!(count_tuning_warnings(), 0);
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_report_false_positive_nonexistent_violation() {
+        let _guard = TestDbGuard::new();
+        let result = report_false_positive(999, "fixture".to_string()).await;
+        assert!(result.is_err());
+    }
+
+    fn violation_status(violation_id: i64) -> (String, Option<String>) {
+        let conn = db::get_connection();
+        let violation = queries::select_violation(&conn, violation_id).unwrap().unwrap();
+        (violation.status, violation.last_status_change_reason)
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_update_violations_status_bulk() {
+        let _guard = TestDbGuard::new();
+        let project_id = create_test_project();
+        let scan_id = create_test_scan(project_id);
+        let ids: Vec<i64> = (0..3).map(|_| create_test_violation(scan_id)).collect();
+
+        let updated = update_violations_status(
+            vec![ids[0], ids[1], ids[1]],
+            "accepted_risk".to_string(),
+            "Internal admin tool, behind VPN".to_string(),
+        ).await.unwrap();
+
+        assert_eq!(updated, 2);
+        assert_eq!(
+            violation_status(ids[0]),
+            ("accepted_risk".to_string(), Some("Internal admin tool, behind VPN".to_string()))
+        );
+        assert_eq!(violation_status(ids[2]), ("open".to_string(), None));
+
+        let conn = db::get_connection();
+        let (description, metadata): (String, String) = conn
+            .query_row(
+                "SELECT description, metadata FROM audit_events WHERE event_type = 'violations_status_updated'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(description, "Changed status of 2 violations to accepted_risk: Internal admin tool, behind VPN");
+        let metadata: serde_json::Value = serde_json::from_str(&metadata).unwrap();
+        assert_eq!(metadata["violation_ids"], serde_json::json!([ids[0], ids[1]]));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_update_violations_status_rejects_bad_input() {
+        let _guard = TestDbGuard::new();
+        let project_id = create_test_project();
+        let scan_id = create_test_scan(project_id);
+        let id = create_test_violation(scan_id);
+
+        assert!(update_violations_status(vec![id], "ignored".to_string(), "x".to_string()).await.is_err());
+        assert!(update_violations_status(vec![id], "dismissed".to_string(), "  ".to_string()).await.is_err());
+        assert!(update_violations_status(vec![], "dismissed".to_string(), "x".to_string()).await.is_err());
+        assert_eq!(violation_status(id), ("open".to_string(), None));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_update_violations_status_is_atomic() {
+        let _guard = TestDbGuard::new();
+        let project_id = create_test_project();
+        let scan_id = create_test_scan(project_id);
+        let ids: Vec<i64> = (0..2).map(|_| create_test_violation(scan_id)).collect();
+
+        let result = update_violations_status(
+            vec![ids[0], ids[1], 9999],
+            "dismissed".to_string(),
+            "Not applicable".to_string(),
+        ).await;
+
+        assert!(result.is_err());
+        for id in ids {
+            assert_eq!(violation_status(id), ("open".to_string(), None));
+        }
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_reopen_violations() {
+        let _guard = TestDbGuard::new();
+        let project_id = create_test_project();
+        let scan_id = create_test_scan(project_id);
+        let dismissed = create_test_violation(scan_id);
+        let fixed = create_test_violation(scan_id);
+        let accepted = create_test_violation(scan_id);
+        let open = create_test_violation(scan_id);
+
+        update_violations_status(vec![dismissed], "dismissed".to_string(), "False alarm".to_string()).await.unwrap();
+        update_violations_status(vec![fixed], "fixed".to_string(), "Patched".to_string()).await.unwrap();
+        update_violations_status(vec![accepted], "accepted_risk".to_string(), "Legacy".to_string()).await.unwrap();
+
+        let reopened = reopen_violations(scan_id).await.unwrap();
+
+        assert_eq!(reopened, 2);
+        assert_eq!(violation_status(dismissed), ("open".to_string(), None));
+        assert_eq!(violation_status(fixed), ("open".to_string(), None));
+        assert_eq!(violation_status(accepted), ("accepted_risk".to_string(), Some("Legacy".to_string())));
+        assert_eq!(violation_status(open), ("open".to_string(), None));
+
+        let conn = db::get_connection();
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM audit_events WHERE event_type = 'violations_reopened'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_reopen_violations_nonexistent_scan() {
+        let _guard = TestDbGuard::new();
+        assert!(reopen_violations(999).await.is_err());
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_get_violation_status_history() {
+        let _guard = TestDbGuard::new();
+        let project_id = create_test_project();
+        let scan_id = create_test_scan(project_id);
+        let id = create_test_violation(scan_id);
+
+        update_violations_status(vec![id], "dismissed".to_string(), "Behind VPN".to_string()).await.unwrap();
+        reopen_violations(scan_id).await.unwrap();
+        update_violations_status(vec![id], "accepted_risk".to_string(), "Legacy system".to_string()).await.unwrap();
+
+        let history = get_violation_status_history(id).await.unwrap();
+        let steps: Vec<(&str, &str, Option<&str>)> = history
+            .iter()
+            .map(|t| (t.from_status.as_str(), t.to_status.as_str(), t.reason.as_deref()))
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                ("open", "dismissed", Some("Behind VPN")),
+                ("dismissed", "open", None),
+                ("open", "accepted_risk", Some("Legacy system")),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_get_violation_status_history_nonexistent_violation() {
+        let _guard = TestDbGuard::new();
+        assert!(get_violation_status_history(999).await.is_err());
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_dismiss_accepted_risk_violation_rejected() {
+        let _guard = TestDbGuard::new();
+        let project_id = create_test_project();
+        let scan_id = create_test_scan(project_id);
+        let id = create_test_violation(scan_id);
+        update_violations_status(vec![id], "accepted_risk".to_string(), "Legacy".to_string()).await.unwrap();
+
+        let err = dismiss_violation(id).await.unwrap_err();
+
+        assert!(err.contains("accepted_risk -> dismissed"), "{}", err);
+        assert_eq!(violation_status(id).0, "accepted_risk");
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_report_false_positive_records_reason_in_history() {
+        let _guard = TestDbGuard::new();
+        let project_id = create_test_project();
+        let scan_id = create_test_scan(project_id);
+        let id = create_test_violation(scan_id);
+
+        report_false_positive(id, "Test fixture".to_string()).await.unwrap();
+
+        let history = get_violation_status_history(id).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].reason.as_deref(), Some("False positive: Test fixture"));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_update_violations_status_invalid_transition_is_atomic() {
+        let _guard = TestDbGuard::new();
+        let project_id = create_test_project();
+        let scan_id = create_test_scan(project_id);
+        let open = create_test_violation(scan_id);
+        let accepted = create_test_violation(scan_id);
+        update_violations_status(vec![accepted], "accepted_risk".to_string(), "Legacy".to_string()).await.unwrap();
+
+        let result = update_violations_status(
+            vec![open, accepted],
+            "dismissed".to_string(),
+            "Not applicable".to_string(),
+        ).await;
+
+        assert!(result.is_err());
+        assert_eq!(violation_status(open), ("open".to_string(), None));
+        assert_eq!(get_violation_status_history(open).await.unwrap().len(), 0);
+    }
+
+    /// One open violation for each seeded control, plus one for a control outside the catalogue
+    fn create_violations_for_each_control(scan_id: i64) {
+        let conn = db::get_connection();
+        for (line, control_id) in ["CC6.1", "CC6.7", "CC7.2", "A1.2", "CUSTOM.1"].iter().enumerate() {
+            let violation = Violation::new(
+                scan_id,
+                control_id.to_string(),
+                crate::models::Severity::Medium,
+                format!("{} finding", control_id),
+                "app.py".to_string(),
+                line as i64 + 1,
+                "code".to_string(),
+            );
+            queries::insert_violation(&conn, &violation).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_get_violations_filter_by_control_category() {
+        let _guard = TestDbGuard::new();
+        let project_id = create_test_project();
+        let scan_id = create_test_scan(project_id);
+        create_violations_for_each_control(scan_id);
+
+        let filter = |category: &str| ViolationFilters {
+            severity: None,
+            control_id: None,
+            status: None,
+            control_category: Some(category.to_string()),
+            package_name: None,
+        };
+
+        let access = get_violations(scan_id, Some(filter("CC6 - Access Control"))).await.unwrap();
+        let mut access_controls: Vec<&str> = access.iter().map(|v| v.control_id.as_str()).collect();
+        access_controls.sort();
+        assert_eq!(access_controls, vec!["CC6.1", "CC6.7"]);
+
+        let monitoring = get_violations(scan_id, Some(filter("CC7 - System Monitoring"))).await.unwrap();
+        assert_eq!(monitoring.len(), 1);
+        assert_eq!(monitoring[0].control_id, "CC7.2");
+
+        let availability = get_violations(scan_id, Some(filter("A1 - Service Availability"))).await.unwrap();
+        assert_eq!(availability.len(), 1);
+        assert_eq!(availability[0].control_id, "A1.2");
+
+        assert!(get_violations(scan_id, Some(filter("No Such Category"))).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_get_violations_category_combines_with_other_filters() {
+        let _guard = TestDbGuard::new();
+        let project_id = create_test_project();
+        let scan_id = create_test_scan(project_id);
+        create_violations_for_each_control(scan_id);
+
+        let filters = ViolationFilters {
+            severity: None,
+            control_id: Some(vec!["CC6.7".to_string()]),
+            status: None,
+            control_category: Some("CC6 - Access Control".to_string()),
+            package_name: None,
+        };
+
+        let violations = get_violations(scan_id, Some(filters)).await.unwrap();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].control_id, "CC6.7");
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_get_violations_by_category() {
+        let _guard = TestDbGuard::new();
+        let project_id = create_test_project();
+        let scan_id = create_test_scan(project_id);
+        create_violations_for_each_control(scan_id);
+
+        let by_category = get_violations_by_category(scan_id).await.unwrap();
+
+        assert_eq!(by_category.len(), 4);
+        assert_eq!(by_category["CC6 - Access Control"].len(), 2);
+        assert_eq!(by_category["CC7 - System Monitoring"][0].control_id, "CC7.2");
+        assert_eq!(by_category["A1 - Service Availability"][0].control_id, "A1.2");
+        assert_eq!(by_category[UNCATEGORIZED][0].control_id, "CUSTOM.1");
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_violations_by_package() {
+        let _guard = TestDbGuard::new();
+        let project_id = create_test_project();
+        let scan_id = create_test_scan(project_id);
+        {
+            let conn = db::get_connection();
+            let findings = [
+                (Some("@acme/api"), crate::models::Severity::Critical),
+                (Some("@acme/api"), crate::models::Severity::Low),
+                (Some("@acme/web"), crate::models::Severity::High),
+                (None, crate::models::Severity::Medium),
+            ];
+            for (line, (package, severity)) in findings.into_iter().enumerate() {
+                let mut violation = Violation::new(
+                    scan_id,
+                    "CC6.1".to_string(),
+                    severity,
+                    "finding".to_string(),
+                    "app.ts".to_string(),
+                    line as i64 + 1,
+                    "code".to_string(),
+                );
+                violation.package_name = package.map(str::to_string);
+                queries::insert_violation(&conn, &violation).unwrap();
+            }
+        }
+
+        let summaries = get_violations_by_package(scan_id).await.unwrap();
+        let totals: Vec<_> = summaries.iter().map(|s| (s.package_name.as_deref(), s.total)).collect();
+        assert_eq!(totals, vec![(Some("@acme/api"), 2), (None, 1), (Some("@acme/web"), 1)]);
+        assert_eq!(summaries[0].severity_counts, SeverityCounts { critical: 1, high: 0, medium: 0, low: 1 });
+        assert_eq!(summaries[0].open, 2);
+
+        let filters = ViolationFilters {
+            severity: None,
+            control_id: None,
+            status: None,
+            control_category: None,
+            package_name: Some(vec!["@acme/web".to_string()]),
+        };
+        let violations = get_violations(scan_id, Some(filters)).await.unwrap();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].package_name.as_deref(), Some("@acme/web"));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_get_violations_by_category_empty_scan() {
+        let _guard = TestDbGuard::new();
+        let project_id = create_test_project();
+        let scan_id = create_test_scan(project_id);
+
+        assert!(get_violations_by_category(scan_id).await.unwrap().is_empty());
+    }
+
+    fn create_searchable_violation(scan_id: i64, description: &str, code_snippet: &str, llm_reasoning: Option<&str>) -> i64 {
+        let mut violation = Violation::new(
+            scan_id,
+            "CC6.7".to_string(),
+            crate::models::Severity::High,
+            description.to_string(),
+            "config.py".to_string(),
+            1,
+            code_snippet.to_string(),
+        );
+        violation.llm_reasoning = llm_reasoning.map(|r| r.to_string());
+        let conn = db::get_connection();
+        queries::insert_violation(&conn, &violation).unwrap()
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_search_violations_highlights_and_ranks() {
+        let _guard = TestDbGuard::new();
+        let project_id = create_test_project();
+        let scan_id = create_test_scan(project_id);
+        create_searchable_violation(scan_id, "Hardcoded password", "password = 'x'", Some("The PASSWORD is committed"));
+        create_searchable_violation(scan_id, "Hardcoded API key", "API_KEY = 'x'", None);
+        create_searchable_violation(scan_id, "Missing audit log", "db.delete(user)", None);
+
+        let results = search_violations(scan_id, "Password key".to_string()).await.unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].match_count, 3);
+        assert_eq!(results[0].description, "Hardcoded **password**");
+        assert_eq!(results[0].code_snippet, "**password** = 'x'");
+        assert_eq!(results[0].llm_reasoning.as_deref(), Some("The **PASSWORD** is committed"));
+        assert_eq!(results[1].description, "Hardcoded API **key**");
+        assert_eq!(results[1].code_snippet, "API_**KEY** = 'x'");
+        assert_eq!(results[1].llm_reasoning, None);
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_search_violations_does_not_modify_stored_values() {
+        let _guard = TestDbGuard::new();
+        let project_id = create_test_project();
+        let scan_id = create_test_scan(project_id);
+        let id = create_searchable_violation(scan_id, "Hardcoded password", "password = 'x'", None);
+
+        let results = search_violations(scan_id, "password".to_string()).await.unwrap();
+        assert_eq!(results[0].violation.description, "Hardcoded password");
+
+        let conn = db::get_connection();
+        let stored = queries::select_violation(&conn, id).unwrap().unwrap();
+        assert_eq!(stored.description, "Hardcoded password");
+        assert_eq!(stored.code_snippet, "password = 'x'");
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_search_violations_special_characters_and_empty_query() {
+        let _guard = TestDbGuard::new();
+        let project_id = create_test_project();
+        let scan_id = create_test_scan(project_id);
+        create_searchable_violation(scan_id, "SQL injection", "cursor.execute(f\"SELECT * FROM t WHERE id={id}\")", None);
+        create_searchable_violation(scan_id, "Hardcoded secret", "SECRET = 'x'", None);
+
+        let results = search_violations(scan_id, "{id}".to_string()).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].code_snippet, "cursor.execute(f\"SELECT * FROM t WHERE id=**{id}**\")");
+
+        assert!(search_violations(scan_id, ".*".to_string()).await.unwrap().is_empty());
+        assert!(search_violations(scan_id, "  ".to_string()).await.unwrap_err().contains("cannot be empty"));
+    }
+
+    /// Run a completed scan reporting `(control_id, line_number)` findings
+    fn create_completed_scan(project_id: i64, findings: &[(&str, i64)]) -> i64 {
+        let scan_id = create_test_scan(project_id);
+        let conn = db::get_connection();
+        for (control_id, line_number) in findings {
+            let violation = Violation::new(
+                scan_id,
+                control_id.to_string(),
+                crate::models::Severity::High,
+                format!("{} at line {}", control_id, line_number),
+                "views.py".to_string(),
+                *line_number,
+                "code".to_string(),
+            );
+            queries::insert_violation(&conn, &violation).unwrap();
+        }
+        queries::update_scan_status(&conn, scan_id, "completed", Some(&chrono::Utc::now().to_rfc3339())).unwrap();
+        scan_id
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_get_project_violations_dedupes_three_scans() {
+        let _guard = TestDbGuard::new();
+        let project_id = create_test_project();
+        let first = create_completed_scan(project_id, &[("CC6.1", 10), ("CC6.7", 20)]);
+        let second = create_completed_scan(project_id, &[("CC6.1", 10), ("CC6.7", 20)]);
+        let third = create_completed_scan(project_id, &[("CC6.1", 10), ("CC7.2", 30)]);
+
+        let violations = get_project_violations(project_id, None).await.unwrap();
+        assert_eq!(violations.len(), 3);
+
+        let seen: Vec<(&str, i64, Option<i64>, Option<i64>)> = violations
+            .iter()
+            .map(|v| (v.control_id.as_str(), v.scan_id, v.first_seen_scan_id, v.last_seen_scan_id))
+            .collect();
+        assert_eq!(
+            seen,
+            vec![
+                ("CC6.1", third, Some(first), Some(third)),
+                ("CC6.7", second, Some(first), Some(second)),
+                ("CC7.2", third, Some(third), Some(third)),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_get_project_violations_applies_filters() {
+        let _guard = TestDbGuard::new();
+        let project_id = create_test_project();
+        create_completed_scan(project_id, &[("CC6.1", 10), ("CC6.7", 20)]);
+        create_completed_scan(project_id, &[("CC6.1", 10), ("CC7.2", 30)]);
+        let latest = create_completed_scan(project_id, &[("CC6.1", 10), ("CC7.2", 30)]);
+
+        let cc72_id = {
+            let conn = db::get_connection();
+            let id = queries::select_violations(&conn, latest)
+                .unwrap()
+                .into_iter()
+                .find(|v| v.control_id == "CC7.2")
+                .unwrap()
+                .id;
+            queries::update_violation_status(&conn, id, "dismissed", None).unwrap();
+            id
+        };
+
+        let filters = ViolationFilters {
+            severity: None,
+            control_id: None,
+            status: Some(vec!["open".to_string()]),
+            control_category: Some("CC6 - Access Control".to_string()),
+            package_name: None,
+        };
+        let open_access = get_project_violations(project_id, Some(filters)).await.unwrap();
+        let controls: Vec<&str> = open_access.iter().map(|v| v.control_id.as_str()).collect();
+        assert_eq!(controls, vec!["CC6.1", "CC6.7"]);
+
+        let filters = ViolationFilters {
+            severity: None,
+            control_id: None,
+            status: Some(vec!["dismissed".to_string()]),
+            control_category: None,
+            package_name: None,
+        };
+        let dismissed = get_project_violations(project_id, Some(filters)).await.unwrap();
+        assert_eq!(dismissed.len(), 1);
+        assert_eq!(dismissed[0].id, cc72_id);
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_get_violation_history() {
+        let _guard = TestDbGuard::new();
+        let project_id = create_test_project();
+        for findings in [&[("CC6.1", 10), ("CC7.2", 20)][..], &[("CC6.1", 12)][..]] {
+            let scan_id = create_completed_scan(project_id, findings);
+            let conn = db::get_connection();
+            queries::update_violation_history(&conn, project_id, scan_id, true).unwrap();
+        }
+
+        let history = get_violation_history(project_id, None).await.unwrap();
+        assert_eq!(history.len(), 2);
+        let resolved = get_violation_history(project_id, Some("resolved".to_string())).await.unwrap();
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].control_id, "CC7.2");
+
+        assert!(get_violation_history(project_id, Some("open".to_string())).await.unwrap_err().contains("Invalid lifecycle"));
+        assert!(get_violation_history(9999, None).await.unwrap_err().contains("Project not found"));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_get_project_violations_ignores_incomplete_scans() {
+        let _guard = TestDbGuard::new();
+        let project_id = create_test_project();
+        create_completed_scan(project_id, &[("CC6.1", 10)]);
+        let running = create_test_scan(project_id);
+        create_test_violation(running);
+
+        let violations = get_project_violations(project_id, None).await.unwrap();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line_number, 10);
+
+        assert!(get_project_violations(9999, None).await.unwrap_err().contains("Project not found"));
+        assert!(get_project_violations(0, None).await.is_err());
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_create_baseline_writes_open_violations() {
+        let _guard = TestDbGuard::new();
+        let project_id = create_test_project();
+        assert!(list_suppressed(project_id).await.unwrap().is_empty());
+
+        let scan_id = create_completed_scan(project_id, &[("CC6.1", 10), ("CC6.7", 20), ("CC7.2", 30)]);
+        let dismissed = queries::select_violations(&db::get_connection(), scan_id).unwrap()[0].id;
+        dismiss_violation(dismissed).await.unwrap();
+
+        let baseline = create_baseline(scan_id).await.unwrap();
+        assert_eq!(baseline.entries.len(), 2);
+        assert_eq!(baseline.scan_id, scan_id);
+        assert!(baseline.entries.iter().all(|e| e.file_path == "views.py"));
+
+        let project_path = {
+            let conn = db::get_connection();
+            queries::select_project(&conn, project_id).unwrap().unwrap().path
+        };
+        assert!(Path::new(&project_path).join(BASELINE_FILE_NAME).is_file());
+        assert_eq!(list_suppressed(project_id).await.unwrap(), baseline.entries);
+
+        let events = queries::select_audit_events_by_project(&db::get_connection(), project_id).unwrap();
+        assert!(events.iter().any(|e| e.event_type == "baseline_created"));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_create_baseline_rejects_incomplete_scan() {
+        let _guard = TestDbGuard::new();
+        let project_id = create_test_project();
+        let running = create_test_scan(project_id);
+
+        assert!(create_baseline(running).await.unwrap_err().contains("has not completed"));
+        assert!(create_baseline(9999).await.unwrap_err().contains("Scan not found"));
+        assert!(list_suppressed(9999).await.unwrap_err().contains("Project not found"));
+    }
 }
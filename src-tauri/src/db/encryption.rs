@@ -0,0 +1,273 @@
+//! Optional encryption of ryn.db at rest with SQLCipher
+//!
+//! Violation snippets can contain real secrets found in scanned code, so the
+//! database can be kept encrypted on disk. Encryption needs a build with the
+//! `encryption` feature (SQLCipher in place of plain SQLite) and is turned on
+//! with `RYN_DB_ENCRYPTION=1`.
+//!
+//! The passphrase is a random secret stored in the OS keychain (service
+//! "ryn", account "database-key") and created on first use; SQLCipher derives
+//! the page key from it with PBKDF2. `RYN_DB_KEY` overrides the keychain for
+//! CI and headless runs. A plaintext ryn.db is encrypted in place the first
+//! time it's opened with encryption on.
+
+use crate::security::credentials;
+use anyhow::{anyhow, Context, Result};
+use rusqlite::Connection;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Environment variable that turns encryption on ("1" or "true")
+pub const ENCRYPTION_ENV: &str = "RYN_DB_ENCRYPTION";
+
+/// Environment variable with a passphrase to use instead of the keychain's
+pub const KEY_ENV: &str = "RYN_DB_KEY";
+
+/// Keychain account holding the database passphrase
+pub const KEYCHAIN_ACCOUNT: &str = "database-key";
+
+/// First 16 bytes of every plaintext SQLite database
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+/// Whether `RYN_DB_ENCRYPTION` asks for an encrypted database
+pub fn is_enabled() -> bool {
+    parse_enabled(std::env::var(ENCRYPTION_ENV).ok().as_deref())
+}
+
+fn parse_enabled(value: Option<&str>) -> bool {
+    matches!(value.map(str::trim), Some("1") | Some("true"))
+}
+
+/// Whether this build links SQLCipher
+pub fn is_available() -> bool {
+    cfg!(feature = "encryption")
+}
+
+/// Whether `db_path` holds an unencrypted SQLite database
+///
+/// A missing or empty file is not plaintext: SQLCipher creates it encrypted.
+pub fn is_plaintext(db_path: &Path) -> Result<bool> {
+    let mut file = match std::fs::File::open(db_path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e).context(format!("Failed to read database header of {:?}", db_path)),
+    };
+    let mut header = [0u8; 16];
+    match file.read_exact(&mut header) {
+        Ok(()) => Ok(&header == SQLITE_HEADER),
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => Ok(false),
+        Err(e) => Err(e).context(format!("Failed to read database header of {:?}", db_path)),
+    }
+}
+
+/// Whether `db_path` exists with content that isn't a plaintext database
+fn is_encrypted(db_path: &Path) -> Result<bool> {
+    let has_content = std::fs::metadata(db_path).map(|m| m.len() > 0).unwrap_or(false);
+    Ok(has_content && !is_plaintext(db_path)?)
+}
+
+/// Get the passphrase for `db_path`, encrypting a plaintext database first
+///
+/// Returns None when encryption is off.
+///
+/// # Errors
+/// Returns error if encryption is on but this build lacks SQLCipher or no
+/// passphrase is available, if encryption is off but the file is encrypted,
+/// or if encrypting a plaintext database fails
+pub fn prepare(db_path: &Path) -> Result<Option<String>> {
+    if !is_enabled() {
+        if is_encrypted(db_path)? {
+            return Err(anyhow!(
+                "Database {:?} is encrypted; set {}=1 to open it",
+                db_path,
+                ENCRYPTION_ENV
+            ));
+        }
+        return Ok(None);
+    }
+    if !is_available() {
+        return Err(anyhow!(
+            "{} is set but Ryn was built without the `encryption` feature",
+            ENCRYPTION_ENV
+        ));
+    }
+
+    let key = database_key()?;
+    if is_plaintext(db_path)? {
+        migrate_plaintext(db_path, &key)?;
+        eprintln!("[ryn] Encrypted plaintext database at {:?}", db_path);
+    }
+    Ok(Some(key))
+}
+
+/// Get the passphrase from `RYN_DB_KEY`, or else the OS keychain
+///
+/// # Errors
+/// Returns error if `RYN_DB_KEY` is unset and the keychain can't be used
+pub fn database_key() -> Result<String> {
+    if let Some(key) = std::env::var(KEY_ENV).ok().filter(|key| !key.is_empty()) {
+        return Ok(key);
+    }
+    keychain_key()
+}
+
+/// Read the passphrase from the keychain, storing a new random one if there is none
+fn keychain_key() -> Result<String> {
+    if let Some(key) = credentials::read_secret(KEYCHAIN_ACCOUNT)? {
+        return Ok(key);
+    }
+    let mut bytes = [0u8; 32];
+    getrandom::getrandom(&mut bytes).map_err(|e| anyhow!("Failed to generate database key: {}", e))?;
+    let key = hex::encode(bytes);
+    credentials::store_secret(KEYCHAIN_ACCOUNT, &key)?;
+    Ok(key)
+}
+
+/// Unlock an encrypted connection; must run before any other statement
+pub fn apply_key(conn: &Connection, key: &str) -> rusqlite::Result<()> {
+    conn.pragma_update(None, "key", key)
+}
+
+/// Encrypt a plaintext database in place with `sqlcipher_export`
+///
+/// The encrypted copy is written next to `db_path` and then renamed over it,
+/// so a failure leaves the plaintext database untouched. The plaintext
+/// file's WAL and shared-memory files are removed afterwards.
+pub fn migrate_plaintext(db_path: &Path, key: &str) -> Result<()> {
+    let encrypted_path = sibling_path(db_path, "encrypting");
+    let _ = std::fs::remove_file(&encrypted_path);
+
+    {
+        let conn = Connection::open(db_path)
+            .context(format!("Failed to open plaintext database at {:?}", db_path))?;
+        // Fold the WAL into the main file so the export sees every commit
+        conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+            .context("Failed to checkpoint plaintext database")?;
+        let version: i64 = conn
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .context("Failed to read schema version")?;
+
+        conn.execute(
+            "ATTACH DATABASE ?1 AS encrypted KEY ?2",
+            rusqlite::params![encrypted_path.to_string_lossy(), key],
+        )
+        .context("Failed to create encrypted database")?;
+        conn.query_row("SELECT sqlcipher_export('encrypted')", [], |_| Ok(()))
+            .context("Failed to copy data into encrypted database")?;
+        // Keep the migrations' schema version
+        conn.execute_batch(&format!("PRAGMA encrypted.user_version = {};", version))
+            .context("Failed to copy schema version")?;
+        conn.execute_batch("DETACH DATABASE encrypted;")
+            .context("Failed to close encrypted database")?;
+    } // Plaintext connection closed here
+
+    std::fs::rename(&encrypted_path, db_path)
+        .context(format!("Failed to replace {:?} with its encrypted copy", db_path))?;
+    for suffix in ["wal", "shm"] {
+        let _ = std::fs::remove_file(sibling_path(db_path, suffix));
+    }
+
+    Ok(())
+}
+
+/// `ryn.db` -> `ryn.db-{suffix}`, as SQLite names its WAL and shm files
+fn sibling_path(db_path: &Path, suffix: &str) -> PathBuf {
+    let mut name = db_path.as_os_str().to_os_string();
+    name.push(format!("-{}", suffix));
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_enabled() {
+        assert!(parse_enabled(Some("1")));
+        assert!(parse_enabled(Some("true")));
+        assert!(!parse_enabled(Some("0")));
+        assert!(!parse_enabled(Some("")));
+        assert!(!parse_enabled(None));
+    }
+
+    #[test]
+    fn test_is_plaintext() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("ryn.db");
+        assert!(!is_plaintext(&db_path).unwrap());
+
+        std::fs::write(&db_path, b"").unwrap();
+        assert!(!is_plaintext(&db_path).unwrap());
+        assert!(!is_encrypted(&db_path).unwrap());
+
+        std::fs::remove_file(&db_path).unwrap();
+        Connection::open(&db_path)
+            .unwrap()
+            .execute_batch("CREATE TABLE t (x INTEGER);")
+            .unwrap();
+        assert!(is_plaintext(&db_path).unwrap());
+        assert!(!is_encrypted(&db_path).unwrap());
+
+        std::fs::write(&db_path, [0x5a; 4096]).unwrap();
+        assert!(!is_plaintext(&db_path).unwrap());
+        assert!(is_encrypted(&db_path).unwrap());
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_prepare_without_encryption() {
+        std::env::remove_var(ENCRYPTION_ENV);
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("ryn.db");
+        assert_eq!(prepare(&db_path).unwrap(), None);
+
+        // An encrypted file needs encryption turned on
+        std::fs::write(&db_path, [0x5a; 4096]).unwrap();
+        let error = prepare(&db_path).unwrap_err();
+        assert!(error.to_string().contains(ENCRYPTION_ENV));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_database_key_is_generated_once() {
+        std::env::remove_var(KEY_ENV);
+        credentials::delete_secret(KEYCHAIN_ACCOUNT).unwrap();
+        let key = database_key().unwrap();
+        assert_eq!(key.len(), 64);
+        assert_eq!(database_key().unwrap(), key);
+
+        std::env::set_var(KEY_ENV, "from-environment");
+        assert_eq!(database_key().unwrap(), "from-environment");
+        std::env::remove_var(KEY_ENV);
+    }
+
+    #[cfg(feature = "encryption")]
+    #[test]
+    fn test_migrate_plaintext() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("ryn.db");
+        {
+            let conn = Connection::open(&db_path).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE secrets (value TEXT); INSERT INTO secrets VALUES ('sk-1234'); PRAGMA user_version = 7;",
+            )
+            .unwrap();
+        }
+
+        migrate_plaintext(&db_path, "passphrase").unwrap();
+        assert!(!is_plaintext(&db_path).unwrap());
+        assert!(!std::fs::read(&db_path).unwrap().windows(7).any(|w| w == b"sk-1234"));
+
+        let conn = Connection::open(&db_path).unwrap();
+        apply_key(&conn, "passphrase").unwrap();
+        let value: String = conn.query_row("SELECT value FROM secrets", [], |row| row.get(0)).unwrap();
+        assert_eq!(value, "sk-1234");
+        let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0)).unwrap();
+        assert_eq!(version, 7);
+
+        let wrong = Connection::open(&db_path).unwrap();
+        apply_key(&wrong, "wrong").unwrap();
+        assert!(wrong.query_row("SELECT COUNT(*) FROM secrets", [], |row| row.get::<_, i64>(0)).is_err());
+    }
+}
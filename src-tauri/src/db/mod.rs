@@ -1,9 +1,12 @@
 use rusqlite::Connection;
+use r2d2::{Pool, PooledConnection};
+use r2d2_sqlite::SqliteConnectionManager;
 use std::path::PathBuf;
-use std::sync::Mutex;
+use std::time::Duration;
 use anyhow::{Result, Context};
 use once_cell::sync::Lazy;
 
+pub mod encryption;
 pub mod migrations;
 pub mod queries;
 
@@ -13,12 +16,30 @@ pub mod test_helpers;
 pub use migrations::{run_migrations, seed_controls};
 pub use queries::*;
 
-/// Singleton database connection
-/// Initialized once on first access, then reused for all subsequent calls
-static DB_CONNECTION: Lazy<Mutex<Connection>> = Lazy::new(|| {
-    let conn = create_connection()
-        .expect("Failed to initialize database connection");
-    Mutex::new(conn)
+/// A connection checked out of the pool; returned to it when dropped
+pub type DbConnection = PooledConnection<SqliteConnectionManager>;
+
+/// Connections the pool keeps open
+///
+/// The database runs in WAL mode, so readers (the UI) never wait on the
+/// writer (a scan inserting violations); writers still take turns.
+///
+/// Sized above the most tasks that hold a connection at once, so
+/// `get_connection()` doesn't wait out `POOL_TIMEOUT` and panic: up to 10 LLM
+/// analyses of a scan and 10 batch fix generations run concurrently, next to
+/// the scan itself and the UI's readers.
+const POOL_SIZE: u32 = 24;
+
+/// How long a writer waits for another writer's lock before SQLITE_BUSY
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long `get_connection()` waits for a free pooled connection
+const POOL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Database connection pool
+/// Initialized (and migrated) once on first access, then shared by all callers
+static DB_POOL: Lazy<Pool<SqliteConnectionManager>> = Lazy::new(|| {
+    create_pool().expect("Failed to initialize database connection pool")
 });
 
 /// Get the database file path
@@ -38,48 +59,101 @@ pub fn get_db_path() -> Result<PathBuf> {
     Ok(data_dir.join("ryn.db"))
 }
 
-/// Create a new database connection with proper configuration
-/// Called once by the singleton initialization
-fn create_connection() -> Result<Connection> {
-    let db_path = get_db_path()?;
-    let conn = Connection::open(&db_path)
-        .context(format!("Failed to open database at {:?}", db_path))?;
+/// Per-connection settings, applied to every connection the pool opens
+///
+/// `key` unlocks an encrypted database (see `encryption`) and is applied first.
+fn configure_connection(conn: &Connection, key: Option<&str>) -> rusqlite::Result<()> {
+    if let Some(key) = key {
+        encryption::apply_key(conn, key)?;
+    }
 
     // Enable foreign key support
-    conn.execute("PRAGMA foreign_keys = ON", [])
-        .context("Failed to enable foreign keys")?;
+    conn.execute_batch("PRAGMA foreign_keys = ON;")?;
 
-    // Set busy timeout to 5 seconds
-    conn.busy_timeout(std::time::Duration::from_secs(5))
-        .context("Failed to set busy timeout")?;
+    // Safe with WAL: a crash can only lose the last commits, never corrupt the file
+    conn.execute_batch("PRAGMA synchronous = NORMAL;")?;
 
-    // Run migrations
-    run_migrations(&conn)?;
+    conn.busy_timeout(BUSY_TIMEOUT)
+}
 
-    // Seed controls
-    seed_controls(&conn)?;
+/// Switch the database file to write-ahead logging
+///
+/// The journal mode is stored in the file, so this only has to succeed once.
+fn enable_wal(conn: &Connection) -> Result<()> {
+    let mode: String = conn
+        .query_row("PRAGMA journal_mode = WAL", [], |row| row.get(0))
+        .context("Failed to enable WAL journal mode")?;
+
+    if !mode.eq_ignore_ascii_case("wal") {
+        eprintln!("[ryn] WARNING: Database journal mode is {} (WAL unavailable), reads will wait on writes", mode);
+    }
+
+    Ok(())
+}
+
+/// Open and configure a connection to the database file
+///
+/// Fails early with a clear error if `key` doesn't unlock an encrypted database.
+fn open_connection(db_path: &std::path::Path, key: Option<&str>) -> Result<Connection> {
+    let conn = Connection::open(db_path)
+        .context(format!("Failed to open database at {:?}", db_path))?;
+    configure_connection(&conn, key).context("Failed to configure database connection")?;
+
+    if key.is_some() {
+        conn.query_row("SELECT COUNT(*) FROM sqlite_master", [], |_| Ok(()))
+            .context("Failed to unlock encrypted database (wrong key?)")?;
+    }
 
     Ok(conn)
 }
 
-/// Get a reference to the singleton database connection
+/// Create the connection pool
+/// Migrations run on a dedicated connection before the pool hands any out
+fn create_pool() -> Result<Pool<SqliteConnectionManager>> {
+    let db_path = get_db_path()?;
+    // Passphrase when encryption is on; encrypts a plaintext database first
+    let key = encryption::prepare(&db_path)?;
+    {
+        let conn = open_connection(&db_path, key.as_deref())?;
+        enable_wal(&conn)?;
+
+        // Run migrations
+        run_migrations(&conn)?;
+
+        // Seed controls
+        seed_controls(&conn)?;
+    }
+
+    let manager = SqliteConnectionManager::file(&db_path)
+        .with_init(move |conn| configure_connection(conn, key.as_deref()));
+
+    Pool::builder()
+        .max_size(POOL_SIZE)
+        .connection_timeout(POOL_TIMEOUT)
+        .build(manager)
+        .context(format!("Failed to create connection pool for {:?}", db_path))
+}
+
+/// Check a connection out of the shared pool
 /// This replaces init_db() and should be used in all commands
-pub fn get_connection() -> std::sync::MutexGuard<'static, Connection> {
-    DB_CONNECTION.lock().unwrap()
+///
+/// Keep the connection scoped and drop it before any `.await`, so long tasks
+/// don't hold pool slots they aren't using.
+pub fn get_connection() -> DbConnection {
+    DB_POOL
+        .get()
+        .expect("Failed to get a database connection from the pool")
 }
 
 /// Initialize the database connection and run migrations
 /// NOTE: This creates a NEW connection each time. For most use cases, prefer get_connection()
-/// which returns the singleton connection. This function is primarily used in main.rs for
+/// which checks a connection out of the shared pool. This function is primarily used in main.rs for
 /// early initialization with explicit error handling.
 pub fn init_db() -> Result<Connection> {
     let db_path = get_db_path()?;
-    let conn = Connection::open(&db_path)
-        .context(format!("Failed to open database at {:?}", db_path))?;
-
-    // Enable foreign key support
-    conn.execute("PRAGMA foreign_keys = ON", [])
-        .context("Failed to enable foreign keys")?;
+    let key = encryption::prepare(&db_path)?;
+    let conn = open_connection(&db_path, key.as_deref())?;
+    enable_wal(&conn)?;
 
     // Run migrations
     run_migrations(&conn)?;
@@ -105,6 +179,16 @@ mod tests {
         assert!(path.parent().unwrap().exists());
     }
 
+    #[test]
+    #[serial_test::serial]
+    fn test_pool_serves_concurrent_llm_and_fix_tasks() {
+        let _guard = test_helpers::TestDbGuard::new();
+
+        // Every LLM analysis and fix generation holding a connection, plus the scan
+        let held: Vec<DbConnection> = (0..21).map(|_| get_connection()).collect();
+        assert!(held.iter().all(|conn| conn.query_row("SELECT 1", [], |row| row.get::<_, i64>(0)).is_ok()));
+    }
+
     #[test]
     fn test_init_db() {
         let temp_dir = TempDir::new().unwrap();
@@ -155,6 +239,52 @@ mod tests {
             .query_row("SELECT COUNT(*) FROM controls", [], |row| row.get(0))
             .unwrap();
 
-        assert_eq!(control_count, 4);
+        assert_eq!(control_count, 5);
+    }
+
+    #[test]
+    fn test_init_db_enables_wal() {
+        let temp_dir = TempDir::new().unwrap();
+        std::env::set_var("RYN_DATA_DIR", temp_dir.path());
+
+        let conn = init_db().unwrap();
+
+        let mode: String = conn
+            .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+            .unwrap();
+
+        assert_eq!(mode, "wal");
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_pool_reads_while_a_write_is_open() {
+        let _guard = test_helpers::TestDbGuard::new();
+
+        let writer = get_connection();
+        writer.execute_batch("BEGIN IMMEDIATE;").unwrap();
+        writer
+            .execute("INSERT INTO projects (name, path) VALUES ('p', '/tmp/ryn-test/wal')", [])
+            .unwrap();
+
+        // A second connection reads the last committed state without waiting
+        let reader = get_connection();
+        let started = std::time::Instant::now();
+        let count: i64 = reader
+            .query_row("SELECT COUNT(*) FROM projects", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 0);
+        assert!(started.elapsed() < BUSY_TIMEOUT);
+
+        let foreign_keys: bool = reader
+            .query_row("PRAGMA foreign_keys", [], |row| row.get(0))
+            .unwrap();
+        assert!(foreign_keys);
+
+        writer.execute_batch("COMMIT;").unwrap();
+        let count: i64 = reader
+            .query_row("SELECT COUNT(*) FROM projects", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 1);
     }
 }
@@ -1,13 +1,106 @@
-use rusqlite::{Connection, params, OptionalExtension};
+use rusqlite::{Connection, params, OptionalExtension, Transaction, TransactionBehavior};
 use anyhow::{Result, Context};
 use crate::models::*;
+use std::collections::{BTreeMap, HashMap};
+
+/// Begin a transaction that takes the write lock up front
+///
+/// With WAL and several pooled connections, a deferred transaction that reads
+/// before it writes fails with SQLITE_BUSY if another connection committed in
+/// between. Taking the lock at BEGIN waits on the busy timeout instead.
+fn begin_write(conn: &Connection) -> rusqlite::Result<Transaction<'_>> {
+    Transaction::new_unchecked(conn, TransactionBehavior::Immediate)
+}
+
+// ===== NAMESPACE CRUD =====
+
+tokio::task_local! {
+    /// Namespace a background task works in instead of the `current_namespace` setting
+    static NAMESPACE_OVERRIDE: String;
+}
+
+/// Namespace that scopes all project-derived reads, from the `current_namespace` setting
+/// (or the namespace of an [`in_namespace`] task)
+pub fn current_namespace(conn: &Connection) -> Result<String> {
+    if let Ok(namespace) = NAMESPACE_OVERRIDE.try_with(String::clone) {
+        return Ok(namespace);
+    }
+    Ok(select_setting(conn, "current_namespace")?
+        .map(|s| s.value)
+        .unwrap_or_else(|| DEFAULT_NAMESPACE.to_string()))
+}
+
+/// Run `future` with its queries scoped to `namespace`, leaving the app's
+/// current namespace untouched (e.g. a scheduled scan of another namespace's project)
+pub async fn in_namespace<F: std::future::Future>(namespace: String, future: F) -> F::Output {
+    NAMESPACE_OVERRIDE.scope(namespace, future).await
+}
+
+/// Wrap a future about to be spawned so it keeps the caller's [`in_namespace`] scope
+pub fn inherit_namespace<F: std::future::Future>(future: F) -> impl std::future::Future<Output = F::Output> {
+    let namespace = NAMESPACE_OVERRIDE.try_with(String::clone).ok();
+    async move {
+        match namespace {
+            Some(namespace) => NAMESPACE_OVERRIDE.scope(namespace, future).await,
+            None => future.await,
+        }
+    }
+}
+
+pub fn insert_namespace(conn: &Connection, name: &str) -> Result<()> {
+    conn.execute(
+        "INSERT INTO namespaces (name) VALUES (?)",
+        params![name],
+    ).context("Failed to insert namespace")?;
+
+    Ok(())
+}
+
+pub fn select_namespaces(conn: &Connection) -> Result<Vec<Namespace>> {
+    let mut stmt = conn
+        .prepare("SELECT name, created_at FROM namespaces ORDER BY name ASC")
+        .context("Failed to prepare select namespaces query")?;
+
+    let namespaces = stmt
+        .query_map([], |row| {
+            Ok(Namespace {
+                name: row.get(0)?,
+                created_at: row.get(1)?,
+            })
+        })
+        .context("Failed to map namespaces from query")?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .context("Failed to collect namespaces")?;
+
+    Ok(namespaces)
+}
+
+pub fn select_namespace(conn: &Connection, name: &str) -> Result<Option<Namespace>> {
+    let mut stmt = conn
+        .prepare("SELECT name, created_at FROM namespaces WHERE name = ?")
+        .context("Failed to prepare select namespace query")?;
+
+    let namespace = stmt
+        .query_row(params![name], |row| {
+            Ok(Namespace {
+                name: row.get(0)?,
+                created_at: row.get(1)?,
+            })
+        })
+        .optional()
+        .context("Failed to query namespace")?;
+
+    Ok(namespace)
+}
 
 // ===== PROJECT CRUD =====
 
+/// Insert a project into the current namespace
 pub fn insert_project(conn: &Connection, name: &str, path: &str, framework: Option<&str>) -> Result<i64> {
+    let namespace = current_namespace(conn)?;
     conn.execute(
-        "INSERT INTO projects (name, path, framework) VALUES (?, ?, ?)",
-        params![name, path, framework],
+        "INSERT INTO projects (name, path, framework, namespace) VALUES (?, ?, ?, ?)",
+        params![name, path, framework, namespace],
     ).context("Failed to insert project")?;
 
     Ok(conn.last_insert_rowid())
@@ -15,11 +108,12 @@ pub fn insert_project(conn: &Connection, name: &str, path: &str, framework: Opti
 
 pub fn select_projects(conn: &Connection) -> Result<Vec<Project>> {
     let mut stmt = conn
-        .prepare("SELECT id, name, path, framework, created_at, updated_at FROM projects ORDER BY created_at DESC")
+        .prepare("SELECT id, name, path, framework, created_at, updated_at, namespace, project_cost_limit_usd FROM projects WHERE namespace = ? ORDER BY created_at DESC")
         .context("Failed to prepare select projects query")?;
 
+    let namespace = current_namespace(conn)?;
     let projects = stmt
-        .query_map([], |row| {
+        .query_map(params![namespace], |row| {
             Ok(Project {
                 id: row.get(0)?,
                 name: row.get(1)?,
@@ -27,6 +121,8 @@ pub fn select_projects(conn: &Connection) -> Result<Vec<Project>> {
                 framework: row.get(3)?,
                 created_at: row.get(4)?,
                 updated_at: row.get(5)?,
+                namespace: row.get(6)?,
+                project_cost_limit_usd: row.get(7)?,
             })
         })
         .context("Failed to map projects from query")?
@@ -38,11 +134,12 @@ pub fn select_projects(conn: &Connection) -> Result<Vec<Project>> {
 
 pub fn select_project(conn: &Connection, id: i64) -> Result<Option<Project>> {
     let mut stmt = conn
-        .prepare("SELECT id, name, path, framework, created_at, updated_at FROM projects WHERE id = ?")
+        .prepare("SELECT id, name, path, framework, created_at, updated_at, namespace, project_cost_limit_usd FROM projects WHERE id = ? AND namespace = ?")
         .context("Failed to prepare select project query")?;
 
+    let namespace = current_namespace(conn)?;
     let project = stmt
-        .query_row(params![id], |row| {
+        .query_row(params![id, namespace], |row| {
             Ok(Project {
                 id: row.get(0)?,
                 name: row.get(1)?,
@@ -50,6 +147,8 @@ pub fn select_project(conn: &Connection, id: i64) -> Result<Option<Project>> {
                 framework: row.get(3)?,
                 created_at: row.get(4)?,
                 updated_at: row.get(5)?,
+                namespace: row.get(6)?,
+                project_cost_limit_usd: row.get(7)?,
             })
         })
         .optional()
@@ -60,11 +159,12 @@ pub fn select_project(conn: &Connection, id: i64) -> Result<Option<Project>> {
 
 pub fn select_project_by_path(conn: &Connection, path: &str) -> Result<Option<Project>> {
     let mut stmt = conn
-        .prepare("SELECT id, name, path, framework, created_at, updated_at FROM projects WHERE path = ?")
+        .prepare("SELECT id, name, path, framework, created_at, updated_at, namespace, project_cost_limit_usd FROM projects WHERE path = ? AND namespace = ?")
         .context("Failed to prepare select project by path query")?;
 
+    let namespace = current_namespace(conn)?;
     let project = stmt
-        .query_row(params![path], |row| {
+        .query_row(params![path, namespace], |row| {
             Ok(Project {
                 id: row.get(0)?,
                 name: row.get(1)?,
@@ -72,6 +172,8 @@ pub fn select_project_by_path(conn: &Connection, path: &str) -> Result<Option<Pr
                 framework: row.get(3)?,
                 created_at: row.get(4)?,
                 updated_at: row.get(5)?,
+                namespace: row.get(6)?,
+                project_cost_limit_usd: row.get(7)?,
             })
         })
         .optional()
@@ -90,11 +192,38 @@ pub fn update_project(conn: &Connection, id: i64, name: &str, framework: Option<
     Ok(())
 }
 
-pub fn delete_project(conn: &Connection, id: i64) -> Result<()> {
+/// Set or clear (None) a project's cost limit override
+pub fn update_project_cost_limit(conn: &Connection, id: i64, limit_usd: Option<f64>) -> Result<()> {
+    let updated_at = chrono::Utc::now().to_rfc3339();
     conn.execute(
-        "DELETE FROM projects WHERE id = ?",
-        params![id],
+        "UPDATE projects SET project_cost_limit_usd = ?, updated_at = ? WHERE id = ?",
+        params![limit_usd, updated_at, id],
+    ).context("Failed to update project cost limit")?;
+
+    Ok(())
+}
+
+/// Delete a project in the current namespace and (by cascade) its scans,
+/// violations and fixes
+///
+/// The database is optimized afterwards to reclaim the freed pages; a failed
+/// optimization is logged but doesn't fail the delete.
+///
+/// # Errors
+/// Returns error if the project doesn't exist in the current namespace
+pub fn delete_project(conn: &Connection, id: i64) -> Result<()> {
+    let namespace = current_namespace(conn)?;
+    let deleted = conn.execute(
+        "DELETE FROM projects WHERE id = ? AND namespace = ?",
+        params![id, namespace],
     ).context("Failed to delete project")?;
+    if deleted == 0 {
+        return Err(anyhow::anyhow!("Project not found: {}", id));
+    }
+
+    if let Err(e) = vacuum_and_analyze(conn) {
+        eprintln!("[ryn] Database optimization after deleting project {} failed: {}", id, e);
+    }
 
     Ok(())
 }
@@ -112,11 +241,12 @@ pub fn insert_scan(conn: &Connection, project_id: i64, scan_mode: &str) -> Resul
 
 pub fn select_scans(conn: &Connection, project_id: i64) -> Result<Vec<Scan>> {
     let mut stmt = conn
-        .prepare("SELECT id, project_id, started_at, completed_at, files_scanned, total_files, violations_found, status, scan_mode FROM scans WHERE project_id = ? ORDER BY started_at DESC")
+        .prepare("SELECT id, project_id, started_at, completed_at, files_scanned, total_files, violations_found, status, scan_mode, partial_reason, baseline_scan_id, new_violation_count, resolved_violation_count, enriched_count, context_extraction_failed, processing_timeouts, commit_sha FROM scans WHERE project_id = ? AND project_id IN (SELECT id FROM projects WHERE namespace = ?) ORDER BY started_at DESC")
         .context("Failed to prepare select scans query")?;
 
+    let namespace = current_namespace(conn)?;
     let scans = stmt
-        .query_map(params![project_id], |row| {
+        .query_map(params![project_id, namespace], |row| {
             Ok(Scan {
                 id: row.get(0)?,
                 project_id: row.get(1)?,
@@ -127,10 +257,19 @@ pub fn select_scans(conn: &Connection, project_id: i64) -> Result<Vec<Scan>> {
                 violations_found: row.get(6)?,
                 status: row.get(7)?,
                 scan_mode: row.get(8)?,
+                partial_reason: row.get(9)?,
+                baseline_scan_id: row.get(10)?,
+                new_violation_count: row.get(11)?,
+                resolved_violation_count: row.get(12)?,
+                enriched_count: row.get(13)?,
+                context_extraction_failed: row.get(14)?,
+                processing_timeouts: row.get(15)?,
+                commit_sha: row.get(16)?,
                 critical_count: 0,
                 high_count: 0,
                 medium_count: 0,
                 low_count: 0,
+                detail_stats: None,
             })
         })
         .context("Failed to map scans from query")?
@@ -142,11 +281,12 @@ pub fn select_scans(conn: &Connection, project_id: i64) -> Result<Vec<Scan>> {
 
 pub fn select_scan(conn: &Connection, id: i64) -> Result<Option<Scan>> {
     let mut stmt = conn
-        .prepare("SELECT id, project_id, started_at, completed_at, files_scanned, total_files, violations_found, status, scan_mode FROM scans WHERE id = ?")
+        .prepare("SELECT id, project_id, started_at, completed_at, files_scanned, total_files, violations_found, status, scan_mode, partial_reason, baseline_scan_id, new_violation_count, resolved_violation_count, enriched_count, context_extraction_failed, processing_timeouts, commit_sha FROM scans WHERE id = ? AND project_id IN (SELECT id FROM projects WHERE namespace = ?)")
         .context("Failed to prepare select scan query")?;
 
+    let namespace = current_namespace(conn)?;
     let scan = stmt
-        .query_row(params![id], |row| {
+        .query_row(params![id, namespace], |row| {
             Ok(Scan {
                 id: row.get(0)?,
                 project_id: row.get(1)?,
@@ -157,10 +297,19 @@ pub fn select_scan(conn: &Connection, id: i64) -> Result<Option<Scan>> {
                 violations_found: row.get(6)?,
                 status: row.get(7)?,
                 scan_mode: row.get(8)?,
+                partial_reason: row.get(9)?,
+                baseline_scan_id: row.get(10)?,
+                new_violation_count: row.get(11)?,
+                resolved_violation_count: row.get(12)?,
+                enriched_count: row.get(13)?,
+                context_extraction_failed: row.get(14)?,
+                processing_timeouts: row.get(15)?,
+                commit_sha: row.get(16)?,
                 critical_count: 0,
                 high_count: 0,
                 medium_count: 0,
                 low_count: 0,
+                detail_stats: None,
             })
         })
         .optional()
@@ -178,6 +327,62 @@ pub fn update_scan_status(conn: &Connection, id: i64, status: &str, completed_at
     Ok(())
 }
 
+/// Mark a scan partial, recording when and why it stopped early
+pub fn update_scan_partial(conn: &Connection, id: i64, completed_at: &str, partial_reason: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE scans SET status = 'partial', completed_at = ?, partial_reason = ? WHERE id = ?",
+        params![completed_at, partial_reason, id],
+    ).context("Failed to mark scan partial")?;
+
+    Ok(())
+}
+
+/// Record which scan a scan was compared against and the diff counts
+pub fn update_scan_baseline(
+    conn: &Connection,
+    id: i64,
+    baseline_scan_id: i64,
+    new_violation_count: i32,
+    resolved_violation_count: i32,
+) -> Result<()> {
+    conn.execute(
+        "UPDATE scans SET baseline_scan_id = ?, new_violation_count = ?, resolved_violation_count = ? WHERE id = ?",
+        params![baseline_scan_id, new_violation_count, resolved_violation_count, id],
+    ).context("Failed to update scan baseline")?;
+
+    Ok(())
+}
+
+/// Record how many violations got tree-sitter context and how many couldn't
+pub fn update_scan_enrichment(conn: &Connection, id: i64, enriched_count: i32, context_extraction_failed: i32) -> Result<()> {
+    conn.execute(
+        "UPDATE scans SET enriched_count = ?, context_extraction_failed = ? WHERE id = ?",
+        params![enriched_count, context_extraction_failed, id],
+    ).context("Failed to update scan enrichment counts")?;
+
+    Ok(())
+}
+
+/// Record how many files were skipped because their rules hit the per-file timeout
+pub fn update_scan_processing_timeouts(conn: &Connection, id: i64, processing_timeouts: i32) -> Result<()> {
+    conn.execute(
+        "UPDATE scans SET processing_timeouts = ? WHERE id = ?",
+        params![processing_timeouts, id],
+    ).context("Failed to update scan processing timeouts")?;
+
+    Ok(())
+}
+
+/// Record the commit a scan ran at
+pub fn update_scan_commit_sha(conn: &Connection, id: i64, commit_sha: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE scans SET commit_sha = ? WHERE id = ?",
+        params![commit_sha, id],
+    ).context("Failed to update scan commit SHA")?;
+
+    Ok(())
+}
+
 pub fn update_scan_results(conn: &Connection, id: i64, files_scanned: i32, total_files: i32, violations_found: i32) -> Result<()> {
     conn.execute(
         "UPDATE scans SET files_scanned = ?, total_files = ?, violations_found = ? WHERE id = ?",
@@ -191,7 +396,7 @@ pub fn update_scan_results(conn: &Connection, id: i64, files_scanned: i32, total
 
 pub fn insert_violation(conn: &Connection, violation: &Violation) -> Result<i64> {
     conn.execute(
-        "INSERT INTO violations (scan_id, control_id, severity, description, file_path, line_number, code_snippet, status, detection_method, confidence_score, llm_reasoning, regex_reasoning, function_name, class_name) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        "INSERT INTO violations (scan_id, control_id, severity, description, file_path, line_number, code_snippet, status, detection_method, confidence_score, llm_reasoning, regex_reasoning, function_name, class_name, decorator_name, is_new, package_name, fingerprint) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
         params![
             violation.scan_id,
             violation.control_id,
@@ -207,19 +412,76 @@ pub fn insert_violation(conn: &Connection, violation: &Violation) -> Result<i64>
             violation.regex_reasoning,
             violation.function_name,
             violation.class_name,
+            violation.decorator_name,
+            violation.is_new,
+            violation.package_name,
+            violation.compute_fingerprint(),
         ],
     ).context("Failed to insert violation")?;
 
     Ok(conn.last_insert_rowid())
 }
 
+/// Insert many violations in a single transaction with one prepared statement
+///
+/// Much faster than calling [`insert_violation`] in a loop, since SQLite
+/// only syncs once per transaction. Findings already stored for the scan at
+/// the same line (same fingerprint) are skipped by `INSERT OR IGNORE` and
+/// not counted. Any other error (e.g. a `scan_id` that doesn't exist, which
+/// violates the foreign key) rolls back the whole batch.
+///
+/// Returns: The number of rows inserted
+pub fn insert_violations_batch(conn: &Connection, violations: &[Violation]) -> Result<i64> {
+    if violations.is_empty() {
+        return Ok(0);
+    }
+
+    let tx = begin_write(conn)
+        .context("Failed to begin violation batch transaction")?;
+
+    let mut inserted = 0;
+    {
+        let mut stmt = tx
+            .prepare("INSERT OR IGNORE INTO violations (scan_id, control_id, severity, description, file_path, line_number, code_snippet, status, detection_method, confidence_score, llm_reasoning, regex_reasoning, function_name, class_name, decorator_name, is_new, package_name, fingerprint) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)")
+            .context("Failed to prepare violation batch insert")?;
+
+        for violation in violations {
+            inserted += stmt.execute(params![
+                violation.scan_id,
+                violation.control_id,
+                violation.severity,
+                violation.description,
+                violation.file_path,
+                violation.line_number,
+                violation.code_snippet,
+                violation.status,
+                violation.detection_method,
+                violation.confidence_score,
+                violation.llm_reasoning,
+                violation.regex_reasoning,
+                violation.function_name,
+                violation.class_name,
+                violation.decorator_name,
+                violation.is_new,
+                violation.package_name,
+                violation.compute_fingerprint(),
+            ]).context("Failed to insert violation in batch")? as i64;
+        }
+    }
+
+    tx.commit().context("Failed to commit violation batch")?;
+
+    Ok(inserted)
+}
+
 pub fn select_violations(conn: &Connection, scan_id: i64) -> Result<Vec<Violation>> {
     let mut stmt = conn
-        .prepare("SELECT id, scan_id, control_id, severity, description, file_path, line_number, code_snippet, status, detected_at, detection_method, confidence_score, llm_reasoning, regex_reasoning, function_name, class_name FROM violations WHERE scan_id = ? ORDER BY severity DESC, line_number ASC")
+        .prepare("SELECT id, scan_id, control_id, severity, description, file_path, line_number, code_snippet, status, detected_at, detection_method, confidence_score, llm_reasoning, regex_reasoning, function_name, class_name, decorator_name, is_new, last_status_change_reason, package_name, fingerprint, lifecycle, jira_issue_key FROM violations WHERE scan_id = ? AND scan_id IN (SELECT s.id FROM scans s JOIN projects p ON p.id = s.project_id WHERE p.namespace = ?) ORDER BY severity DESC, line_number ASC")
         .context("Failed to prepare select violations query")?;
 
+    let namespace = current_namespace(conn)?;
     let violations = stmt
-        .query_map(params![scan_id], |row| {
+        .query_map(params![scan_id, namespace], |row| {
             Ok(Violation {
                 id: row.get(0)?,
                 scan_id: row.get(1)?,
@@ -237,6 +499,15 @@ pub fn select_violations(conn: &Connection, scan_id: i64) -> Result<Vec<Violatio
                 regex_reasoning: row.get(13)?,
                 function_name: row.get(14)?,
                 class_name: row.get(15)?,
+                decorator_name: row.get(16)?,
+                is_new: row.get(17)?,
+                last_status_change_reason: row.get(18)?,
+                package_name: row.get(19)?,
+                fingerprint: row.get(20)?,
+                lifecycle: row.get(21)?,
+                jira_issue_key: row.get(22)?,
+                first_seen_scan_id: None,
+                last_seen_scan_id: None,
             })
         })
         .context("Failed to map violations from query")?
@@ -246,13 +517,125 @@ pub fn select_violations(conn: &Connection, scan_id: i64) -> Result<Vec<Violatio
     Ok(violations)
 }
 
+/// Get a scan's violations whose control belongs to `category` (controls.category)
+pub fn select_violations_by_category(conn: &Connection, scan_id: i64, category: &str) -> Result<Vec<Violation>> {
+    let mut stmt = conn
+        .prepare("SELECT v.id, v.scan_id, v.control_id, v.severity, v.description, v.file_path, v.line_number, v.code_snippet, v.status, v.detected_at, v.detection_method, v.confidence_score, v.llm_reasoning, v.regex_reasoning, v.function_name, v.class_name, v.decorator_name, v.is_new, v.last_status_change_reason, v.package_name, v.fingerprint, v.lifecycle, v.jira_issue_key FROM violations v JOIN controls c ON c.id = v.control_id WHERE v.scan_id = ? AND c.category = ? AND v.scan_id IN (SELECT s.id FROM scans s JOIN projects p ON p.id = s.project_id WHERE p.namespace = ?) ORDER BY v.severity DESC, v.line_number ASC")
+        .context("Failed to prepare select violations by category query")?;
+
+    let namespace = current_namespace(conn)?;
+    let violations = stmt
+        .query_map(params![scan_id, category, namespace], |row| {
+            Ok(Violation {
+                id: row.get(0)?,
+                scan_id: row.get(1)?,
+                control_id: row.get(2)?,
+                severity: row.get(3)?,
+                description: row.get(4)?,
+                file_path: row.get(5)?,
+                line_number: row.get(6)?,
+                code_snippet: row.get(7)?,
+                status: row.get(8)?,
+                detected_at: row.get(9)?,
+                detection_method: row.get(10)?,
+                confidence_score: row.get(11)?,
+                llm_reasoning: row.get(12)?,
+                regex_reasoning: row.get(13)?,
+                function_name: row.get(14)?,
+                class_name: row.get(15)?,
+                decorator_name: row.get(16)?,
+                is_new: row.get(17)?,
+                last_status_change_reason: row.get(18)?,
+                package_name: row.get(19)?,
+                fingerprint: row.get(20)?,
+                lifecycle: row.get(21)?,
+                jira_issue_key: row.get(22)?,
+                first_seen_scan_id: None,
+                last_seen_scan_id: None,
+            })
+        })
+        .context("Failed to map violations by category from query")?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .context("Failed to collect violations by category")?;
+
+    Ok(violations)
+}
+
+/// Get a project's violations across all completed scans, one per finding
+///
+/// A finding is a `(control_id, file_path, line_number)` tuple. Each is
+/// returned as its occurrence in the latest completed scan that reported it,
+/// with `first_seen_scan_id` / `last_seen_scan_id` set to the earliest and
+/// latest such scans. If that scan reported the tuple more than once, the
+/// last inserted row is used.
+pub fn select_unique_violations_for_project(conn: &Connection, project_id: i64) -> Result<Vec<Violation>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT v.id, v.scan_id, v.control_id, v.severity, v.description, v.file_path, v.line_number, v.code_snippet, v.status, v.detected_at, v.detection_method, v.confidence_score, v.llm_reasoning, v.regex_reasoning, v.function_name, v.class_name, v.decorator_name, v.is_new, v.last_status_change_reason, v.package_name, v.fingerprint, v.lifecycle, v.jira_issue_key, g.first_seen, g.last_seen
+             FROM violations v
+             JOIN (
+                 SELECT cv.control_id, cv.file_path, cv.line_number, MIN(cv.scan_id) AS first_seen, MAX(cv.scan_id) AS last_seen
+                 FROM violations cv
+                 JOIN scans s ON s.id = cv.scan_id
+                 WHERE s.project_id = ?1 AND s.completed_at IS NOT NULL
+                 GROUP BY cv.control_id, cv.file_path, cv.line_number
+             ) g ON g.control_id = v.control_id AND g.file_path = v.file_path AND g.line_number = v.line_number
+             WHERE v.id = (
+                 SELECT MAX(lv.id) FROM violations lv
+                 WHERE lv.scan_id = g.last_seen AND lv.control_id = g.control_id
+                   AND lv.file_path = g.file_path AND lv.line_number = g.line_number
+             )
+             AND ?1 IN (SELECT id FROM projects WHERE namespace = ?2)
+             ORDER BY v.severity DESC, v.file_path ASC, v.line_number ASC",
+        )
+        .context("Failed to prepare select unique violations query")?;
+
+    let namespace = current_namespace(conn)?;
+    let violations = stmt
+        .query_map(params![project_id, namespace], |row| {
+            Ok(Violation {
+                id: row.get(0)?,
+                scan_id: row.get(1)?,
+                control_id: row.get(2)?,
+                severity: row.get(3)?,
+                description: row.get(4)?,
+                file_path: row.get(5)?,
+                line_number: row.get(6)?,
+                code_snippet: row.get(7)?,
+                status: row.get(8)?,
+                detected_at: row.get(9)?,
+                detection_method: row.get(10)?,
+                confidence_score: row.get(11)?,
+                llm_reasoning: row.get(12)?,
+                regex_reasoning: row.get(13)?,
+                function_name: row.get(14)?,
+                class_name: row.get(15)?,
+                decorator_name: row.get(16)?,
+                is_new: row.get(17)?,
+                last_status_change_reason: row.get(18)?,
+                package_name: row.get(19)?,
+                fingerprint: row.get(20)?,
+                lifecycle: row.get(21)?,
+                jira_issue_key: row.get(22)?,
+                first_seen_scan_id: row.get(23)?,
+                last_seen_scan_id: row.get(24)?,
+            })
+        })
+        .context("Failed to map unique violations from query")?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .context("Failed to collect unique violations")?;
+
+    Ok(violations)
+}
+
 pub fn select_violation(conn: &Connection, id: i64) -> Result<Option<Violation>> {
     let mut stmt = conn
-        .prepare("SELECT id, scan_id, control_id, severity, description, file_path, line_number, code_snippet, status, detected_at, detection_method, confidence_score, llm_reasoning, regex_reasoning, function_name, class_name FROM violations WHERE id = ?")
+        .prepare("SELECT id, scan_id, control_id, severity, description, file_path, line_number, code_snippet, status, detected_at, detection_method, confidence_score, llm_reasoning, regex_reasoning, function_name, class_name, decorator_name, is_new, last_status_change_reason, package_name, fingerprint, lifecycle, jira_issue_key FROM violations WHERE id = ? AND scan_id IN (SELECT s.id FROM scans s JOIN projects p ON p.id = s.project_id WHERE p.namespace = ?)")
         .context("Failed to prepare select violation query")?;
 
+    let namespace = current_namespace(conn)?;
     let violation = stmt
-        .query_row(params![id], |row| {
+        .query_row(params![id, namespace], |row| {
             Ok(Violation {
                 id: row.get(0)?,
                 scan_id: row.get(1)?,
@@ -270,6 +653,15 @@ pub fn select_violation(conn: &Connection, id: i64) -> Result<Option<Violation>>
                 regex_reasoning: row.get(13)?,
                 function_name: row.get(14)?,
                 class_name: row.get(15)?,
+                decorator_name: row.get(16)?,
+                is_new: row.get(17)?,
+                last_status_change_reason: row.get(18)?,
+                package_name: row.get(19)?,
+                fingerprint: row.get(20)?,
+                lifecycle: row.get(21)?,
+                jira_issue_key: row.get(22)?,
+                first_seen_scan_id: None,
+                last_seen_scan_id: None,
             })
         })
         .optional()
@@ -278,20 +670,175 @@ pub fn select_violation(conn: &Connection, id: i64) -> Result<Option<Violation>>
     Ok(violation)
 }
 
-pub fn update_violation_status(conn: &Connection, id: i64, status: &str) -> Result<()> {
+/// Move a violation to a new status, enforcing [`ViolationStatus::valid_transitions`]
+///
+/// The change and its reason are recorded in `violation_status_history`, and
+/// the reason is stored as `last_status_change_reason`. Setting the status a
+/// violation already has is a no-op. A forbidden transition fails with an
+/// [`InvalidTransitionError`] (recover it with `downcast_ref`); a violation
+/// outside the current namespace is not found.
+pub fn update_violation_status(conn: &Connection, id: i64, status: &str, reason: Option<&str>) -> Result<()> {
+    let to = ViolationStatus::from_str(status)
+        .ok_or_else(|| anyhow::anyhow!("Invalid violation status: {}", status))?;
+
+    let namespace = current_namespace(conn)?;
+    let tx = begin_write(conn)
+        .context("Failed to begin violation status transaction")?;
+    transition_violation_status(&tx, &namespace, id, to, reason)?;
+    tx.commit().context("Failed to commit violation status transaction")?;
+
+    Ok(())
+}
+
+/// Violations whose scan belongs to a project in the namespace bound to `?`
+const VIOLATION_IN_NAMESPACE: &str =
+    "scan_id IN (SELECT s.id FROM scans s JOIN projects p ON p.id = s.project_id WHERE p.namespace = ?)";
+
+/// Apply one status transition inside the caller's transaction
+///
+/// Violations outside `namespace` are treated as missing. Returns whether
+/// the status changed (false when it was already `to`).
+fn transition_violation_status(
+    conn: &Connection,
+    namespace: &str,
+    id: i64,
+    to: ViolationStatus,
+    reason: Option<&str>,
+) -> Result<bool> {
+    let current: String = conn
+        .query_row(
+            &format!("SELECT status FROM violations WHERE id = ? AND {}", VIOLATION_IN_NAMESPACE),
+            params![id, namespace],
+            |row| row.get(0),
+        )
+        .optional()
+        .context("Failed to query violation status")?
+        .ok_or_else(|| anyhow::anyhow!("Violation not found: {}", id))?;
+    let from = ViolationStatus::from_str(&current)
+        .ok_or_else(|| anyhow::anyhow!("Violation {} has unknown status: {}", id, current))?;
+
+    if from == to {
+        return Ok(false);
+    }
+    if !from.can_transition_to(to) {
+        return Err(InvalidTransitionError { violation_id: id, from, to }.into());
+    }
+
+    conn.execute(
+        &format!("UPDATE violations SET status = ?, last_status_change_reason = ? WHERE id = ? AND {}", VIOLATION_IN_NAMESPACE),
+        params![to.as_str(), reason, id, namespace],
+    ).with_context(|| format!("Failed to update status of violation {}", id))?;
+
+    conn.execute(
+        "INSERT INTO violation_status_history (violation_id, from_status, to_status, reason)
+         VALUES (?, ?, ?, ?)",
+        params![id, from.as_str(), to.as_str(), reason],
+    ).with_context(|| format!("Failed to record status history of violation {}", id))?;
+
+    Ok(true)
+}
+
+/// Set the status and reason of several violations in one transaction
+///
+/// Either every violation is updated or, if any is missing, the transition is
+/// not allowed or the update fails, none are. Returns the number of violations updated.
+pub fn update_violations_status(conn: &Connection, ids: &[i64], status: &str, reason: &str) -> Result<i64> {
+    let to = ViolationStatus::from_str(status)
+        .ok_or_else(|| anyhow::anyhow!("Invalid violation status: {}", status))?;
+
+    let namespace = current_namespace(conn)?;
+    let tx = begin_write(conn)
+        .context("Failed to begin violation status transaction")?;
+
+    for id in ids {
+        transition_violation_status(&tx, &namespace, *id, to, Some(reason))?;
+    }
+
+    tx.commit().context("Failed to commit violation status transaction")?;
+
+    Ok(ids.len() as i64)
+}
+
+/// Set a scan's dismissed and fixed (including fixed_verified) violations back to open
+///
+/// Clears `last_status_change_reason` and records each change in the status
+/// history. Returns the number of violations reopened.
+pub fn reopen_violations(conn: &Connection, scan_id: i64) -> Result<i64> {
+    let namespace = current_namespace(conn)?;
+    let tx = begin_write(conn)
+        .context("Failed to begin reopen transaction")?;
+
+    let mut stmt = tx.prepare(&format!(
+        "SELECT id FROM violations
+         WHERE scan_id = ? AND status IN ('dismissed', 'fixed', 'fixed_verified') AND {}",
+        VIOLATION_IN_NAMESPACE
+    )).context("Failed to prepare reopen query")?;
+    let ids = stmt
+        .query_map(params![scan_id, namespace], |row| row.get::<_, i64>(0))
+        .context("Failed to query violations to reopen")?
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .context("Failed to read violations to reopen")?;
+    drop(stmt);
+
+    for id in &ids {
+        transition_violation_status(&tx, &namespace, *id, ViolationStatus::Open, None)?;
+    }
+
+    tx.commit().context("Failed to commit reopen transaction")?;
+
+    Ok(ids.len() as i64)
+}
+
+/// A violation's status transitions, oldest first
+pub fn select_violation_status_history(conn: &Connection, violation_id: i64) -> Result<Vec<StatusTransition>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, violation_id, from_status, to_status, reason, changed_at
+         FROM violation_status_history WHERE violation_id = ? ORDER BY id ASC",
+    ).context("Failed to prepare status history query")?;
+
+    let transitions = stmt
+        .query_map(params![violation_id], |row| {
+            Ok(StatusTransition {
+                id: row.get(0)?,
+                violation_id: row.get(1)?,
+                from_status: row.get(2)?,
+                to_status: row.get(3)?,
+                reason: row.get(4)?,
+                changed_at: row.get(5)?,
+            })
+        })
+        .context("Failed to query status history")?
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .context("Failed to read status history")?;
+
+    Ok(transitions)
+}
+
+/// Tag a violation as new relative to its scan's baseline
+pub fn update_violation_is_new(conn: &Connection, id: i64, is_new: bool) -> Result<()> {
     conn.execute(
-        "UPDATE violations SET status = ? WHERE id = ?",
-        params![status, id],
-    ).context("Failed to update violation status")?;
+        "UPDATE violations SET is_new = ? WHERE id = ?",
+        params![is_new, id],
+    ).context("Failed to update violation is_new")?;
 
     Ok(())
 }
 
 // ===== FIX CRUD =====
 
+/// Insert a fix as the next attempt for its violation
+///
+/// `fix_attempt_number` is assigned by the database (one more than the
+/// violation's latest attempt); the value on `fix` is ignored.
 pub fn insert_fix(conn: &Connection, fix: &Fix) -> Result<i64> {
+    let created_at = if fix.created_at.is_empty() {
+        chrono::Utc::now().to_rfc3339()
+    } else {
+        fix.created_at.clone()
+    };
     conn.execute(
-        "INSERT INTO fixes (violation_id, original_code, fixed_code, explanation, trust_level, applied_by) VALUES (?, ?, ?, ?, ?, ?)",
+        "INSERT INTO fixes (violation_id, original_code, fixed_code, explanation, trust_level, applied_by, fix_attempt_number, created_at, changes_summary, test_recommendation)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, (SELECT COALESCE(MAX(fix_attempt_number), 0) + 1 FROM fixes WHERE violation_id = ?1), ?7, ?8, ?9)",
         params![
             fix.violation_id,
             fix.original_code,
@@ -299,6 +846,9 @@ pub fn insert_fix(conn: &Connection, fix: &Fix) -> Result<i64> {
             fix.explanation,
             fix.trust_level,
             fix.applied_by,
+            created_at,
+            fix.changes_summary,
+            fix.test_recommendation,
         ],
     ).context("Failed to insert fix")?;
 
@@ -307,11 +857,12 @@ pub fn insert_fix(conn: &Connection, fix: &Fix) -> Result<i64> {
 
 pub fn select_fix(conn: &Connection, id: i64) -> Result<Option<Fix>> {
     let mut stmt = conn
-        .prepare("SELECT id, violation_id, original_code, fixed_code, explanation, trust_level, applied_at, applied_by, git_commit_sha, backup_path FROM fixes WHERE id = ?")
+        .prepare("SELECT id, violation_id, original_code, fixed_code, explanation, trust_level, applied_at, applied_by, git_commit_sha, backup_path, verification_status, verified_at, fix_attempt_number, created_at, git_branch, changes_summary, test_recommendation FROM fixes WHERE id = ? AND violation_id IN (SELECT v.id FROM violations v JOIN scans s ON s.id = v.scan_id JOIN projects p ON p.id = s.project_id WHERE p.namespace = ?)")
         .context("Failed to prepare select fix query")?;
 
+    let namespace = current_namespace(conn)?;
     let fix = stmt
-        .query_row(params![id], |row| {
+        .query_row(params![id, namespace], |row| {
             Ok(Fix {
                 id: row.get(0)?,
                 violation_id: row.get(1)?,
@@ -323,6 +874,13 @@ pub fn select_fix(conn: &Connection, id: i64) -> Result<Option<Fix>> {
                 applied_by: row.get(7)?,
                 git_commit_sha: row.get(8)?,
                 backup_path: row.get(9)?,
+                verification_status: row.get(10)?,
+                verified_at: row.get(11)?,
+                fix_attempt_number: row.get(12)?,
+                created_at: row.get(13)?,
+                git_branch: row.get(14)?,
+                changes_summary: row.get(15)?,
+                test_recommendation: row.get(16)?,
             })
         })
         .optional()
@@ -333,11 +891,12 @@ pub fn select_fix(conn: &Connection, id: i64) -> Result<Option<Fix>> {
 
 pub fn select_fix_for_violation(conn: &Connection, violation_id: i64) -> Result<Option<Fix>> {
     let mut stmt = conn
-        .prepare("SELECT id, violation_id, original_code, fixed_code, explanation, trust_level, applied_at, applied_by, git_commit_sha, backup_path FROM fixes WHERE violation_id = ? LIMIT 1")
+        .prepare("SELECT id, violation_id, original_code, fixed_code, explanation, trust_level, applied_at, applied_by, git_commit_sha, backup_path, verification_status, verified_at, fix_attempt_number, created_at, git_branch, changes_summary, test_recommendation FROM fixes WHERE violation_id = ? AND violation_id IN (SELECT v.id FROM violations v JOIN scans s ON s.id = v.scan_id JOIN projects p ON p.id = s.project_id WHERE p.namespace = ?) ORDER BY created_at DESC, id DESC LIMIT 1")
         .context("Failed to prepare select fix query")?;
 
+    let namespace = current_namespace(conn)?;
     let fix = stmt
-        .query_row(params![violation_id], |row| {
+        .query_row(params![violation_id, namespace], |row| {
             Ok(Fix {
                 id: row.get(0)?,
                 violation_id: row.get(1)?,
@@ -349,6 +908,13 @@ pub fn select_fix_for_violation(conn: &Connection, violation_id: i64) -> Result<
                 applied_by: row.get(7)?,
                 git_commit_sha: row.get(8)?,
                 backup_path: row.get(9)?,
+                verification_status: row.get(10)?,
+                verified_at: row.get(11)?,
+                fix_attempt_number: row.get(12)?,
+                created_at: row.get(13)?,
+                git_branch: row.get(14)?,
+                changes_summary: row.get(15)?,
+                test_recommendation: row.get(16)?,
             })
         })
         .optional()
@@ -357,16 +923,121 @@ pub fn select_fix_for_violation(conn: &Connection, violation_id: i64) -> Result<
     Ok(fix)
 }
 
+/// All fixes generated for a violation, newest first
+pub fn select_fixes_for_violation(conn: &Connection, violation_id: i64) -> Result<Vec<Fix>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, violation_id, original_code, fixed_code, explanation, trust_level, applied_at, applied_by, git_commit_sha, backup_path, verification_status, verified_at, fix_attempt_number, created_at, git_branch, changes_summary, test_recommendation
+             FROM fixes
+             WHERE violation_id = ? AND violation_id IN (SELECT v.id FROM violations v JOIN scans s ON s.id = v.scan_id JOIN projects p ON p.id = s.project_id WHERE p.namespace = ?)
+             ORDER BY created_at DESC, id DESC",
+        )
+        .context("Failed to prepare select fixes for violation query")?;
+
+    let namespace = current_namespace(conn)?;
+    let fixes = stmt
+        .query_map(params![violation_id, namespace], |row| {
+            Ok(Fix {
+                id: row.get(0)?,
+                violation_id: row.get(1)?,
+                original_code: row.get(2)?,
+                fixed_code: row.get(3)?,
+                explanation: row.get(4)?,
+                trust_level: row.get(5)?,
+                applied_at: row.get(6)?,
+                applied_by: row.get(7)?,
+                git_commit_sha: row.get(8)?,
+                backup_path: row.get(9)?,
+                verification_status: row.get(10)?,
+                verified_at: row.get(11)?,
+                fix_attempt_number: row.get(12)?,
+                created_at: row.get(13)?,
+                git_branch: row.get(14)?,
+                changes_summary: row.get(15)?,
+                test_recommendation: row.get(16)?,
+            })
+        })
+        .context("Failed to query fixes for violation")?
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .context("Failed to collect fixes for violation")?;
+
+    Ok(fixes)
+}
+
 pub fn update_fix_applied(conn: &Connection, id: i64, git_commit_sha: &str, backup_path: Option<&str>) -> Result<()> {
     let applied_at = chrono::Utc::now().to_rfc3339();
     conn.execute(
-        "UPDATE fixes SET applied_at = ?, git_commit_sha = ?, backup_path = ? WHERE id = ?",
+        "UPDATE fixes SET applied_at = ?, git_commit_sha = ?, backup_path = ?, verification_status = 'pending', verified_at = NULL WHERE id = ?",
         params![applied_at, git_commit_sha, backup_path, id],
     ).context("Failed to update fix applied")?;
 
     Ok(())
 }
 
+pub fn update_fix_git_branch(conn: &Connection, id: i64, git_branch: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE fixes SET git_branch = ? WHERE id = ?",
+        params![git_branch, id],
+    ).context("Failed to update fix git branch")?;
+
+    Ok(())
+}
+
+pub fn update_fix_trust_level(conn: &Connection, id: i64, trust_level: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE fixes SET trust_level = ? WHERE id = ?",
+        params![trust_level, id],
+    ).context("Failed to update fix trust level")?;
+
+    Ok(())
+}
+
+pub fn update_fix_verification(conn: &Connection, id: i64, verification_status: &str, verified_at: Option<&str>) -> Result<()> {
+    conn.execute(
+        "UPDATE fixes SET verification_status = ?, verified_at = ? WHERE id = ?",
+        params![verification_status, verified_at, id],
+    ).context("Failed to update fix verification")?;
+
+    Ok(())
+}
+
+/// Count applied fixes for a project by verification outcome
+///
+/// Returns tuple of (verified, failed, pending) counts. Applied fixes that
+/// predate verification tracking (NULL status) are counted as pending.
+pub fn get_fix_verification_counts(conn: &Connection, project_id: i64) -> Result<(i64, i64, i64)> {
+    let mut stmt = conn.prepare(
+        "SELECT COALESCE(f.verification_status, 'pending'), COUNT(*)
+         FROM fixes f
+         JOIN violations v ON v.id = f.violation_id
+         JOIN scans s ON s.id = v.scan_id
+         JOIN projects p ON p.id = s.project_id
+         WHERE s.project_id = ? AND p.namespace = ? AND f.applied_at IS NOT NULL
+         GROUP BY COALESCE(f.verification_status, 'pending')"
+    ).context("Failed to prepare fix verification counts query")?;
+
+    let mut verified = 0;
+    let mut failed = 0;
+    let mut pending = 0;
+
+    let namespace = current_namespace(conn)?;
+    let rows = stmt.query_map(params![project_id, namespace], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+    }).context("Failed to query fix verification counts")?;
+
+    for result in rows {
+        let (status, count) = result.context("Failed to process fix verification row")?;
+        match status.as_str() {
+            "verified" => verified = count,
+            "failed" => failed = count,
+            "pending" => pending = count,
+            _ => {}
+        }
+    }
+
+    Ok((verified, failed, pending))
+}
+
 // ===== AUDIT EVENT CRUD =====
 
 pub fn insert_audit_event(conn: &Connection, event: &AuditEvent) -> Result<i64> {
@@ -387,11 +1058,12 @@ pub fn insert_audit_event(conn: &Connection, event: &AuditEvent) -> Result<i64>
 
 pub fn select_audit_events(conn: &Connection, limit: i64) -> Result<Vec<AuditEvent>> {
     let mut stmt = conn
-        .prepare("SELECT id, event_type, project_id, violation_id, fix_id, description, metadata, created_at FROM audit_events ORDER BY created_at DESC LIMIT ?")
+        .prepare("SELECT id, event_type, project_id, violation_id, fix_id, description, metadata, created_at FROM audit_events WHERE (project_id IS NULL OR project_id IN (SELECT id FROM projects WHERE namespace = ?)) ORDER BY created_at DESC, id DESC LIMIT ?")
         .context("Failed to prepare select audit events query")?;
 
+    let namespace = current_namespace(conn)?;
     let events = stmt
-        .query_map(params![limit], |row| {
+        .query_map(params![namespace, limit], |row| {
             Ok(AuditEvent {
                 id: row.get(0)?,
                 event_type: row.get(1)?,
@@ -412,11 +1084,12 @@ pub fn select_audit_events(conn: &Connection, limit: i64) -> Result<Vec<AuditEve
 
 pub fn select_audit_events_by_project(conn: &Connection, project_id: i64) -> Result<Vec<AuditEvent>> {
     let mut stmt = conn
-        .prepare("SELECT id, event_type, project_id, violation_id, fix_id, description, metadata, created_at FROM audit_events WHERE project_id = ? ORDER BY created_at DESC")
+        .prepare("SELECT id, event_type, project_id, violation_id, fix_id, description, metadata, created_at FROM audit_events WHERE project_id = ? AND project_id IN (SELECT id FROM projects WHERE namespace = ?) ORDER BY created_at DESC, id DESC")
         .context("Failed to prepare select audit events query")?;
 
+    let namespace = current_namespace(conn)?;
     let events = stmt
-        .query_map(params![project_id], |row| {
+        .query_map(params![project_id, namespace], |row| {
             Ok(AuditEvent {
                 id: row.get(0)?,
                 event_type: row.get(1)?,
@@ -435,6 +1108,57 @@ pub fn select_audit_events_by_project(conn: &Connection, project_id: i64) -> Res
     Ok(events)
 }
 
+/// Stream audit events created in `[start, end)`, newest first
+///
+/// Rows are handed to `f` one at a time as SQLite produces them, so large
+/// ranges are never collected into memory. `start`/`end` compare against
+/// `created_at` as text (e.g. "2025-01-01"). With a `project_id`, only that
+/// project's events are included; otherwise events without a project are too.
+///
+/// Returns the number of events passed to `f`.
+pub fn for_each_audit_event_in_range(
+    conn: &Connection,
+    project_id: Option<i64>,
+    start: &str,
+    end: &str,
+    mut f: impl FnMut(AuditEvent) -> Result<()>,
+) -> Result<i64> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, event_type, project_id, violation_id, fix_id, description, metadata, created_at
+             FROM audit_events
+             WHERE created_at >= ?1 AND created_at < ?2
+               AND (?3 IS NULL OR project_id = ?3)
+               AND (project_id IS NULL OR project_id IN (SELECT id FROM projects WHERE namespace = ?4))
+             ORDER BY created_at DESC, id DESC",
+        )
+        .context("Failed to prepare audit event range query")?;
+
+    let namespace = current_namespace(conn)?;
+    let rows = stmt
+        .query_map(params![start, end, project_id, namespace], |row| {
+            Ok(AuditEvent {
+                id: row.get(0)?,
+                event_type: row.get(1)?,
+                project_id: row.get(2)?,
+                violation_id: row.get(3)?,
+                fix_id: row.get(4)?,
+                description: row.get(5)?,
+                metadata: row.get(6)?,
+                created_at: row.get(7)?,
+            })
+        })
+        .context("Failed to query audit events in range")?;
+
+    let mut count = 0;
+    for row in rows {
+        f(row.context("Failed to read audit event")?)?;
+        count += 1;
+    }
+
+    Ok(count)
+}
+
 // ===== CONTROL QUERIES =====
 
 pub fn select_controls(conn: &Connection) -> Result<Vec<Control>> {
@@ -548,12 +1272,14 @@ pub fn select_all_projects(conn: &Connection) -> Result<Vec<Project>> {
 
 pub fn select_all_scans(conn: &Connection) -> Result<Vec<Scan>> {
     let mut stmt = conn.prepare(
-        "SELECT id, project_id, status, files_scanned, total_files, violations_found, started_at, completed_at, scan_mode
+        "SELECT id, project_id, status, files_scanned, total_files, violations_found, started_at, completed_at, scan_mode, partial_reason, baseline_scan_id, new_violation_count, resolved_violation_count, enriched_count, context_extraction_failed, processing_timeouts, commit_sha
          FROM scans
+         WHERE project_id IN (SELECT id FROM projects WHERE namespace = ?)
          ORDER BY started_at DESC"
     ).context("Failed to prepare select all scans statement")?;
 
-    let scans = stmt.query_map([], |row| {
+    let namespace = current_namespace(conn)?;
+    let scans = stmt.query_map(params![namespace], |row| {
         Ok(Scan {
             id: row.get(0)?,
             project_id: row.get(1)?,
@@ -564,10 +1290,19 @@ pub fn select_all_scans(conn: &Connection) -> Result<Vec<Scan>> {
             started_at: row.get(6)?,
             completed_at: row.get(7)?,
             scan_mode: row.get(8)?,
+            partial_reason: row.get(9)?,
+            baseline_scan_id: row.get(10)?,
+            new_violation_count: row.get(11)?,
+            resolved_violation_count: row.get(12)?,
+            enriched_count: row.get(13)?,
+            context_extraction_failed: row.get(14)?,
+            processing_timeouts: row.get(15)?,
+            commit_sha: row.get(16)?,
             critical_count: 0,
             high_count: 0,
             medium_count: 0,
             low_count: 0,
+            detail_stats: None,
         })
     })
     .context("Failed to query all scans")?
@@ -579,12 +1314,14 @@ pub fn select_all_scans(conn: &Connection) -> Result<Vec<Scan>> {
 
 pub fn select_all_violations(conn: &Connection) -> Result<Vec<Violation>> {
     let mut stmt = conn.prepare(
-        "SELECT id, scan_id, control_id, severity, description, file_path, line_number, code_snippet, status, detected_at, detection_method, confidence_score, llm_reasoning, regex_reasoning, function_name, class_name
+        "SELECT id, scan_id, control_id, severity, description, file_path, line_number, code_snippet, status, detected_at, detection_method, confidence_score, llm_reasoning, regex_reasoning, function_name, class_name, decorator_name, is_new, last_status_change_reason, package_name, fingerprint, lifecycle, jira_issue_key
          FROM violations
+         WHERE scan_id IN (SELECT s.id FROM scans s JOIN projects p ON p.id = s.project_id WHERE p.namespace = ?)
          ORDER BY detected_at DESC"
     ).context("Failed to prepare select all violations statement")?;
 
-    let violations = stmt.query_map([], |row| {
+    let namespace = current_namespace(conn)?;
+    let violations = stmt.query_map(params![namespace], |row| {
         Ok(Violation {
             id: row.get(0)?,
             scan_id: row.get(1)?,
@@ -602,6 +1339,15 @@ pub fn select_all_violations(conn: &Connection) -> Result<Vec<Violation>> {
             regex_reasoning: row.get(13)?,
             function_name: row.get(14)?,
             class_name: row.get(15)?,
+            decorator_name: row.get(16)?,
+            is_new: row.get(17)?,
+            last_status_change_reason: row.get(18)?,
+            package_name: row.get(19)?,
+            fingerprint: row.get(20)?,
+            lifecycle: row.get(21)?,
+            jira_issue_key: row.get(22)?,
+            first_seen_scan_id: None,
+            last_seen_scan_id: None,
         })
     })
     .context("Failed to query all violations")?
@@ -613,12 +1359,14 @@ pub fn select_all_violations(conn: &Connection) -> Result<Vec<Violation>> {
 
 pub fn select_all_fixes(conn: &Connection) -> Result<Vec<Fix>> {
     let mut stmt = conn.prepare(
-        "SELECT id, violation_id, original_code, fixed_code, explanation, trust_level, applied_at, applied_by, git_commit_sha, backup_path
+        "SELECT id, violation_id, original_code, fixed_code, explanation, trust_level, applied_at, applied_by, git_commit_sha, backup_path, verification_status, verified_at, fix_attempt_number, created_at, git_branch, changes_summary, test_recommendation
          FROM fixes
+         WHERE violation_id IN (SELECT v.id FROM violations v JOIN scans s ON s.id = v.scan_id JOIN projects p ON p.id = s.project_id WHERE p.namespace = ?)
          ORDER BY id DESC"
     ).context("Failed to prepare select all fixes statement")?;
 
-    let fixes = stmt.query_map([], |row| {
+    let namespace = current_namespace(conn)?;
+    let fixes = stmt.query_map(params![namespace], |row| {
         Ok(Fix {
             id: row.get(0)?,
             violation_id: row.get(1)?,
@@ -630,6 +1378,13 @@ pub fn select_all_fixes(conn: &Connection) -> Result<Vec<Fix>> {
             applied_by: row.get(7)?,
             git_commit_sha: row.get(8)?,
             backup_path: row.get(9)?,
+            verification_status: row.get(10)?,
+            verified_at: row.get(11)?,
+            fix_attempt_number: row.get(12)?,
+            created_at: row.get(13)?,
+            git_branch: row.get(14)?,
+            changes_summary: row.get(15)?,
+            test_recommendation: row.get(16)?,
         })
     })
     .context("Failed to query all fixes")?
@@ -643,10 +1398,12 @@ pub fn select_all_audit_events(conn: &Connection) -> Result<Vec<AuditEvent>> {
     let mut stmt = conn.prepare(
         "SELECT id, event_type, project_id, violation_id, fix_id, description, metadata, created_at
          FROM audit_events
+         WHERE (project_id IS NULL OR project_id IN (SELECT id FROM projects WHERE namespace = ?))
          ORDER BY created_at DESC"
     ).context("Failed to prepare select all audit events statement")?;
 
-    let events = stmt.query_map([], |row| {
+    let namespace = current_namespace(conn)?;
+    let events = stmt.query_map(params![namespace], |row| {
         Ok(AuditEvent {
             id: row.get(0)?,
             event_type: row.get(1)?,
@@ -696,14 +1453,163 @@ pub fn get_severity_counts(conn: &Connection, scan_id: i64) -> Result<(i32, i32,
     Ok((critical, high, medium, low))
 }
 
-// ===== SCAN COSTS CRUD =====
+/// Get violation counts by control and severity for a scan
+///
+/// Returns map of control_id to (critical, high, medium, low) counts; controls
+/// without violations are absent
+pub fn get_severity_counts_by_control(conn: &Connection, scan_id: i64) -> Result<HashMap<String, (i64, i64, i64, i64)>> {
+    let mut stmt = conn.prepare(
+        "SELECT control_id, severity, COUNT(*) FROM violations WHERE scan_id = ? GROUP BY control_id, severity"
+    ).context("Failed to prepare control severity counts query")?;
 
-pub fn insert_scan_cost(conn: &Connection, scan_cost: &ScanCost) -> Result<i64> {
-    conn.execute(
-        "INSERT INTO scan_costs (scan_id, files_analyzed_with_llm, input_tokens, output_tokens, cache_read_tokens, cache_write_tokens, total_cost_usd, created_at)
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
-        params![
-            scan_cost.scan_id,
+    let rows = stmt.query_map([scan_id], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, i64>(2)?))
+    }).context("Failed to query control severity counts")?;
+
+    let mut counts: HashMap<String, (i64, i64, i64, i64)> = HashMap::new();
+    for result in rows {
+        let (control_id, severity, count) = result.context("Failed to process control severity row")?;
+        let entry = counts.entry(control_id).or_default();
+        match severity.as_str() {
+            "critical" => entry.0 = count,
+            "high" => entry.1 = count,
+            "medium" => entry.2 = count,
+            "low" => entry.3 = count,
+            _ => {}
+        }
+    }
+
+    Ok(counts)
+}
+
+/// Get total and open violation counts by control category for a scan
+///
+/// Returns map of category to (total, open) counts; categories without
+/// violations are absent
+pub fn get_violation_counts_by_category(conn: &Connection, scan_id: i64) -> Result<HashMap<String, (i64, i64)>> {
+    let mut stmt = conn.prepare(
+        "SELECT c.category, COUNT(*), SUM(CASE WHEN v.status = 'open' THEN 1 ELSE 0 END)
+         FROM violations v
+         JOIN controls c ON c.id = v.control_id
+         WHERE v.scan_id = ?
+         GROUP BY c.category"
+    ).context("Failed to prepare category counts query")?;
+
+    let rows = stmt.query_map([scan_id], |row| {
+        Ok((row.get::<_, String>(0)?, (row.get::<_, i64>(1)?, row.get::<_, i64>(2)?)))
+    }).context("Failed to query category counts")?;
+
+    rows.collect::<std::result::Result<HashMap<_, _>, _>>()
+        .context("Failed to collect category counts")
+}
+
+/// Get a control's violation count in each of a project's last `num_scans` completed scans
+///
+/// Returns (completed_at, violation_count) pairs, oldest scan first
+pub fn get_control_violation_trend(
+    conn: &Connection,
+    project_id: i64,
+    control_id: &str,
+    num_scans: usize,
+) -> Result<Vec<(String, i64)>> {
+    let mut stmt = conn.prepare(
+        "SELECT s.completed_at, (SELECT COUNT(*) FROM violations v WHERE v.scan_id = s.id AND v.control_id = ?)
+         FROM scans s
+         WHERE s.project_id = ? AND s.completed_at IS NOT NULL
+           AND s.project_id IN (SELECT id FROM projects WHERE namespace = ?)
+         ORDER BY s.completed_at DESC, s.id DESC
+         LIMIT ?"
+    ).context("Failed to prepare control trend query")?;
+
+    let namespace = current_namespace(conn)?;
+    let mut trend = stmt
+        .query_map(params![control_id, project_id, namespace, num_scans as i64], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+        })
+        .context("Failed to query control trend")?
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .context("Failed to collect control trend")?;
+
+    trend.reverse();
+    Ok(trend)
+}
+
+// ===== COMPLIANCE SCORES =====
+
+/// Score a completed scan's controls and store the result
+///
+/// Re-recording a scan replaces its earlier score.
+pub fn record_compliance_score(conn: &Connection, scan_id: i64) -> Result<ComplianceDataPoint> {
+    let (project_id, completed_at): (i64, Option<String>) = conn
+        .query_row(
+            "SELECT project_id, completed_at FROM scans WHERE id = ?",
+            [scan_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .context("Failed to fetch scan for compliance score")?;
+    let completed_at = completed_at.context("Scan has not completed")?;
+
+    let counts = get_severity_counts_by_control(conn, scan_id)?;
+    let point = ComplianceDataPoint::from_counts(scan_id, completed_at, &counts);
+
+    conn.execute(
+        "INSERT OR REPLACE INTO compliance_scores (scan_id, project_id, cc61_score, cc67_score, cc72_score, a12_score, overall_score)
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+        params![
+            point.scan_id,
+            project_id,
+            point.cc61_score,
+            point.cc67_score,
+            point.cc72_score,
+            point.a12_score,
+            point.overall_score
+        ],
+    ).context("Failed to insert compliance score")?;
+
+    Ok(point)
+}
+
+/// Get the compliance scores of a project's last `num_scans` scored scans, oldest first
+pub fn get_compliance_trend(conn: &Connection, project_id: i64, num_scans: usize) -> Result<Vec<ComplianceDataPoint>> {
+    let mut stmt = conn.prepare(
+        "SELECT c.scan_id, s.completed_at, c.cc61_score, c.cc67_score, c.cc72_score, c.a12_score, c.overall_score
+         FROM compliance_scores c
+         JOIN scans s ON s.id = c.scan_id
+         WHERE c.project_id = ? AND s.completed_at IS NOT NULL
+           AND c.project_id IN (SELECT id FROM projects WHERE namespace = ?)
+         ORDER BY s.completed_at DESC, s.id DESC
+         LIMIT ?"
+    ).context("Failed to prepare compliance trend query")?;
+
+    let namespace = current_namespace(conn)?;
+    let mut trend = stmt
+        .query_map(params![project_id, namespace, num_scans as i64], |row| {
+            Ok(ComplianceDataPoint {
+                scan_id: row.get(0)?,
+                completed_at: row.get(1)?,
+                cc61_score: row.get(2)?,
+                cc67_score: row.get(3)?,
+                cc72_score: row.get(4)?,
+                a12_score: row.get(5)?,
+                overall_score: row.get(6)?,
+            })
+        })
+        .context("Failed to query compliance trend")?
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .context("Failed to collect compliance trend")?;
+
+    trend.reverse();
+    Ok(trend)
+}
+
+// ===== SCAN COSTS CRUD =====
+
+pub fn insert_scan_cost(conn: &Connection, scan_cost: &ScanCost) -> Result<i64> {
+    conn.execute(
+        "INSERT INTO scan_costs (scan_id, files_analyzed_with_llm, input_tokens, output_tokens, cache_read_tokens, cache_write_tokens, total_cost_usd, created_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        params![
+            scan_cost.scan_id,
             scan_cost.files_analyzed_with_llm,
             scan_cost.input_tokens,
             scan_cost.output_tokens,
@@ -771,11 +1677,12 @@ pub fn select_scan_cost_by_scan_id(conn: &Connection, scan_id: i64) -> Result<Op
 /// Used for analytics dashboard to show costs over time
 pub fn select_scan_costs_since(conn: &Connection, since: &str) -> Result<Vec<ScanCost>> {
     let mut stmt = conn
-        .prepare("SELECT id, scan_id, files_analyzed_with_llm, input_tokens, output_tokens, cache_read_tokens, cache_write_tokens, total_cost_usd, created_at FROM scan_costs WHERE created_at >= ? ORDER BY created_at DESC")
+        .prepare("SELECT id, scan_id, files_analyzed_with_llm, input_tokens, output_tokens, cache_read_tokens, cache_write_tokens, total_cost_usd, created_at FROM scan_costs WHERE created_at >= ? AND scan_id IN (SELECT s.id FROM scans s JOIN projects p ON p.id = s.project_id WHERE p.namespace = ?) ORDER BY created_at DESC")
         .context("Failed to prepare select scan costs since query")?;
 
+    let namespace = current_namespace(conn)?;
     let scan_costs = stmt
-        .query_map(params![since], |row| {
+        .query_map(params![since, namespace], |row| {
             Ok(ScanCost {
                 id: row.get(0)?,
                 scan_id: row.get(1)?,
@@ -797,11 +1704,12 @@ pub fn select_scan_costs_since(conn: &Connection, since: &str) -> Result<Vec<Sca
 
 pub fn select_all_scan_costs(conn: &Connection) -> Result<Vec<ScanCost>> {
     let mut stmt = conn
-        .prepare("SELECT id, scan_id, files_analyzed_with_llm, input_tokens, output_tokens, cache_read_tokens, cache_write_tokens, total_cost_usd, created_at FROM scan_costs ORDER BY created_at DESC")
+        .prepare("SELECT id, scan_id, files_analyzed_with_llm, input_tokens, output_tokens, cache_read_tokens, cache_write_tokens, total_cost_usd, created_at FROM scan_costs WHERE scan_id IN (SELECT s.id FROM scans s JOIN projects p ON p.id = s.project_id WHERE p.namespace = ?) ORDER BY created_at DESC")
         .context("Failed to prepare select all scan costs query")?;
 
+    let namespace = current_namespace(conn)?;
     let scan_costs = stmt
-        .query_map([], |row| {
+        .query_map(params![namespace], |row| {
             Ok(ScanCost {
                 id: row.get(0)?,
                 scan_id: row.get(1)?,
@@ -821,6 +1729,804 @@ pub fn select_all_scan_costs(conn: &Connection) -> Result<Vec<ScanCost>> {
     Ok(scan_costs)
 }
 
+/// Sum scan costs in the current namespace, optionally for one project and/or
+/// since a timestamp (RFC3339)
+pub fn get_total_spend(conn: &Connection, project_id: Option<i64>, since: Option<String>) -> Result<SpendSummary> {
+    let filter = "FROM scan_costs c
+         JOIN scans s ON s.id = c.scan_id
+         JOIN projects p ON p.id = s.project_id
+         WHERE p.namespace = ?3 AND (?1 IS NULL OR s.project_id = ?1) AND (?2 IS NULL OR c.created_at >= ?2)";
+    let namespace = current_namespace(conn)?;
+
+    let (total_cost_usd, total_input_tokens, total_output_tokens, total_cache_read_tokens, scan_count) = conn
+        .query_row(
+            &format!(
+                "SELECT COALESCE(SUM(c.total_cost_usd), 0.0), COALESCE(SUM(c.input_tokens), 0),
+                        COALESCE(SUM(c.output_tokens), 0), COALESCE(SUM(c.cache_read_tokens), 0),
+                        COUNT(DISTINCT c.scan_id)
+                 {}",
+                filter
+            ),
+            params![project_id, since, namespace],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)),
+        )
+        .context("Failed to sum scan costs")?;
+
+    let most_expensive_scan_id = conn
+        .query_row(
+            &format!("SELECT c.scan_id {} ORDER BY c.total_cost_usd DESC, c.scan_id DESC LIMIT 1", filter),
+            params![project_id, since, namespace],
+            |row| row.get(0),
+        )
+        .optional()
+        .context("Failed to fetch most expensive scan")?;
+
+    Ok(SpendSummary {
+        total_cost_usd,
+        total_input_tokens,
+        total_output_tokens,
+        total_cache_read_tokens,
+        scan_count,
+        avg_cost_per_scan: if scan_count > 0 { total_cost_usd / scan_count as f64 } else { 0.0 },
+        most_expensive_scan_id,
+    })
+}
+
+/// Spend per UTC day in the current namespace for the last `days` days
+/// (including today), oldest first
+///
+/// Days without scan costs are included with zero spend.
+pub fn get_spend_by_day(conn: &Connection, project_id: Option<i64>, days: u32) -> Result<Vec<DailySpend>> {
+    let today = chrono::Utc::now().date_naive();
+    let first_day = today - chrono::Duration::days(days.saturating_sub(1) as i64);
+
+    let mut stmt = conn.prepare(
+        "SELECT substr(c.created_at, 1, 10) AS day, SUM(c.total_cost_usd), COUNT(DISTINCT c.scan_id)
+         FROM scan_costs c
+         JOIN scans s ON s.id = c.scan_id
+         JOIN projects p ON p.id = s.project_id
+         WHERE p.namespace = ?3 AND (?1 IS NULL OR s.project_id = ?1) AND c.created_at >= ?2
+         GROUP BY day"
+    ).context("Failed to prepare spend by day query")?;
+
+    let namespace = current_namespace(conn)?;
+    let by_day: HashMap<String, (f64, i64)> = stmt
+        .query_map(params![project_id, first_day.to_string(), namespace], |row| {
+            Ok((row.get::<_, String>(0)?, (row.get(1)?, row.get(2)?)))
+        })
+        .context("Failed to query spend by day")?
+        .collect::<rusqlite::Result<_>>()
+        .context("Failed to collect spend by day")?;
+
+    Ok((0..days)
+        .map(|offset| {
+            let date = (first_day + chrono::Duration::days(offset as i64)).to_string();
+            let (total_cost_usd, scan_count) = by_day.get(&date).copied().unwrap_or((0.0, 0));
+            DailySpend { date, total_cost_usd, scan_count }
+        })
+        .collect())
+}
+
+// ===== FALSE POSITIVE REPORTS =====
+
+pub fn insert_false_positive(conn: &Connection, report: &FalsePositive) -> Result<i64> {
+    conn.execute(
+        "INSERT INTO false_positive_reports (project_id, violation_id, control_id, file_path, code_snippet, detection_method, reason, reported_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        params![
+            report.project_id,
+            report.violation_id,
+            report.control_id,
+            report.file_path,
+            report.code_snippet,
+            report.detection_method,
+            report.reason,
+            report.reported_at,
+        ],
+    ).context("Failed to insert false positive report")?;
+
+    Ok(conn.last_insert_rowid())
+}
+
+pub fn select_false_positives_by_control(conn: &Connection, control_id: &str) -> Result<Vec<FalsePositive>> {
+    let mut stmt = conn
+        .prepare("SELECT id, project_id, violation_id, control_id, file_path, code_snippet, detection_method, reason, reported_at FROM false_positive_reports WHERE control_id = ? AND project_id IN (SELECT id FROM projects WHERE namespace = ?) ORDER BY reported_at DESC")
+        .context("Failed to prepare select false positives by control query")?;
+
+    let namespace = current_namespace(conn)?;
+    let reports = stmt
+        .query_map(params![control_id, namespace], |row| {
+            Ok(FalsePositive {
+                id: row.get(0)?,
+                project_id: row.get(1)?,
+                violation_id: row.get(2)?,
+                control_id: row.get(3)?,
+                file_path: row.get(4)?,
+                code_snippet: row.get(5)?,
+                detection_method: row.get(6)?,
+                reason: row.get(7)?,
+                reported_at: row.get(8)?,
+            })
+        })
+        .context("Failed to map false positives from query")?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .context("Failed to collect false positives")?;
+
+    Ok(reports)
+}
+
+pub fn select_false_positives_by_project(conn: &Connection, project_id: i64) -> Result<Vec<FalsePositive>> {
+    let mut stmt = conn
+        .prepare("SELECT id, project_id, violation_id, control_id, file_path, code_snippet, detection_method, reason, reported_at FROM false_positive_reports WHERE project_id = ? AND project_id IN (SELECT id FROM projects WHERE namespace = ?) ORDER BY reported_at DESC")
+        .context("Failed to prepare select false positives by project query")?;
+
+    let namespace = current_namespace(conn)?;
+    let reports = stmt
+        .query_map(params![project_id, namespace], |row| {
+            Ok(FalsePositive {
+                id: row.get(0)?,
+                project_id: row.get(1)?,
+                violation_id: row.get(2)?,
+                control_id: row.get(3)?,
+                file_path: row.get(4)?,
+                code_snippet: row.get(5)?,
+                detection_method: row.get(6)?,
+                reason: row.get(7)?,
+                reported_at: row.get(8)?,
+            })
+        })
+        .context("Failed to map false positives from query")?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .context("Failed to collect false positives")?;
+
+    Ok(reports)
+}
+
+// ===== SLACK CONFIG CRUD =====
+
+pub fn insert_slack_config(conn: &Connection, config: &SlackConfig) -> Result<i64> {
+    let severities = serde_json::to_string(&config.notify_on_severities)
+        .context("Failed to serialize notify_on_severities")?;
+
+    conn.execute(
+        "INSERT INTO slack_configs (webhook_url, channel, notify_on_severities, project_id, enabled, signing_secret) VALUES (?, ?, ?, ?, ?, ?)",
+        params![
+            config.webhook_url,
+            config.channel,
+            severities,
+            config.project_id,
+            config.enabled,
+            config.signing_secret,
+        ],
+    ).context("Failed to insert slack config")?;
+
+    Ok(conn.last_insert_rowid())
+}
+
+fn map_slack_config_row(row: &rusqlite::Row) -> rusqlite::Result<SlackConfig> {
+    let severities: String = row.get(3)?;
+    Ok(SlackConfig {
+        id: row.get(0)?,
+        webhook_url: row.get(1)?,
+        channel: row.get(2)?,
+        notify_on_severities: serde_json::from_str(&severities).unwrap_or_default(),
+        project_id: row.get(4)?,
+        enabled: row.get(5)?,
+        signing_secret: row.get(6)?,
+        created_at: row.get(7)?,
+    })
+}
+
+pub fn select_slack_configs(conn: &Connection) -> Result<Vec<SlackConfig>> {
+    let mut stmt = conn
+        .prepare("SELECT id, webhook_url, channel, notify_on_severities, project_id, enabled, signing_secret, created_at FROM slack_configs ORDER BY id ASC")
+        .context("Failed to prepare select slack configs query")?;
+
+    let configs = stmt
+        .query_map([], map_slack_config_row)
+        .context("Failed to map slack configs from query")?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .context("Failed to collect slack configs")?;
+
+    Ok(configs)
+}
+
+pub fn select_slack_config(conn: &Connection, id: i64) -> Result<Option<SlackConfig>> {
+    let mut stmt = conn
+        .prepare("SELECT id, webhook_url, channel, notify_on_severities, project_id, enabled, signing_secret, created_at FROM slack_configs WHERE id = ?")
+        .context("Failed to prepare select slack config query")?;
+
+    let config = stmt
+        .query_row(params![id], map_slack_config_row)
+        .optional()
+        .context("Failed to query slack config")?;
+
+    Ok(config)
+}
+
+pub fn delete_slack_config(conn: &Connection, id: i64) -> Result<()> {
+    conn.execute(
+        "DELETE FROM slack_configs WHERE id = ?",
+        params![id],
+    ).context("Failed to delete slack config")?;
+
+    Ok(())
+}
+
+// ===== SCAN SCHEDULE CRUD =====
+
+const SCAN_SCHEDULE_COLUMNS: &str =
+    "s.id, s.project_id, s.cron_expr, s.enabled, s.last_run_at, s.last_scan_id, s.next_run_at, s.created_at";
+
+pub fn insert_scan_schedule(conn: &Connection, schedule: &ScanSchedule) -> Result<i64> {
+    conn.execute(
+        "INSERT INTO scan_schedules (project_id, cron_expr, enabled, next_run_at) VALUES (?, ?, ?, ?)",
+        params![schedule.project_id, schedule.cron_expr, schedule.enabled, schedule.next_run_at],
+    ).context("Failed to insert scan schedule")?;
+
+    Ok(conn.last_insert_rowid())
+}
+
+fn map_scan_schedule_row(row: &rusqlite::Row) -> rusqlite::Result<ScanSchedule> {
+    Ok(ScanSchedule {
+        id: row.get(0)?,
+        project_id: row.get(1)?,
+        cron_expr: row.get(2)?,
+        enabled: row.get(3)?,
+        last_run_at: row.get(4)?,
+        last_scan_id: row.get(5)?,
+        next_run_at: row.get(6)?,
+        created_at: row.get(7)?,
+    })
+}
+
+/// Schedules of projects in the current namespace
+pub fn select_scan_schedules(conn: &Connection) -> Result<Vec<ScanSchedule>> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM scan_schedules s JOIN projects p ON p.id = s.project_id WHERE p.namespace = ? ORDER BY s.id ASC",
+            SCAN_SCHEDULE_COLUMNS
+        ))
+        .context("Failed to prepare select scan schedules query")?;
+
+    let namespace = current_namespace(conn)?;
+    let schedules = stmt
+        .query_map(params![namespace], map_scan_schedule_row)
+        .context("Failed to map scan schedules from query")?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .context("Failed to collect scan schedules")?;
+
+    Ok(schedules)
+}
+
+/// Enabled schedules in every namespace whose next run is at or before `now`,
+/// each with its project's namespace
+///
+/// Unscoped so the background scheduler runs schedules whichever namespace
+/// the app has selected. `now` must use the same format as `next_run_at`
+/// (UTC RFC 3339 to the second).
+pub fn select_due_scan_schedules(conn: &Connection, now: &str) -> Result<Vec<(ScanSchedule, String)>> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {}, p.namespace FROM scan_schedules s JOIN projects p ON p.id = s.project_id
+             WHERE s.enabled = 1 AND s.next_run_at <= ? ORDER BY s.next_run_at ASC, s.id ASC",
+            SCAN_SCHEDULE_COLUMNS
+        ))
+        .context("Failed to prepare select due scan schedules query")?;
+
+    let column_count = stmt.column_count();
+    let schedules = stmt
+        .query_map(params![now], |row| Ok((map_scan_schedule_row(row)?, row.get(column_count - 1)?)))
+        .context("Failed to map due scan schedules from query")?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .context("Failed to collect due scan schedules")?;
+
+    Ok(schedules)
+}
+
+/// Record that a schedule ran at `last_run_at` and when it runs next
+pub fn update_scan_schedule_run(
+    conn: &Connection,
+    id: i64,
+    last_run_at: &str,
+    next_run_at: &str,
+) -> Result<()> {
+    conn.execute(
+        "UPDATE scan_schedules SET last_run_at = ?, next_run_at = ? WHERE id = ?",
+        params![last_run_at, next_run_at, id],
+    ).context("Failed to update scan schedule run")?;
+
+    Ok(())
+}
+
+pub fn set_scan_schedule_enabled(conn: &Connection, id: i64, enabled: bool) -> Result<()> {
+    conn.execute(
+        "UPDATE scan_schedules SET enabled = ? WHERE id = ?",
+        params![enabled, id],
+    ).context("Failed to update scan schedule enabled")?;
+
+    Ok(())
+}
+
+/// Record the scan a schedule's last run started
+pub fn update_scan_schedule_last_scan(conn: &Connection, id: i64, scan_id: i64) -> Result<()> {
+    conn.execute(
+        "UPDATE scan_schedules SET last_scan_id = ? WHERE id = ?",
+        params![scan_id, id],
+    ).context("Failed to update scan schedule last scan")?;
+
+    Ok(())
+}
+
+// ===== PROJECT SETTINGS CRUD =====
+
+/// A project's reporting thresholds, or None if they've never been set
+pub fn select_project_settings(conn: &Connection, project_id: i64) -> Result<Option<ProjectSettings>> {
+    let row = conn
+        .query_row(
+            "SELECT project_id, min_severity, min_llm_confidence, disabled_controls, updated_at FROM project_settings WHERE project_id = ?",
+            params![project_id],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    row.get::<_, Option<i64>>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                ))
+            },
+        )
+        .optional()
+        .context("Failed to query project settings")?;
+
+    row.map(|(project_id, min_severity, min_llm_confidence, disabled_controls, updated_at)| {
+        let disabled_controls = serde_json::from_str(&disabled_controls)
+            .context("Failed to parse project_settings.disabled_controls")?;
+        Ok(ProjectSettings {
+            project_id,
+            min_severity,
+            min_llm_confidence,
+            disabled_controls,
+            updated_at: Some(updated_at),
+        })
+    })
+    .transpose()
+}
+
+/// Create or replace a project's reporting thresholds
+pub fn upsert_project_settings(conn: &Connection, settings: &ProjectSettings) -> Result<()> {
+    let disabled_controls = serde_json::to_string(&settings.disabled_controls)
+        .context("Failed to serialize disabled controls")?;
+    conn.execute(
+        "INSERT INTO project_settings (project_id, min_severity, min_llm_confidence, disabled_controls, updated_at)
+         VALUES (?, ?, ?, ?, datetime('now'))
+         ON CONFLICT(project_id) DO UPDATE SET
+            min_severity = excluded.min_severity,
+            min_llm_confidence = excluded.min_llm_confidence,
+            disabled_controls = excluded.disabled_controls,
+            updated_at = excluded.updated_at",
+        params![settings.project_id, settings.min_severity, settings.min_llm_confidence, disabled_controls],
+    ).context("Failed to save project settings")?;
+
+    Ok(())
+}
+
+// ===== PROJECT ROOT CRUD =====
+
+pub fn insert_project_root(
+    conn: &Connection,
+    project_id: i64,
+    path: &str,
+    label: &str,
+    framework: Option<&str>,
+) -> Result<i64> {
+    conn.execute(
+        "INSERT INTO project_roots (project_id, path, label, framework) VALUES (?, ?, ?, ?)",
+        params![project_id, path, label, framework],
+    ).context("Failed to insert project root")?;
+
+    Ok(conn.last_insert_rowid())
+}
+
+/// A project's extra roots, oldest first (the primary root is the project's path)
+pub fn select_project_roots(conn: &Connection, project_id: i64) -> Result<Vec<ProjectRoot>> {
+    let mut stmt = conn
+        .prepare("SELECT id, project_id, path, label, framework, created_at FROM project_roots WHERE project_id = ? ORDER BY id")
+        .context("Failed to prepare select project roots query")?;
+
+    let roots = stmt
+        .query_map(params![project_id], |row| {
+            Ok(ProjectRoot {
+                id: row.get(0)?,
+                project_id: row.get(1)?,
+                path: row.get(2)?,
+                label: row.get(3)?,
+                framework: row.get(4)?,
+                created_at: row.get(5)?,
+            })
+        })
+        .context("Failed to map project roots from query")?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .context("Failed to collect project roots")?;
+
+    Ok(roots)
+}
+
+pub fn update_project_root_framework(conn: &Connection, id: i64, framework: Option<&str>) -> Result<()> {
+    conn.execute(
+        "UPDATE project_roots SET framework = ? WHERE id = ?",
+        params![framework, id],
+    ).context("Failed to update project root framework")?;
+
+    Ok(())
+}
+
+/// Remove one of a project's roots
+///
+/// Returns: Whether the root existed
+pub fn delete_project_root(conn: &Connection, project_id: i64, id: i64) -> Result<bool> {
+    let deleted = conn.execute(
+        "DELETE FROM project_roots WHERE id = ? AND project_id = ?",
+        params![id, project_id],
+    ).context("Failed to delete project root")?;
+
+    Ok(deleted > 0)
+}
+
+// ===== VIOLATION HISTORY =====
+
+/// Record a scan's findings in its project's violation history
+///
+/// Findings are matched by fingerprint. One the project has never reported
+/// gets a "new" history row; one reported before becomes "recurring", even if
+/// it had been resolved. The scan's violations get the same lifecycle. With
+/// `resolve_missing`, findings in the history that the scan didn't report are
+/// marked "resolved" by it; pass false for scans that didn't look at every
+/// file (partial scans, single-file rescans).
+///
+/// Recording a scan again only counts findings added to it since.
+///
+/// Returns: How many findings were new, recurring and resolved
+pub fn update_violation_history(
+    conn: &Connection,
+    project_id: i64,
+    scan_id: i64,
+    resolve_missing: bool,
+) -> Result<LifecycleCounts> {
+    let tx = begin_write(conn).context("Failed to begin violation history transaction")?;
+    let mut counts = LifecycleCounts::default();
+    {
+        // Violations sharing a fingerprint within a scan are one finding
+        let mut stmt = tx
+            .prepare("SELECT fingerprint, control_id, file_path, severity FROM violations WHERE scan_id = ? ORDER BY id")
+            .context("Failed to prepare scan fingerprints query")?;
+        let found: BTreeMap<String, (String, String, String)> = stmt
+            .query_map(params![scan_id], |row| Ok((row.get(0)?, (row.get(1)?, row.get(2)?, row.get(3)?))))
+            .context("Failed to query scan fingerprints")?
+            .collect::<std::result::Result<_, _>>()
+            .context("Failed to collect scan fingerprints")?;
+
+        let mut stmt = tx
+            .prepare("SELECT fingerprint, id, lifecycle, last_seen_scan_id FROM violation_history WHERE project_id = ?")
+            .context("Failed to prepare violation history query")?;
+        let history: HashMap<String, (i64, String, i64)> = stmt
+            .query_map(params![project_id], |row| Ok((row.get(0)?, (row.get(1)?, row.get(2)?, row.get(3)?))))
+            .context("Failed to query violation history")?
+            .collect::<std::result::Result<_, _>>()
+            .context("Failed to collect violation history")?;
+
+        for (fingerprint, (control_id, file_path, severity)) in &found {
+            let lifecycle = match history.get(fingerprint) {
+                None => {
+                    tx.execute(
+                        "INSERT INTO violation_history (project_id, fingerprint, control_id, file_path, severity, lifecycle, first_seen_scan_id, last_seen_scan_id)
+                         VALUES (?, ?, ?, ?, ?, 'new', ?, ?)",
+                        params![project_id, fingerprint, control_id, file_path, severity, scan_id, scan_id],
+                    ).context("Failed to insert violation history")?;
+                    counts.new += 1;
+                    ViolationLifecycle::New
+                }
+                // Already recorded for this scan
+                Some((_, lifecycle, last_seen)) if *last_seen == scan_id => {
+                    if lifecycle == ViolationLifecycle::New.as_str() {
+                        ViolationLifecycle::New
+                    } else {
+                        ViolationLifecycle::Recurring
+                    }
+                }
+                Some((id, _, _)) => {
+                    tx.execute(
+                        "UPDATE violation_history
+                         SET lifecycle = 'recurring', severity = ?, last_seen_scan_id = ?, times_seen = times_seen + 1,
+                             last_seen_at = datetime('now'), resolved_scan_id = NULL, resolved_at = NULL
+                         WHERE id = ?",
+                        params![severity, scan_id, id],
+                    ).context("Failed to update violation history")?;
+                    counts.recurring += 1;
+                    ViolationLifecycle::Recurring
+                }
+            };
+            tx.execute(
+                "UPDATE violations SET lifecycle = ? WHERE scan_id = ? AND fingerprint = ?",
+                params![lifecycle.as_str(), scan_id, fingerprint],
+            ).context("Failed to update violation lifecycle")?;
+        }
+
+        if resolve_missing {
+            for (fingerprint, (id, lifecycle, _)) in &history {
+                if lifecycle == ViolationLifecycle::Resolved.as_str() || found.contains_key(fingerprint) {
+                    continue;
+                }
+                tx.execute(
+                    "UPDATE violation_history SET lifecycle = 'resolved', resolved_scan_id = ?, resolved_at = datetime('now') WHERE id = ?",
+                    params![scan_id, id],
+                ).context("Failed to resolve violation history")?;
+                counts.resolved += 1;
+            }
+        }
+    }
+    tx.commit().context("Failed to commit violation history")?;
+
+    Ok(counts)
+}
+
+/// A project's findings across scans, most recently seen first
+///
+/// `lifecycle` limits the result to "new", "recurring" or "resolved" findings.
+pub fn select_violation_history(
+    conn: &Connection,
+    project_id: i64,
+    lifecycle: Option<&str>,
+) -> Result<Vec<ViolationHistoryEntry>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, project_id, fingerprint, control_id, file_path, severity, lifecycle, first_seen_scan_id, last_seen_scan_id, resolved_scan_id, times_seen, first_seen_at, last_seen_at, resolved_at
+             FROM violation_history
+             WHERE project_id = ?1 AND (?2 IS NULL OR lifecycle = ?2)
+             ORDER BY last_seen_scan_id DESC, id ASC",
+        )
+        .context("Failed to prepare select violation history query")?;
+
+    let entries = stmt
+        .query_map(params![project_id, lifecycle], |row| {
+            Ok(ViolationHistoryEntry {
+                id: row.get(0)?,
+                project_id: row.get(1)?,
+                fingerprint: row.get(2)?,
+                control_id: row.get(3)?,
+                file_path: row.get(4)?,
+                severity: row.get(5)?,
+                lifecycle: row.get(6)?,
+                first_seen_scan_id: row.get(7)?,
+                last_seen_scan_id: row.get(8)?,
+                resolved_scan_id: row.get(9)?,
+                times_seen: row.get(10)?,
+                first_seen_at: row.get(11)?,
+                last_seen_at: row.get(12)?,
+                resolved_at: row.get(13)?,
+            })
+        })
+        .context("Failed to map violation history from query")?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .context("Failed to collect violation history")?;
+
+    Ok(entries)
+}
+
+// ===== JIRA CONFIG CRUD =====
+
+/// Jira config of a project, or None if it isn't linked (`api_token` is always None)
+pub fn select_jira_config(conn: &Connection, project_id: i64) -> Result<Option<JiraConfig>> {
+    let row = conn
+        .query_row(
+            "SELECT project_id, base_url, email, project_key, issue_type, labels, updated_at FROM jira_configs WHERE project_id = ?",
+            params![project_id],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                    row.get::<_, String>(5)?,
+                    row.get::<_, String>(6)?,
+                ))
+            },
+        )
+        .optional()
+        .context("Failed to query Jira config")?;
+
+    row.map(|(project_id, base_url, email, project_key, issue_type, labels, updated_at)| {
+        let labels = serde_json::from_str(&labels).context("Failed to parse jira_configs.labels")?;
+        Ok(JiraConfig {
+            project_id,
+            base_url,
+            email,
+            project_key,
+            issue_type,
+            labels,
+            api_token: None,
+            updated_at: Some(updated_at),
+        })
+    })
+    .transpose()
+}
+
+/// Insert or replace a project's Jira config (the API token isn't stored here)
+pub fn upsert_jira_config(conn: &Connection, config: &JiraConfig) -> Result<()> {
+    let labels = serde_json::to_string(&config.labels).context("Failed to serialize Jira labels")?;
+    conn.execute(
+        "INSERT INTO jira_configs (project_id, base_url, email, project_key, issue_type, labels, updated_at)
+         VALUES (?, ?, ?, ?, ?, ?, datetime('now'))
+         ON CONFLICT(project_id) DO UPDATE SET
+            base_url = excluded.base_url,
+            email = excluded.email,
+            project_key = excluded.project_key,
+            issue_type = excluded.issue_type,
+            labels = excluded.labels,
+            updated_at = excluded.updated_at",
+        params![config.project_id, config.base_url, config.email, config.project_key, config.issue_type, labels],
+    ).context("Failed to save Jira config")?;
+
+    Ok(())
+}
+
+/// Record the Jira issue filed for a violation
+pub fn update_violation_jira_issue_key(conn: &Connection, violation_id: i64, issue_key: &str) -> Result<()> {
+    let updated = conn
+        .execute(
+            "UPDATE violations SET jira_issue_key = ? WHERE id = ?",
+            params![issue_key, violation_id],
+        )
+        .context("Failed to update violation Jira issue key")?;
+    if updated == 0 {
+        return Err(anyhow::anyhow!("Violation not found: {}", violation_id));
+    }
+
+    Ok(())
+}
+
+// ===== SETTINGS PROFILE CRUD =====
+
+pub fn insert_settings_profile(conn: &Connection, profile: &SettingsProfile) -> Result<i64> {
+    let settings = serde_json::to_string(&profile.settings)
+        .context("Failed to serialize profile settings")?;
+
+    conn.execute(
+        "INSERT INTO settings_profiles (name, description, settings, built_in) VALUES (?, ?, ?, ?)",
+        params![profile.name, profile.description, settings, profile.built_in],
+    ).context("Failed to insert settings profile")?;
+
+    Ok(conn.last_insert_rowid())
+}
+
+fn map_settings_profile_row(row: &rusqlite::Row) -> rusqlite::Result<SettingsProfile> {
+    let settings: String = row.get(3)?;
+    Ok(SettingsProfile {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        description: row.get(2)?,
+        settings: serde_json::from_str(&settings).unwrap_or_default(),
+        built_in: row.get(4)?,
+        created_at: row.get(5)?,
+    })
+}
+
+pub fn select_settings_profiles(conn: &Connection) -> Result<Vec<SettingsProfile>> {
+    let mut stmt = conn
+        .prepare("SELECT id, name, description, settings, built_in, created_at FROM settings_profiles ORDER BY id ASC")
+        .context("Failed to prepare select settings profiles query")?;
+
+    let profiles = stmt
+        .query_map([], map_settings_profile_row)
+        .context("Failed to map settings profiles from query")?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .context("Failed to collect settings profiles")?;
+
+    Ok(profiles)
+}
+
+pub fn select_settings_profile(conn: &Connection, id: i64) -> Result<Option<SettingsProfile>> {
+    let mut stmt = conn
+        .prepare("SELECT id, name, description, settings, built_in, created_at FROM settings_profiles WHERE id = ?")
+        .context("Failed to prepare select settings profile query")?;
+
+    let profile = stmt
+        .query_row(params![id], map_settings_profile_row)
+        .optional()
+        .context("Failed to query settings profile")?;
+
+    Ok(profile)
+}
+
+pub fn select_settings_profile_by_name(conn: &Connection, name: &str) -> Result<Option<SettingsProfile>> {
+    let mut stmt = conn
+        .prepare("SELECT id, name, description, settings, built_in, created_at FROM settings_profiles WHERE name = ?")
+        .context("Failed to prepare select settings profile by name query")?;
+
+    let profile = stmt
+        .query_row(params![name], map_settings_profile_row)
+        .optional()
+        .context("Failed to query settings profile by name")?;
+
+    Ok(profile)
+}
+
+/// Write several settings in one transaction
+///
+/// Either every setting is written or, if any write fails, none are.
+pub fn insert_or_update_settings(conn: &Connection, settings: &[(&str, &str)]) -> Result<()> {
+    let tx = begin_write(conn)
+        .context("Failed to begin settings transaction")?;
+
+    for (key, value) in settings {
+        insert_or_update_setting(&tx, key, value)
+            .with_context(|| format!("Failed to write setting {}", key))?;
+    }
+
+    tx.commit().context("Failed to commit settings transaction")?;
+
+    Ok(())
+}
+
+// ===== MAINTENANCE =====
+
+/// Size of the database in pages and bytes, from `PRAGMA page_count`/`page_size`
+fn database_size(conn: &Connection) -> Result<(i64, u64)> {
+    let page_count: i64 = conn
+        .query_row("PRAGMA page_count", [], |row| row.get(0))
+        .context("Failed to read page count")?;
+    let page_size: i64 = conn
+        .query_row("PRAGMA page_size", [], |row| row.get(0))
+        .context("Failed to read page size")?;
+
+    Ok((page_count, (page_count * page_size) as u64))
+}
+
+/// Refresh query planner statistics and defragment the database
+///
+/// Runs `PRAGMA optimize`, `ANALYZE` and `VACUUM` in that order, then records
+/// the time in the `last_optimized_at` setting. `VACUUM` rewrites the whole
+/// file, so this must not be called inside a transaction.
+pub fn vacuum_and_analyze(conn: &Connection) -> Result<MaintenanceStats> {
+    let started = std::time::Instant::now();
+    let (pages_before, size_before_bytes) = database_size(conn)?;
+
+    conn.execute_batch("PRAGMA optimize;").context("Failed to run PRAGMA optimize")?;
+    conn.execute_batch("ANALYZE;").context("Failed to analyze database")?;
+    conn.execute_batch("VACUUM;").context("Failed to vacuum database")?;
+
+    let (pages_after, size_after_bytes) = database_size(conn)?;
+
+    insert_or_update_setting(conn, LAST_OPTIMIZED_AT_SETTING, &chrono::Utc::now().to_rfc3339())
+        .context("Failed to record optimization time")?;
+
+    Ok(MaintenanceStats {
+        pages_freed: (pages_before - pages_after).max(0),
+        size_before_bytes,
+        size_after_bytes,
+        duration_ms: started.elapsed().as_millis() as u64,
+    })
+}
+
+/// Return the `EXPLAIN QUERY PLAN` output for `query`, one plan step per line
+///
+/// Placeholders are left unbound (NULL), so the prepared SQL of a real query
+/// can be passed as-is. Used by tests to check which indexes a query uses.
+pub fn analyze_query_plan(conn: &Connection, query: &str) -> Result<String> {
+    let mut stmt = conn
+        .prepare(&format!("EXPLAIN QUERY PLAN {}", query))
+        .context("Failed to prepare query plan")?;
+
+    let mut rows = stmt.raw_query();
+    let mut steps = Vec::new();
+    while let Some(row) = rows.next().context("Failed to read query plan")? {
+        steps.push(row.get::<_, String>(3).context("Failed to read query plan detail")?);
+    }
+
+    Ok(steps.join("\n"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -862,6 +2568,155 @@ mod tests {
         assert!(deleted.is_none());
     }
 
+    #[test]
+    fn test_update_project_cost_limit() {
+        let (_temp_dir, conn) = setup_test_db();
+        let id = insert_project(&conn, "test-app", "/path/to/app", None).unwrap();
+        assert_eq!(select_project(&conn, id).unwrap().unwrap().project_cost_limit_usd, None);
+
+        update_project_cost_limit(&conn, id, Some(2.5)).unwrap();
+        assert_eq!(select_project(&conn, id).unwrap().unwrap().project_cost_limit_usd, Some(2.5));
+
+        update_project_cost_limit(&conn, id, None).unwrap();
+        assert_eq!(select_project(&conn, id).unwrap().unwrap().project_cost_limit_usd, None);
+    }
+
+    #[test]
+    fn test_jira_config_round_trip() {
+        let (_temp_dir, conn) = setup_test_db();
+        let id = insert_project(&conn, "test-app", "/path/to/app", None).unwrap();
+        assert_eq!(select_jira_config(&conn, id).unwrap(), None);
+
+        let mut config = JiraConfig {
+            project_id: id,
+            base_url: "https://acme.atlassian.net".to_string(),
+            email: "security@acme.com".to_string(),
+            project_key: "SEC".to_string(),
+            issue_type: "Bug".to_string(),
+            labels: vec!["soc2".to_string()],
+            api_token: Some("secret-token".to_string()),
+            updated_at: None,
+        };
+        upsert_jira_config(&conn, &config).unwrap();
+        let stored = select_jira_config(&conn, id).unwrap().unwrap();
+        assert!(stored.updated_at.is_some());
+        // The token never reaches the database
+        assert_eq!(JiraConfig { updated_at: None, api_token: Some("secret-token".to_string()), ..stored }, config);
+
+        config.project_key = "OPS".to_string();
+        upsert_jira_config(&conn, &config).unwrap();
+        assert_eq!(select_jira_config(&conn, id).unwrap().unwrap().project_key, "OPS");
+
+        let scan_id = insert_scan(&conn, id, "regex_only").unwrap();
+        let violation = Violation::new(scan_id, "CC6.7".to_string(), Severity::High, "Secret".to_string(), "app.py".to_string(), 1, "key = 1".to_string());
+        let violation_id = insert_violation(&conn, &violation).unwrap();
+        update_violation_jira_issue_key(&conn, violation_id, "OPS-7").unwrap();
+        assert_eq!(select_violation(&conn, violation_id).unwrap().unwrap().jira_issue_key.as_deref(), Some("OPS-7"));
+        assert!(update_violation_jira_issue_key(&conn, 999, "OPS-8").is_err());
+    }
+
+    #[test]
+    fn test_project_settings_round_trip() {
+        let (_temp_dir, conn) = setup_test_db();
+        let id = insert_project(&conn, "test-app", "/path/to/app", None).unwrap();
+        assert_eq!(select_project_settings(&conn, id).unwrap(), None);
+
+        let mut settings = ProjectSettings {
+            project_id: id,
+            min_severity: Some("medium".to_string()),
+            min_llm_confidence: Some(75),
+            disabled_controls: vec!["CC7.2".to_string()],
+            updated_at: None,
+        };
+        upsert_project_settings(&conn, &settings).unwrap();
+        let stored = select_project_settings(&conn, id).unwrap().unwrap();
+        assert!(stored.updated_at.is_some());
+        assert_eq!(ProjectSettings { updated_at: None, ..stored }, settings);
+
+        settings.min_severity = None;
+        settings.disabled_controls.clear();
+        upsert_project_settings(&conn, &settings).unwrap();
+        let stored = select_project_settings(&conn, id).unwrap().unwrap();
+        assert_eq!(stored.min_severity, None);
+        assert!(stored.disabled_controls.is_empty());
+    }
+
+    #[test]
+    fn test_project_roots_crud() {
+        let (_temp_dir, conn) = setup_test_db();
+        let id = insert_project(&conn, "test-app", "/work/api", None).unwrap();
+        assert!(select_project_roots(&conn, id).unwrap().is_empty());
+
+        let web = insert_project_root(&conn, id, "/work/web", "web", Some("react")).unwrap();
+        insert_project_root(&conn, id, "/work/jobs", "jobs", None).unwrap();
+        assert!(insert_project_root(&conn, id, "/work/other", "web", None).is_err());
+
+        update_project_root_framework(&conn, web, Some("nextjs")).unwrap();
+        let roots = select_project_roots(&conn, id).unwrap();
+        assert_eq!(roots.iter().map(|r| r.label.as_str()).collect::<Vec<_>>(), vec!["web", "jobs"]);
+        assert_eq!(roots[0].framework.as_deref(), Some("nextjs"));
+
+        assert!(delete_project_root(&conn, id, web).unwrap());
+        assert!(!delete_project_root(&conn, id, web).unwrap());
+        assert_eq!(select_project_roots(&conn, id).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_violation_history_lifecycle() {
+        let (_temp_dir, conn) = setup_test_db();
+        let project_id = insert_project(&conn, "test", "/path", None).unwrap();
+        // Each scan reports (snippet, line) findings in app.py
+        let scan = |findings: &[(&str, i64)]| {
+            let scan_id = insert_scan(&conn, project_id, "regex_only").unwrap();
+            for (snippet, line) in findings {
+                let violation = Violation::new(
+                    scan_id,
+                    "CC6.7".to_string(),
+                    Severity::High,
+                    "Hardcoded secret".to_string(),
+                    "app.py".to_string(),
+                    *line,
+                    snippet.to_string(),
+                );
+                insert_violation(&conn, &violation).unwrap();
+            }
+            scan_id
+        };
+        let lifecycles = |scan_id: i64| -> Vec<String> {
+            select_violations(&conn, scan_id).unwrap().into_iter().map(|v| v.lifecycle).collect()
+        };
+
+        let first = scan(&[("key = 'a'", 10), ("key = 'b'", 20)]);
+        let counts = update_violation_history(&conn, project_id, first, true).unwrap();
+        assert_eq!(counts, LifecycleCounts { new: 2, recurring: 0, resolved: 0 });
+
+        // 'a' moved down a few lines but is the same finding; 'b' is gone
+        let second = scan(&[("key  =  'a'", 14)]);
+        let counts = update_violation_history(&conn, project_id, second, true).unwrap();
+        assert_eq!(counts, LifecycleCounts { new: 0, recurring: 1, resolved: 1 });
+        assert_eq!(lifecycles(second), vec!["recurring"]);
+
+        // Recording the same scan again changes nothing
+        let counts = update_violation_history(&conn, project_id, second, true).unwrap();
+        assert_eq!(counts, LifecycleCounts::default());
+
+        // 'b' comes back, 'c' is new; a partial scan resolves nothing
+        let third = scan(&[("key = 'b'", 20), ("key = 'c'", 30)]);
+        let counts = update_violation_history(&conn, project_id, third, false).unwrap();
+        assert_eq!(counts, LifecycleCounts { new: 1, recurring: 1, resolved: 0 });
+
+        let history = select_violation_history(&conn, project_id, None).unwrap();
+        assert_eq!(history.len(), 3);
+        let a = history.iter().find(|h| h.last_seen_scan_id == second).unwrap();
+        assert_eq!((a.first_seen_scan_id, a.times_seen), (first, 2));
+        assert_eq!(a.get_lifecycle(), Some(ViolationLifecycle::Recurring));
+        let b = history.iter().find(|h| h.last_seen_scan_id == third && h.first_seen_scan_id == first).unwrap();
+        assert_eq!((b.lifecycle.as_str(), b.resolved_scan_id), ("recurring", None));
+
+        assert_eq!(select_violation_history(&conn, project_id, Some("new")).unwrap().len(), 1);
+        assert!(select_violation_history(&conn, project_id, Some("resolved")).unwrap().is_empty());
+    }
+
     #[test]
     fn test_scan_crud() {
         let (_temp_dir, conn) = setup_test_db();
@@ -870,7 +2725,7 @@ mod tests {
         let project_id = insert_project(&conn, "test", "/path", None).unwrap();
 
         // Create scan
-        let scan_id = insert_scan(&conn, project_id).unwrap();
+        let scan_id = insert_scan(&conn, project_id, "regex_only").unwrap();
         assert!(scan_id > 0);
 
         // Read
@@ -892,6 +2747,24 @@ mod tests {
         assert_eq!(with_results.files_scanned, 100);
         assert_eq!(with_results.total_files, 150);
         assert_eq!(with_results.violations_found, 5);
+
+        // Update enrichment counts
+        update_scan_enrichment(&conn, scan_id, 4, 1).unwrap();
+        let enriched = select_scan(&conn, scan_id).unwrap().unwrap();
+        assert_eq!(enriched.enriched_count, 4);
+        assert_eq!(enriched.context_extraction_failed, 1);
+
+        // Processing timeouts stay unset until the file walk records them
+        assert_eq!(enriched.processing_timeouts, None);
+        update_scan_processing_timeouts(&conn, scan_id, 2).unwrap();
+        let timed_out = select_scan(&conn, scan_id).unwrap().unwrap();
+        assert_eq!(timed_out.processing_timeouts, Some(2));
+
+        // Commit SHA is only recorded for scans of git repositories
+        assert_eq!(timed_out.commit_sha, None);
+        update_scan_commit_sha(&conn, scan_id, "3f2a9c1").unwrap();
+        let with_sha = select_scan(&conn, scan_id).unwrap().unwrap();
+        assert_eq!(with_sha.commit_sha.as_deref(), Some("3f2a9c1"));
     }
 
     #[test]
@@ -899,7 +2772,7 @@ mod tests {
         let (_temp_dir, conn) = setup_test_db();
 
         let project_id = insert_project(&conn, "test", "/path", None).unwrap();
-        let scan_id = insert_scan(&conn, project_id).unwrap();
+        let scan_id = insert_scan(&conn, project_id, "regex_only").unwrap();
 
         let violation = Violation::new(
             scan_id,
@@ -918,17 +2791,309 @@ mod tests {
         assert_eq!(stored.control_id, "CC6.1");
         assert_eq!(stored.severity, "critical");
 
-        update_violation_status(&conn, viol_id, "fixed").unwrap();
+        update_violation_status(&conn, viol_id, "fixed", None).unwrap();
         let fixed = select_violation(&conn, viol_id).unwrap().unwrap();
         assert_eq!(fixed.status, "fixed");
     }
 
+    #[test]
+    fn test_select_unique_violations_for_project_across_scans() {
+        let (_temp_dir, conn) = setup_test_db();
+        let project_id = insert_project(&conn, "test", "/path", None).unwrap();
+        let other_project = insert_project(&conn, "other", "/other", None).unwrap();
+        let finding = |scan_id: i64, control: &str, line: i64, description: &str| {
+            let violation = Violation::new(
+                scan_id,
+                control.to_string(),
+                Severity::High,
+                description.to_string(),
+                "app/views.py".to_string(),
+                line,
+                "code".to_string(),
+            );
+            insert_violation(&conn, &violation).unwrap()
+        };
+
+        // Three consecutive scans: line 10 in all, line 20 fixed after the
+        // first, line 30 new in the last
+        let mut scan_ids = Vec::new();
+        for (i, lines) in [vec![10, 20], vec![10], vec![10, 30]].into_iter().enumerate() {
+            let scan_id = insert_scan(&conn, project_id, "regex_only").unwrap();
+            for line in lines {
+                finding(scan_id, "CC6.1", line, &format!("Scan {} line {}", i + 1, line));
+            }
+            update_scan_status(&conn, scan_id, "completed", Some("2026-01-01T00:00:00Z")).unwrap();
+            scan_ids.push(scan_id);
+        }
+
+        // Running scans and other projects are ignored
+        let running = insert_scan(&conn, project_id, "regex_only").unwrap();
+        finding(running, "CC6.1", 40, "Running scan");
+        let other_scan = insert_scan(&conn, other_project, "regex_only").unwrap();
+        finding(other_scan, "CC6.1", 10, "Other project");
+        update_scan_status(&conn, other_scan, "completed", Some("2026-01-01T00:00:00Z")).unwrap();
+
+        let unique = select_unique_violations_for_project(&conn, project_id).unwrap();
+        let summary: Vec<(i64, &str, Option<i64>, Option<i64>)> = unique
+            .iter()
+            .map(|v| (v.line_number, v.description.as_str(), v.first_seen_scan_id, v.last_seen_scan_id))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (10, "Scan 3 line 10", Some(scan_ids[0]), Some(scan_ids[2])),
+                (20, "Scan 1 line 20", Some(scan_ids[0]), Some(scan_ids[0])),
+                (30, "Scan 3 line 30", Some(scan_ids[2]), Some(scan_ids[2])),
+            ]
+        );
+        assert_eq!(unique[0].scan_id, scan_ids[2]);
+
+        // The same finding can't be stored twice in one scan
+        let duplicate = Violation::new(
+            scan_ids[2],
+            "CC6.1".to_string(),
+            Severity::High,
+            "Scan 3 line 30 again".to_string(),
+            "app/views.py".to_string(),
+            30,
+            "code".to_string(),
+        );
+        assert!(insert_violation(&conn, &duplicate).is_err());
+        assert_eq!(select_unique_violations_for_project(&conn, project_id).unwrap().len(), 3);
+
+        // A different control on the same line is a separate finding
+        finding(scan_ids[1], "CC7.2", 10, "Logging");
+        assert_eq!(select_unique_violations_for_project(&conn, project_id).unwrap().len(), 4);
+    }
+
+    fn batch_violations(scan_id: i64, count: usize) -> Vec<Violation> {
+        (0..count)
+            .map(|i| {
+                Violation::new(
+                    scan_id,
+                    "CC6.1".to_string(),
+                    Severity::High,
+                    format!("Finding {}", i),
+                    "app/views.py".to_string(),
+                    i as i64 + 1,
+                    "code".to_string(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_insert_violations_batch_returns_inserted_count() {
+        let (_temp_dir, conn) = setup_test_db();
+        let project_id = insert_project(&conn, "test", "/path", None).unwrap();
+        let scan_id = insert_scan(&conn, project_id, "regex_only").unwrap();
+
+        let mut violations = batch_violations(scan_id, 25);
+        violations[3].detection_method = "hybrid".to_string();
+        violations[3].function_name = Some("create_user".to_string());
+
+        let inserted = insert_violations_batch(&conn, &violations).unwrap();
+        assert_eq!(inserted, 25);
+
+        let stored = select_violations(&conn, scan_id).unwrap();
+        assert_eq!(stored.len(), 25);
+        let hybrid = stored.iter().find(|v| v.line_number == 4).unwrap();
+        assert_eq!(hybrid.detection_method, "hybrid");
+        assert_eq!(hybrid.function_name.as_deref(), Some("create_user"));
+
+        assert_eq!(insert_violations_batch(&conn, &[]).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_insert_violations_batch_skips_duplicate_findings() {
+        let (_temp_dir, conn) = setup_test_db();
+        let project_id = insert_project(&conn, "test", "/path", None).unwrap();
+        let scan_id = insert_scan(&conn, project_id, "regex_only").unwrap();
+
+        // Lines 1 and 2 appear twice, once already stored
+        let mut violations = batch_violations(scan_id, 3);
+        violations.push(violations[0].clone());
+        insert_violation(&conn, &violations[1]).unwrap();
+
+        assert_eq!(insert_violations_batch(&conn, &violations).unwrap(), 2);
+        assert_eq!(select_violations(&conn, scan_id).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_insert_violations_batch_rolls_back_on_foreign_key_error() {
+        let (_temp_dir, conn) = setup_test_db();
+        let project_id = insert_project(&conn, "test", "/path", None).unwrap();
+        let scan_id = insert_scan(&conn, project_id, "regex_only").unwrap();
+
+        // The last violation points at a scan that doesn't exist
+        let mut violations = batch_violations(scan_id, 5);
+        violations.push(batch_violations(scan_id + 1000, 1).remove(0));
+
+        assert!(insert_violations_batch(&conn, &violations).is_err());
+
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM violations", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 0, "A failed batch must not leave partial rows behind");
+    }
+
+    #[test]
+    #[ignore] // Timing-sensitive; run with `cargo test --release -- --ignored`
+    fn bench_insert_violations_batch_vs_single() {
+        let (_temp_dir, conn) = setup_test_db();
+        let project_id = insert_project(&conn, "test", "/path", None).unwrap();
+        let single_scan_id = insert_scan(&conn, project_id, "regex_only").unwrap();
+        let batch_scan_id = insert_scan(&conn, project_id, "regex_only").unwrap();
+
+        let violations = batch_violations(single_scan_id, 1000);
+        let start = std::time::Instant::now();
+        for violation in &violations {
+            insert_violation(&conn, violation).unwrap();
+        }
+        let single = start.elapsed();
+
+        // A separate scan, or every row would be skipped as a duplicate
+        let violations = batch_violations(batch_scan_id, 1000);
+        let start = std::time::Instant::now();
+        assert_eq!(insert_violations_batch(&conn, &violations).unwrap(), 1000);
+        let batch = start.elapsed();
+
+        println!("1000 violations: single inserts {:?}, batch {:?}", single, batch);
+        assert!(single >= batch * 5, "Batch insert should be at least 5x faster ({:?} vs {:?})", single, batch);
+    }
+
+    #[test]
+    fn test_update_and_reopen_violations_status() {
+        let (_temp_dir, conn) = setup_test_db();
+
+        let project_id = insert_project(&conn, "test", "/path", None).unwrap();
+        let scan_id = insert_scan(&conn, project_id, "regex_only").unwrap();
+        let ids: Vec<i64> = (1..=3)
+            .map(|line| {
+                let violation = Violation::new(
+                    scan_id,
+                    "CC6.1".to_string(),
+                    Severity::High,
+                    "Missing auth".to_string(),
+                    "app/views.py".to_string(),
+                    line,
+                    "def view():".to_string(),
+                );
+                insert_violation(&conn, &violation).unwrap()
+            })
+            .collect();
+
+        let updated = update_violations_status(&conn, &ids[..2], "dismissed", "Internal tool").unwrap();
+        assert_eq!(updated, 2);
+        let dismissed = select_violation(&conn, ids[0]).unwrap().unwrap();
+        assert_eq!(dismissed.status, "dismissed");
+        assert_eq!(dismissed.last_status_change_reason, Some("Internal tool".to_string()));
+
+        // An unknown ID rolls back the whole batch
+        assert!(update_violations_status(&conn, &[ids[2], 9999], "accepted_risk", "x").is_err());
+        assert_eq!(select_violation(&conn, ids[2]).unwrap().unwrap().status, "open");
+
+        assert_eq!(reopen_violations(&conn, scan_id).unwrap(), 2);
+        let reopened = select_violation(&conn, ids[0]).unwrap().unwrap();
+        assert_eq!(reopened.status, "open");
+        assert_eq!(reopened.last_status_change_reason, None);
+    }
+
+    fn insert_open_violation(conn: &Connection) -> i64 {
+        let project_id = insert_project(conn, "test", "/path", None).unwrap();
+        let scan_id = insert_scan(conn, project_id, "regex_only").unwrap();
+        let violation = Violation::new(
+            scan_id,
+            "CC6.1".to_string(),
+            Severity::High,
+            "Missing auth".to_string(),
+            "app/views.py".to_string(),
+            1,
+            "def view():".to_string(),
+        );
+        insert_violation(conn, &violation).unwrap()
+    }
+
+    #[test]
+    fn test_update_violation_status_records_history() {
+        let (_temp_dir, conn) = setup_test_db();
+        let id = insert_open_violation(&conn);
+
+        update_violation_status(&conn, id, "fixed", None).unwrap();
+        update_violation_status(&conn, id, "open", Some("Regression detected")).unwrap();
+
+        let history = select_violation_status_history(&conn, id).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!((history[0].from_status.as_str(), history[0].to_status.as_str()), ("open", "fixed"));
+        assert_eq!(history[1].reason.as_deref(), Some("Regression detected"));
+        let stored = select_violation(&conn, id).unwrap().unwrap();
+        assert_eq!(stored.last_status_change_reason.as_deref(), Some("Regression detected"));
+    }
+
+    #[test]
+    fn test_update_violation_status_rejects_invalid_transition() {
+        let (_temp_dir, conn) = setup_test_db();
+        let id = insert_open_violation(&conn);
+        update_violation_status(&conn, id, "dismissed", None).unwrap();
+
+        let err = update_violation_status(&conn, id, "fixed", None).unwrap_err();
+
+        assert_eq!(
+            err.downcast_ref::<InvalidTransitionError>(),
+            Some(&InvalidTransitionError {
+                violation_id: id,
+                from: ViolationStatus::Dismissed,
+                to: ViolationStatus::Fixed,
+            })
+        );
+        assert_eq!(select_violation(&conn, id).unwrap().unwrap().status, "dismissed");
+        assert_eq!(select_violation_status_history(&conn, id).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_update_violation_status_rejected_transition_writes_no_history() {
+        let (_temp_dir, conn) = setup_test_db();
+        let id = insert_open_violation(&conn);
+
+        assert!(update_violation_status(&conn, id, "fixed_verified", None).is_err());
+
+        assert_eq!(select_violation(&conn, id).unwrap().unwrap().status, "open");
+        assert!(select_violation_status_history(&conn, id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_update_violation_status_same_status_is_noop() {
+        let (_temp_dir, conn) = setup_test_db();
+        let id = insert_open_violation(&conn);
+
+        update_violation_status(&conn, id, "open", None).unwrap();
+
+        assert!(select_violation_status_history(&conn, id).unwrap().is_empty());
+        assert!(update_violation_status(&conn, id, "bogus", None).is_err());
+        assert!(update_violation_status(&conn, 9999, "fixed", None).is_err());
+    }
+
+    #[test]
+    fn test_reopen_violations_records_history() {
+        let (_temp_dir, conn) = setup_test_db();
+        let id = insert_open_violation(&conn);
+        let scan_id = select_violation(&conn, id).unwrap().unwrap().scan_id;
+        update_violation_status(&conn, id, "fixed", None).unwrap();
+        update_violation_status(&conn, id, "fixed_verified", None).unwrap();
+
+        assert_eq!(reopen_violations(&conn, scan_id).unwrap(), 1);
+
+        let history = select_violation_status_history(&conn, id).unwrap();
+        let last = history.last().unwrap();
+        assert_eq!((last.from_status.as_str(), last.to_status.as_str()), ("fixed_verified", "open"));
+    }
+
     #[test]
     fn test_control_queries() {
         let (_temp_dir, conn) = setup_test_db();
 
         let controls = select_controls(&conn).unwrap();
-        assert_eq!(controls.len(), 4);
+        assert_eq!(controls.len(), 5);
 
         let cc6_1 = select_control(&conn, "CC6.1").unwrap();
         assert!(cc6_1.is_some());
@@ -953,4 +3118,411 @@ mod tests {
         let deleted = select_setting(&conn, "theme").unwrap();
         assert!(deleted.is_none());
     }
+
+    #[test]
+    fn test_fix_verification_counts() {
+        let (_temp_dir, conn) = setup_test_db();
+
+        let project_id = insert_project(&conn, "test", "/path", None).unwrap();
+        let scan_id = insert_scan(&conn, project_id, "regex_only").unwrap();
+
+        let mut fix_ids = Vec::new();
+        for line in 1..=4 {
+            let violation = Violation::new(
+                scan_id,
+                "CC6.7".to_string(),
+                Severity::High,
+                "Hardcoded secret".to_string(),
+                "app/config.py".to_string(),
+                line,
+                "api_key = 'x'".to_string(),
+            );
+            let viol_id = insert_violation(&conn, &violation).unwrap();
+            let fix = Fix::new(viol_id, "x".to_string(), "y".to_string(), "z".to_string(), TrustLevel::Review);
+            fix_ids.push(insert_fix(&conn, &fix).unwrap());
+        }
+
+        // Three applied fixes, one left unapplied (not counted)
+        for fix_id in &fix_ids[..3] {
+            update_fix_applied(&conn, *fix_id, "", None).unwrap();
+        }
+        let stored = select_fix(&conn, fix_ids[0]).unwrap().unwrap();
+        assert_eq!(stored.verification_status, Some("pending".to_string()));
+
+        let now = chrono::Utc::now().to_rfc3339();
+        update_fix_verification(&conn, fix_ids[0], "verified", Some(&now)).unwrap();
+        update_fix_verification(&conn, fix_ids[1], "failed", Some(&now)).unwrap();
+
+        let (verified, failed, pending) = get_fix_verification_counts(&conn, project_id).unwrap();
+        assert_eq!((verified, failed, pending), (1, 1, 1));
+
+        let verified_fix = select_fix(&conn, fix_ids[0]).unwrap().unwrap();
+        assert_eq!(verified_fix.verified_at, Some(now));
+    }
+
+    #[test]
+    fn test_fix_attempt_numbers_per_violation() {
+        let (_temp_dir, conn) = setup_test_db();
+
+        let project_id = insert_project(&conn, "test", "/path", None).unwrap();
+        let scan_id = insert_scan(&conn, project_id, "regex_only").unwrap();
+        let mut violation_ids = Vec::new();
+        for line in 1..=2 {
+            let violation = Violation::new(
+                scan_id,
+                "CC6.7".to_string(),
+                Severity::High,
+                "Hardcoded secret".to_string(),
+                "app/config.py".to_string(),
+                line,
+                "api_key = 'x'".to_string(),
+            );
+            violation_ids.push(insert_violation(&conn, &violation).unwrap());
+        }
+
+        for (violation_id, fixed_code) in [(violation_ids[0], "first"), (violation_ids[1], "other"), (violation_ids[0], "second")] {
+            let fix = Fix::new(violation_id, "x".to_string(), fixed_code.to_string(), "z".to_string(), TrustLevel::Review);
+            insert_fix(&conn, &fix).unwrap();
+        }
+
+        let history = select_fixes_for_violation(&conn, violation_ids[0]).unwrap();
+        let attempts: Vec<(i32, &str)> = history
+            .iter()
+            .map(|fix| (fix.fix_attempt_number, fix.fixed_code.as_str()))
+            .collect();
+        assert_eq!(attempts, vec![(2, "second"), (1, "first")]);
+        assert!(history.iter().all(|fix| !fix.created_at.is_empty()));
+
+        let other = select_fixes_for_violation(&conn, violation_ids[1]).unwrap();
+        assert_eq!(other.len(), 1);
+        assert_eq!(other[0].fix_attempt_number, 1);
+
+        // The latest attempt is the violation's current fix
+        let latest = select_fix_for_violation(&conn, violation_ids[0]).unwrap().unwrap();
+        assert_eq!(latest.fixed_code, "second");
+    }
+
+    #[test]
+    fn test_fix_changes_summary_and_test_recommendation_round_trip() {
+        let (_temp_dir, conn) = setup_test_db();
+
+        let project_id = insert_project(&conn, "test", "/path", None).unwrap();
+        let scan_id = insert_scan(&conn, project_id, "regex_only").unwrap();
+        let violation = Violation::new(
+            scan_id,
+            "CC6.7".to_string(),
+            Severity::High,
+            "Hardcoded secret".to_string(),
+            "app/config.py".to_string(),
+            1,
+            "api_key = 'x'".to_string(),
+        );
+        let violation_id = insert_violation(&conn, &violation).unwrap();
+
+        let mut fix = Fix::new(violation_id, "x".to_string(), "y".to_string(), "z".to_string(), TrustLevel::Review);
+        fix.changes_summary = Some("Read api_key from the environment".to_string());
+        fix.test_recommendation = Some("Start the app without API_KEY set".to_string());
+        let fix_id = insert_fix(&conn, &fix).unwrap();
+
+        let stored = select_fix(&conn, fix_id).unwrap().unwrap();
+        assert_eq!(stored.changes_summary.as_deref(), Some("Read api_key from the environment"));
+        assert_eq!(stored.test_recommendation.as_deref(), Some("Start the app without API_KEY set"));
+
+        let plain = Fix::new(violation_id, "x".to_string(), "w".to_string(), "z".to_string(), TrustLevel::Review);
+        let plain_id = insert_fix(&conn, &plain).unwrap();
+        let stored = select_fix(&conn, plain_id).unwrap().unwrap();
+        assert_eq!(stored.changes_summary, None);
+        assert_eq!(stored.test_recommendation, None);
+    }
+
+    #[test]
+    fn test_vacuum_and_analyze_reclaims_deleted_rows() {
+        let (_temp_dir, conn) = setup_test_db();
+
+        let padding = "x".repeat(1024);
+        for i in 0..1000 {
+            conn.execute(
+                "INSERT INTO audit_events (event_type, description) VALUES ('scan_completed', ?)",
+                params![format!("{} {}", i, padding)],
+            ).unwrap();
+        }
+        conn.execute("DELETE FROM audit_events", []).unwrap();
+
+        let free_pages = |conn: &Connection| -> i64 {
+            conn.query_row("PRAGMA freelist_count", [], |row| row.get(0)).unwrap()
+        };
+        assert!(free_pages(&conn) > 200, "deleted rows should leave free pages");
+
+        let stats = vacuum_and_analyze(&conn).unwrap();
+
+        assert_eq!(free_pages(&conn), 0);
+        assert!(stats.pages_freed > 200);
+        assert!(stats.size_after_bytes < stats.size_before_bytes);
+        let last_optimized = select_setting(&conn, LAST_OPTIMIZED_AT_SETTING).unwrap().unwrap();
+        assert!(chrono::DateTime::parse_from_rfc3339(&last_optimized.value).is_ok());
+    }
+
+    #[test]
+    fn test_delete_project_optimizes_database() {
+        let (_temp_dir, conn) = setup_test_db();
+        conn.execute("DELETE FROM settings WHERE key = ?", params![LAST_OPTIMIZED_AT_SETTING]).unwrap();
+
+        let id = insert_project(&conn, "doomed", "/doomed", None).unwrap();
+        delete_project(&conn, id).unwrap();
+
+        assert!(select_setting(&conn, LAST_OPTIMIZED_AT_SETTING).unwrap().is_some());
+    }
+
+    /// Insert one violation per (control_id, severity) pair
+    fn insert_violations(conn: &Connection, scan_id: i64, findings: &[(&str, Severity)]) {
+        for (line, (control_id, severity)) in findings.iter().enumerate() {
+            let violation = Violation::new(
+                scan_id,
+                control_id.to_string(),
+                *severity,
+                "Finding".to_string(),
+                "app/views.py".to_string(),
+                line as i64 + 1,
+                "code".to_string(),
+            );
+            insert_violation(conn, &violation).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_severity_counts_by_control() {
+        let (_temp_dir, conn) = setup_test_db();
+
+        let project_id = insert_project(&conn, "test", "/path", None).unwrap();
+        let scan_id = insert_scan(&conn, project_id, "regex_only").unwrap();
+        let other_scan_id = insert_scan(&conn, project_id, "regex_only").unwrap();
+        insert_violations(&conn, scan_id, &[
+            ("CC6.1", Severity::Critical),
+            ("CC6.1", Severity::Critical),
+            ("CC6.1", Severity::Medium),
+            ("CC6.7", Severity::High),
+            ("CC6.7", Severity::Low),
+            ("A1.2", Severity::Medium),
+        ]);
+        insert_violations(&conn, other_scan_id, &[("CC7.2", Severity::High)]);
+
+        let counts = get_severity_counts_by_control(&conn, scan_id).unwrap();
+
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts["CC6.1"], (2, 0, 1, 0));
+        assert_eq!(counts["CC6.7"], (0, 1, 0, 1));
+        assert_eq!(counts["A1.2"], (0, 0, 1, 0));
+        assert!(!counts.contains_key("CC7.2"));
+
+        // Per-control counts add up to the scan totals
+        let (critical, high, medium, low) = get_severity_counts(&conn, scan_id).unwrap();
+        let summed = counts.values().fold((0, 0, 0, 0), |acc, c| (acc.0 + c.0, acc.1 + c.1, acc.2 + c.2, acc.3 + c.3));
+        assert_eq!(summed, (critical as i64, high as i64, medium as i64, low as i64));
+    }
+
+    #[test]
+    fn test_control_violation_trend() {
+        let (_temp_dir, conn) = setup_test_db();
+
+        let project_id = insert_project(&conn, "test", "/path", None).unwrap();
+        let findings: [&[(&str, Severity)]; 3] = [
+            &[("CC6.7", Severity::High), ("CC6.7", Severity::Low), ("CC6.7", Severity::Medium)],
+            &[("CC6.7", Severity::High), ("CC6.1", Severity::Critical)],
+            &[("CC6.1", Severity::Critical)],
+        ];
+        for (day, scan_findings) in findings.iter().enumerate() {
+            let scan_id = insert_scan(&conn, project_id, "regex_only").unwrap();
+            insert_violations(&conn, scan_id, scan_findings);
+            let completed_at = format!("2025-01-0{}T00:00:00+00:00", day + 1);
+            update_scan_status(&conn, scan_id, "completed", Some(&completed_at)).unwrap();
+        }
+        // Running scans have no completed_at and are left out
+        let running_id = insert_scan(&conn, project_id, "regex_only").unwrap();
+        insert_violations(&conn, running_id, &[("CC6.7", Severity::High)]);
+
+        let trend = get_control_violation_trend(&conn, project_id, "CC6.7", 10).unwrap();
+        assert_eq!(
+            trend,
+            vec![
+                ("2025-01-01T00:00:00+00:00".to_string(), 3),
+                ("2025-01-02T00:00:00+00:00".to_string(), 1),
+                ("2025-01-03T00:00:00+00:00".to_string(), 0),
+            ]
+        );
+
+        // Only the most recent scans, still oldest first
+        let recent = get_control_violation_trend(&conn, project_id, "CC6.1", 2).unwrap();
+        assert_eq!(
+            recent,
+            vec![
+                ("2025-01-02T00:00:00+00:00".to_string(), 1),
+                ("2025-01-03T00:00:00+00:00".to_string(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compliance_trend_records_scores_oldest_first() {
+        let (_temp_dir, conn) = setup_test_db();
+
+        let project_id = insert_project(&conn, "test", "/path", None).unwrap();
+        let findings: [&[(&str, Severity)]; 3] = [
+            &[("CC6.7", Severity::Critical), ("CC6.7", Severity::High), ("A1.2", Severity::Medium)],
+            &[("CC6.7", Severity::High)],
+            &[],
+        ];
+        for (day, scan_findings) in findings.iter().enumerate() {
+            let scan_id = insert_scan(&conn, project_id, "regex_only").unwrap();
+            insert_violations(&conn, scan_id, scan_findings);
+            let completed_at = format!("2025-01-0{}T00:00:00+00:00", day + 1);
+            update_scan_status(&conn, scan_id, "completed", Some(&completed_at)).unwrap();
+            record_compliance_score(&conn, scan_id).unwrap();
+        }
+
+        // Running scans can't be scored
+        let running_id = insert_scan(&conn, project_id, "regex_only").unwrap();
+        assert!(record_compliance_score(&conn, running_id).is_err());
+
+        let trend = get_compliance_trend(&conn, project_id, 10).unwrap();
+        let cc67: Vec<f32> = trend.iter().map(|p| p.cc67_score).collect();
+        assert_eq!(cc67, vec![77.0, 92.0, 100.0]);
+        assert_eq!(trend[0].a12_score, 97.0);
+        assert_eq!(trend[0].completed_at, "2025-01-01T00:00:00+00:00");
+        assert!(compliance_trend_slope(&trend) > 0.0);
+
+        // Re-recording replaces the score instead of adding a point
+        record_compliance_score(&conn, trend[2].scan_id).unwrap();
+        let recent = get_compliance_trend(&conn, project_id, 2).unwrap();
+        assert_eq!(recent.len(), 2);
+        assert_eq!(recent[0].scan_id, trend[1].scan_id);
+        assert_eq!(recent[1].overall_score, 100.0);
+    }
+
+    /// Insert a scan cost for a new scan of `project_id`, created `days_ago` days ago
+    fn insert_cost(conn: &Connection, project_id: i64, days_ago: i64, input_tokens: i64, output_tokens: i64) -> i64 {
+        let scan_id = insert_scan(conn, project_id, "smart").unwrap();
+        let mut cost = ScanCost::new(scan_id, 3, input_tokens, output_tokens, 1_000, 0);
+        cost.created_at = (chrono::Utc::now() - chrono::Duration::days(days_ago)).to_rfc3339();
+        insert_scan_cost(conn, &cost).unwrap();
+        scan_id
+    }
+
+    #[test]
+    fn test_get_total_spend_aggregates_costs() {
+        let (_temp_dir, conn) = setup_test_db();
+
+        let project_a = insert_project(&conn, "a", "/a", None).unwrap();
+        let project_b = insert_project(&conn, "b", "/b", None).unwrap();
+        insert_cost(&conn, project_a, 0, 100_000, 10_000);
+        let expensive = insert_cost(&conn, project_a, 40, 1_000_000, 100_000);
+        insert_cost(&conn, project_b, 1, 50_000, 5_000);
+
+        let all = get_total_spend(&conn, None, None).unwrap();
+        assert_eq!(all.scan_count, 3);
+        assert_eq!(all.total_input_tokens, 1_150_000);
+        assert_eq!(all.total_output_tokens, 115_000);
+        assert_eq!(all.total_cache_read_tokens, 3_000);
+        assert_eq!(all.most_expensive_scan_id, Some(expensive));
+        assert!((all.avg_cost_per_scan - all.total_cost_usd / 3.0).abs() < 1e-9);
+
+        let since = (chrono::Utc::now() - chrono::Duration::days(30)).to_rfc3339();
+        let recent_a = get_total_spend(&conn, Some(project_a), Some(since)).unwrap();
+        assert_eq!(recent_a.scan_count, 1);
+        assert_eq!(recent_a.total_input_tokens, 100_000);
+        assert!((recent_a.total_cost_usd - ScanCost::calculate_cost(100_000, 10_000, 1_000, 0)).abs() < 1e-9);
+
+        let none = get_total_spend(&conn, Some(999), None).unwrap();
+        assert_eq!(none, SpendSummary::default());
+    }
+
+    #[test]
+    fn test_get_spend_by_day_fills_missing_days() {
+        let (_temp_dir, conn) = setup_test_db();
+
+        let project_id = insert_project(&conn, "a", "/a", None).unwrap();
+        insert_cost(&conn, project_id, 0, 100_000, 0);
+        insert_cost(&conn, project_id, 0, 100_000, 0);
+        insert_cost(&conn, project_id, 2, 200_000, 0);
+        insert_cost(&conn, project_id, 10, 300_000, 0);
+
+        let days = get_spend_by_day(&conn, Some(project_id), 7).unwrap();
+
+        assert_eq!(days.len(), 7);
+        assert_eq!(days[6].date, chrono::Utc::now().date_naive().to_string());
+        assert_eq!(days[6].scan_count, 2);
+        assert!((days[6].total_cost_usd - 2.0 * ScanCost::calculate_cost(100_000, 0, 1_000, 0)).abs() < 1e-9);
+        assert_eq!(days[4].scan_count, 1);
+        assert_eq!(days.iter().map(|d| d.scan_count).sum::<i64>(), 3);
+        assert_eq!(days[0].total_cost_usd, 0.0);
+
+        assert!(get_spend_by_day(&conn, None, 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_spend_is_scoped_to_current_namespace() {
+        let (_temp_dir, conn) = setup_test_db();
+
+        let project_a = insert_project(&conn, "a", "/a", None).unwrap();
+        insert_cost(&conn, project_a, 0, 100_000, 0);
+
+        insert_namespace(&conn, "team-b").unwrap();
+        insert_or_update_setting(&conn, "current_namespace", "team-b").unwrap();
+        let project_b = insert_project(&conn, "b", "/b", None).unwrap();
+        insert_cost(&conn, project_b, 0, 200_000, 0);
+
+        let total = get_total_spend(&conn, None, None).unwrap();
+        assert_eq!((total.scan_count, total.total_input_tokens), (1, 200_000));
+        assert_eq!(get_total_spend(&conn, Some(project_a), None).unwrap(), SpendSummary::default());
+
+        let days = get_spend_by_day(&conn, None, 1).unwrap();
+        assert_eq!(days[0].scan_count, 1);
+        assert!(get_spend_by_day(&conn, Some(project_a), 1).unwrap().iter().all(|d| d.scan_count == 0));
+    }
+
+    #[test]
+    fn test_violation_queries_use_composite_indexes() {
+        let (_temp_dir, conn) = setup_test_db();
+
+        let cases = [
+            (
+                "SELECT id FROM violations WHERE scan_id = ? AND control_id = ?",
+                "idx_violations_scan_control",
+            ),
+            (
+                "SELECT id FROM violations WHERE scan_id = ? AND severity = ? AND status = ?",
+                "idx_violations_scan_severity",
+            ),
+            (
+                "SELECT id FROM violations WHERE file_path = ? AND line_number = ?",
+                "idx_violations_file_line",
+            ),
+            (
+                "SELECT id FROM violations WHERE scan_id = ? AND status = ?",
+                "idx_violations_scan_status",
+            ),
+            (
+                "SELECT severity, COUNT(*) FROM violations WHERE scan_id = ? GROUP BY severity",
+                "idx_violations_scan_severity",
+            ),
+        ];
+
+        for (query, index) in cases {
+            let plan = analyze_query_plan(&conn, query).unwrap();
+            assert!(plan.contains(index), "{} should use {}, plan:\n{}", query, index, plan);
+        }
+    }
+
+    #[test]
+    fn test_select_violations_plan_avoids_table_scan() {
+        let (_temp_dir, conn) = setup_test_db();
+
+        let plan = analyze_query_plan(
+            &conn,
+            "SELECT id FROM violations WHERE scan_id = ? AND scan_id IN (SELECT s.id FROM scans s JOIN projects p ON p.id = s.project_id WHERE p.namespace = ?) ORDER BY severity DESC, line_number ASC",
+        )
+        .unwrap();
+
+        assert!(plan.contains("idx_violations_scan_severity"), "plan:\n{}", plan);
+        assert!(!plan.contains("SCAN violations"), "plan:\n{}", plan);
+    }
 }
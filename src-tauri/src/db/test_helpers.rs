@@ -14,27 +14,40 @@ pub struct TestDbGuard {
 impl TestDbGuard {
     /// Create a new test environment with SHARED test database
     ///
-    /// All tests use /tmp/ryn-test/ to work with the singleton DB_CONNECTION
+    /// All tests use /tmp/ryn-test/ to work with the shared DB_POOL
     /// Tests run serially (#[serial_test::serial]) so no conflicts occur
     pub fn new() -> Self {
         let test_dir = std::path::PathBuf::from("/tmp/ryn-test");
         std::fs::create_dir_all(&test_dir).unwrap();
         std::env::set_var("RYN_DATA_DIR", &test_dir);
 
-        // Clear all data from existing database tables using the singleton connection
-        // This ensures we're clearing the same connection that tests will use
+        // Clear all data from existing database tables through the shared pool
+        // This ensures we're clearing the same database that tests will use
         {
             let conn = super::get_connection();
             let _ = conn.execute("DELETE FROM fixes", []);
+            let _ = conn.execute("DELETE FROM violation_status_history", []);
+            let _ = conn.execute("DELETE FROM violation_history", []);
             let _ = conn.execute("DELETE FROM violations", []);
             let _ = conn.execute("DELETE FROM scans", []);
             let _ = conn.execute("DELETE FROM scan_costs", []);
             let _ = conn.execute("DELETE FROM audit_events", []);
+            let _ = conn.execute("DELETE FROM false_positive_reports", []);
+            let _ = conn.execute("DELETE FROM slack_configs", []);
+            let _ = conn.execute("DELETE FROM scan_schedules", []);
+            let _ = conn.execute("DELETE FROM project_settings", []);
+            let _ = conn.execute("DELETE FROM jira_configs", []);
+            let _ = conn.execute("DELETE FROM project_roots", []);
             let _ = conn.execute("DELETE FROM projects", []);
             let _ = conn.execute("DELETE FROM settings", []);
+            let _ = conn.execute("DELETE FROM namespaces WHERE name != 'default'", []);
+            let _ = conn.execute("DELETE FROM settings_profiles WHERE built_in = 0", []);
             // Reset auto-increment counters so IDs start from 1 in each test
             let _ = conn.execute("DELETE FROM sqlite_sequence", []);
-        } // Drop the MutexGuard here
+        } // Return the connection to the pool here
+
+        // Scan IDs restart at 1, so drop profiles recorded by earlier tests
+        let _ = std::fs::remove_dir_all(test_dir.join(crate::scanner::scan_profiler::PROFILE_DIR));
 
         // Use a fake temp_dir to satisfy struct requirement
         let temp_dir = tempfile::TempDir::new().unwrap();
@@ -82,7 +95,7 @@ mod tests {
             let count: i64 = conn1
                 .query_row("SELECT COUNT(*) FROM controls", [], |row| row.get(0))
                 .unwrap();
-            assert_eq!(count, 4);
+            assert_eq!(count, 5);
         }
 
         // Second test - should have clean database
@@ -93,7 +106,7 @@ mod tests {
             let count: i64 = conn2
                 .query_row("SELECT COUNT(*) FROM controls", [], |row| row.get(0))
                 .unwrap();
-            assert_eq!(count, 4);
+            assert_eq!(count, 5);
         }
     }
 
@@ -496,7 +496,7 @@ impl ClaudeClient {
     /// Creates a comprehensive system prompt with all SOC 2 control definitions.
     /// This prompt is cacheable (>2048 tokens) and will be reused across files.
     fn build_soc2_system_prompt() -> String {
-        let controls = Control::all_controls();
+        let controls = Control::code_controls();
 
         let mut prompt = String::from(
             "You are a SOC 2 compliance expert analyzing application code for security violations.\n\n\
@@ -632,6 +632,15 @@ impl ClaudeClient {
                     regex_reasoning: None,
                     function_name: None,
                     class_name: None,
+                    decorator_name: None,
+                    is_new: false,
+                    last_status_change_reason: None,
+                    package_name: None,
+                    fingerprint: String::new(),
+                    lifecycle: "new".to_string(),
+                    jira_issue_key: None,
+                    first_seen_scan_id: None,
+                    last_seen_scan_id: None,
                 }
             })
             .collect();
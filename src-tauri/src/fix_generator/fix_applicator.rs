@@ -1,12 +1,38 @@
 //! File modification and fix application module
 //!
 //! Provides operations for applying AI-generated fixes to files on disk,
-//! with backup/restore capabilities and basic syntax validation.
+//! with backup/restore capabilities and basic syntax validation, and
+//! unified diff previews of a fix before it is applied.
 
 use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
 use std::fs;
 use std::path::Path;
 
+/// Unchanged lines shown before and after the change in a preview diff
+const DIFF_CONTEXT_LINES: usize = 3;
+
+/// Lines `start..=end` of a file (1-based, inclusive)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LineRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// What applying a fix would change, computed without writing the file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FixPreview {
+    /// File the fix applies to, relative to the project root
+    pub file_path: String,
+    /// Unified diff (`--- a/...`, `+++ b/...`, one hunk); empty when nothing changes
+    pub diff: String,
+    /// Lines of the current file that are replaced (None for a pure insertion)
+    pub removed_lines: Option<LineRange>,
+    /// Lines of the fixed file that replace them (None for a pure deletion)
+    pub added_lines: Option<LineRange>,
+}
+
 /// Applies fixes to source files and manages backups
 pub struct FixApplicator;
 
@@ -200,6 +226,78 @@ impl FixApplicator {
 
         Ok(diff_count)
     }
+
+    /// Preview a fix as a unified diff, without touching the file
+    ///
+    /// A fix replaces one snippet, so the lines shared at the start and end
+    /// of both versions are left out and everything between them forms a
+    /// single hunk with up to 3 lines of context on each side.
+    ///
+    /// # Arguments
+    /// * `file_path` - Path shown in the diff header, relative to the project root
+    /// * `original` - Current file content
+    /// * `fixed` - File content with the fix applied
+    pub fn preview_fix(file_path: &str, original: &str, fixed: &str) -> FixPreview {
+        let old_lines: Vec<&str> = original.split_inclusive('\n').collect();
+        let new_lines: Vec<&str> = fixed.split_inclusive('\n').collect();
+
+        let prefix = old_lines.iter().zip(&new_lines).take_while(|(a, b)| a == b).count();
+        let suffix = old_lines
+            .iter()
+            .rev()
+            .zip(new_lines.iter().rev())
+            .take(old_lines.len().min(new_lines.len()) - prefix)
+            .take_while(|(a, b)| a == b)
+            .count();
+        let old_end = old_lines.len() - suffix;
+        let new_end = new_lines.len() - suffix;
+
+        let range = |start: usize, end: usize| (end > start).then_some(LineRange { start: start + 1, end });
+        let mut preview = FixPreview {
+            file_path: file_path.to_string(),
+            diff: String::new(),
+            removed_lines: range(prefix, old_end),
+            added_lines: range(prefix, new_end),
+        };
+        if preview.removed_lines.is_none() && preview.added_lines.is_none() {
+            return preview;
+        }
+
+        let context_start = prefix.saturating_sub(DIFF_CONTEXT_LINES);
+        let context_after = suffix.min(DIFF_CONTEXT_LINES);
+        // A hunk with no lines on one side starts at the line before it
+        let hunk_range = |len: usize| {
+            let count = len - context_start;
+            let start = if count == 0 { context_start } else { context_start + 1 };
+            format!("{},{}", start, count)
+        };
+
+        let diff = &mut preview.diff;
+        let _ = writeln!(diff, "--- a/{}", file_path);
+        let _ = writeln!(diff, "+++ b/{}", file_path);
+        let _ = writeln!(
+            diff,
+            "@@ -{} +{} @@",
+            hunk_range(old_end + context_after),
+            hunk_range(new_end + context_after)
+        );
+
+        let lines = old_lines[context_start..prefix]
+            .iter()
+            .map(|line| (' ', *line))
+            .chain(old_lines[prefix..old_end].iter().map(|line| ('-', *line)))
+            .chain(new_lines[prefix..new_end].iter().map(|line| ('+', *line)))
+            .chain(old_lines[old_end..old_end + context_after].iter().map(|line| (' ', *line)));
+        for (marker, line) in lines {
+            diff.push(marker);
+            diff.push_str(line);
+            if !line.ends_with('\n') {
+                diff.push_str("\n\\ No newline at end of file\n");
+            }
+        }
+
+        preview
+    }
 }
 
 #[cfg(test)]
@@ -388,6 +486,43 @@ mod tests {
         assert_eq!(diff_count, 2); // 2 new lines
     }
 
+    #[test]
+    fn test_preview_fix_single_line_change() {
+        let original = "import os\n\ndef connect():\n    password = \"hunter2\"\n    return db(password)\n\n\n\nprint('done')\n";
+        let fixed = original.replace("\"hunter2\"", "os.environ[\"DB_PASSWORD\"]");
+
+        let preview = FixApplicator::preview_fix("app/db.py", original, &fixed);
+
+        assert_eq!(
+            preview.diff,
+            "--- a/app/db.py\n+++ b/app/db.py\n@@ -1,7 +1,7 @@\n import os\n \n def connect():\n\
+             -    password = \"hunter2\"\n+    password = os.environ[\"DB_PASSWORD\"]\n\
+             \x20    return db(password)\n \n \n"
+        );
+        assert_eq!(preview.removed_lines, Some(LineRange { start: 4, end: 4 }));
+        assert_eq!(preview.added_lines, Some(LineRange { start: 4, end: 4 }));
+    }
+
+    #[test]
+    fn test_preview_fix_insertion_and_missing_newline() {
+        let original = "a\nb\nc";
+        let fixed = "a\nb\nlog()\nc";
+
+        let preview = FixApplicator::preview_fix("x.py", original, fixed);
+
+        assert_eq!(preview.removed_lines, None);
+        assert_eq!(preview.added_lines, Some(LineRange { start: 3, end: 3 }));
+        assert!(preview.diff.contains("@@ -1,3 +1,4 @@\n a\n b\n+log()\n c\n\\ No newline at end of file\n"));
+    }
+
+    #[test]
+    fn test_preview_fix_unchanged_content() {
+        let preview = FixApplicator::preview_fix("x.py", "same\n", "same\n");
+        assert!(preview.diff.is_empty());
+        assert_eq!(preview.removed_lines, None);
+        assert_eq!(preview.added_lines, None);
+    }
+
     #[test]
     fn test_backup_and_restore_roundtrip() {
         let dir = TempDir::new().unwrap();
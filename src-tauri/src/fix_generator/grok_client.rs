@@ -10,10 +10,107 @@
 //! - Format: OpenAI-compatible chat completions
 
 use anyhow::{anyhow, Context, Result};
-use reqwest::Client;
+use once_cell::sync::Lazy;
+use reqwest::{Client, StatusCode};
 use serde::{Deserialize, Serialize};
-use std::env;
-use crate::models::{Control, Violation, Severity, DetectionMethod};
+use std::sync::Arc;
+use std::time::Duration;
+use crate::models::{Control, FalsePositive, Violation, Severity, DetectionMethod};
+use crate::security::credentials::{self, CredentialProvider};
+
+/// Per-request timeout for Grok API calls
+const REQUEST_TIMEOUT_SECS: u64 = 120;
+
+/// Retries after the first attempt for fix generation and analysis calls
+const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// First backoff delay; doubles on each further retry
+const DEFAULT_RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+
+/// Appended to fix generation system prompts; the JSON schema itself is sent
+/// as the request's `response_format`
+const FIX_RESPONSE_INSTRUCTIONS: &str = "Respond with a JSON object: \
+    \"fixed_code\" is the complete fixed code without Markdown fences, \
+    \"explanation\" says why the change resolves the violation, \
+    \"changes_summary\" summarizes what changed in one sentence, and \
+    \"test_recommendation\" says how to test that the fix works and breaks nothing.";
+
+/// Wait used when a 429 has no usable Retry-After header
+const DEFAULT_RETRY_AFTER_SECS: u64 = 1;
+
+/// Timeout for the `health_check` request
+const HEALTH_CHECK_TIMEOUT_SECS: u64 = 10;
+
+/// Production X.AI API base URL
+const DEFAULT_API_BASE: &str = "https://api.x.ai/v1";
+
+/// Idle connections kept open per host by the shared clients
+const POOL_MAX_IDLE_PER_HOST: usize = 20;
+
+/// HTTP client shared by every `GrokClient` talking to the X.AI API
+///
+/// LLM scans create a `GrokClient` per file task; sharing one client lets
+/// those tasks multiplex over a few pooled HTTP/2 connections instead of
+/// each opening its own TLS connection.
+static HTTP_CLIENT: Lazy<Arc<Client>> = Lazy::new(|| {
+    Arc::new(
+        Client::builder()
+            .http2_prior_knowledge()
+            .pool_max_idle_per_host(POOL_MAX_IDLE_PER_HOST)
+            .tcp_keepalive(Duration::from_secs(60))
+            .build()
+            .expect("Failed to build Grok HTTP client"),
+    )
+});
+
+/// Shared client for custom API bases (`with_url`)
+///
+/// Local mocks and staging proxies may only speak HTTP/1.1, so this client
+/// negotiates the protocol instead of assuming HTTP/2.
+static CUSTOM_ENDPOINT_CLIENT: Lazy<Arc<Client>> = Lazy::new(|| {
+    Arc::new(
+        Client::builder()
+            .pool_max_idle_per_host(POOL_MAX_IDLE_PER_HOST)
+            .tcp_keepalive(Duration::from_secs(60))
+            .build()
+            .expect("Failed to build Grok HTTP client"),
+    )
+});
+
+/// Grok API failures, carried inside `anyhow::Error` (recover with `downcast_ref`)
+#[derive(Debug, thiserror::Error)]
+pub enum GrokApiError {
+    /// Non-success HTTP status other than 429, with the response body
+    #[error("Grok API error ({0}): {1}")]
+    ApiError(u16, String),
+    /// Response body that isn't a valid chat completion
+    #[error("Failed to parse Grok API response: {0}")]
+    ParseError(String),
+    #[error("Grok API request timed out")]
+    Timeout,
+    #[error("Grok API rate limited, retry after {retry_after_secs}s")]
+    RateLimited { retry_after_secs: u64 },
+}
+
+impl GrokApiError {
+    /// Delay before retry number `attempt` (1-based), or `None` if the error isn't retryable
+    ///
+    /// Rate limits and overload (529) back off exponentially from `base_delay`,
+    /// never waiting less than the server's Retry-After. Server errors and
+    /// timeouts back off linearly.
+    pub fn retry_delay(&self, attempt: u32, base_delay: Duration) -> Option<Duration> {
+        let exponential = base_delay.saturating_mul(2_u32.saturating_pow(attempt.saturating_sub(1)));
+        match self {
+            GrokApiError::RateLimited { retry_after_secs } => {
+                Some(exponential.max(Duration::from_secs(*retry_after_secs)))
+            }
+            GrokApiError::ApiError(529, _) => Some(exponential),
+            GrokApiError::ApiError(status, _) if *status >= 500 => Some(base_delay.saturating_mul(attempt)),
+            GrokApiError::Timeout => Some(base_delay.saturating_mul(attempt)),
+            GrokApiError::ApiError(..) | GrokApiError::ParseError(_) => None,
+        }
+    }
+}
 
 /// Request body structure for Grok Chat Completions API
 /// OpenAI-compatible format
@@ -32,6 +129,9 @@ pub struct GrokRequest {
     /// Maximum tokens in response (optional)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_tokens: Option<i32>,
+    /// Structured output format, e.g. a JSON schema the reply must follow (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<serde_json::Value>,
 }
 
 /// Message in conversation (OpenAI-compatible)
@@ -76,7 +176,7 @@ pub struct Choice {
 }
 
 /// Token usage and cache metrics
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
 pub struct UsageMetrics {
     /// Input tokens used
     pub prompt_tokens: i32,
@@ -110,6 +210,74 @@ pub struct AnalysisResult {
     pub usage: UsageMetrics,
 }
 
+/// A fix returned by `generate_fix`, parsed from Grok's structured output
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GeneratedFix {
+    /// Replacement for the original code, without Markdown fences
+    pub fixed_code: String,
+    /// Why the change resolves the violation (stored as `Fix.explanation`)
+    pub explanation: String,
+    /// One-line summary of what changed (stored as `Fix.changes_summary`)
+    pub changes_summary: String,
+    /// How to check the fix didn't break anything (stored as `Fix.test_recommendation`)
+    pub test_recommendation: String,
+    /// Tokens the request used (not part of the model's reply)
+    #[serde(skip)]
+    pub usage: UsageMetrics,
+}
+
+impl GeneratedFix {
+    /// `response_format` asking Grok to answer with a `GeneratedFix` JSON object
+    pub fn response_format() -> serde_json::Value {
+        serde_json::json!({
+            "type": "json_schema",
+            "json_schema": {
+                "name": "generated_fix",
+                "strict": true,
+                "schema": {
+                    "type": "object",
+                    "properties": {
+                        "fixed_code": {"type": "string"},
+                        "explanation": {"type": "string"},
+                        "changes_summary": {"type": "string"},
+                        "test_recommendation": {"type": "string"}
+                    },
+                    "required": ["fixed_code", "explanation", "changes_summary", "test_recommendation"],
+                    "additionalProperties": false
+                }
+            }
+        })
+    }
+
+    /// Parse the message content of a structured fix response
+    ///
+    /// Tolerates Markdown fences around the JSON. Fails when the content isn't
+    /// a JSON object with all four fields.
+    pub fn parse(content: &str) -> Result<Self> {
+        let cleaned = content
+            .trim()
+            .trim_start_matches("```json")
+            .trim_start_matches("```")
+            .trim_end_matches("```")
+            .trim();
+
+        serde_json::from_str(cleaned)
+            .map_err(|e| anyhow!(GrokApiError::ParseError(content.to_string())).context(format!("Invalid structured fix: {}", e)))
+    }
+}
+
+/// Longest false positive snippet or reason quoted in an analysis prompt
+const PROMPT_EXCERPT_CHARS: usize = 200;
+
+/// Collapse `text` to one line, truncated to `PROMPT_EXCERPT_CHARS`
+fn prompt_excerpt(text: &str) -> String {
+    let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    match line.char_indices().nth(PROMPT_EXCERPT_CHARS) {
+        Some((end, _)) => format!("{}...", &line[..end]),
+        None => line,
+    }
+}
+
 /// Violation detection from LLM (JSON deserialization)
 #[derive(Debug, Clone, Deserialize)]
 struct ViolationDetection {
@@ -127,27 +295,32 @@ struct ViolationDetection {
 pub struct GrokClient {
     /// API key for authentication
     api_key: String,
-    /// HTTP client (reused across requests)
-    http_client: Client,
+    /// Shared HTTP client (reused across requests and clients)
+    http_client: &'static Client,
     /// API base URL
     api_base: String,
+    /// Retries after the first attempt on retryable errors
+    max_retries: u32,
+    /// First backoff delay between retries
+    retry_base_delay: Duration,
 }
 
 impl GrokClient {
-    /// Create new Grok client from XAI_API_KEY environment variable
+    /// Create new Grok client with the key from the OS keychain or XAI_API_KEY
     ///
     /// # Errors
-    /// Returns error if XAI_API_KEY is not set
+    /// Returns error if no key is set
     pub fn new() -> Result<Self> {
-        let api_key = env::var("XAI_API_KEY")
-            .context("XAI_API_KEY environment variable not set")?;
+        let api_key = credentials::get_api_key(CredentialProvider::Xai)?;
 
         Self::validate_api_key(&api_key)?;
 
         Ok(Self {
             api_key,
-            http_client: Client::new(),
-            api_base: "https://api.x.ai/v1".to_string(),
+            http_client: Self::client(),
+            api_base: DEFAULT_API_BASE.to_string(),
+            max_retries: DEFAULT_MAX_RETRIES,
+            retry_base_delay: DEFAULT_RETRY_BASE_DELAY,
         })
     }
 
@@ -157,8 +330,10 @@ impl GrokClient {
 
         Ok(Self {
             api_key,
-            http_client: Client::new(),
-            api_base: "https://api.x.ai/v1".to_string(),
+            http_client: Self::client(),
+            api_base: DEFAULT_API_BASE.to_string(),
+            max_retries: DEFAULT_MAX_RETRIES,
+            retry_base_delay: DEFAULT_RETRY_BASE_DELAY,
         })
     }
 
@@ -168,11 +343,61 @@ impl GrokClient {
 
         Ok(Self {
             api_key,
-            http_client: Client::new(),
+            http_client: &CUSTOM_ENDPOINT_CLIENT,
             api_base,
+            max_retries: DEFAULT_MAX_RETRIES,
+            retry_base_delay: DEFAULT_RETRY_BASE_DELAY,
         })
     }
 
+    /// HTTP/2 client shared by all clients using the X.AI API
+    pub fn client() -> &'static Client {
+        &HTTP_CLIENT
+    }
+
+    /// Check that the API is reachable by listing models (no tokens are used)
+    ///
+    /// # Returns
+    /// `true` if the API accepted the request, `false` if it answered with an
+    /// error status (e.g. 401 for a revoked key)
+    ///
+    /// # Errors
+    /// Returns error if the API could not be reached
+    pub async fn health_check(&self) -> Result<bool> {
+        let response = self
+            .http_client
+            .get(format!("{}/models", self.api_base))
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .timeout(Duration::from_secs(HEALTH_CHECK_TIMEOUT_SECS))
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_timeout() {
+                    anyhow!(GrokApiError::Timeout)
+                } else {
+                    anyhow!(e).context("Failed to reach Grok API")
+                }
+            })?;
+
+        Ok(response.status().is_success())
+    }
+
+    /// Send a one-word test prompt to check that the API key is accepted
+    ///
+    /// Unlike `health_check`, this runs a chat completion, so it also fails
+    /// for keys without model access. Not retried.
+    pub async fn send_test_prompt(&self) -> Result<()> {
+        self.call_api("Reply with the single word OK.", None, None).await?;
+        Ok(())
+    }
+
+    /// Override how retryable errors (rate limits, 5xx, timeouts) are retried
+    pub fn with_retries(mut self, max_retries: u32, base_delay: Duration) -> Self {
+        self.max_retries = max_retries;
+        self.retry_base_delay = base_delay;
+        self
+    }
+
     /// Validate API key format
     fn validate_api_key(key: &str) -> Result<()> {
         if key.is_empty() {
@@ -196,9 +421,11 @@ impl GrokClient {
     /// * `framework` - Framework type (e.g., "django", "express")
     /// * `function_name` - Function name where violation was found (from tree-sitter)
     /// * `class_name` - Class name where violation was found (from tree-sitter)
+    /// * `previous_attempts` - Fixed code of earlier fixes for this violation, oldest first
     ///
     /// # Returns
-    /// Fixed code as a string
+    /// The fixed code with an explanation, a summary of the changes and a test
+    /// recommendation, requested through Grok's structured output mode
     pub async fn generate_fix(
         &self,
         violation_control_id: &str,
@@ -207,27 +434,21 @@ impl GrokClient {
         framework: &str,
         function_name: Option<&str>,
         class_name: Option<&str>,
-    ) -> Result<String> {
-        let system_prompt = "You are a security-focused code fixer for SOC 2 compliance. \
-                            Your task is to fix compliance violations in code without breaking functionality. \
-                            Always follow the framework's best practices.";
+        previous_attempts: &[String],
+    ) -> Result<GeneratedFix> {
+        let system_prompt = Self::build_fix_system_prompt();
 
-        let user_prompt = self.build_fix_prompt(
+        let user_prompt = Self::build_fix_prompt(
             violation_control_id,
             violation_description,
             original_code,
             framework,
             function_name,
             class_name,
+            previous_attempts,
         );
 
-        let response = self.call_api(&user_prompt, Some(system_prompt)).await?;
-
-        Ok(response
-            .choices
-            .first()
-            .map(|choice| choice.message.content.clone())
-            .unwrap_or_default())
+        self.request_fix(&user_prompt, &system_prompt).await
     }
 
     /// Generate fix with custom system context
@@ -242,34 +463,58 @@ impl GrokClient {
         function_name: Option<&str>,
         class_name: Option<&str>,
         system_context: &str,
-    ) -> Result<String> {
-        let user_prompt = self.build_fix_prompt(
+    ) -> Result<GeneratedFix> {
+        let user_prompt = Self::build_fix_prompt(
             violation_control_id,
             violation_description,
             original_code,
             framework,
             function_name,
             class_name,
+            &[],
         );
 
-        let response = self.call_api(&user_prompt, Some(system_context)).await?;
+        let system_prompt = format!("{}\n\n{}", system_context, FIX_RESPONSE_INSTRUCTIONS);
+        self.request_fix(&user_prompt, &system_prompt).await
+    }
 
-        Ok(response
-            .choices
-            .first()
-            .map(|choice| choice.message.content.clone())
-            .unwrap_or_default())
+    /// Ask for a fix in structured output mode and parse the reply
+    async fn request_fix(&self, user_prompt: &str, system_prompt: &str) -> Result<GeneratedFix> {
+        let response_format = GeneratedFix::response_format();
+        let response = self
+            .call_api_with_retry(user_prompt, Some(system_prompt), Some(&response_format), self.max_retries)
+            .await?;
+
+        let mut fix = GeneratedFix::parse(
+            response
+                .choices
+                .first()
+                .map(|choice| choice.message.content.as_str())
+                .unwrap_or_default(),
+        )?;
+        fix.usage = response.usage;
+        Ok(fix)
+    }
+
+    /// System prompt for fix generation, including the structured reply instructions
+    pub(crate) fn build_fix_system_prompt() -> String {
+        format!(
+            "You are a security-focused code fixer for SOC 2 compliance. \
+             Your task is to fix compliance violations in code without breaking functionality. \
+             Always follow the framework's best practices.\n\n{}",
+            FIX_RESPONSE_INSTRUCTIONS
+        )
     }
 
     /// Build fix prompt based on SOC 2 control
-    fn build_fix_prompt(
-        &self,
+    pub(crate) fn build_fix_prompt(
         control_id: &str,
         description: &str,
         code: &str,
         framework: &str,
         function_name: Option<&str>,
         class_name: Option<&str>,
+        previous_attempts: &[String],
     ) -> String {
         let mut context_section = String::new();
         if function_name.is_some() || class_name.is_some() {
@@ -280,39 +525,42 @@ impl GrokClient {
             if let Some(cls) = class_name {
                 context_section.push_str(&format!("- Class: {}\n", cls));
             }
-            context_section.push_str("\n");
+            context_section.push('\n');
+        }
+        if !previous_attempts.is_empty() {
+            context_section.push_str("Previous attempts that didn't resolve the issue:\n");
+            for (i, attempt) in previous_attempts.iter().enumerate() {
+                context_section.push_str(&format!("Attempt {}:\n```\n{}\n```\n", i + 1, attempt));
+            }
+            context_section.push('\n');
         }
 
         match control_id {
             "CC6.1" => format!(
                 "Fix the following access control violation in {} code:\n\n\
                  Violation: {}\n\n{}\
-                 Original code:\n```\n{}\n```\n\n\
-                 Provide the fixed code only, no explanation.",
+                 Original code:\n```\n{}\n```",
                 framework, description, context_section, code
             ),
             "CC6.7" => format!(
                 "Fix the following secrets/cryptography violation in {} code:\n\n\
                  Violation: {}\n\n{}\
                  Original code:\n```\n{}\n```\n\n\
-                 Move hardcoded secrets to environment variables. \
-                 Provide the fixed code only, no explanation.",
+                 Move hardcoded secrets to environment variables.",
                 framework, description, context_section, code
             ),
             "CC7.2" => format!(
                 "Fix the following logging violation in {} code:\n\n\
                  Violation: {}\n\n{}\
                  Original code:\n```\n{}\n```\n\n\
-                 Add proper audit logging without logging sensitive data. \
-                 Provide the fixed code only, no explanation.",
+                 Add proper audit logging without logging sensitive data.",
                 framework, description, context_section, code
             ),
             "A1.2" => format!(
                 "Fix the following resilience violation in {} code:\n\n\
                  Violation: {}\n\n{}\
                  Original code:\n```\n{}\n```\n\n\
-                 Add error handling, timeouts, and retry logic. \
-                 Provide the fixed code only, no explanation.",
+                 Add error handling, timeouts, and retry logic.",
                 framework, description, context_section, code
             ),
             _ => format!(
@@ -325,7 +573,12 @@ impl GrokClient {
     }
 
     /// Call Grok Chat Completions API
-    async fn call_api(&self, prompt: &str, system: Option<&str>) -> Result<GrokResponse> {
+    async fn call_api(
+        &self,
+        prompt: &str,
+        system: Option<&str>,
+        response_format: Option<&serde_json::Value>,
+    ) -> Result<GrokResponse> {
         let mut messages = Vec::new();
 
         if let Some(sys) = system {
@@ -346,6 +599,7 @@ impl GrokClient {
             stream: Some(false),
             temperature: Some(0.0),
             max_tokens: Some(4096),
+            response_format: response_format.cloned(),
         };
 
         let response = self
@@ -353,45 +607,64 @@ impl GrokClient {
             .post(format!("{}/chat/completions", self.api_base))
             .header("Authorization", format!("Bearer {}", self.api_key))
             .header("Content-Type", "application/json")
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
             .json(&request)
             .send()
             .await
-            .context("Failed to send request to Grok API")?;
+            .map_err(|e| {
+                if e.is_timeout() {
+                    anyhow!(GrokApiError::Timeout)
+                } else {
+                    anyhow!(e).context("Failed to send request to Grok API")
+                }
+            })?;
 
         let status = response.status();
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after_secs = response
+                .headers()
+                .get("Retry-After")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .unwrap_or(DEFAULT_RETRY_AFTER_SECS);
+            return Err(GrokApiError::RateLimited { retry_after_secs }.into());
+        }
+
         let response_text = response
             .text()
             .await
             .context("Failed to read response body")?;
 
         if !status.is_success() {
-            return Err(anyhow!(
-                "Grok API error ({}): {}",
-                status,
-                response_text
-            ));
+            return Err(GrokApiError::ApiError(status.as_u16(), response_text).into());
         }
 
         let grok_response: GrokResponse = serde_json::from_str(&response_text)
-            .context("Failed to parse Grok API response")?;
+            .map_err(|_| GrokApiError::ParseError(response_text))?;
 
         Ok(grok_response)
     }
 
+    /// Analyze a file for SOC 2 violations the regex rules missed
+    ///
+    /// `false_positives` are code the project's reviewers reported as false
+    /// positives; they're listed in the prompt so the model doesn't flag
+    /// similar code again.
     pub async fn analyze_for_violations(
         &self,
         scan_id: i64,
         file_path: &str,
         code: &str,
         regex_findings: Vec<Violation>,
+        false_positives: &[FalsePositive],
     ) -> Result<AnalysisResult> {
         let system_prompt = Self::build_soc2_system_prompt();
-        let user_prompt = Self::build_analysis_prompt(file_path, code, &regex_findings);
+        let user_prompt = Self::build_analysis_prompt(file_path, code, &regex_findings, false_positives);
 
-        let response = self.call_api_with_retry(&user_prompt, Some(&system_prompt), 3).await?;
+        let response = self.call_api_with_retry(&user_prompt, Some(&system_prompt), None, self.max_retries).await?;
 
         let violations = Self::parse_violations_response(
-            &response.choices.first().map(|c| c.message.content.as_str()).unwrap_or(""),
+            response.choices.first().map(|c| c.message.content.as_str()).unwrap_or(""),
             scan_id,
             file_path,
         )?;
@@ -402,8 +675,8 @@ impl GrokClient {
         })
     }
 
-    fn build_soc2_system_prompt() -> String {
-        let controls = Control::all_controls();
+    pub(crate) fn build_soc2_system_prompt() -> String {
+        let controls = Control::code_controls();
 
         let mut prompt = String::from(
             "You are a SOC 2 compliance expert analyzing application code for security violations.\n\n\
@@ -448,7 +721,12 @@ impl GrokClient {
         prompt
     }
 
-    fn build_analysis_prompt(file_path: &str, code: &str, regex_findings: &[Violation]) -> String {
+    pub(crate) fn build_analysis_prompt(
+        file_path: &str,
+        code: &str,
+        regex_findings: &[Violation],
+        false_positives: &[FalsePositive],
+    ) -> String {
         let mut prompt = format!(
             "Analyze this file for SOC 2 compliance violations:\n\n\
             **File**: {}\n\n\
@@ -467,6 +745,19 @@ impl GrokClient {
             prompt.push_str("\nFocus on finding violations that regex patterns missed.\n\n");
         }
 
+        if !false_positives.is_empty() {
+            prompt.push_str("**Known False Positives** (reviewers confirmed these are not violations; do not report similar code):\n");
+            for report in false_positives {
+                prompt.push_str(&format!(
+                    "- {}: `{}` ({})\n",
+                    report.control_id,
+                    prompt_excerpt(&report.code_snippet),
+                    prompt_excerpt(&report.reason)
+                ));
+            }
+            prompt.push('\n');
+        }
+
         prompt.push_str(
             "Respond with JSON array of violations (or [] if none found). \
             Consider semantic issues, not just keyword matching."
@@ -475,7 +766,7 @@ impl GrokClient {
         prompt
     }
 
-    fn parse_violations_response(
+    pub(crate) fn parse_violations_response(
         response_text: &str,
         scan_id: i64,
         file_path: &str,
@@ -535,6 +826,15 @@ impl GrokClient {
                     regex_reasoning: None,
                     function_name: None,
                     class_name: None,
+                    decorator_name: None,
+                    is_new: false,
+                    last_status_change_reason: None,
+                    package_name: None,
+                    fingerprint: String::new(),
+                    lifecycle: "new".to_string(),
+                    jira_issue_key: None,
+                    first_seen_scan_id: None,
+                    last_seen_scan_id: None,
                 }
             })
             .collect();
@@ -542,34 +842,37 @@ impl GrokClient {
         Ok(violations)
     }
 
+    /// Call the API, retrying errors that `GrokApiError::retry_delay` allows
+    ///
+    /// Gives up after `max_retries` retries, returning the last error.
     async fn call_api_with_retry(
         &self,
         prompt: &str,
         system: Option<&str>,
+        response_format: Option<&serde_json::Value>,
         max_retries: u32,
     ) -> Result<GrokResponse> {
         let mut attempt = 0;
 
         loop {
-            match self.call_api(prompt, system).await {
+            match self.call_api(prompt, system, response_format).await {
                 Ok(response) => return Ok(response),
                 Err(e) => {
                     attempt += 1;
 
-                    let error_msg = e.to_string();
-                    let is_retryable = error_msg.contains("429") || error_msg.contains("500") || error_msg.contains("529") || error_msg.contains("network") || error_msg.contains("timeout");
+                    let delay = e
+                        .downcast_ref::<GrokApiError>()
+                        .and_then(|api_error| api_error.retry_delay(attempt, self.retry_base_delay));
 
-                    if !is_retryable || attempt >= max_retries {
+                    let Some(delay) = delay.filter(|_| attempt <= max_retries) else {
                         return Err(e);
-                    }
-
-                    let delay_ms = if error_msg.contains("429") || error_msg.contains("529") {
-                        1000 * (2_u64.pow(attempt))
-                    } else {
-                        2000 * attempt as u64
                     };
 
-                    tokio::time::sleep(tokio::time::Duration::from_millis(delay_ms)).await;
+                    eprintln!(
+                        "[ryn] Grok API call failed ({}), retrying in {:?} (attempt {}/{})",
+                        e, delay, attempt, max_retries
+                    );
+                    tokio::time::sleep(delay).await;
                 }
             }
         }
@@ -587,6 +890,9 @@ impl GrokClient {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
 
     #[test]
     fn test_validate_api_key_valid() {
@@ -639,4 +945,474 @@ mod tests {
             "https://api.x.ai/v1/chat/completions"
         );
     }
+
+    // ===== Typed errors and retries =====
+
+    const TEST_KEY: &str = "xai-1234567890123456789";
+
+    fn completion_body(content: &str) -> String {
+        serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 0,
+            "model": "grok-code-fast-1",
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": content},
+                "finish_reason": "stop"
+            }],
+            "usage": {"prompt_tokens": 10, "completion_tokens": 5, "total_tokens": 15}
+        })
+        .to_string()
+    }
+
+    /// Minimal HTTP server answering with `responses` (status, extra headers, body)
+    /// in order, then 500; returns the API base URL and a request counter
+    async fn mock_api(responses: Vec<(u16, &'static str, String)>) -> (String, Arc<Mutex<usize>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/v1", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(0));
+        let requests_clone = requests.clone();
+
+        tokio::spawn(async move {
+            let mut responses = responses.into_iter();
+            loop {
+                let (mut socket, _) = match listener.accept().await {
+                    Ok(conn) => conn,
+                    Err(_) => return,
+                };
+
+                // Read headers and the JSON body
+                let mut raw = Vec::new();
+                let mut buf = [0u8; 4096];
+                loop {
+                    let n = socket.read(&mut buf).await.unwrap();
+                    if n == 0 {
+                        break;
+                    }
+                    raw.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&raw);
+                    if let Some(split) = text.find("\r\n\r\n") {
+                        let content_length = text[..split]
+                            .lines()
+                            .find_map(|l| {
+                                l.to_lowercase()
+                                    .strip_prefix("content-length:")
+                                    .map(|v| v.trim().parse::<usize>().unwrap())
+                            })
+                            .unwrap_or(0);
+                        if raw.len() >= split + 4 + content_length {
+                            break;
+                        }
+                    }
+                }
+                *requests_clone.lock().unwrap() += 1;
+
+                let (status, headers, body) = responses
+                    .next()
+                    .unwrap_or((500, "", "server error".to_string()));
+                let response = format!(
+                    "HTTP/1.1 {} Status\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    headers,
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        (url, requests)
+    }
+
+    fn test_client(api_base: String, max_retries: u32) -> GrokClient {
+        GrokClient::with_url(TEST_KEY.to_string(), api_base)
+            .unwrap()
+            .with_retries(max_retries, Duration::from_millis(10))
+    }
+
+    fn api_error<T: std::fmt::Debug>(result: Result<T>) -> GrokApiError {
+        let error = result.unwrap_err();
+        match error.downcast::<GrokApiError>() {
+            Ok(api_error) => api_error,
+            Err(other) => panic!("expected GrokApiError, got {}", other),
+        }
+    }
+
+    #[test]
+    fn test_grok_api_error_display() {
+        assert_eq!(
+            GrokApiError::ApiError(401, "bad key".to_string()).to_string(),
+            "Grok API error (401): bad key"
+        );
+        assert_eq!(
+            GrokApiError::RateLimited { retry_after_secs: 7 }.to_string(),
+            "Grok API rate limited, retry after 7s"
+        );
+    }
+
+    #[test]
+    fn test_rate_limited_backs_off_exponentially_and_honours_retry_after() {
+        let base = Duration::from_secs(1);
+        let limited = GrokApiError::RateLimited { retry_after_secs: 0 };
+
+        assert_eq!(limited.retry_delay(1, base), Some(Duration::from_secs(1)));
+        assert_eq!(limited.retry_delay(2, base), Some(Duration::from_secs(2)));
+        assert_eq!(limited.retry_delay(3, base), Some(Duration::from_secs(4)));
+
+        let slow_down = GrokApiError::RateLimited { retry_after_secs: 30 };
+        assert_eq!(slow_down.retry_delay(1, base), Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn test_retry_delay_only_for_retryable_errors() {
+        let base = Duration::from_secs(2);
+
+        assert_eq!(GrokApiError::ApiError(503, String::new()).retry_delay(2, base), Some(Duration::from_secs(4)));
+        assert_eq!(GrokApiError::ApiError(529, String::new()).retry_delay(3, base), Some(Duration::from_secs(8)));
+        assert_eq!(GrokApiError::Timeout.retry_delay(1, base), Some(Duration::from_secs(2)));
+
+        assert_eq!(GrokApiError::ApiError(400, String::new()).retry_delay(1, base), None);
+        assert_eq!(GrokApiError::ParseError("{}".to_string()).retry_delay(1, base), None);
+    }
+
+    #[test]
+    fn test_analysis_prompt_lists_false_positives() {
+        let report = FalsePositive {
+            id: 1,
+            project_id: 1,
+            violation_id: Some(3),
+            control_id: "CC6.7".to_string(),
+            file_path: "tests/fixtures.py".to_string(),
+            code_snippet: "API_KEY = \"test-key\"\n  # fixture".to_string(),
+            detection_method: "llm".to_string(),
+            reason: "Dummy key used only in tests".to_string(),
+            reported_at: String::new(),
+        };
+
+        let prompt = GrokClient::build_analysis_prompt("app.py", "x = 1", &[], &[report]);
+        assert!(prompt.contains("**Known False Positives**"));
+        assert!(prompt.contains("- CC6.7: `API_KEY = \"test-key\" # fixture` (Dummy key used only in tests)"));
+
+        let prompt = GrokClient::build_analysis_prompt("app.py", "x = 1", &[], &[]);
+        assert!(!prompt.contains("Known False Positives"));
+        assert_eq!(prompt_excerpt(&"a".repeat(300)).len(), PROMPT_EXCERPT_CHARS + 3);
+    }
+
+    #[test]
+    fn test_fix_prompt_includes_previous_attempts() {
+        let first = GrokClient::build_fix_prompt("CC6.7", "Hardcoded secret", "KEY = 'x'", "django", None, None, &[]);
+        assert!(!first.contains("Previous attempts"));
+
+        let attempts = vec!["KEY = 'y'".to_string(), "KEY = get('KEY', 'x')".to_string()];
+        let retry = GrokClient::build_fix_prompt("CC6.7", "Hardcoded secret", "KEY = 'x'", "django", None, None, &attempts);
+        assert!(retry.contains("Previous attempts that didn't resolve the issue:"));
+        assert!(retry.contains("Attempt 1:\n```\nKEY = 'y'\n```"));
+        assert!(retry.contains("Attempt 2:\n```\nKEY = get('KEY', 'x')\n```"));
+        // Attempts come before the code to fix
+        assert!(retry.find("Attempt 2").unwrap() < retry.find("Original code").unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_returns_typed_error_with_retry_after() {
+        let (url, requests) = mock_api(vec![(429, "Retry-After: 12\r\n", String::new())]).await;
+        let client = test_client(url, 0);
+
+        let result = client.generate_fix("CC6.7", "Hardcoded secret", "KEY = 'x'", "django", None, None, &[]).await;
+
+        assert!(matches!(api_error(result), GrokApiError::RateLimited { retry_after_secs: 12 }));
+        assert_eq!(*requests.lock().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_api_error_carries_status_and_body() {
+        let (url, requests) = mock_api(vec![(401, "", "invalid api key".to_string())]).await;
+        let client = test_client(url, 3);
+
+        let result = client.generate_fix("CC6.7", "Hardcoded secret", "KEY = 'x'", "django", None, None, &[]).await;
+
+        match api_error(result) {
+            GrokApiError::ApiError(status, body) => {
+                assert_eq!(status, 401);
+                assert_eq!(body, "invalid api key");
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+        // Client errors aren't retried
+        assert_eq!(*requests.lock().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_unparseable_response_returns_raw_body() {
+        let (url, _) = mock_api(vec![(200, "", "not a completion".to_string())]).await;
+        let client = test_client(url, 3);
+
+        let result = client.generate_fix("CC6.7", "Hardcoded secret", "KEY = 'x'", "django", None, None, &[]).await;
+
+        match api_error(result) {
+            GrokApiError::ParseError(raw) => assert_eq!(raw, "not a completion"),
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generate_fix_retries_after_rate_limit() {
+        let (url, requests) = mock_api(vec![
+            (429, "Retry-After: 0\r\n", String::new()),
+            (503, "", "overloaded".to_string()),
+            (200, "", completion_body(&fix_json("KEY = os.environ['KEY']"))),
+        ])
+        .await;
+        let client = test_client(url, 3);
+
+        let fixed = client
+            .generate_fix("CC6.7", "Hardcoded secret", "KEY = 'x'", "django", None, None, &[])
+            .await
+            .unwrap();
+
+        assert_eq!(fixed.fixed_code, "KEY = os.environ['KEY']");
+        assert_eq!(*requests.lock().unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_generate_fix_gives_up_after_max_retries() {
+        let rate_limited = || (429, "Retry-After: 0\r\n", String::new());
+        let (url, requests) = mock_api(vec![rate_limited(), rate_limited(), rate_limited()]).await;
+        let client = test_client(url, 2);
+
+        let result = client.generate_fix("CC6.7", "Hardcoded secret", "KEY = 'x'", "django", None, None, &[]).await;
+
+        assert!(matches!(api_error(result), GrokApiError::RateLimited { .. }));
+        assert_eq!(*requests.lock().unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_analysis_backs_off_until_max_retries_then_returns_rate_limit() {
+        let rate_limited = || (429, "Retry-After: 0\r\n", String::new());
+        let (url, requests) = mock_api(vec![rate_limited(), rate_limited(), rate_limited(), rate_limited()]).await;
+        let client = test_client(url, 2);
+
+        let start = std::time::Instant::now();
+        let result = client.analyze_for_violations(1, "app.py", "KEY = 'x'", Vec::new(), &[]).await;
+
+        assert!(matches!(api_error(result), GrokApiError::RateLimited { retry_after_secs: 0 }));
+        // The first attempt plus max_retries, never the fourth response
+        assert_eq!(*requests.lock().unwrap(), 3);
+        // Exponential backoff from the 10ms base: 10ms, then 20ms
+        assert!(start.elapsed() >= Duration::from_millis(30));
+    }
+
+    // ===== Structured fix output =====
+
+    fn fix_json(fixed_code: &str) -> String {
+        serde_json::json!({
+            "fixed_code": fixed_code,
+            "explanation": "The key is read from the environment instead of the source.",
+            "changes_summary": "Replaced hardcoded KEY with os.environ lookup",
+            "test_recommendation": "Run the app with KEY unset and check it fails fast"
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_generated_fix_parses_structured_output() {
+        let fix = GeneratedFix::parse(&fix_json("KEY = os.environ['KEY']")).unwrap();
+        assert_eq!(fix.fixed_code, "KEY = os.environ['KEY']");
+        assert_eq!(fix.explanation, "The key is read from the environment instead of the source.");
+        assert_eq!(fix.changes_summary, "Replaced hardcoded KEY with os.environ lookup");
+        assert_eq!(fix.test_recommendation, "Run the app with KEY unset and check it fails fast");
+    }
+
+    #[test]
+    fn test_generated_fix_parse_strips_markdown_fences() {
+        let content = format!("```json\n{}\n```", fix_json("KEY = os.environ['KEY']"));
+        assert_eq!(GeneratedFix::parse(&content).unwrap().fixed_code, "KEY = os.environ['KEY']");
+    }
+
+    #[test]
+    fn test_generated_fix_parse_rejects_prose_and_missing_fields() {
+        let prose = "Here is the fix:\n```python\nKEY = os.environ['KEY']\n```";
+        match api_error(GeneratedFix::parse(prose)) {
+            GrokApiError::ParseError(raw) => assert_eq!(raw, prose),
+            other => panic!("unexpected error: {:?}", other),
+        }
+
+        let partial = r#"{"fixed_code": "KEY = os.environ['KEY']", "explanation": "moved"}"#;
+        assert!(GeneratedFix::parse(partial).is_err());
+    }
+
+    #[test]
+    fn test_response_format_is_json_schema_for_generated_fix() {
+        let format = GeneratedFix::response_format();
+        assert_eq!(format["type"], "json_schema");
+        assert_eq!(format["json_schema"]["strict"], true);
+
+        let required: Vec<&str> = format["json_schema"]["schema"]["required"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|field| field.as_str().unwrap())
+            .collect();
+        assert_eq!(required, vec!["fixed_code", "explanation", "changes_summary", "test_recommendation"]);
+    }
+
+    #[test]
+    fn test_request_omits_response_format_unless_set() {
+        let mut request = GrokRequest {
+            model: GrokClient::model().to_string(),
+            messages: Vec::new(),
+            stream: None,
+            temperature: None,
+            max_tokens: None,
+            response_format: None,
+        };
+        assert!(serde_json::to_value(&request).unwrap().get("response_format").is_none());
+
+        request.response_format = Some(GeneratedFix::response_format());
+        let body = serde_json::to_value(&request).unwrap();
+        assert_eq!(body["response_format"]["json_schema"]["name"], "generated_fix");
+    }
+
+    #[tokio::test]
+    async fn test_generate_fix_returns_structured_fix() {
+        let (url, _) = mock_api(vec![(200, "", completion_body(&fix_json("KEY = os.environ['KEY']")))]).await;
+        let client = test_client(url, 0);
+
+        let fix = client
+            .generate_fix("CC6.7", "Hardcoded secret", "KEY = 'x'", "django", None, None, &[])
+            .await
+            .unwrap();
+
+        assert_eq!(fix.fixed_code, "KEY = os.environ['KEY']");
+        assert_eq!(fix.changes_summary, "Replaced hardcoded KEY with os.environ lookup");
+    }
+
+    #[tokio::test]
+    async fn test_generate_fix_with_prose_reply_is_parse_error() {
+        let (url, requests) = mock_api(vec![(200, "", completion_body("Sure! Use os.environ['KEY'] instead."))]).await;
+        let client = test_client(url, 3);
+
+        let result = client.generate_fix("CC6.7", "Hardcoded secret", "KEY = 'x'", "django", None, None, &[]).await;
+
+        assert!(matches!(api_error(result), GrokApiError::ParseError(_)));
+        // A malformed reply isn't retried
+        assert_eq!(*requests.lock().unwrap(), 1);
+    }
+
+    // ===== Shared HTTP client =====
+
+    #[test]
+    fn test_clients_share_one_http_client() {
+        let first = GrokClient::with_key(TEST_KEY.to_string()).unwrap();
+        let second = GrokClient::with_key(TEST_KEY.to_string()).unwrap();
+        assert!(std::ptr::eq(first.http_client, GrokClient::client()));
+        assert!(std::ptr::eq(first.http_client, second.http_client));
+    }
+
+    #[tokio::test]
+    async fn test_health_check_reports_api_status() {
+        let (url, requests) = mock_api(vec![
+            (200, "", r#"{"data":[]}"#.to_string()),
+            (401, "", "invalid api key".to_string()),
+        ])
+        .await;
+        let client = test_client(url, 0);
+
+        assert!(client.health_check().await.unwrap());
+        assert!(!client.health_check().await.unwrap());
+        assert_eq!(*requests.lock().unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_health_check_unreachable_api_is_error() {
+        // Bind then drop a listener so nothing accepts on the port
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/v1", listener.local_addr().unwrap());
+        drop(listener);
+
+        let result = test_client(url, 0).health_check().await;
+        assert!(result.is_err());
+    }
+
+    /// Runs `waves` rounds of `tasks` concurrent requests, like the batches of
+    /// LLM analyses in a scan, returning the time taken
+    async fn run_waves<F, Fut>(waves: usize, tasks: usize, request: F) -> Duration
+    where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = bool> + Send + 'static,
+    {
+        let start = std::time::Instant::now();
+        for _ in 0..waves {
+            let handles: Vec<_> = (0..tasks).map(|_| tokio::spawn(request())).collect();
+            for handle in handles {
+                assert!(handle.await.unwrap());
+            }
+        }
+        start.elapsed()
+    }
+
+    /// Compares a client built per task (the old behaviour) against the
+    /// shared client, on a local keep-alive server that charges
+    /// `CONNECT_COST` for every new connection in place of the TCP and TLS
+    /// handshakes:
+    /// `cargo test --release bench_shared_client -- --ignored --nocapture`
+    #[tokio::test(flavor = "multi_thread")]
+    #[ignore] // Timing-sensitive
+    async fn bench_shared_client_concurrent_analyses() {
+        const WAVES: usize = 5;
+        const TASKS: usize = 10;
+        const CONNECT_COST: Duration = Duration::from_millis(50);
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/v1", listener.local_addr().unwrap());
+        let connections = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let accepted = connections.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                accepted.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                tokio::spawn(async move {
+                    tokio::time::sleep(CONNECT_COST).await;
+                    // Bodyless GETs from health_check, one read each
+                    let mut buf = [0u8; 4096];
+                    while matches!(socket.read(&mut buf).await, Ok(n) if n > 0) {
+                        let response = "HTTP/1.1 200 OK\r\nContent-Length: 11\r\n\r\n{\"data\":[]}";
+                        if socket.write_all(response.as_bytes()).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+        let opened = || connections.swap(0, std::sync::atomic::Ordering::Relaxed);
+
+        let models_url = format!("{}/models", url);
+        let per_task_clients = run_waves(WAVES, TASKS, || {
+            let models_url = models_url.clone();
+            async move {
+                Client::new()
+                    .get(models_url)
+                    .header("Authorization", format!("Bearer {}", TEST_KEY))
+                    .send()
+                    .await
+                    .is_ok_and(|r| r.status().is_success())
+            }
+        })
+        .await;
+        let per_task_connections = opened();
+
+        let shared_client = run_waves(WAVES, TASKS, || {
+            let url = url.clone();
+            async move { test_client(url, 0).health_check().await.unwrap() }
+        })
+        .await;
+        let shared_connections = opened();
+
+        println!(
+            "{} waves of {} requests: per-task clients {:?} ({} connections), shared client {:?} ({} connections)",
+            WAVES, TASKS, per_task_clients, per_task_connections, shared_client, shared_connections
+        );
+        assert!(shared_connections <= TASKS, "The shared client should reuse pooled connections");
+        assert!(shared_client < per_task_clients);
+    }
 }
@@ -0,0 +1,224 @@
+//! LLM provider selection for scans and fix generation
+//!
+//! Grok (X.AI) is the default. Setting `llm_provider` to "ollama" sends
+//! violation analysis and fix generation to a local Ollama server instead
+//! (`ollama_host`, `ollama_model`), for teams that can't send source code to
+//! a cloud API. Local calls still report token counts but cost $0, so cost
+//! limits and budgets never stop an Ollama scan.
+
+use anyhow::{anyhow, Result};
+use rusqlite::Connection;
+use crate::db::queries;
+use crate::fix_generator::grok_client::{AnalysisResult, GeneratedFix, GrokClient, UsageMetrics};
+use crate::fix_generator::ollama_client::{OllamaClient, DEFAULT_OLLAMA_HOST, DEFAULT_OLLAMA_MODEL};
+use crate::models::{FalsePositive, Violation};
+
+/// Setting selecting the provider: "grok" (default) or "ollama"
+pub const LLM_PROVIDER_SETTING: &str = "llm_provider";
+
+/// Setting with the Ollama server address
+pub const OLLAMA_HOST_SETTING: &str = "ollama_host";
+
+/// Setting with the Ollama model name
+pub const OLLAMA_MODEL_SETTING: &str = "ollama_model";
+
+/// Which LLM to use, read from settings
+#[derive(Debug, Clone, PartialEq)]
+pub enum LlmProvider {
+    /// X.AI Grok API (needs XAI_API_KEY)
+    Grok,
+    /// Local Ollama server
+    Ollama { host: String, model: String },
+}
+
+impl LlmProvider {
+    /// Read the provider from settings, defaulting to Grok
+    ///
+    /// # Errors
+    /// Returns error if `llm_provider` names an unknown provider
+    pub fn from_settings(conn: &Connection) -> Result<Self> {
+        let setting = |key: &str| {
+            queries::select_setting(conn, key)
+                .ok()
+                .flatten()
+                .map(|s| s.value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+
+        match setting(LLM_PROVIDER_SETTING).as_deref().unwrap_or("grok") {
+            "grok" => Ok(LlmProvider::Grok),
+            "ollama" => Ok(LlmProvider::Ollama {
+                host: setting(OLLAMA_HOST_SETTING).unwrap_or_else(|| DEFAULT_OLLAMA_HOST.to_string()),
+                model: setting(OLLAMA_MODEL_SETTING).unwrap_or_else(|| DEFAULT_OLLAMA_MODEL.to_string()),
+            }),
+            other => Err(anyhow!("Unknown LLM provider '{}': expected 'grok' or 'ollama'", other)),
+        }
+    }
+
+    /// Short name for logs and errors ("grok" or "ollama")
+    pub fn name(&self) -> &'static str {
+        match self {
+            LlmProvider::Grok => "grok",
+            LlmProvider::Ollama { .. } => "ollama",
+        }
+    }
+
+    /// Whether calls are billed (false for local models)
+    pub fn is_billed(&self) -> bool {
+        matches!(self, LlmProvider::Grok)
+    }
+
+    /// Check the provider can be used before starting work with it
+    ///
+    /// Grok needs XAI_API_KEY; Ollama needs a valid host and model.
+    pub fn check_configured(&self) -> Result<()> {
+        self.client().map(|_| ())
+    }
+
+    /// Create a client for this provider
+    pub fn client(&self) -> Result<LlmClient> {
+        match self {
+            LlmProvider::Grok => Ok(LlmClient::Grok(GrokClient::new()?)),
+            LlmProvider::Ollama { host, model } => Ok(LlmClient::Ollama(OllamaClient::new(host, model)?)),
+        }
+    }
+}
+
+/// Client for whichever provider is configured
+pub enum LlmClient {
+    Grok(GrokClient),
+    Ollama(OllamaClient),
+}
+
+impl LlmClient {
+    /// Analyze a file for SOC 2 violations (see `GrokClient::analyze_for_violations`)
+    pub async fn analyze_for_violations(
+        &self,
+        scan_id: i64,
+        file_path: &str,
+        code: &str,
+        regex_findings: Vec<Violation>,
+        false_positives: &[FalsePositive],
+    ) -> Result<AnalysisResult> {
+        match self {
+            LlmClient::Grok(client) => {
+                client.analyze_for_violations(scan_id, file_path, code, regex_findings, false_positives).await
+            }
+            LlmClient::Ollama(client) => {
+                client.analyze_for_violations(scan_id, file_path, code, regex_findings, false_positives).await
+            }
+        }
+    }
+
+    /// Generate a fix for a violation (see `GrokClient::generate_fix`)
+    pub async fn generate_fix(
+        &self,
+        violation_control_id: &str,
+        violation_description: &str,
+        original_code: &str,
+        framework: &str,
+        function_name: Option<&str>,
+        class_name: Option<&str>,
+        previous_attempts: &[String],
+    ) -> Result<GeneratedFix> {
+        match self {
+            LlmClient::Grok(client) => {
+                client
+                    .generate_fix(
+                        violation_control_id,
+                        violation_description,
+                        original_code,
+                        framework,
+                        function_name,
+                        class_name,
+                        previous_attempts,
+                    )
+                    .await
+            }
+            LlmClient::Ollama(client) => {
+                client
+                    .generate_fix(
+                        violation_control_id,
+                        violation_description,
+                        original_code,
+                        framework,
+                        function_name,
+                        class_name,
+                        previous_attempts,
+                    )
+                    .await
+            }
+        }
+    }
+
+    /// Cost in USD of a call's token usage (always 0 for Ollama)
+    pub fn cost(&self, usage: &UsageMetrics) -> f64 {
+        match self {
+            LlmClient::Grok(_) => usage.calculate_cost(),
+            LlmClient::Ollama(_) => 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{self, test_helpers::TestDbGuard};
+
+    fn set(key: &str, value: &str) {
+        let conn = db::get_connection();
+        queries::insert_or_update_setting(&conn, key, value).unwrap();
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_provider_defaults_to_grok() {
+        let _guard = TestDbGuard::new();
+        let provider = LlmProvider::from_settings(&db::get_connection()).unwrap();
+        assert_eq!(provider, LlmProvider::Grok);
+        assert!(provider.is_billed());
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_provider_reads_ollama_settings() {
+        let _guard = TestDbGuard::new();
+        set(LLM_PROVIDER_SETTING, "ollama");
+        assert_eq!(
+            LlmProvider::from_settings(&db::get_connection()).unwrap(),
+            LlmProvider::Ollama {
+                host: DEFAULT_OLLAMA_HOST.to_string(),
+                model: DEFAULT_OLLAMA_MODEL.to_string(),
+            }
+        );
+
+        set(OLLAMA_HOST_SETTING, "http://gpu-box:11434");
+        set(OLLAMA_MODEL_SETTING, "llama3.1:70b");
+        let provider = LlmProvider::from_settings(&db::get_connection()).unwrap();
+        assert_eq!(provider.name(), "ollama");
+        assert!(!provider.is_billed());
+        assert!(provider.check_configured().is_ok());
+
+        let client = provider.client().unwrap();
+        let usage = UsageMetrics {
+            prompt_tokens: 1_000_000,
+            completion_tokens: 1_000_000,
+            total_tokens: 2_000_000,
+        };
+        assert_eq!(client.cost(&usage), 0.0);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_provider_rejects_unknown_and_bad_host() {
+        let _guard = TestDbGuard::new();
+        set(LLM_PROVIDER_SETTING, "openai");
+        let error = LlmProvider::from_settings(&db::get_connection()).unwrap_err();
+        assert!(error.to_string().contains("Unknown LLM provider 'openai'"));
+
+        set(LLM_PROVIDER_SETTING, "ollama");
+        set(OLLAMA_HOST_SETTING, "gpu-box:11434");
+        let provider = LlmProvider::from_settings(&db::get_connection()).unwrap();
+        assert!(provider.check_configured().is_err());
+    }
+}
@@ -1,12 +1,17 @@
 //! Fix generation module with AI integration
 //!
 //! Provides production-ready Grok Code Fast 1 client for generating SOC 2 compliance fixes
-//! and applying them to files on disk.
+//! and applying them to files on disk, plus a local Ollama client selected
+//! through the `llm_provider` setting.
 
 pub mod grok_client;
+pub mod ollama_client;
+pub mod llm_provider;
 pub mod fix_applicator;
 
 pub use grok_client::{
-    GrokClient, GrokRequest, GrokResponse, Message, UsageMetrics, AnalysisResult,
+    GrokClient, GrokRequest, GrokResponse, Message, UsageMetrics, AnalysisResult, GeneratedFix,
 };
-pub use fix_applicator::FixApplicator;
+pub use fix_applicator::{FixApplicator, FixPreview, LineRange};
+pub use ollama_client::OllamaClient;
+pub use llm_provider::{LlmClient, LlmProvider};
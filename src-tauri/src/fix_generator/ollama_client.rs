@@ -0,0 +1,359 @@
+//! Ollama client for running violation analysis and fix generation locally
+//!
+//! Sends the same prompts as `GrokClient` to a locally running Ollama server
+//! (https://ollama.com) through its native chat endpoint, so source code never
+//! leaves the machine. Local models are free: token counts are reported for
+//! analytics but cost nothing.
+//!
+//! API Specifications:
+//! - Endpoint: POST {host}/api/chat with `stream: false`
+//! - Authentication: none
+//! - Structured output: JSON schema in the request's `format` field
+
+use anyhow::{anyhow, Context, Result};
+use once_cell::sync::Lazy;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use crate::fix_generator::grok_client::{AnalysisResult, GeneratedFix, GrokClient, Message, UsageMetrics};
+use crate::models::{FalsePositive, Violation};
+
+/// Default Ollama server address
+pub const DEFAULT_OLLAMA_HOST: &str = "http://localhost:11434";
+
+/// Default model; any model pulled into the local Ollama works
+pub const DEFAULT_OLLAMA_MODEL: &str = "qwen2.5-coder:7b";
+
+/// Per-request timeout; local models on a laptop CPU are much slower than the API
+const REQUEST_TIMEOUT_SECS: u64 = 300;
+
+/// HTTP client shared by every `OllamaClient`
+static HTTP_CLIENT: Lazy<Client> = Lazy::new(|| {
+    Client::builder()
+        .tcp_keepalive(Duration::from_secs(60))
+        .build()
+        .expect("Failed to build Ollama HTTP client")
+});
+
+/// Request body for Ollama's `/api/chat`
+#[derive(Debug, Clone, Serialize)]
+struct OllamaRequest {
+    model: String,
+    messages: Vec<Message>,
+    stream: bool,
+    /// "json" or a JSON schema the reply must follow
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<serde_json::Value>,
+    options: OllamaOptions,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct OllamaOptions {
+    temperature: f32,
+}
+
+/// Response from Ollama's `/api/chat` (non-streaming)
+#[derive(Debug, Clone, Deserialize)]
+struct OllamaResponse {
+    message: Message,
+    /// Input tokens (absent when the prompt was fully cached)
+    #[serde(default)]
+    prompt_eval_count: i32,
+    /// Output tokens
+    #[serde(default)]
+    eval_count: i32,
+}
+
+/// Ollama API Client
+pub struct OllamaClient {
+    /// Server address, e.g. "http://localhost:11434"
+    host: String,
+    /// Model name, e.g. "qwen2.5-coder:7b"
+    model: String,
+}
+
+impl OllamaClient {
+    /// Create a client for the Ollama server at `host` using `model`
+    ///
+    /// # Errors
+    /// Returns error if `host` isn't an http(s) URL or `model` is empty
+    pub fn new(host: &str, model: &str) -> Result<Self> {
+        let host = host.trim().trim_end_matches('/');
+        if !host.starts_with("http://") && !host.starts_with("https://") {
+            return Err(anyhow!("Ollama host must be an http:// or https:// URL, got '{}'", host));
+        }
+        if model.trim().is_empty() {
+            return Err(anyhow!("Ollama model cannot be empty"));
+        }
+
+        Ok(Self {
+            host: host.to_string(),
+            model: model.trim().to_string(),
+        })
+    }
+
+    /// Model this client sends requests to
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// Chat endpoint URL
+    pub fn api_endpoint(&self) -> String {
+        format!("{}/api/chat", self.host)
+    }
+
+    /// Analyze a file for SOC 2 violations the regex rules missed
+    ///
+    /// Same prompt and reply format as `GrokClient::analyze_for_violations`.
+    pub async fn analyze_for_violations(
+        &self,
+        scan_id: i64,
+        file_path: &str,
+        code: &str,
+        regex_findings: Vec<Violation>,
+        false_positives: &[FalsePositive],
+    ) -> Result<AnalysisResult> {
+        let system_prompt = GrokClient::build_soc2_system_prompt();
+        let user_prompt = GrokClient::build_analysis_prompt(file_path, code, &regex_findings, false_positives);
+
+        let response = self.call_api(&user_prompt, &system_prompt, None).await?;
+        let violations = GrokClient::parse_violations_response(&response.message.content, scan_id, file_path)?;
+
+        Ok(AnalysisResult {
+            violations,
+            usage: Self::usage(&response),
+        })
+    }
+
+    /// Generate a fix for a SOC 2 compliance violation
+    ///
+    /// Same arguments and prompts as `GrokClient::generate_fix`; the reply is
+    /// constrained to the `GeneratedFix` JSON schema.
+    pub async fn generate_fix(
+        &self,
+        violation_control_id: &str,
+        violation_description: &str,
+        original_code: &str,
+        framework: &str,
+        function_name: Option<&str>,
+        class_name: Option<&str>,
+        previous_attempts: &[String],
+    ) -> Result<GeneratedFix> {
+        let system_prompt = GrokClient::build_fix_system_prompt();
+        let user_prompt = GrokClient::build_fix_prompt(
+            violation_control_id,
+            violation_description,
+            original_code,
+            framework,
+            function_name,
+            class_name,
+            previous_attempts,
+        );
+
+        // Ollama takes the bare schema rather than OpenAI's response_format wrapper
+        let schema = GeneratedFix::response_format()["json_schema"]["schema"].clone();
+        let response = self.call_api(&user_prompt, &system_prompt, Some(schema)).await?;
+        let mut fix = GeneratedFix::parse(&response.message.content)?;
+        fix.usage = Self::usage(&response);
+        Ok(fix)
+    }
+
+    /// Token counts for a response (cost is always zero for local models)
+    fn usage(response: &OllamaResponse) -> UsageMetrics {
+        UsageMetrics {
+            prompt_tokens: response.prompt_eval_count,
+            completion_tokens: response.eval_count,
+            total_tokens: response.prompt_eval_count + response.eval_count,
+        }
+    }
+
+    /// Call Ollama's chat endpoint
+    async fn call_api(&self, prompt: &str, system: &str, format: Option<serde_json::Value>) -> Result<OllamaResponse> {
+        let request = OllamaRequest {
+            model: self.model.clone(),
+            messages: vec![
+                Message {
+                    role: "system".to_string(),
+                    content: system.to_string(),
+                },
+                Message {
+                    role: "user".to_string(),
+                    content: prompt.to_string(),
+                },
+            ],
+            stream: false,
+            format,
+            options: OllamaOptions { temperature: 0.0 },
+        };
+
+        let response = HTTP_CLIENT
+            .post(self.api_endpoint())
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .json(&request)
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach Ollama at {} (is `ollama serve` running?)", self.host))?;
+
+        let status = response.status();
+        let response_text = response.text().await.context("Failed to read Ollama response body")?;
+
+        if !status.is_success() {
+            return Err(anyhow!("Ollama error ({}): {}", status.as_u16(), response_text));
+        }
+
+        serde_json::from_str(&response_text)
+            .with_context(|| format!("Failed to parse Ollama response: {}", response_text))
+    }
+}
+
+/// Mock Ollama server for tests here and in the commands that call Ollama
+#[cfg(test)]
+pub(crate) mod test_support {
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// `/api/chat` response body with `content` as the assistant message
+    pub(crate) fn chat_body(content: &str) -> String {
+        serde_json::json!({
+            "model": "qwen2.5-coder:7b",
+            "created_at": "2025-01-01T00:00:00Z",
+            "message": {"role": "assistant", "content": content},
+            "done": true,
+            "prompt_eval_count": 120,
+            "eval_count": 30
+        })
+        .to_string()
+    }
+
+    /// Minimal HTTP server answering every request with `status` and `body`;
+    /// returns the server address and the request bodies it received
+    pub(crate) async fn mock_ollama(status: u16, body: String) -> (String, Arc<Mutex<Vec<serde_json::Value>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let host = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let requests_clone = requests.clone();
+
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut raw = Vec::new();
+                let mut buf = [0u8; 4096];
+                let request_body = loop {
+                    let n = socket.read(&mut buf).await.unwrap();
+                    if n == 0 {
+                        break String::new();
+                    }
+                    raw.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&raw).to_string();
+                    if let Some(split) = text.find("\r\n\r\n") {
+                        let content_length = text[..split]
+                            .lines()
+                            .find_map(|l| {
+                                l.to_lowercase()
+                                    .strip_prefix("content-length:")
+                                    .map(|v| v.trim().parse::<usize>().unwrap())
+                            })
+                            .unwrap_or(0);
+                        if raw.len() >= split + 4 + content_length {
+                            break text[split + 4..].to_string();
+                        }
+                    }
+                };
+                if let Ok(json) = serde_json::from_str(&request_body) {
+                    requests_clone.lock().unwrap().push(json);
+                }
+
+                let response = format!(
+                    "HTTP/1.1 {} Status\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        (host, requests)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::test_support::{chat_body, mock_ollama};
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_new_validates_host_and_model() {
+        let client = OllamaClient::new("http://localhost:11434/", "llama3.1").unwrap();
+        assert_eq!(client.api_endpoint(), "http://localhost:11434/api/chat");
+        assert_eq!(client.model(), "llama3.1");
+
+        assert!(OllamaClient::new("localhost:11434", "llama3.1").is_err());
+        assert!(OllamaClient::new(DEFAULT_OLLAMA_HOST, " ").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_analyze_for_violations_parses_local_reply() {
+        let reply = r#"[{"control_id": "CC6.7", "severity": "critical", "description": "Hardcoded key",
+            "line_number": 1, "code_snippet": "KEY = 'x'", "confidence_score": 90, "reasoning": "Literal secret"}]"#;
+        let (host, requests) = mock_ollama(200, chat_body(reply)).await;
+        let client = OllamaClient::new(&host, DEFAULT_OLLAMA_MODEL).unwrap();
+
+        let analysis = client.analyze_for_violations(7, "app.py", "KEY = 'x'\n", vec![], &[]).await.unwrap();
+
+        assert_eq!(analysis.violations.len(), 1);
+        assert_eq!(analysis.violations[0].scan_id, 7);
+        assert_eq!(analysis.violations[0].detection_method, "llm");
+        assert_eq!(analysis.usage.prompt_tokens, 120);
+        assert_eq!(analysis.usage.completion_tokens, 30);
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0]["model"], DEFAULT_OLLAMA_MODEL);
+        assert_eq!(requests[0]["stream"], false);
+        assert_eq!(requests[0]["messages"][0]["role"], "system");
+    }
+
+    #[tokio::test]
+    async fn test_generate_fix_sends_schema_as_format() {
+        let fix = serde_json::json!({
+            "fixed_code": "KEY = os.environ['KEY']",
+            "explanation": "Reads the key from the environment",
+            "changes_summary": "Replaced hardcoded KEY",
+            "test_recommendation": "Run with KEY set"
+        });
+        let (host, requests) = mock_ollama(200, chat_body(&fix.to_string())).await;
+        let client = OllamaClient::new(&host, DEFAULT_OLLAMA_MODEL).unwrap();
+
+        let generated = client
+            .generate_fix("CC6.7", "Hardcoded secret", "KEY = 'x'", "django", None, None, &[])
+            .await
+            .unwrap();
+
+        assert_eq!(generated.fixed_code, "KEY = os.environ['KEY']");
+        assert_eq!(generated.usage.prompt_tokens + generated.usage.completion_tokens, 150);
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0]["format"]["type"], "object");
+        assert!(requests[0]["format"]["required"].as_array().unwrap().contains(&"fixed_code".into()));
+    }
+
+    #[tokio::test]
+    async fn test_error_status_and_unreachable_server() {
+        let (host, _) = mock_ollama(404, r#"{"error":"model 'missing' not found"}"#.to_string()).await;
+        let client = OllamaClient::new(&host, "missing").unwrap();
+        let error = client.analyze_for_violations(1, "app.py", "", vec![], &[]).await.unwrap_err();
+        assert!(error.to_string().contains("Ollama error (404)"));
+        assert!(error.to_string().contains("not found"));
+
+        // Bind then drop a listener so nothing accepts on the port
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let host = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        let error = OllamaClient::new(&host, DEFAULT_OLLAMA_MODEL)
+            .unwrap()
+            .analyze_for_violations(1, "app.py", "", vec![], &[])
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("is `ollama serve` running?"));
+    }
+}
@@ -0,0 +1,411 @@
+//! GitHub pull requests for applied fixes
+//!
+//! When a project has pull requests enabled, `apply_fix` commits the fix to
+//! its own `ryn/fix/...` branch, pushes it to the configured remote and opens
+//! a PR against the base branch through the GitHub REST API, instead of
+//! leaving the commit on the working branch.
+//!
+//! The API token comes from the OS keychain or the `GITHUB_TOKEN` environment
+//! variable (see `security::credentials`); `GITHUB_API_URL` points at a
+//! GitHub Enterprise server.
+
+use anyhow::{anyhow, Context, Result};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::models::{Fix, Violation};
+use crate::security::credentials::{self, CredentialProvider};
+
+/// Public GitHub API, used unless `GITHUB_API_URL` is set
+pub const DEFAULT_GITHUB_API_URL: &str = "https://api.github.com";
+
+/// PR description used when the project has no template
+pub const DEFAULT_PR_TEMPLATE: &str = "\
+Ryn fixed a SOC 2 {control_id} ({severity}) violation in `{file_path}:{line_number}`.
+
+**Violation:** {description}
+
+**Fix:** {explanation}
+
+**Testing:** {test_recommendation}
+";
+
+/// Settings key holding a project's pull request configuration
+pub fn setting_key(project_id: i64) -> String {
+    format!("fix_pull_requests:{}", project_id)
+}
+
+/// Per-project pull request settings (stored as JSON under `setting_key`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PullRequestConfig {
+    /// Open a PR for every applied fix
+    pub enabled: bool,
+    /// Remote the fix branch is pushed to (must point at GitHub)
+    #[serde(default = "default_remote")]
+    pub remote: String,
+    /// Branch the PR targets; the branch checked out when the fix is applied if unset
+    #[serde(default)]
+    pub base_branch: Option<String>,
+    /// PR description with `{control_id}`-style placeholders (see `render_pr_body`)
+    #[serde(default)]
+    pub pr_template: Option<String>,
+}
+
+fn default_remote() -> String {
+    "origin".to_string()
+}
+
+impl Default for PullRequestConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            remote: default_remote(),
+            base_branch: None,
+            pr_template: None,
+        }
+    }
+}
+
+impl PullRequestConfig {
+    /// Parse the stored JSON (missing or malformed means disabled)
+    pub fn parse(value: &str) -> Self {
+        serde_json::from_str(value).unwrap_or_default()
+    }
+
+    /// Check the remote and base branch are usable names
+    pub fn validate(&self) -> Result<()> {
+        if self.remote.trim().is_empty() {
+            return Err(anyhow!("Remote name is required"));
+        }
+        if let Some(base) = &self.base_branch {
+            if !git2::Branch::name_is_valid(base).unwrap_or(false) {
+                return Err(anyhow!("Invalid base branch name: {}", base));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A GitHub repository, as `owner/name`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitHubRepo {
+    pub owner: String,
+    pub name: String,
+}
+
+impl GitHubRepo {
+    /// Parse a remote URL (`https://github.com/o/r.git`, `git@github.com:o/r.git`
+    /// or `ssh://git@github.com/o/r`)
+    ///
+    /// Only the path is checked, so GitHub Enterprise hosts work too.
+    pub fn from_remote_url(url: &str) -> Result<Self> {
+        let url = url.trim();
+        let path = if let Some((_, rest)) = url.split_once("://") {
+            rest.split_once('/').map(|(_, path)| path)
+        } else {
+            // scp-like syntax: git@host:owner/name.git
+            url.split_once(':').map(|(_, path)| path)
+        }
+        .ok_or_else(|| anyhow!("Unrecognized remote URL: {}", url))?;
+
+        let path = path.trim_matches('/');
+        let path = path.strip_suffix(".git").unwrap_or(path);
+        match path.split('/').collect::<Vec<_>>().as_slice() {
+            [owner, name] if !owner.is_empty() && !name.is_empty() => Ok(Self {
+                owner: owner.to_string(),
+                name: name.to_string(),
+            }),
+            _ => Err(anyhow!("Remote URL is not a GitHub repository: {}", url)),
+        }
+    }
+}
+
+/// A pull request opened by `GitHubClient::create_pull_request`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PullRequest {
+    pub number: i64,
+    pub html_url: String,
+}
+
+/// PR title for a fix
+pub fn pr_title(violation: &Violation) -> String {
+    format!("Fix {} violation in {}:{}", violation.control_id, violation.file_path, violation.line_number)
+}
+
+/// Fill in a PR template for a fix
+///
+/// Placeholders: `{control_id}`, `{severity}`, `{description}`, `{file_path}`,
+/// `{line_number}`, `{explanation}`, `{changes_summary}`, `{test_recommendation}`.
+pub fn render_pr_body(template: &str, fix: &Fix, violation: &Violation) -> String {
+    let not_given = |value: &Option<String>| value.clone().unwrap_or_else(|| "Not provided".to_string());
+    [
+        ("{control_id}", violation.control_id.clone()),
+        ("{severity}", violation.severity.clone()),
+        ("{description}", violation.description.clone()),
+        ("{file_path}", violation.file_path.clone()),
+        ("{line_number}", violation.line_number.to_string()),
+        ("{explanation}", fix.explanation.clone()),
+        ("{changes_summary}", not_given(&fix.changes_summary)),
+        ("{test_recommendation}", not_given(&fix.test_recommendation)),
+    ]
+    .iter()
+    .fold(template.to_string(), |body, (placeholder, value)| body.replace(placeholder, value))
+}
+
+/// Client for the GitHub pull request API
+pub struct GitHubClient {
+    http_client: Client,
+    api_url: String,
+    token: String,
+}
+
+impl GitHubClient {
+    /// Create a client with the stored GitHub token (and `GITHUB_API_URL`, if set)
+    ///
+    /// # Errors
+    /// Returns error if no token is in the keychain or GITHUB_TOKEN
+    pub fn from_env() -> Result<Self> {
+        let token = credentials::get_api_key(CredentialProvider::Github)?;
+        let api_url = std::env::var("GITHUB_API_URL").unwrap_or_else(|_| DEFAULT_GITHUB_API_URL.to_string());
+        Self::new(&api_url, &token)
+    }
+
+    pub fn new(api_url: &str, token: &str) -> Result<Self> {
+        let http_client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .context("Failed to build GitHub HTTP client")?;
+
+        Ok(Self {
+            http_client,
+            api_url: api_url.trim_end_matches('/').to_string(),
+            token: token.to_string(),
+        })
+    }
+
+    /// Login of the user the token belongs to, to check the token works
+    ///
+    /// # Errors
+    /// Returns error if GitHub can't be reached or rejects the token
+    pub async fn authenticated_user(&self) -> Result<String> {
+        #[derive(Deserialize)]
+        struct User {
+            login: String,
+        }
+
+        let response = self
+            .http_client
+            .get(format!("{}/user", self.api_url))
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "ryn")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send()
+            .await
+            .context("Failed to reach GitHub")?;
+
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        if status.as_u16() == 401 || status.as_u16() == 403 {
+            return Err(anyhow!("GitHub token was rejected ({})", status.as_u16()));
+        }
+        if !status.is_success() {
+            return Err(anyhow!("GitHub returned {}: {}", status, text));
+        }
+
+        let user: User =
+            serde_json::from_str(&text).with_context(|| format!("Failed to parse GitHub response: {}", text))?;
+        Ok(user.login)
+    }
+
+    /// Open a pull request from `head` into `base`
+    pub async fn create_pull_request(
+        &self,
+        repo: &GitHubRepo,
+        head: &str,
+        base: &str,
+        title: &str,
+        body: &str,
+    ) -> Result<PullRequest> {
+        let url = format!("{}/repos/{}/{}/pulls", self.api_url, repo.owner, repo.name);
+        let response = self
+            .http_client
+            .post(&url)
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "ryn")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .json(&serde_json::json!({
+                "title": title,
+                "head": head,
+                "base": base,
+                "body": body,
+            }))
+            .send()
+            .await
+            .context("Failed to send GitHub pull request")?;
+
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            return Err(anyhow!("GitHub returned {}: {}", status, text));
+        }
+
+        serde_json::from_str(&text).with_context(|| format!("Failed to parse GitHub response: {}", text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Severity, TrustLevel};
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// HTTP server answering one request with `status` and `body`;
+    /// returns its URL and the raw request it received
+    async fn mock_github(status: u16, body: &'static str) -> (String, Arc<Mutex<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let captured = Arc::new(Mutex::new(String::new()));
+        let captured_clone = captured.clone();
+
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut raw = Vec::new();
+            let mut buf = [0u8; 4096];
+            loop {
+                let n = socket.read(&mut buf).await.unwrap();
+                if n == 0 {
+                    break;
+                }
+                raw.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&raw);
+                if let Some(split) = text.find("\r\n\r\n") {
+                    let content_length = text[..split]
+                        .lines()
+                        .find_map(|l| {
+                            l.to_lowercase()
+                                .strip_prefix("content-length:")
+                                .map(|v| v.trim().parse::<usize>().unwrap())
+                        })
+                        .unwrap_or(0);
+                    if raw.len() >= split + 4 + content_length {
+                        break;
+                    }
+                }
+            }
+            *captured_clone.lock().unwrap() = String::from_utf8_lossy(&raw).to_string();
+
+            let response = format!(
+                "HTTP/1.1 {} Status\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            let _ = socket.write_all(response.as_bytes()).await;
+        });
+
+        (url, captured)
+    }
+
+    fn violation() -> Violation {
+        Violation::new(
+            1,
+            "CC6.7".to_string(),
+            Severity::Critical,
+            "Hardcoded API key".to_string(),
+            "app/settings.py".to_string(),
+            12,
+            "KEY = 'sk_live_abc'".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_repo_from_remote_url() {
+        let expected = GitHubRepo {
+            owner: "acme".to_string(),
+            name: "payments".to_string(),
+        };
+        for url in [
+            "https://github.com/acme/payments.git",
+            "https://github.com/acme/payments/",
+            "git@github.com:acme/payments.git",
+            "ssh://git@github.example.com/acme/payments",
+        ] {
+            assert_eq!(GitHubRepo::from_remote_url(url).unwrap(), expected, "{}", url);
+        }
+
+        assert!(GitHubRepo::from_remote_url("/srv/git/payments.git").is_err());
+        assert!(GitHubRepo::from_remote_url("https://github.com/acme").is_err());
+    }
+
+    #[test]
+    fn test_config_parse_and_validate() {
+        let config = PullRequestConfig::parse(r#"{"enabled": true}"#);
+        assert!(config.enabled);
+        assert_eq!(config.remote, "origin");
+        assert!(config.validate().is_ok());
+
+        assert_eq!(PullRequestConfig::parse("not json"), PullRequestConfig::default());
+
+        let bad_base = PullRequestConfig {
+            base_branch: Some("main..dev".to_string()),
+            ..config
+        };
+        assert!(bad_base.validate().is_err());
+    }
+
+    #[test]
+    fn test_render_pr_body_fills_placeholders() {
+        let violation = violation();
+        let mut fix = Fix::new(
+            1,
+            violation.code_snippet.clone(),
+            "KEY = os.environ['KEY']".to_string(),
+            "Read the key from the environment".to_string(),
+            TrustLevel::Review,
+        );
+        fix.test_recommendation = Some("Run the payment tests with KEY set".to_string());
+
+        let body = render_pr_body(DEFAULT_PR_TEMPLATE, &fix, &violation);
+        assert!(body.contains("CC6.7 (critical) violation in `app/settings.py:12`"));
+        assert!(body.contains("**Fix:** Read the key from the environment"));
+        assert!(body.contains("**Testing:** Run the payment tests with KEY set"));
+
+        let body = render_pr_body("{changes_summary} / {unknown}", &fix, &violation);
+        assert_eq!(body, "Not provided / {unknown}");
+    }
+
+    #[tokio::test]
+    async fn test_create_pull_request_posts_to_repo() {
+        let (url, captured) =
+            mock_github(201, r#"{"number": 17, "html_url": "https://github.com/acme/payments/pull/17"}"#).await;
+        let client = GitHubClient::new(&url, "ghp_test").unwrap();
+        let repo = GitHubRepo::from_remote_url("git@github.com:acme/payments.git").unwrap();
+
+        let pr = client
+            .create_pull_request(&repo, "ryn/fix/CC6.7/1", "main", &pr_title(&violation()), "body")
+            .await
+            .unwrap();
+
+        assert_eq!(pr.number, 17);
+        let request = captured.lock().unwrap().clone();
+        assert!(request.starts_with("POST /repos/acme/payments/pulls "));
+        assert!(request.to_lowercase().contains("authorization: bearer ghp_test"));
+        assert!(request.contains(r#""head":"ryn/fix/CC6.7/1""#));
+        assert!(request.contains(r#""title":"Fix CC6.7 violation in app/settings.py:12""#));
+    }
+
+    #[tokio::test]
+    async fn test_create_pull_request_reports_api_errors() {
+        let (url, _) = mock_github(422, r#"{"message": "Validation Failed"}"#).await;
+        let client = GitHubClient::new(&url, "ghp_test").unwrap();
+        let repo = GitHubRepo::from_remote_url("https://github.com/acme/payments").unwrap();
+
+        let error = client.create_pull_request(&repo, "head", "main", "title", "body").await.unwrap_err();
+        assert!(error.to_string().contains("Validation Failed"));
+    }
+}
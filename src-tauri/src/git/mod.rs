@@ -3,9 +3,12 @@
 //! Provides functionality for:
 //! - Committing fixes to git repositories
 //! - Checking repository status
-//! - Managing branches and commits
+//! - Managing branches and commits (including `ryn/fix/...` fix branches)
 //! - Tracking file changes
+//! - Pushing fix branches and opening GitHub pull requests for them
 
 pub mod operations;
+pub mod github;
 
-pub use operations::{GitOperations, CommitInfo};
+pub use operations::{GitOperations, CommitInfo, FIX_BRANCH_PREFIX};
+pub use github::{GitHubClient, GitHubRepo, PullRequest, PullRequestConfig};
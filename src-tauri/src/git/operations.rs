@@ -4,9 +4,15 @@
 //! checking repository status, and managing branches.
 
 use anyhow::{anyhow, Context, Result};
-use git2::{Signature, Repository};
+use git2::{Branch, BranchType, Commit, DiffOptions, Oid, Signature, Repository};
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 
+use crate::models::Violation;
+
+/// Prefix of branches created for fixes: `ryn/fix/{control_id}/{violation_id}`
+pub const FIX_BRANCH_PREFIX: &str = "ryn/fix/";
+
 /// Git operations for fix application and version control
 pub struct GitOperations;
 
@@ -60,6 +66,136 @@ impl GitOperations {
         Ok(branch.to_string())
     }
 
+    /// Branch name for a violation's fix: `ryn/fix/{control_id}/{violation_id}`
+    ///
+    /// The control ID is reduced to characters safe in a ref name
+    /// (`CC6.1` stays `CC6.1`, `A1 2/x` becomes `A1-2-x`).
+    pub fn fix_branch_name(violation: &Violation) -> String {
+        format!(
+            "{}{}/{}",
+            FIX_BRANCH_PREFIX,
+            sanitize_branch_component(&violation.control_id),
+            violation.id
+        )
+    }
+
+    /// Create a fix branch for `violation` at HEAD and switch to it
+    ///
+    /// The branch starts at the current commit, so the working tree is left
+    /// as is. Commits made afterwards (e.g. by `commit_fix`) land on the new
+    /// branch.
+    ///
+    /// # Arguments
+    /// * `repo_path` - Path to git repository root
+    /// * `violation` - Violation being fixed
+    ///
+    /// # Returns
+    /// Name of the new branch
+    ///
+    /// # Errors
+    /// Returns error if the repository has no commits or the branch already exists
+    pub fn create_fix_branch(repo_path: &Path, violation: &Violation) -> Result<String> {
+        let repo = Repository::open(repo_path)
+            .context("Failed to open git repository")?;
+
+        let branch_name = Self::fix_branch_name(violation);
+        if !Branch::name_is_valid(&branch_name).unwrap_or(false) {
+            return Err(anyhow!("Invalid branch name: {}", branch_name));
+        }
+
+        let head_commit = repo.head()
+            .context("Failed to get repository HEAD")?
+            .peel_to_commit()
+            .context("Failed to get HEAD commit")?;
+
+        let branch = repo.branch(&branch_name, &head_commit, false)
+            .with_context(|| format!("Failed to create branch {}", branch_name))?;
+
+        let reference = branch.get()
+            .name()
+            .ok_or_else(|| anyhow!("Branch reference name is not valid UTF-8"))?;
+
+        repo.set_head(reference)
+            .with_context(|| format!("Failed to switch to branch {}", branch_name))?;
+
+        Ok(branch_name)
+    }
+
+    /// List local branches created for fixes (`ryn/fix/...`), sorted by name
+    ///
+    /// # Arguments
+    /// * `repo_path` - Path to git repository root
+    ///
+    /// # Errors
+    /// Returns error if repository cannot be opened or branches cannot be read
+    pub fn list_ryn_fix_branches(repo_path: &Path) -> Result<Vec<String>> {
+        let repo = Repository::open(repo_path)
+            .context("Failed to open git repository")?;
+
+        let mut branches = Vec::new();
+        for entry in repo.branches(Some(BranchType::Local)).context("Failed to list branches")? {
+            let (branch, _) = entry.context("Failed to read branch")?;
+            if let Some(name) = branch.name().context("Failed to read branch name")? {
+                if name.starts_with(FIX_BRANCH_PREFIX) {
+                    branches.push(name.to_string());
+                }
+            }
+        }
+
+        branches.sort();
+        Ok(branches)
+    }
+
+    /// URL of a remote (e.g. `origin`)
+    ///
+    /// # Errors
+    /// Returns error if the repository cannot be opened or has no such remote
+    pub fn remote_url(repo_path: &Path, remote: &str) -> Result<String> {
+        let repo = Repository::open(repo_path)
+            .context("Failed to open git repository")?;
+
+        let remote_ref = repo.find_remote(remote)
+            .with_context(|| format!("Remote not found: {}", remote))?;
+
+        remote_ref.url()
+            .map(str::to_string)
+            .ok_or_else(|| anyhow!("Remote {} URL is not valid UTF-8", remote))
+    }
+
+    /// Push a local branch to `remote`, setting it as the branch's upstream
+    ///
+    /// Runs the `git` command line rather than libgit2 so the user's
+    /// credential helpers and SSH agent are used for authentication.
+    ///
+    /// # Arguments
+    /// * `repo_path` - Path to git repository root
+    /// * `remote` - Remote name (e.g. `origin`)
+    /// * `branch` - Local branch to push
+    ///
+    /// # Errors
+    /// Returns error if git can't be run or the push is rejected
+    pub fn push_branch(repo_path: &Path, remote: &str, branch: &str) -> Result<()> {
+        let output = std::process::Command::new("git")
+            .arg("-C")
+            .arg(repo_path)
+            .args(["push", "--set-upstream", remote, branch])
+            // Fail instead of waiting for a password prompt nobody can answer
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .output()
+            .context("Failed to run git push")?;
+
+        if !output.status.success() {
+            return Err(anyhow!(
+                "git push {} {} failed: {}",
+                remote,
+                branch,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        Ok(())
+    }
+
     /// Commit fix to repository
     ///
     /// Stages the specified file and creates a commit with the given message.
@@ -173,15 +309,7 @@ impl GitOperations {
 
             if let Ok(oid) = oid_result {
                 if let Ok(commit) = repo.find_commit(oid) {
-                    commits.push(CommitInfo {
-                        sha: commit.id().to_string(),
-                        message: commit.message()
-                            .unwrap_or("Unknown message")
-                            .to_string(),
-                        author: commit.author().name()
-                            .unwrap_or("Unknown")
-                            .to_string(),
-                    });
+                    commits.push(Self::commit_info(&repo, &commit)?);
                 }
             }
         }
@@ -189,6 +317,76 @@ impl GitOperations {
         Ok(commits)
     }
 
+    /// Get details of a single commit
+    ///
+    /// Used to record what `apply_fix` committed in the fix's audit event.
+    ///
+    /// # Arguments
+    /// * `repo_path` - Path to git repository root
+    /// * `commit_sha` - Full SHA-1 of the commit
+    ///
+    /// # Returns
+    /// CommitInfo with author, timestamp, message and the files the commit changed
+    ///
+    /// # Errors
+    /// Returns error if the repository cannot be opened, the SHA is invalid,
+    /// or the commit does not exist
+    pub fn get_commit_info(repo_path: &Path, commit_sha: &str) -> Result<CommitInfo> {
+        let repo = Repository::open(repo_path)
+            .context("Failed to open git repository")?;
+
+        let oid = Oid::from_str(commit_sha)
+            .with_context(|| format!("Invalid commit SHA: {}", commit_sha))?;
+
+        let commit = repo.find_commit(oid)
+            .with_context(|| format!("Commit not found: {}", commit_sha))?;
+
+        Self::commit_info(&repo, &commit)
+    }
+
+    /// Build CommitInfo for `commit`, diffing against its first parent
+    /// (or the empty tree for a root commit) for the changed files
+    fn commit_info(repo: &Repository, commit: &Commit) -> Result<CommitInfo> {
+        let sha = commit.id().to_string();
+        let author = commit.author();
+
+        let parent_tree = match commit.parent(0) {
+            Ok(parent) => Some(parent.tree().context("Failed to get parent tree")?),
+            Err(_) => None,
+        };
+        let diff = repo.diff_tree_to_tree(
+            parent_tree.as_ref(),
+            Some(&commit.tree().context("Failed to get commit tree")?),
+            None,
+        ).context("Failed to create diff")?;
+
+        let files_changed = diff
+            .deltas()
+            .filter_map(|delta| delta.new_file().path().or_else(|| delta.old_file().path()))
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
+
+        let timestamp = chrono::DateTime::from_timestamp(commit.time().seconds(), 0)
+            .map(|time| time.to_rfc3339())
+            .unwrap_or_default();
+
+        Ok(CommitInfo {
+            short_sha: sha.chars().take(7).collect(),
+            sha,
+            message: commit.message()
+                .unwrap_or("Unknown message")
+                .to_string(),
+            author: author.name()
+                .unwrap_or("Unknown")
+                .to_string(),
+            email: author.email()
+                .unwrap_or("")
+                .to_string(),
+            timestamp,
+            files_changed,
+        })
+    }
+
     /// Check if file has uncommitted changes
     ///
     /// Returns true if the file has modifications, additions, or deletions
@@ -340,17 +538,93 @@ impl GitOperations {
 
         Ok(false)
     }
+
+    /// Files changed between `base_ref` and the working directory
+    ///
+    /// Covers committed, staged and unstaged changes as well as untracked
+    /// (non-ignored) files. Deleted files are included so callers can drop
+    /// findings for them.
+    ///
+    /// # Arguments
+    /// * `repo_path` - Path to git repository root
+    /// * `base_ref` - Commit SHA, branch, tag or revision expression (`HEAD~3`)
+    ///
+    /// # Returns
+    /// Paths relative to the repository root, sorted and deduplicated
+    ///
+    /// # Errors
+    /// Returns error if the repository cannot be opened or `base_ref` does
+    /// not resolve to a commit
+    pub fn changed_files_since(repo_path: &Path, base_ref: &str) -> Result<Vec<String>> {
+        let repo = Repository::open(repo_path)
+            .context("Failed to open git repository")?;
+
+        let base = repo.revparse_single(base_ref)
+            .with_context(|| format!("Unknown git ref: {}", base_ref))?
+            .peel_to_commit()
+            .with_context(|| format!("Not a commit: {}", base_ref))?;
+
+        let mut options = DiffOptions::new();
+        options.include_untracked(true).recurse_untracked_dirs(true);
+
+        let diff = repo.diff_tree_to_workdir_with_index(
+            Some(&base.tree().context("Failed to get base tree")?),
+            Some(&mut options),
+        ).context("Failed to create diff")?;
+
+        let mut files: Vec<String> = diff
+            .deltas()
+            .flat_map(|delta| [delta.old_file().path(), delta.new_file().path()])
+            .flatten()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
+        files.sort();
+        files.dedup();
+
+        Ok(files)
+    }
+}
+
+/// Reduce `component` to `[A-Za-z0-9._-]` for use inside a branch name
+///
+/// Other characters become `-`, and runs of dots (invalid in refs) are
+/// collapsed. Leading/trailing dots and dashes are trimmed; an empty result
+/// becomes `unknown`.
+fn sanitize_branch_component(component: &str) -> String {
+    let mut sanitized = String::with_capacity(component.len());
+    for c in component.chars() {
+        let c = if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-') { c } else { '-' };
+        if c == '.' && sanitized.ends_with('.') {
+            continue;
+        }
+        sanitized.push(c);
+    }
+
+    let trimmed = sanitized.trim_matches(|c| c == '.' || c == '-');
+    if trimmed.is_empty() {
+        "unknown".to_string()
+    } else {
+        trimmed.to_string()
+    }
 }
 
 /// Information about a single commit
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CommitInfo {
     /// SHA-1 commit hash (40 hex characters)
     pub sha: String,
+    /// First 7 characters of the SHA
+    pub short_sha: String,
     /// Commit message
     pub message: String,
     /// Author name
     pub author: String,
+    /// Author email
+    pub email: String,
+    /// Commit time (RFC 3339, UTC)
+    pub timestamp: String,
+    /// Paths changed by the commit, relative to the repository root
+    pub files_changed: Vec<String>,
 }
 
 #[cfg(test)]
@@ -465,6 +739,43 @@ mod tests {
         assert_eq!(commits[0].author, "test");
     }
 
+    #[test]
+    fn test_get_commit_info() {
+        let (dir, _repo) = init_test_repo();
+        fs::write(dir.path().join("app.py"), "password = os.environ['DB_PASSWORD']\n").unwrap();
+        let sha = GitOperations::commit_fix(dir.path(), Path::new("app.py"), "Fix CC6.7 violation in app.py:1").unwrap();
+
+        let info = GitOperations::get_commit_info(dir.path(), &sha).unwrap();
+
+        assert_eq!(info.sha, sha);
+        assert_eq!(info.short_sha, sha[..7]);
+        assert_eq!(info.author, "ryn-ai");
+        assert_eq!(info.email, "compliance@ryn.local");
+        assert_eq!(info.message, "Fix CC6.7 violation in app.py:1");
+        assert_eq!(info.files_changed, vec!["app.py".to_string()]);
+        assert!(chrono::DateTime::parse_from_rfc3339(&info.timestamp).is_ok());
+    }
+
+    #[test]
+    fn test_get_commit_info_root_commit_lists_all_files() {
+        let (dir, _repo) = init_test_repo();
+        let sha = GitOperations::get_last_commit_sha(dir.path()).unwrap();
+
+        let info = GitOperations::get_commit_info(dir.path(), &sha).unwrap();
+
+        assert_eq!(info.message, "Initial commit");
+        assert_eq!(info.email, "test@test.com");
+        assert_eq!(info.files_changed, vec!["README.md".to_string()]);
+    }
+
+    #[test]
+    fn test_get_commit_info_unknown_or_invalid_sha() {
+        let (dir, _repo) = init_test_repo();
+
+        assert!(GitOperations::get_commit_info(dir.path(), "not-a-sha").is_err());
+        assert!(GitOperations::get_commit_info(dir.path(), &"0".repeat(40)).is_err());
+    }
+
     #[test]
     fn test_get_recent_commits_limit() {
         let (dir, repo) = init_test_repo();
@@ -663,4 +974,138 @@ mod tests {
         let commits = GitOperations::get_recent_commits(dir.path(), 3).unwrap();
         assert_eq!(commits.len(), 3); // Initial + 2 fixes
     }
+
+    fn violation(id: i64, control_id: &str) -> Violation {
+        let mut violation = Violation::new(
+            1,
+            control_id.to_string(),
+            crate::models::Severity::Critical,
+            "Hardcoded secret".to_string(),
+            "config.py".to_string(),
+            1,
+            "password = 'x'".to_string(),
+        );
+        violation.id = id;
+        violation
+    }
+
+    #[test]
+    fn test_fix_branch_name_sanitizes_control_id() {
+        assert_eq!(GitOperations::fix_branch_name(&violation(42, "CC6.1")), "ryn/fix/CC6.1/42");
+        assert_eq!(GitOperations::fix_branch_name(&violation(7, "A1 2/x")), "ryn/fix/A1-2-x/7");
+        assert_eq!(GitOperations::fix_branch_name(&violation(3, "CC..7~^:")), "ryn/fix/CC.7/3");
+        assert_eq!(GitOperations::fix_branch_name(&violation(1, "..")), "ryn/fix/unknown/1");
+    }
+
+    #[test]
+    fn test_create_fix_branch_switches_head() {
+        let (dir, _repo) = init_test_repo();
+        let original_branch = GitOperations::get_current_branch(dir.path()).unwrap();
+
+        let branch = GitOperations::create_fix_branch(dir.path(), &violation(42, "CC6.7")).unwrap();
+
+        assert_eq!(branch, "ryn/fix/CC6.7/42");
+        assert_eq!(GitOperations::get_current_branch(dir.path()).unwrap(), "ryn/fix/CC6.7/42");
+
+        // Commits now land on the fix branch, leaving the original branch untouched
+        fs::write(dir.path().join("config.py"), "password = os.getenv('PASSWORD')").unwrap();
+        let sha = GitOperations::commit_fix(dir.path(), Path::new("config.py"), "Fix CC6.7").unwrap();
+
+        let repo = Repository::open(dir.path()).unwrap();
+        let original = repo.find_branch(&original_branch, BranchType::Local).unwrap();
+        assert_ne!(original.get().peel_to_commit().unwrap().id().to_string(), sha);
+        assert_eq!(GitOperations::get_last_commit_sha(dir.path()).unwrap(), sha);
+    }
+
+    #[test]
+    fn test_create_fix_branch_twice_fails() {
+        let (dir, _repo) = init_test_repo();
+
+        GitOperations::create_fix_branch(dir.path(), &violation(1, "CC6.1")).unwrap();
+        assert!(GitOperations::create_fix_branch(dir.path(), &violation(1, "CC6.1")).is_err());
+    }
+
+    #[test]
+    fn test_list_ryn_fix_branches() {
+        let (dir, repo) = init_test_repo();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("feature/login", &head, false).unwrap();
+
+        GitOperations::create_fix_branch(dir.path(), &violation(9, "CC7.2")).unwrap();
+        GitOperations::create_fix_branch(dir.path(), &violation(2, "A1.2")).unwrap();
+
+        let branches = GitOperations::list_ryn_fix_branches(dir.path()).unwrap();
+        assert_eq!(branches, vec!["ryn/fix/A1.2/2".to_string(), "ryn/fix/CC7.2/9".to_string()]);
+    }
+
+    #[test]
+    fn test_push_branch_to_remote() {
+        let (dir, repo) = init_test_repo();
+        let remote_dir = TempDir::new().unwrap();
+        Repository::init_bare(remote_dir.path()).unwrap();
+        repo.remote("origin", &remote_dir.path().to_string_lossy()).unwrap();
+        assert_eq!(
+            GitOperations::remote_url(dir.path(), "origin").unwrap(),
+            remote_dir.path().to_string_lossy()
+        );
+        assert!(GitOperations::remote_url(dir.path(), "upstream").is_err());
+
+        let branch = GitOperations::create_fix_branch(dir.path(), &violation(5, "CC6.7")).unwrap();
+        GitOperations::push_branch(dir.path(), "origin", &branch).unwrap();
+
+        let remote = Repository::open_bare(remote_dir.path()).unwrap();
+        let pushed = remote.find_branch(&branch, BranchType::Local).unwrap();
+        assert_eq!(
+            pushed.get().peel_to_commit().unwrap().id().to_string(),
+            GitOperations::get_last_commit_sha(dir.path()).unwrap()
+        );
+
+        assert!(GitOperations::push_branch(dir.path(), "origin", "no-such-branch").is_err());
+    }
+
+    fn commit_all(repo: &Repository, message: &str) {
+        let mut index = repo.index().unwrap();
+        index.add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None).unwrap();
+        index.update_all(["*"].iter(), None).unwrap();
+        index.write().unwrap();
+
+        let sig = Signature::now("test", "test@test.com").unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let parent = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &[&parent]).unwrap();
+    }
+
+    #[test]
+    fn test_changed_files_since_covers_commits_and_working_tree() {
+        let (dir, repo) = init_test_repo();
+        fs::write(dir.path().join("keep.py"), "x = 1").unwrap();
+        fs::write(dir.path().join("gone.py"), "y = 2").unwrap();
+        commit_all(&repo, "Add files");
+        let base = GitOperations::get_last_commit_sha(dir.path()).unwrap();
+
+        // Committed change
+        fs::write(dir.path().join("committed.py"), "z = 3").unwrap();
+        commit_all(&repo, "Add committed.py");
+        // Unstaged change, deletion and untracked file
+        fs::write(dir.path().join("README.md"), "# Changed").unwrap();
+        fs::remove_file(dir.path().join("gone.py")).unwrap();
+        fs::create_dir(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src").join("new.py"), "w = 4").unwrap();
+
+        let changed = GitOperations::changed_files_since(dir.path(), &base).unwrap();
+        assert_eq!(changed, vec!["README.md", "committed.py", "gone.py", "src/new.py"]);
+    }
+
+    #[test]
+    fn test_changed_files_since_head_is_empty_when_clean() {
+        let (dir, _repo) = init_test_repo();
+        assert!(GitOperations::changed_files_since(dir.path(), "HEAD").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_changed_files_since_unknown_ref() {
+        let (dir, _repo) = init_test_repo();
+        let err = GitOperations::changed_files_since(dir.path(), "no-such-branch").unwrap_err();
+        assert!(err.to_string().contains("Unknown git ref"));
+    }
 }
@@ -0,0 +1,8 @@
+//! Issue tracker integrations
+//!
+//! Files remediation tickets for violations in the trackers customers
+//! already use (currently Jira).
+
+pub mod jira;
+
+pub use jira::{JiraClient, JiraIssue};
@@ -10,6 +10,15 @@ pub mod fix_generator;
 pub mod utils;
 pub mod security;
 pub mod rate_limiter;
+pub mod notifications;
+pub mod git;
+pub mod mcp_tools;
+pub mod scheduler;
+pub mod reporting;
+pub mod integrations;
+
+#[cfg(feature = "headless")]
+pub mod cli;
 
 // Re-export commonly used types (types added in later phases)
 // pub use models::{
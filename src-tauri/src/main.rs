@@ -12,11 +12,16 @@
 
 // Import command modules
 use ryn::commands::{
-    project, scan, violation, fix, audit, settings, analytics, logger
+    project, scan, violation, fix, audit, settings, analytics, logger, namespace, notification, deep_link, export, onboarding, schedule, integration
 };
-use tauri_plugin_mcp_bridge;
 
 fn main() {
+    // `ryn scan ...` and friends run headless and never start the GUI
+    #[cfg(feature = "headless")]
+    if ryn::cli::is_cli_invocation(std::env::args()) {
+        std::process::exit(ryn::cli::run());
+    }
+
     // Load environment variables from .env file
     // This allows API keys and config to be read from .env during development
     if let Err(e) = ryn::utils::env::load_env() {
@@ -44,9 +49,36 @@ fn main() {
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_notification::init())
-        .plugin(tauri_plugin_mcp_bridge::init())
+        .plugin(tauri_plugin_mcp_bridge::init_with_ryn_tools(ryn::mcp_tools::RynMcpTools))
+        .plugin(tauri_plugin_deep_link::init())
         .manage(scan::ScanResponseChannels::default())
-        .manage(scan::FileWatcherState::default());
+        .manage(scan::FileWatcherState::default())
+        .manage(deep_link::DeepLinkState::default())
+        .setup(|app| {
+            use tauri_plugin_deep_link::DeepLinkExt;
+
+            // Deep links are queued until the frontend emits "app-ready"
+            deep_link::listen_for_app_ready(app.handle());
+
+            // Link that launched the app, if any
+            if let Ok(Some(urls)) = app.deep_link().get_current() {
+                for url in urls {
+                    deep_link::handle_deep_link(app.handle(), url.to_string());
+                }
+            }
+
+            let handle = app.handle().clone();
+            app.deep_link().on_open_url(move |event| {
+                for url in event.urls() {
+                    deep_link::handle_deep_link(&handle, url.to_string());
+                }
+            });
+
+            // Runs scheduled scans for as long as the app is open
+            ryn::scheduler::spawn(app.handle().clone());
+
+            Ok(())
+        });
 
     // MCP plugin disabled - not linked in Cargo.toml
     // Uncomment and add dependency if needed for development
@@ -71,39 +103,114 @@ fn main() {
     // If this fails, log detailed error and exit gracefully
     if let Err(e) = builder
         .invoke_handler(tauri::generate_handler![
-            // Project Commands (3)
+            // Project Commands (14) - added add_project_root, remove_project_root and get_project_roots
             project::select_project_folder,
             project::create_project,
             project::get_projects,
-            // Scan Commands (8) - added watch_project, stop_watching, and cancel_scan
+            project::set_project_scan_excludes,
+            project::get_project_scan_excludes,
+            project::set_project_cost_limit,
+            project::remove_project_cost_limit,
+            project::set_project_pull_request_config,
+            project::get_project_pull_request_config,
+            project::set_project_settings,
+            project::get_project_settings,
+            project::add_project_root,
+            project::remove_project_root,
+            project::get_project_roots,
+            // Scan Commands (15) - added watch_project, stop_watching, cancel_scan, explain_file_selection, export_scan_profile, get_watcher_stats, scan_project_with_baseline, scan_project_incremental, get_scan_detail_stats and get_effective_ignores
             scan::detect_framework,
             scan::scan_project,
+            scan::scan_project_with_baseline,
+            scan::scan_project_incremental,
             scan::watch_project,
             scan::stop_watching,
             scan::get_scan_progress,
             scan::get_scans,
+            scan::get_scan_detail_stats,
             scan::respond_to_cost_limit,
             scan::cancel_scan,
-            // Violation Commands (3)
+            scan::explain_file_selection,
+            scan::export_scan_profile,
+            scan::get_watcher_stats,
+            scan::get_effective_ignores,
+            // Violation Commands (14)
             violation::get_violations,
             violation::get_violation,
             violation::dismiss_violation,
-            // Fix Commands (2)
+            violation::report_false_positive,
+            violation::update_violations_status,
+            violation::reopen_violations,
+            violation::get_violation_status_history,
+            violation::get_violations_by_category,
+            violation::get_violations_by_package,
+            violation::search_violations,
+            violation::get_project_violations,
+            violation::get_violation_history,
+            violation::create_baseline,
+            violation::list_suppressed,
+            // Fix Commands (8)
             fix::generate_fix,
+            fix::generate_fixes_batch,
+            fix::preview_fix,
             fix::apply_fix,
+            fix::get_fix_history,
+            fix::escalate_trust_level,
+            fix::get_fix_branches,
+            fix::get_fix_commit_info,
             // Audit Commands (1)
             audit::get_audit_events,
-            // Settings Commands (5)
+            // Settings Commands (12)
             settings::get_settings,
             settings::update_settings,
             settings::clear_database,
             settings::export_data,
             settings::complete_onboarding,
-            // Analytics Commands (2)
+            settings::create_profile,
+            settings::list_profiles,
+            settings::apply_profile,
+            settings::export_profile,
+            settings::import_profile,
+            settings::export_audit_events_csv,
+            settings::optimize_database,
+            // Onboarding Commands (5) - added set_api_key and test_api_key
+            onboarding::start_onboarding_check,
+            onboarding::validate_api_key_connection,
+            onboarding::get_onboarding_progress,
+            onboarding::set_api_key,
+            onboarding::test_api_key,
+            // Analytics Commands (8)
             analytics::get_scan_costs,
             analytics::get_scan_cost,
+            analytics::get_fix_quality_report,
+            analytics::get_false_positive_report,
+            analytics::estimate_scan_cost,
+            analytics::get_compliance_trend,
+            analytics::get_spending_report,
+            analytics::get_category_summary,
             // Logger Commands (1)
             logger::log_frontend_message,
+            // Namespace Commands (4)
+            namespace::create_namespace,
+            namespace::list_namespaces,
+            namespace::switch_namespace,
+            namespace::get_current_namespace,
+            // Notification Commands (5)
+            notification::create_slack_config,
+            notification::get_slack_configs,
+            notification::delete_slack_config,
+            notification::test_slack_notification,
+            notification::test_notification,
+            // Schedule Commands (2)
+            schedule::schedule_scan,
+            schedule::list_schedules,
+            // Export Commands (3)
+            export::import_sarif,
+            export::generate_report,
+            export::export_violations,
+            // Integration Commands (2)
+            integration::link_jira,
+            integration::create_jira_issue,
         ])
         .run(tauri::generate_context!())
     {
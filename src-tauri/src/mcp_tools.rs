@@ -0,0 +1,317 @@
+//! Ryn compliance tools for the MCP bridge
+//!
+//! Implements the bridge plugin's `RynTools` so AI agents connected over MCP
+//! can scan projects, list and dismiss violations, and generate fixes. Tools
+//! go straight to `db::queries` and the scan pipeline rather than through
+//! Tauri IPC. Violations are returned as [`AgentViolation`]s.
+
+use crate::commands::fix::generate_fix as generate_fix_command;
+use crate::commands::scan::{scan_project_internal, ScanResponseChannels};
+use crate::db::{self, queries};
+use crate::models::Violation;
+use crate::scanner::NullEventEmitter;
+use crate::utils::create_audit_event;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::Path;
+use tauri_plugin_mcp_bridge::{RynTools, ToolFuture};
+
+/// A violation as returned to MCP clients
+///
+/// Keeps what an agent needs to locate and judge a finding, and leaves out
+/// internal detection details (regex reasoning, tree-sitter context).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AgentViolation {
+    pub id: i64,
+    pub scan_id: i64,
+    pub control_id: String,
+    pub severity: String,
+    pub status: String,
+    pub description: String,
+    pub file_path: String,
+    pub line_number: i64,
+    pub code_snippet: String,
+    pub detection_method: String,
+    pub confidence_score: Option<i64>,
+}
+
+impl From<&Violation> for AgentViolation {
+    fn from(v: &Violation) -> Self {
+        Self {
+            id: v.id,
+            scan_id: v.scan_id,
+            control_id: v.control_id.clone(),
+            severity: v.severity.clone(),
+            status: v.status.clone(),
+            description: v.description.clone(),
+            file_path: v.file_path.clone(),
+            line_number: v.line_number,
+            code_snippet: v.code_snippet.clone(),
+            detection_method: v.detection_method.clone(),
+            confidence_score: v.confidence_score,
+        }
+    }
+}
+
+/// `RynTools` implementation registered with the MCP bridge in main.rs
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RynMcpTools;
+
+impl RynTools for RynMcpTools {
+    fn scan_project(&self, path: String) -> ToolFuture {
+        Box::pin(scan_project(path))
+    }
+
+    fn get_violations(&self, scan_id: i64, control_id: Option<String>) -> Result<Value, String> {
+        get_violations(scan_id, control_id.as_deref())
+    }
+
+    fn dismiss_violation(&self, violation_id: i64, reason: String) -> Result<Value, String> {
+        dismiss_violation(violation_id, &reason)
+    }
+
+    fn generate_fix(&self, violation_id: i64) -> ToolFuture {
+        Box::pin(generate_fix(violation_id))
+    }
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<Value, String> {
+    serde_json::to_value(value).map_err(|e| format!("Failed to serialize response: {}", e))
+}
+
+/// Scan the project at `path`, registering it as a project if it's new
+///
+/// Runs headless: prompts that need the UI (the cost limit) are declined.
+///
+/// Returns: `{ scan, violations }`
+pub async fn scan_project(path: String) -> Result<Value, String> {
+    eprintln!("[ryn] mcp_scan_project called: path={}", path);
+
+    let path = Path::new(&path)
+        .canonicalize()
+        .map_err(|e| format!("Invalid project path: {}", e))?;
+    if !path.is_dir() {
+        return Err(format!("Not a directory: {}", path.display()));
+    }
+    let path_str = path.to_string_lossy().to_string();
+
+    let project_id = {
+        let conn = db::get_connection();
+        match queries::select_project_by_path(&conn, &path_str)
+            .map_err(|e| format!("Failed to fetch project: {}", e))?
+        {
+            Some(project) => project.id,
+            None => {
+                let name = path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_else(|| path_str.clone());
+                queries::insert_project(&conn, &name, &path_str, None)
+                    .map_err(|e| format!("Failed to create project: {}", e))?
+            }
+        }
+    }; // Connection dropped here
+
+    let scan = scan_project_internal(NullEventEmitter, &ScanResponseChannels::default(), project_id, None).await?;
+
+    let conn = db::get_connection();
+    let violations: Vec<AgentViolation> = queries::select_violations(&conn, scan.id)
+        .map_err(|e| format!("Failed to fetch violations: {}", e))?
+        .iter()
+        .map(AgentViolation::from)
+        .collect();
+
+    Ok(json!({ "scan": to_json(&scan)?, "violations": to_json(&violations)? }))
+}
+
+/// Violations of a scan, optionally only those of `control_id`
+///
+/// Returns: Array of `AgentViolation`
+pub fn get_violations(scan_id: i64, control_id: Option<&str>) -> Result<Value, String> {
+    let conn = db::get_connection();
+
+    queries::select_scan(&conn, scan_id)
+        .map_err(|e| format!("Failed to fetch scan: {}", e))?
+        .ok_or_else(|| format!("Scan not found: {}", scan_id))?;
+
+    let violations: Vec<AgentViolation> = queries::select_violations(&conn, scan_id)
+        .map_err(|e| format!("Failed to fetch violations: {}", e))?
+        .iter()
+        .filter(|v| control_id.is_none_or(|id| v.control_id == id))
+        .map(AgentViolation::from)
+        .collect();
+
+    to_json(&violations)
+}
+
+/// Dismiss a violation, storing `reason` and logging an audit event
+///
+/// Returns: The dismissed `AgentViolation`
+pub fn dismiss_violation(violation_id: i64, reason: &str) -> Result<Value, String> {
+    eprintln!("[ryn] mcp_dismiss_violation called: violation_id={}", violation_id);
+
+    let conn = db::get_connection();
+
+    let violation = queries::select_violation(&conn, violation_id)
+        .map_err(|e| format!("Failed to fetch violation: {}", e))?
+        .ok_or_else(|| format!("Violation not found: {}", violation_id))?;
+
+    queries::update_violation_status(&conn, violation_id, "dismissed", Some(reason))
+        .map_err(|e| format!("Failed to dismiss violation: {}", e))?;
+
+    if let Some(scan) = queries::select_scan(&conn, violation.scan_id)
+        .map_err(|e| format!("Failed to fetch scan: {}", e))?
+    {
+        if let Ok(event) = create_audit_event(
+            &conn,
+            "violation_dismissed",
+            Some(scan.project_id),
+            Some(violation_id),
+            None,
+            &format!("Dismissed violation via MCP: {} ({})", violation.description, reason),
+        ) {
+            let _ = queries::insert_audit_event(&conn, &event);
+        }
+    }
+
+    let dismissed = queries::select_violation(&conn, violation_id)
+        .map_err(|e| format!("Failed to fetch violation: {}", e))?
+        .ok_or_else(|| format!("Violation not found: {}", violation_id))?;
+
+    to_json(&AgentViolation::from(&dismissed))
+}
+
+/// Generate a fix for a violation (stored for review, not applied)
+///
+/// Returns: The generated `Fix`
+pub async fn generate_fix(violation_id: i64) -> Result<Value, String> {
+    eprintln!("[ryn] mcp_generate_fix called: violation_id={}", violation_id);
+
+    let fix = generate_fix_command(violation_id).await?;
+    to_json(&fix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_helpers::TestDbGuard;
+
+    fn create_violation(scan_id: i64, control_id: &str, line_number: i64) -> i64 {
+        let violation = Violation::new(
+            scan_id,
+            control_id.to_string(),
+            crate::models::Severity::High,
+            format!("{} finding", control_id),
+            "app.py".to_string(),
+            line_number,
+            "api_key = \"sk-live\"".to_string(),
+        );
+
+        let conn = db::get_connection();
+        queries::insert_violation(&conn, &violation).unwrap()
+    }
+
+    fn create_scan() -> i64 {
+        let conn = db::get_connection();
+        let project_id = queries::insert_project(&conn, "mcp-project", "/tmp/mcp-project", None).unwrap();
+        queries::insert_scan(&conn, project_id, "regex_only").unwrap()
+    }
+
+    #[test]
+    fn test_agent_violation_omits_internal_fields() {
+        let mut violation = Violation::new(
+            1,
+            "CC6.7".to_string(),
+            crate::models::Severity::Critical,
+            "Hardcoded secret".to_string(),
+            "config.py".to_string(),
+            10,
+            "SECRET = \"abc\"".to_string(),
+        );
+        violation.regex_reasoning = Some("matched secret pattern".to_string());
+        violation.function_name = Some("load".to_string());
+
+        let json = serde_json::to_value(AgentViolation::from(&violation)).unwrap();
+        assert_eq!(json["control_id"], "CC6.7");
+        assert_eq!(json["severity"], "critical");
+        assert_eq!(json["line_number"], 10);
+        assert!(json.get("regex_reasoning").is_none());
+        assert!(json.get("function_name").is_none());
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_mcp_scan_project_registers_and_scans_path() {
+        let _guard = TestDbGuard::new();
+        let project_dir = tempfile::Builder::new().prefix("ryntest").tempdir().unwrap();
+        std::fs::write(project_dir.path().join("app.py"), "api_key = \"sk-1234567890abcdef\"\n").unwrap();
+        let path = project_dir.path().to_string_lossy().to_string();
+
+        let result = scan_project(path.clone()).await.unwrap();
+        assert!(result["scan"]["id"].as_i64().unwrap() > 0);
+        let violations: Vec<AgentViolation> = serde_json::from_value(result["violations"].clone()).unwrap();
+        assert!(violations.iter().any(|v| v.control_id == "CC6.7"));
+
+        // A second scan reuses the registered project
+        scan_project(path).await.unwrap();
+        let conn = db::get_connection();
+        assert_eq!(queries::select_projects(&conn).unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_mcp_scan_project_rejects_missing_path() {
+        let _guard = TestDbGuard::new();
+        let result = scan_project("/nonexistent/mcp/path".to_string()).await;
+        assert!(result.unwrap_err().starts_with("Invalid project path"));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_mcp_get_violations_filters_by_control() {
+        let _guard = TestDbGuard::new();
+        let scan_id = create_scan();
+        create_violation(scan_id, "CC6.7", 3);
+        create_violation(scan_id, "CC6.7", 4);
+        create_violation(scan_id, "CC7.2", 3);
+
+        let all: Vec<AgentViolation> = serde_json::from_value(get_violations(scan_id, None).unwrap()).unwrap();
+        assert_eq!(all.len(), 3);
+
+        let secrets: Vec<AgentViolation> =
+            serde_json::from_value(get_violations(scan_id, Some("CC6.7")).unwrap()).unwrap();
+        assert_eq!(secrets.len(), 2);
+        assert!(secrets.iter().all(|v| v.control_id == "CC6.7"));
+
+        assert!(get_violations(9999, None).unwrap_err().contains("Scan not found"));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_mcp_dismiss_violation_stores_reason() {
+        let _guard = TestDbGuard::new();
+        let scan_id = create_scan();
+        let violation_id = create_violation(scan_id, "CC6.7", 3);
+
+        let result = dismiss_violation(violation_id, "test fixture key").unwrap();
+        assert_eq!(result["status"], "dismissed");
+
+        let conn = db::get_connection();
+        let violation = queries::select_violation(&conn, violation_id).unwrap().unwrap();
+        assert_eq!(violation.status, "dismissed");
+        assert_eq!(violation.last_status_change_reason.as_deref(), Some("test fixture key"));
+        let events = queries::select_audit_events(&conn, 10).unwrap();
+        assert!(events.iter().any(|e| e.event_type == "violation_dismissed"));
+        drop(conn);
+
+        assert!(dismiss_violation(9999, "gone").unwrap_err().contains("Violation not found"));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_mcp_generate_fix_unknown_violation() {
+        let _guard = TestDbGuard::new();
+        let result = generate_fix(9999).await;
+        assert!(result.unwrap_err().contains("Violation not found"));
+    }
+}
@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::SeverityCounts;
+
+/// Points deducted from a control's score per violation, by severity
+const CRITICAL_PENALTY: i64 = 15;
+const HIGH_PENALTY: i64 = 8;
+const MEDIUM_PENALTY: i64 = 3;
+const LOW_PENALTY: i64 = 1;
+
+/// Controls scored on every scan
+pub const SCORED_CONTROLS: [&str; 4] = ["CC6.1", "CC6.7", "CC7.2", "A1.2"];
+
+/// Per-control compliance scores (0-100) for one completed scan
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ComplianceDataPoint {
+    pub scan_id: i64,
+    pub completed_at: String,
+    pub cc61_score: f32,
+    pub cc67_score: f32,
+    pub cc72_score: f32,
+    pub a12_score: f32,
+    /// Mean of the four control scores
+    pub overall_score: f32,
+}
+
+/// Score a control: 100 minus the severity-weighted violation count, floored at 0
+pub fn control_score(counts: SeverityCounts) -> f32 {
+    let penalty = counts.critical * CRITICAL_PENALTY
+        + counts.high * HIGH_PENALTY
+        + counts.medium * MEDIUM_PENALTY
+        + counts.low * LOW_PENALTY;
+    (100 - penalty).max(0) as f32
+}
+
+impl ComplianceDataPoint {
+    /// Score a scan from its (critical, high, medium, low) counts keyed by control_id
+    ///
+    /// Controls without violations score 100.
+    pub fn from_counts(
+        scan_id: i64,
+        completed_at: String,
+        counts_by_control: &HashMap<String, (i64, i64, i64, i64)>,
+    ) -> Self {
+        let score = |control_id: &str| {
+            control_score(counts_by_control.get(control_id).copied().unwrap_or_default().into())
+        };
+
+        let [cc61_score, cc67_score, cc72_score, a12_score] = SCORED_CONTROLS.map(score);
+
+        Self {
+            scan_id,
+            completed_at,
+            cc61_score,
+            cc67_score,
+            cc72_score,
+            a12_score,
+            overall_score: (cc61_score + cc67_score + cc72_score + a12_score) / 4.0,
+        }
+    }
+}
+
+/// Least-squares slope of the overall score per scan, oldest point first
+///
+/// Positive means compliance is improving. Fewer than two points have no trend (0.0).
+pub fn compliance_trend_slope(points: &[ComplianceDataPoint]) -> f32 {
+    let n = points.len();
+    if n < 2 {
+        return 0.0;
+    }
+
+    let mean_x = (n - 1) as f32 / 2.0;
+    let mean_y = points.iter().map(|p| p.overall_score).sum::<f32>() / n as f32;
+
+    let (covariance, variance) = points.iter().enumerate().fold((0.0, 0.0), |(cov, var), (i, p)| {
+        let dx = i as f32 - mean_x;
+        (cov + dx * (p.overall_score - mean_y), var + dx * dx)
+    });
+
+    covariance / variance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(overall_score: f32) -> ComplianceDataPoint {
+        ComplianceDataPoint {
+            scan_id: 1,
+            completed_at: String::new(),
+            cc61_score: overall_score,
+            cc67_score: overall_score,
+            cc72_score: overall_score,
+            a12_score: overall_score,
+            overall_score,
+        }
+    }
+
+    #[test]
+    fn test_control_score() {
+        assert_eq!(control_score(SeverityCounts::default()), 100.0);
+        assert_eq!(control_score((1, 1, 1, 1).into()), 73.0);
+        assert_eq!(control_score((2, 0, 3, 5).into()), 56.0);
+        // Capped at 0 rather than going negative
+        assert_eq!(control_score((10, 0, 0, 0).into()), 0.0);
+    }
+
+    #[test]
+    fn test_from_counts_scores_each_control() {
+        let counts = HashMap::from([
+            ("CC6.7".to_string(), (2, 1, 0, 0)),
+            ("A1.2".to_string(), (0, 0, 4, 2)),
+            ("CC9.9".to_string(), (5, 0, 0, 0)),
+        ]);
+
+        let point = ComplianceDataPoint::from_counts(3, "2025-01-01T00:00:00Z".to_string(), &counts);
+
+        assert_eq!(point.cc61_score, 100.0);
+        assert_eq!(point.cc67_score, 62.0);
+        assert_eq!(point.cc72_score, 100.0);
+        assert_eq!(point.a12_score, 86.0);
+        assert_eq!(point.overall_score, 87.0);
+    }
+
+    #[test]
+    fn test_trend_slope_direction() {
+        let improving: Vec<_> = [50.0, 60.0, 70.0, 80.0].map(point).to_vec();
+        assert!((compliance_trend_slope(&improving) - 10.0).abs() < 1e-4);
+
+        let declining: Vec<_> = [90.0, 85.0, 70.0, 60.0].map(point).to_vec();
+        assert!(compliance_trend_slope(&declining) < 0.0);
+
+        let flat: Vec<_> = [75.0, 75.0, 75.0].map(point).to_vec();
+        assert_eq!(compliance_trend_slope(&flat), 0.0);
+    }
+
+    #[test]
+    fn test_trend_slope_needs_two_points() {
+        assert_eq!(compliance_trend_slope(&[]), 0.0);
+        assert_eq!(compliance_trend_slope(&[point(40.0)]), 0.0);
+    }
+}
@@ -49,6 +49,17 @@ impl Control {
         )
     }
 
+    /// SOC 2 CC7.1 - Vulnerability Management
+    pub fn cc7_1() -> Self {
+        Self::new(
+            "CC7.1".to_string(),
+            "Vulnerability Management".to_string(),
+            "The organization uses detection and monitoring procedures to identify susceptibilities to newly discovered vulnerabilities, including third-party dependencies with published security advisories.".to_string(),
+            "Upgrade dependencies with known vulnerabilities to patched versions.".to_string(),
+            "CC7 - System Monitoring".to_string(),
+        )
+    }
+
     /// SOC 2 CC7.2 - System Monitoring
     pub fn cc7_2() -> Self {
         Self::new(
@@ -75,10 +86,20 @@ impl Control {
         vec![
             Control::cc6_1(),
             Control::cc6_7(),
+            Control::cc7_1(),
             Control::cc7_2(),
             Control::a1_2(),
         ]
     }
+
+    /// Controls checked by reading application code (CC7.1 findings come from
+    /// dependency advisories instead)
+    pub fn code_controls() -> Vec<Self> {
+        Self::all_controls()
+            .into_iter()
+            .filter(|control| control.id != "CC7.1")
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -115,6 +136,14 @@ mod tests {
         assert!(cc6_7.description.contains("encryption"));
     }
 
+    #[test]
+    fn test_cc7_1_control() {
+        let cc7_1 = Control::cc7_1();
+        assert_eq!(cc7_1.id, "CC7.1");
+        assert!(cc7_1.name.contains("Vulnerability"));
+        assert!(cc7_1.category.contains("CC7"));
+    }
+
     #[test]
     fn test_cc7_2_control() {
         let cc7_2 = Control::cc7_2();
@@ -134,15 +163,22 @@ mod tests {
     #[test]
     fn test_all_controls() {
         let controls = Control::all_controls();
-        assert_eq!(controls.len(), 4);
+        assert_eq!(controls.len(), 5);
 
         let ids: Vec<String> = controls.iter().map(|c| c.id.clone()).collect();
         assert!(ids.contains(&"CC6.1".to_string()));
         assert!(ids.contains(&"CC6.7".to_string()));
+        assert!(ids.contains(&"CC7.1".to_string()));
         assert!(ids.contains(&"CC7.2".to_string()));
         assert!(ids.contains(&"A1.2".to_string()));
     }
 
+    #[test]
+    fn test_code_controls_exclude_cc7_1() {
+        let ids: Vec<String> = Control::code_controls().into_iter().map(|c| c.id).collect();
+        assert_eq!(ids, vec!["CC6.1", "CC6.7", "CC7.2", "A1.2"]);
+    }
+
     #[test]
     fn test_control_serde() {
         let control = Control::cc6_1();
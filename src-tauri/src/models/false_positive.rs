@@ -0,0 +1,186 @@
+use serde::{Deserialize, Serialize};
+
+use super::Violation;
+
+/// Reports sharing a control and snippet pattern beyond this count flag the pattern for tuning
+pub const FALSE_POSITIVE_TUNING_THRESHOLD: usize = 3;
+
+/// False positives per control shown to the LLM as examples not to report
+pub const FALSE_POSITIVE_PROMPT_EXAMPLES: usize = 3;
+
+/// A user report that a detected violation was not a real issue
+///
+/// Snapshot of the violation at report time so the feedback survives
+/// even after scan history is cleared.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FalsePositive {
+    pub id: i64,
+    pub project_id: i64,
+    pub violation_id: Option<i64>,
+    pub control_id: String,
+    pub file_path: String,
+    pub code_snippet: String,
+    pub detection_method: String,
+    pub reason: String,
+    pub reported_at: String,
+}
+
+impl FalsePositive {
+    pub fn from_violation(project_id: i64, violation: &Violation, reason: String) -> Self {
+        Self {
+            id: 0,
+            project_id,
+            violation_id: Some(violation.id),
+            control_id: violation.control_id.clone(),
+            file_path: violation.file_path.clone(),
+            code_snippet: violation.code_snippet.clone(),
+            detection_method: violation.detection_method.clone(),
+            reason,
+            reported_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// Pick the reports to show the LLM as known false positives
+///
+/// Keeps the first `per_control` reports of each control from `reports`
+/// (newest first), skipping ones whose snippet pattern was already taken, so
+/// repeated reports of the same code don't crowd out other examples.
+pub fn prompt_examples(reports: &[FalsePositive], per_control: usize) -> Vec<FalsePositive> {
+    let mut seen = std::collections::HashSet::new();
+    let mut per_control_counts = std::collections::HashMap::new();
+
+    reports
+        .iter()
+        .filter(|report| !report.code_snippet.trim().is_empty())
+        .filter(|report| seen.insert((report.control_id.clone(), snippet_pattern(&report.code_snippet))))
+        .filter(|report| {
+            let count = per_control_counts.entry(report.control_id.clone()).or_insert(0);
+            *count += 1;
+            *count <= per_control
+        })
+        .cloned()
+        .collect()
+}
+
+/// Reduce a code snippet to its shape so near-identical matches group together
+///
+/// String literals become `"_"`, numbers become `0`, whitespace is collapsed and
+/// the result is lowercased. `password = "abc"` and `PASSWORD  = 'xyz'` share a pattern.
+pub fn snippet_pattern(code_snippet: &str) -> String {
+    let mut pattern = String::with_capacity(code_snippet.len());
+    let mut chars = code_snippet.trim().chars().peekable();
+    let mut last_was_space = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' | '\'' | '`' => {
+                // Skip to the matching close quote, honouring escapes
+                while let Some(inner) = chars.next() {
+                    if inner == '\\' {
+                        chars.next();
+                    } else if inner == c {
+                        break;
+                    }
+                }
+                pattern.push_str("\"_\"");
+                last_was_space = false;
+            }
+            c if c.is_ascii_digit() => {
+                while chars.peek().is_some_and(|n| n.is_ascii_digit() || *n == '.') {
+                    chars.next();
+                }
+                pattern.push('0');
+                last_was_space = false;
+            }
+            c if c.is_whitespace() => {
+                if !last_was_space {
+                    pattern.push(' ');
+                    last_was_space = true;
+                }
+            }
+            c => {
+                pattern.extend(c.to_lowercase());
+                last_was_space = false;
+            }
+        }
+    }
+
+    pattern
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Severity;
+
+    #[test]
+    fn test_from_violation_copies_fields() {
+        let mut violation = Violation::new(
+            7,
+            "CC6.7".to_string(),
+            Severity::High,
+            "Hardcoded secret".to_string(),
+            "config.py".to_string(),
+            3,
+            "API_KEY = \"abc\"".to_string(),
+        );
+        violation.id = 42;
+
+        let report = FalsePositive::from_violation(1, &violation, "Test fixture".to_string());
+        assert_eq!(report.violation_id, Some(42));
+        assert_eq!(report.control_id, "CC6.7");
+        assert_eq!(report.file_path, "config.py");
+        assert_eq!(report.detection_method, "regex");
+        assert_eq!(report.reason, "Test fixture");
+    }
+
+    #[test]
+    fn test_snippet_pattern_normalizes_literals() {
+        assert_eq!(
+            snippet_pattern("password = \"hunter2\""),
+            snippet_pattern("PASSWORD  = 'other-value'")
+        );
+        assert_eq!(snippet_pattern("user_id = 123"), snippet_pattern("user_id = 4.5"));
+        assert_eq!(snippet_pattern("token = \"a\\\"b\""), "token = \"_\"");
+    }
+
+    #[test]
+    fn test_prompt_examples_dedupes_and_limits_per_control() {
+        let report = |control_id: &str, snippet: &str| FalsePositive {
+            id: 0,
+            project_id: 1,
+            violation_id: None,
+            control_id: control_id.to_string(),
+            file_path: "tests/fixtures.py".to_string(),
+            code_snippet: snippet.to_string(),
+            detection_method: "llm".to_string(),
+            reason: "Test fixture".to_string(),
+            reported_at: String::new(),
+        };
+        let reports = vec![
+            report("CC6.7", "password = \"a\""),
+            report("CC6.7", "PASSWORD = 'b'"),
+            report("CC6.7", "token = \"c\""),
+            report("CC6.7", ""),
+            report("CC6.7", "secret = \"d\""),
+            report("CC7.2", "password = \"a\""),
+        ];
+
+        let examples = prompt_examples(&reports, 2);
+        let snippets: Vec<(&str, &str)> =
+            examples.iter().map(|r| (r.control_id.as_str(), r.code_snippet.as_str())).collect();
+        assert_eq!(
+            snippets,
+            vec![("CC6.7", "password = \"a\""), ("CC6.7", "token = \"c\""), ("CC7.2", "password = \"a\"")]
+        );
+    }
+
+    #[test]
+    fn test_snippet_pattern_keeps_structure() {
+        assert_ne!(
+            snippet_pattern("password = \"x\""),
+            snippet_pattern("password = os.getenv(\"X\")")
+        );
+    }
+}
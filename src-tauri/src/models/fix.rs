@@ -28,6 +28,52 @@ impl TrustLevel {
             _ => None,
         }
     }
+
+    /// Whether fixes at this level need more human involvement than at `other`
+    ///
+    /// Ordered auto < review < manual.
+    pub fn is_stricter_than(&self, other: TrustLevel) -> bool {
+        self.rank() > other.rank()
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            TrustLevel::Auto => 0,
+            TrustLevel::Review => 1,
+            TrustLevel::Manual => 2,
+        }
+    }
+}
+
+/// Outcome of re-scanning a file after a fix was applied
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum VerificationStatus {
+    #[serde(rename = "pending")]
+    Pending,
+    #[serde(rename = "verified")]
+    Verified,
+    #[serde(rename = "failed")]
+    Failed,
+}
+
+impl VerificationStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VerificationStatus::Pending => "pending",
+            VerificationStatus::Verified => "verified",
+            VerificationStatus::Failed => "failed",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "pending" => Some(VerificationStatus::Pending),
+            "verified" => Some(VerificationStatus::Verified),
+            "failed" => Some(VerificationStatus::Failed),
+            _ => None,
+        }
+    }
 }
 
 /// Represents an AI-generated fix for a violation
@@ -43,6 +89,26 @@ pub struct Fix {
     pub applied_by: String,
     pub git_commit_sha: Option<String>,
     pub backup_path: Option<String>,
+    // Fix verification fields (v5 schema)
+    pub verification_status: Option<String>,
+    pub verified_at: Option<String>,
+    // Fix history fields (v15 schema)
+    /// 1 for the first fix generated for the violation, 2 for the next, ...
+    #[serde(default)]
+    pub fix_attempt_number: i32,
+    #[serde(default)]
+    pub created_at: String,
+    // Fix branch field (v18 schema)
+    /// Branch the fix was committed to, when applied with `fix_on_new_branch`
+    #[serde(default)]
+    pub git_branch: Option<String>,
+    // Structured fix fields (v23 schema)
+    /// One-line summary of what the fix changes
+    #[serde(default)]
+    pub changes_summary: Option<String>,
+    /// How to test the fix, recorded in the `fix_applied` audit event
+    #[serde(default)]
+    pub test_recommendation: Option<String>,
 }
 
 impl Fix {
@@ -64,6 +130,13 @@ impl Fix {
             applied_by: "ryn-ai".to_string(),
             git_commit_sha: None,
             backup_path: None,
+            verification_status: None,
+            verified_at: None,
+            fix_attempt_number: 1,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            git_branch: None,
+            changes_summary: None,
+            test_recommendation: None,
         }
     }
 
@@ -85,6 +158,12 @@ impl Fix {
     pub fn is_applied(&self) -> bool {
         self.applied_at.is_some()
     }
+
+    pub fn get_verification_status(&self) -> Option<VerificationStatus> {
+        self.verification_status
+            .as_deref()
+            .and_then(VerificationStatus::from_str)
+    }
 }
 
 #[cfg(test)]
@@ -106,6 +185,26 @@ mod tests {
         assert_eq!(TrustLevel::from_str("invalid"), None);
     }
 
+    #[test]
+    fn test_trust_level_strictness() {
+        assert!(TrustLevel::Review.is_stricter_than(TrustLevel::Auto));
+        assert!(TrustLevel::Manual.is_stricter_than(TrustLevel::Review));
+        assert!(!TrustLevel::Auto.is_stricter_than(TrustLevel::Review));
+        assert!(!TrustLevel::Review.is_stricter_than(TrustLevel::Review));
+    }
+
+    #[test]
+    fn test_verification_status_roundtrip() {
+        for status in [
+            VerificationStatus::Pending,
+            VerificationStatus::Verified,
+            VerificationStatus::Failed,
+        ] {
+            assert_eq!(VerificationStatus::from_str(status.as_str()), Some(status));
+        }
+        assert_eq!(VerificationStatus::from_str("invalid"), None);
+    }
+
     #[test]
     fn test_fix_creation() {
         let fix = Fix::new(
@@ -121,6 +220,7 @@ mod tests {
         assert_eq!(fix.applied_by, "ryn-ai");
         assert_eq!(fix.applied_at, None);
         assert!(!fix.is_applied());
+        assert_eq!(fix.get_verification_status(), None);
     }
 
     #[test]
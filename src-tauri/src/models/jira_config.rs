@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+
+/// Issue type used when a config doesn't name one
+pub const DEFAULT_JIRA_ISSUE_TYPE: &str = "Task";
+
+/// Jira project a Ryn project files remediation tickets in, stored in `jira_configs`
+///
+/// The API token is kept in the OS keychain, not the database; `api_token`
+/// only carries it in from `link_jira`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JiraConfig {
+    #[serde(default)]
+    pub project_id: i64,
+    /// Site URL, e.g. "https://acme.atlassian.net"
+    pub base_url: String,
+    /// Account the API token belongs to
+    pub email: String,
+    /// Jira project key, e.g. "SEC"
+    pub project_key: String,
+    #[serde(default = "default_issue_type")]
+    pub issue_type: String,
+    /// Extra labels added to every issue besides "ryn" and the control ID
+    #[serde(default)]
+    pub labels: Vec<String>,
+    #[serde(default, skip_serializing)]
+    pub api_token: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+fn default_issue_type() -> String {
+    DEFAULT_JIRA_ISSUE_TYPE.to_string()
+}
+
+impl JiraConfig {
+    /// Keychain account holding a project's Jira API token
+    pub fn token_account(project_id: i64) -> String {
+        format!("jira-api-token-{}", project_id)
+    }
+
+    /// Site URL without a trailing slash
+    pub fn site_url(&self) -> &str {
+        self.base_url.trim_end_matches('/')
+    }
+
+    /// Browser link to an issue
+    pub fn issue_url(&self, issue_key: &str) -> String {
+        format!("{}/browse/{}", self.site_url(), issue_key)
+    }
+
+    /// Check the fields before the config is saved
+    ///
+    /// # Errors
+    /// Returns a message naming the invalid field
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.base_url.starts_with("https://") && !self.base_url.starts_with("http://") {
+            return Err(format!("Invalid Jira URL: {}", self.base_url));
+        }
+        if !self.email.contains('@') {
+            return Err(format!("Invalid Jira account email: {}", self.email));
+        }
+        // Jira keys start with a letter and hold uppercase letters, digits and underscores
+        let key_is_valid = self.project_key.chars().next().is_some_and(|c| c.is_ascii_uppercase())
+            && self.project_key.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+        if !key_is_valid {
+            return Err(format!("Invalid Jira project key: {}", self.project_key));
+        }
+        if self.issue_type.trim().is_empty() {
+            return Err("Jira issue type cannot be empty".to_string());
+        }
+        // Jira rejects labels containing spaces
+        if let Some(label) = self.labels.iter().find(|l| l.is_empty() || l.contains(char::is_whitespace)) {
+            return Err(format!("Invalid Jira label '{}': labels cannot be empty or contain spaces", label));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> JiraConfig {
+        JiraConfig {
+            project_id: 1,
+            base_url: "https://acme.atlassian.net/".to_string(),
+            email: "security@acme.com".to_string(),
+            project_key: "SEC".to_string(),
+            issue_type: default_issue_type(),
+            labels: vec!["soc2".to_string()],
+            api_token: Some("token".to_string()),
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_issue_url() {
+        assert_eq!(config().issue_url("SEC-12"), "https://acme.atlassian.net/browse/SEC-12");
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(config().validate().is_ok());
+
+        let url = JiraConfig { base_url: "acme.atlassian.net".to_string(), ..config() };
+        assert!(url.validate().unwrap_err().contains("URL"));
+        let key = JiraConfig { project_key: "sec".to_string(), ..config() };
+        assert!(key.validate().unwrap_err().contains("project key"));
+        let label = JiraConfig { labels: vec!["needs review".to_string()], ..config() };
+        assert!(label.validate().is_err());
+    }
+
+    #[test]
+    fn test_api_token_not_serialized() {
+        let json = serde_json::to_string(&config()).unwrap();
+        assert!(!json.contains("api_token"));
+    }
+}
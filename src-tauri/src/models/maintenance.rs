@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+/// Settings key recording when the database was last optimized (RFC 3339)
+pub const LAST_OPTIMIZED_AT_SETTING: &str = "last_optimized_at";
+
+/// Result of a database optimization run (`PRAGMA optimize`, `ANALYZE`, `VACUUM`)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MaintenanceStats {
+    /// Pages returned to the file system by `VACUUM`
+    pub pages_freed: i64,
+    pub size_before_bytes: u64,
+    pub size_after_bytes: u64,
+    pub duration_ms: u64,
+}
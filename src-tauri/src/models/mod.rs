@@ -8,13 +8,37 @@ pub mod audit;
 pub mod control;
 pub mod settings;
 pub mod scan_cost;
+pub mod false_positive;
+pub mod namespace;
+pub mod slack_config;
+pub mod settings_profile;
+pub mod maintenance;
+pub mod compliance;
+pub mod onboarding;
+pub mod scan_schedule;
+pub mod project_settings;
+pub mod project_root;
+pub mod violation_history;
+pub mod jira_config;
 
 // Re-exports for convenience
 pub use project::Project;
-pub use scan::{Scan, ScanStatus};
-pub use violation::{Violation, Severity, ViolationStatus, DetectionMethod};
-pub use fix::{Fix, TrustLevel};
+pub use scan::{Scan, ScanStatus, ScanDetailStats, SeverityCounts, ControlTrendPoint};
+pub use violation::{Violation, Severity, ViolationStatus, DetectionMethod, InvalidTransitionError, StatusTransition};
+pub use fix::{Fix, TrustLevel, VerificationStatus};
 pub use audit::{AuditEvent, AuditEventType};
 pub use control::Control;
 pub use settings::Settings;
-pub use scan_cost::{ScanCost, ClaudePricing};
+pub use scan_cost::{ScanCost, ClaudePricing, DailySpend, SpendSummary};
+pub use false_positive::{FalsePositive, FALSE_POSITIVE_PROMPT_EXAMPLES, FALSE_POSITIVE_TUNING_THRESHOLD};
+pub use namespace::{Namespace, DEFAULT_NAMESPACE};
+pub use slack_config::SlackConfig;
+pub use settings_profile::{SettingsProfile, SettingsProfileExport};
+pub use maintenance::{MaintenanceStats, LAST_OPTIMIZED_AT_SETTING};
+pub use compliance::{ComplianceDataPoint, compliance_trend_slope};
+pub use onboarding::{OnboardingStep, OnboardingStatus, ApiKeyValidation, OnboardingStepProgress, OnboardingProgress};
+pub use scan_schedule::ScanSchedule;
+pub use project_settings::ProjectSettings;
+pub use project_root::ProjectRoot;
+pub use violation_history::{ViolationLifecycle, ViolationHistoryEntry, LifecycleCounts, violation_fingerprint};
+pub use jira_config::{JiraConfig, DEFAULT_JIRA_ISSUE_TYPE};
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+/// Namespace every installation starts with; existing projects are migrated into it
+pub const DEFAULT_NAMESPACE: &str = "default";
+
+/// An isolated set of projects, typically one per team
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Namespace {
+    pub name: String,
+    pub created_at: String,
+}
+
+impl Namespace {
+    /// Check that a namespace name is non-empty and limited to `[A-Za-z0-9_-]`, at most 64 chars
+    pub fn is_valid_name(name: &str) -> bool {
+        !name.is_empty()
+            && name.len() <= 64
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_name() {
+        assert!(Namespace::is_valid_name("default"));
+        assert!(Namespace::is_valid_name("team-payments_2"));
+        assert!(!Namespace::is_valid_name(""));
+        assert!(!Namespace::is_valid_name("team a"));
+        assert!(!Namespace::is_valid_name("../etc"));
+        assert!(!Namespace::is_valid_name(&"a".repeat(65)));
+    }
+}
@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+
+/// A step of the first-run onboarding wizard, in wizard order
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum OnboardingStep {
+    /// XAI_API_KEY set and accepted by the Grok API
+    #[serde(rename = "api_key")]
+    ApiKey,
+    #[serde(rename = "first_project")]
+    FirstProject,
+    #[serde(rename = "first_scan")]
+    FirstScan,
+}
+
+impl OnboardingStep {
+    /// All steps, in wizard order
+    pub const ALL: [OnboardingStep; 3] = [
+        OnboardingStep::ApiKey,
+        OnboardingStep::FirstProject,
+        OnboardingStep::FirstScan,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OnboardingStep::ApiKey => "api_key",
+            OnboardingStep::FirstProject => "first_project",
+            OnboardingStep::FirstScan => "first_scan",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "api_key" => Some(OnboardingStep::ApiKey),
+            "first_project" => Some(OnboardingStep::FirstProject),
+            "first_scan" => Some(OnboardingStep::FirstScan),
+            _ => None,
+        }
+    }
+
+    /// Settings key set to "true" once the step is done,
+    /// e.g. `onboarding_step_api_key`
+    pub fn setting_key(&self) -> String {
+        format!("onboarding_step_{}", self.as_str())
+    }
+}
+
+/// Where a user is in onboarding, from `start_onboarding_check`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OnboardingStatus {
+    /// `complete_onboarding` has saved the user's scan preferences
+    pub completed: bool,
+    /// XAI_API_KEY is present (the `ApiKey` step also needs it validated)
+    pub api_key_set: bool,
+    pub first_project_created: bool,
+    pub first_scan_completed: bool,
+    /// Steps not done yet, in wizard order
+    pub steps_remaining: Vec<OnboardingStep>,
+}
+
+/// Result of sending a test prompt with the configured API key
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApiKeyValidation {
+    pub valid: bool,
+    /// Why the key was rejected or the API couldn't be reached
+    pub error: Option<String>,
+    /// Round trip of the test prompt, when the API answered
+    pub latency_ms: Option<u64>,
+}
+
+/// Completion state of one onboarding step
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OnboardingStepProgress {
+    pub step: OnboardingStep,
+    pub completed: bool,
+    /// When the step was recorded as done (RFC 3339)
+    pub completed_at: Option<String>,
+}
+
+/// Per-step onboarding progress, from `get_onboarding_progress`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OnboardingProgress {
+    pub steps: Vec<OnboardingStepProgress>,
+    pub completed_steps: usize,
+    pub total_steps: usize,
+}
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use super::namespace::DEFAULT_NAMESPACE;
+
 /// Represents a scanned project
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Project {
@@ -9,6 +11,10 @@ pub struct Project {
     pub framework: Option<String>,
     pub created_at: String,
     pub updated_at: String,
+    pub namespace: String,
+    /// Cost limit for this project's scans, overriding `cost_limit_per_scan`
+    #[serde(default)]
+    pub project_cost_limit_usd: Option<f64>,
 }
 
 impl Project {
@@ -20,6 +26,8 @@ impl Project {
             framework: None,
             created_at: chrono::Utc::now().to_rfc3339(),
             updated_at: chrono::Utc::now().to_rfc3339(),
+            namespace: DEFAULT_NAMESPACE.to_string(),
+            project_cost_limit_usd: None,
         }
     }
 
@@ -39,6 +47,8 @@ mod tests {
         assert_eq!(project.name, "my-app");
         assert_eq!(project.path, "/path/to/app");
         assert_eq!(project.framework, None);
+        assert_eq!(project.namespace, "default");
+        assert_eq!(project.project_cost_limit_usd, None);
     }
 
     #[test]
@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+
+/// An extra directory scanned as part of a project, stored in `project_roots`
+///
+/// The project's own path is its primary root; extra roots (e.g. a frontend
+/// repo next to the backend) are walked by the same scan. Violations in an
+/// extra root record their path as `@{label}/{path within the root}`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProjectRoot {
+    pub id: i64,
+    pub project_id: i64,
+    pub path: String,
+    /// Unique within the project; defaults to the directory name
+    pub label: String,
+    /// Detected when the root is added and refreshed by each scan
+    pub framework: Option<String>,
+    pub created_at: String,
+}
+
+impl ProjectRoot {
+    /// Check a label can prefix violation paths
+    ///
+    /// # Errors
+    /// Returns a message if the label is empty, too long or has characters
+    /// other than letters, digits, '-', '_' and '.'
+    pub fn validate_label(label: &str) -> Result<(), String> {
+        if label.is_empty() || label.len() > 64 {
+            return Err("Root label must be 1-64 characters".to_string());
+        }
+        if label == "." || label == ".." {
+            return Err(format!("Invalid root label '{}'", label));
+        }
+        if !label.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')) {
+            return Err(format!(
+                "Invalid root label '{}': use letters, digits, '-', '_' and '.'",
+                label
+            ));
+        }
+        Ok(())
+    }
+
+    /// Label used when none is given: the directory name, with other characters replaced by '-'
+    pub fn default_label(path: &str) -> String {
+        let name = std::path::Path::new(path)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let label: String = name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') { c } else { '-' })
+            .take(64)
+            .collect();
+        if Self::validate_label(&label).is_ok() {
+            label
+        } else {
+            "root".to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_label() {
+        assert!(ProjectRoot::validate_label("frontend").is_ok());
+        assert!(ProjectRoot::validate_label("web-app_2.0").is_ok());
+        assert!(ProjectRoot::validate_label("").is_err());
+        assert!(ProjectRoot::validate_label("..").is_err());
+        assert!(ProjectRoot::validate_label("a/b").is_err());
+        assert!(ProjectRoot::validate_label(&"a".repeat(65)).is_err());
+    }
+
+    #[test]
+    fn test_default_label() {
+        assert_eq!(ProjectRoot::default_label("/work/frontend"), "frontend");
+        assert_eq!(ProjectRoot::default_label("/work/My App"), "My-App");
+        assert_eq!(ProjectRoot::default_label("/"), "root");
+    }
+}
@@ -0,0 +1,132 @@
+use serde::{Deserialize, Serialize};
+
+use super::violation::{DetectionMethod, Severity, Violation};
+
+/// Per-project reporting thresholds, stored in `project_settings`
+///
+/// A project without a row reports everything, as if every field were unset.
+/// Violations below a threshold are dropped when a scan merges its findings,
+/// so they're never stored.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ProjectSettings {
+    pub project_id: i64,
+    /// Lowest severity to report ("low", "medium", "high", "critical"); None reports all
+    pub min_severity: Option<String>,
+    /// Lowest confidence (0-100) for LLM-only findings to be kept; None keeps all
+    pub min_llm_confidence: Option<i64>,
+    /// Control IDs (e.g. "CC7.2") whose violations aren't reported
+    pub disabled_controls: Vec<String>,
+    pub updated_at: Option<String>,
+}
+
+impl ProjectSettings {
+    /// Check the thresholds are in range
+    ///
+    /// # Errors
+    /// Returns a message naming the invalid field
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(severity) = &self.min_severity {
+            if Severity::from_str(severity).is_none() {
+                return Err(format!(
+                    "Invalid minimum severity '{}': expected low, medium, high or critical",
+                    severity
+                ));
+            }
+        }
+        if let Some(confidence) = self.min_llm_confidence {
+            if !(0..=100).contains(&confidence) {
+                return Err(format!("Minimum LLM confidence must be between 0 and 100, got {}", confidence));
+            }
+        }
+        if self.disabled_controls.iter().any(|control| control.trim().is_empty()) {
+            return Err("Disabled control IDs cannot be empty".to_string());
+        }
+        Ok(())
+    }
+
+    /// Whether `violation` meets the project's thresholds
+    ///
+    /// The confidence threshold only applies to violations found by the LLM
+    /// alone; regex and hybrid findings are kept whatever their score.
+    pub fn allows(&self, violation: &Violation) -> bool {
+        if self.disabled_controls.contains(&violation.control_id) {
+            return false;
+        }
+
+        let min_severity = self.min_severity.as_deref().and_then(Severity::from_str);
+        if let (Some(min), Some(severity)) = (min_severity, violation.get_severity()) {
+            if severity.numeric_value() < min.numeric_value() {
+                return false;
+            }
+        }
+
+        match (self.min_llm_confidence, violation.confidence_score) {
+            (Some(min), Some(score)) if violation.detection_method == DetectionMethod::Llm.as_str() => score >= min,
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn violation(control_id: &str, severity: Severity) -> Violation {
+        Violation::new(
+            1,
+            control_id.to_string(),
+            severity,
+            "Issue".to_string(),
+            "app.py".to_string(),
+            10,
+            "code".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_default_allows_everything() {
+        let settings = ProjectSettings::default();
+        assert!(settings.validate().is_ok());
+        assert!(settings.allows(&violation("CC7.2", Severity::Low)));
+    }
+
+    #[test]
+    fn test_thresholds() {
+        let settings = ProjectSettings {
+            project_id: 1,
+            min_severity: Some("high".to_string()),
+            min_llm_confidence: Some(80),
+            disabled_controls: vec!["A1.2".to_string()],
+            updated_at: None,
+        };
+
+        assert!(settings.allows(&violation("CC6.7", Severity::Critical)));
+        assert!(!settings.allows(&violation("CC6.7", Severity::Medium)));
+        assert!(!settings.allows(&violation("A1.2", Severity::Critical)));
+
+        let mut llm = violation("CC6.1", Severity::High);
+        llm.set_detection_method(DetectionMethod::Llm);
+        llm.confidence_score = Some(70);
+        assert!(!settings.allows(&llm));
+        llm.confidence_score = Some(80);
+        assert!(settings.allows(&llm));
+
+        // Confirmed by a regex match, so a low score doesn't drop it
+        llm.set_detection_method(DetectionMethod::Hybrid);
+        llm.confidence_score = Some(50);
+        assert!(settings.allows(&llm));
+    }
+
+    #[test]
+    fn test_validate_rejects_out_of_range() {
+        let severity = ProjectSettings { min_severity: Some("severe".to_string()), ..Default::default() };
+        assert!(severity.validate().unwrap_err().contains("severe"));
+
+        let confidence = ProjectSettings { min_llm_confidence: Some(101), ..Default::default() };
+        assert!(confidence.validate().is_err());
+
+        let control = ProjectSettings { disabled_controls: vec![" ".to_string()], ..Default::default() };
+        assert!(control.validate().is_err());
+    }
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
@@ -9,6 +10,12 @@ pub enum ScanStatus {
     Completed,
     #[serde(rename = "failed")]
     Failed,
+    /// Synthetic scan holding results imported from a SARIF file
+    #[serde(rename = "imported")]
+    Imported,
+    /// Stopped at the scan timeout; holds the violations found up to that point
+    #[serde(rename = "partial")]
+    Partial,
 }
 
 impl ScanStatus {
@@ -17,6 +24,8 @@ impl ScanStatus {
             ScanStatus::Running => "running",
             ScanStatus::Completed => "completed",
             ScanStatus::Failed => "failed",
+            ScanStatus::Imported => "imported",
+            ScanStatus::Partial => "partial",
         }
     }
 
@@ -25,6 +34,8 @@ impl ScanStatus {
             "running" => Some(ScanStatus::Running),
             "completed" => Some(ScanStatus::Completed),
             "failed" => Some(ScanStatus::Failed),
+            "imported" => Some(ScanStatus::Imported),
+            "partial" => Some(ScanStatus::Partial),
             _ => None,
         }
     }
@@ -42,10 +53,38 @@ pub struct Scan {
     pub violations_found: i32,
     pub status: String,
     pub scan_mode: String,
+    /// Why a partial scan stopped early, e.g. "Timed out after 30m during LLM analysis"
+    #[serde(default)]
+    pub partial_reason: Option<String>,
+    /// Earlier scan this scan was compared against, if any
+    #[serde(default)]
+    pub baseline_scan_id: Option<i64>,
+    /// Violations with no match in the baseline scan
+    #[serde(default)]
+    pub new_violation_count: i32,
+    /// Open baseline violations no longer found by this scan
+    #[serde(default)]
+    pub resolved_violation_count: i32,
+    /// Violations given tree-sitter function/class context
+    #[serde(default)]
+    pub enriched_count: i32,
+    /// Violations whose file couldn't be read or parsed for context
+    #[serde(default)]
+    pub context_extraction_failed: i32,
+    /// Files skipped because their rules exceeded the per-file timeout
+    /// (None for scans from before the timeout existed)
+    #[serde(default)]
+    pub processing_timeouts: Option<i32>,
+    /// HEAD commit when the scan started (None outside git repositories)
+    #[serde(default)]
+    pub commit_sha: Option<String>,
     pub critical_count: i32,
     pub high_count: i32,
     pub medium_count: i32,
     pub low_count: i32,
+    /// Per-control breakdown, filled in by `get_scan_progress`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail_stats: Option<ScanDetailStats>,
 }
 
 impl Scan {
@@ -60,10 +99,19 @@ impl Scan {
             violations_found: 0,
             status: ScanStatus::Running.as_str().to_string(),
             scan_mode,
+            partial_reason: None,
+            baseline_scan_id: None,
+            new_violation_count: 0,
+            resolved_violation_count: 0,
+            enriched_count: 0,
+            context_extraction_failed: 0,
+            processing_timeouts: None,
+            commit_sha: None,
             critical_count: 0,
             high_count: 0,
             medium_count: 0,
             low_count: 0,
+            detail_stats: None,
         }
     }
 
@@ -88,6 +136,38 @@ impl Scan {
     }
 }
 
+/// Violation counts by severity for one control
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SeverityCounts {
+    pub critical: i64,
+    pub high: i64,
+    pub medium: i64,
+    pub low: i64,
+}
+
+impl From<(i64, i64, i64, i64)> for SeverityCounts {
+    fn from((critical, high, medium, low): (i64, i64, i64, i64)) -> Self {
+        Self { critical, high, medium, low }
+    }
+}
+
+/// A control's violation count in one completed scan
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ControlTrendPoint {
+    pub completed_at: String,
+    pub violation_count: i64,
+}
+
+/// Per-control statistics for a scan
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ScanDetailStats {
+    pub scan_id: i64,
+    /// Severity counts keyed by control_id (controls without violations are omitted)
+    pub by_control: BTreeMap<String, SeverityCounts>,
+    /// Violation counts over the project's most recent completed scans, oldest first
+    pub trends: BTreeMap<String, Vec<ControlTrendPoint>>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -97,6 +177,8 @@ mod tests {
         assert_eq!(ScanStatus::Running.as_str(), "running");
         assert_eq!(ScanStatus::Completed.as_str(), "completed");
         assert_eq!(ScanStatus::Failed.as_str(), "failed");
+        assert_eq!(ScanStatus::Imported.as_str(), "imported");
+        assert_eq!(ScanStatus::Partial.as_str(), "partial");
     }
 
     #[test]
@@ -104,6 +186,8 @@ mod tests {
         assert_eq!(ScanStatus::from_str("running"), Some(ScanStatus::Running));
         assert_eq!(ScanStatus::from_str("completed"), Some(ScanStatus::Completed));
         assert_eq!(ScanStatus::from_str("failed"), Some(ScanStatus::Failed));
+        assert_eq!(ScanStatus::from_str("imported"), Some(ScanStatus::Imported));
+        assert_eq!(ScanStatus::from_str("partial"), Some(ScanStatus::Partial));
         assert_eq!(ScanStatus::from_str("invalid"), None);
     }
 
@@ -116,6 +200,7 @@ mod tests {
         assert_eq!(scan.violations_found, 0);
         assert_eq!(scan.completed_at, None);
         assert_eq!(scan.scan_mode, "regex_only");
+        assert_eq!(scan.partial_reason, None);
     }
 
     #[test]
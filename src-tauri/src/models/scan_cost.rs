@@ -131,6 +131,29 @@ impl ScanCost {
     }
 }
 
+/// Aggregated LLM spend across a set of scans
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct SpendSummary {
+    pub total_cost_usd: f64,
+    pub total_input_tokens: i64,
+    pub total_output_tokens: i64,
+    pub total_cache_read_tokens: i64,
+    /// Scans that incurred LLM cost
+    pub scan_count: i64,
+    /// 0.0 when no scans incurred cost
+    pub avg_cost_per_scan: f64,
+    pub most_expensive_scan_id: Option<i64>,
+}
+
+/// LLM spend for one calendar day (UTC)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DailySpend {
+    /// YYYY-MM-DD
+    pub date: String,
+    pub total_cost_usd: f64,
+    pub scan_count: i64,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// A recurring scan of a project, run by the background scheduler
+///
+/// Times are UTC RFC 3339 to the second; `cron_expr` is evaluated in the
+/// machine's local time zone.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScanSchedule {
+    pub id: i64,
+    pub project_id: i64,
+    /// Five-field cron expression, e.g. "0 2 * * *" for 02:00 every night
+    pub cron_expr: String,
+    pub enabled: bool,
+    pub last_run_at: Option<String>,
+    /// Scan started by the last run (None if it failed before creating one)
+    pub last_scan_id: Option<i64>,
+    pub next_run_at: String,
+    pub created_at: String,
+}
+
+impl ScanSchedule {
+    pub fn new(project_id: i64, cron_expr: String, next_run_at: String) -> Self {
+        Self {
+            id: 0,
+            project_id,
+            cron_expr,
+            enabled: true,
+            last_run_at: None,
+            last_scan_id: None,
+            next_run_at,
+            created_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Named set of settings that can be applied in one step
+///
+/// Built-in profiles (development, staging, production) are seeded by the
+/// migrations and can't be replaced by user profiles of the same name.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SettingsProfile {
+    pub id: i64,
+    pub name: String,
+    pub description: String,
+    /// Setting key/value pairs written to the settings table on apply
+    pub settings: HashMap<String, String>,
+    #[serde(default)]
+    pub built_in: bool,
+    #[serde(default)]
+    pub created_at: String,
+}
+
+/// Portable form of a profile written by `export_profile` and read by `import_profile`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SettingsProfileExport {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub settings: HashMap<String, String>,
+}
+
+impl SettingsProfile {
+    pub fn new(name: String, description: String, settings: HashMap<String, String>) -> Self {
+        Self {
+            id: 0,
+            name,
+            description,
+            settings,
+            built_in: false,
+            created_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Presets for each deployment environment
+    pub fn built_in_profiles() -> Vec<Self> {
+        let profile = |name: &str, description: &str, settings: &[(&str, &str)]| Self {
+            built_in: true,
+            ..Self::new(
+                name.to_string(),
+                description.to_string(),
+                settings
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+                    .collect(),
+            )
+        };
+
+        vec![
+            profile(
+                "development",
+                "Regex-only scanning with no LLM usage or cost",
+                &[("llm_scan_mode", "regex_only")],
+            ),
+            profile(
+                "staging",
+                "Smart LLM scanning of security-relevant files, $1.00 per scan",
+                &[("llm_scan_mode", "smart"), ("cost_limit_per_scan", "1.00")],
+            ),
+            profile(
+                "production",
+                "LLM analysis of every file, $5.00 per scan, findings at 80%+ confidence",
+                &[
+                    ("llm_scan_mode", "analyze_all"),
+                    ("cost_limit_per_scan", "5.00"),
+                    ("min_confidence", "80"),
+                ],
+            ),
+        ]
+    }
+
+    pub fn to_export(&self) -> SettingsProfileExport {
+        SettingsProfileExport {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            settings: self.settings.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_built_in_profiles() {
+        let profiles = SettingsProfile::built_in_profiles();
+        let names: Vec<&str> = profiles.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["development", "staging", "production"]);
+        assert!(profiles.iter().all(|p| p.built_in));
+
+        let production = &profiles[2];
+        assert_eq!(production.settings["llm_scan_mode"], "analyze_all");
+        assert_eq!(production.settings["cost_limit_per_scan"], "5.00");
+        assert_eq!(production.settings["min_confidence"], "80");
+        assert!(!profiles[0].settings.contains_key("cost_limit_per_scan"));
+    }
+
+    #[test]
+    fn test_export_round_trip() {
+        let profile = SettingsProfile::built_in_profiles().remove(1);
+        let json = serde_json::to_string(&profile.to_export()).unwrap();
+        let export: SettingsProfileExport = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(export.name, "staging");
+        assert_eq!(export.settings, profile.settings);
+        assert!(!json.contains("built_in"));
+    }
+}
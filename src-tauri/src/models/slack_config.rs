@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+
+/// Slack incoming-webhook destination for scan alerts
+///
+/// `project_id = None` applies the config to every project in the namespace.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SlackConfig {
+    pub id: i64,
+    pub webhook_url: String,
+    pub channel: Option<String>,
+    /// Severities that trigger a notification, e.g. `["critical", "high"]`
+    pub notify_on_severities: Vec<String>,
+    pub project_id: Option<i64>,
+    pub enabled: bool,
+    /// Shared secret used to HMAC-sign outgoing requests (never serialized back to the UI)
+    #[serde(default, skip_serializing)]
+    pub signing_secret: Option<String>,
+    pub created_at: String,
+}
+
+impl SlackConfig {
+    pub fn new(webhook_url: String, notify_on_severities: Vec<String>) -> Self {
+        Self {
+            id: 0,
+            webhook_url,
+            channel: None,
+            notify_on_severities,
+            project_id: None,
+            enabled: true,
+            signing_secret: None,
+            created_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Whether this config should fire for a scan of `project_id`
+    pub fn applies_to_project(&self, project_id: i64) -> bool {
+        self.enabled && self.project_id.is_none_or(|id| id == project_id)
+    }
+
+    /// Whether violations of `severity` should be included in notifications
+    pub fn notifies_on(&self, severity: &str) -> bool {
+        self.notify_on_severities.iter().any(|s| s == severity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_applies_to_project() {
+        let mut config = SlackConfig::new("https://hooks.slack.com/x".to_string(), vec![]);
+        assert!(config.applies_to_project(1));
+        assert!(config.applies_to_project(2));
+
+        config.project_id = Some(1);
+        assert!(config.applies_to_project(1));
+        assert!(!config.applies_to_project(2));
+
+        config.enabled = false;
+        assert!(!config.applies_to_project(1));
+    }
+
+    #[test]
+    fn test_notifies_on() {
+        let config = SlackConfig::new(
+            "https://hooks.slack.com/x".to_string(),
+            vec!["critical".to_string(), "high".to_string()],
+        );
+        assert!(config.notifies_on("critical"));
+        assert!(config.notifies_on("high"));
+        assert!(!config.notifies_on("medium"));
+    }
+
+    #[test]
+    fn test_signing_secret_not_serialized() {
+        let mut config = SlackConfig::new("https://hooks.slack.com/x".to_string(), vec![]);
+        config.signing_secret = Some("shh".to_string());
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(!json.contains("shh"));
+    }
+}
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use super::violation_history::{violation_fingerprint, ViolationLifecycle};
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum Severity {
@@ -22,6 +24,9 @@ pub enum DetectionMethod {
     Llm,
     #[serde(rename = "hybrid")]
     Hybrid,
+    /// Imported from another tool's results (SARIF)
+    #[serde(rename = "imported")]
+    Imported,
 }
 
 impl DetectionMethod {
@@ -30,6 +35,7 @@ impl DetectionMethod {
             DetectionMethod::Regex => "regex",
             DetectionMethod::Llm => "llm",
             DetectionMethod::Hybrid => "hybrid",
+            DetectionMethod::Imported => "imported",
         }
     }
 
@@ -38,6 +44,7 @@ impl DetectionMethod {
             "regex" => Some(DetectionMethod::Regex),
             "llm" => Some(DetectionMethod::Llm),
             "hybrid" => Some(DetectionMethod::Hybrid),
+            "imported" => Some(DetectionMethod::Imported),
             _ => None,
         }
     }
@@ -71,6 +78,15 @@ impl Severity {
             Severity::Low => 1,
         }
     }
+
+    /// One level lower, bottoming out at `Low`
+    pub fn downgrade(&self) -> Self {
+        match self {
+            Severity::Critical => Severity::High,
+            Severity::High => Severity::Medium,
+            Severity::Medium | Severity::Low => Severity::Low,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -80,8 +96,16 @@ pub enum ViolationStatus {
     Open,
     #[serde(rename = "fixed")]
     Fixed,
+    #[serde(rename = "fixed_verified")]
+    FixedVerified,
     #[serde(rename = "dismissed")]
     Dismissed,
+    /// No longer found by a scan compared against the scan that reported it
+    #[serde(rename = "resolved")]
+    Resolved,
+    /// Acknowledged but deliberately not fixed (business reason recorded)
+    #[serde(rename = "accepted_risk")]
+    AcceptedRisk,
 }
 
 impl ViolationStatus {
@@ -89,7 +113,10 @@ impl ViolationStatus {
         match self {
             ViolationStatus::Open => "open",
             ViolationStatus::Fixed => "fixed",
+            ViolationStatus::FixedVerified => "fixed_verified",
             ViolationStatus::Dismissed => "dismissed",
+            ViolationStatus::Resolved => "resolved",
+            ViolationStatus::AcceptedRisk => "accepted_risk",
         }
     }
 
@@ -97,10 +124,58 @@ impl ViolationStatus {
         match s {
             "open" => Some(ViolationStatus::Open),
             "fixed" => Some(ViolationStatus::Fixed),
+            "fixed_verified" => Some(ViolationStatus::FixedVerified),
             "dismissed" => Some(ViolationStatus::Dismissed),
+            "resolved" => Some(ViolationStatus::Resolved),
+            "accepted_risk" => Some(ViolationStatus::AcceptedRisk),
             _ => None,
         }
     }
+
+    /// Statuses a violation in this status may move to
+    ///
+    /// Open violations can be dismissed, fixed, accepted as a risk or resolved
+    /// by a baseline scan; applied fixes can be verified. Every other status
+    /// can only go back to open (reopened, or a fix regressed).
+    pub fn valid_transitions(&self) -> Vec<ViolationStatus> {
+        match self {
+            ViolationStatus::Open => vec![
+                ViolationStatus::Dismissed,
+                ViolationStatus::Fixed,
+                ViolationStatus::AcceptedRisk,
+                ViolationStatus::Resolved,
+            ],
+            ViolationStatus::Fixed => vec![ViolationStatus::Open, ViolationStatus::FixedVerified],
+            ViolationStatus::FixedVerified
+            | ViolationStatus::Dismissed
+            | ViolationStatus::Resolved
+            | ViolationStatus::AcceptedRisk => vec![ViolationStatus::Open],
+        }
+    }
+
+    pub fn can_transition_to(&self, to: ViolationStatus) -> bool {
+        self.valid_transitions().contains(&to)
+    }
+}
+
+/// A status change the violation state machine does not allow
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("Invalid status transition for violation {violation_id}: {} -> {}", .from.as_str(), .to.as_str())]
+pub struct InvalidTransitionError {
+    pub violation_id: i64,
+    pub from: ViolationStatus,
+    pub to: ViolationStatus,
+}
+
+/// One entry of a violation's status history
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StatusTransition {
+    pub id: i64,
+    pub violation_id: i64,
+    pub from_status: String,
+    pub to_status: String,
+    pub reason: Option<String>,
+    pub changed_at: String,
 }
 
 /// Represents a SOC 2 compliance violation found in code
@@ -124,6 +199,33 @@ pub struct Violation {
     // Tree-sitter context fields (v3 schema)
     pub function_name: Option<String>,
     pub class_name: Option<String>,
+    /// Innermost decorator on the enclosing method or class (v11 schema)
+    pub decorator_name: Option<String>,
+    /// Not present in the baseline scan this scan was compared against (v13 schema)
+    #[serde(default)]
+    pub is_new: bool,
+    /// Reason given for the last bulk status change (v19 schema)
+    #[serde(default)]
+    pub last_status_change_reason: Option<String>,
+    /// Monorepo package containing the file (v28 schema)
+    #[serde(default)]
+    pub package_name: Option<String>,
+    /// Identity across scans, see `violation_fingerprint` (v29 schema)
+    #[serde(default)]
+    pub fingerprint: String,
+    /// "new" or "recurring" relative to the project's earlier scans (v29 schema)
+    #[serde(default)]
+    pub lifecycle: String,
+    /// Key of the Jira issue tracking remediation, e.g. "SEC-142" (v30 schema)
+    #[serde(default)]
+    pub jira_issue_key: Option<String>,
+    /// Earliest scan of the project reporting this finding (computed by
+    /// `select_unique_violations_for_project`, not stored)
+    #[serde(default)]
+    pub first_seen_scan_id: Option<i64>,
+    /// Latest scan of the project reporting this finding (computed, not stored)
+    #[serde(default)]
+    pub last_seen_scan_id: Option<i64>,
 }
 
 impl Violation {
@@ -136,6 +238,7 @@ impl Violation {
         line_number: i64,
         code_snippet: String,
     ) -> Self {
+        let fingerprint = violation_fingerprint(&control_id, &file_path, &code_snippet);
         Self {
             id: 0,
             scan_id,
@@ -155,6 +258,15 @@ impl Violation {
             // Tree-sitter context fields default to None
             function_name: None,
             class_name: None,
+            decorator_name: None,
+            is_new: false,
+            last_status_change_reason: None,
+            package_name: None,
+            fingerprint,
+            lifecycle: ViolationLifecycle::New.as_str().to_string(),
+            jira_issue_key: None,
+            first_seen_scan_id: None,
+            last_seen_scan_id: None,
         }
     }
 
@@ -182,6 +294,11 @@ impl Violation {
         self.detection_method = method.as_str().to_string();
     }
 
+    /// Fingerprint of the current control, file path and snippet
+    pub fn compute_fingerprint(&self) -> String {
+        violation_fingerprint(&self.control_id, &self.file_path, &self.code_snippet)
+    }
+
     pub fn dismiss(mut self) -> Self {
         self.set_status(ViolationStatus::Dismissed);
         self
@@ -229,21 +346,71 @@ mod tests {
         assert!(Severity::Medium.numeric_value() > Severity::Low.numeric_value());
     }
 
+    #[test]
+    fn test_severity_downgrade() {
+        assert_eq!(Severity::Critical.downgrade(), Severity::High);
+        assert_eq!(Severity::High.downgrade(), Severity::Medium);
+        assert_eq!(Severity::Medium.downgrade(), Severity::Low);
+        assert_eq!(Severity::Low.downgrade(), Severity::Low);
+    }
+
     #[test]
     fn test_violation_status_as_str() {
         assert_eq!(ViolationStatus::Open.as_str(), "open");
         assert_eq!(ViolationStatus::Fixed.as_str(), "fixed");
+        assert_eq!(ViolationStatus::FixedVerified.as_str(), "fixed_verified");
         assert_eq!(ViolationStatus::Dismissed.as_str(), "dismissed");
+        assert_eq!(ViolationStatus::Resolved.as_str(), "resolved");
+    }
+
+    #[test]
+    fn test_violation_status_valid_transitions() {
+        assert_eq!(
+            ViolationStatus::Open.valid_transitions(),
+            vec![
+                ViolationStatus::Dismissed,
+                ViolationStatus::Fixed,
+                ViolationStatus::AcceptedRisk,
+                ViolationStatus::Resolved,
+            ]
+        );
+        assert!(ViolationStatus::Fixed.can_transition_to(ViolationStatus::FixedVerified));
+        assert!(ViolationStatus::Fixed.can_transition_to(ViolationStatus::Open));
+        assert!(ViolationStatus::Dismissed.can_transition_to(ViolationStatus::Open));
+        assert!(ViolationStatus::AcceptedRisk.can_transition_to(ViolationStatus::Open));
+    }
+
+    #[test]
+    fn test_violation_status_forbidden_transitions() {
+        assert!(!ViolationStatus::Dismissed.can_transition_to(ViolationStatus::Fixed));
+        assert!(!ViolationStatus::AcceptedRisk.can_transition_to(ViolationStatus::Dismissed));
+        assert!(!ViolationStatus::Open.can_transition_to(ViolationStatus::FixedVerified));
+        assert!(!ViolationStatus::Open.can_transition_to(ViolationStatus::Open));
+    }
+
+    #[test]
+    fn test_invalid_transition_error_message() {
+        let err = InvalidTransitionError {
+            violation_id: 7,
+            from: ViolationStatus::Dismissed,
+            to: ViolationStatus::Fixed,
+        };
+        assert_eq!(err.to_string(), "Invalid status transition for violation 7: dismissed -> fixed");
     }
 
     #[test]
     fn test_violation_status_from_str() {
         assert_eq!(ViolationStatus::from_str("open"), Some(ViolationStatus::Open));
         assert_eq!(ViolationStatus::from_str("fixed"), Some(ViolationStatus::Fixed));
+        assert_eq!(
+            ViolationStatus::from_str("fixed_verified"),
+            Some(ViolationStatus::FixedVerified)
+        );
         assert_eq!(
             ViolationStatus::from_str("dismissed"),
             Some(ViolationStatus::Dismissed)
         );
+        assert_eq!(ViolationStatus::from_str("resolved"), Some(ViolationStatus::Resolved));
         assert_eq!(ViolationStatus::from_str("invalid"), None);
     }
 
@@ -308,6 +475,7 @@ mod tests {
         assert_eq!(DetectionMethod::Regex.as_str(), "regex");
         assert_eq!(DetectionMethod::Llm.as_str(), "llm");
         assert_eq!(DetectionMethod::Hybrid.as_str(), "hybrid");
+        assert_eq!(DetectionMethod::Imported.as_str(), "imported");
     }
 
     #[test]
@@ -321,6 +489,10 @@ mod tests {
             DetectionMethod::from_str("hybrid"),
             Some(DetectionMethod::Hybrid)
         );
+        assert_eq!(
+            DetectionMethod::from_str("imported"),
+            Some(DetectionMethod::Imported)
+        );
         assert_eq!(DetectionMethod::from_str("invalid"), None);
     }
 
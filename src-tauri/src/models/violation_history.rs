@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Where a finding is in its life across a project's scans
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum ViolationLifecycle {
+    /// First reported by this scan
+    #[serde(rename = "new")]
+    New,
+    /// Reported by an earlier scan too, including one that came back after being resolved
+    #[serde(rename = "recurring")]
+    Recurring,
+    /// No longer reported by the latest full scan
+    #[serde(rename = "resolved")]
+    Resolved,
+}
+
+impl ViolationLifecycle {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ViolationLifecycle::New => "new",
+            ViolationLifecycle::Recurring => "recurring",
+            ViolationLifecycle::Resolved => "resolved",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "new" => Some(ViolationLifecycle::New),
+            "recurring" => Some(ViolationLifecycle::Recurring),
+            "resolved" => Some(ViolationLifecycle::Resolved),
+            _ => None,
+        }
+    }
+}
+
+/// Stable identity of a finding across scans
+///
+/// SHA-256 of the control, file path and code snippet with its whitespace
+/// collapsed. The line number is left out, so a finding keeps its identity
+/// when code above it is added or removed.
+pub fn violation_fingerprint(control_id: &str, file_path: &str, code_snippet: &str) -> String {
+    let snippet = code_snippet.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut hasher = Sha256::new();
+    for part in [control_id, file_path, snippet.as_str()] {
+        hasher.update(part.as_bytes());
+        hasher.update([0u8]);
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// A finding's lifecycle in one project, stored in `violation_history`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ViolationHistoryEntry {
+    pub id: i64,
+    pub project_id: i64,
+    pub fingerprint: String,
+    pub control_id: String,
+    pub file_path: String,
+    /// Severity when last seen
+    pub severity: String,
+    /// "new", "recurring" or "resolved"
+    pub lifecycle: String,
+    pub first_seen_scan_id: i64,
+    pub last_seen_scan_id: i64,
+    /// Scan that stopped reporting it; None while it's still reported
+    pub resolved_scan_id: Option<i64>,
+    /// Number of scans that reported it
+    pub times_seen: i64,
+    pub first_seen_at: String,
+    pub last_seen_at: String,
+    pub resolved_at: Option<String>,
+}
+
+impl ViolationHistoryEntry {
+    pub fn get_lifecycle(&self) -> Option<ViolationLifecycle> {
+        ViolationLifecycle::from_str(&self.lifecycle)
+    }
+}
+
+/// How a scan changed a project's violation history
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LifecycleCounts {
+    /// Findings the project had never reported
+    pub new: i64,
+    /// Findings reported by an earlier scan too
+    pub recurring: i64,
+    /// Findings an earlier scan reported that this scan didn't
+    pub resolved: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lifecycle_round_trip() {
+        for lifecycle in [ViolationLifecycle::New, ViolationLifecycle::Recurring, ViolationLifecycle::Resolved] {
+            assert_eq!(ViolationLifecycle::from_str(lifecycle.as_str()), Some(lifecycle));
+        }
+        assert_eq!(ViolationLifecycle::from_str("open"), None);
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_whitespace_only() {
+        let fingerprint = violation_fingerprint("CC6.7", "app.py", "api_key = \"sk-123\"");
+        assert_eq!(fingerprint.len(), 64);
+        assert_eq!(fingerprint, violation_fingerprint("CC6.7", "app.py", "  api_key  =\t\"sk-123\"\n"));
+
+        assert_ne!(fingerprint, violation_fingerprint("CC6.1", "app.py", "api_key = \"sk-123\""));
+        assert_ne!(fingerprint, violation_fingerprint("CC6.7", "config.py", "api_key = \"sk-123\""));
+        assert_ne!(fingerprint, violation_fingerprint("CC6.7", "app.py", "api_key = \"sk-456\""));
+        // Fields can't bleed into each other
+        assert_ne!(violation_fingerprint("a", "bc", "d"), violation_fingerprint("ab", "c", "d"));
+    }
+}
@@ -0,0 +1,11 @@
+//! Outbound notifications
+//!
+//! Delivers scan results to external channels: per-project Slack configs
+//! ([`slack`]) and the Slack and generic HTTP webhooks set in settings
+//! ([`webhook`]).
+
+pub mod slack;
+pub mod webhook;
+
+pub use slack::SlackNotifier;
+pub use webhook::{Notification, NotificationEndpoints};
@@ -0,0 +1,452 @@
+//! Slack incoming-webhook notifications
+//!
+//! Posts a scan summary and the top violations to Slack after a scan completes.
+//! Requests are optionally HMAC-SHA256 signed with the config's signing secret
+//! (same `v0:{timestamp}:{body}` scheme Slack uses) so a relay can verify them.
+//! HTTP 429 responses are retried, honouring `Retry-After`.
+
+use anyhow::{anyhow, Context, Result};
+use hmac::{Hmac, Mac};
+use reqwest::{Client, StatusCode};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::time::Duration;
+
+use crate::models::{Project, Scan, Severity, SlackConfig, Violation};
+
+/// Number of violations listed in a scan notification
+const TOP_VIOLATIONS: usize = 5;
+
+/// Retries after the first attempt when Slack rate-limits us
+const MAX_RATE_LIMIT_RETRIES: u32 = 3;
+
+/// Wait used when a 429 has no usable Retry-After header
+const DEFAULT_RETRY_AFTER_SECS: u64 = 1;
+
+/// Upper bound on a single Retry-After wait
+const MAX_RETRY_AFTER_SECS: u64 = 30;
+
+pub const SIGNATURE_HEADER: &str = "X-Ryn-Signature";
+pub const TIMESTAMP_HEADER: &str = "X-Ryn-Request-Timestamp";
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Compute the `v0=<hex>` signature for a request body
+pub fn sign_payload(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(format!("v0:{}:{}", timestamp, body).as_bytes());
+    format!("v0={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Build the Slack message for a completed scan
+///
+/// Only violations whose severity is in `config.notify_on_severities` are listed,
+/// highest severity first.
+pub fn build_scan_message(
+    config: &SlackConfig,
+    project: &Project,
+    scan: &Scan,
+    violations: &[Violation],
+) -> Value {
+    let mut notable: Vec<&Violation> = violations
+        .iter()
+        .filter(|v| config.notifies_on(&v.severity))
+        .collect();
+    notable.sort_by_key(|v| {
+        std::cmp::Reverse(Severity::from_str(&v.severity).map_or(0, |s| s.numeric_value()))
+    });
+
+    let summary = format!(
+        "*Ryn scan completed for {}*\n{} files scanned, {} violations ({} critical, {} high, {} medium, {} low)",
+        project.name,
+        scan.files_scanned,
+        scan.violations_found,
+        scan.critical_count,
+        scan.high_count,
+        scan.medium_count,
+        scan.low_count,
+    );
+
+    let top_lines: Vec<String> = notable
+        .iter()
+        .take(TOP_VIOLATIONS)
+        .map(|v| {
+            format!(
+                "• [{}] {} `{}:{}` {}",
+                v.severity.to_uppercase(),
+                v.control_id,
+                v.file_path,
+                v.line_number,
+                v.description
+            )
+        })
+        .collect();
+
+    let project_link = format!("ryn://projects/{}", project.id);
+
+    let mut blocks = vec![json!({
+        "type": "section",
+        "text": { "type": "mrkdwn", "text": summary },
+    })];
+    if !top_lines.is_empty() {
+        blocks.push(json!({
+            "type": "section",
+            "text": {
+                "type": "mrkdwn",
+                "text": format!("*Top violations*\n{}", top_lines.join("\n")),
+            },
+        }));
+    }
+    blocks.push(json!({
+        "type": "context",
+        "elements": [
+            { "type": "mrkdwn", "text": format!("<{}|Open {} in Ryn>", project_link, project.name) },
+        ],
+    }));
+
+    let mut message = json!({
+        "text": format!("Ryn scan completed for {}: {} violations", project.name, scan.violations_found),
+        "blocks": blocks,
+    });
+    if let Some(channel) = &config.channel {
+        message["channel"] = json!(channel);
+    }
+
+    message
+}
+
+/// Whether a completed scan should trigger a notification for this config
+pub fn should_notify(config: &SlackConfig, project_id: i64, violations: &[Violation]) -> bool {
+    config.applies_to_project(project_id) && violations.iter().any(|v| config.notifies_on(&v.severity))
+}
+
+/// Sends messages to Slack incoming webhooks
+pub struct SlackNotifier {
+    http_client: Client,
+}
+
+impl SlackNotifier {
+    pub fn new() -> Result<Self> {
+        let http_client = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .context("Failed to build Slack HTTP client")?;
+
+        Ok(Self { http_client })
+    }
+
+    /// POST a message to the config's webhook, retrying on HTTP 429
+    pub async fn send(&self, config: &SlackConfig, message: &Value) -> Result<()> {
+        self.post(&config.webhook_url, config.signing_secret.as_deref(), message).await
+    }
+
+    /// POST JSON to any webhook URL, signed with `signing_secret` if given,
+    /// retrying on HTTP 429 (also used for generic HTTP webhooks)
+    pub async fn post(&self, url: &str, signing_secret: Option<&str>, message: &Value) -> Result<()> {
+        let body = serde_json::to_string(message).context("Failed to serialize webhook message")?;
+
+        let mut attempt = 0;
+        loop {
+            let mut request = self
+                .http_client
+                .post(url)
+                .header("Content-Type", "application/json")
+                .body(body.clone());
+
+            if let Some(secret) = signing_secret {
+                let timestamp = chrono::Utc::now().timestamp();
+                request = request
+                    .header(TIMESTAMP_HEADER, timestamp.to_string())
+                    .header(SIGNATURE_HEADER, sign_payload(secret, timestamp, &body));
+            }
+
+            let response = request
+                .send()
+                .await
+                .context("Failed to send webhook request")?;
+
+            let status = response.status();
+            if status.is_success() {
+                return Ok(());
+            }
+
+            if status == StatusCode::TOO_MANY_REQUESTS && attempt < MAX_RATE_LIMIT_RETRIES {
+                let wait = response
+                    .headers()
+                    .get("Retry-After")
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.trim().parse::<u64>().ok())
+                    .unwrap_or(DEFAULT_RETRY_AFTER_SECS)
+                    .min(MAX_RETRY_AFTER_SECS);
+                attempt += 1;
+                eprintln!(
+                    "[ryn] Webhook rate limited, retrying in {}s (attempt {}/{})",
+                    wait, attempt, MAX_RATE_LIMIT_RETRIES
+                );
+                tokio::time::sleep(Duration::from_secs(wait)).await;
+                continue;
+            }
+
+            let text = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Webhook returned {}: {}", status, text));
+        }
+    }
+
+    /// Send the scan summary to every matching config, logging failures
+    pub async fn notify_scan_completed(
+        &self,
+        configs: &[SlackConfig],
+        project: &Project,
+        scan: &Scan,
+        violations: &[Violation],
+    ) {
+        for config in configs {
+            if !should_notify(config, project.id, violations) {
+                continue;
+            }
+            let message = build_scan_message(config, project, scan, violations);
+            if let Err(e) = self.send(config, &message).await {
+                eprintln!("[ryn] Slack notification {} failed: {}", config.id, e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Request captured by the mock webhook server
+    #[derive(Debug, Clone)]
+    struct CapturedRequest {
+        headers: String,
+        body: String,
+    }
+
+    /// Minimal HTTP server that answers with `statuses` in order, then 200
+    async fn mock_webhook(statuses: Vec<u16>) -> (String, Arc<Mutex<Vec<CapturedRequest>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/services/T000/B000/XXX", listener.local_addr().unwrap());
+        let captured = Arc::new(Mutex::new(Vec::new()));
+        let captured_clone = captured.clone();
+
+        tokio::spawn(async move {
+            let mut statuses = statuses.into_iter();
+            loop {
+                let (mut socket, _) = match listener.accept().await {
+                    Ok(conn) => conn,
+                    Err(_) => return,
+                };
+
+                let mut raw = Vec::new();
+                let mut buf = [0u8; 4096];
+                loop {
+                    let n = socket.read(&mut buf).await.unwrap();
+                    if n == 0 {
+                        break;
+                    }
+                    raw.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&raw);
+                    if let Some(split) = text.find("\r\n\r\n") {
+                        let headers = &text[..split];
+                        let content_length = headers
+                            .lines()
+                            .find_map(|l| {
+                                let lower = l.to_lowercase();
+                                lower.strip_prefix("content-length:").map(|v| v.trim().parse::<usize>().unwrap())
+                            })
+                            .unwrap_or(0);
+                        if raw.len() >= split + 4 + content_length {
+                            break;
+                        }
+                    }
+                }
+
+                let text = String::from_utf8_lossy(&raw).to_string();
+                let (headers, body) = text.split_once("\r\n\r\n").unwrap_or((&text, ""));
+                captured_clone.lock().unwrap().push(CapturedRequest {
+                    headers: headers.to_string(),
+                    body: body.to_string(),
+                });
+
+                let status = statuses.next().unwrap_or(200);
+                let response = if status == 429 {
+                    "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 0\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+                } else {
+                    format!("HTTP/1.1 {} OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok", status)
+                };
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        (url, captured)
+    }
+
+    fn test_project() -> Project {
+        let mut project = Project::new("payments-api".to_string(), "/repo".to_string());
+        project.id = 7;
+        project
+    }
+
+    fn test_scan() -> Scan {
+        let mut scan = Scan::new(7, "regex_only".to_string());
+        scan.files_scanned = 42;
+        scan.violations_found = 7;
+        scan.critical_count = 2;
+        scan.high_count = 5;
+        scan
+    }
+
+    fn test_violations() -> Vec<Violation> {
+        let mut violations = Vec::new();
+        for i in 0..5 {
+            violations.push(Violation::new(
+                1,
+                "CC6.1".to_string(),
+                Severity::High,
+                format!("Missing auth {}", i),
+                "views.py".to_string(),
+                10 + i,
+                "def view(request):".to_string(),
+            ));
+        }
+        for i in 0..2 {
+            violations.push(Violation::new(
+                1,
+                "CC6.7".to_string(),
+                Severity::Critical,
+                format!("Hardcoded secret {}", i),
+                "settings.py".to_string(),
+                1 + i,
+                "SECRET = 'x'".to_string(),
+            ));
+        }
+        violations.push(Violation::new(
+            1,
+            "CC7.2".to_string(),
+            Severity::Low,
+            "Missing log".to_string(),
+            "app.py".to_string(),
+            3,
+            "pass".to_string(),
+        ));
+        violations
+    }
+
+    fn test_config(url: String) -> SlackConfig {
+        let mut config = SlackConfig::new(url, vec!["critical".to_string(), "high".to_string()]);
+        config.id = 1;
+        config.channel = Some("#security".to_string());
+        config
+    }
+
+    #[test]
+    fn test_sign_payload_is_deterministic_hex() {
+        let sig = sign_payload("secret", 1_700_000_000, "{\"text\":\"hi\"}");
+        assert!(sig.starts_with("v0="));
+        assert_eq!(sig.len(), 3 + 64);
+        assert_eq!(sig, sign_payload("secret", 1_700_000_000, "{\"text\":\"hi\"}"));
+        assert_ne!(sig, sign_payload("other", 1_700_000_000, "{\"text\":\"hi\"}"));
+    }
+
+    #[test]
+    fn test_build_scan_message_lists_top_five_by_severity() {
+        let config = test_config("https://hooks.slack.com/x".to_string());
+        let message = build_scan_message(&config, &test_project(), &test_scan(), &test_violations());
+
+        assert_eq!(message["channel"], "#security");
+        let blocks = message["blocks"].as_array().unwrap();
+        assert_eq!(blocks.len(), 3);
+
+        let top = blocks[1]["text"]["text"].as_str().unwrap();
+        let lines: Vec<&str> = top.lines().skip(1).collect();
+        assert_eq!(lines.len(), 5);
+        // Critical first, low never listed
+        assert!(lines[0].contains("[CRITICAL]"));
+        assert!(lines[1].contains("[CRITICAL]"));
+        assert!(lines[0].contains("settings.py:1"));
+        assert!(!top.contains("CC7.2"));
+
+        let link = blocks[2]["elements"][0]["text"].as_str().unwrap();
+        assert!(link.contains("ryn://projects/7"));
+    }
+
+    #[test]
+    fn test_should_notify_requires_matching_severity() {
+        let config = test_config("https://hooks.slack.com/x".to_string());
+        let low_only: Vec<Violation> = test_violations()
+            .into_iter()
+            .filter(|v| v.severity == "low")
+            .collect();
+
+        assert!(should_notify(&config, 7, &test_violations()));
+        assert!(!should_notify(&config, 7, &low_only));
+    }
+
+    #[tokio::test]
+    async fn test_send_posts_signed_payload_to_webhook() {
+        let (url, captured) = mock_webhook(vec![200]).await;
+        let mut config = test_config(url);
+        config.signing_secret = Some("signing-secret".to_string());
+
+        let notifier = SlackNotifier::new().unwrap();
+        let message = build_scan_message(&config, &test_project(), &test_scan(), &test_violations());
+        notifier.send(&config, &message).await.unwrap();
+
+        let requests = captured.lock().unwrap().clone();
+        assert_eq!(requests.len(), 1);
+
+        let payload: Value = serde_json::from_str(&requests[0].body).unwrap();
+        assert_eq!(payload["channel"], "#security");
+        assert!(payload["text"].as_str().unwrap().contains("payments-api"));
+        assert!(payload["blocks"].is_array());
+
+        let headers = requests[0].headers.to_lowercase();
+        let timestamp: i64 = headers
+            .lines()
+            .find_map(|l| l.strip_prefix("x-ryn-request-timestamp:"))
+            .unwrap()
+            .trim()
+            .parse()
+            .unwrap();
+        let expected = sign_payload("signing-secret", timestamp, &requests[0].body);
+        assert!(headers.contains(&format!("x-ryn-signature: {}", expected)));
+    }
+
+    #[tokio::test]
+    async fn test_send_retries_on_rate_limit() {
+        let (url, captured) = mock_webhook(vec![429, 429, 200]).await;
+        let config = test_config(url);
+
+        let notifier = SlackNotifier::new().unwrap();
+        notifier.send(&config, &json!({ "text": "hi" })).await.unwrap();
+
+        assert_eq!(captured.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_send_gives_up_after_max_retries() {
+        let (url, captured) = mock_webhook(vec![429, 429, 429, 429, 429]).await;
+        let config = test_config(url);
+
+        let notifier = SlackNotifier::new().unwrap();
+        let result = notifier.send(&config, &json!({ "text": "hi" })).await;
+
+        assert!(result.is_err());
+        assert_eq!(captured.lock().unwrap().len(), (MAX_RATE_LIMIT_RETRIES + 1) as usize);
+    }
+
+    #[tokio::test]
+    async fn test_send_surfaces_non_retryable_errors() {
+        let (url, _captured) = mock_webhook(vec![404]).await;
+        let config = test_config(url);
+
+        let notifier = SlackNotifier::new().unwrap();
+        let result = notifier.send(&config, &json!({ "text": "hi" })).await;
+        assert!(result.is_err());
+    }
+}
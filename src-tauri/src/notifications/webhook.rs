@@ -0,0 +1,425 @@
+//! Scan summaries and watcher alerts for the endpoints in settings
+//!
+//! Besides the per-project Slack configs in [`super::slack`], three settings
+//! keys name one Slack incoming webhook and one generic HTTP webhook that get
+//! every completed scan's summary (violations by severity, LLM cost and a
+//! `ryn://scan/{id}` link), and an alert whenever the file watcher finds a new
+//! critical violation. The generic webhook receives a JSON document whose
+//! `event` is "scan_completed", "critical_violations" or "test", signed like
+//! the Slack requests when `notification_webhook_secret` is set.
+
+use anyhow::{anyhow, Result};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::SlackNotifier;
+use crate::db::queries;
+use crate::models::{Project, Scan, ScanCost, Severity, Violation};
+
+/// Settings key holding a Slack incoming-webhook URL
+pub const SLACK_WEBHOOK_SETTING: &str = "notification_slack_webhook_url";
+
+/// Settings key holding a generic HTTP webhook URL
+pub const WEBHOOK_URL_SETTING: &str = "notification_webhook_url";
+
+/// Settings key holding the secret that signs generic webhook requests
+pub const WEBHOOK_SECRET_SETTING: &str = "notification_webhook_secret";
+
+/// Critical violations listed in one watcher alert
+const MAX_ALERT_VIOLATIONS: usize = 10;
+
+/// Where notifications go; blank settings count as unset
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NotificationEndpoints {
+    pub slack_webhook_url: Option<String>,
+    pub webhook_url: Option<String>,
+    pub webhook_secret: Option<String>,
+}
+
+impl NotificationEndpoints {
+    /// Read the endpoints from the settings table
+    pub fn load(conn: &Connection) -> Self {
+        let setting = |key: &str| {
+            queries::select_setting(conn, key)
+                .ok()
+                .flatten()
+                .map(|s| s.value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        Self {
+            slack_webhook_url: setting(SLACK_WEBHOOK_SETTING),
+            webhook_url: setting(WEBHOOK_URL_SETTING),
+            webhook_secret: setting(WEBHOOK_SECRET_SETTING),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slack_webhook_url.is_none() && self.webhook_url.is_none()
+    }
+}
+
+/// A completed scan, as reported to the endpoints
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScanSummary {
+    pub project_id: i64,
+    pub project_name: String,
+    pub scan_id: i64,
+    pub status: String,
+    pub files_scanned: i32,
+    pub violations_found: i32,
+    pub critical: i32,
+    pub high: i32,
+    pub medium: i32,
+    pub low: i32,
+    /// LLM spend of the scan; None when it made no LLM calls
+    pub cost_usd: Option<f64>,
+    pub link: String,
+}
+
+impl ScanSummary {
+    /// Summarize `scan`, which must carry its severity counts
+    pub fn new(project: &Project, scan: &Scan, cost: Option<&ScanCost>) -> Self {
+        Self {
+            project_id: project.id,
+            project_name: project.name.clone(),
+            scan_id: scan.id,
+            status: scan.status.clone(),
+            files_scanned: scan.files_scanned,
+            violations_found: scan.violations_found,
+            critical: scan.critical_count,
+            high: scan.high_count,
+            medium: scan.medium_count,
+            low: scan.low_count,
+            cost_usd: cost.map(|c| c.total_cost_usd),
+            link: format!("ryn://scan/{}", scan.id),
+        }
+    }
+}
+
+/// A violation listed in a watcher alert
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlertViolation {
+    pub control_id: String,
+    pub file_path: String,
+    pub line_number: i64,
+    pub description: String,
+}
+
+/// New critical violations the file watcher found in a project
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CriticalAlert {
+    pub project_id: i64,
+    pub project_name: String,
+    pub scan_id: i64,
+    pub violations: Vec<AlertViolation>,
+    pub link: String,
+}
+
+impl CriticalAlert {
+    /// Alert for the critical violations among `violations`, or None if there are none
+    pub fn new(project: &Project, scan_id: i64, violations: &[Violation]) -> Option<Self> {
+        let critical: Vec<AlertViolation> = violations
+            .iter()
+            .filter(|v| v.get_severity() == Some(Severity::Critical))
+            .map(|v| AlertViolation {
+                control_id: v.control_id.clone(),
+                file_path: v.file_path.clone(),
+                line_number: v.line_number,
+                description: v.description.clone(),
+            })
+            .collect();
+        if critical.is_empty() {
+            return None;
+        }
+        Some(Self {
+            project_id: project.id,
+            project_name: project.name.clone(),
+            scan_id,
+            violations: critical,
+            link: format!("ryn://scan/{}", scan_id),
+        })
+    }
+}
+
+/// Something worth telling the endpoints about
+#[derive(Debug, Clone, PartialEq)]
+pub enum Notification {
+    ScanCompleted(ScanSummary),
+    CriticalViolations(CriticalAlert),
+    /// Sent by `test_notification`
+    Test,
+}
+
+impl Notification {
+    pub fn event(&self) -> &'static str {
+        match self {
+            Notification::ScanCompleted(_) => "scan_completed",
+            Notification::CriticalViolations(_) => "critical_violations",
+            Notification::Test => "test",
+        }
+    }
+
+    /// JSON document for the generic webhook
+    pub fn webhook_payload(&self) -> Value {
+        let data = match self {
+            Notification::ScanCompleted(summary) => json!(summary),
+            Notification::CriticalViolations(alert) => json!(alert),
+            Notification::Test => json!({}),
+        };
+        json!({
+            "event": self.event(),
+            "sent_at": chrono::Utc::now().to_rfc3339(),
+            "data": data,
+        })
+    }
+
+    /// Slack message with the same content
+    pub fn slack_message(&self) -> Value {
+        match self {
+            Notification::ScanCompleted(summary) => {
+                let cost = summary
+                    .cost_usd
+                    .map(|cost| format!(", LLM cost ${:.2}", cost))
+                    .unwrap_or_default();
+                let text = format!(
+                    "*Ryn scan {} for {}*\n{} files scanned, {} violations ({} critical, {} high, {} medium, {} low){}",
+                    summary.status,
+                    summary.project_name,
+                    summary.files_scanned,
+                    summary.violations_found,
+                    summary.critical,
+                    summary.high,
+                    summary.medium,
+                    summary.low,
+                    cost
+                );
+                slack_blocks(
+                    format!("Ryn scan {} for {}: {} violations", summary.status, summary.project_name, summary.violations_found),
+                    text,
+                    &summary.link,
+                )
+            }
+            Notification::CriticalViolations(alert) => {
+                let mut lines: Vec<String> = alert
+                    .violations
+                    .iter()
+                    .take(MAX_ALERT_VIOLATIONS)
+                    .map(|v| format!("• {} `{}:{}` {}", v.control_id, v.file_path, v.line_number, v.description))
+                    .collect();
+                if alert.violations.len() > MAX_ALERT_VIOLATIONS {
+                    lines.push(format!("…and {} more", alert.violations.len() - MAX_ALERT_VIOLATIONS));
+                }
+                let text = format!(
+                    "*{} new critical violation(s) in {}*\n{}",
+                    alert.violations.len(),
+                    alert.project_name,
+                    lines.join("\n")
+                );
+                slack_blocks(
+                    format!("{} new critical violation(s) in {}", alert.violations.len(), alert.project_name),
+                    text,
+                    &alert.link,
+                )
+            }
+            Notification::Test => json!({
+                "text": "Ryn test notification: this webhook will receive scan summaries and critical violation alerts.",
+            }),
+        }
+    }
+}
+
+fn slack_blocks(fallback: String, text: String, link: &str) -> Value {
+    json!({
+        "text": fallback,
+        "blocks": [
+            { "type": "section", "text": { "type": "mrkdwn", "text": text } },
+            { "type": "context", "elements": [{ "type": "mrkdwn", "text": format!("<{}|Open in Ryn>", link) }] },
+        ],
+    })
+}
+
+/// Post `notification` to every configured endpoint
+///
+/// Returns: Names of the endpoints notified ("slack", "webhook")
+///
+/// # Errors
+/// Returns error naming every endpoint that failed; the others are still sent
+pub async fn send(endpoints: &NotificationEndpoints, notification: &Notification) -> Result<Vec<&'static str>> {
+    let notifier = SlackNotifier::new()?;
+    let mut sent = Vec::new();
+    let mut failures = Vec::new();
+
+    if let Some(url) = &endpoints.slack_webhook_url {
+        match notifier.post(url, None, &notification.slack_message()).await {
+            Ok(()) => sent.push("slack"),
+            Err(e) => failures.push(format!("Slack: {}", e)),
+        }
+    }
+    if let Some(url) = &endpoints.webhook_url {
+        let payload = notification.webhook_payload();
+        match notifier.post(url, endpoints.webhook_secret.as_deref(), &payload).await {
+            Ok(()) => sent.push("webhook"),
+            Err(e) => failures.push(format!("webhook: {}", e)),
+        }
+    }
+
+    if failures.is_empty() {
+        Ok(sent)
+    } else {
+        Err(anyhow!(failures.join("; ")))
+    }
+}
+
+/// Send `notification` in the background, logging failures
+///
+/// Does nothing without endpoints or outside a tokio runtime.
+pub fn spawn_send(endpoints: NotificationEndpoints, notification: Notification) {
+    if endpoints.is_empty() {
+        return;
+    }
+    let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+        return;
+    };
+    runtime.spawn(async move {
+        if let Err(e) = send(&endpoints, &notification).await {
+            eprintln!("[ryn] {} notification failed: {}", notification.event(), e);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// HTTP server answering every request with `status`; returns its URL and the request bodies
+    async fn mock_endpoint(status: u16) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let bodies = Arc::new(Mutex::new(Vec::new()));
+        let bodies_clone = bodies.clone();
+
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut raw = Vec::new();
+                let mut buf = [0u8; 4096];
+                loop {
+                    let n = socket.read(&mut buf).await.unwrap();
+                    if n == 0 {
+                        break;
+                    }
+                    raw.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&raw);
+                    if let Some(split) = text.find("\r\n\r\n") {
+                        let content_length = text[..split]
+                            .lines()
+                            .find_map(|l| {
+                                l.to_lowercase()
+                                    .strip_prefix("content-length:")
+                                    .map(|v| v.trim().parse::<usize>().unwrap())
+                            })
+                            .unwrap_or(0);
+                        if raw.len() >= split + 4 + content_length {
+                            break;
+                        }
+                    }
+                }
+                let text = String::from_utf8_lossy(&raw).to_string();
+                let body = text.split_once("\r\n\r\n").map(|(_, body)| body.to_string()).unwrap_or_default();
+                bodies_clone.lock().unwrap().push(body);
+
+                let response = format!("HTTP/1.1 {} Status\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status);
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        (url, bodies)
+    }
+
+    fn project() -> Project {
+        let mut project = Project::new("payments-api".to_string(), "/repo".to_string());
+        project.id = 7;
+        project
+    }
+
+    fn summary() -> ScanSummary {
+        let mut scan = Scan::new(7, "smart".to_string());
+        scan.id = 12;
+        scan.status = "completed".to_string();
+        scan.files_scanned = 40;
+        scan.violations_found = 3;
+        scan.critical_count = 1;
+        scan.high_count = 2;
+        let cost = ScanCost::new(12, 4, 1000, 200, 0, 0);
+        ScanSummary::new(&project(), &scan, Some(&cost))
+    }
+
+    #[test]
+    fn test_scan_summary_messages() {
+        let summary = summary();
+        assert_eq!(summary.link, "ryn://scan/12");
+        assert!(summary.cost_usd.is_some());
+
+        let notification = Notification::ScanCompleted(summary);
+        let payload = notification.webhook_payload();
+        assert_eq!(payload["event"], "scan_completed");
+        assert_eq!(payload["data"]["critical"], 1);
+        assert_eq!(payload["data"]["project_name"], "payments-api");
+
+        let message = notification.slack_message();
+        let text = message["blocks"][0]["text"]["text"].as_str().unwrap();
+        assert!(text.contains("3 violations (1 critical, 2 high, 0 medium, 0 low), LLM cost $"));
+        assert!(message["blocks"][1]["elements"][0]["text"].as_str().unwrap().contains("ryn://scan/12"));
+    }
+
+    #[test]
+    fn test_critical_alert_only_lists_critical() {
+        let violation = |severity| {
+            Violation::new(12, "CC6.7".to_string(), severity, "Hardcoded key".to_string(), "app.py".to_string(), 3, "k".to_string())
+        };
+        assert_eq!(CriticalAlert::new(&project(), 12, &[violation(Severity::High)]), None);
+
+        let alert = CriticalAlert::new(&project(), 12, &[violation(Severity::High), violation(Severity::Critical)]).unwrap();
+        assert_eq!(alert.violations.len(), 1);
+        let message = Notification::CriticalViolations(alert).slack_message();
+        assert!(message["text"].as_str().unwrap().starts_with("1 new critical violation(s) in payments-api"));
+    }
+
+    #[tokio::test]
+    async fn test_send_posts_to_both_endpoints() {
+        let (slack_url, slack_bodies) = mock_endpoint(200).await;
+        let (webhook_url, webhook_bodies) = mock_endpoint(200).await;
+        let endpoints = NotificationEndpoints {
+            slack_webhook_url: Some(slack_url),
+            webhook_url: Some(webhook_url),
+            webhook_secret: Some("secret".to_string()),
+        };
+
+        let sent = send(&endpoints, &Notification::ScanCompleted(summary())).await.unwrap();
+        assert_eq!(sent, vec!["slack", "webhook"]);
+
+        let slack: Value = serde_json::from_str(&slack_bodies.lock().unwrap()[0]).unwrap();
+        assert!(slack["blocks"].is_array());
+        let webhook: Value = serde_json::from_str(&webhook_bodies.lock().unwrap()[0]).unwrap();
+        assert_eq!(webhook["event"], "scan_completed");
+        assert_eq!(webhook["data"]["scan_id"], 12);
+    }
+
+    #[tokio::test]
+    async fn test_send_reports_failed_endpoint() {
+        let (slack_url, _) = mock_endpoint(200).await;
+        let (webhook_url, _) = mock_endpoint(500).await;
+        let endpoints = NotificationEndpoints {
+            slack_webhook_url: Some(slack_url),
+            webhook_url: Some(webhook_url),
+            webhook_secret: None,
+        };
+
+        let error = send(&endpoints, &Notification::Test).await.unwrap_err();
+        assert!(error.to_string().starts_with("webhook: "));
+        assert!(!error.to_string().contains("Slack"));
+    }
+}
@@ -99,6 +99,12 @@ pub struct RateLimiter {
     total_calls: Arc<Mutex<u64>>,
 }
 
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl RateLimiter {
     /// Create new rate limiter with default config
     pub fn new() -> Self {
@@ -0,0 +1,240 @@
+//! HTML rendering of compliance reports
+//!
+//! A single self-contained page (inline CSS and SVG, no scripts or external
+//! assets) so the file can be attached to an audit request as-is. The trend
+//! data is also embedded as JSON (`#ryn-trend-data`) for tools that want to
+//! chart it themselves.
+
+use std::fmt::Write;
+
+use super::{ComplianceReport, ControlSection};
+use crate::models::{ComplianceDataPoint, SeverityCounts};
+
+const STYLE: &str = "body{font-family:-apple-system,Helvetica,Arial,sans-serif;color:#1f2933;margin:40px auto;max-width:1000px;padding:0 24px}\
+h1{margin-bottom:4px}h2{border-bottom:2px solid #e4e7eb;padding-bottom:4px;margin-top:36px}\
+table{border-collapse:collapse;width:100%;margin:12px 0;font-size:14px}\
+th,td{border:1px solid #e4e7eb;padding:6px 8px;text-align:left;vertical-align:top}th{background:#f5f7fa}\
+.meta{color:#616e7c}.critical{color:#b42318;font-weight:600}.high{color:#c4320a}.medium{color:#b54708}.low{color:#475467}\
+.score{font-size:28px;font-weight:700}code{font-size:13px}";
+
+/// Render a report as an HTML document
+pub fn render(report: &ComplianceReport) -> String {
+    let mut out = String::new();
+    let title = format!("SOC 2 Compliance Report: {}", report.project.name);
+
+    let _ = writeln!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>",
+        escape(&title),
+        STYLE
+    );
+    let _ = writeln!(out, "<h1>{}</h1>", escape(&title));
+    let _ = writeln!(
+        out,
+        "<p class=\"meta\">Generated {} by Ryn from scan #{}</p>",
+        escape(&report.generated_at),
+        report.scan.id
+    );
+
+    render_summary(&mut out, report);
+    render_severity_breakdown(&mut out, &report.severity_counts);
+    render_status_breakdown(&mut out, report);
+    render_trend(&mut out, &report.compliance_trend);
+
+    out.push_str("<h2>Findings by Control</h2>\n");
+    for section in &report.controls {
+        render_control(&mut out, section);
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn render_summary(out: &mut String, report: &ComplianceReport) {
+    let scan = &report.scan;
+    let rows = [
+        ("Project", report.project.name.clone()),
+        ("Path", report.project.path.clone()),
+        ("Framework", report.project.framework.clone().unwrap_or_else(|| "Unknown".to_string())),
+        ("Scan", format!("#{} ({}, {} mode)", scan.id, scan.status, scan.scan_mode)),
+        ("Started", scan.started_at.clone()),
+        ("Completed", scan.completed_at.clone().unwrap_or_else(|| "Not completed".to_string())),
+        ("Files scanned", format!("{} of {}", scan.files_scanned, scan.total_files)),
+        ("Commit", scan.commit_sha.clone().unwrap_or_else(|| "Not recorded".to_string())),
+        ("Total violations", report.total_violations().to_string()),
+        ("Outstanding violations", report.outstanding_violations().to_string()),
+    ];
+
+    out.push_str("<h2>Summary</h2>\n<table>\n");
+    for (label, value) in rows {
+        let _ = writeln!(out, "<tr><th>{}</th><td>{}</td></tr>", label, escape(&value));
+    }
+    out.push_str("</table>\n");
+    if let Some(reason) = &scan.partial_reason {
+        let _ = writeln!(out, "<p class=\"high\">Partial scan: {}</p>", escape(reason));
+    }
+}
+
+fn render_severity_breakdown(out: &mut String, counts: &SeverityCounts) {
+    out.push_str("<h2>Severity Breakdown</h2>\n<table>\n<tr><th>Severity</th><th>Violations</th></tr>\n");
+    for (severity, count) in severity_rows(counts) {
+        let _ = writeln!(out, "<tr><td class=\"{0}\">{0}</td><td>{1}</td></tr>", severity, count);
+    }
+    out.push_str("</table>\n");
+}
+
+fn render_status_breakdown(out: &mut String, report: &ComplianceReport) {
+    out.push_str("<h2>Remediation Status</h2>\n");
+    if report.status_counts.is_empty() {
+        out.push_str("<p>No violations were found.</p>\n");
+        return;
+    }
+    out.push_str("<table>\n<tr><th>Status</th><th>Violations</th></tr>\n");
+    for (status, count) in &report.status_counts {
+        let _ = writeln!(out, "<tr><td>{}</td><td>{}</td></tr>", escape(status), count);
+    }
+    out.push_str("</table>\n");
+}
+
+fn render_trend(out: &mut String, trend: &[ComplianceDataPoint]) {
+    out.push_str("<h2>Compliance Trend</h2>\n");
+    if trend.is_empty() {
+        out.push_str("<p>No completed scans have been scored yet.</p>\n");
+        return;
+    }
+
+    out.push_str(&trend_chart(trend));
+    out.push_str("<table>\n<tr><th>Scan</th><th>Completed</th><th>CC6.1</th><th>CC6.7</th><th>CC7.2</th><th>A1.2</th><th>Overall</th></tr>\n");
+    for point in trend {
+        let _ = writeln!(
+            out,
+            "<tr><td>#{}</td><td>{}</td><td>{:.0}</td><td>{:.0}</td><td>{:.0}</td><td>{:.0}</td><td>{:.1}</td></tr>",
+            point.scan_id,
+            escape(&point.completed_at),
+            point.cc61_score,
+            point.cc67_score,
+            point.cc72_score,
+            point.a12_score,
+            point.overall_score
+        );
+    }
+    out.push_str("</table>\n");
+
+    // "</" can't appear inside a script element
+    let data = serde_json::to_string(trend).unwrap_or_else(|_| "[]".to_string()).replace("</", "<\\/");
+    let _ = writeln!(out, "<script type=\"application/json\" id=\"ryn-trend-data\">{}</script>", data);
+}
+
+/// Inline SVG line chart of the overall score (0-100) per scan
+fn trend_chart(trend: &[ComplianceDataPoint]) -> String {
+    const WIDTH: f32 = 600.0;
+    const HEIGHT: f32 = 160.0;
+    const PADDING: f32 = 20.0;
+
+    let step = if trend.len() > 1 {
+        (WIDTH - 2.0 * PADDING) / (trend.len() - 1) as f32
+    } else {
+        0.0
+    };
+    let points: Vec<(f32, f32)> = trend
+        .iter()
+        .enumerate()
+        .map(|(i, point)| {
+            let x = PADDING + step * i as f32;
+            let y = HEIGHT - PADDING - (point.overall_score.clamp(0.0, 100.0) / 100.0) * (HEIGHT - 2.0 * PADDING);
+            (x, y)
+        })
+        .collect();
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\" role=\"img\" aria-label=\"Overall compliance score per scan\">\n",
+        WIDTH, HEIGHT
+    );
+    let _ = writeln!(
+        svg,
+        "<rect x=\"0\" y=\"0\" width=\"{}\" height=\"{}\" fill=\"#f5f7fa\"/>",
+        WIDTH, HEIGHT
+    );
+    let polyline: Vec<String> = points.iter().map(|(x, y)| format!("{:.1},{:.1}", x, y)).collect();
+    let _ = writeln!(
+        svg,
+        "<polyline fill=\"none\" stroke=\"#2563eb\" stroke-width=\"2\" points=\"{}\"/>",
+        polyline.join(" ")
+    );
+    for ((x, y), point) in points.iter().zip(trend) {
+        let _ = writeln!(
+            svg,
+            "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"3\" fill=\"#2563eb\"><title>Scan #{}: {:.1}</title></circle>",
+            x, y, point.scan_id, point.overall_score
+        );
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn render_control(out: &mut String, section: &ControlSection) {
+    let _ = writeln!(
+        out,
+        "<h3>{} {}</h3>\n<p class=\"meta\">{} &middot; score <span class=\"score\">{:.0}</span>/100</p>",
+        escape(&section.control_id),
+        escape(&section.name),
+        escape(&section.category),
+        section.score
+    );
+
+    if section.violations.is_empty() {
+        out.push_str("<p>No violations found for this control.</p>\n");
+        return;
+    }
+
+    out.push_str("<table>\n<tr><th>Severity</th><th>Location</th><th>Description</th><th>Status</th><th>Remediation</th></tr>\n");
+    for violation in &section.violations {
+        let mut remediation = Vec::new();
+        if let Some(applied_at) = &violation.fix_applied_at {
+            remediation.push(format!("Fix applied {}", escape(applied_at)));
+        }
+        if let Some(sha) = &violation.fix_commit_sha {
+            remediation.push(format!("commit <code>{}</code>", escape(sha)));
+        }
+        if let Some(reason) = &violation.status_reason {
+            remediation.push(format!("Reason: {}", escape(reason)));
+        }
+
+        let _ = writeln!(
+            out,
+            "<tr><td class=\"{0}\">{0}</td><td><code>{1}:{2}</code></td><td>{3}</td><td>{4}</td><td>{5}</td></tr>",
+            escape(&violation.severity),
+            escape(&violation.file_path),
+            violation.line_number,
+            escape(&violation.description),
+            escape(&violation.status),
+            remediation.join("; ")
+        );
+    }
+    out.push_str("</table>\n");
+}
+
+pub(super) fn severity_rows(counts: &SeverityCounts) -> [(&'static str, i64); 4] {
+    [
+        ("critical", counts.critical),
+        ("high", counts.high),
+        ("medium", counts.medium),
+        ("low", counts.low),
+    ]
+}
+
+/// Escape text for HTML element content and attribute values
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,330 @@
+//! SOC 2 evidence reports
+//!
+//! Builds a [`ComplianceReport`] for one scan (violations grouped by
+//! control, severity and remediation status breakdowns, and the project's
+//! compliance score trend) and renders it as a standalone HTML page or a
+//! PDF to hand to auditors. Both formats are rendered from the same data so
+//! they never disagree. [`spreadsheet`] exports a scan's violations as CSV or
+//! Excel rows instead.
+
+pub mod html;
+pub mod pdf;
+pub mod spreadsheet;
+
+use anyhow::{anyhow, Context, Result};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::db::queries;
+use crate::models::compliance::control_score;
+use crate::models::{ComplianceDataPoint, Project, Scan, SeverityCounts, ViolationStatus};
+
+/// Completed scans included in a report's compliance trend
+pub const REPORT_TREND_SCANS: usize = 12;
+
+/// Output format of a report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    Html,
+    Pdf,
+}
+
+impl ReportFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportFormat::Html => "html",
+            ReportFormat::Pdf => "pdf",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "html" => Some(ReportFormat::Html),
+            "pdf" => Some(ReportFormat::Pdf),
+            _ => None,
+        }
+    }
+
+    /// File extension for reports in this format
+    pub fn extension(&self) -> &'static str {
+        self.as_str()
+    }
+}
+
+/// A violation as listed in a report
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReportViolation {
+    pub id: i64,
+    pub severity: String,
+    pub status: String,
+    pub file_path: String,
+    pub line_number: i64,
+    pub description: String,
+    pub detection_method: String,
+    /// When the latest applied fix for the violation was applied
+    pub fix_applied_at: Option<String>,
+    /// Commit the latest applied fix was committed in
+    pub fix_commit_sha: Option<String>,
+    /// Reason recorded for the last status change (e.g. why a risk was accepted)
+    pub status_reason: Option<String>,
+}
+
+/// One control's section of a report
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ControlSection {
+    pub control_id: String,
+    pub name: String,
+    pub category: String,
+    pub severity_counts: SeverityCounts,
+    /// Compliance score (0-100), as on the dashboard
+    pub score: f32,
+    /// Most severe first
+    pub violations: Vec<ReportViolation>,
+}
+
+/// Everything in a scan's evidence report
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ComplianceReport {
+    pub generated_at: String,
+    pub project: Project,
+    pub scan: Scan,
+    /// Severity counts over all the scan's violations
+    pub severity_counts: SeverityCounts,
+    /// Violation counts keyed by status (open, fixed, accepted_risk, ...)
+    pub status_counts: BTreeMap<String, i64>,
+    /// SOC 2 controls first (including those without violations), then any
+    /// other control with violations in the scan
+    pub controls: Vec<ControlSection>,
+    /// Compliance scores of the project's recent completed scans, oldest first
+    pub compliance_trend: Vec<ComplianceDataPoint>,
+}
+
+impl ComplianceReport {
+    /// Gather a scan's report data
+    ///
+    /// # Errors
+    /// Returns error if the scan or its project doesn't exist in the current namespace
+    pub fn build(conn: &Connection, scan_id: i64) -> Result<Self> {
+        let scan = queries::select_scan(conn, scan_id)?.ok_or_else(|| anyhow!("Scan not found: {}", scan_id))?;
+        let project = queries::select_project(conn, scan.project_id)?
+            .ok_or_else(|| anyhow!("Project not found: {}", scan.project_id))?;
+        let controls = queries::select_controls(conn).context("Failed to fetch controls")?;
+        let violations = queries::select_violations(conn, scan_id).context("Failed to fetch violations")?;
+        let compliance_trend = queries::get_compliance_trend(conn, project.id, REPORT_TREND_SCANS)
+            .context("Failed to fetch compliance trend")?;
+
+        let mut severity_counts = SeverityCounts::default();
+        let mut status_counts = BTreeMap::new();
+        let mut by_control: HashMap<String, Vec<ReportViolation>> = HashMap::new();
+        let mut counts_by_control: HashMap<String, SeverityCounts> = HashMap::new();
+
+        for violation in violations {
+            let counts = counts_by_control.entry(violation.control_id.clone()).or_default();
+            for counts in [&mut severity_counts, counts] {
+                match violation.severity.as_str() {
+                    "critical" => counts.critical += 1,
+                    "high" => counts.high += 1,
+                    "medium" => counts.medium += 1,
+                    _ => counts.low += 1,
+                }
+            }
+            *status_counts.entry(violation.status.clone()).or_insert(0) += 1;
+
+            let applied_fix = queries::select_fixes_for_violation(conn, violation.id)
+                .context("Failed to fetch fixes")?
+                .into_iter()
+                .filter(|fix| fix.applied_at.is_some())
+                .max_by_key(|fix| fix.fix_attempt_number);
+
+            by_control.entry(violation.control_id.clone()).or_default().push(ReportViolation {
+                id: violation.id,
+                severity: violation.severity,
+                status: violation.status,
+                file_path: violation.file_path,
+                line_number: violation.line_number,
+                description: violation.description,
+                detection_method: violation.detection_method,
+                fix_applied_at: applied_fix.as_ref().and_then(|fix| fix.applied_at.clone()),
+                fix_commit_sha: applied_fix.and_then(|fix| fix.git_commit_sha),
+                status_reason: violation.last_status_change_reason,
+            });
+        }
+
+        // Controls outside the catalog, e.g. from SARIF imports
+        let mut other_ids: Vec<String> = by_control
+            .keys()
+            .filter(|id| !controls.iter().any(|control| &control.id == *id))
+            .cloned()
+            .collect();
+        other_ids.sort();
+
+        let mut section = |control_id: String, name: String, category: String| {
+            let severity_counts = counts_by_control.remove(&control_id).unwrap_or_default();
+            let violations = by_control.remove(&control_id).unwrap_or_default();
+            ControlSection {
+                score: control_score(severity_counts),
+                control_id,
+                name,
+                category,
+                severity_counts,
+                violations: sorted_by_severity(violations),
+            }
+        };
+
+        let mut sections: Vec<ControlSection> = controls
+            .into_iter()
+            .map(|control| section(control.id, control.name, control.category))
+            .collect();
+        for control_id in other_ids {
+            sections.push(section(control_id.clone(), control_id, "Other".to_string()));
+        }
+
+        Ok(Self {
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            project,
+            scan,
+            severity_counts,
+            status_counts,
+            controls: sections,
+            compliance_trend,
+        })
+    }
+
+    /// Violations not yet remediated (open, or fixed but not verified)
+    pub fn outstanding_violations(&self) -> i64 {
+        [ViolationStatus::Open, ViolationStatus::Fixed]
+            .iter()
+            .map(|status| self.status_counts.get(status.as_str()).copied().unwrap_or(0))
+            .sum()
+    }
+
+    /// Total violations in the scan
+    pub fn total_violations(&self) -> i64 {
+        self.status_counts.values().sum()
+    }
+
+    /// Render the report in `format`
+    pub fn render(&self, format: ReportFormat) -> Vec<u8> {
+        match format {
+            ReportFormat::Html => html::render(self).into_bytes(),
+            ReportFormat::Pdf => pdf::render(self),
+        }
+    }
+}
+
+fn severity_rank(severity: &str) -> u8 {
+    match severity {
+        "critical" => 0,
+        "high" => 1,
+        "medium" => 2,
+        _ => 3,
+    }
+}
+
+fn sorted_by_severity(mut violations: Vec<ReportViolation>) -> Vec<ReportViolation> {
+    violations.sort_by(|a, b| {
+        (severity_rank(&a.severity), &a.file_path, a.line_number).cmp(&(
+            severity_rank(&b.severity),
+            &b.file_path,
+            b.line_number,
+        ))
+    });
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{self, test_helpers::TestDbGuard};
+    use crate::models::{Fix, Severity, TrustLevel, Violation};
+
+    /// Completed scan with a critical CC6.7 violation (fixed), a medium CC6.7
+    /// violation (open) and an imported violation for a non-SOC 2 control
+    fn create_scored_scan() -> i64 {
+        let conn = db::get_connection();
+        let project_id = queries::insert_project(&conn, "billing <api>", "/tmp/ryn-test/billing", None).unwrap();
+        let scan_id = queries::insert_scan(&conn, project_id, "regex_only").unwrap();
+
+        let violation = |control_id: &str, severity: Severity, line: i64| {
+            Violation::new(
+                scan_id,
+                control_id.to_string(),
+                severity,
+                format!("Finding on line {}", line),
+                "app.py".to_string(),
+                line,
+                String::new(),
+            )
+        };
+        let fixed = queries::insert_violation(&conn, &violation("CC6.7", Severity::Critical, 3)).unwrap();
+        queries::insert_violation(&conn, &violation("CC6.7", Severity::Medium, 9)).unwrap();
+        queries::insert_violation(&conn, &violation("SEMGREP.1", Severity::Low, 20)).unwrap();
+
+        let fix = Fix::new(fixed, "KEY = 'x'".to_string(), "KEY = env".to_string(), "Moved".to_string(), TrustLevel::Review);
+        let fix_id = queries::insert_fix(&conn, &fix).unwrap();
+        queries::update_fix_applied(&conn, fix_id, "abc1234", None).unwrap();
+        queries::update_violation_status(&conn, fixed, "fixed", None).unwrap();
+
+        queries::update_scan_status(&conn, scan_id, "completed", Some("2026-03-10T02:00:00Z")).unwrap();
+        queries::record_compliance_score(&conn, scan_id).unwrap();
+        scan_id
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_build_report_groups_violations_by_control() {
+        let _guard = TestDbGuard::new();
+        let scan_id = create_scored_scan();
+
+        let report = ComplianceReport::build(&db::get_connection(), scan_id).unwrap();
+        assert_eq!(report.scan.id, scan_id);
+        assert_eq!(report.total_violations(), 3);
+        assert_eq!(report.outstanding_violations(), 3);
+        assert_eq!(report.status_counts.get("fixed"), Some(&1));
+        assert_eq!(report.severity_counts, SeverityCounts { critical: 1, high: 0, medium: 1, low: 1 });
+
+        // Every SOC 2 control gets a section; imported controls come last
+        let ids: Vec<&str> = report.controls.iter().map(|c| c.control_id.as_str()).collect();
+        assert!(ids.contains(&"CC6.1"));
+        assert_eq!(ids.last(), Some(&"SEMGREP.1"));
+
+        let cc67 = report.controls.iter().find(|c| c.control_id == "CC6.7").unwrap();
+        assert_eq!(cc67.violations.len(), 2);
+        assert_eq!(cc67.violations[0].severity, "critical");
+        assert_eq!(cc67.violations[0].fix_commit_sha.as_deref(), Some("abc1234"));
+        assert!(cc67.violations[0].fix_applied_at.is_some());
+        assert!(cc67.score < 100.0);
+
+        assert_eq!(report.compliance_trend.len(), 1);
+        assert_eq!(report.compliance_trend[0].scan_id, scan_id);
+
+        let missing = ComplianceReport::build(&db::get_connection(), 999).unwrap_err();
+        assert!(missing.to_string().contains("Scan not found"));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_render_html_escapes_and_embeds_trend() {
+        let _guard = TestDbGuard::new();
+        let scan_id = create_scored_scan();
+        let report = ComplianceReport::build(&db::get_connection(), scan_id).unwrap();
+
+        let html = String::from_utf8(report.render(ReportFormat::Html)).unwrap();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("SOC 2 Compliance Report: billing &lt;api&gt;"));
+        assert!(!html.contains("billing <api>"));
+        assert!(html.contains("<code>abc1234</code>"));
+        assert!(html.contains("id=\"ryn-trend-data\""));
+        assert!(html.contains("<svg"));
+    }
+
+    #[test]
+    fn test_report_format_parsing() {
+        assert_eq!(ReportFormat::from_str("HTML"), Some(ReportFormat::Html));
+        assert_eq!(ReportFormat::from_str("pdf"), Some(ReportFormat::Pdf));
+        assert_eq!(ReportFormat::from_str("docx"), None);
+        assert_eq!(ReportFormat::Pdf.extension(), "pdf");
+    }
+}
@@ -0,0 +1,334 @@
+//! PDF rendering of compliance reports
+//!
+//! Writes a plain text-only PDF 1.4 document by hand: US Letter pages set in
+//! the standard Helvetica fonts, which every PDF reader has built in, so
+//! nothing needs to be embedded. Characters outside ASCII are replaced with
+//! '?' since the standard fonts only cover Latin-1.
+
+use std::fmt::Write;
+
+use super::html::severity_rows;
+use super::ComplianceReport;
+
+const PAGE_WIDTH: f32 = 612.0;
+const PAGE_HEIGHT: f32 = 792.0;
+const MARGIN: f32 = 50.0;
+
+/// Average Helvetica glyph width as a fraction of the font size, for wrapping
+const AVERAGE_GLYPH_WIDTH: f32 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Style {
+    Title,
+    Heading,
+    Subheading,
+    Body,
+}
+
+impl Style {
+    fn font(&self) -> &'static str {
+        match self {
+            Style::Body => "F1",
+            _ => "F2",
+        }
+    }
+
+    fn size(&self) -> f32 {
+        match self {
+            Style::Title => 18.0,
+            Style::Heading => 14.0,
+            Style::Subheading => 11.0,
+            Style::Body => 9.0,
+        }
+    }
+
+    /// Space above the line
+    fn space_before(&self) -> f32 {
+        match self {
+            Style::Title => 0.0,
+            Style::Heading => 14.0,
+            Style::Subheading => 8.0,
+            Style::Body => 0.0,
+        }
+    }
+}
+
+/// Render a report as a PDF document
+pub fn render(report: &ComplianceReport) -> Vec<u8> {
+    let mut doc = TextDocument::default();
+    let scan = &report.scan;
+
+    doc.line(Style::Title, &format!("SOC 2 Compliance Report: {}", report.project.name));
+    doc.line(Style::Body, &format!("Generated {} by Ryn from scan #{}", report.generated_at, scan.id));
+
+    doc.line(Style::Heading, "Summary");
+    doc.line(Style::Body, &format!("Project: {} ({})", report.project.name, report.project.path));
+    doc.line(
+        Style::Body,
+        &format!("Framework: {}", report.project.framework.as_deref().unwrap_or("Unknown")),
+    );
+    doc.line(Style::Body, &format!("Scan: #{} ({}, {} mode)", scan.id, scan.status, scan.scan_mode));
+    doc.line(
+        Style::Body,
+        &format!(
+            "Started: {}   Completed: {}",
+            scan.started_at,
+            scan.completed_at.as_deref().unwrap_or("Not completed")
+        ),
+    );
+    doc.line(Style::Body, &format!("Files scanned: {} of {}", scan.files_scanned, scan.total_files));
+    doc.line(Style::Body, &format!("Commit: {}", scan.commit_sha.as_deref().unwrap_or("Not recorded")));
+    doc.line(
+        Style::Body,
+        &format!(
+            "Violations: {} total, {} outstanding",
+            report.total_violations(),
+            report.outstanding_violations()
+        ),
+    );
+    if let Some(reason) = &scan.partial_reason {
+        doc.line(Style::Body, &format!("Partial scan: {}", reason));
+    }
+
+    doc.line(Style::Heading, "Severity Breakdown");
+    for (severity, count) in severity_rows(&report.severity_counts) {
+        doc.line(Style::Body, &format!("{:<10} {}", severity, count));
+    }
+
+    doc.line(Style::Heading, "Remediation Status");
+    if report.status_counts.is_empty() {
+        doc.line(Style::Body, "No violations were found.");
+    }
+    for (status, count) in &report.status_counts {
+        doc.line(Style::Body, &format!("{:<16} {}", status, count));
+    }
+
+    doc.line(Style::Heading, "Compliance Trend");
+    if report.compliance_trend.is_empty() {
+        doc.line(Style::Body, "No completed scans have been scored yet.");
+    }
+    for point in &report.compliance_trend {
+        doc.line(
+            Style::Body,
+            &format!(
+                "Scan #{} ({}): overall {:.1} | CC6.1 {:.0}  CC6.7 {:.0}  CC7.2 {:.0}  A1.2 {:.0}",
+                point.scan_id,
+                point.completed_at,
+                point.overall_score,
+                point.cc61_score,
+                point.cc67_score,
+                point.cc72_score,
+                point.a12_score
+            ),
+        );
+    }
+
+    doc.line(Style::Heading, "Findings by Control");
+    for section in &report.controls {
+        doc.line(
+            Style::Subheading,
+            &format!(
+                "{} {} ({}) - score {:.0}/100",
+                section.control_id, section.name, section.category, section.score
+            ),
+        );
+        if section.violations.is_empty() {
+            doc.line(Style::Body, "No violations found for this control.");
+        }
+        for violation in &section.violations {
+            doc.line(
+                Style::Body,
+                &format!(
+                    "[{}] {}:{} - {} ({})",
+                    violation.severity.to_uppercase(),
+                    violation.file_path,
+                    violation.line_number,
+                    violation.description,
+                    violation.status
+                ),
+            );
+            if let Some(applied_at) = &violation.fix_applied_at {
+                let commit = violation
+                    .fix_commit_sha
+                    .as_deref()
+                    .map(|sha| format!(" in commit {}", sha))
+                    .unwrap_or_default();
+                doc.line(Style::Body, &format!("    Fix applied {}{}", applied_at, commit));
+            }
+            if let Some(reason) = &violation.status_reason {
+                doc.line(Style::Body, &format!("    Reason: {}", reason));
+            }
+        }
+    }
+
+    doc.finish(&format!("SOC 2 Compliance Report: {}", report.project.name))
+}
+
+/// Lines of text laid out onto pages
+#[derive(Default)]
+struct TextDocument {
+    /// Content stream of each finished page
+    pages: Vec<String>,
+    current: String,
+    /// Baseline of the last line, from the bottom of the page (None before the first page)
+    y: Option<f32>,
+}
+
+impl TextDocument {
+    /// Add a line of text, wrapping it to the page width
+    fn line(&mut self, style: Style, text: &str) {
+        let max_chars = ((PAGE_WIDTH - 2.0 * MARGIN) / (style.size() * AVERAGE_GLYPH_WIDTH)) as usize;
+        let leading = style.size() * 1.35;
+
+        for (i, chunk) in wrap(&sanitize(text), max_chars).into_iter().enumerate() {
+            let space = if i == 0 { style.space_before() } else { 0.0 };
+            let y = match self.y {
+                Some(y) if y - space - leading >= MARGIN => y - space - leading,
+                _ => {
+                    self.new_page();
+                    PAGE_HEIGHT - MARGIN - leading
+                }
+            };
+            self.y = Some(y);
+            let _ = writeln!(
+                self.current,
+                "BT /{} {} Tf {} {:.1} Td ({}) Tj ET",
+                style.font(),
+                style.size(),
+                MARGIN,
+                y,
+                escape(&chunk)
+            );
+        }
+    }
+
+    fn new_page(&mut self) {
+        if self.y.is_some() {
+            self.pages.push(std::mem::take(&mut self.current));
+        }
+    }
+
+    /// Write the PDF file
+    fn finish(mut self, title: &str) -> Vec<u8> {
+        // The last page, or a blank one for an empty document
+        self.pages.push(std::mem::take(&mut self.current));
+
+        // 1: catalog, 2: page tree, 3-4: fonts, 5: info, then a page and its content per page
+        let page_ids: Vec<usize> = (0..self.pages.len()).map(|i| 6 + 2 * i).collect();
+        let mut objects = vec![
+            "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {} >>",
+                page_ids.iter().map(|id| format!("{} 0 R", id)).collect::<Vec<_>>().join(" "),
+                page_ids.len()
+            ),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>".to_string(),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>".to_string(),
+            format!("<< /Title ({}) /Producer (Ryn) >>", escape(&sanitize(title))),
+        ];
+        for (page_id, content) in page_ids.iter().zip(&self.pages) {
+            objects.push(format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                PAGE_WIDTH,
+                PAGE_HEIGHT,
+                page_id + 1
+            ));
+            objects.push(format!("<< /Length {} >>\nstream\n{}endstream", content.len(), content));
+        }
+
+        let mut pdf = String::from("%PDF-1.4\n");
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (i, object) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            let _ = writeln!(pdf, "{} 0 obj\n{}\nendobj", i + 1, object);
+        }
+
+        let xref_offset = pdf.len();
+        let _ = writeln!(pdf, "xref\n0 {}\n0000000000 65535 f ", objects.len() + 1);
+        for offset in offsets {
+            let _ = writeln!(pdf, "{:010} 00000 n ", offset);
+        }
+        let _ = writeln!(
+            pdf,
+            "trailer\n<< /Size {} /Root 1 0 R /Info 5 0 R >>\nstartxref\n{}\n%%EOF",
+            objects.len() + 1,
+            xref_offset
+        );
+
+        pdf.into_bytes()
+    }
+}
+
+/// Replace characters the standard fonts can't show, so byte offsets stay ASCII
+fn sanitize(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '\t' => ' ',
+            c if c.is_ascii() && !c.is_ascii_control() => c,
+            _ => '?',
+        })
+        .collect()
+}
+
+/// Split text into lines of at most `max_chars`, breaking at spaces where possible
+fn wrap(text: &str, max_chars: usize) -> Vec<String> {
+    let max_chars = max_chars.max(1);
+    let mut lines = Vec::new();
+    let mut rest = text;
+
+    while rest.len() > max_chars {
+        let split = rest[..max_chars].rfind(' ').filter(|i| *i > 0).unwrap_or(max_chars);
+        lines.push(rest[..split].trim_end().to_string());
+        rest = rest[split..].trim_start();
+    }
+    lines.push(rest.to_string());
+    lines
+}
+
+/// Escape text for a PDF literal string
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('(', "\\(").replace(')', "\\)")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_breaks_at_spaces() {
+        assert_eq!(wrap("short", 20), vec!["short"]);
+        assert_eq!(wrap("alpha beta gamma", 11), vec!["alpha beta", "gamma"]);
+        // No space to break at
+        assert_eq!(wrap("abcdefgh", 3), vec!["abc", "def", "gh"]);
+    }
+
+    #[test]
+    fn test_text_is_escaped_and_sanitized() {
+        assert_eq!(escape(&sanitize("f(x) \\ \u{2713}\ty")), "f\\(x\\) \\\\ ? y");
+    }
+
+    #[test]
+    fn test_document_structure_and_pagination() {
+        let mut doc = TextDocument::default();
+        doc.line(Style::Title, "Report (draft)");
+        for i in 0..200 {
+            doc.line(Style::Body, &format!("Line {}", i));
+        }
+        let pdf = String::from_utf8(doc.finish("Report")).unwrap();
+
+        assert!(pdf.starts_with("%PDF-1.4\n"));
+        assert!(pdf.ends_with("%%EOF\n"));
+        assert!(pdf.contains("(Report \\(draft\\)) Tj"));
+        // 200 body lines don't fit on one page
+        let pages = pdf.matches("/Type /Page ").count();
+        assert!(pages >= 3, "expected several pages, got {}", pages);
+        assert!(pdf.contains(&format!("/Count {}", pages)));
+
+        // The xref table points at each object
+        let xref_offset: usize = pdf.rsplit("startxref\n").next().unwrap().lines().next().unwrap().parse().unwrap();
+        assert!(pdf[xref_offset..].starts_with("xref\n"));
+        let first_entry = pdf[xref_offset..].lines().nth(3).unwrap();
+        let offset: usize = first_entry[..10].parse().unwrap();
+        assert!(pdf[offset..].starts_with("1 0 obj"));
+    }
+}
@@ -0,0 +1,394 @@
+//! Spreadsheet exports of a scan's violations
+//!
+//! Writes one row per violation with the columns the caller picks, as CSV or
+//! as an Excel workbook, for compliance managers who track findings in
+//! spreadsheets. Text cells in CSV that a spreadsheet would read as a formula
+//! (leading `=`, `+`, `-`, `@`) are prefixed with `'`; XLSX cells are always
+//! written as plain strings.
+
+use anyhow::{anyhow, Context, Result};
+use rust_xlsxwriter::{Format, Workbook};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::models::Violation;
+
+/// Longest text Excel stores in one cell
+const XLSX_MAX_CELL_CHARS: usize = 32_767;
+
+/// Output format of a violation export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SpreadsheetFormat {
+    Csv,
+    Xlsx,
+}
+
+impl SpreadsheetFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SpreadsheetFormat::Csv => "csv",
+            SpreadsheetFormat::Xlsx => "xlsx",
+        }
+    }
+
+    /// Parse a format name; "excel" is accepted for XLSX
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "csv" => Some(SpreadsheetFormat::Csv),
+            "xlsx" | "excel" => Some(SpreadsheetFormat::Xlsx),
+            _ => None,
+        }
+    }
+
+    /// File extension for exports in this format
+    pub fn extension(&self) -> &'static str {
+        self.as_str()
+    }
+}
+
+/// A column of a violation export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ViolationColumn {
+    Id,
+    ControlId,
+    /// Control name from the controls table, e.g. "Logical Access Controls"
+    ControlName,
+    Severity,
+    Status,
+    FilePath,
+    LineNumber,
+    Description,
+    CodeSnippet,
+    DetectionMethod,
+    ConfidenceScore,
+    FunctionName,
+    ClassName,
+    PackageName,
+    Lifecycle,
+    DetectedAt,
+    StatusReason,
+    LlmReasoning,
+}
+
+impl ViolationColumn {
+    pub const ALL: [ViolationColumn; 18] = [
+        ViolationColumn::Id,
+        ViolationColumn::ControlId,
+        ViolationColumn::ControlName,
+        ViolationColumn::Severity,
+        ViolationColumn::Status,
+        ViolationColumn::FilePath,
+        ViolationColumn::LineNumber,
+        ViolationColumn::Description,
+        ViolationColumn::CodeSnippet,
+        ViolationColumn::DetectionMethod,
+        ViolationColumn::ConfidenceScore,
+        ViolationColumn::FunctionName,
+        ViolationColumn::ClassName,
+        ViolationColumn::PackageName,
+        ViolationColumn::Lifecycle,
+        ViolationColumn::DetectedAt,
+        ViolationColumn::StatusReason,
+        ViolationColumn::LlmReasoning,
+    ];
+
+    /// Columns exported when the caller doesn't pick any
+    pub const DEFAULT: [ViolationColumn; 9] = [
+        ViolationColumn::Id,
+        ViolationColumn::ControlId,
+        ViolationColumn::ControlName,
+        ViolationColumn::Severity,
+        ViolationColumn::Status,
+        ViolationColumn::FilePath,
+        ViolationColumn::LineNumber,
+        ViolationColumn::Description,
+        ViolationColumn::DetectedAt,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ViolationColumn::Id => "id",
+            ViolationColumn::ControlId => "control_id",
+            ViolationColumn::ControlName => "control_name",
+            ViolationColumn::Severity => "severity",
+            ViolationColumn::Status => "status",
+            ViolationColumn::FilePath => "file_path",
+            ViolationColumn::LineNumber => "line_number",
+            ViolationColumn::Description => "description",
+            ViolationColumn::CodeSnippet => "code_snippet",
+            ViolationColumn::DetectionMethod => "detection_method",
+            ViolationColumn::ConfidenceScore => "confidence_score",
+            ViolationColumn::FunctionName => "function_name",
+            ViolationColumn::ClassName => "class_name",
+            ViolationColumn::PackageName => "package_name",
+            ViolationColumn::Lifecycle => "lifecycle",
+            ViolationColumn::DetectedAt => "detected_at",
+            ViolationColumn::StatusReason => "status_reason",
+            ViolationColumn::LlmReasoning => "llm_reasoning",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        let s = s.trim();
+        Self::ALL.into_iter().find(|column| column.as_str() == s)
+    }
+
+    /// Header row label
+    pub fn header(&self) -> &'static str {
+        match self {
+            ViolationColumn::Id => "ID",
+            ViolationColumn::ControlId => "Control",
+            ViolationColumn::ControlName => "Control Name",
+            ViolationColumn::Severity => "Severity",
+            ViolationColumn::Status => "Status",
+            ViolationColumn::FilePath => "File",
+            ViolationColumn::LineNumber => "Line",
+            ViolationColumn::Description => "Description",
+            ViolationColumn::CodeSnippet => "Code",
+            ViolationColumn::DetectionMethod => "Detection Method",
+            ViolationColumn::ConfidenceScore => "Confidence",
+            ViolationColumn::FunctionName => "Function",
+            ViolationColumn::ClassName => "Class",
+            ViolationColumn::PackageName => "Package",
+            ViolationColumn::Lifecycle => "Lifecycle",
+            ViolationColumn::DetectedAt => "Detected At",
+            ViolationColumn::StatusReason => "Status Reason",
+            ViolationColumn::LlmReasoning => "AI Reasoning",
+        }
+    }
+
+    /// Parse column names, or the default columns if `names` is None or empty
+    ///
+    /// # Errors
+    /// Returns error naming the first unknown or repeated column
+    pub fn parse_list(names: Option<&[String]>) -> Result<Vec<ViolationColumn>> {
+        let names = match names {
+            Some(names) if !names.is_empty() => names,
+            _ => return Ok(Self::DEFAULT.to_vec()),
+        };
+        let mut columns = Vec::with_capacity(names.len());
+        for name in names {
+            let column = Self::from_str(name).ok_or_else(|| anyhow!("Unknown export column '{}'", name))?;
+            if columns.contains(&column) {
+                return Err(anyhow!("Export column '{}' is listed twice", name));
+            }
+            columns.push(column);
+        }
+        Ok(columns)
+    }
+}
+
+/// One cell of an export
+#[derive(Debug, Clone, PartialEq)]
+enum Cell {
+    Text(String),
+    Number(i64),
+    Empty,
+}
+
+fn text(value: &str) -> Cell {
+    Cell::Text(value.to_string())
+}
+
+fn optional_text(value: Option<&str>) -> Cell {
+    value.map(text).unwrap_or(Cell::Empty)
+}
+
+fn cell(violation: &Violation, column: ViolationColumn, control_names: &HashMap<String, String>) -> Cell {
+    match column {
+        ViolationColumn::Id => Cell::Number(violation.id),
+        ViolationColumn::ControlId => text(&violation.control_id),
+        ViolationColumn::ControlName => optional_text(control_names.get(&violation.control_id).map(String::as_str)),
+        ViolationColumn::Severity => text(&violation.severity),
+        ViolationColumn::Status => text(&violation.status),
+        ViolationColumn::FilePath => text(&violation.file_path),
+        ViolationColumn::LineNumber => Cell::Number(violation.line_number),
+        ViolationColumn::Description => text(&violation.description),
+        ViolationColumn::CodeSnippet => text(&violation.code_snippet),
+        ViolationColumn::DetectionMethod => text(&violation.detection_method),
+        ViolationColumn::ConfidenceScore => violation.confidence_score.map(Cell::Number).unwrap_or(Cell::Empty),
+        ViolationColumn::FunctionName => optional_text(violation.function_name.as_deref()),
+        ViolationColumn::ClassName => optional_text(violation.class_name.as_deref()),
+        ViolationColumn::PackageName => optional_text(violation.package_name.as_deref()),
+        ViolationColumn::Lifecycle => text(&violation.lifecycle),
+        ViolationColumn::DetectedAt => text(&violation.detected_at),
+        ViolationColumn::StatusReason => optional_text(violation.last_status_change_reason.as_deref()),
+        ViolationColumn::LlmReasoning => optional_text(violation.llm_reasoning.as_deref()),
+    }
+}
+
+/// Sort critical first, then by file and line, the order a reviewer works through them
+fn sorted(violations: &[Violation]) -> Vec<&Violation> {
+    let rank = |v: &Violation| v.get_severity().map(|s| -s.numeric_value()).unwrap_or(0);
+    let mut sorted: Vec<&Violation> = violations.iter().collect();
+    sorted.sort_by(|a, b| (rank(a), &a.file_path, a.line_number).cmp(&(rank(b), &b.file_path, b.line_number)));
+    sorted
+}
+
+/// Render `violations` in `format`, one row per violation
+///
+/// `control_names` maps control IDs to names for the `control_name` column.
+///
+/// # Errors
+/// Returns error if the CSV or workbook can't be written
+pub fn render(
+    violations: &[Violation],
+    columns: &[ViolationColumn],
+    control_names: &HashMap<String, String>,
+    format: SpreadsheetFormat,
+) -> Result<Vec<u8>> {
+    let rows: Vec<Vec<Cell>> = sorted(violations)
+        .into_iter()
+        .map(|violation| columns.iter().map(|column| cell(violation, *column, control_names)).collect())
+        .collect();
+    match format {
+        SpreadsheetFormat::Csv => render_csv(columns, &rows),
+        SpreadsheetFormat::Xlsx => render_xlsx(columns, &rows),
+    }
+}
+
+fn render_csv(columns: &[ViolationColumn], rows: &[Vec<Cell>]) -> Result<Vec<u8>> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer
+        .write_record(columns.iter().map(|column| column.header()))
+        .context("Failed to write CSV header")?;
+    for row in rows {
+        writer
+            .write_record(row.iter().map(|cell| match cell {
+                Cell::Text(value) => escape_formula(value),
+                Cell::Number(value) => value.to_string(),
+                Cell::Empty => String::new(),
+            }))
+            .context("Failed to write CSV row")?;
+    }
+    writer.into_inner().map_err(|e| anyhow!("Failed to finish CSV: {}", e))
+}
+
+/// Keep spreadsheets from evaluating text such as `=HYPERLINK(...)` from scanned code
+fn escape_formula(value: &str) -> String {
+    if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    }
+}
+
+fn render_xlsx(columns: &[ViolationColumn], rows: &[Vec<Cell>]) -> Result<Vec<u8>> {
+    let mut workbook = Workbook::new();
+    let bold = Format::new().set_bold();
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_name("Violations").context("Failed to name worksheet")?;
+
+    for (col, column) in columns.iter().enumerate() {
+        worksheet
+            .write_string_with_format(0, col as u16, column.header(), &bold)
+            .context("Failed to write XLSX header")?;
+    }
+    for (index, row) in rows.iter().enumerate() {
+        let row_number = index as u32 + 1;
+        for (col, cell) in row.iter().enumerate() {
+            let written = match cell {
+                Cell::Text(value) => {
+                    let value: String = value.chars().take(XLSX_MAX_CELL_CHARS).collect();
+                    worksheet.write_string(row_number, col as u16, value)
+                }
+                Cell::Number(value) => worksheet.write_number(row_number, col as u16, *value as f64),
+                Cell::Empty => continue,
+            };
+            written.context("Failed to write XLSX cell")?;
+        }
+    }
+
+    if !columns.is_empty() {
+        worksheet.set_freeze_panes(1, 0).context("Failed to freeze header row")?;
+        worksheet
+            .autofilter(0, 0, rows.len() as u32, columns.len() as u16 - 1)
+            .context("Failed to add filters")?;
+        worksheet.autofit();
+    }
+
+    workbook.save_to_buffer().context("Failed to write workbook")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Severity;
+
+    fn violation(id: i64, severity: Severity, file_path: &str, description: &str) -> Violation {
+        let mut violation = Violation::new(
+            1,
+            "CC6.7".to_string(),
+            severity,
+            description.to_string(),
+            file_path.to_string(),
+            id * 10,
+            "api_key = 'x'".to_string(),
+        );
+        violation.id = id;
+        violation
+    }
+
+    #[test]
+    fn test_parse_columns() {
+        assert_eq!(ViolationColumn::parse_list(None).unwrap(), ViolationColumn::DEFAULT.to_vec());
+        assert_eq!(ViolationColumn::parse_list(Some(&[])).unwrap(), ViolationColumn::DEFAULT.to_vec());
+
+        let names = vec!["severity".to_string(), " file_path ".to_string()];
+        assert_eq!(
+            ViolationColumn::parse_list(Some(&names)).unwrap(),
+            vec![ViolationColumn::Severity, ViolationColumn::FilePath]
+        );
+
+        let unknown = vec!["owner".to_string()];
+        assert!(ViolationColumn::parse_list(Some(&unknown)).unwrap_err().to_string().contains("owner"));
+        let repeated = vec!["id".to_string(), "id".to_string()];
+        assert!(ViolationColumn::parse_list(Some(&repeated)).is_err());
+
+        for column in ViolationColumn::ALL {
+            assert_eq!(ViolationColumn::from_str(column.as_str()), Some(column));
+        }
+    }
+
+    #[test]
+    fn test_render_csv() {
+        let violations = vec![
+            violation(1, Severity::Low, "b.py", "Debug logging"),
+            violation(2, Severity::Critical, "a.py", "=HYPERLINK(\"http://x\")"),
+        ];
+        let control_names = HashMap::from([("CC6.7".to_string(), "Transmission of Information".to_string())]);
+        let columns = [
+            ViolationColumn::Id,
+            ViolationColumn::ControlName,
+            ViolationColumn::Severity,
+            ViolationColumn::Description,
+            ViolationColumn::PackageName,
+        ];
+
+        let bytes = render(&violations, &columns, &control_names, SpreadsheetFormat::Csv).unwrap();
+        let csv = String::from_utf8(bytes).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "ID,Control Name,Severity,Description,Package");
+        // Critical first, formula neutralized
+        assert_eq!(lines[1], "2,Transmission of Information,critical,\"'=HYPERLINK(\"\"http://x\"\")\",");
+        assert_eq!(lines[2], "1,Transmission of Information,low,Debug logging,");
+    }
+
+    #[test]
+    fn test_render_xlsx() {
+        let violations = vec![violation(1, Severity::High, "app.py", "Hardcoded key")];
+        let bytes = render(&violations, &ViolationColumn::DEFAULT, &HashMap::new(), SpreadsheetFormat::Xlsx).unwrap();
+        // XLSX is a zip archive
+        assert!(bytes.starts_with(b"PK\x03\x04"));
+    }
+
+    #[test]
+    fn test_format_names() {
+        assert_eq!(SpreadsheetFormat::from_str("CSV"), Some(SpreadsheetFormat::Csv));
+        assert_eq!(SpreadsheetFormat::from_str("excel"), Some(SpreadsheetFormat::Xlsx));
+        assert_eq!(SpreadsheetFormat::from_str("ods"), None);
+        assert_eq!(SpreadsheetFormat::Xlsx.extension(), "xlsx");
+    }
+}
@@ -9,12 +9,86 @@
 //! - No retry logic on transient failures
 //! - Missing circuit breaker patterns
 //! - Unhandled database query failures
+//! - Rust `unwrap()`/`expect()` on network results, which panic on connection errors
+//! - Go `http.Get`-style calls on the default client, `http.Client` values without a `Timeout`,
+//!   and requests built without a context deadline
+//! - Debug mode left enabled (Django, Flask, Express, Node, Spring Boot profiles)
+//! - Database connections set up without a connect timeout (SQLAlchemy, Django
+//!   `DATABASES`, Node MySQL, Mongoose, SQLite `busy_timeout`, Go `sql.Open`/`mongo.Connect`)
 
 use anyhow::Context;
 use anyhow::Result;
 use crate::models::{Severity, Violation};
 use regex::Regex;
 
+/// Database client setup recognized by `A12ResilienceRule::db_call_detector`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DbSetupKind {
+    /// SQLAlchemy `create_engine(...)`
+    SqlAlchemyEngine,
+    /// Django `DATABASES = {...}` settings
+    DjangoDatabases,
+    /// Node `mysql`/`mysql2` `createConnection`/`createPool`
+    MySqlConnection,
+    /// `mongoose.connect`/`mongoose.createConnection`
+    MongooseConnect,
+    /// SQLite connection (Python `sqlite3`, better-sqlite3, rusqlite, Go drivers)
+    Sqlite,
+    /// Go `sql.Open` with a MySQL or Postgres driver
+    GoSqlOpen,
+    /// Go mongo-driver `mongo.Connect`
+    GoMongoConnect,
+}
+
+impl DbSetupKind {
+    /// What the setup is missing, or None if it configures a timeout
+    ///
+    /// Options are often built in a separate variable (or a DSN constant), so
+    /// a timeout key anywhere in the file counts.
+    fn missing_timeout(self, block: &str, code: &str) -> Option<String> {
+        let has = |keys: &[&str]| keys.iter().any(|k| block.contains(k) || code.contains(k));
+
+        let missing = match self {
+            DbSetupKind::SqlAlchemyEngine => (!has(&["connect_timeout", "pool_timeout"]))
+                .then_some("SQLAlchemy create_engine() without connect_args connect_timeout or pool_timeout"),
+            DbSetupKind::DjangoDatabases => {
+                let mut missing = Vec::new();
+                if !has(&["CONN_MAX_AGE", "conn_max_age"]) {
+                    missing.push("CONN_MAX_AGE");
+                }
+                // SQLite backends take a busy `timeout` option instead
+                let timeout_key = if block.contains("sqlite3") { "timeout" } else { "connect_timeout" };
+                if !has(&[timeout_key]) {
+                    missing.push("OPTIONS connect_timeout");
+                }
+                return (!missing.is_empty())
+                    .then(|| format!("Django DATABASES without {}", missing.join(" or ")));
+            }
+            DbSetupKind::MySqlConnection => (!has(&["connectTimeout"]))
+                .then_some("MySQL connection created without connectTimeout"),
+            DbSetupKind::MongooseConnect => (!has(&["serverSelectionTimeoutMS"]))
+                .then_some("mongoose.connect() without serverSelectionTimeoutMS"),
+            DbSetupKind::Sqlite => (!(block.contains("timeout") || has(&["busy_timeout", "busyTimeout"])))
+                .then_some("SQLite connection without busy_timeout"),
+            DbSetupKind::GoSqlOpen => (!has(&["timeout=", "Timeout:"]))
+                .then_some("Go sql.Open() DSN without a connect timeout"),
+            DbSetupKind::GoMongoConnect => (!has(&["SetServerSelectionTimeout", "SetConnectTimeout", "serverSelectionTimeoutMS"]))
+                .then_some("Go mongo.Connect() without a server selection timeout"),
+        };
+        missing.map(|m| m.to_string())
+    }
+}
+
+/// A database setup statement, spanning continuation lines until its
+/// brackets balance
+#[derive(Debug, Clone)]
+struct DbSetupBlock {
+    kind: DbSetupKind,
+    /// Zero-based index of the statement's first line
+    line: usize,
+    text: String,
+}
+
 /// A1.2 Resilience & Error Handling Rule Engine
 ///
 /// Detects violations of resilience and error handling requirements in code.
@@ -34,6 +108,11 @@ impl A12ResilienceRule {
     pub fn analyze(code: &str, file_path: &str, scan_id: i64) -> Result<Vec<Violation>> {
         let mut violations = Vec::new();
 
+        // Spring Boot properties files only carry the debug profile check
+        if file_path.ends_with(".properties") {
+            return Self::detect_debug_mode_enabled(code, file_path, scan_id);
+        }
+
         // Pattern 1: External calls without error handling
         violations.extend(Self::detect_unhandled_external_calls(code, file_path, scan_id)?);
 
@@ -49,6 +128,18 @@ impl A12ResilienceRule {
         // Pattern 5: Missing circuit breaker patterns
         violations.extend(Self::detect_missing_circuit_breaker(code, file_path, scan_id)?);
 
+        // Pattern 6: Rust network results unwrapped instead of handled
+        violations.extend(Self::detect_rust_network_unwrap(code, file_path, scan_id)?);
+
+        // Pattern 7: Go HTTP calls and clients without a timeout
+        violations.extend(Self::detect_go_http_without_timeout(code, file_path, scan_id)?);
+
+        // Pattern 8: Debug/development mode enabled outside development config
+        violations.extend(Self::detect_debug_mode_enabled(code, file_path, scan_id)?);
+
+        // Pattern 9: Database connections without a connect timeout
+        violations.extend(Self::detect_missing_db_timeouts(code, file_path, scan_id)?);
+
         Ok(violations)
     }
 
@@ -82,7 +173,7 @@ impl A12ResilienceRule {
 
             if external_call.is_match(line) {
                 // Check if there's try/except in the surrounding context
-                let check_start = if idx > 3 { idx - 3 } else { 0 };
+                let check_start = idx.saturating_sub(3);
                 let check_end = std::cmp::min(idx + 5, lines.len());
                 let context = lines[check_start..check_end].join(" ");
 
@@ -292,6 +383,444 @@ impl A12ResilienceRule {
 
         Ok(violations)
     }
+
+    /// Detects `unwrap()`/`expect()` on Rust network results
+    ///
+    /// A result counts as a network result when the statement makes an HTTP
+    /// call (`reqwest::get(url).await.unwrap()`), or the unwrapped value is a
+    /// variable named like `response`/`resp` or bound from an HTTP call
+    /// (e.g. `let result = client.get(url).send().await;`). Method chains
+    /// continued on following lines count as one statement, and `#[cfg(test)]`
+    /// modules are skipped.
+    fn detect_rust_network_unwrap(
+        code: &str,
+        file_path: &str,
+        scan_id: i64,
+    ) -> Result<Vec<Violation>> {
+        let mut violations = Vec::new();
+
+        if !file_path.ends_with(".rs") {
+            return Ok(violations);
+        }
+
+        let http_call = Regex::new(
+            r"(reqwest::|awc::|ureq::|surf::|hyper::|\.send\(\)|\b(?:client|http_client|http)\s*\.\s*(?:get|post|put|delete|patch|request)\s*\()",
+        )
+        .context("Failed to compile Rust HTTP call pattern")?;
+
+        let let_binding = Regex::new(r"^\s*let\s+(?:mut\s+)?(\w+)")
+            .context("Failed to compile let binding pattern")?;
+
+        let unwrap_call = Regex::new(r"\.(unwrap|expect)\s*\(")
+            .context("Failed to compile unwrap pattern")?;
+
+        let lines: Vec<&str> = code.lines().collect();
+        let mut network_vars: Vec<String> = Vec::new();
+        let mut idx = 0;
+
+        while idx < lines.len() {
+            let start = idx;
+            let mut statement = lines[idx].trim().to_string();
+
+            // Join method chains continued on the following lines
+            while idx + 1 < lines.len() && lines[idx + 1].trim_start().starts_with('.') {
+                idx += 1;
+                statement.push_str(lines[idx].trim());
+            }
+            idx += 1;
+
+            if statement.starts_with("#[cfg(test)]") {
+                break;
+            }
+            if statement.starts_with("//") {
+                continue;
+            }
+
+            let makes_http_call = http_call.is_match(&statement);
+            if makes_http_call {
+                if let Some(caps) = let_binding.captures(&statement) {
+                    network_vars.push(caps[1].to_string());
+                }
+            }
+
+            let unwraps_network_result = unwrap_call.find_iter(&statement).any(|m| {
+                if makes_http_call {
+                    return true;
+                }
+                let receiver = Self::receiver_root(&statement[..m.start()]);
+                let lower = receiver.to_lowercase();
+                lower.contains("response") || lower == "resp" || network_vars.iter().any(|v| v == receiver)
+            });
+
+            if unwraps_network_result {
+                violations.push(Violation::new(
+                    scan_id,
+                    "A1.2".to_string(),
+                    Severity::Medium,
+                    "Network call result unwrapped (panics on failure instead of handling the error)".to_string(),
+                    file_path.to_string(),
+                    (start + 1) as i64,
+                    statement,
+                ));
+            }
+        }
+
+        Ok(violations)
+    }
+
+    /// Detects Go HTTP calls that can hang forever
+    ///
+    /// `http.Get`/`http.Post`/`http.Head`/`http.PostForm` use
+    /// `http.DefaultClient`, which has no timeout unless the file sets
+    /// `http.DefaultClient.Timeout`. `http.Client{...}` literals are flagged
+    /// when they set no `Timeout` field and the client's `Timeout` is not
+    /// assigned later (`client.Timeout = 10 * time.Second`).
+    ///
+    /// Requests built with `http.NewRequest` or with `context.Background()`/
+    /// `context.TODO()` are flagged when nothing in the file bounds them: no
+    /// `context.WithTimeout`/`WithDeadline` and no client timeout.
+    fn detect_go_http_without_timeout(
+        code: &str,
+        file_path: &str,
+        scan_id: i64,
+    ) -> Result<Vec<Violation>> {
+        let mut violations = Vec::new();
+
+        if !file_path.ends_with(".go") {
+            return Ok(violations);
+        }
+
+        let default_client_call = Regex::new(r"\bhttp\.(Get|Post|Head|PostForm)\s*\(")
+            .context("Failed to compile Go default client pattern")?;
+        let client_literal = Regex::new(r"(?:\b(\w+)\s*:?=\s*)?&?\bhttp\.Client\s*\{")
+            .context("Failed to compile Go client literal pattern")?;
+        let default_client_timeout = Regex::new(r"http\.DefaultClient(\.Timeout\s*=|\s*=\s*&?http\.Client\s*\{[^}]*Timeout\s*:)")
+            .context("Failed to compile Go default client timeout pattern")?;
+
+        let request_without_context = Regex::new(r"\bhttp\.(NewRequest\s*\(|NewRequestWithContext\s*\(\s*context\.(?:Background|TODO)\(\))")
+            .context("Failed to compile Go request context pattern")?;
+        let request_deadline = Regex::new(r"context\.With(Timeout|Deadline)\s*\(|\bTimeout\s*:|\.Timeout\s*=")
+            .context("Failed to compile Go request deadline pattern")?;
+
+        let lines: Vec<&str> = code.lines().collect();
+        let default_client_has_timeout = default_client_timeout.is_match(code);
+        let file_has_deadline = request_deadline.is_match(code);
+
+        for (idx, line) in lines.iter().enumerate() {
+            if line.trim_start().starts_with("//") {
+                continue;
+            }
+
+            if let Some(caps) = default_client_call.captures(line) {
+                if !default_client_has_timeout {
+                    violations.push(Violation::new(
+                        scan_id,
+                        "A1.2".to_string(),
+                        Severity::High,
+                        format!("Go http.{} uses the default client, which has no timeout", &caps[1]),
+                        file_path.to_string(),
+                        (idx + 1) as i64,
+                        line.trim().to_string(),
+                    ));
+                }
+                continue;
+            }
+
+            if let Some(caps) = request_without_context.captures(line) {
+                if !file_has_deadline {
+                    let request = if caps[1].starts_with("NewRequestWithContext") {
+                        "NewRequestWithContext"
+                    } else {
+                        "NewRequest"
+                    };
+                    violations.push(Violation::new(
+                        scan_id,
+                        "A1.2".to_string(),
+                        Severity::Medium,
+                        format!("Go http.{} request has no context deadline or client timeout", request),
+                        file_path.to_string(),
+                        (idx + 1) as i64,
+                        line.trim().to_string(),
+                    ));
+                }
+                continue;
+            }
+
+            let Some(caps) = client_literal.captures(line) else {
+                continue;
+            };
+
+            // The literal's fields, ending where its braces balance
+            let mut depth = 0;
+            let mut literal_end = idx;
+            for (i, literal_line) in lines.iter().enumerate().skip(idx) {
+                depth += literal_line.matches('{').count() as i32 - literal_line.matches('}').count() as i32;
+                literal_end = i;
+                if depth <= 0 {
+                    break;
+                }
+            }
+            if lines[idx..=literal_end].iter().any(|l| l.contains("Timeout:")) {
+                continue;
+            }
+
+            let timeout_set_later = caps.get(1).is_some_and(|name| {
+                let assignment = format!("{}.Timeout", name.as_str());
+                lines[literal_end..].iter().any(|l| l.contains(&assignment))
+            });
+            if timeout_set_later || line.contains("http.DefaultClient") {
+                continue;
+            }
+
+            violations.push(Violation::new(
+                scan_id,
+                "A1.2".to_string(),
+                Severity::High,
+                "Go http.Client created without a Timeout".to_string(),
+                file_path.to_string(),
+                (idx + 1) as i64,
+                line.trim().to_string(),
+            ));
+        }
+
+        Ok(violations)
+    }
+
+    /// Detects debug/development mode left enabled in application config
+    ///
+    /// - Django `DEBUG = True` and Flask `app.run(debug=True)`
+    /// - Express `app.set('env', 'development')`
+    /// - Node checks that treat anything but production as development
+    ///   (`process.env.NODE_ENV !== 'production'`, `NODE_ENV || 'development'`)
+    /// - Spring Boot `spring.profiles.active` naming a dev profile but no
+    ///   production one in `application.properties`
+    ///
+    /// Test files and files meant for development only (`dev.py`,
+    /// `development.js`, `.env.development`, ...) are skipped.
+    fn detect_debug_mode_enabled(
+        code: &str,
+        file_path: &str,
+        scan_id: i64,
+    ) -> Result<Vec<Violation>> {
+        let mut violations = Vec::new();
+
+        if Self::is_development_only_file(file_path) {
+            return Ok(violations);
+        }
+
+        let extension = std::path::Path::new(file_path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("");
+
+        let patterns: &[&str] = match extension {
+            "py" => &[
+                r"^\s*DEBUG\s*=\s*True\b",
+                r"\.run\([^)]*\bdebug\s*=\s*True\b",
+            ],
+            "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" => &[
+                r#"\.set\(\s*['"]env['"]\s*,\s*['"]development['"]\s*\)"#,
+                r#"process\.env\.NODE_ENV\s*!==?\s*['"]production['"]"#,
+                r#"process\.env\.NODE_ENV\s*(\|\||\?\?)\s*['"]development['"]"#,
+            ],
+            "properties" => &[
+                r"^\s*spring\.profiles\.active\s*[=:]\s*(.*)$",
+            ],
+            _ => return Ok(violations),
+        };
+        let patterns = patterns
+            .iter()
+            .map(|p| Regex::new(p))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .context("Failed to compile debug mode pattern")?;
+
+        for (idx, line) in code.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.starts_with('#') || trimmed.starts_with("//") || trimmed.starts_with('!') {
+                continue;
+            }
+
+            let enabled = patterns.iter().any(|pattern| match pattern.captures(line) {
+                // Spring: only a dev profile without a production one
+                Some(caps) if extension == "properties" => {
+                    let profiles: Vec<String> = caps[1]
+                        .split(',')
+                        .map(|p| p.trim().to_lowercase())
+                        .collect();
+                    profiles.iter().any(|p| matches!(p.as_str(), "dev" | "development" | "local"))
+                        && !profiles.iter().any(|p| matches!(p.as_str(), "prod" | "production"))
+                }
+                Some(_) => true,
+                None => false,
+            });
+
+            if enabled {
+                violations.push(Violation::new(
+                    scan_id,
+                    "A1.2".to_string(),
+                    Severity::Medium,
+                    "Debug mode enabled: exposes stack traces and detailed errors".to_string(),
+                    file_path.to_string(),
+                    (idx + 1) as i64,
+                    trimmed.to_string(),
+                ));
+            }
+        }
+
+        Ok(violations)
+    }
+
+    /// Detects database connections set up without a timeout
+    ///
+    /// Without one, a database that stops answering hangs requests (or the
+    /// whole process at startup) instead of failing fast. See
+    /// [`DbSetupKind::missing_timeout`] for what each setup needs. Test and
+    /// development-only files are skipped.
+    fn detect_missing_db_timeouts(
+        code: &str,
+        file_path: &str,
+        scan_id: i64,
+    ) -> Result<Vec<Violation>> {
+        let mut violations = Vec::new();
+
+        if Self::is_development_only_file(file_path) {
+            return Ok(violations);
+        }
+
+        for block in Self::db_call_detector(code, file_path)? {
+            if let Some(missing) = block.kind.missing_timeout(&block.text, code) {
+                let first_line = block.text.lines().next().unwrap_or("").trim();
+                violations.push(Violation::new(
+                    scan_id,
+                    "A1.2".to_string(),
+                    Severity::Medium,
+                    format!("Database connection without timeout: {}", missing),
+                    file_path.to_string(),
+                    (block.line + 1) as i64,
+                    first_line.to_string(),
+                ));
+            }
+        }
+
+        Ok(violations)
+    }
+
+    /// Finds database client setup statements in Python, JavaScript/TypeScript,
+    /// Go and Rust files
+    ///
+    /// Each block runs from the setup call (or `DATABASES = {`) until its
+    /// brackets balance, capped at 40 lines. Commented-out lines are skipped.
+    fn db_call_detector(code: &str, file_path: &str) -> Result<Vec<DbSetupBlock>> {
+        let extension = std::path::Path::new(file_path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("");
+
+        let patterns: Vec<(DbSetupKind, &str)> = match extension {
+            "py" => vec![
+                (DbSetupKind::SqlAlchemyEngine, r"\bcreate_(?:async_)?engine\s*\("),
+                (DbSetupKind::DjangoDatabases, r"^\s*DATABASES\s*=\s*\{"),
+                (DbSetupKind::Sqlite, r"\b(?:sqlite3|aiosqlite)\.connect\s*\("),
+            ],
+            "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" => {
+                let mut patterns = vec![
+                    (DbSetupKind::MySqlConnection, r"\bmysql2?\.create(?:Connection|Pool)\s*\("),
+                    (DbSetupKind::MongooseConnect, r"\bmongoose\.(?:connect|createConnection)\s*\("),
+                ];
+                // `Database` alone is too generic without a SQLite import
+                if code.contains("sqlite3") {
+                    patterns.push((DbSetupKind::Sqlite, r"\bnew\s+(?:sqlite3\.)?Database\s*\("));
+                }
+                patterns
+            }
+            "go" => vec![
+                // Driver name decides between SQLite and network databases
+                (DbSetupKind::GoSqlOpen, r#"\bsqlx?\.(?:Open|Connect)\s*\(\s*"(\w+)""#),
+                (DbSetupKind::GoMongoConnect, r"\bmongo\.Connect\s*\("),
+            ],
+            "rs" if code.contains("rusqlite") => vec![
+                (DbSetupKind::Sqlite, r"\bConnection::open\s*\("),
+            ],
+            _ => return Ok(Vec::new()),
+        };
+        let patterns = patterns
+            .into_iter()
+            .map(|(kind, pattern)| Regex::new(pattern).map(|re| (kind, re)))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .context("Failed to compile database setup pattern")?;
+
+        let lines: Vec<&str> = code.lines().collect();
+        let mut blocks = Vec::new();
+
+        for (idx, line) in lines.iter().enumerate() {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with('#') || trimmed.starts_with("//") {
+                continue;
+            }
+
+            let Some((kind, caps)) = patterns
+                .iter()
+                .find_map(|(kind, re)| re.captures(line).map(|caps| (*kind, caps)))
+            else {
+                continue;
+            };
+
+            let kind = match (kind, caps.get(1).map(|d| d.as_str())) {
+                (DbSetupKind::GoSqlOpen, Some(driver)) if driver.contains("sqlite") => DbSetupKind::Sqlite,
+                (DbSetupKind::GoSqlOpen, Some("mysql" | "postgres" | "pgx")) => DbSetupKind::GoSqlOpen,
+                (DbSetupKind::GoSqlOpen, _) => continue,
+                (kind, _) => kind,
+            };
+
+            let mut depth = 0i32;
+            let mut end = idx;
+            for (i, block_line) in lines.iter().enumerate().skip(idx).take(40) {
+                depth += block_line.matches(['(', '{', '[']).count() as i32
+                    - block_line.matches([')', '}', ']']).count() as i32;
+                end = i;
+                if depth <= 0 {
+                    break;
+                }
+            }
+
+            blocks.push(DbSetupBlock {
+                kind,
+                line: idx,
+                text: lines[idx..=end].join("\n"),
+            });
+        }
+
+        Ok(blocks)
+    }
+
+    /// Whether a file is a test or is only used in development
+    /// (`settings/dev.py`, `config/development.js`, `.env.development`,
+    /// `application-dev.properties`)
+    fn is_development_only_file(file_path: &str) -> bool {
+        let path = file_path.to_lowercase();
+        if path.contains("test") || path.contains(".spec.") {
+            return true;
+        }
+
+        let file_name = path.rsplit(['/', '\\']).next().unwrap_or(&path);
+        let stem = file_name.split('.').next().unwrap_or(file_name);
+        matches!(stem, "dev" | "development" | "local" | "application-dev" | "application-local")
+            || file_name.starts_with(".env.dev")
+            || file_name.starts_with(".env.local")
+    }
+
+    /// Root identifier of the method chain ending `expr`,
+    /// e.g. "response" for `let body = response.json::<Body>().await`
+    fn receiver_root(expr: &str) -> &str {
+        let chain = expr
+            .rsplit(|c: char| c.is_whitespace() || matches!(c, '=' | ',' | '&' | '!' | ';'))
+            .next()
+            .unwrap_or("");
+        let root = chain.split('.').next().unwrap_or("");
+        root.rsplit(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .next()
+            .unwrap_or("")
+    }
 }
 
 #[cfg(test)]
@@ -547,4 +1076,446 @@ mod tests {
             "Should detect unhandled cursor.execute"
         );
     }
+
+    fn rust_unwrap_violations(code: &str) -> Vec<Violation> {
+        A12ResilienceRule::analyze(code, "src/client.rs", 1)
+            .unwrap()
+            .into_iter()
+            .filter(|v| v.description.contains("unwrapped"))
+            .collect()
+    }
+
+    #[test]
+    fn test_rust_unwrap_on_http_call_flagged() {
+        let code = "let body = reqwest::get(url).await.unwrap().text().await.unwrap();";
+        let violations = rust_unwrap_violations(code);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].severity, "medium");
+        assert_eq!(violations[0].control_id, "A1.2");
+    }
+
+    #[test]
+    fn test_rust_expect_on_response_variable_flagged() {
+        let code = "let response = fetch_status().await?;\nlet data: Status = response.json().await.expect(\"bad body\");";
+        let violations = rust_unwrap_violations(code);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line_number, 2);
+    }
+
+    #[test]
+    fn test_rust_response_errors_handled_not_flagged() {
+        let code = "let data: Status = response.json().await.map_err(ApiError::from)?;\nlet retries: u32 = settings.get(\"retries\").expect(\"retries set\");\nlog_response(&response);";
+        assert!(rust_unwrap_violations(code).is_empty());
+    }
+
+    #[test]
+    fn test_rust_unwrap_on_result_bound_from_http_call() {
+        let code = "let result = client.get(url).send().await;\nlet resp = result.unwrap();";
+        let violations = rust_unwrap_violations(code);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line_number, 2);
+    }
+
+    #[test]
+    fn test_rust_multiline_chain_unwrap_flagged() {
+        let code = "let resp = client\n    .post(url)\n    .json(&payload)\n    .send()\n    .await\n    .unwrap();";
+        let violations = rust_unwrap_violations(code);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line_number, 1);
+    }
+
+    #[test]
+    fn test_rust_unwrap_on_non_network_values_ignored() {
+        let code = "let result = parse_config(&raw);\nlet config = result.unwrap();\nlet port: u16 = env_port.parse().unwrap();";
+        assert!(rust_unwrap_violations(code).is_empty());
+    }
+
+    #[test]
+    fn test_rust_handled_network_errors_and_tests_ignored() {
+        let code = r#"let resp = reqwest::get(url).await?;
+let body = match resp.text().await {
+    Ok(body) => body,
+    Err(e) => return Err(e.into()),
+};
+
+#[cfg(test)]
+mod tests {
+    fn fixture() { let r = reqwest::blocking::get(URL).unwrap(); }
+}
+"#;
+        assert!(rust_unwrap_violations(code).is_empty());
+        assert!(A12ResilienceRule::analyze("let r = reqwest::get(url).await.unwrap();", "client.py", 1)
+            .unwrap()
+            .iter()
+            .all(|v| !v.description.contains("unwrapped")));
+    }
+
+    fn go_timeout_violations(code: &str) -> Vec<Violation> {
+        A12ResilienceRule::analyze(code, "client.go", 1)
+            .unwrap()
+            .into_iter()
+            .filter(|v| v.description.starts_with("Go http."))
+            .collect()
+    }
+
+    #[test]
+    fn test_go_default_client_call_flagged() {
+        let code = "func fetch(url string) (*http.Response, error) {\n    return http.Get(url)\n}\n";
+        let violations = go_timeout_violations(code);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line_number, 2);
+        assert_eq!(violations[0].severity, "high");
+        assert!(violations[0].description.contains("http.Get"));
+
+        // Setting a timeout on the default client covers every call
+        let configured = "func init() {\n    http.DefaultClient.Timeout = 10 * time.Second\n}\n\nfunc fetch(url string) (*http.Response, error) {\n    return http.Get(url)\n}\n";
+        assert!(go_timeout_violations(configured).is_empty());
+    }
+
+    #[test]
+    fn test_go_client_literal_without_timeout_flagged() {
+        let code = "client := &http.Client{\n    Transport: transport,\n}\nresp, err := client.Do(req)\n";
+        let violations = go_timeout_violations(code);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line_number, 1);
+        assert_eq!(violations[0].description, "Go http.Client created without a Timeout");
+    }
+
+    #[test]
+    fn test_go_client_with_timeout_allowed() {
+        let code = r#"client := &http.Client{
+    Timeout:   10 * time.Second,
+    Transport: transport,
+}
+other := &http.Client{}
+other.Timeout = 5 * time.Second
+resp, err := client.Get(url)
+"#;
+        assert!(go_timeout_violations(code).is_empty());
+        assert!(go_timeout_violations("// resp, err := http.Get(url)").is_empty());
+        assert!(A12ResilienceRule::analyze("resp, err := http.Get(url)", "client.py", 1)
+            .unwrap()
+            .iter()
+            .all(|v| !v.description.starts_with("Go http.")));
+    }
+
+    #[test]
+    fn test_go_request_without_deadline_flagged() {
+        let code = r#"func fetch(client *http.Client, url string) (*http.Response, error) {
+    req, err := http.NewRequest("GET", url, nil)
+    other, _ := http.NewRequestWithContext(context.Background(), "GET", url, nil)
+    return client.Do(req)
+}
+"#;
+        let violations = go_timeout_violations(code);
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].severity, "medium");
+        assert!(violations[0].description.contains("http.NewRequest "));
+        assert!(violations[1].description.contains("http.NewRequestWithContext"));
+    }
+
+    #[test]
+    fn test_go_request_with_deadline_allowed() {
+        let code = r#"func fetch(ctx context.Context, url string) (*http.Response, error) {
+    ctx, cancel := context.WithTimeout(ctx, 5*time.Second)
+    defer cancel()
+    req, err := http.NewRequestWithContext(ctx, "GET", url, nil)
+    fallback, _ := http.NewRequest("GET", url, nil)
+    return http.DefaultClient.Do(req)
+}
+"#;
+        assert!(go_timeout_violations(code).is_empty());
+    }
+
+    // ===== Debug mode =====
+
+    fn debug_violations(code: &str, file_path: &str) -> Vec<Violation> {
+        A12ResilienceRule::analyze(code, file_path, 1)
+            .unwrap()
+            .into_iter()
+            .filter(|v| v.description.starts_with("Debug mode enabled"))
+            .collect()
+    }
+
+    #[test]
+    fn test_django_debug_true_flagged() {
+        let code = "SECRET_KEY = os.environ['SECRET_KEY']\nDEBUG = True\nALLOWED_HOSTS = ['*']\n";
+        let violations = debug_violations(code, "mysite/settings.py");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line_number, 2);
+        assert_eq!(violations[0].severity, "medium");
+        assert_eq!(violations[0].description, "Debug mode enabled: exposes stack traces and detailed errors");
+    }
+
+    #[test]
+    fn test_django_debug_from_environment_allowed() {
+        let code = "DEBUG = os.environ.get('DJANGO_DEBUG') == '1'\nDEBUG = False\n# DEBUG = True\n";
+        assert!(debug_violations(code, "mysite/settings.py").is_empty());
+    }
+
+    #[test]
+    fn test_flask_run_debug_true_flagged() {
+        let code = "if __name__ == '__main__':\n    app.run(host='0.0.0.0', port=5000, debug=True)\n";
+        let violations = debug_violations(code, "app.py");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line_number, 2);
+    }
+
+    #[test]
+    fn test_flask_run_without_debug_allowed() {
+        let code = "app.run(host='0.0.0.0', debug=False)\napp.run()\n";
+        assert!(debug_violations(code, "app.py").is_empty());
+    }
+
+    #[test]
+    fn test_express_env_development_flagged() {
+        let code = "const app = express();\napp.set('env', 'development');\n";
+        let violations = debug_violations(code, "server.js");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line_number, 2);
+    }
+
+    #[test]
+    fn test_express_env_production_allowed() {
+        let code = "app.set(\"env\", \"production\");\napp.set('view engine', 'pug');\n";
+        assert!(debug_violations(code, "server.ts").is_empty());
+    }
+
+    #[test]
+    fn test_node_env_not_production_check_flagged() {
+        let code = "const isDev = process.env.NODE_ENV !== 'production';\nif (isDev) app.use(errorHandler({ dumpExceptions: true }));\n";
+        assert_eq!(debug_violations(code, "src/app.ts").len(), 1);
+    }
+
+    #[test]
+    fn test_node_env_defaulting_to_development_flagged() {
+        let code = "const env = process.env.NODE_ENV || 'development';\nconst mode = process.env.NODE_ENV ?? \"development\";\n";
+        assert_eq!(debug_violations(code, "config/index.js").len(), 2);
+    }
+
+    #[test]
+    fn test_node_env_production_check_allowed() {
+        let code = "const isProd = process.env.NODE_ENV === 'production';\nconst env = process.env.NODE_ENV || 'production';\n";
+        assert!(debug_violations(code, "src/app.js").is_empty());
+    }
+
+    #[test]
+    fn test_spring_dev_profile_without_production_flagged() {
+        let code = "server.port=8080\nspring.profiles.active=dev\n";
+        let violations = debug_violations(code, "src/main/resources/application.properties");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line_number, 2);
+        assert_eq!(violations[0].code_snippet, "spring.profiles.active=dev");
+    }
+
+    #[test]
+    fn test_spring_profile_with_production_allowed() {
+        let code = "spring.profiles.active=dev,prod\n";
+        assert!(debug_violations(code, "application.properties").is_empty());
+        let code = "spring.profiles.active: production\n# spring.profiles.active=dev\n";
+        assert!(debug_violations(code, "application.properties").is_empty());
+    }
+
+    #[test]
+    fn test_properties_files_only_get_debug_check() {
+        let code = "spring.profiles.active = local\napi.url=http://partner.io/query(\n";
+        let violations = A12ResilienceRule::analyze(code, "application.properties", 1).unwrap();
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].description.starts_with("Debug mode enabled"));
+    }
+
+    #[test]
+    fn test_debug_mode_in_development_files_skipped() {
+        assert!(debug_violations("DEBUG = True", "mysite/settings/dev.py").is_empty());
+        assert!(debug_violations("app.set('env', 'development');", "config/development.js").is_empty());
+        assert!(debug_violations("spring.profiles.active=dev", "application-dev.properties").is_empty());
+        assert!(A12ResilienceRule::is_development_only_file(".env.development"));
+        assert!(A12ResilienceRule::is_development_only_file("config/.env.local"));
+    }
+
+    #[test]
+    fn test_debug_mode_in_test_files_skipped() {
+        assert!(debug_violations("DEBUG = True", "tests/settings.py").is_empty());
+        assert!(debug_violations("const isDev = process.env.NODE_ENV !== 'production';", "src/app.spec.ts").is_empty());
+    }
+
+    #[test]
+    fn test_development_only_file_heuristic_keeps_production_names() {
+        assert!(!A12ResilienceRule::is_development_only_file("mysite/settings.py"));
+        assert!(!A12ResilienceRule::is_development_only_file("config/production.js"));
+        assert!(!A12ResilienceRule::is_development_only_file("devices/views.py"));
+        assert!(!A12ResilienceRule::is_development_only_file("src/main/resources/application.properties"));
+    }
+
+    // ===== Database connection timeouts =====
+
+    fn db_timeout_violations(code: &str, file_path: &str) -> Vec<Violation> {
+        A12ResilienceRule::analyze(code, file_path, 1)
+            .unwrap()
+            .into_iter()
+            .filter(|v| v.description.starts_with("Database connection without timeout"))
+            .collect()
+    }
+
+    #[test]
+    fn test_sqlalchemy_engine_without_timeout_flagged() {
+        let code = "from sqlalchemy import create_engine\nengine = create_engine(\"postgresql://app@db/prod\")\n";
+        let violations = db_timeout_violations(code, "app/db.py");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].severity, "medium");
+        assert_eq!(violations[0].line_number, 2);
+        assert!(violations[0].description.contains("SQLAlchemy"));
+    }
+
+    #[test]
+    fn test_sqlalchemy_engine_with_connect_timeout_allowed() {
+        let code = "engine = create_engine(\n    DATABASE_URL,\n    connect_args={\"connect_timeout\": 10},\n)\n";
+        assert!(db_timeout_violations(code, "app/db.py").is_empty());
+    }
+
+    #[test]
+    fn test_sqlalchemy_engine_with_pool_timeout_allowed() {
+        let code = "engine = create_async_engine(DATABASE_URL, pool_size=5, pool_timeout=30)\n";
+        assert!(db_timeout_violations(code, "app/db.py").is_empty());
+    }
+
+    #[test]
+    fn test_django_databases_missing_both_settings() {
+        let code = "DATABASES = {\n    'default': {\n        'ENGINE': 'django.db.backends.postgresql',\n        'NAME': 'prod',\n    }\n}\n";
+        let violations = db_timeout_violations(code, "mysite/settings.py");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line_number, 1);
+        assert!(violations[0].description.contains("CONN_MAX_AGE or OPTIONS connect_timeout"));
+    }
+
+    #[test]
+    fn test_django_databases_missing_connect_timeout_only() {
+        let code = "DATABASES = {\n    'default': {\n        'ENGINE': 'django.db.backends.mysql',\n        'CONN_MAX_AGE': 60,\n    }\n}\n";
+        let violations = db_timeout_violations(code, "mysite/settings.py");
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].description.ends_with("without OPTIONS connect_timeout"));
+    }
+
+    #[test]
+    fn test_django_databases_with_timeouts_allowed() {
+        let code = "DATABASES = {\n    'default': {\n        'ENGINE': 'django.db.backends.postgresql',\n        'CONN_MAX_AGE': 60,\n        'OPTIONS': {'connect_timeout': 5},\n    }\n}\n";
+        assert!(db_timeout_violations(code, "mysite/settings.py").is_empty());
+    }
+
+    #[test]
+    fn test_django_conn_max_age_set_after_databases() {
+        let code = "DATABASES = {'default': dj_database_url.config()}\nDATABASES['default']['CONN_MAX_AGE'] = 600\nDATABASES['default']['OPTIONS'] = {'connect_timeout': 5}\n";
+        assert!(db_timeout_violations(code, "mysite/settings.py").is_empty());
+    }
+
+    #[test]
+    fn test_python_sqlite_without_timeout_flagged() {
+        let code = "import sqlite3\nconn = sqlite3.connect('app.db')\n";
+        let violations = db_timeout_violations(code, "store.py");
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].description.contains("busy_timeout"));
+    }
+
+    #[test]
+    fn test_python_sqlite_with_timeout_or_pragma_allowed() {
+        assert!(db_timeout_violations("conn = sqlite3.connect('app.db', timeout=10)\n", "store.py").is_empty());
+        let code = "conn = sqlite3.connect('app.db')\nconn.execute('PRAGMA busy_timeout = 5000')\n";
+        assert!(db_timeout_violations(code, "store.py").is_empty());
+    }
+
+    #[test]
+    fn test_mysql_create_connection_without_connect_timeout_flagged() {
+        let code = "const mysql = require('mysql');\nconst connection = mysql.createConnection({\n  host: 'db.internal',\n  user: 'app',\n});\n";
+        let violations = db_timeout_violations(code, "src/db.js");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line_number, 2);
+        assert!(violations[0].description.contains("connectTimeout"));
+    }
+
+    #[test]
+    fn test_mysql2_pool_with_connect_timeout_allowed() {
+        let code = "import mysql from 'mysql2/promise';\nexport const pool = mysql.createPool({\n  host: process.env.DB_HOST,\n  connectTimeout: 10000,\n});\n";
+        assert!(db_timeout_violations(code, "src/db.ts").is_empty());
+    }
+
+    #[test]
+    fn test_mongoose_connect_without_server_selection_timeout_flagged() {
+        let code = "await mongoose.connect(process.env.MONGO_URL);\n";
+        let violations = db_timeout_violations(code, "src/server.ts");
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].description.contains("serverSelectionTimeoutMS"));
+    }
+
+    #[test]
+    fn test_mongoose_connect_with_options_variable_allowed() {
+        let code = "const options = { serverSelectionTimeoutMS: 5000 };\nawait mongoose.connect(uri, options);\n";
+        assert!(db_timeout_violations(code, "src/server.js").is_empty());
+    }
+
+    #[test]
+    fn test_better_sqlite3_timeout_option() {
+        let code = "const Database = require('better-sqlite3');\nconst db = new Database('app.db');\n";
+        assert_eq!(db_timeout_violations(code, "src/store.js").len(), 1);
+        let code = "const Database = require('better-sqlite3');\nconst db = new Database('app.db', { timeout: 5000 });\n";
+        assert!(db_timeout_violations(code, "src/store.js").is_empty());
+    }
+
+    #[test]
+    fn test_generic_database_class_without_sqlite_import_ignored() {
+        let code = "import { Database } from './models';\nconst db = new Database(config);\n";
+        assert!(db_timeout_violations(code, "src/index.ts").is_empty());
+    }
+
+    #[test]
+    fn test_go_sql_open_mysql_without_timeout_flagged() {
+        let code = "dsn := \"app:secret@tcp(db:3306)/prod\"\ndb, err := sql.Open(\"mysql\", dsn)\n";
+        let violations = db_timeout_violations(code, "internal/store/db.go");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line_number, 2);
+        assert!(violations[0].description.contains("sql.Open()"));
+    }
+
+    #[test]
+    fn test_go_sql_open_postgres_with_connect_timeout_allowed() {
+        let code = "db, err := sql.Open(\"postgres\", \"host=db dbname=prod connect_timeout=5\")\n";
+        assert!(db_timeout_violations(code, "internal/store/db.go").is_empty());
+    }
+
+    #[test]
+    fn test_go_sqlite_busy_timeout() {
+        let code = "db, err := sql.Open(\"sqlite3\", \"file:app.db\")\n";
+        let violations = db_timeout_violations(code, "internal/store/db.go");
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].description.contains("SQLite"));
+        let code = "db, err := sql.Open(\"sqlite3\", \"file:app.db?_busy_timeout=5000\")\n";
+        assert!(db_timeout_violations(code, "internal/store/db.go").is_empty());
+    }
+
+    #[test]
+    fn test_go_mongo_connect_server_selection_timeout() {
+        let code = "client, err := mongo.Connect(ctx, options.Client().ApplyURI(uri))\n";
+        assert_eq!(db_timeout_violations(code, "cmd/api/main.go").len(), 1);
+        let code = "opts := options.Client().ApplyURI(uri).SetServerSelectionTimeout(5 * time.Second)\nclient, err := mongo.Connect(ctx, opts)\n";
+        assert!(db_timeout_violations(code, "cmd/api/main.go").is_empty());
+    }
+
+    #[test]
+    fn test_db_call_detector_blocks() {
+        let code = "engine = create_engine(\n    DATABASE_URL,\n    echo=False,\n)\n# conn = sqlite3.connect('old.db')\nconn = sqlite3.connect('app.db')\n";
+        let blocks = A12ResilienceRule::db_call_detector(code, "app/db.py").unwrap();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].kind, DbSetupKind::SqlAlchemyEngine);
+        assert_eq!(blocks[0].line, 0);
+        assert_eq!(blocks[0].text.lines().count(), 4);
+        assert_eq!(blocks[1].kind, DbSetupKind::Sqlite);
+        assert_eq!(blocks[1].line, 5);
+
+        let code = "use rusqlite::Connection;\nlet conn = Connection::open(path)?;\n";
+        let blocks = A12ResilienceRule::db_call_detector(code, "src/db.rs").unwrap();
+        assert_eq!(blocks[0].kind, DbSetupKind::Sqlite);
+
+        // Unsupported drivers and test files are left alone
+        assert!(A12ResilienceRule::db_call_detector("sql.Open(\"sqlserver\", dsn)", "db.go").unwrap().is_empty());
+        assert!(db_timeout_violations("engine = create_engine(URL)", "tests/conftest.py").is_empty());
+    }
 }
@@ -9,11 +9,120 @@
 //! - Missing transaction logging
 //! - Insufficient error logging
 //! - Missing authentication event logging
+//! - Admin/privileged operations (role changes, deletions, impersonation) that
+//!   write to the database without logging, including Express admin routes
+//! - Go `net/http` and gin handlers passing request user data to the standard `log` package
+//! - Flask/Express/FastAPI handlers that read or write the database without any
+//!   logging, in files that import a logging library
+//! - Error responses that send exception messages, stack traces or SQL to the
+//!   client (`jsonify(error=str(e))`, `res.json({ error: err.stack })`)
+//! - User-action log entries without an `event_id`/`correlation_id`, and
+//!   warnings in authentication functions without a `correlation_id`
 
 use anyhow::Context;
 use anyhow::Result;
 use crate::models::{Severity, Violation};
-use regex::Regex;
+use crate::scanner::tree_sitter_utils::{CodeParser, ParseResult};
+use regex::{Regex, RegexSet};
+
+/// Function name fragments (snake_case) that mark admin/privileged operations
+const ADMIN_OPERATION_NAMES: &[&str] = &[
+    "admin_",
+    "delete_",
+    "update_role",
+    "change_permission",
+    "reset_password",
+    "impersonate_",
+];
+
+/// Database writes inside an admin operation that must be audited
+const ADMIN_DB_WRITE_PATTERN: &str =
+    r"(\bdb\.session\.commit\(\)|\.save\(|\.update\(|\.delete\()";
+
+/// Calls that count as audit logging inside an admin operation
+const ADMIN_LOGGING_PATTERN: &str =
+    r"(\b(log|logger|logging|audit_log|audit_logger)\.\w+\(|console\.(log|info|warn|error)\(|\baudit\w*\()";
+
+/// Express route registration on an admin/management path
+const ADMIN_ROUTE_PATTERN: &str =
+    r#"\b\w+\.(get|post|put|patch|delete|all)\s*\(\s*['"`]([^'"`]*/(admin|management)(/[^'"`]*)?)['"`]"#;
+
+/// Function name fragments (snake_case) that mark an API handler
+const API_HANDLER_NAMES: &[&str] = &["view", "handler", "endpoint", "route", "controller"];
+
+/// Flask/FastAPI route decorators (`@app.route(...)`, `@router.post(...)`)
+const ROUTE_DECORATOR_PATTERN: &str = r"^@\w+\.(route|get|post|put|patch|delete)\s*\(";
+
+/// Database writes inside an API handler
+const API_DB_WRITE_PATTERN: &str =
+    r"(\bdb\.session\.(commit|add)\(|\.save\(|\bdb\.update\(|\.update\(|\.create\(|\.insert\w*\(|\.delete\(|\.destroy\()";
+
+/// Database reads inside an API handler
+const API_DB_READ_PATTERN: &str =
+    r"(\.query\b|\.filter(_by)?\(|\.find\w*\(|\.all\(\)|\.first\(\)|\.get_or_404\(|\bSELECT\s)";
+
+/// Imports of a logging library (Python stdlib/structlog/loguru, Node loggers or
+/// a local `logger` module)
+const LOGGING_IMPORT_PATTERN: &str = r#"(?m)(^\s*(import|from)\s+(logging|structlog|loguru)\b|(require\(\s*|from\s+)['"](winston|pino|bunyan|log4js|loglevel|signale|[./\w-]*/logger)['"])"#;
+
+/// Lowercase fragments that mark a logged value as sensitive, with their display names
+const SENSITIVE_LOG_KEYWORDS: [(&str, &str); 11] = [
+    ("password", "password"),
+    ("pwd", "password"),
+    ("secret", "secret"),
+    ("api_key", "API key"),
+    ("apikey", "API key"),
+    ("token", "token"),
+    ("ssn", "SSN"),
+    ("social", "social security"),
+    ("card_number", "credit card"),
+    ("card", "credit card"),
+    ("cvv", "CVV"),
+];
+
+/// Calls that send a string to the HTTP client: Flask/FastAPI/Django responses
+/// and Express `res` methods, including `res.status(500).json(...)`
+const RESPONSE_PATTERNS: &[&str] = &[
+    r"\bjsonify\s*\(",
+    r"\bmake_response\s*\(",
+    r"\b(HttpResponse\w*|JsonResponse|JSONResponse|PlainTextResponse|HTMLResponse)\s*\(",
+    r"\bHTTPException\s*\(",
+    r"\babort\s*\(\s*\d{3}\s*,",
+    r"\b(res|response)\s*(\.status\s*\([^)]*\))?\s*\.(send|json|jsonp|end|write)\s*\(",
+];
+
+/// Imports of a Python web framework, where a view's `return` is the response
+const PYTHON_WEB_IMPORT_PATTERN: &str = r"(?m)^\s*(from|import)\s+(flask|django|fastapi|starlette|werkzeug)\b";
+
+/// Variable names that hold a SQL statement
+const SQL_VARIABLE_PATTERN: &str = r"\w*sql\w*|query|stmt|statement|raw_query";
+
+/// Log calls at info level and above (`logger.info(`, `app.logger.warning(`, `log.error(`)
+const LOG_CALL_PATTERN: &str =
+    r"\b(logger|logging|log|audit_log|audit_logger)\.(info|warning|warn|error|critical)\s*\(";
+
+/// Identifiers that mark a log call as recording a user's action
+const USER_IDENTITY_PATTERN: &str =
+    r"\b(user_id|userId|user\.id|user\.email|username|userName|current_user|actor_id|actorId)\b|\b(req|request)\.user\b";
+
+/// Function name fragments (snake_case) that mark an authentication function
+const AUTH_FUNCTION_NAMES: &[&str] = &[
+    "login",
+    "logout",
+    "authenticate",
+    "sign_in",
+    "signin",
+    "verify_token",
+    "verify_password",
+    "validate_credentials",
+];
+
+/// Lines where a caught error is in scope, and the names bound to it
+struct ErrorScope {
+    start: usize,
+    end: usize,
+    names: Vec<String>,
+}
 
 /// CC7.2 Logging & Monitoring Rule Engine
 ///
@@ -46,6 +155,24 @@ impl CC72LoggingRule {
         // Pattern 4: Database modifications without transaction logging
         violations.extend(Self::detect_missing_transaction_logging(code, file_path, scan_id)?);
 
+        // Pattern 5: Admin operations writing to the database without logging
+        violations.extend(Self::detect_unlogged_admin_operations(code, file_path, scan_id)?);
+
+        // Pattern 6: Express admin/management routes without logging
+        violations.extend(Self::detect_unlogged_admin_routes(code, file_path, scan_id)?);
+
+        // Pattern 7: Go HTTP handlers logging request user data
+        violations.extend(Self::detect_go_handler_user_data_logging(code, file_path, scan_id)?);
+
+        // Pattern 8: Flask/Express/FastAPI handlers touching the database without logging
+        violations.extend(Self::detect_unlogged_api_handlers(code, file_path, scan_id)?);
+
+        // Pattern 9: Exception details, stack traces or SQL sent in HTTP responses
+        violations.extend(Self::detect_information_leakage_in_error_responses(code, file_path, scan_id)?);
+
+        // Pattern 10: User-action and auth log entries without an event/correlation ID
+        violations.extend(Self::detect_log_calls_without_event_id(code, file_path, scan_id)?);
+
         Ok(violations)
     }
 
@@ -71,7 +198,7 @@ impl CC72LoggingRule {
         for (idx, line) in lines.iter().enumerate() {
             if sensitive_ops.is_match(line) && !line.trim().starts_with("#") && !line.trim().starts_with("//") {
                 // Check if logging exists in current or next 3 lines
-                let check_start = if idx > 1 { idx - 1 } else { 0 };
+                let check_start = idx.saturating_sub(1);
                 let check_end = std::cmp::min(idx + 3, lines.len());
                 let context_lines = lines[check_start..check_end].join(" ");
 
@@ -105,20 +232,6 @@ impl CC72LoggingRule {
         let logging_func = Regex::new(r"(logger|print|console|log)\s*(\.\w+)?\s*\(")
             .context("Failed to compile logging function pattern")?;
 
-        let sensitive_patterns = [
-            ("password", "password"),
-            ("pwd", "password"),
-            ("secret", "secret"),
-            ("api_key", "API key"),
-            ("apikey", "API key"),
-            ("token", "token"),
-            ("ssn", "SSN"),
-            ("social", "social security"),
-            ("card_number", "credit card"),
-            ("card", "credit card"),
-            ("cvv", "CVV"),
-        ];
-
         for (idx, line) in code.lines().enumerate() {
             if line.trim().starts_with("#") || line.trim().starts_with("//") {
                 continue;
@@ -128,7 +241,7 @@ impl CC72LoggingRule {
             if logging_func.is_match(line) {
                 // Check for sensitive keywords
                 let line_lower = line.to_lowercase();
-                for (keyword, display_name) in sensitive_patterns.iter() {
+                for (keyword, display_name) in SENSITIVE_LOG_KEYWORDS.iter() {
                     if line_lower.contains(keyword) {
                         violations.push(Violation::new(
                             scan_id,
@@ -233,199 +346,1720 @@ impl CC72LoggingRule {
 
         Ok(violations)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Detects admin operations that write to the database without audit logging
+    ///
+    /// Function boundaries and names come from tree-sitter, so the whole
+    /// function body is checked rather than a few lines around the write.
+    /// Names are normalized to snake_case so `deleteUser`/`resetPassword` match.
+    /// Python functions with an audit/log decorator are treated as logged.
+    fn detect_unlogged_admin_operations(
+        code: &str,
+        file_path: &str,
+        scan_id: i64,
+    ) -> Result<Vec<Violation>> {
+        let mut violations = Vec::new();
 
-    #[test]
-    fn test_detect_missing_audit_log_on_save() {
-        let code = "user.save()";
-        let violations = CC72LoggingRule::analyze(code, "models.py", 1).unwrap();
-        assert!(
-            !violations.is_empty(),
-            "Should detect missing audit log on save()"
-        );
-        assert_eq!(violations[0].severity, "medium");
-        assert_eq!(violations[0].control_id, "CC7.2");
-    }
+        // Cheap pre-check so files without admin-looking names skip parsing
+        let lower = code.to_lowercase();
+        if !["admin", "delete", "role", "permission", "password", "impersonate"]
+            .iter()
+            .any(|keyword| lower.contains(keyword))
+        {
+            return Ok(violations);
+        }
 
-    #[test]
-    fn test_with_audit_log_on_save() {
-        let code = "logger.info('User saved')\nuser.save()";
-        let violations = CC72LoggingRule::analyze(code, "models.py", 1).unwrap();
-        assert!(violations.is_empty(), "Should allow save with logging");
-    }
+        let Some(parse_result) = Self::parse_functions(code, file_path) else {
+            return Ok(violations);
+        };
 
-    #[test]
-    fn test_detect_missing_audit_log_on_delete() {
-        let code = "user.delete()";
-        let violations = CC72LoggingRule::analyze(code, "models.py", 1).unwrap();
-        assert!(
-            !violations.is_empty(),
-            "Should detect missing audit log on delete()"
-        );
-    }
+        let db_write = Regex::new(ADMIN_DB_WRITE_PATTERN)
+            .context("Failed to compile admin database write pattern")?;
+        let logging = Regex::new(ADMIN_LOGGING_PATTERN)
+            .context("Failed to compile admin logging pattern")?;
+        let function_name = Regex::new(r"(?:def|function)\s*\*?\s*(\w+)")
+            .context("Failed to compile function name pattern")?;
 
-    #[test]
-    fn test_detect_missing_audit_log_on_update() {
-        let code = "user.update(name='John')";
-        let violations = CC72LoggingRule::analyze(code, "models.py", 1).unwrap();
-        assert!(
-            !violations.is_empty(),
-            "Should detect missing audit log on update()"
-        );
-    }
+        let lines: Vec<&str> = code.lines().collect();
 
-    #[test]
-    fn test_detect_logging_password_in_logger() {
-        let code = "logger.info(f'password: {password}')";
-        let violations = CC72LoggingRule::analyze(code, "auth.py", 1).unwrap();
-        assert!(!violations.is_empty(), "Should detect password in logging");
-        assert_eq!(violations[0].severity, "critical");
-    }
+        for func in &parse_result.functions {
+            let header = func.text.lines().next().unwrap_or_default();
+            let Some(name) = function_name.captures(header).map(|c| c[1].to_string()) else {
+                continue;
+            };
 
-    #[test]
-    fn test_detect_logging_token() {
-        let code = "print(f'auth token: {token}')";
-        let violations = CC72LoggingRule::analyze(code, "auth.py", 1).unwrap();
-        assert!(
-            !violations.is_empty(),
-            "Should detect token in logging statement"
-        );
-        assert_eq!(violations[0].severity, "critical");
-    }
+            let snake_name = to_snake_case(&name);
+            if !ADMIN_OPERATION_NAMES.iter().any(|pattern| snake_name.contains(pattern)) {
+                continue;
+            }
 
-    #[test]
-    fn test_detect_logging_api_key() {
-        let code = "console.log('API Key: ' + apikey)";
-        let violations = CC72LoggingRule::analyze(code, "service.js", 1).unwrap();
-        assert!(
-            !violations.is_empty(),
-            "Should detect API key in logging"
-        );
-        assert_eq!(violations[0].severity, "critical");
-    }
+            let body = strip_comment_lines(&func.text);
+            if !db_write.is_match(&body) || logging.is_match(&body) {
+                continue;
+            }
 
-    #[test]
-    fn test_detect_logging_ssn() {
-        let code = "logger.info(f'SSN: {ssn}')";
-        let violations = CC72LoggingRule::analyze(code, "user.py", 1).unwrap();
-        assert!(
-            !violations.is_empty(),
-            "Should detect SSN in logging statement"
-        );
-        assert_eq!(violations[0].severity, "critical");
-    }
+            if Self::has_logging_decorator(&lines, func.start_row) {
+                continue;
+            }
 
-    #[test]
-    fn test_detect_logging_credit_card() {
-        let code = "print(f'Card Number: {card_number}')";
-        let violations = CC72LoggingRule::analyze(code, "payment.py", 1).unwrap();
-        assert!(
-            !violations.is_empty(),
-            "Should detect credit card in logging"
-        );
-    }
+            let mut violation = Violation::new(
+                scan_id,
+                "CC7.2".to_string(),
+                Severity::High,
+                format!("Admin operation '{}' modifies data without audit logging", name),
+                file_path.to_string(),
+                (func.start_row + 1) as i64,
+                header.trim().to_string(),
+            );
+            violation.function_name = Some(name);
+            violations.push(violation);
+        }
 
-    #[test]
-    fn test_detect_missing_auth_logging() {
-        let code = "def authenticate(username, password):\n    return check_password(username, password)";
-        let violations = CC72LoggingRule::analyze(code, "auth.py", 1).unwrap();
-        assert!(
-            !violations.is_empty(),
-            "Should detect missing auth event logging"
-        );
-        assert_eq!(violations[0].severity, "high");
+        Ok(violations)
     }
 
-    #[test]
-    fn test_auth_with_logging() {
-        let code = "def authenticate(username, password):\n    logger.info('Auth attempt')\n    return check_password(username, password)";
-        let violations = CC72LoggingRule::analyze(code, "auth.py", 1).unwrap();
-        // The check_password call should not trigger a violation if logging is nearby
-        let auth_violations: Vec<_> = violations.iter().filter(|v| v.description.contains("Authentication event")).collect();
-        assert!(auth_violations.is_empty(), "Should allow auth with logging nearby");
-    }
+    /// Detects Express route handlers on /admin/ or /management/ paths without logging
+    ///
+    /// Only inline handlers (arrow functions or function expressions) are checked;
+    /// routes delegating to a named controller are left to Pattern 5.
+    fn detect_unlogged_admin_routes(
+        code: &str,
+        file_path: &str,
+        scan_id: i64,
+    ) -> Result<Vec<Violation>> {
+        let mut violations = Vec::new();
 
-    #[test]
-    fn test_detect_missing_transaction_logging() {
-        let code = "BEGIN\nUPDATE users SET active=1\nCOMMIT";
-        let violations = CC72LoggingRule::analyze(code, "schema.sql", 1).unwrap();
-        assert!(
-            !violations.is_empty(),
-            "Should detect transaction without logging"
-        );
-        assert_eq!(violations[0].severity, "medium");
-    }
+        if !is_javascript_file(file_path) {
+            return Ok(violations);
+        }
 
-    #[test]
-    fn test_transaction_with_logging() {
-        let code = "logger.info('Starting transaction')\nBEGIN\nUPDATE users SET active=1\nCOMMIT\nlogger.info('Transaction complete')";
-        let violations = CC72LoggingRule::analyze(code, "schema.sql", 1).unwrap();
-        assert!(violations.is_empty(), "Should allow transaction with logging");
-    }
+        let admin_route = Regex::new(ADMIN_ROUTE_PATTERN)
+            .context("Failed to compile admin route pattern")?;
+        let logging = Regex::new(ADMIN_LOGGING_PATTERN)
+            .context("Failed to compile admin logging pattern")?;
 
-    #[test]
-    fn test_ignore_commented_operations() {
-        let code = "# user.save()\nuser = None";
-        let violations = CC72LoggingRule::analyze(code, "models.py", 1).unwrap();
-        assert!(
-            violations.is_empty(),
-            "Should ignore commented sensitive operations"
-        );
-    }
+        let mut line_start = 0;
+        for (idx, line) in code.split('\n').enumerate() {
+            let offset = line_start;
+            line_start += line.len() + 1;
 
-    #[test]
-    fn test_multiple_violations() {
-        let code = "user.save()\nlogger.info(f'password: {pwd}')\nauthenticate()";
-        let violations = CC72LoggingRule::analyze(code, "app.py", 1).unwrap();
-        // Should detect at least the password in logging and the authenticate without logging
-        assert!(violations.len() >= 1, "Should detect violations");
-        let has_password = violations.iter().any(|v| v.description.contains("password") || v.description.contains("Sensitive data"));
-        assert!(has_password, "Should detect password logging");
-    }
+            if line.trim().starts_with("//") {
+                continue;
+            }
 
-    #[test]
-    fn test_insert_without_logging() {
-        let code = "INSERT INTO users (name, email) VALUES ('John', 'john@example.com')";
-        let violations = CC72LoggingRule::analyze(code, "schema.sql", 1).unwrap();
-        assert!(
-            !violations.is_empty(),
-            "Should detect INSERT without logging"
-        );
-    }
+            let Some(captures) = admin_route.captures(line) else {
+                continue;
+            };
 
-    #[test]
-    fn test_delete_from_without_logging() {
-        let code = "DELETE FROM users WHERE id = 42";
-        let violations = CC72LoggingRule::analyze(code, "schema.sql", 1).unwrap();
-        assert!(
-            !violations.is_empty(),
-            "Should detect DELETE FROM without logging"
-        );
-    }
+            // Byte offset of the route call's opening parenthesis
+            let route = captures.get(0).expect("match has group 0");
+            let call_start = offset + route.start() + route.as_str().find('(').unwrap_or(0);
+            let handler = strip_comment_lines(balanced_call(code, call_start));
+            if !handler.contains("=>") && !handler.contains("function") {
+                continue;
+            }
 
-    #[test]
-    fn test_secret_in_format_string() {
-        let code = "logger.info(f'Secret: {secret}')";
-        let violations = CC72LoggingRule::analyze(code, "config.py", 1).unwrap();
-        // This may or may not trigger depending on whether "secret" is in the password list
-        let secret_violations: Vec<_> = violations.iter().filter(|v| v.description.contains("Sensitive data")).collect();
-        assert!(
-            !secret_violations.is_empty(),
-            "Should detect secret in logging statement"
-        );
+            if !logging.is_match(&handler) {
+                violations.push(Violation::new(
+                    scan_id,
+                    "CC7.2".to_string(),
+                    Severity::High,
+                    format!("Admin route '{}' handler has no audit logging", &captures[2]),
+                    file_path.to_string(),
+                    (idx + 1) as i64,
+                    line.trim().to_string(),
+                ));
+            }
+        }
+
+        Ok(violations)
     }
 
-    #[test]
-    fn test_verify_token_without_logging() {
-        let code = "def verify_token(token):\n    return token_valid(token)";
-        let violations = CC72LoggingRule::analyze(code, "auth.py", 1).unwrap();
-        assert!(
-            !violations.is_empty(),
-            "Should detect verify_token without logging"
-        );
+    /// Detects Go HTTP handlers that write request user data to the standard logger
+    ///
+    /// Inside a `func(w http.ResponseWriter, r *http.Request)` or gin
+    /// `func(c *gin.Context)` handler, flags `log.Print*`/`log.Fatal*`/`log.Panic*`
+    /// calls whose arguments use the request or values taken from it
+    /// (`r.FormValue(...)`, `c.Query(...)`, a struct filled by
+    /// `json.NewDecoder(r.Body).Decode(&user)` or `c.ShouldBindJSON(&user)`).
+    /// Lines with sensitive keywords are left to Pattern 2, which reports them
+    /// as critical.
+    fn detect_go_handler_user_data_logging(
+        code: &str,
+        file_path: &str,
+        scan_id: i64,
+    ) -> Result<Vec<Violation>> {
+        let mut violations = Vec::new();
+
+        if !file_path.ends_with(".go") {
+            return Ok(violations);
+        }
+
+        let handler_signature = Regex::new(
+            r"\bfunc\s*(?:\([^)]*\)\s*)?\w*\s*\(\s*(?:\w+\s+http\.ResponseWriter\s*,\s*(\w+)\s+\*http\.Request|(\w+)\s+\*gin\.Context)\s*\)",
+        )
+        .context("Failed to compile Go handler signature pattern")?;
+        let std_log_call = Regex::new(r"\blog\.(Print|Printf|Println|Fatal|Fatalf|Fatalln|Panic|Panicf|Panicln)\s*\((.*)")
+            .context("Failed to compile Go log call pattern")?;
+        let decode_target = Regex::new(r"\.(?:Decode|Bind\w*|ShouldBind\w*)\(\s*&(\w+)\s*\)")
+            .context("Failed to compile Go decode pattern")?;
+        let assignment = Regex::new(r"^\s*([\w\s,]+?)\s*:?=\s*(.+)$")
+            .context("Failed to compile Go assignment pattern")?;
+        let string_literal = Regex::new(r#""(?:[^"\\]|\\.)*"|`[^`]*`"#)
+            .context("Failed to compile Go string literal pattern")?;
+
+        let lines: Vec<&str> = code.lines().collect();
+        let mut idx = 0;
+
+        while idx < lines.len() {
+            let Some(caps) = handler_signature.captures(lines[idx]) else {
+                idx += 1;
+                continue;
+            };
+
+            // Handler body, ending where its braces balance
+            let mut depth = 0;
+            let mut opened = false;
+            let mut body_end = idx;
+            for (i, body_line) in lines.iter().enumerate().skip(idx) {
+                depth += body_line.matches('{').count() as i32 - body_line.matches('}').count() as i32;
+                opened |= body_line.contains('{');
+                body_end = i;
+                if opened && depth <= 0 {
+                    break;
+                }
+            }
+
+            // The request and every variable derived from it in this handler
+            let request = caps.get(1).or_else(|| caps.get(2)).map_or("", |m| m.as_str());
+            let mut user_data = vec![request.to_string()];
+            let uses_user_data = |text: &str, names: &[String]| {
+                names.iter().any(|name| {
+                    Regex::new(&format!(r"\b{}\b", regex::escape(name)))
+                        .map(|re| re.is_match(text))
+                        .unwrap_or(false)
+                })
+            };
+
+            for (i, line) in lines.iter().enumerate().take(body_end + 1).skip(idx + 1) {
+                let trimmed = line.trim();
+                if trimmed.starts_with("//") {
+                    continue;
+                }
+
+                if let Some(log_caps) = std_log_call.captures(line) {
+                    let lower = line.to_lowercase();
+                    let has_sensitive_keyword =
+                        SENSITIVE_LOG_KEYWORDS.iter().any(|(keyword, _)| lower.contains(keyword));
+
+                    // Words inside the message text ("invalid order") aren't variables
+                    let args = string_literal.replace_all(&log_caps[2], "\"\"");
+                    if !has_sensitive_keyword && uses_user_data(&args, &user_data) {
+                        violations.push(Violation::new(
+                            scan_id,
+                            "CC7.2".to_string(),
+                            Severity::High,
+                            "User data from the request logged in Go HTTP handler".to_string(),
+                            file_path.to_string(),
+                            (i + 1) as i64,
+                            trimmed.to_string(),
+                        ));
+                    }
+                    continue;
+                }
+
+                if let Some(target) = decode_target.captures(line) {
+                    user_data.push(target[1].to_string());
+                } else if let Some(assign) = assignment.captures(line) {
+                    if uses_user_data(&assign[2], &user_data) {
+                        user_data.extend(
+                            assign[1]
+                                .split(',')
+                                .map(str::trim)
+                                .filter(|name| !name.is_empty() && *name != "_" && *name != "err")
+                                .map(str::to_string),
+                        );
+                    }
+                }
+            }
+
+            idx = body_end + 1;
+        }
+
+        Ok(violations)
+    }
+
+    /// Detects API handlers that touch the database without logging anything
+    ///
+    /// Handlers are functions (found by tree-sitter) named like a handler
+    /// (`create_user_view`, `updateOrderHandler`, `userController`) or, in
+    /// Python, carrying a Flask/FastAPI route decorator. A handler whose body
+    /// has no logging call is high severity when it writes to the database and
+    /// medium when it only reads. Only files that import a logging library are
+    /// checked (see [`Self::has_logging_import`]): there, an unlogged handler is
+    /// an omission rather than a project without logging. Unit test files and
+    /// admin operations (Pattern 5) are skipped.
+    fn detect_unlogged_api_handlers(
+        code: &str,
+        file_path: &str,
+        scan_id: i64,
+    ) -> Result<Vec<Violation>> {
+        let mut violations = Vec::new();
+
+        if is_unit_test_file(file_path) || !Self::has_logging_import(code) {
+            return Ok(violations);
+        }
+
+        let Some(parse_result) = Self::parse_functions(code, file_path) else {
+            return Ok(violations);
+        };
+
+        let route_decorator = Regex::new(ROUTE_DECORATOR_PATTERN)
+            .context("Failed to compile route decorator pattern")?;
+        let db_write = Regex::new(API_DB_WRITE_PATTERN)
+            .context("Failed to compile API database write pattern")?;
+        let db_read = Regex::new(API_DB_READ_PATTERN)
+            .context("Failed to compile API database read pattern")?;
+        let logging = Regex::new(ADMIN_LOGGING_PATTERN)
+            .context("Failed to compile logging pattern")?;
+        let function_name = Regex::new(r"\b(?:def|function)\b\s*\*?\s*(\w+)|^\s*(?:async\s+)?(\w+)\s*\(")
+            .context("Failed to compile function name pattern")?;
+
+        let lines: Vec<&str> = code.lines().collect();
+
+        for func in &parse_result.functions {
+            let header = func.text.lines().next().unwrap_or_default();
+            let Some(name) = function_name
+                .captures(header)
+                .and_then(|c| c.get(1).or_else(|| c.get(2)))
+                .map(|m| m.as_str().to_string())
+            else {
+                continue;
+            };
+
+            let snake_name = to_snake_case(&name);
+            if ADMIN_OPERATION_NAMES.iter().any(|pattern| snake_name.contains(pattern)) {
+                continue;
+            }
+
+            let is_handler = API_HANDLER_NAMES.iter().any(|fragment| snake_name.contains(fragment))
+                || Self::has_route_decorator(&lines, func.start_row, &route_decorator);
+            if !is_handler {
+                continue;
+            }
+
+            let body = strip_comment_lines(&func.text);
+            if logging.is_match(&body) || Self::has_logging_decorator(&lines, func.start_row) {
+                continue;
+            }
+
+            let (severity, operation) = if db_write.is_match(&body) {
+                (Severity::High, "writes")
+            } else if db_read.is_match(&body) {
+                (Severity::Medium, "reads")
+            } else {
+                continue;
+            };
+
+            let mut violation = Violation::new(
+                scan_id,
+                "CC7.2".to_string(),
+                severity,
+                format!("API handler '{}' {} data without logging the request", name, operation),
+                file_path.to_string(),
+                (func.start_row + 1) as i64,
+                header.trim().to_string(),
+            );
+            violation.function_name = Some(name);
+            violations.push(violation);
+        }
+
+        Ok(violations)
+    }
+
+    /// Detects error handling that sends internal details to the HTTP client
+    ///
+    /// A response is any call matching [`RESPONSE_PATTERNS`] and, in Python
+    /// modules importing a web framework, a `return` inside an `except` block
+    /// or a Flask/FastAPI error handler. The response is flagged when it carries
+    /// `traceback.format_exc()`, `err.stack`, a SQL variable interpolated into an
+    /// f-string (`f"Query failed: {sql}"`), or the caught exception itself
+    /// (`str(e)`, `f"Error: {e}"`, `err.message`, `res.send(err)`). Logging the
+    /// error and returning a generic message is the expected pattern and is not
+    /// flagged. In Django modules that also set `DEBUG = True`, the description
+    /// notes that debug pages expose the same details.
+    fn detect_information_leakage_in_error_responses(
+        code: &str,
+        file_path: &str,
+        scan_id: i64,
+    ) -> Result<Vec<Violation>> {
+        let mut violations = Vec::new();
+
+        let is_python = file_path.ends_with(".py");
+        let is_javascript = is_javascript_file(file_path);
+        if (!is_python && !is_javascript) || is_unit_test_file(file_path) {
+            return Ok(violations);
+        }
+
+        let response = RegexSet::new(RESPONSE_PATTERNS).context("Failed to compile response patterns")?;
+        let view_return = Regex::new(r"^\s*return\b").context("Failed to compile return pattern")?;
+        let is_python_web_module = is_python
+            && Regex::new(PYTHON_WEB_IMPORT_PATTERN)
+                .context("Failed to compile web framework import pattern")?
+                .is_match(code);
+        let debug_enabled = is_python
+            && Regex::new(r"(?m)^\s*DEBUG\s*=\s*True\b")
+                .context("Failed to compile DEBUG pattern")?
+                .is_match(code);
+
+        let lines: Vec<&str> = code.lines().collect();
+        let scopes = if is_python {
+            Self::python_error_scopes(&lines)?
+        } else {
+            Self::javascript_error_scopes(&lines)?
+        };
+
+        for (idx, line) in lines.iter().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.starts_with('#') || trimmed.starts_with("//") {
+                continue;
+            }
+
+            let error_names: Vec<&str> = scopes
+                .iter()
+                .filter(|scope| scope.start <= idx && idx <= scope.end)
+                .flat_map(|scope| scope.names.iter().map(String::as_str))
+                .collect();
+
+            let is_response = response.is_match(line)
+                || (is_python_web_module && !error_names.is_empty() && view_return.is_match(line));
+            if !is_response {
+                continue;
+            }
+
+            // The whole statement, for responses built over several lines
+            let mut statement = String::new();
+            let mut depth = 0i32;
+            for statement_line in lines.iter().skip(idx).take(10) {
+                statement.push_str(statement_line);
+                statement.push('\n');
+                depth += statement_line.matches(['(', '[', '{']).count() as i32
+                    - statement_line.matches([')', ']', '}']).count() as i32;
+                if depth <= 0 {
+                    break;
+                }
+            }
+
+            let Some(leaked) = Self::leaked_error_detail(&statement, &error_names, is_python)? else {
+                continue;
+            };
+
+            let mut description = format!("{} returned to the HTTP client in error response", leaked);
+            if debug_enabled && statement.contains("HttpResponse") {
+                description.push_str(" (DEBUG = True also exposes tracebacks on error pages)");
+            }
+
+            violations.push(Violation::new(
+                scan_id,
+                "CC7.2".to_string(),
+                Severity::Medium,
+                description,
+                file_path.to_string(),
+                (idx + 1) as i64,
+                trimmed.to_string(),
+            ));
+        }
+
+        Ok(violations)
+    }
+
+    /// Detects user-action and authentication log entries without an event ID
+    ///
+    /// A log call at info level or above that references the acting user
+    /// (`user_id`, `userId`, `current_user`, `req.user`, ...) must carry an
+    /// `event_id` or `correlation_id` (see [`log_has_event_id`]), e.g. through
+    /// `extra={'event_id': ...}` or an `eventId` field, so each audit entry can
+    /// be traced. Warnings inside authentication functions (`login`,
+    /// `authenticate`, `verifyToken`, ...) must carry a `correlation_id`
+    /// whether or not they name the user. Each description ends with a hint on
+    /// how to add the ID. Unit test files are skipped.
+    fn detect_log_calls_without_event_id(
+        code: &str,
+        file_path: &str,
+        scan_id: i64,
+    ) -> Result<Vec<Violation>> {
+        let mut violations = Vec::new();
+
+        let is_python = file_path.ends_with(".py");
+        if (!is_python && !is_javascript_file(file_path)) || is_unit_test_file(file_path) {
+            return Ok(violations);
+        }
+
+        let log_call = Regex::new(LOG_CALL_PATTERN).context("Failed to compile log call pattern")?;
+        if !log_call.is_match(code) {
+            return Ok(violations);
+        }
+        let user_identity = Regex::new(USER_IDENTITY_PATTERN)
+            .context("Failed to compile user identity pattern")?;
+        let correlation_id = Regex::new(r"(?i)\bcorrelation[_-]?id\b")
+            .context("Failed to compile correlation ID pattern")?;
+
+        // Authentication functions, as (name, first row, last row)
+        let auth_functions: Vec<(String, usize, usize)> = Self::parse_functions(code, file_path)
+            .map(|parse_result| {
+                parse_result
+                    .functions
+                    .into_iter()
+                    .filter_map(|func| {
+                        let name = func.name?;
+                        let snake_name = to_snake_case(&name);
+                        AUTH_FUNCTION_NAMES
+                            .iter()
+                            .any(|fragment| snake_name.contains(fragment))
+                            .then_some((name, func.start_row, func.end_row))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let lines: Vec<&str> = code.lines().collect();
+
+        for call_match in log_call.find_iter(code) {
+            let row = code[..call_match.start()].matches('\n').count();
+            let line = lines.get(row).copied().unwrap_or_default();
+            let trimmed = line.trim();
+            if trimmed.starts_with('#') || trimmed.starts_with("//") {
+                continue;
+            }
+
+            let call = balanced_call(code, call_match.end() - 1);
+            let is_warning = call_match.as_str().contains(".warn");
+            let auth_function = auth_functions
+                .iter()
+                .filter(|(_, start, end)| *start <= row && row <= *end)
+                .map(|(name, _, _)| name.as_str())
+                .next_back();
+
+            let (description, function_name) = match auth_function {
+                Some(name) if is_warning => {
+                    if correlation_id.is_match(call) {
+                        continue;
+                    }
+                    (
+                        format!(
+                            "Authentication warning in '{}' logged without a correlation_id. {}",
+                            name,
+                            recommended_fix_hint(is_python, true)
+                        ),
+                        Some(name.to_string()),
+                    )
+                }
+                _ => {
+                    if !user_identity.is_match(call) || log_has_event_id(call) {
+                        continue;
+                    }
+                    (
+                        format!(
+                            "User action logged without an event_id or correlation_id. {}",
+                            recommended_fix_hint(is_python, false)
+                        ),
+                        None,
+                    )
+                }
+            };
+
+            let mut violation = Violation::new(
+                scan_id,
+                "CC7.2".to_string(),
+                Severity::Low,
+                description,
+                file_path.to_string(),
+                (row + 1) as i64,
+                trimmed.to_string(),
+            );
+            violation.function_name = function_name;
+            violations.push(violation);
+        }
+
+        Ok(violations)
+    }
+
+    /// What internal detail, if any, a response statement sends to the client
+    fn leaked_error_detail(statement: &str, error_names: &[&str], is_python: bool) -> Result<Option<&'static str>> {
+        let stack_trace = if is_python {
+            r"\btraceback\.format_exc(eption)?\s*\(".to_string()
+        } else {
+            r"\b\w+\.stack\b".to_string()
+        };
+        if Regex::new(&stack_trace)
+            .context("Failed to compile stack trace pattern")?
+            .is_match(statement)
+        {
+            return Ok(Some("Stack trace"));
+        }
+
+        let sql = if is_python {
+            format!(r#"\b[rR]?[fF][rR]?["'][^"'\n]*\{{\s*({})\b"#, SQL_VARIABLE_PATTERN)
+        } else {
+            format!(r"\$\{{\s*({})\b", SQL_VARIABLE_PATTERN)
+        };
+        if Regex::new(&format!("(?i){}", sql))
+            .context("Failed to compile SQL interpolation pattern")?
+            .is_match(statement)
+        {
+            return Ok(Some("SQL query"));
+        }
+
+        for name in error_names {
+            let name = regex::escape(name);
+            let exception = if is_python {
+                format!(
+                    r#"\b(str|repr)\(\s*{0}\s*\)|\b[rR]?[fF][rR]?["'][^"'\n]*\{{\s*(str\(\s*)?{0}\b|\.format\([^)]*\b{0}\b|\b{0}\.(args|message)\b"#,
+                    name
+                )
+            } else {
+                format!(
+                    r"\b{0}\.(message|toString\(\))|\bString\(\s*{0}\s*\)|\$\{{\s*{0}\s*\}}|[(\{{,:]\s*{0}\s*[,)\}}]",
+                    name
+                )
+            };
+            if Regex::new(&exception)
+                .context("Failed to compile exception pattern")?
+                .is_match(statement)
+            {
+                return Ok(Some("Exception message"));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Python `except ... as e:` blocks and `@app.errorhandler`/`exception_handler`
+    /// functions, with the names bound to the caught exception
+    fn python_error_scopes(lines: &[&str]) -> Result<Vec<ErrorScope>> {
+        let except_clause = Regex::new(r"^\s*except\b.*?\bas\s+(\w+)\s*:")
+            .context("Failed to compile except clause pattern")?;
+        let error_handler = Regex::new(r"^\s*@\w+\.(errorhandler|exception_handler)\s*\(")
+            .context("Failed to compile error handler pattern")?;
+        let def_params = Regex::new(r"^\s*(async\s+)?def\s+\w+\s*\(([^)]*)\)")
+            .context("Failed to compile function definition pattern")?;
+
+        let mut scopes = Vec::new();
+        for (idx, line) in lines.iter().enumerate() {
+            if let Some(caps) = except_clause.captures(line) {
+                scopes.push(ErrorScope {
+                    start: idx + 1,
+                    end: indented_block_end(lines, idx),
+                    names: vec![caps[1].to_string()],
+                });
+            } else if error_handler.is_match(line) {
+                let Some((def_idx, caps)) = lines
+                    .iter()
+                    .enumerate()
+                    .skip(idx + 1)
+                    .find(|(_, l)| !l.trim_start().starts_with('@'))
+                    .and_then(|(i, l)| def_params.captures(l).map(|c| (i, c)))
+                else {
+                    continue;
+                };
+                // `handle_error(e)` in Flask, `handler(request, exc)` in FastAPI
+                let names = caps[2]
+                    .split(',')
+                    .map(|param| param.split([':', '=']).next().unwrap_or_default().trim())
+                    .filter(|param| !param.is_empty() && !matches!(*param, "self" | "request" | "req"))
+                    .map(str::to_string)
+                    .collect();
+                scopes.push(ErrorScope {
+                    start: def_idx + 1,
+                    end: indented_block_end(lines, def_idx),
+                    names,
+                });
+            }
+        }
+
+        Ok(scopes)
+    }
+
+    /// JavaScript `catch (err)` blocks, `.catch(err => ...)` callbacks and Express
+    /// error middleware `(err, req, res, next)`, with the name of the error
+    fn javascript_error_scopes(lines: &[&str]) -> Result<Vec<ErrorScope>> {
+        let catch_clause = Regex::new(r"\bcatch\s*\(\s*\(?\s*(\w+)")
+            .context("Failed to compile catch clause pattern")?;
+        let error_middleware = Regex::new(r"\(\s*(\w+)\s*,\s*req\w*\s*,\s*res\w*\s*,\s*next\s*\)")
+            .context("Failed to compile error middleware pattern")?;
+
+        let mut scopes = Vec::new();
+        for (idx, line) in lines.iter().enumerate() {
+            let Some(caps) = error_middleware
+                .captures(line)
+                .or_else(|| catch_clause.captures(line))
+            else {
+                continue;
+            };
+            // `} catch (err) {` closes the try block before opening its own
+            let opening = &line[caps.get(0).map_or(0, |m| m.start())..];
+            scopes.push(ErrorScope {
+                start: idx,
+                end: brace_block_end(lines, idx, opening),
+                names: vec![caps[1].to_string()],
+            });
+        }
+
+        Ok(scopes)
+    }
+
+    /// Whether the file imports a logging library, i.e. logging is in use
+    fn has_logging_import(code: &str) -> bool {
+        Regex::new(LOGGING_IMPORT_PATTERN)
+            .map(|re| re.is_match(code))
+            .unwrap_or(false)
+    }
+
+    /// Whether the decorators directly above `def_row` include a route decorator
+    fn has_route_decorator(lines: &[&str], def_row: usize, route_decorator: &Regex) -> bool {
+        lines[..def_row.min(lines.len())]
+            .iter()
+            .rev()
+            .map(|line| line.trim())
+            .take_while(|line| line.starts_with('@'))
+            .any(|decorator| route_decorator.is_match(decorator))
+    }
+
+    /// Parse a Python/JavaScript/TypeScript file for its function definitions
+    fn parse_functions(code: &str, file_path: &str) -> Option<ParseResult> {
+        let parser = CodeParser::new().ok()?;
+        let extension = std::path::Path::new(file_path)
+            .extension()
+            .and_then(|e| e.to_str())?;
+
+        match extension {
+            "py" => parser.parse_python(code).ok(),
+            "js" | "jsx" | "mjs" | "cjs" => parser.parse_javascript(code).ok(),
+            "ts" | "tsx" => parser.parse_typescript(code).ok(),
+            _ => None,
+        }
+    }
+
+    /// Whether the decorators directly above `def_row` include an audit/log decorator
+    fn has_logging_decorator(lines: &[&str], def_row: usize) -> bool {
+        lines[..def_row.min(lines.len())]
+            .iter()
+            .rev()
+            .map(|line| line.trim())
+            .take_while(|line| line.starts_with('@'))
+            .any(|decorator| {
+                let decorator = decorator.to_lowercase();
+                decorator.contains("audit") || decorator.contains("log")
+            })
+    }
+}
+
+
+/// Convert camelCase/PascalCase names to snake_case (`resetPassword` -> `reset_password`)
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+    let mut previous_lower = false;
+    for c in name.chars() {
+        if c.is_uppercase() && previous_lower {
+            snake.push('_');
+        }
+        previous_lower = c.is_lowercase() || c.is_ascii_digit();
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
+
+/// Drop full-line `#` and `//` comments so commented-out calls don't count
+fn strip_comment_lines(text: &str) -> String {
+    text.lines()
+        .filter(|line| {
+            let trimmed = line.trim_start();
+            !trimmed.starts_with('#') && !trimmed.starts_with("//")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Whether a file holds unit tests (`test_*.py`, `*_test.py`, `*.test.ts`,
+/// `*.spec.js`, or anything under `tests/`/`__tests__/`)
+fn is_unit_test_file(file_path: &str) -> bool {
+    let path = file_path.replace('\\', "/").to_lowercase();
+    let file_name = path.rsplit('/').next().unwrap_or(&path);
+    file_name.starts_with("test_")
+        || file_name.starts_with("conftest")
+        || file_name.contains("_test.")
+        || file_name.contains(".test.")
+        || file_name.contains(".spec.")
+        || path.starts_with("tests/")
+        || path.contains("/tests/")
+        || path.contains("__tests__/")
+}
+
+fn is_javascript_file(file_path: &str) -> bool {
+    matches!(
+        std::path::Path::new(file_path).extension().and_then(|e| e.to_str()),
+        Some("js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx")
+    )
+}
+
+/// Whether a log call carries a unique `event_id` or `correlation_id` field,
+/// in any case and as snake_case, kebab-case or camelCase (`eventId`)
+fn log_has_event_id(log_call: &str) -> bool {
+    Regex::new(r"(?i)\b(event|correlation)[_-]?id\b")
+        .map(|re| re.is_match(log_call))
+        .unwrap_or(false)
+}
+
+/// How to add the missing ID to a log call, for violation descriptions
+fn recommended_fix_hint(is_python: bool, auth_warning: bool) -> &'static str {
+    match (is_python, auth_warning) {
+        (true, false) => "Add a unique ID to the entry, e.g. extra={'event_id': str(uuid.uuid4())}",
+        (false, false) => "Add a unique ID field to the entry, e.g. { eventId: crypto.randomUUID(), ... }",
+        (true, true) => "Pass the request's ID, e.g. extra={'correlation_id': request_id}",
+        (false, true) => "Pass the request's ID, e.g. { correlationId: req.id, ... }",
+    }
+}
+
+/// Last line of the Python block opened by the statement at `header`: the
+/// following lines indented deeper than it
+fn indented_block_end(lines: &[&str], header: usize) -> usize {
+    let indent = |line: &str| line.len() - line.trim_start().len();
+    let header_indent = indent(lines[header]);
+    let mut end = header;
+    for (i, line) in lines.iter().enumerate().skip(header + 1) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if indent(line) <= header_indent {
+            break;
+        }
+        end = i;
+    }
+    end
+}
+
+/// Last line of the brace block opened by `opening`, the tail of line `start`
+/// (or the last line, if unbalanced)
+fn brace_block_end(lines: &[&str], start: usize, opening: &str) -> usize {
+    let mut depth = 0i32;
+    let mut opened = false;
+    let rest = lines.iter().skip(start + 1).copied();
+    for (i, line) in std::iter::once(opening).chain(rest).enumerate() {
+        let i = start + i;
+        depth += line.matches('{').count() as i32 - line.matches('}').count() as i32;
+        opened |= line.contains('{');
+        if opened && depth <= 0 {
+            return i;
+        }
+    }
+    lines.len().saturating_sub(1)
+}
+
+/// Text of the call whose opening parenthesis is at `open_paren`, up to the
+/// matching close (or end of input). Parentheses inside string literals are ignored.
+fn balanced_call(code: &str, open_paren: usize) -> &str {
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+
+    for (offset, c) in code[open_paren..].char_indices() {
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+
+        match c {
+            '\'' | '"' | '`' => quote = Some(c),
+            '(' => depth += 1,
+            ')' => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    return &code[open_paren..open_paren + offset + 1];
+                }
+            }
+            _ => {}
+        }
+    }
+
+    &code[open_paren..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_missing_audit_log_on_save() {
+        let code = "user.save()";
+        let violations = CC72LoggingRule::analyze(code, "models.py", 1).unwrap();
+        assert!(
+            !violations.is_empty(),
+            "Should detect missing audit log on save()"
+        );
+        assert_eq!(violations[0].severity, "medium");
+        assert_eq!(violations[0].control_id, "CC7.2");
+    }
+
+    #[test]
+    fn test_with_audit_log_on_save() {
+        let code = "logger.info('User saved')\nuser.save()";
+        let violations = CC72LoggingRule::analyze(code, "models.py", 1).unwrap();
+        assert!(violations.is_empty(), "Should allow save with logging");
+    }
+
+    #[test]
+    fn test_detect_missing_audit_log_on_delete() {
+        let code = "user.delete()";
+        let violations = CC72LoggingRule::analyze(code, "models.py", 1).unwrap();
+        assert!(
+            !violations.is_empty(),
+            "Should detect missing audit log on delete()"
+        );
+    }
+
+    #[test]
+    fn test_detect_missing_audit_log_on_update() {
+        let code = "user.update(name='John')";
+        let violations = CC72LoggingRule::analyze(code, "models.py", 1).unwrap();
+        assert!(
+            !violations.is_empty(),
+            "Should detect missing audit log on update()"
+        );
+    }
+
+    #[test]
+    fn test_detect_logging_password_in_logger() {
+        let code = "logger.info(f'password: {password}')";
+        let violations = CC72LoggingRule::analyze(code, "auth.py", 1).unwrap();
+        assert!(!violations.is_empty(), "Should detect password in logging");
+        assert_eq!(violations[0].severity, "critical");
+    }
+
+    #[test]
+    fn test_detect_logging_token() {
+        let code = "print(f'auth token: {token}')";
+        let violations = CC72LoggingRule::analyze(code, "auth.py", 1).unwrap();
+        assert!(
+            !violations.is_empty(),
+            "Should detect token in logging statement"
+        );
+        assert_eq!(violations[0].severity, "critical");
+    }
+
+    #[test]
+    fn test_detect_logging_api_key() {
+        let code = "console.log('API Key: ' + apikey)";
+        let violations = CC72LoggingRule::analyze(code, "service.js", 1).unwrap();
+        assert!(
+            !violations.is_empty(),
+            "Should detect API key in logging"
+        );
+        assert_eq!(violations[0].severity, "critical");
+    }
+
+    #[test]
+    fn test_detect_logging_ssn() {
+        let code = "logger.info(f'SSN: {ssn}')";
+        let violations = CC72LoggingRule::analyze(code, "user.py", 1).unwrap();
+        assert!(
+            !violations.is_empty(),
+            "Should detect SSN in logging statement"
+        );
+        assert_eq!(violations[0].severity, "critical");
+    }
+
+    #[test]
+    fn test_detect_logging_credit_card() {
+        let code = "print(f'Card Number: {card_number}')";
+        let violations = CC72LoggingRule::analyze(code, "payment.py", 1).unwrap();
+        assert!(
+            !violations.is_empty(),
+            "Should detect credit card in logging"
+        );
+    }
+
+    #[test]
+    fn test_detect_missing_auth_logging() {
+        let code = "def authenticate(username, password):\n    return check_password(username, password)";
+        let violations = CC72LoggingRule::analyze(code, "auth.py", 1).unwrap();
+        assert!(
+            !violations.is_empty(),
+            "Should detect missing auth event logging"
+        );
+        assert_eq!(violations[0].severity, "high");
+    }
+
+    #[test]
+    fn test_auth_with_logging() {
+        let code = "def authenticate(username, password):\n    logger.info('Auth attempt')\n    return check_password(username, password)";
+        let violations = CC72LoggingRule::analyze(code, "auth.py", 1).unwrap();
+        // The check_password call should not trigger a violation if logging is nearby
+        let auth_violations: Vec<_> = violations.iter().filter(|v| v.description.contains("Authentication event")).collect();
+        assert!(auth_violations.is_empty(), "Should allow auth with logging nearby");
+    }
+
+    #[test]
+    fn test_detect_missing_transaction_logging() {
+        let code = "BEGIN\nUPDATE users SET active=1\nCOMMIT";
+        let violations = CC72LoggingRule::analyze(code, "schema.sql", 1).unwrap();
+        assert!(
+            !violations.is_empty(),
+            "Should detect transaction without logging"
+        );
+        assert_eq!(violations[0].severity, "medium");
+    }
+
+    #[test]
+    fn test_transaction_with_logging() {
+        let code = "logger.info('Starting transaction')\nBEGIN\nUPDATE users SET active=1\nCOMMIT\nlogger.info('Transaction complete')";
+        let violations = CC72LoggingRule::analyze(code, "schema.sql", 1).unwrap();
+        assert!(violations.is_empty(), "Should allow transaction with logging");
+    }
+
+    #[test]
+    fn test_ignore_commented_operations() {
+        let code = "# user.save()\nuser = None";
+        let violations = CC72LoggingRule::analyze(code, "models.py", 1).unwrap();
+        assert!(
+            violations.is_empty(),
+            "Should ignore commented sensitive operations"
+        );
+    }
+
+    #[test]
+    fn test_multiple_violations() {
+        let code = "user.save()\nlogger.info(f'password: {pwd}')\nauthenticate()";
+        let violations = CC72LoggingRule::analyze(code, "app.py", 1).unwrap();
+        // Should detect at least the password in logging and the authenticate without logging
+        assert!(!violations.is_empty(), "Should detect violations");
+        let has_password = violations.iter().any(|v| v.description.contains("password") || v.description.contains("Sensitive data"));
+        assert!(has_password, "Should detect password logging");
+    }
+
+    #[test]
+    fn test_insert_without_logging() {
+        let code = "INSERT INTO users (name, email) VALUES ('John', 'john@example.com')";
+        let violations = CC72LoggingRule::analyze(code, "schema.sql", 1).unwrap();
+        assert!(
+            !violations.is_empty(),
+            "Should detect INSERT without logging"
+        );
+    }
+
+    #[test]
+    fn test_delete_from_without_logging() {
+        let code = "DELETE FROM users WHERE id = 42";
+        let violations = CC72LoggingRule::analyze(code, "schema.sql", 1).unwrap();
+        assert!(
+            !violations.is_empty(),
+            "Should detect DELETE FROM without logging"
+        );
+    }
+
+    #[test]
+    fn test_secret_in_format_string() {
+        let code = "logger.info(f'Secret: {secret}')";
+        let violations = CC72LoggingRule::analyze(code, "config.py", 1).unwrap();
+        // This may or may not trigger depending on whether "secret" is in the password list
+        let secret_violations: Vec<_> = violations.iter().filter(|v| v.description.contains("Sensitive data")).collect();
+        assert!(
+            !secret_violations.is_empty(),
+            "Should detect secret in logging statement"
+        );
+    }
+
+    #[test]
+    fn test_verify_token_without_logging() {
+        let code = "def verify_token(token):\n    return token_valid(token)";
+        let violations = CC72LoggingRule::analyze(code, "auth.py", 1).unwrap();
+        assert!(
+            !violations.is_empty(),
+            "Should detect verify_token without logging"
+        );
+    }
+
+    // ===== Admin operations (Pattern 5) and admin routes (Pattern 6) =====
+
+    fn admin_violations(code: &str, file_path: &str) -> Vec<Violation> {
+        CC72LoggingRule::analyze(code, file_path, 1)
+            .unwrap()
+            .into_iter()
+            .filter(|v| v.description.starts_with("Admin "))
+            .collect()
+    }
+
+    #[test]
+    fn test_admin_delete_without_logging() {
+        let code = "def delete_user(user_id):\n    user = User.query.get(user_id)\n    db.session.delete(user)\n    db.session.commit()\n";
+        let violations = admin_violations(code, "admin.py");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].severity, "high");
+        assert_eq!(violations[0].control_id, "CC7.2");
+        assert_eq!(violations[0].line_number, 1);
+        assert_eq!(violations[0].function_name.as_deref(), Some("delete_user"));
+        assert!(violations[0].description.contains("'delete_user'"));
+    }
+
+    #[test]
+    fn test_admin_delete_with_logger_info() {
+        let code = "def delete_user(user_id):\n    user = User.query.get(user_id)\n    db.session.delete(user)\n    db.session.commit()\n    logger.info('Deleted user %s', user_id)\n";
+        assert!(admin_violations(code, "admin.py").is_empty());
+    }
+
+    #[test]
+    fn test_admin_prefixed_function_with_save() {
+        let code = "def admin_ban_user(user):\n    user.banned = True\n    user.save()\n";
+        let violations = admin_violations(code, "admin.py");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].code_snippet, "def admin_ban_user(user):");
+    }
+
+    #[test]
+    fn test_admin_update_role_with_save() {
+        let code = "def update_role(user, role):\n    user.role = role\n    user.save(update_fields=['role'])\n";
+        assert_eq!(admin_violations(code, "roles.py").len(), 1);
+    }
+
+    #[test]
+    fn test_admin_change_permission_with_queryset_update() {
+        let code = "def change_permission(user_id, level):\n    Permission.objects.filter(user_id=user_id).update(level=level)\n";
+        assert_eq!(admin_violations(code, "permissions.py").len(), 1);
+    }
+
+    #[test]
+    fn test_admin_reset_password_with_commit() {
+        let code = "def reset_password(user, new_password):\n    user.password_hash = hash_password(new_password)\n    db.session.commit()\n";
+        let violations = admin_violations(code, "auth.py");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].function_name.as_deref(), Some("reset_password"));
+    }
+
+    #[test]
+    fn test_admin_impersonate_with_save() {
+        let code = "def impersonate_user(admin, target):\n    session = Session(user=target, actor=admin)\n    session.save()\n    return session\n";
+        assert_eq!(admin_violations(code, "support.py").len(), 1);
+    }
+
+    #[test]
+    fn test_admin_function_without_db_write_not_flagged() {
+        let code = "def admin_dashboard(request):\n    stats = get_stats()\n    return render(request, 'admin/dashboard.html', stats)\n";
+        assert!(admin_violations(code, "views.py").is_empty());
+    }
+
+    #[test]
+    fn test_non_admin_function_with_db_write_not_flagged() {
+        let code = "def update_profile(user, bio):\n    user.bio = bio\n    user.save()\n";
+        assert!(admin_violations(code, "profile.py").is_empty());
+    }
+
+    #[test]
+    fn test_admin_with_logger_audit() {
+        let code = "def delete_account(account):\n    account.delete()\n    logger.audit('account_deleted', account_id=account.id)\n";
+        assert!(admin_violations(code, "accounts.py").is_empty());
+    }
+
+    #[test]
+    fn test_admin_commented_logging_does_not_count() {
+        let code = "def delete_account(account):\n    account.delete()\n    # logger.info('account deleted')\n";
+        assert_eq!(admin_violations(code, "accounts.py").len(), 1);
+    }
+
+    #[test]
+    fn test_admin_with_audit_decorator_not_flagged() {
+        let code = "@audit_log('user_deleted')\n@login_required\ndef delete_user(user_id):\n    User.objects.get(id=user_id).delete()\n";
+        assert!(admin_violations(code, "admin.py").is_empty());
+    }
+
+    #[test]
+    fn test_admin_class_method_reports_def_line() {
+        let code = "class AdminService:\n    def delete_account(self, account_id):\n        Account.objects.get(id=account_id).delete()\n";
+        let violations = admin_violations(code, "services.py");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line_number, 2);
+        assert_eq!(violations[0].function_name.as_deref(), Some("delete_account"));
+    }
+
+    #[test]
+    fn test_admin_javascript_camel_case_function() {
+        let code = "async function deleteUser(req, res) {\n  const user = await User.findById(req.params.id);\n  await user.delete();\n  res.sendStatus(204);\n}\n";
+        let violations = admin_violations(code, "admin.js");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].function_name.as_deref(), Some("deleteUser"));
+    }
+
+    #[test]
+    fn test_admin_javascript_with_console_log() {
+        let code = "async function deleteUser(req, res) {\n  const user = await User.findById(req.params.id);\n  await user.delete();\n  console.log(`user ${user.id} deleted by ${req.user.id}`);\n}\n";
+        assert!(admin_violations(code, "admin.js").is_empty());
+    }
+
+    #[test]
+    fn test_admin_typescript_reset_password() {
+        let code = "export async function resetPassword(userId: string, hash: string): Promise<void> {\n  await db.users.update({ where: { id: userId }, data: { hash } });\n}\n";
+        let violations = admin_violations(code, "users.ts");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].function_name.as_deref(), Some("resetPassword"));
+    }
+
+    #[test]
+    fn test_admin_express_route_without_logging() {
+        let code = "router.post('/admin/users/:id/ban', async (req, res) => {\n  await User.update({ banned: true }, { where: { id: req.params.id } });\n  res.sendStatus(204);\n});\n";
+        let violations = admin_violations(code, "routes/admin.js");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].severity, "high");
+        assert_eq!(violations[0].line_number, 1);
+        assert!(violations[0].description.contains("'/admin/users/:id/ban'"));
+    }
+
+    #[test]
+    fn test_admin_express_management_route_with_logging() {
+        let code = "app.delete('/api/management/tenants/:id', function (req, res) {\n  Tenant.destroy({ where: { id: req.params.id } });\n  logger.info('tenant removed', { id: req.params.id });\n  res.end();\n});\n";
+        assert!(admin_violations(code, "server.js").is_empty());
+    }
+
+    #[test]
+    fn test_admin_express_non_admin_route_not_flagged() {
+        let code = "router.post('/api/users/:id', async (req, res) => {\n  await User.update(req.body, { where: { id: req.params.id } });\n  res.sendStatus(204);\n});\n";
+        assert!(admin_violations(code, "routes/users.ts").is_empty());
+    }
+
+    #[test]
+    fn test_admin_express_route_delegating_to_controller_not_flagged() {
+        let code = "router.delete('/admin/users/:id', requireAdmin, adminController.removeUser);\n";
+        assert!(admin_violations(code, "routes/admin.js").is_empty());
+    }
+
+    fn go_logging_violations(code: &str) -> Vec<Violation> {
+        CC72LoggingRule::analyze(code, "handlers.go", 1)
+            .unwrap()
+            .into_iter()
+            .filter(|v| v.description.contains("Go HTTP handler"))
+            .collect()
+    }
+
+    #[test]
+    fn test_go_handler_logging_form_value_flagged() {
+        let code = r#"func signupHandler(w http.ResponseWriter, r *http.Request) {
+    email := r.FormValue("email")
+    log.Printf("new signup: %s", email)
+    log.Println("signup handled")
+}
+"#;
+        let violations = go_logging_violations(code);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line_number, 3);
+        assert_eq!(violations[0].severity, "high");
+    }
+
+    #[test]
+    fn test_go_handler_logging_decoded_body_flagged() {
+        let code = r#"func (s *Server) updateProfile(w http.ResponseWriter, req *http.Request) {
+    var profile Profile
+    if err := json.NewDecoder(req.Body).Decode(&profile); err != nil {
+        log.Println("bad request")
+        return
+    }
+    log.Printf("%+v", profile)
+}
+"#;
+        let violations = go_logging_violations(code);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line_number, 7);
+    }
+
+    #[test]
+    fn test_gin_handler_logging_request_data_flagged() {
+        let code = r#"func createOrder(c *gin.Context) {
+    var order Order
+    if err := c.ShouldBindJSON(&order); err != nil {
+        log.Println("invalid order")
+        return
+    }
+    ref := c.Query("ref")
+    log.Printf("order %+v from %s", order, ref)
+}
+"#;
+        let violations = go_logging_violations(code);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line_number, 8);
+    }
+
+    #[test]
+    fn test_go_logging_outside_handlers_or_with_secrets_left_to_other_patterns() {
+        // Not a handler: nothing comes from a request
+        let code = "func main() {\n    email := loadConfig().Email\n    log.Println(email)\n}\n";
+        assert!(go_logging_violations(code).is_empty());
+
+        // Secrets are already reported as critical by the sensitive data pattern
+        let code = "func login(w http.ResponseWriter, r *http.Request) {\n    log.Println(r.FormValue(\"password\"))\n}\n";
+        assert!(go_logging_violations(code).is_empty());
+        let violations = CC72LoggingRule::analyze(code, "handlers.go", 1).unwrap();
+        assert!(violations.iter().any(|v| v.severity == "critical"));
+
+        // Only Go files
+        let code = "func login(w http.ResponseWriter, r *http.Request) {\n    log.Println(r.URL.Query())\n}\n";
+        assert_eq!(go_logging_violations(code).len(), 1);
+        assert!(CC72LoggingRule::analyze(code, "handlers.py", 1)
+            .unwrap()
+            .iter()
+            .all(|v| !v.description.contains("Go HTTP handler")));
+    }
+
+    // ===== API handlers without logging (Pattern 8) =====
+
+    fn handler_violations(code: &str, file_path: &str) -> Vec<Violation> {
+        CC72LoggingRule::analyze(code, file_path, 1)
+            .unwrap()
+            .into_iter()
+            .filter(|v| v.description.starts_with("API handler "))
+            .collect()
+    }
+
+    #[test]
+    fn test_flask_route_write_without_logging() {
+        let code = r#"import logging
+
+logger = logging.getLogger(__name__)
+
+@app.route("/orders", methods=["POST"])
+def create_order():
+    order = Order(**request.json)
+    db.session.add(order)
+    db.session.commit()
+    return jsonify(order.id), 201
+"#;
+        let violations = handler_violations(code, "app/orders.py");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].control_id, "CC7.2");
+        assert_eq!(violations[0].severity, "high");
+        assert_eq!(violations[0].line_number, 6);
+        assert_eq!(violations[0].function_name.as_deref(), Some("create_order"));
+        assert!(violations[0].description.contains("writes"));
+    }
+
+    #[test]
+    fn test_flask_route_write_with_logging_not_flagged() {
+        let code = r#"import logging
+
+@app.route("/orders", methods=["POST"])
+def create_order():
+    order = Order(**request.json)
+    db.session.add(order)
+    db.session.commit()
+    current_app.logger.info("order %s created by %s", order.id, current_user.id)
+    return jsonify(order.id), 201
+"#;
+        assert!(handler_violations(code, "app/orders.py").is_empty());
+    }
+
+    #[test]
+    fn test_flask_route_read_without_logging_is_medium() {
+        let code = "import logging\n\n@bp.get(\"/orders/<int:order_id>\")\ndef get_order(order_id):\n    order = Order.query.get_or_404(order_id)\n    return jsonify(order.to_dict())\n";
+        let violations = handler_violations(code, "orders.py");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].severity, "medium");
+        assert!(violations[0].description.contains("reads"));
+    }
+
+    #[test]
+    fn test_handler_named_function_without_route_decorator() {
+        let code = "import logging\n\ndef profile_update_view(request):\n    request.user.bio = request.POST['bio']\n    request.user.save()\n    return redirect('/profile')\n";
+        let violations = handler_violations(code, "accounts/views.py");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].severity, "high");
+        assert_eq!(violations[0].function_name.as_deref(), Some("profile_update_view"));
+    }
+
+    #[test]
+    fn test_fastapi_endpoint_write_without_logging() {
+        let code = r#"import structlog
+from fastapi import APIRouter
+
+@router.post("/items")
+async def create_item(item: Item, session: Session = Depends(get_session)):
+    session.add(item)
+    await item.save()
+    return item
+"#;
+        let violations = handler_violations(code, "api/items.py");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line_number, 5);
+    }
+
+    #[test]
+    fn test_loguru_import_activates_rule() {
+        let code = "from loguru import logger\n\n@app.put(\"/users/<int:uid>\")\ndef update_user(uid):\n    User.query.filter_by(id=uid).update(request.json)\n    db.session.commit()\n    return '', 204\n";
+        assert_eq!(handler_violations(code, "users.py").len(), 1);
+    }
+
+    #[test]
+    fn test_no_logging_import_skips_rule() {
+        let code = "@app.route(\"/orders\", methods=[\"POST\"])\ndef create_order():\n    db.session.add(Order(**request.json))\n    db.session.commit()\n    return '', 201\n";
+        assert!(handler_violations(code, "orders.py").is_empty());
+    }
+
+    #[test]
+    fn test_non_handler_function_not_flagged() {
+        let code = "import logging\n\ndef sync_inventory(items):\n    for item in items:\n        item.save()\n";
+        assert!(handler_violations(code, "tasks.py").is_empty());
+    }
+
+    #[test]
+    fn test_handler_without_database_access_not_flagged() {
+        let code = "import logging\n\n@app.route(\"/health\")\ndef health_view():\n    return {'status': 'ok'}\n";
+        assert!(handler_violations(code, "app.py").is_empty());
+    }
+
+    #[test]
+    fn test_unit_test_files_excluded() {
+        let code = "import logging\n\n@app.route(\"/orders\", methods=[\"POST\"])\ndef create_order():\n    db.session.commit()\n";
+        assert!(handler_violations(code, "tests/orders.py").is_empty());
+        assert!(handler_violations(code, "app/test_orders.py").is_empty());
+        assert!(handler_violations(code, "app/orders_test.py").is_empty());
+        assert_eq!(handler_violations(code, "app/orders.py").len(), 1);
+    }
+
+    #[test]
+    fn test_admin_handler_left_to_admin_pattern() {
+        let code = "import logging\n\n@app.route(\"/users/<int:uid>\", methods=[\"DELETE\"])\ndef delete_user(uid):\n    User.query.get(uid).delete()\n    db.session.commit()\n    return '', 204\n";
+        assert!(handler_violations(code, "users.py").is_empty());
+        assert_eq!(admin_violations(code, "users.py").len(), 1);
+    }
+
+    #[test]
+    fn test_handler_with_logging_decorator_not_flagged() {
+        let code = "import logging\n\n@app.route(\"/orders\", methods=[\"POST\"])\n@log_request\ndef create_order():\n    db.session.commit()\n    return '', 201\n";
+        assert!(handler_violations(code, "orders.py").is_empty());
+    }
+
+    #[test]
+    fn test_handler_commented_logging_does_not_count() {
+        let code = "import logging\n\n@app.route(\"/orders\", methods=[\"POST\"])\ndef create_order():\n    db.session.commit()\n    # logger.info('order created')\n    return '', 201\n";
+        assert_eq!(handler_violations(code, "orders.py").len(), 1);
+    }
+
+    #[test]
+    fn test_express_handler_write_without_logging() {
+        let code = r#"const logger = require('winston');
+
+async function createOrderHandler(req, res) {
+  const order = await Order.create(req.body);
+  res.status(201).json(order);
+}
+
+router.post('/orders', createOrderHandler);
+"#;
+        let violations = handler_violations(code, "routes/orders.js");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].severity, "high");
+        assert_eq!(violations[0].line_number, 3);
+        assert_eq!(violations[0].function_name.as_deref(), Some("createOrderHandler"));
+    }
+
+    #[test]
+    fn test_express_handler_with_logger_not_flagged() {
+        let code = r#"const logger = require('winston');
+
+async function createOrderHandler(req, res) {
+  const order = await Order.create(req.body);
+  logger.info('order created', { orderId: order.id, userId: req.user.id, ip: req.ip });
+  res.status(201).json(order);
+}
+"#;
+        assert!(handler_violations(code, "routes/orders.js").is_empty());
+    }
+
+    #[test]
+    fn test_express_handler_without_logger_import_not_flagged() {
+        let code = "async function createOrderHandler(req, res) {\n  const order = await Order.create(req.body);\n  res.status(201).json(order);\n}\n";
+        assert!(handler_violations(code, "routes/orders.js").is_empty());
+    }
+
+    #[test]
+    fn test_express_read_controller_is_medium() {
+        let code = "import pino from 'pino';\n\nexport async function listOrdersController(req, res) {\n  const orders = await Order.findAll({ where: { userId: req.user.id } });\n  res.json(orders);\n}\n";
+        let violations = handler_violations(code, "controllers/orders.ts");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].severity, "medium");
+    }
+
+    #[test]
+    fn test_local_logger_module_import_activates_rule() {
+        let code = "import logger from '../lib/logger';\n\nasync function updateProfileHandler(req, res) {\n  await User.update(req.body, { where: { id: req.user.id } });\n  res.sendStatus(204);\n}\n";
+        assert_eq!(handler_violations(code, "src/handlers/profile.ts").len(), 1);
+    }
+
+    #[test]
+    fn test_spec_files_excluded() {
+        let code = "const logger = require('pino');\n\nasync function createOrderHandler(req, res) {\n  await Order.create(req.body);\n}\n";
+        assert!(handler_violations(code, "routes/orders.spec.js").is_empty());
+        assert!(handler_violations(code, "src/__tests__/orders.js").is_empty());
+        assert!(handler_violations(code, "routes/orders.test.ts").is_empty());
+    }
+
+    #[test]
+    fn test_is_unit_test_file() {
+        assert!(is_unit_test_file("tests/test_views.py"));
+        assert!(is_unit_test_file("app/conftest.py"));
+        assert!(is_unit_test_file("src\\__tests__\\api.js"));
+        assert!(!is_unit_test_file("app/views.py"));
+        assert!(!is_unit_test_file("app/contest_views.py"));
+        assert!(!is_unit_test_file("src/latest/handlers.ts"));
+    }
+
+    // ===== Error responses leaking internals (Pattern 9) =====
+
+    fn leakage_violations(code: &str, file_path: &str) -> Vec<Violation> {
+        CC72LoggingRule::detect_information_leakage_in_error_responses(code, file_path, 1).unwrap()
+    }
+
+    #[test]
+    fn test_flask_jsonify_str_exception_flagged() {
+        let code = "from flask import Flask, jsonify\n\n@app.route('/orders')\ndef list_orders():\n    try:\n        return jsonify(Order.query.all())\n    except Exception as e:\n        return jsonify({'error': str(e)}), 500\n";
+        let violations = leakage_violations(code, "app/views.py");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].severity, "medium");
+        assert_eq!(violations[0].line_number, 8);
+        assert!(violations[0].description.starts_with("Exception message"));
+    }
+
+    #[test]
+    fn test_flask_traceback_in_response_flagged() {
+        let code = "import traceback\nfrom flask import jsonify\n\n@app.route('/report')\ndef report():\n    try:\n        return build_report()\n    except Exception:\n        return jsonify(error=traceback.format_exc()), 500\n";
+        let violations = leakage_violations(code, "app/views.py");
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].description.starts_with("Stack trace"));
+    }
+
+    #[test]
+    fn test_flask_logged_error_with_generic_message_not_flagged() {
+        let code = "import logging\nfrom flask import jsonify\n\nlogger = logging.getLogger(__name__)\n\n@app.route('/orders')\ndef list_orders():\n    try:\n        return jsonify(Order.query.all())\n    except Exception as e:\n        logger.exception(f\"Failed to list orders: {e}\")\n        return jsonify({'error': 'Internal server error'}), 500\n";
+        assert!(leakage_violations(code, "app/views.py").is_empty());
+    }
+
+    #[test]
+    fn test_flask_errorhandler_returning_exception_flagged() {
+        let code = "from flask import Flask\n\napp = Flask(__name__)\n\n@app.errorhandler(500)\ndef handle_error(error):\n    return str(error), 500\n";
+        let violations = leakage_violations(code, "app/__init__.py");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line_number, 7);
+    }
+
+    #[test]
+    fn test_return_in_non_web_module_not_flagged() {
+        let code = "def parse_config(path):\n    try:\n        return load(path)\n    except ValueError as e:\n        return str(e)\n";
+        assert!(leakage_violations(code, "app/config_loader.py").is_empty());
+    }
+
+    #[test]
+    fn test_python_fstring_with_exception_flagged() {
+        let code = "from flask import jsonify\n\ndef charge():\n    try:\n        stripe.Charge.create(amount=100)\n    except stripe.error.CardError as exc:\n        return jsonify(message=f\"Error: {exc}\"), 402\n";
+        assert_eq!(leakage_violations(code, "billing/views.py").len(), 1);
+    }
+
+    #[test]
+    fn test_python_fstring_with_sql_flagged() {
+        let code = "from flask import jsonify\n\ndef search(term):\n    sql = f\"SELECT * FROM items WHERE name = '{term}'\"\n    rows = db.execute(sql)\n    if not rows:\n        return jsonify(error=f\"Query failed: {sql}\"), 404\n    return jsonify(rows)\n";
+        let violations = leakage_violations(code, "app/search.py");
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].description.starts_with("SQL query"));
+    }
+
+    #[test]
+    fn test_django_http_response_with_debug_flagged() {
+        let code = "from django.http import HttpResponse\n\nDEBUG = True\n\ndef export_view(request):\n    try:\n        return build_export(request)\n    except Exception as exception:\n        return HttpResponse(str(exception), status=500)\n";
+        let violations = leakage_violations(code, "exports/views.py");
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].description.contains("DEBUG = True"));
+    }
+
+    #[test]
+    fn test_django_http_response_without_debug_flagged() {
+        let code = "from django.http import HttpResponse\n\ndef export_view(request):\n    try:\n        return build_export(request)\n    except Exception as e:\n        return HttpResponse(str(e), status=500)\n";
+        let violations = leakage_violations(code, "exports/views.py");
+        assert_eq!(violations.len(), 1);
+        assert!(!violations[0].description.contains("DEBUG"));
+    }
+
+    #[test]
+    fn test_express_error_middleware_stack_flagged() {
+        let code = "app.use((err, req, res, next) => {\n  res.status(500).json({ error: err.stack });\n});\n";
+        let violations = leakage_violations(code, "server.js");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line_number, 2);
+        assert!(violations[0].description.starts_with("Stack trace"));
+    }
+
+    #[test]
+    fn test_express_error_middleware_message_flagged() {
+        let code = "function errorHandler(err, req, res, next) {\n  res.status(err.status || 500);\n  res.send(err.message);\n}\n";
+        let violations = leakage_violations(code, "middleware/errors.ts");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line_number, 3);
+    }
+
+    #[test]
+    fn test_express_logged_error_with_generic_message_not_flagged() {
+        let code = "function errorHandler(err, req, res, next) {\n  logger.error(err.stack);\n  res.status(500).json({ error: 'Internal server error' });\n}\n";
+        assert!(leakage_violations(code, "middleware/errors.js").is_empty());
+    }
+
+    #[test]
+    fn test_express_catch_sending_error_object_flagged() {
+        let code = "router.post('/orders', async (req, res) => {\n  try {\n    res.json(await Order.create(req.body));\n  } catch (error) {\n    res.status(400).json({\n      success: false,\n      error: error,\n    });\n  }\n});\n";
+        let violations = leakage_violations(code, "routes/orders.js");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line_number, 5);
+    }
+
+    #[test]
+    fn test_error_variable_outside_catch_not_flagged() {
+        let code = "router.get('/status', (req, res) => {\n  const error = validate(req.query);\n  res.json({ error: error });\n});\n";
+        assert!(leakage_violations(code, "routes/status.js").is_empty());
+    }
+
+    #[test]
+    fn test_leakage_skips_comments_and_test_files() {
+        let code = "from flask import jsonify\n\ndef view():\n    try:\n        run()\n    except Exception as e:\n        # return jsonify(error=str(e)), 500\n        return jsonify(error='failed'), 500\n";
+        assert!(leakage_violations(code, "app/views.py").is_empty());
+
+        let leaking = "app.use((err, req, res, next) => res.status(500).send(err.stack));\n";
+        assert_eq!(leakage_violations(leaking, "server.js").len(), 1);
+        assert!(leakage_violations(leaking, "test/server.test.js").is_empty());
+    }
+
+    // ===== Log calls without event IDs (Pattern 10) =====
+
+    fn event_id_violations(code: &str, file_path: &str) -> Vec<Violation> {
+        CC72LoggingRule::detect_log_calls_without_event_id(code, file_path, 1).unwrap()
+    }
+
+    #[test]
+    fn test_python_user_action_fstring_without_event_id_flagged() {
+        let code = "def archive(user_id, action):\n    logger.info(f\"User {user_id} did {action}\")\n";
+        let violations = event_id_violations(code, "app/activity.py");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].severity, "low");
+        assert_eq!(violations[0].line_number, 2);
+        assert!(violations[0].description.starts_with("User action logged without an event_id"));
+        assert!(violations[0].description.contains("extra={'event_id': str(uuid.uuid4())}"));
+    }
+
+    #[test]
+    fn test_python_user_action_with_extra_event_id_not_flagged() {
+        let code = "def archive(user_id, action):\n    logger.info(f\"User {user_id} did {action}\", extra={'event_id': str(uuid.uuid4())})\n";
+        assert!(event_id_violations(code, "app/activity.py").is_empty());
+    }
+
+    #[test]
+    fn test_python_user_action_with_correlation_id_not_flagged() {
+        let code = "logger.info(\"order placed\", extra={\"user_id\": current_user.id, \"correlation_id\": g.request_id})\n";
+        assert!(event_id_violations(code, "app/orders.py").is_empty());
+    }
+
+    #[test]
+    fn test_python_multiline_log_call_checked_as_a_whole() {
+        let flagged = "logger.info(\n    \"profile updated\",\n    extra={\"user_id\": user_id},\n)\n";
+        let violations = event_id_violations(flagged, "app/profile.py");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line_number, 1);
+
+        let with_id = "logger.info(\n    \"profile updated\",\n    extra={\"user_id\": user_id, \"event_id\": event_id},\n)\n";
+        assert!(event_id_violations(with_id, "app/profile.py").is_empty());
+    }
+
+    #[test]
+    fn test_log_without_user_identity_not_flagged() {
+        let code = "logger.info(\"Cache warmed in %.2fs\", elapsed)\nlogger.error(f\"Worker {worker_id} crashed\")\n";
+        assert!(event_id_violations(code, "app/cache.py").is_empty());
+    }
+
+    #[test]
+    fn test_debug_level_and_print_not_flagged() {
+        let code = "logger.debug(f\"User {user_id} opened settings\")\nprint(f\"User {user_id} opened settings\")\n";
+        assert!(event_id_violations(code, "app/settings.py").is_empty());
+    }
+
+    #[test]
+    fn test_javascript_object_log_without_event_id_flagged() {
+        let code = "function archive(userId, action) {\n  logger.info({ userId, action });\n}\n";
+        let violations = event_id_violations(code, "src/activity.js");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line_number, 2);
+        assert!(violations[0].description.contains("eventId: crypto.randomUUID()"));
+    }
+
+    #[test]
+    fn test_javascript_object_log_with_event_id_not_flagged() {
+        let code = "function archive(userId, action) {\n  logger.info({ eventId: randomUUID(), userId, action });\n}\n";
+        assert!(event_id_violations(code, "src/activity.ts").is_empty());
+    }
+
+    #[test]
+    fn test_javascript_req_user_in_message_flagged() {
+        let code = "router.delete('/orders/:id', async (req, res) => {\n  await Order.destroy({ where: { id: req.params.id } });\n  logger.info(`order ${req.params.id} deleted by ${req.user.id}`);\n  res.sendStatus(204);\n});\n";
+        let violations = event_id_violations(code, "routes/orders.js");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line_number, 3);
+    }
+
+    #[test]
+    fn test_python_auth_warning_without_correlation_id_flagged() {
+        let code = "def login(username, password):\n    user = User.query.filter_by(username=username).first()\n    if not user:\n        logger.warning(\"Login failed: unknown account\")\n        return None\n    return user\n";
+        let violations = event_id_violations(code, "app/auth.py");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line_number, 4);
+        assert_eq!(violations[0].function_name.as_deref(), Some("login"));
+        assert!(violations[0].description.starts_with("Authentication warning in 'login' logged without a correlation_id"));
+        assert!(violations[0].description.contains("extra={'correlation_id': request_id}"));
+    }
+
+    #[test]
+    fn test_python_auth_warning_with_correlation_id_not_flagged() {
+        let code = "def authenticate(username, password):\n    if not check_password(username, password):\n        logger.warning(\"Bad password\", extra={\"correlation_id\": g.request_id})\n        return False\n    return True\n";
+        assert!(event_id_violations(code, "app/auth.py").is_empty());
+    }
+
+    #[test]
+    fn test_auth_warning_needs_correlation_id_not_just_event_id() {
+        let code = "def verify_token(token):\n    if expired(token):\n        logger.warning(\"Expired token\", extra={\"event_id\": new_event_id()})\n        return None\n    return decode(token)\n";
+        assert_eq!(event_id_violations(code, "app/tokens.py").len(), 1);
+    }
+
+    #[test]
+    fn test_javascript_auth_warning_flagged_and_warning_outside_auth_not() {
+        let code = "async function verifyToken(token) {\n  if (!token) {\n    logger.warn('missing token');\n    return null;\n  }\n  return jwt.verify(token, secret);\n}\n\nfunction loadConfig() {\n  logger.warn('config file missing, using defaults');\n}\n";
+        let violations = event_id_violations(code, "src/auth.ts");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line_number, 3);
+        assert!(violations[0].description.contains("correlationId: req.id"));
+    }
+
+    #[test]
+    fn test_event_id_skips_comments_test_files_and_other_languages() {
+        let code = "# logger.info(f\"User {user_id} signed up\")\nlogger.info(\"signup\", extra={\"event_id\": eid, \"user_id\": user_id})\n";
+        assert!(event_id_violations(code, "app/signup.py").is_empty());
+
+        let flagged = "logger.info(f\"User {user_id} signed up\")\n";
+        assert_eq!(event_id_violations(flagged, "app/signup.py").len(), 1);
+        assert!(event_id_violations(flagged, "tests/test_signup.py").is_empty());
+        assert!(event_id_violations(flagged, "signup.go").is_empty());
+    }
+
+    #[test]
+    fn test_log_has_event_id() {
+        assert!(log_has_event_id("logger.info('x', extra={'event_id': eid})"));
+        assert!(log_has_event_id("logger.info({ eventId, userId })"));
+        assert!(log_has_event_id("logger.info('x', extra={'correlation_id': cid})"));
+        assert!(log_has_event_id("logger.info({ correlationId: req.id })"));
+        assert!(log_has_event_id("logger.info('x', headers['X-Correlation-ID'])"));
+        assert!(!log_has_event_id("logger.info(f'User {user_id} did {action}')"));
+        assert!(!log_has_event_id("logger.info({ prevented: true, userId })"));
     }
 }
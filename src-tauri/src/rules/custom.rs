@@ -0,0 +1,394 @@
+//! Custom rule packs
+//!
+//! Compliance teams can encode organization-specific policies without
+//! recompiling. Rules are regex patterns with a severity, the SOC 2 control
+//! they map to and optional file globs, loaded from every pack in the global
+//! rules directory (`~/.ryn/rules/*.yaml|yml|toml`) and then from
+//! `ryn-rules.yaml` (or `.yml`/`.toml`) in the project root. A project rule
+//! replaces a global rule with the same `id`.
+//!
+//! ```yaml
+//! rules:
+//!   - id: no-customer-email-logging
+//!     control_id: CC7.2
+//!     severity: medium
+//!     description: Customer email written to logs
+//!     pattern: 'log(ger)?\.\w+\(.*\bcustomer_email\b'
+//!     files: ["src/**/*.py"]
+//!     exclude: ["src/legacy/**"]
+//! ```
+//!
+//! Patterns are matched against each line; every matching line is one
+//! violation. Custom rules run alongside the built-in CC6.1/CC6.7/CC7.2/A1.2
+//! engines on every file those engines scan.
+
+use anyhow::{anyhow, bail, Context, Result};
+use crate::models::{Severity, Violation};
+use crate::scanner::scan_excludes;
+use crate::utils::extract_context_from_string;
+use globset::GlobSet;
+use regex::Regex;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Rule pack file names looked for in the project root (the first one found is used)
+pub const PROJECT_RULE_FILES: [&str; 3] = ["ryn-rules.yaml", "ryn-rules.yml", "ryn-rules.toml"];
+
+/// A rule as written in a rule pack
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CustomRuleDefinition {
+    /// Identifies the rule across packs, e.g. "no-customer-email-logging"
+    pub id: String,
+    /// SOC 2 control the violation is filed under, e.g. "CC7.2"
+    pub control_id: String,
+    pub severity: Severity,
+    pub description: String,
+    /// Regex matched against each line
+    pub pattern: String,
+    /// Globs (relative to the project root) the rule applies to; every file when empty
+    #[serde(default)]
+    pub files: Vec<String>,
+    /// Globs the rule never applies to
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+/// Top level of a rule pack file
+#[derive(Debug, Deserialize)]
+struct RulePack {
+    #[serde(default)]
+    rules: Vec<CustomRuleDefinition>,
+}
+
+/// A definition with its pattern and globs compiled
+#[derive(Debug)]
+struct CustomRule {
+    definition: CustomRuleDefinition,
+    regex: Regex,
+    files: GlobSet,
+    exclude: GlobSet,
+    /// Pack the rule was loaded from, for violation reasoning
+    source: PathBuf,
+}
+
+impl CustomRule {
+    fn compile(definition: CustomRuleDefinition, source: &Path) -> Result<Self> {
+        for (field, value) in [
+            ("id", &definition.id),
+            ("control_id", &definition.control_id),
+            ("description", &definition.description),
+            ("pattern", &definition.pattern),
+        ] {
+            if value.trim().is_empty() {
+                bail!("Custom rule {:?} in {} has an empty {}", definition.id, source.display(), field);
+            }
+        }
+
+        let regex = Regex::new(&definition.pattern)
+            .with_context(|| format!("Invalid pattern in custom rule {:?}", definition.id))?;
+        let files = scan_excludes::build_exclude_set(&definition.files)
+            .with_context(|| format!("Invalid files glob in custom rule {:?}", definition.id))?;
+        let exclude = scan_excludes::build_exclude_set(&definition.exclude)
+            .with_context(|| format!("Invalid exclude glob in custom rule {:?}", definition.id))?;
+
+        Ok(Self {
+            definition,
+            regex,
+            files,
+            exclude,
+            source: source.to_path_buf(),
+        })
+    }
+
+    /// Whether the rule applies to `file_path` (relative to the project root)
+    fn applies_to(&self, file_path: &Path) -> bool {
+        (self.files.is_empty() || self.files.is_match(file_path))
+            && !scan_excludes::is_excluded(&self.exclude, file_path)
+    }
+}
+
+/// The custom rules in effect for a project
+#[derive(Debug, Default)]
+pub struct CustomRuleSet {
+    rules: Vec<CustomRule>,
+}
+
+impl CustomRuleSet {
+    /// Load the global packs and the project's pack
+    ///
+    /// Returns an empty set when neither exists. A pack that can't be read or
+    /// parsed, or a rule with an invalid pattern or glob, is an error.
+    pub fn load(project_path: &Path) -> Result<Self> {
+        Self::load_from(project_path, global_rules_dir().as_deref())
+    }
+
+    /// Load the packs in `global_dir` (sorted by file name), then the project's pack
+    pub fn load_from(project_path: &Path, global_dir: Option<&Path>) -> Result<Self> {
+        let mut packs = Vec::new();
+
+        if let Some(dir) = global_dir.filter(|dir| dir.is_dir()) {
+            let mut global_packs: Vec<PathBuf> = std::fs::read_dir(dir)
+                .with_context(|| format!("Failed to read rules directory {}", dir.display()))?
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| path.is_file() && pack_format(path).is_some())
+                .collect();
+            global_packs.sort();
+            packs.extend(global_packs);
+        }
+
+        if let Some(project_pack) = PROJECT_RULE_FILES
+            .iter()
+            .map(|name| project_path.join(name))
+            .find(|path| path.is_file())
+        {
+            packs.push(project_pack);
+        }
+
+        let mut rule_set = Self::default();
+        for pack in packs {
+            let content = std::fs::read_to_string(&pack)
+                .with_context(|| format!("Failed to read rule pack {}", pack.display()))?;
+            for definition in Self::parse_pack(&content, &pack)? {
+                rule_set.insert(CustomRule::compile(definition, &pack)?);
+            }
+        }
+        Ok(rule_set)
+    }
+
+    /// Parse a rule pack, as YAML or TOML depending on `path`'s extension
+    pub fn parse_pack(content: &str, path: &Path) -> Result<Vec<CustomRuleDefinition>> {
+        let pack: RulePack = match pack_format(path) {
+            Some(PackFormat::Yaml) => serde_yaml::from_str(content)
+                .with_context(|| format!("Failed to parse rule pack {}", path.display()))?,
+            Some(PackFormat::Toml) => toml::from_str(content)
+                .with_context(|| format!("Failed to parse rule pack {}", path.display()))?,
+            None => return Err(anyhow!("Unsupported rule pack format: {}", path.display())),
+        };
+        Ok(pack.rules)
+    }
+
+    /// Add a rule, replacing any earlier rule with the same id
+    fn insert(&mut self, rule: CustomRule) {
+        match self.rules.iter_mut().find(|r| r.definition.id == rule.definition.id) {
+            Some(existing) => *existing = rule,
+            None => self.rules.push(rule),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Definitions of the loaded rules, in load order
+    pub fn definitions(&self) -> impl Iterator<Item = &CustomRuleDefinition> {
+        self.rules.iter().map(|rule| &rule.definition)
+    }
+
+    /// Run every rule that applies to `file_path` (relative to the project root)
+    ///
+    /// The rule packs themselves are never scanned, since their patterns would
+    /// often match their own text.
+    pub fn analyze(&self, code: &str, file_path: &str, scan_id: i64) -> Vec<Violation> {
+        let path = Path::new(file_path);
+        if self.rules.is_empty() || PROJECT_RULE_FILES.iter().any(|name| path == Path::new(name)) {
+            return Vec::new();
+        }
+
+        let mut violations = Vec::new();
+        for rule in self.rules.iter().filter(|rule| rule.applies_to(path)) {
+            for (idx, line) in code.lines().enumerate() {
+                if !rule.regex.is_match(line) {
+                    continue;
+                }
+                let line_num = (idx + 1) as i64;
+                let (context, _) = extract_context_from_string(code, line_num, 3);
+                let mut violation = Violation::new(
+                    scan_id,
+                    rule.definition.control_id.clone(),
+                    rule.definition.severity,
+                    rule.definition.description.clone(),
+                    file_path.to_string(),
+                    line_num,
+                    context,
+                );
+                violation.regex_reasoning = Some(format!(
+                    "Custom rule {:?} from {} matched /{}/",
+                    rule.definition.id,
+                    rule.source.display(),
+                    rule.definition.pattern
+                ));
+                violations.push(violation);
+            }
+        }
+        violations
+    }
+}
+
+/// Directory of organization-wide rule packs: `~/.ryn/rules`
+pub fn global_rules_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".ryn/rules"))
+}
+
+enum PackFormat {
+    Yaml,
+    Toml,
+}
+
+fn pack_format(path: &Path) -> Option<PackFormat> {
+    match path.extension()?.to_str()? {
+        "yaml" | "yml" => Some(PackFormat::Yaml),
+        "toml" => Some(PackFormat::Toml),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const YAML_PACK: &str = r#"
+rules:
+  - id: no-customer-email-logging
+    control_id: CC7.2
+    severity: medium
+    description: Customer email written to logs
+    pattern: 'log(ger)?\.\w+\(.*\bcustomer_email\b'
+    files: ["src/**/*.py"]
+    exclude: ["src/legacy/**"]
+  - id: no-eval
+    control_id: CC6.1
+    severity: high
+    description: eval() on untrusted input
+    pattern: '\beval\('
+"#;
+
+    const TOML_PACK: &str = r#"
+[[rules]]
+id = "no-eval"
+control_id = "CC6.1"
+severity = "critical"
+description = "eval() is banned"
+pattern = '\beval\('
+"#;
+
+    fn rule_set(content: &str, pack: &str) -> CustomRuleSet {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(pack), content).unwrap();
+        CustomRuleSet::load_from(dir.path(), None).unwrap()
+    }
+
+    #[test]
+    fn test_parse_yaml_pack() {
+        let definitions = CustomRuleSet::parse_pack(YAML_PACK, Path::new("ryn-rules.yaml")).unwrap();
+        assert_eq!(definitions.len(), 2);
+        assert_eq!(definitions[0].id, "no-customer-email-logging");
+        assert_eq!(definitions[0].severity, Severity::Medium);
+        assert_eq!(definitions[0].files, vec!["src/**/*.py"]);
+        assert!(definitions[1].files.is_empty());
+    }
+
+    #[test]
+    fn test_parse_toml_pack() {
+        let definitions = CustomRuleSet::parse_pack(TOML_PACK, Path::new("ryn-rules.toml")).unwrap();
+        assert_eq!(definitions.len(), 1);
+        assert_eq!(definitions[0].control_id, "CC6.1");
+        assert_eq!(definitions[0].severity, Severity::Critical);
+    }
+
+    #[test]
+    fn test_parse_pack_rejects_unknown_severity() {
+        let pack = "rules:\n  - id: x\n    control_id: CC6.1\n    severity: urgent\n    description: d\n    pattern: x\n";
+        assert!(CustomRuleSet::parse_pack(pack, Path::new("ryn-rules.yaml")).is_err());
+    }
+
+    #[test]
+    fn test_load_rejects_invalid_pattern() {
+        let dir = tempfile::tempdir().unwrap();
+        let pack = "rules:\n  - id: broken\n    control_id: CC6.1\n    severity: low\n    description: d\n    pattern: 'eval(('\n";
+        fs::write(dir.path().join("ryn-rules.yaml"), pack).unwrap();
+
+        let err = CustomRuleSet::load_from(dir.path(), None).unwrap_err();
+        assert!(err.to_string().contains("broken"));
+    }
+
+    #[test]
+    fn test_load_rejects_empty_control_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let pack = "rules:\n  - id: x\n    control_id: ''\n    severity: low\n    description: d\n    pattern: x\n";
+        fs::write(dir.path().join("ryn-rules.yaml"), pack).unwrap();
+
+        assert!(CustomRuleSet::load_from(dir.path(), None).is_err());
+    }
+
+    #[test]
+    fn test_load_without_packs_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let rules = CustomRuleSet::load_from(dir.path(), Some(&dir.path().join("missing"))).unwrap();
+        assert!(rules.is_empty());
+    }
+
+    #[test]
+    fn test_analyze_reports_matching_lines() {
+        let rules = rule_set(YAML_PACK, "ryn-rules.yaml");
+        let code = "import logging\nlogger.info(\"signup %s\", customer_email)\nresult = eval(expr)\n";
+
+        let violations = rules.analyze(code, "src/users/signup.py", 7);
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].control_id, "CC7.2");
+        assert_eq!(violations[0].severity, "medium");
+        assert_eq!(violations[0].line_number, 2);
+        assert_eq!(violations[0].scan_id, 7);
+        assert!(violations[0].regex_reasoning.as_ref().unwrap().contains("no-customer-email-logging"));
+        assert_eq!(violations[1].control_id, "CC6.1");
+        assert_eq!(violations[1].line_number, 3);
+    }
+
+    #[test]
+    fn test_analyze_respects_file_globs() {
+        let rules = rule_set(YAML_PACK, "ryn-rules.yaml");
+        let code = "logger.info(customer_email)\n";
+
+        assert_eq!(rules.analyze(code, "src/app.py", 1).len(), 1);
+        // Outside `files`
+        assert!(rules.analyze(code, "scripts/app.py", 1).is_empty());
+        // Inside `exclude`
+        assert!(rules.analyze(code, "src/legacy/app.py", 1).is_empty());
+    }
+
+    #[test]
+    fn test_analyze_skips_rule_pack_itself() {
+        let rules = rule_set(YAML_PACK, "ryn-rules.yaml");
+        assert!(rules.analyze("pattern: 'eval(x)'", "ryn-rules.yaml", 1).is_empty());
+    }
+
+    #[test]
+    fn test_project_rule_overrides_global_rule() {
+        let global = tempfile::tempdir().unwrap();
+        fs::write(global.path().join("org.toml"), TOML_PACK).unwrap();
+        fs::write(global.path().join("notes.txt"), "not a pack").unwrap();
+        let project = tempfile::tempdir().unwrap();
+        fs::write(project.path().join("ryn-rules.yaml"), YAML_PACK).unwrap();
+
+        let rules = CustomRuleSet::load_from(project.path(), Some(global.path())).unwrap();
+        assert_eq!(rules.len(), 2);
+        let no_eval = rules.definitions().find(|d| d.id == "no-eval").unwrap();
+        assert_eq!(no_eval.severity, Severity::High);
+    }
+
+    #[test]
+    fn test_global_rules_apply_without_project_pack() {
+        let global = tempfile::tempdir().unwrap();
+        fs::write(global.path().join("org.toml"), TOML_PACK).unwrap();
+        let project = tempfile::tempdir().unwrap();
+
+        let rules = CustomRuleSet::load_from(project.path(), Some(global.path())).unwrap();
+        let violations = rules.analyze("eval(payload)\n", "app.js", 1);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].severity, "critical");
+    }
+}
@@ -0,0 +1,1026 @@
+//! Infrastructure as Code: Terraform and CloudFormation misconfigurations
+//!
+//! SOC 2 Requirement: The cloud resources an application runs on are in audit
+//! scope, so access to them must be restricted, stored data encrypted and
+//! account activity logged.
+//!
+//! This rule detects, in Terraform (`.tf`) files and CloudFormation templates
+//! (YAML or JSON):
+//! - S3 buckets with public canned ACLs or a public access block that leaves
+//!   public access open (CC6.1)
+//! - Security groups allowing ingress from 0.0.0.0/0 or ::/0 on anything but
+//!   HTTP/HTTPS (CC6.1)
+//! - RDS instances and clusters, EBS volumes and EFS file systems without
+//!   encryption at rest (CC6.7)
+//! - CloudTrail trails with logging turned off, and AWS infrastructure with no
+//!   trail anywhere in the project (CC7.2)
+//!
+//! Only literal values are checked; an attribute set from a variable or an
+//! intrinsic function (`var.encrypted`, `!Ref Encrypt`) is assumed to be
+//! configured correctly.
+
+use crate::models::{Severity, Violation};
+use anyhow::Result;
+use serde_yaml::Value;
+
+/// Canned S3 ACLs that open a bucket to everyone (Terraform spelling)
+const PUBLIC_S3_ACLS: [&str; 3] = ["public-read", "public-read-write", "authenticated-read"];
+
+/// Canned S3 ACLs that open a bucket to everyone (CloudFormation spelling)
+const PUBLIC_CFN_ACLS: [&str; 3] = ["PublicRead", "PublicReadWrite", "AuthenticatedRead"];
+
+/// S3 public access block settings, all of which must be on (Terraform spelling)
+const PUBLIC_ACCESS_BLOCK_FLAGS: [&str; 4] = [
+    "block_public_acls",
+    "block_public_policy",
+    "ignore_public_acls",
+    "restrict_public_buckets",
+];
+
+/// S3 public access block settings, all of which must be on (CloudFormation spelling)
+const CFN_PUBLIC_ACCESS_BLOCK_FLAGS: [&str; 4] = [
+    "BlockPublicAcls",
+    "BlockPublicPolicy",
+    "IgnorePublicAcls",
+    "RestrictPublicBuckets",
+];
+
+/// Terraform resources storing data at rest, with the attribute that encrypts them
+const TF_ENCRYPTED_STORAGE: [(&str, &str); 4] = [
+    ("aws_db_instance", "storage_encrypted"),
+    ("aws_rds_cluster", "storage_encrypted"),
+    ("aws_ebs_volume", "encrypted"),
+    ("aws_efs_file_system", "encrypted"),
+];
+
+/// CloudFormation resources storing data at rest, with the property that encrypts them
+const CFN_ENCRYPTED_STORAGE: [(&str, &str); 4] = [
+    ("AWS::RDS::DBInstance", "StorageEncrypted"),
+    ("AWS::RDS::DBCluster", "StorageEncrypted"),
+    ("AWS::EC2::Volume", "Encrypted"),
+    ("AWS::EFS::FileSystem", "Encrypted"),
+];
+
+/// Ports whose exposure to the internet is critical (SSH, RDP and databases)
+const SENSITIVE_PORTS: [i64; 7] = [22, 3389, 3306, 5432, 1433, 6379, 27017];
+
+/// Ports a public load balancer or web server is expected to open
+const WEB_PORTS: [i64; 2] = [80, 443];
+
+/// A Terraform block, e.g. `resource "aws_s3_bucket" "logs" { ... }`
+#[derive(Debug, Clone, Default)]
+struct TfBlock {
+    /// Block type (`resource`, `provider`, `ingress`, ...)
+    kind: String,
+    labels: Vec<String>,
+    /// Zero-based index of the block's first line
+    line: usize,
+    attributes: Vec<TfAttribute>,
+    blocks: Vec<TfBlock>,
+}
+
+/// A Terraform attribute, e.g. `acl = "private"`
+#[derive(Debug, Clone)]
+struct TfAttribute {
+    key: String,
+    /// Expression as written, continuation lines joined with newlines
+    value: String,
+    /// Zero-based index of the attribute's first line
+    line: usize,
+}
+
+impl TfBlock {
+    /// Resource type for `resource`/`data` blocks (first label)
+    fn resource_type(&self) -> &str {
+        self.labels.first().map(String::as_str).unwrap_or("")
+    }
+
+    /// Resource name (last label)
+    fn name(&self) -> &str {
+        self.labels.last().map(String::as_str).unwrap_or("")
+    }
+
+    fn attribute(&self, key: &str) -> Option<&TfAttribute> {
+        self.attributes.iter().find(|attr| attr.key == key)
+    }
+
+    /// Value of a literal string attribute (not an interpolation)
+    fn string(&self, key: &str) -> Option<&str> {
+        let value = self.attribute(key)?.value.trim();
+        let inner = value.strip_prefix('"')?.strip_suffix('"')?;
+        (!inner.contains("${")).then_some(inner)
+    }
+
+    /// Value of a literal boolean attribute
+    fn bool(&self, key: &str) -> Option<bool> {
+        match self.attribute(key)?.value.trim().trim_matches('"') {
+            "true" => Some(true),
+            "false" => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Value of a literal number attribute
+    fn number(&self, key: &str) -> Option<i64> {
+        self.attribute(key)?.value.trim().trim_matches('"').parse().ok()
+    }
+
+    /// Whether `key` is missing or literally false
+    fn is_off(&self, key: &str) -> bool {
+        self.attribute(key).is_none() || self.bool(key) == Some(false)
+    }
+}
+
+/// A resource in a CloudFormation template's `Resources` section
+struct CfnResource<'a> {
+    /// Logical ID
+    id: &'a str,
+    /// Resource type, e.g. "AWS::S3::Bucket"
+    kind: &'a str,
+    properties: Option<&'a Value>,
+    /// Zero-based index of the line declaring the logical ID
+    line: usize,
+}
+
+impl CfnResource<'_> {
+    fn property(&self, key: &str) -> Option<&Value> {
+        self.properties?.get(key)
+    }
+
+    /// Whether `key` is missing or literally false
+    fn is_off(&self, key: &str) -> bool {
+        let value = self.property(key);
+        value.is_none() || value.and_then(cfn_bool) == Some(false)
+    }
+}
+
+/// Infrastructure as Code Rule Engine
+///
+/// Detects cloud misconfigurations in Terraform and CloudFormation files.
+pub struct IacRule;
+
+impl IacRule {
+    /// Whether a file may hold Terraform or a CloudFormation template
+    pub fn is_iac_file(file_path: &str) -> bool {
+        [".tf", ".yaml", ".yml", ".json"].iter().any(|ext| file_path.ends_with(ext))
+    }
+
+    /// Analyzes a Terraform file or CloudFormation template for misconfigurations
+    ///
+    /// YAML and JSON files that aren't CloudFormation templates have no violations.
+    ///
+    /// # Arguments
+    /// * `code` - The file contents
+    /// * `file_path` - The path to the file being analyzed
+    /// * `scan_id` - The ID of the current scan
+    ///
+    /// # Returns
+    /// A vector of violations found in the file
+    pub fn analyze(code: &str, file_path: &str, scan_id: i64) -> Result<Vec<Violation>> {
+        let lines: Vec<&str> = code.lines().collect();
+        let finding = |control_id: &str, severity: Severity, description: String, line: usize| {
+            Violation::new(
+                scan_id,
+                control_id.to_string(),
+                severity,
+                description,
+                file_path.to_string(),
+                (line + 1) as i64,
+                lines.get(line).map_or("", |l| l.trim()).to_string(),
+            )
+        };
+
+        let mut violations = Vec::new();
+        if file_path.ends_with(".tf") {
+            for block in parse_terraform(code).iter().filter(|b| b.kind == "resource") {
+                Self::check_terraform_resource(block, &mut |control_id, severity, description, line| {
+                    violations.push(finding(control_id, severity, description, line))
+                });
+            }
+        } else if let Some(template) = parse_cloudformation(code) {
+            for resource in cfn_resources(&template, &lines) {
+                Self::check_cfn_resource(&resource, &lines, &mut |control_id, severity, description, line| {
+                    violations.push(finding(control_id, severity, description, line))
+                });
+            }
+        }
+        Ok(violations)
+    }
+
+    /// Flags AWS infrastructure code that defines no CloudTrail trail
+    ///
+    /// Trails are usually defined once per account, so every Terraform file
+    /// and template in the project is considered together. Reports at most
+    /// one violation, on the first `provider "aws"` block (or, failing that,
+    /// the first AWS resource).
+    ///
+    /// # Arguments
+    /// * `files` - (path, contents) of the project's IaC files
+    /// * `scan_id` - The ID of the current scan
+    pub fn detect_missing_cloudtrail(files: &[(String, String)], scan_id: i64) -> Option<Violation> {
+        let mut sorted: Vec<&(String, String)> = files.iter().collect();
+        sorted.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut provider: Option<(&str, &str, usize)> = None;
+        let mut resource: Option<(&str, &str, usize)> = None;
+        for (path, code) in sorted {
+            if path.ends_with(".tf") {
+                let blocks = parse_terraform(code);
+                if blocks.iter().any(|b| b.kind == "resource" && b.resource_type() == "aws_cloudtrail") {
+                    return None;
+                }
+                if let Some(block) = blocks.iter().find(|b| b.kind == "provider" && b.resource_type() == "aws") {
+                    provider = provider.or(Some((path.as_str(), code.as_str(), block.line)));
+                }
+                if let Some(block) = blocks
+                    .iter()
+                    .find(|b| b.kind == "resource" && b.resource_type().starts_with("aws_"))
+                {
+                    resource = resource.or(Some((path.as_str(), code.as_str(), block.line)));
+                }
+            } else if let Some(template) = parse_cloudformation(code) {
+                let lines: Vec<&str> = code.lines().collect();
+                let resources = cfn_resources(&template, &lines);
+                if resources.iter().any(|r| r.kind == "AWS::CloudTrail::Trail") {
+                    return None;
+                }
+                if let Some(first) = resources.iter().find(|r| r.kind.starts_with("AWS::")) {
+                    resource = resource.or(Some((path.as_str(), code.as_str(), first.line)));
+                }
+            }
+        }
+
+        let (path, code, line) = provider.or(resource)?;
+        Some(Violation::new(
+            scan_id,
+            "CC7.2".to_string(),
+            Severity::Medium,
+            "AWS infrastructure is defined without a CloudTrail trail, so account activity is not audited".to_string(),
+            path.to_string(),
+            (line + 1) as i64,
+            code.lines().nth(line).map_or("", |l| l.trim()).to_string(),
+        ))
+    }
+
+    fn check_terraform_resource(
+        block: &TfBlock,
+        report: &mut dyn FnMut(&str, Severity, String, usize),
+    ) {
+        let name = block.name();
+        match block.resource_type() {
+            "aws_s3_bucket" | "aws_s3_bucket_acl" => {
+                if let Some(acl) = block.string("acl").filter(|acl| PUBLIC_S3_ACLS.contains(acl)) {
+                    let line = block.attribute("acl").map_or(block.line, |attr| attr.line);
+                    let severity = if acl == "public-read-write" { Severity::Critical } else { Severity::High };
+                    report("CC6.1", severity, format!("S3 bucket {} uses the public \"{}\" ACL", name, acl), line);
+                }
+            }
+            "aws_s3_bucket_public_access_block" => {
+                let open: Vec<&str> = PUBLIC_ACCESS_BLOCK_FLAGS
+                    .iter()
+                    .copied()
+                    .filter(|flag| block.is_off(flag))
+                    .collect();
+                if !open.is_empty() {
+                    report(
+                        "CC6.1",
+                        Severity::High,
+                        format!("S3 public access block {} does not enable {}", name, open.join(", ")),
+                        block.line,
+                    );
+                }
+            }
+            "aws_security_group" => {
+                for ingress in block.blocks.iter().filter(|b| b.kind == "ingress") {
+                    Self::check_terraform_ingress(ingress, name, report);
+                }
+            }
+            "aws_security_group_rule" => {
+                if block.string("type") == Some("ingress") {
+                    Self::check_terraform_ingress(block, name, report);
+                }
+            }
+            "aws_vpc_security_group_ingress_rule" => Self::check_terraform_ingress(block, name, report),
+            "aws_cloudtrail" => {
+                if block.bool("enable_logging") == Some(false) {
+                    let line = block.attribute("enable_logging").map_or(block.line, |attr| attr.line);
+                    report("CC7.2", Severity::High, format!("CloudTrail trail {} has logging disabled", name), line);
+                }
+            }
+            resource_type => {
+                let Some((_, key)) = TF_ENCRYPTED_STORAGE.iter().find(|(kind, _)| *kind == resource_type) else {
+                    return;
+                };
+                // Read replicas inherit the source database's encryption
+                if resource_type == "aws_db_instance" && block.attribute("replicate_source_db").is_some() {
+                    return;
+                }
+                if block.is_off(key) {
+                    let line = block.attribute(key).map_or(block.line, |attr| attr.line);
+                    report(
+                        "CC6.7",
+                        Severity::High,
+                        format!("{} {} is not encrypted at rest ({} is not enabled)", resource_type, name, key),
+                        line,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Checks an `ingress` block or ingress rule resource for internet exposure
+    fn check_terraform_ingress(block: &TfBlock, group: &str, report: &mut dyn FnMut(&str, Severity, String, usize)) {
+        let Some(cidr) = ["cidr_blocks", "ipv6_cidr_blocks", "cidr_ipv4", "cidr_ipv6"]
+            .iter()
+            .filter_map(|key| block.attribute(key))
+            .find(|attr| attr.value.contains("\"0.0.0.0/0\"") || attr.value.contains("\"::/0\""))
+        else {
+            return;
+        };
+        let protocol = block.string("protocol").or_else(|| block.string("ip_protocol"));
+        if let Some((severity, ports)) = ingress_exposure(block.number("from_port"), block.number("to_port"), protocol) {
+            report(
+                "CC6.1",
+                severity,
+                format!("Security group {} allows ingress from the internet on {}", group, ports),
+                cidr.line,
+            );
+        }
+    }
+
+    fn check_cfn_resource(
+        resource: &CfnResource,
+        lines: &[&str],
+        report: &mut dyn FnMut(&str, Severity, String, usize),
+    ) {
+        let id = resource.id;
+        let line_of = |key: &str| line_of_key(lines, resource.line, key).unwrap_or(resource.line);
+        match resource.kind {
+            "AWS::S3::Bucket" => {
+                if let Some(acl) = resource
+                    .property("AccessControl")
+                    .and_then(Value::as_str)
+                    .filter(|acl| PUBLIC_CFN_ACLS.contains(acl))
+                {
+                    let severity = if acl == "PublicReadWrite" { Severity::Critical } else { Severity::High };
+                    report(
+                        "CC6.1",
+                        severity,
+                        format!("S3 bucket {} uses the public \"{}\" ACL", id, acl),
+                        line_of("AccessControl"),
+                    );
+                }
+                if let Some(block) = resource.property("PublicAccessBlockConfiguration") {
+                    let open: Vec<&str> = CFN_PUBLIC_ACCESS_BLOCK_FLAGS
+                        .iter()
+                        .copied()
+                        .filter(|flag| {
+                            let value = block.get(flag);
+                            value.is_none() || value.and_then(cfn_bool) == Some(false)
+                        })
+                        .collect();
+                    if !open.is_empty() {
+                        report(
+                            "CC6.1",
+                            Severity::High,
+                            format!("S3 bucket {} public access block does not enable {}", id, open.join(", ")),
+                            line_of("PublicAccessBlockConfiguration"),
+                        );
+                    }
+                }
+            }
+            "AWS::EC2::SecurityGroup" => {
+                let rules = resource.property("SecurityGroupIngress").and_then(Value::as_sequence);
+                let mut search_from = resource.line;
+                for rule in rules.into_iter().flatten() {
+                    Self::check_cfn_ingress(rule, id, lines, &mut search_from, report);
+                }
+            }
+            "AWS::EC2::SecurityGroupIngress" => {
+                if let Some(rule) = resource.properties {
+                    let mut search_from = resource.line;
+                    Self::check_cfn_ingress(rule, id, lines, &mut search_from, report);
+                }
+            }
+            "AWS::CloudTrail::Trail" => {
+                if resource.property("IsLogging").and_then(cfn_bool) == Some(false) {
+                    report(
+                        "CC7.2",
+                        Severity::High,
+                        format!("CloudTrail trail {} has logging disabled", id),
+                        line_of("IsLogging"),
+                    );
+                }
+            }
+            kind => {
+                let Some((_, key)) = CFN_ENCRYPTED_STORAGE.iter().find(|(storage, _)| *storage == kind) else {
+                    return;
+                };
+                // Aurora instances are encrypted through their cluster, replicas through their source
+                if kind == "AWS::RDS::DBInstance"
+                    && (resource.property("DBClusterIdentifier").is_some()
+                        || resource.property("SourceDBInstanceIdentifier").is_some())
+                {
+                    return;
+                }
+                if resource.is_off(key) {
+                    report(
+                        "CC6.7",
+                        Severity::High,
+                        format!("{} {} is not encrypted at rest ({} is not enabled)", kind, id, key),
+                        line_of(key),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Checks one CloudFormation ingress rule for internet exposure
+    ///
+    /// `search_from` advances past each open CIDR so rules in the same group
+    /// are reported on their own lines.
+    fn check_cfn_ingress(
+        rule: &Value,
+        group: &str,
+        lines: &[&str],
+        search_from: &mut usize,
+        report: &mut dyn FnMut(&str, Severity, String, usize),
+    ) {
+        let text = |key: &str| rule.get(key).and_then(cfn_text);
+        let cidr = match (text("CidrIp"), text("CidrIpv6")) {
+            (Some(cidr), _) if cidr == "0.0.0.0/0" => cidr,
+            (_, Some(cidr)) if cidr == "::/0" => cidr,
+            _ => return,
+        };
+        let line = (*search_from..lines.len())
+            .find(|&idx| lines[idx].contains(cidr.as_str()))
+            .unwrap_or(*search_from);
+        *search_from = line + 1;
+
+        let number = |key: &str| text(key).and_then(|value| value.parse::<i64>().ok());
+        let protocol = text("IpProtocol");
+        let Some((severity, ports)) = ingress_exposure(number("FromPort"), number("ToPort"), protocol.as_deref()) else {
+            return;
+        };
+        report(
+            "CC6.1",
+            severity,
+            format!("Security group {} allows ingress from the internet on {}", group, ports),
+            line,
+        );
+    }
+}
+
+/// Severity and description of the ports an internet-facing rule opens
+///
+/// Returns None for HTTP/HTTPS-only and ICMP rules, which public services need.
+fn ingress_exposure(from: Option<i64>, to: Option<i64>, protocol: Option<&str>) -> Option<(Severity, String)> {
+    if matches!(protocol, Some("icmp") | Some("icmpv6") | Some("1") | Some("58")) {
+        return None;
+    }
+    let all_protocols = matches!(protocol, Some("-1") | Some("all"));
+    let (from, to) = match (from, to) {
+        (Some(from), Some(to)) => (from, to),
+        (Some(port), None) | (None, Some(port)) => (port, port),
+        (None, None) => (0, 65535),
+    };
+    if all_protocols || (from <= 0 && (to <= 0 || to >= 65535)) {
+        return Some((Severity::Critical, "all ports".to_string()));
+    }
+    if from == to && WEB_PORTS.contains(&from) {
+        return None;
+    }
+
+    let severity = if SENSITIVE_PORTS.iter().any(|port| (from..=to).contains(port)) {
+        Severity::Critical
+    } else {
+        Severity::High
+    };
+    let ports = if from == to { format!("port {}", from) } else { format!("ports {}-{}", from, to) };
+    Some((severity, ports))
+}
+
+/// Parse the blocks of a Terraform file
+///
+/// Understands the subset of HCL the checks need: nested blocks, attributes
+/// (continuing over lines until brackets balance, and heredocs) and comments.
+/// Unrecognized lines are skipped.
+fn parse_terraform(code: &str) -> Vec<TfBlock> {
+    let lines: Vec<&str> = code.lines().collect();
+    // The bottom of the stack collects top-level blocks
+    let mut stack = vec![TfBlock::default()];
+    let mut idx = 0;
+
+    while idx < lines.len() {
+        let start = idx;
+        let raw = lines[idx].trim();
+        idx += 1;
+
+        if raw.starts_with("/*") {
+            while idx < lines.len() && !lines[idx - 1].contains("*/") {
+                idx += 1;
+            }
+            continue;
+        }
+
+        let line = strip_hcl_comment(raw);
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if line == "}" {
+            if stack.len() > 1 {
+                let block = stack.pop().unwrap_or_default();
+                if let Some(parent) = stack.last_mut() {
+                    parent.blocks.push(block);
+                }
+            }
+            continue;
+        }
+
+        if let Some((kind, labels, rest)) = block_header(line) {
+            let mut block = TfBlock { kind, labels, line: start, ..Default::default() };
+            match rest.strip_suffix('}') {
+                // One-line block: `versioning { enabled = true }`
+                Some(inner) => {
+                    if let Some((key, value)) = attribute(inner.trim()) {
+                        block.attributes.push(TfAttribute { key, value, line: start });
+                    }
+                    if let Some(parent) = stack.last_mut() {
+                        parent.blocks.push(block);
+                    }
+                }
+                None => stack.push(block),
+            }
+            continue;
+        }
+
+        if let Some((key, mut value)) = attribute(line) {
+            let heredoc = value.strip_prefix("<<").map(|marker| marker.trim_start_matches('-').trim().to_string());
+            if let Some(marker) = heredoc {
+                while idx < lines.len() && lines[idx].trim() != marker {
+                    value.push('\n');
+                    value.push_str(lines[idx]);
+                    idx += 1;
+                }
+                idx += 1;
+            } else {
+                let mut depth = bracket_depth(&value);
+                while depth > 0 && idx < lines.len() {
+                    let next = strip_hcl_comment(lines[idx]);
+                    value.push('\n');
+                    value.push_str(next.trim());
+                    depth += bracket_depth(&next);
+                    idx += 1;
+                }
+            }
+            if let Some(block) = stack.last_mut() {
+                block.attributes.push(TfAttribute { key, value, line: start });
+            }
+        }
+    }
+
+    // Blocks left open by a truncated file
+    while stack.len() > 1 {
+        let block = stack.pop().unwrap_or_default();
+        if let Some(parent) = stack.last_mut() {
+            parent.blocks.push(block);
+        }
+    }
+    stack.pop().map(|root| root.blocks).unwrap_or_default()
+}
+
+/// Split a block header (`resource "aws_s3_bucket" "logs" {`) into its type,
+/// labels and whatever follows the opening brace
+fn block_header(line: &str) -> Option<(String, Vec<String>, &str)> {
+    let (header, rest) = line.split_once('{')?;
+    if header.contains('=') {
+        return None;
+    }
+    let mut parts = header.split_whitespace();
+    let kind = parts.next()?;
+    if !is_identifier(kind) {
+        return None;
+    }
+    let mut labels = Vec::new();
+    for part in parts {
+        let label = part.trim_matches('"');
+        if label.is_empty() || !(is_identifier(label) || part.starts_with('"')) {
+            return None;
+        }
+        labels.push(label.to_string());
+    }
+    Some((kind.to_string(), labels, rest.trim()))
+}
+
+/// Split an attribute (`key = value`) into its key and value
+fn attribute(line: &str) -> Option<(String, String)> {
+    let (key, value) = line.split_once('=')?;
+    let key = key.trim();
+    if !is_identifier(key) || value.starts_with('=') || value.trim().is_empty() {
+        return None;
+    }
+    Some((key.to_string(), value.trim().to_string()))
+}
+
+fn is_identifier(s: &str) -> bool {
+    s.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Remove a trailing `#` or `//` comment outside of strings
+fn strip_hcl_comment(line: &str) -> String {
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut prev = ' ';
+    for (i, c) in line.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+        } else if c == '"' {
+            in_string = true;
+        } else if c == '#' {
+            return line[..i].to_string();
+        } else if c == '/' && prev == '/' {
+            return line[..i - 1].to_string();
+        }
+        prev = c;
+    }
+    line.to_string()
+}
+
+/// Opening minus closing brackets outside of strings
+fn bracket_depth(text: &str) -> i32 {
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    for c in text.chars() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '{' | '[' | '(' => depth += 1,
+            '}' | ']' | ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    depth
+}
+
+/// Parse a CloudFormation template, or None if `code` isn't one
+fn parse_cloudformation(code: &str) -> Option<Value> {
+    // Cheap pre-check: most YAML and JSON files in a project aren't templates
+    if !code.contains("AWS::") {
+        return None;
+    }
+    let template: Value = serde_yaml::from_str(code).ok()?;
+    template.get("Resources")?.as_mapping()?;
+    Some(template)
+}
+
+/// A template's resources, with the lines declaring them
+fn cfn_resources<'a>(template: &'a Value, lines: &[&str]) -> Vec<CfnResource<'a>> {
+    let Some(resources) = template.get("Resources").and_then(Value::as_mapping) else {
+        return Vec::new();
+    };
+    let section_line = line_of_key(lines, 0, "Resources").unwrap_or(0);
+
+    resources
+        .iter()
+        .filter_map(|(id, resource)| {
+            let id = id.as_str()?;
+            let kind = resource.get("Type").and_then(Value::as_str)?;
+            Some(CfnResource {
+                id,
+                kind,
+                properties: resource.get("Properties"),
+                line: line_of_key(lines, section_line, id).unwrap_or(section_line),
+            })
+        })
+        .collect()
+}
+
+/// First line at or after `from` declaring `key` (YAML `key:` or JSON `"key":`)
+fn line_of_key(lines: &[&str], from: usize, key: &str) -> Option<usize> {
+    (from..lines.len()).find(|&idx| {
+        let line = lines[idx].trim_start().trim_start_matches("- ");
+        let line = line.strip_prefix('"').unwrap_or(line);
+        line.strip_prefix(key)
+            .map(|rest| rest.trim_start_matches('"').trim_start().starts_with(':'))
+            .unwrap_or(false)
+    })
+}
+
+/// A scalar property as text (CloudFormation accepts numbers and booleans as strings)
+fn cfn_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+fn cfn_bool(value: &Value) -> Option<bool> {
+    match cfn_text(value)?.to_ascii_lowercase().as_str() {
+        "true" => Some(true),
+        "false" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn descriptions(violations: &[Violation]) -> Vec<&str> {
+        violations.iter().map(|v| v.description.as_str()).collect()
+    }
+
+    #[test]
+    fn test_terraform_public_s3_bucket() {
+        let code = r#"
+resource "aws_s3_bucket" "assets" {
+  bucket = "acme-assets"
+  acl    = "public-read" # served by the CDN
+}
+
+resource "aws_s3_bucket" "logs" {
+  bucket = "acme-logs"
+  acl    = "private"
+}
+
+resource "aws_s3_bucket_public_access_block" "assets" {
+  bucket              = aws_s3_bucket.assets.id
+  block_public_acls   = true
+  block_public_policy = false
+}
+"#;
+        let violations = IacRule::analyze(code, "infra/s3.tf", 1).unwrap();
+        assert_eq!(violations.len(), 2);
+
+        assert_eq!(violations[0].control_id, "CC6.1");
+        assert_eq!(violations[0].severity, "high");
+        assert_eq!(violations[0].line_number, 4);
+        assert_eq!(violations[0].code_snippet, "acl    = \"public-read\" # served by the CDN");
+        assert_eq!(violations[0].description, "S3 bucket assets uses the public \"public-read\" ACL");
+
+        assert_eq!(violations[1].line_number, 12);
+        assert_eq!(
+            violations[1].description,
+            "S3 public access block assets does not enable block_public_policy, ignore_public_acls, restrict_public_buckets"
+        );
+    }
+
+    #[test]
+    fn test_terraform_security_group_open_to_internet() {
+        let code = r#"
+resource "aws_security_group" "bastion" {
+  name = "bastion"
+
+  ingress {
+    from_port   = 22
+    to_port     = 22
+    protocol    = "tcp"
+    cidr_blocks = ["0.0.0.0/0"]
+  }
+
+  ingress {
+    from_port   = 443
+    to_port     = 443
+    protocol    = "tcp"
+    cidr_blocks = ["0.0.0.0/0"]
+  }
+
+  ingress {
+    from_port   = 8080
+    to_port     = 8081
+    protocol    = "tcp"
+    cidr_blocks = [
+      "10.0.0.0/8",
+      "0.0.0.0/0",
+    ]
+  }
+}
+
+resource "aws_security_group_rule" "internal" {
+  type        = "ingress"
+  from_port   = 0
+  to_port     = 0
+  protocol    = "-1"
+  cidr_blocks = var.office_cidrs
+}
+
+resource "aws_vpc_security_group_ingress_rule" "everything" {
+  security_group_id = aws_security_group.bastion.id
+  cidr_ipv4         = "0.0.0.0/0"
+  ip_protocol       = "-1"
+}
+"#;
+        let violations = IacRule::analyze(code, "network.tf", 1).unwrap();
+        assert_eq!(
+            descriptions(&violations),
+            vec![
+                "Security group bastion allows ingress from the internet on port 22",
+                "Security group bastion allows ingress from the internet on ports 8080-8081",
+                "Security group everything allows ingress from the internet on all ports",
+            ]
+        );
+        assert_eq!(violations[0].severity, "critical");
+        assert_eq!(violations[0].line_number, 9);
+        assert_eq!(violations[1].severity, "high");
+        assert_eq!(violations[1].line_number, 23);
+        assert_eq!(violations[2].severity, "critical");
+    }
+
+    #[test]
+    fn test_terraform_unencrypted_storage_and_trail() {
+        let code = r#"
+resource "aws_db_instance" "main" {
+  engine            = "postgres"
+  storage_encrypted = false
+}
+
+resource "aws_db_instance" "replica" {
+  replicate_source_db = aws_db_instance.main.identifier
+}
+
+resource "aws_ebs_volume" "data" {
+  size = 100
+}
+
+resource "aws_efs_file_system" "shared" {
+  encrypted = var.encrypt_efs
+}
+
+resource "aws_cloudtrail" "audit" {
+  name           = "audit"
+  enable_logging = false
+}
+"#;
+        let violations = IacRule::analyze(code, "main.tf", 1).unwrap();
+        assert_eq!(
+            descriptions(&violations),
+            vec![
+                "aws_db_instance main is not encrypted at rest (storage_encrypted is not enabled)",
+                "aws_ebs_volume data is not encrypted at rest (encrypted is not enabled)",
+                "CloudTrail trail audit has logging disabled",
+            ]
+        );
+        assert_eq!(violations[0].control_id, "CC6.7");
+        assert_eq!(violations[0].line_number, 4);
+        assert_eq!(violations[1].line_number, 11);
+        assert_eq!(violations[2].control_id, "CC7.2");
+    }
+
+    #[test]
+    fn test_cloudformation_yaml_template() {
+        let code = r#"AWSTemplateFormatVersion: "2010-09-09"
+Parameters:
+  Encrypt:
+    Type: String
+Resources:
+  WebsiteBucket:
+    Type: AWS::S3::Bucket
+    Properties:
+      AccessControl: PublicReadWrite
+      BucketName: !Sub "${AWS::StackName}-site"
+  AppSecurityGroup:
+    Type: AWS::EC2::SecurityGroup
+    Properties:
+      GroupDescription: App servers
+      SecurityGroupIngress:
+        - IpProtocol: tcp
+          FromPort: 443
+          ToPort: 443
+          CidrIp: 0.0.0.0/0
+        - IpProtocol: tcp
+          FromPort: "3389"
+          ToPort: "3389"
+          CidrIp: 0.0.0.0/0
+  Database:
+    Type: AWS::RDS::DBInstance
+    Properties:
+      Engine: mysql
+      StorageEncrypted: "false"
+  Volume:
+    Type: AWS::EC2::Volume
+    Properties:
+      Size: 10
+      Encrypted: !Ref Encrypt
+"#;
+        let violations = IacRule::analyze(code, "cloudformation/app.yaml", 1).unwrap();
+        assert_eq!(
+            descriptions(&violations),
+            vec![
+                "S3 bucket WebsiteBucket uses the public \"PublicReadWrite\" ACL",
+                "Security group AppSecurityGroup allows ingress from the internet on port 3389",
+                "AWS::RDS::DBInstance Database is not encrypted at rest (StorageEncrypted is not enabled)",
+            ]
+        );
+        assert_eq!(violations[0].severity, "critical");
+        assert_eq!(violations[0].line_number, 9);
+        assert_eq!(violations[1].line_number, 23);
+        assert_eq!(violations[2].line_number, 28);
+    }
+
+    #[test]
+    fn test_cloudformation_json_and_other_files() {
+        let code = r#"{
+  "Resources": {
+    "Logs": {
+      "Type": "AWS::S3::Bucket",
+      "Properties": {
+        "PublicAccessBlockConfiguration": {
+          "BlockPublicAcls": true,
+          "BlockPublicPolicy": true,
+          "IgnorePublicAcls": true,
+          "RestrictPublicBuckets": false
+        }
+      }
+    }
+  }
+}"#;
+        let violations = IacRule::analyze(code, "template.json", 1).unwrap();
+        assert_eq!(
+            descriptions(&violations),
+            vec!["S3 bucket Logs public access block does not enable RestrictPublicBuckets"]
+        );
+        assert_eq!(violations[0].line_number, 6);
+
+        // Other YAML and JSON files are ignored
+        let compose = "services:\n  db:\n    image: postgres\n    ports: [\"0.0.0.0:5432:5432\"]\n";
+        assert!(IacRule::analyze(compose, "docker-compose.yml", 1).unwrap().is_empty());
+        assert!(IacRule::analyze("{\"name\": \"app\"}", "package.json", 1).unwrap().is_empty());
+        assert!(!IacRule::is_iac_file("app.py"));
+        assert!(IacRule::is_iac_file("modules/vpc/main.tf"));
+    }
+
+    #[test]
+    fn test_detect_missing_cloudtrail() {
+        let provider = "provider \"aws\" {\n  region = \"us-east-1\"\n}\n".to_string();
+        let bucket = "resource \"aws_s3_bucket\" \"logs\" {\n  bucket = \"logs\"\n}\n".to_string();
+        let trail = "resource \"aws_cloudtrail\" \"audit\" {\n  name = \"audit\"\n}\n".to_string();
+
+        let files = vec![("storage.tf".to_string(), bucket.clone()), ("main.tf".to_string(), provider)];
+        let violation = IacRule::detect_missing_cloudtrail(&files, 3).unwrap();
+        assert_eq!(violation.control_id, "CC7.2");
+        assert_eq!(violation.severity, "medium");
+        assert_eq!(violation.file_path, "main.tf");
+        assert_eq!(violation.line_number, 1);
+        assert_eq!(violation.scan_id, 3);
+
+        let mut with_trail = files.clone();
+        with_trail.push(("audit.tf".to_string(), trail));
+        assert!(IacRule::detect_missing_cloudtrail(&with_trail, 3).is_none());
+
+        // No AWS infrastructure, nothing to audit
+        let other = vec![("main.tf".to_string(), "provider \"google\" {}\n".to_string())];
+        assert!(IacRule::detect_missing_cloudtrail(&other, 3).is_none());
+
+        let template = "Resources:\n  Queue:\n    Type: AWS::SQS::Queue\n".to_string();
+        let violation = IacRule::detect_missing_cloudtrail(&[("stack.yml".to_string(), template)], 3).unwrap();
+        assert_eq!(violation.line_number, 2);
+    }
+
+    #[test]
+    fn test_parse_terraform_blocks() {
+        let code = r#"
+/* Shared
+   storage */
+resource "aws_s3_bucket" "docs" {
+  bucket = "docs" // comment
+  policy = <<EOF
+{ "Version": "2012-10-17" }
+EOF
+  versioning { enabled = true }
+  tags = {
+    Name = "docs#1"
+  }
+}
+"#;
+        let blocks = parse_terraform(code);
+        assert_eq!(blocks.len(), 1);
+        let bucket = &blocks[0];
+        assert_eq!(bucket.line, 3);
+        assert_eq!(bucket.resource_type(), "aws_s3_bucket");
+        assert_eq!(bucket.name(), "docs");
+        assert_eq!(bucket.string("bucket"), Some("docs"));
+        assert_eq!(bucket.attributes.iter().map(|a| a.key.as_str()).collect::<Vec<_>>(), vec!["bucket", "policy", "tags"]);
+        assert!(bucket.attribute("tags").unwrap().value.contains("docs#1"));
+        assert_eq!(bucket.blocks[0].kind, "versioning");
+        assert_eq!(bucket.blocks[0].bool("enabled"), Some(true));
+    }
+}
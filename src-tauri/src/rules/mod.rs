@@ -5,8 +5,12 @@ pub mod cc6_1_access_control;
 pub mod cc6_7_secrets;
 pub mod cc7_2_logging;
 pub mod a1_2_resilience;
+pub mod custom;
+pub mod iac;
 
 pub use cc6_1_access_control::CC61AccessControlRule;
 pub use cc6_7_secrets::CC67SecretsRule;
 pub use cc7_2_logging::CC72LoggingRule;
 pub use a1_2_resilience::A12ResilienceRule;
+pub use custom::CustomRuleSet;
+pub use iac::IacRule;
@@ -0,0 +1,275 @@
+//! Automatic re-scan of watched projects
+//!
+//! When enabled, `watch_project` forwards changed paths to [`run`], which
+//! collects them until `debounce_ms` passes without a new change and then
+//! triggers one scan for the whole batch. A [`CircuitBreaker`] stops runaway
+//! loops (e.g. a fix whose write triggers another scan, whose fix triggers
+//! another...) by allowing at most `max_consecutive_scans` scans that each
+//! start within `cooldown_ms` of the previous one finishing.
+//!
+//! The config is stored as a JSON object in the settings table under
+//! [`SETTING_KEY`].
+
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// Settings key holding the auto-rescan config
+pub const SETTING_KEY: &str = "auto_rescan";
+
+/// Auto-rescan settings; missing fields take their defaults
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutoRescanConfig {
+    pub enabled: bool,
+    /// Quiet period after the last change before a scan starts
+    pub debounce_ms: u64,
+    /// Scans allowed back-to-back before the breaker opens
+    pub max_consecutive_scans: u32,
+    /// Gap after a scan that resets the consecutive count
+    pub cooldown_ms: u64,
+}
+
+impl Default for AutoRescanConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            debounce_ms: 2_000,
+            max_consecutive_scans: 3,
+            cooldown_ms: 60_000,
+        }
+    }
+}
+
+impl AutoRescanConfig {
+    /// Parse the stored JSON object (missing or malformed means defaults, i.e. disabled)
+    pub fn parse(value: &str) -> Self {
+        serde_json::from_str(value).unwrap_or_default()
+    }
+
+    pub fn debounce(&self) -> Duration {
+        Duration::from_millis(self.debounce_ms)
+    }
+
+    pub fn cooldown(&self) -> Duration {
+        Duration::from_millis(self.cooldown_ms)
+    }
+}
+
+/// Limits how many auto-rescans can run back-to-back
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    max_consecutive_scans: u32,
+    cooldown: Duration,
+    consecutive_scans: u32,
+    last_scan_finished: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: &AutoRescanConfig) -> Self {
+        Self {
+            max_consecutive_scans: config.max_consecutive_scans,
+            cooldown: config.cooldown(),
+            consecutive_scans: 0,
+            last_scan_finished: None,
+        }
+    }
+
+    /// Whether a scan may start at `now`
+    ///
+    /// The consecutive count resets once `cooldown` has passed since the last scan.
+    pub fn allow(&mut self, now: Instant) -> bool {
+        if let Some(last) = self.last_scan_finished {
+            if now.saturating_duration_since(last) >= self.cooldown {
+                self.consecutive_scans = 0;
+            }
+        }
+        self.consecutive_scans < self.max_consecutive_scans
+    }
+
+    /// Record a scan that finished at `now`
+    pub fn record_scan(&mut self, now: Instant) {
+        self.consecutive_scans += 1;
+        self.last_scan_finished = Some(now);
+    }
+
+    pub fn consecutive_scans(&self) -> u32 {
+        self.consecutive_scans
+    }
+}
+
+/// Debounce changed paths and call `on_rescan` with each settled batch
+///
+/// Runs until `changes` closes (the watcher stopped); changes still pending
+/// at that point are dropped. Batches arriving while the breaker is open are
+/// discarded rather than queued, so a loop can't resume once it cools down.
+pub async fn run<F, Fut>(
+    mut changes: mpsc::UnboundedReceiver<PathBuf>,
+    config: AutoRescanConfig,
+    mut on_rescan: F,
+) where
+    F: FnMut(Vec<PathBuf>) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let mut breaker = CircuitBreaker::new(&config);
+    let mut pending: Vec<PathBuf> = Vec::new();
+
+    loop {
+        if pending.is_empty() {
+            match changes.recv().await {
+                Some(path) => pending.push(path),
+                None => break,
+            }
+            continue;
+        }
+
+        tokio::select! {
+            change = changes.recv() => match change {
+                Some(path) => {
+                    if !pending.contains(&path) {
+                        pending.push(path);
+                    }
+                }
+                None => break,
+            },
+            _ = tokio::time::sleep(config.debounce()) => {
+                let batch = std::mem::take(&mut pending);
+
+                if breaker.allow(Instant::now()) {
+                    on_rescan(batch).await;
+                    breaker.record_scan(Instant::now());
+                } else {
+                    eprintln!(
+                        "[ryn] auto-rescan: skipped {} changes, {} consecutive scans within cooldown",
+                        batch.len(),
+                        breaker.consecutive_scans()
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    fn test_config(max_consecutive_scans: u32, cooldown_ms: u64) -> AutoRescanConfig {
+        AutoRescanConfig {
+            enabled: true,
+            debounce_ms: 50,
+            max_consecutive_scans,
+            cooldown_ms,
+        }
+    }
+
+    /// Batches recorded by `spawn_recorder`, in scan order
+    type Batches = Arc<Mutex<Vec<Vec<PathBuf>>>>;
+
+    /// Spawn `run` recording each batch it scans
+    fn spawn_recorder(
+        config: AutoRescanConfig,
+    ) -> (mpsc::UnboundedSender<PathBuf>, Batches, tokio::task::JoinHandle<()>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let batches = Arc::new(Mutex::new(Vec::new()));
+        let recorded = batches.clone();
+        let task = tokio::spawn(run(rx, config, move |batch| {
+            recorded.lock().unwrap().push(batch);
+            async {}
+        }));
+        (tx, batches, task)
+    }
+
+    #[test]
+    fn test_config_parse_defaults() {
+        assert_eq!(AutoRescanConfig::parse("not json"), AutoRescanConfig::default());
+        assert!(!AutoRescanConfig::default().enabled);
+
+        let config = AutoRescanConfig::parse(r#"{"enabled": true, "debounce_ms": 500}"#);
+        assert!(config.enabled);
+        assert_eq!(config.debounce(), Duration::from_millis(500));
+        assert_eq!(config.max_consecutive_scans, 3);
+    }
+
+    #[tokio::test]
+    async fn test_changes_within_debounce_window_trigger_one_scan() {
+        let (tx, batches, task) = spawn_recorder(test_config(3, 60_000));
+
+        for name in ["a.py", "b.py", "a.py", "c.py"] {
+            tx.send(PathBuf::from(name)).unwrap();
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(batches.lock().unwrap().is_empty(), "scan started before changes settled");
+
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        drop(tx);
+        task.await.unwrap();
+
+        let batches = batches.lock().unwrap();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(
+            batches[0],
+            vec![PathBuf::from("a.py"), PathBuf::from("b.py"), PathBuf::from("c.py")]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_separated_changes_trigger_separate_scans() {
+        let (tx, batches, task) = spawn_recorder(test_config(3, 60_000));
+
+        tx.send(PathBuf::from("a.py")).unwrap();
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        tx.send(PathBuf::from("b.py")).unwrap();
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        drop(tx);
+        task.await.unwrap();
+
+        assert_eq!(batches.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_stops_rescan_loop() {
+        let (tx, batches, task) = spawn_recorder(test_config(2, 60_000));
+
+        // Each change lands after the previous scan, like a fix rewriting a file
+        for name in ["a.py", "b.py", "c.py", "d.py"] {
+            tx.send(PathBuf::from(name)).unwrap();
+            tokio::time::sleep(Duration::from_millis(150)).await;
+        }
+        drop(tx);
+        task.await.unwrap();
+
+        let batches = batches.lock().unwrap();
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[1], vec![PathBuf::from("b.py")]);
+    }
+
+    #[tokio::test]
+    async fn test_pending_changes_dropped_when_watcher_stops() {
+        let (tx, batches, task) = spawn_recorder(test_config(3, 60_000));
+
+        tx.send(PathBuf::from("a.py")).unwrap();
+        drop(tx);
+        task.await.unwrap();
+
+        assert!(batches.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_circuit_breaker_resets_after_cooldown() {
+        let mut breaker = CircuitBreaker::new(&test_config(2, 1_000));
+        let start = Instant::now();
+
+        assert!(breaker.allow(start));
+        breaker.record_scan(start);
+        assert!(breaker.allow(start + Duration::from_millis(100)));
+        breaker.record_scan(start + Duration::from_millis(100));
+
+        assert!(!breaker.allow(start + Duration::from_millis(500)));
+        assert!(breaker.allow(start + Duration::from_millis(1_100)));
+        assert_eq!(breaker.consecutive_scans(), 0);
+    }
+}
@@ -0,0 +1,265 @@
+//! Baseline files for suppressing known violations
+//!
+//! Teams adopting Ryn on a legacy codebase can accept the violations it has
+//! today and only hear about new ones. `create_baseline` writes a scan's open
+//! and accepted-risk violations to `.ryn-baseline.json` in the project root
+//! (meant to be committed), and every later scan drops violations that match
+//! an entry before saving its results.
+//!
+//! An entry matches a violation with the same control_id and file when the
+//! flagged line has the same text (so entries survive code moving around) or
+//! is within ±3 lines of the recorded line. Each entry suppresses at most one
+//! violation, so a second copy of an accepted problem is still reported.
+
+use anyhow::{Context, Result};
+use crate::models::Violation;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Baseline file name, in the project root
+pub const BASELINE_FILE_NAME: &str = ".ryn-baseline.json";
+
+/// Current `.ryn-baseline.json` format
+pub const BASELINE_VERSION: u32 = 1;
+
+/// How far a violation may drift from an entry's line and still match it
+const LINE_TOLERANCE: i64 = 3;
+
+/// An accepted violation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub control_id: String,
+    /// Relative to the project root
+    pub file_path: String,
+    pub line_number: i64,
+    pub severity: String,
+    pub description: String,
+    /// SHA-256 of the flagged line with surrounding whitespace trimmed
+    /// (empty if the file couldn't be read)
+    pub line_hash: String,
+}
+
+/// Contents of `.ryn-baseline.json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub version: u32,
+    /// When the baseline was written (RFC 3339)
+    pub created_at: String,
+    /// Scan the entries were taken from
+    pub scan_id: i64,
+    pub entries: Vec<BaselineEntry>,
+}
+
+impl Baseline {
+    /// Baseline accepting `violations`, hashing their lines from the files under `project_root`
+    pub fn from_violations(violations: &[Violation], scan_id: i64, project_root: &Path) -> Self {
+        let mut lines = FileLines::new(project_root);
+        let mut entries: Vec<BaselineEntry> = violations
+            .iter()
+            .map(|v| BaselineEntry {
+                control_id: v.control_id.clone(),
+                file_path: v.file_path.clone(),
+                line_number: v.line_number,
+                severity: v.severity.clone(),
+                description: v.description.clone(),
+                line_hash: lines.hash(&v.file_path, v.line_number).unwrap_or_default(),
+            })
+            .collect();
+        // Stable order keeps diffs of the committed file small
+        entries.sort_by(|a, b| {
+            (&a.file_path, a.line_number, &a.control_id).cmp(&(&b.file_path, b.line_number, &b.control_id))
+        });
+
+        Self {
+            version: BASELINE_VERSION,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            scan_id,
+            entries,
+        }
+    }
+
+    /// Path of the baseline file for a project
+    pub fn path(project_root: &Path) -> PathBuf {
+        project_root.join(BASELINE_FILE_NAME)
+    }
+
+    /// Read the project's baseline, or None if it has none
+    pub fn load(project_root: &Path) -> Result<Option<Self>> {
+        let path = Self::path(project_root);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let baseline = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        Ok(Some(baseline))
+    }
+
+    /// Write the baseline to the project root, replacing any existing one
+    pub fn write(&self, project_root: &Path) -> Result<PathBuf> {
+        let path = Self::path(project_root);
+        let json = serde_json::to_string_pretty(self).context("Failed to serialize baseline")?;
+        std::fs::write(&path, json + "\n").with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+
+    /// Split `violations` into those to report and those the baseline suppresses
+    ///
+    /// Returns: (kept, suppressed)
+    pub fn partition(&self, violations: Vec<Violation>, project_root: &Path) -> (Vec<Violation>, Vec<Violation>) {
+        let mut lines = FileLines::new(project_root);
+        let mut entry_used = vec![false; self.entries.len()];
+        let mut kept = Vec::new();
+        let mut suppressed = Vec::new();
+
+        for violation in violations {
+            let (used, v) = (&entry_used, &violation);
+            let candidates = || {
+                self.entries.iter().enumerate().filter(move |(idx, entry)| {
+                    !used[*idx] && entry.control_id == v.control_id && entry.file_path == v.file_path
+                })
+            };
+
+            // Prefer an entry for the same line text, then the nearest entry in range
+            let line_hash = lines.hash(&violation.file_path, violation.line_number);
+            let matched = candidates()
+                .find(|(_, entry)| !entry.line_hash.is_empty() && Some(&entry.line_hash) == line_hash.as_ref())
+                .or_else(|| {
+                    candidates()
+                        .filter(|(_, entry)| (entry.line_number - violation.line_number).abs() <= LINE_TOLERANCE)
+                        .min_by_key(|(_, entry)| (entry.line_number - violation.line_number).abs())
+                })
+                .map(|(idx, _)| idx);
+
+            match matched {
+                Some(idx) => {
+                    entry_used[idx] = true;
+                    suppressed.push(violation);
+                }
+                None => kept.push(violation),
+            }
+        }
+
+        (kept, suppressed)
+    }
+}
+
+/// Lines of project files, read once each
+struct FileLines<'a> {
+    project_root: &'a Path,
+    files: HashMap<String, Option<Vec<String>>>,
+}
+
+impl<'a> FileLines<'a> {
+    fn new(project_root: &'a Path) -> Self {
+        Self {
+            project_root,
+            files: HashMap::new(),
+        }
+    }
+
+    /// Hash of line `line_number` (1-based) of `file_path`, if it exists
+    fn hash(&mut self, file_path: &str, line_number: i64) -> Option<String> {
+        let project_root = self.project_root;
+        let lines = self.files.entry(file_path.to_string()).or_insert_with(|| {
+            std::fs::read_to_string(project_root.join(file_path))
+                .ok()
+                .map(|content| content.lines().map(str::to_string).collect())
+        });
+        let line = lines.as_ref()?.get(usize::try_from(line_number).ok()?.checked_sub(1)?)?;
+        Some(hex::encode(Sha256::digest(line.trim().as_bytes())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Severity;
+    use std::fs;
+
+    fn violation(control_id: &str, file_path: &str, line: i64) -> Violation {
+        Violation::new(
+            1,
+            control_id.to_string(),
+            Severity::Critical,
+            "Hardcoded secret".to_string(),
+            file_path.to_string(),
+            line,
+            String::new(),
+        )
+    }
+
+    fn project(files: &[(&str, &str)]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, content) in files {
+            fs::write(dir.path().join(name), content).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_write_and_load_round_trip() {
+        let dir = project(&[("app.py", "import os\nKEY = 'sk_live_abc'\n")]);
+        let baseline = Baseline::from_violations(&[violation("CC6.7", "app.py", 2)], 7, dir.path());
+
+        let path = baseline.write(dir.path()).unwrap();
+        assert!(path.ends_with(BASELINE_FILE_NAME));
+        let loaded = Baseline::load(dir.path()).unwrap().unwrap();
+        assert_eq!(loaded, baseline);
+        assert_eq!(loaded.scan_id, 7);
+        assert_eq!(loaded.entries[0].line_hash.len(), 64);
+    }
+
+    #[test]
+    fn test_load_missing_and_invalid() {
+        let dir = project(&[]);
+        assert!(Baseline::load(dir.path()).unwrap().is_none());
+
+        fs::write(dir.path().join(BASELINE_FILE_NAME), "{ not json").unwrap();
+        assert!(Baseline::load(dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_partition_suppresses_accepted_violations() {
+        let dir = project(&[("app.py", "KEY = 'a'\nTOKEN = 'b'\n")]);
+        let baseline = Baseline::from_violations(&[violation("CC6.7", "app.py", 1)], 1, dir.path());
+
+        let (kept, suppressed) = baseline.partition(
+            vec![violation("CC6.7", "app.py", 1), violation("CC6.7", "app.py", 2), violation("CC7.2", "app.py", 1)],
+            dir.path(),
+        );
+        assert_eq!(suppressed.len(), 1);
+        assert_eq!(suppressed[0].line_number, 1);
+        // Each entry suppresses one violation; other controls aren't covered
+        assert_eq!(kept.len(), 2);
+    }
+
+    #[test]
+    fn test_partition_follows_moved_lines() {
+        let dir = project(&[("app.py", "KEY = 'a'\n")]);
+        let baseline = Baseline::from_violations(&[violation("CC6.7", "app.py", 1)], 1, dir.path());
+
+        // Twenty lines added above the accepted line
+        fs::write(dir.path().join("app.py"), format!("{}KEY = 'a'\n", "# header\n".repeat(20))).unwrap();
+        let (kept, suppressed) = baseline.partition(vec![violation("CC6.7", "app.py", 21)], dir.path());
+        assert!(kept.is_empty());
+        assert_eq!(suppressed.len(), 1);
+    }
+
+    #[test]
+    fn test_partition_tolerates_small_line_drift() {
+        let dir = project(&[("app.py", "KEY = 'a'\n")]);
+        let baseline = Baseline::from_violations(&[violation("CC6.7", "app.py", 1)], 1, dir.path());
+
+        // Line text changed, but still within ±3 lines
+        fs::write(dir.path().join("app.py"), "import os\n\nKEY = 'rotated'\n").unwrap();
+        let (kept, _) = baseline.partition(vec![violation("CC6.7", "app.py", 3)], dir.path());
+        assert!(kept.is_empty());
+
+        let (kept, _) = baseline.partition(vec![violation("CC6.7", "app.py", 9)], dir.path());
+        assert_eq!(kept.len(), 1);
+    }
+}
@@ -20,9 +20,12 @@ pub const SKIP_DIRECTORIES: &[&str] = &[
     // Version control
     ".git",
 
-    // Python virtual environments and caches
+    // Python virtual environments, packaging and caches
     "venv",
     ".venv",
+    "env",
+    "virtualenv",
+    ".eggs",
     "__pycache__",
     ".pytest_cache",
     ".tox",
@@ -42,6 +45,17 @@ pub const SKIP_DIRECTORIES: &[&str] = &[
     "coverage",
 ];
 
+/// Glob patterns for directory names to skip, matched against each path component
+///
+/// For names that vary per package, e.g. `mypkg.egg-info`.
+pub const SKIP_GLOB_PATTERNS: &[&str] = &["*.egg-info"];
+
+/// Files that mark a directory as a Python virtual environment root
+///
+/// Virtual environments can have any name, so a directory containing one of
+/// these is skipped along with everything under it.
+pub const VIRTUAL_ENV_MARKERS: &[&str] = &["pyvenv.cfg", "bin/activate", "Scripts/activate"];
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -58,4 +72,12 @@ mod tests {
         assert!(SKIP_DIRECTORIES.contains(&"target"));
         assert!(SKIP_DIRECTORIES.contains(&"venv"));
     }
+
+    #[test]
+    fn test_skip_directories_contains_dependency_dirs() {
+        for dir in [".venv", "venv", "env", "virtualenv", "vendor", ".cargo", "target", "dist", "build", ".eggs"] {
+            assert!(SKIP_DIRECTORIES.contains(&dir), "{} not skipped", dir);
+        }
+        assert!(SKIP_GLOB_PATTERNS.contains(&"*.egg-info"));
+    }
 }
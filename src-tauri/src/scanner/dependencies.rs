@@ -0,0 +1,784 @@
+//! Known-vulnerable dependencies (software composition analysis)
+//!
+//! Reads the project's dependency files (`package.json`, `poetry.lock`,
+//! `Cargo.lock` and `go.sum`, at any depth) and looks every package up in the
+//! OSV database (<https://osv.dev>) with `querybatch` requests. Each advisory
+//! found is then fetched to grade it: a vulnerable package becomes a CC7.1
+//! violation on the line declaring it, with the severity of its worst
+//! advisory's CVSS v3 base score. Like the `go.mod` check in [`go_modules`],
+//! the lookup is turned off by the [`OSV_CHECK_SETTING`] setting and network
+//! failures never fail the scan.
+//!
+//! `package.json` ranges are checked at their lower bound (`^4.17.1` as
+//! 4.17.1); lockfiles give the exact versions installed.
+//!
+//! [`go_modules`]: super::go_modules
+//! [`OSV_CHECK_SETTING`]: super::go_modules::OSV_CHECK_SETTING
+
+use anyhow::{Context, Result};
+use crate::models::{Severity, Violation};
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::time::Duration;
+
+/// OSV API base URL (`/querybatch` and `/vulns/{id}`)
+pub const OSV_API_URL: &str = "https://api.osv.dev/v1";
+
+/// SOC 2 control vulnerable dependencies are filed under
+pub const DEPENDENCY_CONTROL_ID: &str = "CC7.1";
+
+/// File names read for dependencies
+pub const DEPENDENCY_FILES: [&str; 4] = ["package.json", "poetry.lock", "Cargo.lock", "go.sum"];
+
+/// Most queries OSV accepts in one `querybatch` request
+const OSV_BATCH_SIZE: usize = 1000;
+
+/// Package ecosystems, named as OSV names them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ecosystem {
+    Npm,
+    PyPI,
+    CratesIo,
+    Go,
+}
+
+impl Ecosystem {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Ecosystem::Npm => "npm",
+            Ecosystem::PyPI => "PyPI",
+            Ecosystem::CratesIo => "crates.io",
+            Ecosystem::Go => "Go",
+        }
+    }
+}
+
+/// A package version declared by a dependency file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dependency {
+    pub ecosystem: Ecosystem,
+    pub name: String,
+    /// Version as written, e.g. "v0.7.0" in `go.sum`
+    pub version: String,
+    /// Dependency file declaring the package (violation path)
+    pub file_path: String,
+    /// 1-based line declaring the package
+    pub line_number: i64,
+}
+
+impl Dependency {
+    /// Version as OSV stores it (Go versions without the leading `v`)
+    pub fn query_version(&self) -> &str {
+        match self.ecosystem {
+            Ecosystem::Go => self.version.trim_start_matches('v'),
+            _ => &self.version,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvBatchResponse {
+    #[serde(default)]
+    results: Vec<OsvBatchResult>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct OsvBatchResult {
+    #[serde(default)]
+    vulns: Vec<OsvVulnRef>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvVulnRef {
+    id: String,
+}
+
+/// An OSV advisory, as returned by `GET /vulns/{id}`
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct Advisory {
+    pub id: String,
+    /// Other IDs of the same vulnerability (CVE, GHSA, ...)
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    #[serde(default)]
+    pub severity: Vec<AdvisorySeverity>,
+    /// Database-specific fields; GitHub advisories carry a `severity` rating
+    #[serde(default)]
+    pub database_specific: Option<Value>,
+}
+
+/// A severity score attached to an advisory
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct AdvisorySeverity {
+    /// Scoring system, e.g. "CVSS_V3"
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Score in the system's notation (a vector string for CVSS)
+    pub score: String,
+}
+
+impl Advisory {
+    /// The CVE alias if the advisory has one, otherwise its OSV ID
+    pub fn display_id(&self) -> &str {
+        self.aliases
+            .iter()
+            .find(|alias| alias.starts_with("CVE-"))
+            .unwrap_or(&self.id)
+    }
+
+    /// Highest CVSS v3 base score among the advisory's severity entries
+    pub fn cvss_score(&self) -> Option<f64> {
+        self.severity
+            .iter()
+            .filter(|s| s.kind == "CVSS_V3")
+            .filter_map(|s| cvss_v3_base_score(&s.score))
+            .reduce(f64::max)
+    }
+
+    /// Severity from the CVSS score, else the database's own rating, else medium
+    pub fn severity(&self) -> Severity {
+        if let Some(score) = self.cvss_score() {
+            return severity_from_cvss(score);
+        }
+        let rating = self
+            .database_specific
+            .as_ref()
+            .and_then(|db| db.get("severity"))
+            .and_then(Value::as_str)
+            .unwrap_or("")
+            .to_ascii_uppercase();
+        match rating.as_str() {
+            "CRITICAL" => Severity::Critical,
+            "HIGH" => Severity::High,
+            "LOW" => Severity::Low,
+            _ => Severity::Medium,
+        }
+    }
+}
+
+/// Severity for a CVSS base score, using the CVSS qualitative rating bands
+pub fn severity_from_cvss(score: f64) -> Severity {
+    if score >= 9.0 {
+        Severity::Critical
+    } else if score >= 7.0 {
+        Severity::High
+    } else if score >= 4.0 {
+        Severity::Medium
+    } else {
+        Severity::Low
+    }
+}
+
+/// Base score of a CVSS v3.0/v3.1 vector, e.g. "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"
+///
+/// Returns None for other versions or vectors missing a base metric.
+pub fn cvss_v3_base_score(vector: &str) -> Option<f64> {
+    let mut parts = vector.split('/');
+    if !matches!(parts.next(), Some("CVSS:3.0") | Some("CVSS:3.1")) {
+        return None;
+    }
+    let metrics: HashMap<&str, &str> = parts.filter_map(|part| part.split_once(':')).collect();
+    let metric = |name: &str| metrics.get(name).copied();
+
+    let scope_changed = match metric("S")? {
+        "U" => false,
+        "C" => true,
+        _ => return None,
+    };
+    let attack_vector: f64 = match metric("AV")? {
+        "N" => 0.85,
+        "A" => 0.62,
+        "L" => 0.55,
+        "P" => 0.2,
+        _ => return None,
+    };
+    let attack_complexity = match metric("AC")? {
+        "L" => 0.77,
+        "H" => 0.44,
+        _ => return None,
+    };
+    let privileges_required = match (metric("PR")?, scope_changed) {
+        ("N", _) => 0.85,
+        ("L", false) => 0.62,
+        ("L", true) => 0.68,
+        ("H", false) => 0.27,
+        ("H", true) => 0.5,
+        _ => return None,
+    };
+    let user_interaction = match metric("UI")? {
+        "N" => 0.85,
+        "R" => 0.62,
+        _ => return None,
+    };
+    let impact_weight = |name: &str| match metric(name)? {
+        "H" => Some(0.56_f64),
+        "L" => Some(0.22),
+        "N" => Some(0.0),
+        _ => None,
+    };
+    let (confidentiality, integrity, availability) = (impact_weight("C")?, impact_weight("I")?, impact_weight("A")?);
+
+    let base_impact = 1.0 - (1.0 - confidentiality) * (1.0 - integrity) * (1.0 - availability);
+    let impact = if scope_changed {
+        7.52 * (base_impact - 0.029) - 3.25 * (base_impact - 0.02).powi(15)
+    } else {
+        6.42 * base_impact
+    };
+    if impact <= 0.0 {
+        return Some(0.0);
+    }
+    let exploitability = 8.22 * attack_vector * attack_complexity * privileges_required * user_interaction;
+    let score = if scope_changed {
+        (1.08 * (impact + exploitability)).min(10.0)
+    } else {
+        (impact + exploitability).min(10.0)
+    };
+    Some(round_up(score))
+}
+
+/// CVSS v3.1 "Roundup": smallest one-decimal number not below `value`
+fn round_up(value: f64) -> f64 {
+    let scaled = (value * 100_000.0).round() as i64;
+    if scaled % 10_000 == 0 {
+        scaled as f64 / 100_000.0
+    } else {
+        (scaled / 10_000 + 1) as f64 / 10.0
+    }
+}
+
+/// Whether `file_path` is a dependency file this module reads
+pub fn is_dependency_file(file_path: &str) -> bool {
+    Path::new(file_path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| DEPENDENCY_FILES.contains(&name))
+}
+
+/// Parse the dependencies declared by a dependency file
+///
+/// Unparseable files and other file names have no dependencies.
+pub fn parse_dependency_file(file_path: &str, content: &str) -> Vec<Dependency> {
+    let name = Path::new(file_path).file_name().and_then(|n| n.to_str()).unwrap_or("");
+    match name {
+        "package.json" => parse_package_json(file_path, content),
+        "poetry.lock" => parse_lock_packages(file_path, content, Ecosystem::PyPI),
+        "Cargo.lock" => parse_lock_packages(file_path, content, Ecosystem::CratesIo),
+        "go.sum" => parse_go_sum(file_path, content),
+        _ => Vec::new(),
+    }
+}
+
+/// `dependencies`, `devDependencies` and `optionalDependencies` of a `package.json`
+fn parse_package_json(file_path: &str, content: &str) -> Vec<Dependency> {
+    let Ok(manifest) = serde_json::from_str::<Value>(content) else {
+        return Vec::new();
+    };
+    let lines: Vec<&str> = content.lines().collect();
+    let key_line = |from: usize, key: &str| {
+        let quoted = format!("\"{}\"", key);
+        (from..lines.len()).find(|&idx| lines[idx].trim_start().starts_with(&quoted))
+    };
+
+    let mut dependencies = Vec::new();
+    for section in ["dependencies", "devDependencies", "optionalDependencies"] {
+        let Some(packages) = manifest.get(section).and_then(Value::as_object) else {
+            continue;
+        };
+        let section_line = key_line(0, section).unwrap_or(0);
+        for (name, spec) in packages {
+            let Some(version) = spec.as_str().and_then(npm_version) else {
+                continue;
+            };
+            dependencies.push(Dependency {
+                ecosystem: Ecosystem::Npm,
+                name: name.clone(),
+                version,
+                file_path: file_path.to_string(),
+                line_number: key_line(section_line, name).map_or(1, |idx| idx + 1) as i64,
+            });
+        }
+    }
+    dependencies
+}
+
+/// Version an npm range is pinned at or starts from (`^4.17` → "4.17.0")
+///
+/// None for tags, URLs, `workspace:`/`file:` specs, wildcards and compound ranges.
+fn npm_version(spec: &str) -> Option<String> {
+    let version = spec
+        .trim()
+        .trim_start_matches(['^', '~', '=', '>', 'v'])
+        .trim();
+    if version.contains(char::is_whitespace) {
+        return None;
+    }
+    let (core, suffix) = version.split_at(version.find(['-', '+']).unwrap_or(version.len()));
+    let parts: Vec<&str> = core.split('.').collect();
+    if parts.len() > 3 || !parts.iter().all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit())) {
+        return None;
+    }
+
+    let mut padded = parts.join(".");
+    for _ in parts.len()..3 {
+        padded.push_str(".0");
+    }
+    Some(format!("{}{}", padded, suffix))
+}
+
+/// `[[package]]` entries of a `poetry.lock` or `Cargo.lock`
+///
+/// Packages from git, path or URL sources aren't in OSV and are skipped, as
+/// are a Cargo workspace's own crates.
+fn parse_lock_packages(file_path: &str, content: &str, ecosystem: Ecosystem) -> Vec<Dependency> {
+    let Ok(lock) = toml::from_str::<toml::Table>(content) else {
+        return Vec::new();
+    };
+    let Some(packages) = lock.get("package").and_then(|p| p.as_array()) else {
+        return Vec::new();
+    };
+    let lines: Vec<&str> = content.lines().collect();
+
+    let mut dependencies = Vec::new();
+    let mut search_from = 0;
+    for package in packages {
+        let field = |key: &str| package.get(key).and_then(|v| v.as_str());
+        let (Some(name), Some(version)) = (field("name"), field("version")) else {
+            continue;
+        };
+        let from_registry = match ecosystem {
+            Ecosystem::CratesIo => field("source").is_some_and(|source| source.starts_with("registry+")),
+            // Poetry marks non-PyPI sources with a [package.source] table; "legacy" is a package index
+            _ => !matches!(
+                package.get("source").and_then(|source| source.get("type")).and_then(|kind| kind.as_str()),
+                Some(kind) if kind != "legacy"
+            ),
+        };
+
+        let declaration = format!("name = \"{}\"", name);
+        let line = (search_from..lines.len()).find(|&idx| lines[idx].trim() == declaration);
+        if let Some(idx) = line {
+            search_from = idx + 1;
+        }
+        if !from_registry {
+            continue;
+        }
+        dependencies.push(Dependency {
+            ecosystem,
+            name: name.to_string(),
+            version: version.to_string(),
+            file_path: file_path.to_string(),
+            line_number: line.map_or(1, |idx| idx + 1) as i64,
+        });
+    }
+    dependencies
+}
+
+/// Module versions in a `go.sum` (entries for `go.mod` files only are skipped)
+fn parse_go_sum(file_path: &str, content: &str) -> Vec<Dependency> {
+    let mut seen = HashSet::new();
+    let mut dependencies = Vec::new();
+
+    for (idx, line) in content.lines().enumerate() {
+        let mut parts = line.split_whitespace();
+        let (Some(module), Some(version)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        if version.ends_with("/go.mod") || !seen.insert((module, version)) {
+            continue;
+        }
+        dependencies.push(Dependency {
+            ecosystem: Ecosystem::Go,
+            name: module.to_string(),
+            version: version.to_string(),
+            file_path: file_path.to_string(),
+            line_number: (idx + 1) as i64,
+        });
+    }
+    dependencies
+}
+
+/// Body of an OSV `querybatch` request for `dependencies`, in order
+pub fn build_osv_query(dependencies: &[Dependency]) -> Value {
+    let queries: Vec<Value> = dependencies
+        .iter()
+        .map(|dep| {
+            json!({
+                "package": { "name": dep.name, "ecosystem": dep.ecosystem.as_str() },
+                "version": dep.query_version(),
+            })
+        })
+        .collect();
+
+    json!({ "queries": queries })
+}
+
+/// A CC7.1 violation for a dependency with known advisories
+///
+/// The severity is the worst among the advisories; each is listed once by
+/// its CVE ID when it has one.
+pub fn dependency_violation(dependency: &Dependency, advisories: &[&Advisory], scan_id: i64) -> Violation {
+    let severity = advisories
+        .iter()
+        .map(|advisory| advisory.severity())
+        .max_by_key(|severity| severity.numeric_value())
+        .unwrap_or(Severity::Medium);
+
+    let mut ids: Vec<&str> = Vec::new();
+    for advisory in advisories {
+        let id = advisory.display_id();
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+
+    Violation::new(
+        scan_id,
+        DEPENDENCY_CONTROL_ID.to_string(),
+        severity,
+        format!(
+            "{} dependency {} {} has known vulnerabilities: {}",
+            dependency.ecosystem.as_str(),
+            dependency.name,
+            dependency.version,
+            ids.join(", ")
+        ),
+        dependency.file_path.clone(),
+        dependency.line_number,
+        format!("{} {}", dependency.name, dependency.version),
+    )
+}
+
+/// Look up the dependencies declared by `files` ((path, contents)) in OSV at `api_url`
+///
+/// Returns no violations (without a request) when the files declare no
+/// dependencies. An advisory that can't be fetched is graded medium.
+///
+/// # Errors
+/// Returns error if a `querybatch` request fails or its response can't be parsed
+pub async fn check_dependencies(files: &[(String, String)], api_url: &str, scan_id: i64) -> Result<Vec<Violation>> {
+    let dependencies: Vec<Dependency> = files
+        .iter()
+        .flat_map(|(path, content)| parse_dependency_file(path, content))
+        .collect();
+    if dependencies.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let client = Client::builder()
+        .timeout(Duration::from_secs(15))
+        .build()
+        .context("Failed to build OSV HTTP client")?;
+
+    // OSV answers with one result per query, in query order
+    let mut vuln_ids: Vec<Vec<String>> = Vec::with_capacity(dependencies.len());
+    for chunk in dependencies.chunks(OSV_BATCH_SIZE) {
+        let response = client
+            .post(format!("{}/querybatch", api_url))
+            .json(&build_osv_query(chunk))
+            .send()
+            .await
+            .context("OSV request failed")?
+            .error_for_status()
+            .context("OSV returned an error status")?
+            .text()
+            .await
+            .context("Failed to read OSV response")?;
+        let parsed: OsvBatchResponse =
+            serde_json::from_str(&response).context("Failed to parse OSV response")?;
+
+        let mut results = parsed.results.into_iter();
+        for _ in chunk {
+            let ids = results.next().unwrap_or_default().vulns.into_iter().map(|v| v.id).collect();
+            vuln_ids.push(ids);
+        }
+    }
+
+    let mut advisories: HashMap<&str, Advisory> = HashMap::new();
+    for id in vuln_ids.iter().flatten() {
+        if !advisories.contains_key(id.as_str()) {
+            let advisory = fetch_advisory(&client, api_url, id).await;
+            advisories.insert(id.as_str(), advisory);
+        }
+    }
+
+    let violations = dependencies
+        .iter()
+        .zip(&vuln_ids)
+        .filter(|(_, ids)| !ids.is_empty())
+        .map(|(dependency, ids)| {
+            let found: Vec<&Advisory> = ids.iter().filter_map(|id| advisories.get(id.as_str())).collect();
+            dependency_violation(dependency, &found, scan_id)
+        })
+        .collect();
+    Ok(violations)
+}
+
+/// Fetch an advisory, falling back to one with only its ID
+async fn fetch_advisory(client: &Client, api_url: &str, id: &str) -> Advisory {
+    let fetched = async {
+        client
+            .get(format!("{}/vulns/{}", api_url, id))
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Advisory>()
+            .await
+    }
+    .await;
+
+    fetched.unwrap_or_else(|e| {
+        eprintln!("[ryn] Failed to fetch OSV advisory {}: {}", id, e);
+        Advisory { id: id.to_string(), ..Default::default() }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    const PACKAGE_JSON: &str = r#"{
+  "name": "shop",
+  "dependencies": {
+    "express": "^4.17",
+    "lodash": "4.17.20",
+    "internal-ui": "workspace:*"
+  },
+  "devDependencies": {
+    "jest": "~29.1.0-beta.1",
+    "eslint": ">=8.0.0 <9",
+    "typescript": "latest"
+  }
+}
+"#;
+
+    const CARGO_LOCK: &str = r#"version = 3
+
+[[package]]
+name = "shop"
+version = "0.1.0"
+dependencies = ["smallvec"]
+
+[[package]]
+name = "smallvec"
+version = "1.6.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "abc"
+
+[[package]]
+name = "forked"
+version = "0.2.0"
+source = "git+https://github.com/acme/forked#deadbeef"
+"#;
+
+    /// OSV server answering `querybatch` with `batch` and `GET /vulns/{id}`
+    /// with the matching advisory (404 for IDs not in `advisories`)
+    async fn mock_osv(batch: &'static str, advisories: Vec<(&'static str, &'static str)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/v1", listener.local_addr().unwrap());
+
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut raw = Vec::new();
+                let mut buf = [0u8; 4096];
+                loop {
+                    let n = socket.read(&mut buf).await.unwrap();
+                    if n == 0 {
+                        break;
+                    }
+                    raw.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&raw);
+                    if let Some(split) = text.find("\r\n\r\n") {
+                        let content_length = text[..split]
+                            .lines()
+                            .find_map(|l| {
+                                l.to_lowercase()
+                                    .strip_prefix("content-length:")
+                                    .map(|v| v.trim().parse::<usize>().unwrap())
+                            })
+                            .unwrap_or(0);
+                        if raw.len() >= split + 4 + content_length {
+                            break;
+                        }
+                    }
+                }
+                let request = String::from_utf8_lossy(&raw).to_string();
+                let path = request.split_whitespace().nth(1).unwrap_or("").to_string();
+
+                let (status, body) = if path.ends_with("/querybatch") {
+                    (200, batch.to_string())
+                } else {
+                    let id = path.rsplit('/').next().unwrap_or("");
+                    match advisories.iter().find(|(advisory_id, _)| *advisory_id == id) {
+                        Some((_, advisory)) => (200, advisory.to_string()),
+                        None => (404, "{}".to_string()),
+                    }
+                };
+                let response = format!(
+                    "HTTP/1.1 {} Status\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        url
+    }
+
+    #[test]
+    fn test_parse_package_json() {
+        let deps = parse_dependency_file("web/package.json", PACKAGE_JSON);
+
+        let found: Vec<_> = deps.iter().map(|d| (d.name.as_str(), d.version.as_str(), d.line_number)).collect();
+        assert_eq!(
+            found,
+            vec![("express", "4.17.0", 4), ("lodash", "4.17.20", 5), ("jest", "29.1.0-beta.1", 9)]
+        );
+        assert!(deps.iter().all(|d| d.ecosystem == Ecosystem::Npm && d.file_path == "web/package.json"));
+        assert!(parse_dependency_file("package.json", "not json").is_empty());
+    }
+
+    #[test]
+    fn test_parse_lockfiles() {
+        let crates = parse_dependency_file("Cargo.lock", CARGO_LOCK);
+        assert_eq!(
+            crates,
+            vec![Dependency {
+                ecosystem: Ecosystem::CratesIo,
+                name: "smallvec".to_string(),
+                version: "1.6.0".to_string(),
+                file_path: "Cargo.lock".to_string(),
+                line_number: 9,
+            }]
+        );
+
+        let poetry = r#"[[package]]
+name = "django"
+version = "3.2.0"
+
+[[package]]
+name = "internal-lib"
+version = "1.0.0"
+
+[package.source]
+type = "git"
+url = "https://github.com/acme/internal-lib.git"
+"#;
+        let packages = parse_dependency_file("api/poetry.lock", poetry);
+        assert_eq!(packages.len(), 1);
+        assert_eq!((packages[0].name.as_str(), packages[0].line_number), ("django", 2));
+        assert_eq!(packages[0].ecosystem, Ecosystem::PyPI);
+    }
+
+    #[test]
+    fn test_parse_go_sum() {
+        let go_sum = "golang.org/x/net v0.7.0 h1:abc=\ngolang.org/x/net v0.7.0/go.mod h1:def=\ngithub.com/gorilla/mux v1.8.0/go.mod h1:ghi=\ngolang.org/x/net v0.7.0 h1:abc=\n";
+
+        let deps = parse_dependency_file("go.sum", go_sum);
+
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].version, "v0.7.0");
+        assert_eq!(deps[0].query_version(), "0.7.0");
+        assert_eq!(deps[0].line_number, 1);
+
+        let query = build_osv_query(&deps);
+        assert_eq!(query["queries"][0]["package"]["ecosystem"], "Go");
+        assert_eq!(query["queries"][0]["version"], "0.7.0");
+    }
+
+    #[test]
+    fn test_is_dependency_file() {
+        assert!(is_dependency_file("package.json"));
+        assert!(is_dependency_file("services/api/poetry.lock"));
+        assert!(!is_dependency_file("package-lock.json"));
+        assert!(!is_dependency_file("src/Cargo.lock.bak"));
+    }
+
+    #[test]
+    fn test_cvss_v3_base_score() {
+        assert_eq!(cvss_v3_base_score("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"), Some(9.8));
+        assert_eq!(cvss_v3_base_score("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H"), Some(10.0));
+        assert_eq!(cvss_v3_base_score("CVSS:3.0/AV:N/AC:L/PR:N/UI:R/S:C/C:L/I:L/A:N"), Some(6.1));
+        assert_eq!(cvss_v3_base_score("CVSS:3.1/AV:N/AC:H/PR:L/UI:R/S:U/C:L/I:N/A:N"), Some(2.6));
+        assert_eq!(cvss_v3_base_score("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:N/I:N/A:N"), Some(0.0));
+        assert_eq!(cvss_v3_base_score("CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N"), None);
+        assert_eq!(cvss_v3_base_score("CVSS:3.1/AV:N/AC:L"), None);
+
+        assert_eq!(severity_from_cvss(9.8), Severity::Critical);
+        assert_eq!(severity_from_cvss(7.0), Severity::High);
+        assert_eq!(severity_from_cvss(6.1), Severity::Medium);
+        assert_eq!(severity_from_cvss(2.6), Severity::Low);
+    }
+
+    #[test]
+    fn test_advisory_severity_and_violation() {
+        let scored: Advisory = serde_json::from_str(
+            r#"{"id": "GHSA-jf85-cpcp-j695", "aliases": ["CVE-2019-10744"],
+                "severity": [{"type": "CVSS_V3", "score": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:N/I:H/A:H"}]}"#,
+        )
+        .unwrap();
+        let rated: Advisory = serde_json::from_str(
+            r#"{"id": "GHSA-p6mc-m468-83gw", "database_specific": {"severity": "MODERATE"}}"#,
+        )
+        .unwrap();
+        assert_eq!(scored.display_id(), "CVE-2019-10744");
+        assert_eq!(scored.severity(), Severity::Critical);
+        assert_eq!(rated.display_id(), "GHSA-p6mc-m468-83gw");
+        assert_eq!(rated.severity(), Severity::Medium);
+        assert_eq!(Advisory { id: "OSV-1".to_string(), ..Default::default() }.severity(), Severity::Medium);
+
+        let deps = parse_dependency_file("package.json", PACKAGE_JSON);
+        let violation = dependency_violation(&deps[1], &[&rated, &scored, &scored], 5);
+        assert_eq!(violation.control_id, DEPENDENCY_CONTROL_ID);
+        assert_eq!(violation.severity, "critical");
+        assert_eq!(violation.line_number, 5);
+        assert_eq!(violation.code_snippet, "lodash 4.17.20");
+        assert_eq!(
+            violation.description,
+            "npm dependency lodash 4.17.20 has known vulnerabilities: GHSA-p6mc-m468-83gw, CVE-2019-10744"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_dependencies_against_osv() {
+        let batch = r#"{"results": [{}, {"vulns": [{"id": "GHSA-1"}, {"id": "GHSA-2"}]}, {}, {"vulns": [{"id": "GHSA-2"}]}]}"#;
+        let url = mock_osv(
+            batch,
+            vec![(
+                "GHSA-1",
+                r#"{"id": "GHSA-1", "aliases": ["CVE-2021-23337"], "severity": [{"type": "CVSS_V3", "score": "CVSS:3.1/AV:N/AC:L/PR:H/UI:N/S:U/C:H/I:H/A:H"}]}"#,
+            )],
+        )
+        .await;
+        let files = vec![
+            ("package.json".to_string(), PACKAGE_JSON.to_string()),
+            ("Cargo.lock".to_string(), CARGO_LOCK.to_string()),
+            ("README.md".to_string(), "# Shop".to_string()),
+        ];
+
+        let violations = check_dependencies(&files, &url, 9).await.unwrap();
+
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].file_path, "package.json");
+        assert_eq!(violations[0].severity, "high");
+        assert!(violations[0].description.ends_with("CVE-2021-23337, GHSA-2"));
+        // GHSA-2 couldn't be fetched, so it's graded medium
+        assert_eq!(violations[1].file_path, "Cargo.lock");
+        assert_eq!(violations[1].severity, "medium");
+        assert_eq!(violations[1].scan_id, 9);
+    }
+
+    #[tokio::test]
+    async fn test_check_dependencies_without_dependencies_skips_request() {
+        // The URL is never contacted when there is nothing to look up
+        let files = vec![("package.json".to_string(), "{\"name\": \"empty\"}".to_string())];
+        let violations = check_dependencies(&files, "http://127.0.0.1:9/v1", 1).await.unwrap();
+        assert!(violations.is_empty());
+    }
+}
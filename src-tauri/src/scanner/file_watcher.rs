@@ -1,14 +1,37 @@
 //! File watching module
 //!
 //! Monitors project files for changes in real-time using the notify crate.
+//! Each path in a notify event is checked against the ignore patterns,
+//! extension whitelist and glob exclusions before it reaches the
+//! [`WatcherHandle`] channel; [`WatcherHandle::watch_stats`] reports how many
+//! paths were received, filtered out, coalesced and emitted.
+//!
+//! Paths that pass the filters are coalesced: the first event for a path opens
+//! a `coalesce_window_ms` window (default [`COALESCE_WINDOW_MS`]), later events
+//! for the path replace it, and only the final one is emitted when the window
+//! closes. Five saves of a file in quick succession reach the handle as one
+//! event; a new file and the writes that fill it reach it as a creation.
 
 use super::SKIP_DIRECTORIES;
+use super::framework_detector::FRAMEWORK_CACHE;
+use super::scan_excludes;
 use anyhow::{anyhow, Result};
+use globset::GlobSet;
 use notify::{RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::task::JoinHandle;
 
+/// Default window in which successive events for a path are merged into one
+pub const COALESCE_WINDOW_MS: u64 = 200;
+
+/// Shortest interval between checks for closed coalescing windows
+const MIN_COALESCE_TICK: Duration = Duration::from_millis(10);
+
 /// File event types emitted by the watcher
 #[derive(Debug, Clone)]
 pub enum FileEvent {
@@ -20,12 +43,64 @@ pub enum FileEvent {
     FileDeleted { path: PathBuf },
 }
 
+impl FileEvent {
+    /// Path the event is about
+    pub fn path(&self) -> &Path {
+        match self {
+            FileEvent::FileModified { path }
+            | FileEvent::FileCreated { path }
+            | FileEvent::FileDeleted { path } => path,
+        }
+    }
+}
+
+/// Event counts for a running watcher
+///
+/// Counts are per path: a notify event touching two files counts twice.
+/// Once pending windows have closed, `events_received` equals
+/// `events_filtered + events_coalesced + events_emitted`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatcherStats {
+    pub events_received: u64,
+    /// Paths dropped by event kind, ignore patterns, extension or exclusion globs
+    pub events_filtered: u64,
+    /// Events replaced by a later event for the same path within the coalescing window
+    #[serde(default)]
+    pub events_coalesced: u64,
+    /// Paths sent to the handle's channel
+    pub events_emitted: u64,
+}
+
+/// Counters shared between the notify callback and the handle
+#[derive(Debug, Default)]
+struct WatcherCounters {
+    received: AtomicU64,
+    filtered: AtomicU64,
+    coalesced: AtomicU64,
+    emitted: AtomicU64,
+}
+
+impl WatcherCounters {
+    fn snapshot(&self) -> WatcherStats {
+        WatcherStats {
+            events_received: self.received.load(Ordering::Relaxed),
+            events_filtered: self.filtered.load(Ordering::Relaxed),
+            events_coalesced: self.coalesced.load(Ordering::Relaxed),
+            events_emitted: self.emitted.load(Ordering::Relaxed),
+        }
+    }
+}
+
 /// Handle to manage the file watcher lifecycle
 pub struct WatcherHandle {
     rx: async_channel::Receiver<FileEvent>,
     shutdown_tx: async_channel::Sender<()>,
+    counters: Arc<WatcherCounters>,
     #[allow(dead_code)] // Kept alive to prevent thread from being dropped prematurely
     watcher_handle: JoinHandle<()>,
+    /// Task merging filtered events before they reach `rx`; ends when the watcher thread does
+    #[allow(dead_code)]
+    coalesce_handle: JoinHandle<()>,
 }
 
 impl WatcherHandle {
@@ -34,6 +109,11 @@ impl WatcherHandle {
         self.rx.recv().await.ok()
     }
 
+    /// Event counts since the watcher started
+    pub fn watch_stats(&self) -> WatcherStats {
+        self.counters.snapshot()
+    }
+
     /// Signal the watcher to shut down gracefully
     pub fn shutdown(&self) {
         let _ = self.shutdown_tx.send_blocking(());
@@ -47,10 +127,13 @@ impl Drop for WatcherHandle {
     }
 }
 
-/// File system watcher with ignore patterns and extension filtering
+/// File system watcher with ignore patterns, extension filtering, glob exclusions
+/// and per-path event coalescing
 pub struct FileWatcher {
     ignore_patterns: Vec<String>,
     extensions: Vec<String>,
+    excluded_paths: Vec<String>,
+    coalesce_window_ms: u64,
 }
 
 impl FileWatcher {
@@ -71,6 +154,23 @@ impl FileWatcher {
         self
     }
 
+    /// Set glob patterns (e.g. `tests/**`, `*.min.js`) for paths to ignore
+    ///
+    /// Patterns are matched against paths relative to the watched directory,
+    /// like scan exclusion patterns; they are compiled in `watch_directory`.
+    pub fn with_excluded_paths(mut self, patterns: Vec<String>) -> Self {
+        self.excluded_paths = patterns;
+        self
+    }
+
+    /// Set the window in which successive events for a path are merged
+    ///
+    /// 0 disables coalescing: every event that passes the filters is emitted.
+    pub fn with_coalesce_window_ms(mut self, window_ms: u64) -> Self {
+        self.coalesce_window_ms = window_ms;
+        self
+    }
+
     /// Start watching a directory for file changes
     ///
     /// # Arguments
@@ -78,109 +178,96 @@ impl FileWatcher {
     ///
     /// # Returns
     /// * `Ok(WatcherHandle)` containing a channel receiver for events
-    /// * `Err(...)` if watcher setup fails
+    /// * `Err(...)` if an exclusion pattern is invalid or watcher setup fails
     pub async fn watch_directory(self, path: &Path) -> Result<WatcherHandle> {
         if !path.exists() {
             return Err(anyhow!("Watch path does not exist: {:?}", path));
         }
 
+        let exclude_set = scan_excludes::build_exclude_set(&self.excluded_paths)?;
+        let filter = Arc::new(PathFilter {
+            roots: watch_roots(path),
+            ignore_patterns: self.ignore_patterns,
+            extensions: self.extensions,
+            exclude_set,
+        });
+
         let path_buf = path.to_path_buf();
-        let ignore_patterns = self.ignore_patterns.clone();
-        let extensions = self.extensions.clone();
+        let counters = Arc::new(WatcherCounters::default());
 
+        // The notify callback feeds filtered events to the coalescing task, which
+        // forwards them to the handle's channel
+        let (raw_tx, raw_rx) = async_channel::unbounded::<FileEvent>();
         let (tx, rx) = async_channel::unbounded::<FileEvent>();
         let (shutdown_tx, shutdown_rx) = async_channel::unbounded::<()>();
 
+        let coalesce_handle = tokio::spawn(coalesce_events(
+            raw_rx,
+            tx,
+            Duration::from_millis(self.coalesce_window_ms),
+            counters.clone(),
+        ));
+
         // Spawn blocking task for file watching
+        let callback_counters = counters.clone();
         let watcher_handle = tokio::task::spawn_blocking(move || {
-            let ignore_patterns = Arc::new(ignore_patterns);
-            let extensions = Arc::new(extensions);
-
-            let tx_clone = tx.clone();
-            let ignore_patterns_clone = ignore_patterns.clone();
-            let extensions_clone = extensions.clone();
-
             let result = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
                 match res {
                     Ok(event) => {
-                        println!("[FileWatcher] Received notify event: kind={:?}, paths={:?}", event.kind, event.paths);
+                        eprintln!("[FileWatcher] Received notify event: kind={:?}, paths={:?}", event.kind, event.paths);
                         use notify::EventKind;
-                        match event.kind {
-                            EventKind::Modify(_) => {
-                                for path in &event.paths {
-                                    let should_watch = Self::should_watch_path(
-                                        path,
-                                        &ignore_patterns_clone,
-                                        &extensions_clone,
-                                    );
-                                    println!("[FileWatcher] Modify event for {:?}, should_watch={}", path, should_watch);
-                                    if should_watch {
-                                        let _ =
-                                            tx_clone.send_blocking(FileEvent::FileModified {
-                                                path: path.clone(),
-                                            });
-                                    }
-                                }
+
+                        // Framework indicator edits (package.json, requirements.txt, ...) make
+                        // cached detection stale, regardless of the filters below
+                        if matches!(event.kind, EventKind::Modify(_) | EventKind::Create(_) | EventKind::Remove(_)) {
+                            for path in &event.paths {
+                                FRAMEWORK_CACHE.invalidate_for_file(path);
                             }
-                            EventKind::Create(_) => {
-                                for path in &event.paths {
-                                    let should_watch = Self::should_watch_path(
-                                        path,
-                                        &ignore_patterns_clone,
-                                        &extensions_clone,
-                                    );
-                                    println!("[FileWatcher] Create event for {:?}, should_watch={}", path, should_watch);
-                                    if should_watch {
-                                        let _ = tx_clone.send_blocking(FileEvent::FileCreated {
-                                            path: path.clone(),
-                                        });
-                                    }
+                        } else {
+                            eprintln!("[FileWatcher] Ignoring event kind: {:?}", event.kind);
+                        }
+
+                        for path in &event.paths {
+                            callback_counters.received.fetch_add(1, Ordering::Relaxed);
+
+                            let file_event = to_file_event(&event.kind, path.clone())
+                                .filter(|_| filter.should_watch(path));
+                            eprintln!("[FileWatcher] {:?} event for {:?}, should_watch={}", event.kind, path, file_event.is_some());
+
+                            match file_event {
+                                Some(file_event) => {
+                                    let _ = raw_tx.send_blocking(file_event);
                                 }
-                            }
-                            EventKind::Remove(_) => {
-                                for path in &event.paths {
-                                    let should_watch = Self::should_watch_path(
-                                        path,
-                                        &ignore_patterns_clone,
-                                        &extensions_clone,
-                                    );
-                                    println!("[FileWatcher] Remove event for {:?}, should_watch={}", path, should_watch);
-                                    if should_watch {
-                                        let _ = tx_clone.send_blocking(FileEvent::FileDeleted {
-                                            path: path.clone(),
-                                        });
-                                    }
+                                None => {
+                                    callback_counters.filtered.fetch_add(1, Ordering::Relaxed);
                                 }
                             }
-                            _ => {
-                                println!("[FileWatcher] Ignoring event kind: {:?}", event.kind);
-                            }
                         }
                     }
-                    Err(e) => println!("[FileWatcher] Watch error: {}", e),
+                    Err(e) => eprintln!("[FileWatcher] Watch error: {}", e),
                 }
             });
 
             match result {
                 Ok(mut watcher) => {
-                    println!("[FileWatcher] Successfully created watcher, watching {:?}", path_buf);
+                    eprintln!("[FileWatcher] Successfully created watcher, watching {:?}", path_buf);
                     if let Err(e) = watcher.watch(&path_buf, RecursiveMode::Recursive) {
-                        println!("[FileWatcher] FAILED to watch directory: {}", e);
+                        eprintln!("[FileWatcher] FAILED to watch directory: {}", e);
                         return;
                     }
-                    println!("[FileWatcher] Now monitoring {:?} for changes", path_buf);
+                    eprintln!("[FileWatcher] Now monitoring {:?} for changes", path_buf);
 
                     // Keep watcher alive until shutdown signal
                     loop {
                         if shutdown_rx.try_recv().is_ok() {
-                            println!("[FileWatcher] Received shutdown signal");
+                            eprintln!("[FileWatcher] Received shutdown signal");
                             break;
                         }
                         std::thread::sleep(std::time::Duration::from_millis(100));
                     }
                 }
                 Err(e) => {
-                    println!("[FileWatcher] FAILED to create watcher: {}", e);
+                    eprintln!("[FileWatcher] FAILED to create watcher: {}", e);
                 }
             }
         });
@@ -188,7 +275,9 @@ impl FileWatcher {
         Ok(WatcherHandle {
             rx,
             shutdown_tx,
+            counters,
             watcher_handle,
+            coalesce_handle,
         })
     }
 
@@ -197,13 +286,14 @@ impl FileWatcher {
         ignore_patterns: &[String],
         extensions: &[String],
     ) -> bool {
-        let path_str = path.to_string_lossy();
-
-        // Check if in ignore list
-        for pattern in ignore_patterns {
-            if path_str.contains(pattern) {
-                return false;
-            }
+        // Check if any directory or file name is in the ignore list (whole
+        // names only, so "env" doesn't ignore environment.py)
+        let ignored = path.components().any(|component| match component {
+            std::path::Component::Normal(name) => ignore_patterns.iter().any(|pattern| name == pattern.as_str()),
+            _ => false,
+        });
+        if ignored {
+            return false;
         }
 
         // Check extension
@@ -217,6 +307,166 @@ impl FileWatcher {
     }
 }
 
+/// Map a notify event kind to the event emitted for one of its paths
+///
+/// Returns None for kinds the watcher doesn't report (access, metadata-only "other", ...).
+fn to_file_event(kind: &notify::EventKind, path: PathBuf) -> Option<FileEvent> {
+    use notify::EventKind;
+
+    match kind {
+        EventKind::Modify(_) => Some(FileEvent::FileModified { path }),
+        EventKind::Create(_) => Some(FileEvent::FileCreated { path }),
+        EventKind::Remove(_) => Some(FileEvent::FileDeleted { path }),
+        _ => None,
+    }
+}
+
+/// Merges successive events for a path
+///
+/// The first event for a path opens a window; later events for the path
+/// replace it, and when the window closes only the latest is released.
+struct EventCoalescer {
+    window: Duration,
+    /// Latest event for each path with an open window
+    pending: HashMap<PathBuf, FileEvent>,
+    /// Paths whose window closes at each instant
+    deadlines: BTreeMap<Instant, Vec<PathBuf>>,
+}
+
+impl EventCoalescer {
+    fn new(window: Duration) -> Self {
+        Self {
+            window,
+            pending: HashMap::new(),
+            deadlines: BTreeMap::new(),
+        }
+    }
+
+    /// Buffer an event received at `now`
+    ///
+    /// Returns true if it merged into a pending event for the same path. Writes
+    /// to a file created in the same window leave it a creation.
+    fn push(&mut self, event: FileEvent, now: Instant) -> bool {
+        let path = event.path().to_path_buf();
+        match self.pending.get_mut(&path) {
+            Some(FileEvent::FileCreated { .. }) if matches!(event, FileEvent::FileModified { .. }) => true,
+            Some(pending) => {
+                *pending = event;
+                true
+            }
+            None => {
+                self.pending.insert(path.clone(), event);
+                self.deadlines.entry(now + self.window).or_default().push(path);
+                false
+            }
+        }
+    }
+
+    /// Remove the events whose window closed before `now`, oldest window first
+    fn drain_expired(&mut self, now: Instant) -> Vec<FileEvent> {
+        let still_open = self.deadlines.split_off(&now);
+        let expired = std::mem::replace(&mut self.deadlines, still_open);
+        self.take(expired)
+    }
+
+    /// Remove every pending event, oldest window first
+    fn drain_all(&mut self) -> Vec<FileEvent> {
+        let all = std::mem::take(&mut self.deadlines);
+        self.take(all)
+    }
+
+    fn take(&mut self, deadlines: BTreeMap<Instant, Vec<PathBuf>>) -> Vec<FileEvent> {
+        deadlines
+            .into_values()
+            .flatten()
+            .filter_map(|path| self.pending.remove(&path))
+            .collect()
+    }
+}
+
+/// Forward events from the notify callback to the handle's channel, coalescing
+/// them per path within `window`
+///
+/// Runs until the callback's sender is dropped (the watcher thread exited),
+/// then flushes anything still pending.
+async fn coalesce_events(
+    raw_rx: async_channel::Receiver<FileEvent>,
+    tx: async_channel::Sender<FileEvent>,
+    window: Duration,
+    counters: Arc<WatcherCounters>,
+) {
+    if window.is_zero() {
+        while let Ok(event) = raw_rx.recv().await {
+            counters.emitted.fetch_add(1, Ordering::Relaxed);
+            let _ = tx.send(event).await;
+        }
+        return;
+    }
+
+    let mut coalescer = EventCoalescer::new(window);
+    let mut ticker = tokio::time::interval((window / 4).max(MIN_COALESCE_TICK));
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        let ready = tokio::select! {
+            received = raw_rx.recv() => match received {
+                Ok(event) => {
+                    if coalescer.push(event, Instant::now()) {
+                        counters.coalesced.fetch_add(1, Ordering::Relaxed);
+                    }
+                    continue;
+                }
+                Err(_) => break,
+            },
+            _ = ticker.tick() => coalescer.drain_expired(Instant::now()),
+        };
+
+        for event in ready {
+            counters.emitted.fetch_add(1, Ordering::Relaxed);
+            let _ = tx.send(event).await;
+        }
+    }
+
+    for event in coalescer.drain_all() {
+        counters.emitted.fetch_add(1, Ordering::Relaxed);
+        let _ = tx.send(event).await;
+    }
+}
+
+/// Filters applied to each path of a notify event
+struct PathFilter {
+    /// Watched directory as given and canonicalized (macOS reports canonical paths)
+    roots: Vec<PathBuf>,
+    ignore_patterns: Vec<String>,
+    extensions: Vec<String>,
+    exclude_set: GlobSet,
+}
+
+impl PathFilter {
+    fn should_watch(&self, path: &Path) -> bool {
+        FileWatcher::should_watch_path(path, &self.ignore_patterns, &self.extensions)
+            && !self.is_excluded(path)
+    }
+
+    fn is_excluded(&self, path: &Path) -> bool {
+        self.roots
+            .iter()
+            .find_map(|root| path.strip_prefix(root).ok())
+            .is_some_and(|relative| scan_excludes::is_excluded(&self.exclude_set, relative))
+    }
+}
+
+/// Prefixes to strip from event paths to get watch-relative paths
+fn watch_roots(path: &Path) -> Vec<PathBuf> {
+    let mut roots = vec![path.to_path_buf()];
+    if let Ok(canonical) = path.canonicalize() {
+        if canonical != path {
+            roots.push(canonical);
+        }
+    }
+    roots
+}
+
 impl Default for FileWatcher {
     fn default() -> Self {
         Self {
@@ -228,6 +478,8 @@ impl Default for FileWatcher {
                 "ts".to_string(),
                 "tsx".to_string(),
             ],
+            excluded_paths: Vec::new(),
+            coalesce_window_ms: COALESCE_WINDOW_MS,
         }
     }
 }
@@ -247,6 +499,7 @@ mod tests {
         assert_eq!(watcher.extensions.len(), 5);
         assert!(watcher.ignore_patterns.contains(&".git".to_string()));
         assert!(watcher.extensions.contains(&"py".to_string()));
+        assert_eq!(watcher.coalesce_window_ms, COALESCE_WINDOW_MS);
     }
 
     #[test]
@@ -258,6 +511,16 @@ mod tests {
         assert!(!FileWatcher::should_watch_path(Path::new("app.txt"), &patterns, &extensions));
     }
 
+    #[test]
+    fn test_ignore_patterns_match_whole_names() {
+        let patterns = vec!["env".to_string(), "out".to_string()];
+        let extensions = vec!["py".to_string(), "tsx".to_string()];
+
+        assert!(!FileWatcher::should_watch_path(Path::new("/project/env/lib/site.py"), &patterns, &extensions));
+        assert!(FileWatcher::should_watch_path(Path::new("/project/src/environment.py"), &patterns, &extensions));
+        assert!(FileWatcher::should_watch_path(Path::new("/project/app/layout.tsx"), &patterns, &extensions));
+    }
+
     #[test]
     fn test_should_ignore_patterns() {
         let patterns = vec![".git".to_string(), "node_modules".to_string()];
@@ -316,4 +579,246 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    fn path_filter(root: &str, extensions: &[&str], excluded: &[&str]) -> PathFilter {
+        let excluded: Vec<String> = excluded.iter().map(|p| p.to_string()).collect();
+        PathFilter {
+            roots: vec![PathBuf::from(root)],
+            ignore_patterns: vec!["node_modules".to_string()],
+            extensions: extensions.iter().map(|e| e.to_string()).collect(),
+            exclude_set: scan_excludes::build_exclude_set(&excluded).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_path_filter_applies_extensions_and_globs() {
+        let filter = path_filter("/project", &["py", "js"], &["tests/**", "*.min.js"]);
+
+        assert!(filter.should_watch(Path::new("/project/app/views.py")));
+        assert!(filter.should_watch(Path::new("/project/static_site/app.js")));
+        assert!(!filter.should_watch(Path::new("/project/README.md")));
+        assert!(!filter.should_watch(Path::new("/project/tests/test_views.py")));
+        assert!(!filter.should_watch(Path::new("/project/dist/bundle.min.js")));
+        assert!(!filter.should_watch(Path::new("/project/node_modules/lib/index.js")));
+    }
+
+    #[test]
+    fn test_path_filter_globs_are_relative_to_watch_root() {
+        let filter = path_filter("/home/dev/tests", &["py"], &["tests/**"]);
+
+        // The root itself lives under "tests", but only paths below it are matched
+        assert!(filter.should_watch(Path::new("/home/dev/tests/app.py")));
+        assert!(!filter.should_watch(Path::new("/home/dev/tests/tests/test_app.py")));
+    }
+
+    #[test]
+    fn test_to_file_event_ignores_access_events() {
+        use notify::event::{AccessKind, CreateKind, ModifyKind};
+        use notify::EventKind;
+
+        let path = PathBuf::from("app.py");
+        assert!(matches!(
+            to_file_event(&EventKind::Create(CreateKind::File), path.clone()),
+            Some(FileEvent::FileCreated { .. })
+        ));
+        assert!(matches!(
+            to_file_event(&EventKind::Modify(ModifyKind::Any), path.clone()),
+            Some(FileEvent::FileModified { .. })
+        ));
+        assert!(to_file_event(&EventKind::Access(AccessKind::Any), path).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_excluded_path_pattern_fails_watch() {
+        let temp_dir = setup_temp_project();
+        let watcher = FileWatcher::new().with_excluded_paths(vec!["src/[".to_string()]);
+
+        assert!(watcher.watch_directory(temp_dir.path()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_watch_stats_count_filtered_and_emitted_events() {
+        let temp_dir = setup_temp_project();
+        std::fs::create_dir(temp_dir.path().join("generated")).unwrap();
+
+        let handle = FileWatcher::new()
+            .with_extensions(vec!["py".to_string()])
+            .with_excluded_paths(vec!["generated/**".to_string()])
+            .watch_directory(temp_dir.path())
+            .await
+            .unwrap();
+        assert_eq!(handle.watch_stats(), WatcherStats::default());
+
+        // Let the watcher thread register before writing
+        tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+        std::fs::write(temp_dir.path().join("app.py"), "print('hello')").unwrap();
+        std::fs::write(temp_dir.path().join("notes.txt"), "notes").unwrap();
+        std::fs::write(temp_dir.path().join("generated/models.py"), "x = 1").unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+
+        let stats = handle.watch_stats();
+        assert!(stats.events_emitted >= 1, "app.py should be emitted: {:?}", stats);
+        assert!(stats.events_filtered >= 2, "txt and excluded files should be filtered: {:?}", stats);
+        assert_eq!(
+            stats.events_received,
+            stats.events_filtered + stats.events_coalesced + stats.events_emitted
+        );
+
+        // Only app.py made it through the filters
+        for _ in 0..stats.events_emitted {
+            let event = handle.recv().await.unwrap();
+            let (FileEvent::FileModified { path } | FileEvent::FileCreated { path } | FileEvent::FileDeleted { path }) = event;
+            assert!(path.ends_with("app.py"), "unexpected event for {:?}", path);
+        }
+    }
+
+    fn modified(path: &str) -> FileEvent {
+        FileEvent::FileModified { path: PathBuf::from(path) }
+    }
+
+    #[test]
+    fn test_coalescer_merges_rapid_saves() {
+        let window = Duration::from_millis(200);
+        let mut coalescer = EventCoalescer::new(window);
+        let start = Instant::now();
+
+        let replaced: Vec<bool> = (0..5)
+            .map(|i| coalescer.push(modified("app.py"), start + Duration::from_millis(i * 20)))
+            .collect();
+        assert_eq!(replaced, vec![false, true, true, true, true]);
+
+        // Nothing is released while the window is open
+        assert!(coalescer.drain_expired(start + Duration::from_millis(150)).is_empty());
+
+        let emitted = coalescer.drain_expired(start + window + Duration::from_millis(1));
+        assert_eq!(emitted.len(), 1);
+        assert_eq!(emitted[0].path(), Path::new("app.py"));
+        assert!(coalescer.drain_all().is_empty());
+    }
+
+    #[test]
+    fn test_coalescer_keeps_final_event_per_path() {
+        let window = Duration::from_millis(200);
+        let mut coalescer = EventCoalescer::new(window);
+        let start = Instant::now();
+
+        coalescer.push(FileEvent::FileCreated { path: PathBuf::from("new.py") }, start);
+        coalescer.push(modified("app.py"), start + Duration::from_millis(10));
+        coalescer.push(FileEvent::FileDeleted { path: PathBuf::from("new.py") }, start + Duration::from_millis(20));
+
+        let emitted = coalescer.drain_expired(start + Duration::from_millis(300));
+        assert_eq!(emitted.len(), 2);
+        assert!(matches!(&emitted[0], FileEvent::FileDeleted { path } if path.ends_with("new.py")));
+        assert!(matches!(&emitted[1], FileEvent::FileModified { path } if path.ends_with("app.py")));
+    }
+
+    #[test]
+    fn test_coalescer_keeps_creation_through_writes() {
+        let window = Duration::from_millis(200);
+        let mut coalescer = EventCoalescer::new(window);
+        let start = Instant::now();
+
+        coalescer.push(FileEvent::FileCreated { path: PathBuf::from("new.py") }, start);
+        assert!(coalescer.push(modified("new.py"), start + Duration::from_millis(5)));
+        assert!(coalescer.push(modified("new.py"), start + Duration::from_millis(10)));
+
+        let emitted = coalescer.drain_expired(start + Duration::from_millis(300));
+        assert_eq!(emitted.len(), 1);
+        assert!(matches!(&emitted[0], FileEvent::FileCreated { path } if path.ends_with("new.py")));
+    }
+
+    #[test]
+    fn test_coalescer_opens_new_window_after_emit() {
+        let window = Duration::from_millis(200);
+        let mut coalescer = EventCoalescer::new(window);
+        let start = Instant::now();
+
+        coalescer.push(modified("app.py"), start);
+        assert_eq!(coalescer.drain_expired(start + Duration::from_millis(250)).len(), 1);
+
+        // A save after the window closed is a new change, not a duplicate
+        assert!(!coalescer.push(modified("app.py"), start + Duration::from_millis(300)));
+        assert!(coalescer.drain_expired(start + Duration::from_millis(400)).is_empty());
+        assert_eq!(coalescer.drain_expired(start + Duration::from_millis(550)).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_coalesce_events_emits_one_event_for_five_saves() {
+        let (raw_tx, raw_rx) = async_channel::unbounded();
+        let (tx, rx) = async_channel::unbounded();
+        let counters = Arc::new(WatcherCounters::default());
+        let task = tokio::spawn(coalesce_events(raw_rx, tx, Duration::from_millis(200), counters.clone()));
+
+        for _ in 0..5 {
+            raw_tx.send(modified("app.py")).await.unwrap();
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        tokio::time::sleep(Duration::from_millis(400)).await;
+
+        let event = rx.try_recv().unwrap();
+        assert_eq!(event.path(), Path::new("app.py"));
+        assert!(rx.try_recv().is_err(), "rapid saves should produce a single event");
+
+        let stats = counters.snapshot();
+        assert_eq!((stats.events_coalesced, stats.events_emitted), (4, 1));
+
+        drop(raw_tx);
+        task.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_coalesce_events_flushes_pending_on_close() {
+        let (raw_tx, raw_rx) = async_channel::unbounded();
+        let (tx, rx) = async_channel::unbounded();
+        let counters = Arc::new(WatcherCounters::default());
+
+        raw_tx.send(modified("app.py")).await.unwrap();
+        drop(raw_tx);
+        coalesce_events(raw_rx, tx, Duration::from_secs(60), counters).await;
+
+        assert_eq!(rx.try_recv().unwrap().path(), Path::new("app.py"));
+    }
+
+    #[tokio::test]
+    async fn test_zero_window_disables_coalescing() {
+        let (raw_tx, raw_rx) = async_channel::unbounded();
+        let (tx, rx) = async_channel::unbounded();
+        let counters = Arc::new(WatcherCounters::default());
+
+        for _ in 0..3 {
+            raw_tx.send(modified("app.py")).await.unwrap();
+        }
+        drop(raw_tx);
+        coalesce_events(raw_rx, tx, Duration::ZERO, counters.clone()).await;
+
+        assert_eq!(rx.len(), 3);
+        assert_eq!(counters.snapshot().events_coalesced, 0);
+    }
+
+    #[tokio::test]
+    async fn test_watcher_coalesces_rapid_saves() {
+        let temp_dir = setup_temp_project();
+        let file = temp_dir.path().join("app.py");
+        std::fs::write(&file, "x = 0").unwrap();
+
+        let handle = FileWatcher::new()
+            .with_extensions(vec!["py".to_string()])
+            .with_coalesce_window_ms(500)
+            .watch_directory(temp_dir.path())
+            .await
+            .unwrap();
+
+        // Let the watcher thread register before writing
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        for i in 1..=5 {
+            std::fs::write(&file, format!("x = {}", i)).unwrap();
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        tokio::time::sleep(Duration::from_millis(1_000)).await;
+
+        let stats = handle.watch_stats();
+        assert_eq!(stats.events_emitted, 1, "five saves should emit one event: {:?}", stats);
+        assert!(stats.events_coalesced >= 4, "{:?}", stats);
+        assert!(handle.recv().await.unwrap().path().ends_with("app.py"));
+    }
 }
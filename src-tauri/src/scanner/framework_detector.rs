@@ -1,15 +1,223 @@
 //! Framework detection module
 //!
 //! Detects the web framework of a project by analyzing:
+//! - Lock files (Pipfile.lock, poetry.lock, requirements.txt, package-lock.json,
+//!   yarn.lock, Gemfile.lock), which outweigh the other signals
 //! - File names and patterns (manage.py, package.json, etc.)
 //! - Package manager files (requirements.txt, package.json)
 //! - Source code imports and patterns
 //!
-//! Supports: Django, Flask, Express, Next.js, React
+//! Supports: Django, Flask, FastAPI, aiohttp, Express, Next.js, React, Rails,
+//! Sinatra, Actix-web and plain Rust (Cargo)
+//!
+//! Results are cached per project in [`FRAMEWORK_CACHE`], keyed on the mtimes of
+//! framework indicator files so edits to e.g. `package.json` are picked up.
+//!
+//! Also detects monorepo packages ([`FrameworkDetector::detect_monorepo`]) from
+//! pnpm-workspace.yaml, package.json `workspaces` (Yarn/npm), Cargo
+//! `[workspace] members` and go.work, so violations can be grouped per package.
 
+use super::SKIP_DIRECTORIES;
 use anyhow::{anyhow, Context, Result};
+use dashmap::DashMap;
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use once_cell::sync::Lazy;
 use walkdir::WalkDir;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Files whose contents or presence decide the detected framework
+pub const FRAMEWORK_INDICATOR_FILES: &[&str] = &[
+    "package.json",
+    "requirements.txt",
+    "pom.xml",
+    "build.gradle",
+    "pyproject.toml",
+    "Pipfile",
+    "Pipfile.lock",
+    "poetry.lock",
+    "package-lock.json",
+    "yarn.lock",
+    "Gemfile.lock",
+    "go.mod",
+    "Cargo.toml",
+    "Gemfile",
+    "composer.json",
+    "manage.py",
+    "settings.py",
+    "app.py",
+    "routes.py",
+];
+
+/// Confidence of a framework found in a lock file or pinned requirements
+pub const LOCK_FILE_CONFIDENCE: f64 = 0.9;
+
+/// Confidence of a framework inferred from a single indicator file
+/// (manage.py, app.py, package.json, Cargo.toml)
+pub const INDICATOR_FILE_CONFIDENCE: f64 = 0.6;
+
+/// Frameworks in detection priority order, which breaks confidence ties
+const FRAMEWORK_PRIORITY: &[&str] = &[
+    "django", "flask", "fastapi", "aiohttp", "nextjs", "express", "react", "rails", "sinatra", "actix", "rust",
+];
+
+/// Python packages (normalized names) that identify a framework
+const PYTHON_FRAMEWORK_PACKAGES: &[&str] = &["django", "flask", "fastapi", "aiohttp"];
+
+/// Ruby gems that identify a framework
+const RUBY_FRAMEWORK_GEMS: &[&str] = &["rails", "sinatra"];
+
+/// How deep below the monorepo root workspace packages are looked for
+const MAX_WORKSPACE_DEPTH: usize = 4;
+
+/// Tool that declares a monorepo's packages
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkspaceKind {
+    /// `packages` in pnpm-workspace.yaml
+    Pnpm,
+    /// `workspaces` in package.json (Yarn and npm)
+    Yarn,
+    /// `[workspace] members` in Cargo.toml
+    Cargo,
+    /// `use` directives in go.work
+    Go,
+}
+
+impl WorkspaceKind {
+    const ALL: [WorkspaceKind; 4] = [WorkspaceKind::Pnpm, WorkspaceKind::Yarn, WorkspaceKind::Cargo, WorkspaceKind::Go];
+
+    /// Manifest a member directory must contain to count as a package
+    fn member_manifest(&self) -> &'static str {
+        match self {
+            WorkspaceKind::Pnpm | WorkspaceKind::Yarn => "package.json",
+            WorkspaceKind::Cargo => "Cargo.toml",
+            WorkspaceKind::Go => "go.mod",
+        }
+    }
+}
+
+/// A package of a monorepo
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkspacePackage {
+    /// Name from the package's manifest, or its directory name
+    pub name: String,
+    /// Directory relative to the monorepo root, with `/` separators
+    pub path: String,
+    pub kind: WorkspaceKind,
+}
+
+/// The packages a project's workspace files declare; empty if it isn't a monorepo
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MonorepoLayout {
+    pub packages: Vec<WorkspacePackage>,
+}
+
+impl MonorepoLayout {
+    pub fn is_empty(&self) -> bool {
+        self.packages.is_empty()
+    }
+
+    /// Package containing `relative_path`, a file path inside the monorepo root
+    ///
+    /// Packages may nest; the deepest one containing the path wins.
+    pub fn package_for(&self, relative_path: &str) -> Option<&WorkspacePackage> {
+        let relative_path = relative_path.replace('\\', "/");
+        self.packages
+            .iter()
+            .filter(|package| {
+                relative_path
+                    .strip_prefix(package.path.as_str())
+                    .is_some_and(|rest| rest.starts_with('/'))
+            })
+            .max_by_key(|package| package.path.len())
+    }
+}
+
+/// A framework the project may use, with how sure detection is of it
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameworkCandidate {
+    pub framework: String,
+    /// [`LOCK_FILE_CONFIDENCE`] or [`INDICATOR_FILE_CONFIDENCE`]
+    pub confidence: f64,
+    /// What the candidate was found in, e.g. "poetry.lock" or "indicator files"
+    pub source: String,
+}
+
+/// Process-wide framework detection cache
+pub static FRAMEWORK_CACHE: Lazy<FrameworkCache> = Lazy::new(FrameworkCache::new);
+
+/// Caches detected frameworks per project path
+///
+/// Each entry stores the newest mtime among the project root and its indicator
+/// files at detection time; a lookup whose current mtime differs re-detects.
+/// Nested indicator files (e.g. `app/manage.py`) are not part of the mtime check,
+/// so the file watcher also calls [`FrameworkCache::invalidate_for_file`].
+#[derive(Default)]
+pub struct FrameworkCache {
+    entries: DashMap<PathBuf, (Option<String>, SystemTime)>,
+}
+
+impl FrameworkCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Detect the project's framework, reusing the cached result while indicators are unchanged
+    pub fn detect(&self, project_path: &Path) -> Result<Option<String>> {
+        let mtime = Self::indicator_mtime(project_path);
+
+        if let Some(entry) = self.entries.get(project_path) {
+            let (framework, cached_mtime) = entry.value();
+            if *cached_mtime == mtime {
+                return Ok(framework.clone());
+            }
+        }
+
+        let framework = FrameworkDetector::detect_framework(project_path)?;
+        self.entries
+            .insert(project_path.to_path_buf(), (framework.clone(), mtime));
+        Ok(framework)
+    }
+
+    /// Drop the cached result for a project
+    pub fn invalidate(&self, project_path: &Path) {
+        self.entries.remove(project_path);
+    }
+
+    /// Drop cached results for every project containing `file_path` if it is an indicator file
+    pub fn invalidate_for_file(&self, file_path: &Path) {
+        if !Self::is_indicator_file(file_path) {
+            return;
+        }
+        self.entries.retain(|project_path, _| !file_path.starts_with(project_path));
+    }
+
+    /// Whether `path` names a framework indicator file
+    pub fn is_indicator_file(path: &Path) -> bool {
+        path.file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|name| FRAMEWORK_INDICATOR_FILES.contains(&name))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Newest mtime among the project root and its root-level indicator files
+    ///
+    /// The root's own mtime changes when indicator files are created or deleted.
+    fn indicator_mtime(project_path: &Path) -> SystemTime {
+        std::iter::once(project_path.to_path_buf())
+            .chain(FRAMEWORK_INDICATOR_FILES.iter().map(|name| project_path.join(name)))
+            .filter_map(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+            .max()
+            .unwrap_or(SystemTime::UNIX_EPOCH)
+    }
+}
 
 /// Framework detector for identifying project frameworks
 pub struct FrameworkDetector;
@@ -21,53 +229,165 @@ impl FrameworkDetector {
     /// * `project_path` - Path to the project root directory
     ///
     /// # Returns
-    /// * `Ok(Some(framework))` if a framework is detected
+    /// * `Ok(Some(framework))` for the highest-confidence candidate from
+    ///   [`Self::detect_framework_candidates`]
     /// * `Ok(None)` if no framework is detected
     /// * `Err(...)` if an error occurs reading files
+    pub fn detect_framework(project_path: &Path) -> Result<Option<String>> {
+        Ok(Self::detect_framework_candidates(project_path)?
+            .into_iter()
+            .next()
+            .map(|candidate| candidate.framework))
+    }
+
+    /// Every framework the project may use, most confident first
     ///
-    /// # Detection Priority
+    /// Frameworks found in a lock file score [`LOCK_FILE_CONFIDENCE`]; those
+    /// inferred from indicator files score [`INDICATOR_FILE_CONFIDENCE`]. Each
+    /// framework appears once, at its highest confidence.
+    ///
+    /// # Detection Priority (breaks ties)
     /// 1. Django (manage.py, settings.py, requirements.txt)
     /// 2. Flask (app.py, routes.py, requirements.txt)
-    /// 3. Next.js (package.json with next and react)
-    /// 4. Express (package.json with express)
-    /// 5. React (package.json with react, but no next)
-    pub fn detect_framework(project_path: &Path) -> Result<Option<String>> {
+    /// 3. FastAPI, then aiohttp (lock files only)
+    /// 4. Next.js (package.json with next and react)
+    /// 5. Express (package.json with express)
+    /// 6. React (package.json with react, but no next)
+    /// 7. Rails, then Sinatra (Gemfile.lock)
+    /// 8. Actix-web (Cargo.toml with actix-web)
+    /// 9. Rust (any other Cargo.toml)
+    pub fn detect_framework_candidates(project_path: &Path) -> Result<Vec<FrameworkCandidate>> {
         // Ensure project_path exists
         if !project_path.exists() {
             return Err(anyhow!("Project path does not exist: {:?}", project_path));
         }
 
+        let mut candidates = Self::lock_file_candidates(project_path);
+        let mut add_indicator = |framework: &str| {
+            candidates.push(FrameworkCandidate {
+                framework: framework.to_string(),
+                confidence: INDICATOR_FILE_CONFIDENCE,
+                source: "indicator files".to_string(),
+            });
+        };
+
         // Check Django
         if Self::is_django(project_path)? {
-            return Ok(Some("django".to_string()));
+            add_indicator("django");
         }
 
         // Check Flask
         if Self::is_flask(project_path)? {
-            return Ok(Some("flask".to_string()));
+            add_indicator("flask");
         }
 
         // Check Next.js and Express/React from package.json
         if let Ok(package_json_content) = Self::read_package_json(project_path) {
-            // Check for Next.js (requires both next and react)
-            if Self::has_in_dependencies(&package_json_content, "next")
-                && Self::has_in_dependencies(&package_json_content, "react")
-            {
-                return Ok(Some("nextjs".to_string()));
+            let dependencies = Self::parse_package_json_deps(&package_json_content);
+            for framework in Self::node_frameworks(&dependencies) {
+                add_indicator(framework);
             }
+        }
 
-            // Check for Express
-            if Self::has_in_dependencies(&package_json_content, "express") {
-                return Ok(Some("express".to_string()));
+        // Check Rust projects from Cargo.toml
+        if let Ok(cargo_toml_content) = Self::read_cargo_toml(project_path) {
+            if Self::has_cargo_dependency(&cargo_toml_content, "actix-web") {
+                add_indicator("actix");
+            } else {
+                add_indicator("rust");
             }
+        }
 
-            // Check for React (without next)
-            if Self::has_in_dependencies(&package_json_content, "react") {
-                return Ok(Some("react".to_string()));
-            }
+        let priority = |candidate: &FrameworkCandidate| {
+            FRAMEWORK_PRIORITY
+                .iter()
+                .position(|framework| *framework == candidate.framework)
+                .unwrap_or(FRAMEWORK_PRIORITY.len())
+        };
+        candidates.sort_by(|a, b| {
+            b.confidence
+                .total_cmp(&a.confidence)
+                .then_with(|| priority(a).cmp(&priority(b)))
+        });
+
+        let mut seen = std::collections::HashSet::new();
+        candidates.retain(|candidate| seen.insert(candidate.framework.clone()));
+        Ok(candidates)
+    }
+
+    /// Package names from requirements.txt, normalized (`Flask_Login` -> `flask-login`)
+    ///
+    /// Skips comments, blank lines and pip options (`-r base.txt`, `--hash=...`),
+    /// and strips extras, version specifiers and environment markers.
+    pub fn parse_requirements_txt(content: &str) -> Vec<String> {
+        content
+            .lines()
+            .map(|line| line.split(" #").next().unwrap_or_default().trim())
+            .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with('-'))
+            .filter_map(|line| {
+                let end = line
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+                    .unwrap_or(line.len());
+                let name = &line[..end];
+                (!name.is_empty()).then(|| normalize_python_package(name))
+            })
+            .collect()
+    }
+
+    /// Dependency names from package.json or package-lock.json
+    ///
+    /// package.json: `dependencies`, `devDependencies` and `peerDependencies`.
+    /// package-lock.json v2/v3: the root package's declared dependencies;
+    /// v1: the top-level `dependencies` not marked `"dev": true`. Invalid JSON
+    /// yields no dependencies.
+    pub fn parse_package_json_deps(content: &str) -> Vec<String> {
+        let Ok(json) = serde_json::from_str::<serde_json::Value>(content) else {
+            return Vec::new();
+        };
+        let keys = |value: &serde_json::Value| -> Vec<String> {
+            value
+                .as_object()
+                .map(|deps| deps.keys().cloned().collect())
+                .unwrap_or_default()
+        };
+        let declared = |package: &serde_json::Value| -> Vec<String> {
+            ["dependencies", "devDependencies", "peerDependencies"]
+                .iter()
+                .flat_map(|section| keys(&package[*section]))
+                .collect()
+        };
+
+        if let Some(root) = json["packages"].get("") {
+            return declared(root);
+        }
+        if json.get("lockfileVersion").is_some() {
+            return json["dependencies"]
+                .as_object()
+                .map(|deps| {
+                    deps.iter()
+                        .filter(|(_, entry)| entry["dev"].as_bool() != Some(true))
+                        .map(|(name, _)| name.clone())
+                        .collect()
+                })
+                .unwrap_or_default();
         }
+        declared(&json)
+    }
 
-        Ok(None)
+    /// Gem names listed under `DEPENDENCIES` in Gemfile.lock (the Gemfile's
+    /// direct dependencies), e.g. `rails (~> 7.0.4)` -> `rails`
+    pub fn parse_gemfile_lock(content: &str) -> Vec<String> {
+        content
+            .lines()
+            .skip_while(|line| line.trim_end() != "DEPENDENCIES")
+            .skip(1)
+            .take_while(|line| line.starts_with(' '))
+            .filter_map(|line| {
+                line.split_whitespace()
+                    .next()
+                    .map(|name| name.trim_end_matches('!').to_string())
+            })
+            .collect()
     }
 
     /// Detect language from file extension
@@ -85,10 +405,49 @@ impl FrameworkDetector {
             "jsx" => Some("javascript".to_string()),
             "ts" => Some("typescript".to_string()),
             "tsx" => Some("typescript".to_string()),
+            "rs" => Some("rust".to_string()),
+            "go" => Some("go".to_string()),
+            "md" => Some("markdown".to_string()),
+            "json" => Some("json".to_string()),
+            "properties" => Some("properties".to_string()),
+            "sh" | "bash" | "zsh" => Some("shell".to_string()),
+            "c" | "h" => Some("c".to_string()),
+            "tf" => Some("terraform".to_string()),
+            "yaml" | "yml" => Some("yaml".to_string()),
             _ => None,
         }
     }
 
+    /// Whether a Markdown or JSON file is in scope for secrets scanning
+    ///
+    /// Only files at the project root or under `docs/` count (README.md,
+    /// config.json, docs/setup.md); JSON deeper in the tree is mostly fixtures
+    /// and generated data. `package-lock.json` and `.ryn-baseline.json` are
+    /// never in scope.
+    ///
+    /// # Arguments
+    /// * `relative_path` - Path of the file relative to the project root
+    pub fn is_project_doc_file(relative_path: &Path) -> bool {
+        if !matches!(
+            Self::detect_language(relative_path).as_deref(),
+            Some("markdown") | Some("json")
+        ) {
+            return false;
+        }
+
+        if matches!(
+            relative_path.file_name().and_then(|n| n.to_str()),
+            Some("package-lock.json") | Some(crate::scanner::baseline::BASELINE_FILE_NAME)
+        ) {
+            return false;
+        }
+
+        let mut components = relative_path.components();
+        let first = components.next();
+        components.next().is_none()
+            || first.and_then(|c| c.as_os_str().to_str()) == Some("docs")
+    }
+
     // Private helper methods
 
     fn is_django(project_path: &Path) -> Result<bool> {
@@ -156,23 +515,357 @@ impl FrameworkDetector {
         Ok(false)
     }
 
+    /// Candidates from the lock files at the project root
+    ///
+    /// yarn.lock also lists transitive packages, so only packages that
+    /// package.json declares directly count.
+    fn lock_file_candidates(project_path: &Path) -> Vec<FrameworkCandidate> {
+        let read = |name: &str| std::fs::read_to_string(project_path.join(name)).ok();
+        let mut candidates = Vec::new();
+        let mut add = |source: &str, frameworks: Vec<&'static str>| {
+            candidates.extend(frameworks.into_iter().map(|framework| FrameworkCandidate {
+                framework: framework.to_string(),
+                confidence: LOCK_FILE_CONFIDENCE,
+                source: source.to_string(),
+            }));
+        };
+
+        let python_frameworks = |packages: Vec<String>| -> Vec<&'static str> {
+            PYTHON_FRAMEWORK_PACKAGES
+                .iter()
+                .copied()
+                .filter(|framework| packages.iter().any(|package| package == framework))
+                .collect()
+        };
+        if let Some(content) = read("Pipfile.lock") {
+            add("Pipfile.lock", python_frameworks(Self::parse_pipfile_lock(&content)));
+        }
+        if let Some(content) = read("poetry.lock") {
+            add("poetry.lock", python_frameworks(Self::parse_poetry_lock(&content)));
+        }
+        if let Some(content) = read("requirements.txt") {
+            add("requirements.txt", python_frameworks(Self::parse_requirements_txt(&content)));
+        }
+
+        if let Some(content) = read("package-lock.json") {
+            add("package-lock.json", Self::node_frameworks(&Self::parse_package_json_deps(&content)));
+        }
+        if let (Some(content), Some(package_json)) = (read("yarn.lock"), read("package.json")) {
+            let declared = Self::parse_package_json_deps(&package_json);
+            let locked: Vec<String> = Self::parse_yarn_lock(&content)
+                .into_iter()
+                .filter(|name| declared.contains(name))
+                .collect();
+            add("yarn.lock", Self::node_frameworks(&locked));
+        }
+
+        if let Some(content) = read("Gemfile.lock") {
+            let gems = Self::parse_gemfile_lock(&content);
+            add(
+                "Gemfile.lock",
+                RUBY_FRAMEWORK_GEMS
+                    .iter()
+                    .copied()
+                    .filter(|framework| gems.iter().any(|gem| gem == framework))
+                    .collect(),
+            );
+        }
+
+        candidates
+    }
+
+    /// Frameworks implied by Node dependencies: Next.js (next and react),
+    /// Express, and React without Next.js
+    fn node_frameworks(dependencies: &[String]) -> Vec<&'static str> {
+        let has = |name: &str| dependencies.iter().any(|dependency| dependency == name);
+        let mut frameworks = Vec::new();
+        if has("next") && has("react") {
+            frameworks.push("nextjs");
+        }
+        if has("express") {
+            frameworks.push("express");
+        }
+        if has("react") && !has("next") {
+            frameworks.push("react");
+        }
+        frameworks
+    }
+
+    /// Package names from the `default` (non-dev) section of Pipfile.lock
+    fn parse_pipfile_lock(content: &str) -> Vec<String> {
+        serde_json::from_str::<serde_json::Value>(content)
+            .ok()
+            .and_then(|json| {
+                json["default"]
+                    .as_object()
+                    .map(|packages| packages.keys().map(|name| normalize_python_package(name)).collect())
+            })
+            .unwrap_or_default()
+    }
+
+    /// Package names from the `[[package]]` tables of poetry.lock
+    fn parse_poetry_lock(content: &str) -> Vec<String> {
+        let mut packages = Vec::new();
+        let mut in_package = false;
+        for line in content.lines().map(str::trim) {
+            if line.starts_with('[') {
+                in_package = line == "[[package]]";
+                continue;
+            }
+            if !in_package {
+                continue;
+            }
+            if let Some(value) = line.strip_prefix("name").map(str::trim_start).and_then(|rest| rest.strip_prefix('=')) {
+                packages.push(normalize_python_package(value.trim().trim_matches('"')));
+                in_package = false;
+            }
+        }
+        packages
+    }
+
+    /// Package names from yarn.lock (classic v1 and Berry entry headers),
+    /// e.g. `"@babel/core@^7.0.0", "@babel/core@^7.12.3":` -> `@babel/core`
+    fn parse_yarn_lock(content: &str) -> Vec<String> {
+        let mut packages: Vec<String> = content
+            .lines()
+            .filter(|line| !line.starts_with([' ', '#']) && line.ends_with(':'))
+            .flat_map(|header| header.trim_end_matches(':').split(", "))
+            .filter_map(|spec| {
+                let spec = spec.trim().trim_matches('"');
+                let at = spec.get(1..)?.find('@')? + 1;
+                Some(spec[..at].to_string())
+            })
+            .collect();
+        packages.dedup();
+        packages
+    }
+
     fn read_package_json(project_path: &Path) -> Result<String> {
         let package_json_path = project_path.join("package.json");
         std::fs::read_to_string(&package_json_path)
             .context("Failed to read package.json")
     }
 
-    fn has_in_dependencies(package_json_content: &str, dependency: &str) -> bool {
-        // Simple check: look for the dependency name in the file
-        // This is a basic implementation that checks for the dependency name
-        // in quotes to avoid false positives
-        let patterns = vec![
-            format!("\"{}\": ", dependency),
-            format!("\"{}\": {{", dependency),
-        ];
+    fn read_cargo_toml(project_path: &Path) -> Result<String> {
+        let cargo_toml_path = project_path.join("Cargo.toml");
+        std::fs::read_to_string(&cargo_toml_path)
+            .context("Failed to read Cargo.toml")
+    }
+
+    /// Detect the packages of a monorepo rooted at `project_path`
+    ///
+    /// Reads pnpm-workspace.yaml, package.json `workspaces`, Cargo.toml
+    /// `[workspace]` and go.work, expands their member globs against the
+    /// directories on disk and keeps those with the tool's manifest
+    /// (package.json, Cargo.toml or go.mod). Missing or malformed workspace
+    /// files are skipped, so a project that isn't a monorepo gets an empty
+    /// layout. A directory declared by several tools is listed once.
+    pub fn detect_monorepo(project_path: &Path) -> MonorepoLayout {
+        let workspaces: Vec<(WorkspaceKind, Vec<String>)> = WorkspaceKind::ALL
+            .into_iter()
+            .map(|kind| (kind, Self::workspace_patterns(project_path, kind)))
+            .filter(|(_, patterns)| !patterns.is_empty())
+            .collect();
+        if workspaces.is_empty() {
+            return MonorepoLayout::default();
+        }
+
+        let directories = Self::workspace_directories(project_path);
+        let mut packages: Vec<WorkspacePackage> = Vec::new();
+        for (kind, patterns) in workspaces {
+            let (excludes, includes): (Vec<&str>, Vec<&str>) =
+                patterns.iter().map(String::as_str).partition(|pattern| pattern.starts_with('!'));
+            let includes = Self::workspace_globs(&includes);
+            let excludes = Self::workspace_globs(&excludes.iter().map(|p| &p[1..]).collect::<Vec<_>>());
+
+            for (relative, path) in &directories {
+                if !includes.is_match(relative)
+                    || excludes.is_match(relative)
+                    || packages.iter().any(|package| package.path == *relative)
+                {
+                    continue;
+                }
+                let manifest = path.join(kind.member_manifest());
+                if !manifest.is_file() {
+                    continue;
+                }
+                let name = Self::package_name(&manifest, kind).unwrap_or_else(|| {
+                    relative.rsplit('/').next().unwrap_or(relative).to_string()
+                });
+                packages.push(WorkspacePackage { name, path: relative.clone(), kind });
+            }
+        }
+
+        packages.sort_by(|a, b| a.path.cmp(&b.path));
+        MonorepoLayout { packages }
+    }
+
+    /// Member patterns declared by `kind`'s workspace file; `!` marks an exclusion
+    fn workspace_patterns(project_path: &Path, kind: WorkspaceKind) -> Vec<String> {
+        let read = |file_name: &str| std::fs::read_to_string(project_path.join(file_name)).ok();
+        let content = match kind {
+            WorkspaceKind::Pnpm => read("pnpm-workspace.yaml"),
+            WorkspaceKind::Yarn => Self::read_package_json(project_path).ok(),
+            WorkspaceKind::Cargo => Self::read_cargo_toml(project_path).ok(),
+            WorkspaceKind::Go => read("go.work"),
+        };
+        let Some(content) = content else {
+            return Vec::new();
+        };
+        match kind {
+            WorkspaceKind::Pnpm => Self::parse_pnpm_workspace(&content),
+            WorkspaceKind::Yarn => Self::parse_package_json_workspaces(&content),
+            WorkspaceKind::Cargo => Self::parse_cargo_workspace(&content),
+            WorkspaceKind::Go => Self::parse_go_work(&content),
+        }
+    }
 
-        patterns.iter().any(|pattern| package_json_content.contains(pattern))
+    /// Parse the `packages` list of pnpm-workspace.yaml
+    pub fn parse_pnpm_workspace(content: &str) -> Vec<String> {
+        let Ok(yaml) = serde_yaml::from_str::<serde_yaml::Value>(content) else {
+            return Vec::new();
+        };
+        yaml.get("packages")
+            .and_then(|packages| packages.as_sequence())
+            .map(|packages| packages.iter().filter_map(|p| p.as_str()).map(str::to_string).collect())
+            .unwrap_or_default()
     }
+
+    /// Parse package.json `workspaces`, either an array or `{ "packages": [...] }`
+    pub fn parse_package_json_workspaces(content: &str) -> Vec<String> {
+        let Ok(json) = serde_json::from_str::<serde_json::Value>(content) else {
+            return Vec::new();
+        };
+        let Some(workspaces) = json.get("workspaces") else {
+            return Vec::new();
+        };
+        workspaces
+            .as_array()
+            .or_else(|| workspaces.get("packages").and_then(|packages| packages.as_array()))
+            .map(|packages| packages.iter().filter_map(|p| p.as_str()).map(str::to_string).collect())
+            .unwrap_or_default()
+    }
+
+    /// Parse `[workspace] members` of Cargo.toml, with `exclude` entries as `!` patterns
+    pub fn parse_cargo_workspace(content: &str) -> Vec<String> {
+        let Ok(toml) = toml::from_str::<toml::Value>(content) else {
+            return Vec::new();
+        };
+        let Some(workspace) = toml.get("workspace") else {
+            return Vec::new();
+        };
+        let list = |key: &str| -> Vec<String> {
+            workspace
+                .get(key)
+                .and_then(|value| value.as_array())
+                .map(|values| values.iter().filter_map(|v| v.as_str()).map(str::to_string).collect())
+                .unwrap_or_default()
+        };
+        let excludes = list("exclude").into_iter().map(|exclude| format!("!{}", exclude));
+        list("members").into_iter().chain(excludes).collect()
+    }
+
+    /// Parse the directories of go.work `use` directives, single or in a `use ( ... )` block
+    pub fn parse_go_work(content: &str) -> Vec<String> {
+        let mut directories = Vec::new();
+        let mut in_block = false;
+
+        for line in content.lines() {
+            let line = line.split("//").next().unwrap_or("").trim();
+            if in_block {
+                if line == ")" {
+                    in_block = false;
+                } else if !line.is_empty() {
+                    directories.push(line.trim_matches('"').to_string());
+                }
+            } else if let Some(rest) = line.strip_prefix("use").filter(|rest| rest.starts_with([' ', '\t', '('])) {
+                match rest.trim() {
+                    "(" => in_block = true,
+                    "" => {}
+                    directory => directories.push(directory.trim_matches('"').to_string()),
+                }
+            }
+        }
+
+        directories
+    }
+
+    /// Directories below `project_path` that could be workspace members,
+    /// as (relative path with `/` separators, absolute path)
+    fn workspace_directories(project_path: &Path) -> Vec<(String, PathBuf)> {
+        WalkDir::new(project_path)
+            .min_depth(1)
+            .max_depth(MAX_WORKSPACE_DEPTH)
+            .into_iter()
+            .filter_entry(|entry| {
+                let name = entry.file_name().to_string_lossy();
+                entry.file_type().is_dir() && !name.starts_with('.') && !SKIP_DIRECTORIES.contains(&&*name)
+            })
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let relative = entry.path().strip_prefix(project_path).ok()?;
+                Some((relative.to_string_lossy().replace('\\', "/"), entry.into_path()))
+            })
+            .collect()
+    }
+
+    /// Compile member patterns, normalizing `./pkg/` to `pkg`
+    ///
+    /// `*` doesn't cross directories, so `packages/*` matches only direct
+    /// children; invalid patterns are skipped.
+    fn workspace_globs(patterns: &[&str]) -> GlobSet {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            let pattern = pattern.trim().trim_start_matches("./").trim_end_matches('/');
+            if pattern.is_empty() || pattern == "." {
+                continue;
+            }
+            if let Ok(glob) = GlobBuilder::new(pattern).literal_separator(true).build() {
+                builder.add(glob);
+            }
+        }
+        builder.build().unwrap_or_else(|_| GlobSet::empty())
+    }
+
+    /// Name declared in a workspace member's manifest
+    fn package_name(manifest: &Path, kind: WorkspaceKind) -> Option<String> {
+        let content = std::fs::read_to_string(manifest).ok()?;
+        let name = match kind {
+            WorkspaceKind::Pnpm | WorkspaceKind::Yarn => {
+                let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+                json.get("name")?.as_str()?.to_string()
+            }
+            WorkspaceKind::Cargo => {
+                let toml: toml::Value = toml::from_str(&content).ok()?;
+                toml.get("package")?.get("name")?.as_str()?.to_string()
+            }
+            WorkspaceKind::Go => content
+                .lines()
+                .find_map(|line| line.trim().strip_prefix("module "))?
+                .trim()
+                .trim_matches('"')
+                .to_string(),
+        };
+        Some(name).filter(|name| !name.is_empty())
+    }
+
+    /// Whether Cargo.toml declares `dependency`, either as `name = ...`,
+    /// `name.workspace = true` or a `[dependencies.name]` table
+    fn has_cargo_dependency(cargo_toml_content: &str, dependency: &str) -> bool {
+        let table_header = format!("[dependencies.{}]", dependency);
+
+        cargo_toml_content.lines().map(str::trim).any(|line| {
+            line == table_header
+                || line.strip_prefix(dependency).is_some_and(|rest| {
+                    rest.trim_start().starts_with('=') || rest.starts_with('.')
+                })
+        })
+    }
+}
+
+/// Normalize a Python package name per PEP 503 (`Flask_Login` -> `flask-login`)
+fn normalize_python_package(name: &str) -> String {
+    name.to_lowercase().replace(['_', '.'], "-")
 }
 
 #[cfg(test)]
@@ -290,6 +983,38 @@ mod tests {
         assert_eq!(result.unwrap(), Some("react".to_string()));
     }
 
+    #[test]
+    fn test_detect_rust_from_cargo_toml() {
+        let temp_dir = create_test_project(vec![(
+            "Cargo.toml",
+            "[package]\nname = \"cli\"\n\n[dependencies]\nserde = \"1\"\n",
+        )]);
+        let result = FrameworkDetector::detect_framework(temp_dir.path());
+
+        assert_eq!(result.unwrap(), Some("rust".to_string()));
+    }
+
+    #[test]
+    fn test_detect_actix_from_cargo_toml() {
+        for dependencies in [
+            "[dependencies]\nactix-web = \"4\"\n",
+            "[dependencies]\nactix-web.workspace = true\n",
+            "[dependencies.actix-web]\nversion = \"4\"\n",
+        ] {
+            let temp_dir = create_test_project(vec![("Cargo.toml", dependencies)]);
+            let result = FrameworkDetector::detect_framework(temp_dir.path());
+
+            assert_eq!(result.unwrap(), Some("actix".to_string()), "{}", dependencies);
+        }
+
+        // actix-web-lab is a different crate
+        let temp_dir = create_test_project(vec![("Cargo.toml", "[dependencies]\nactix-web-lab = \"0.20\"\n")]);
+        assert_eq!(
+            FrameworkDetector::detect_framework(temp_dir.path()).unwrap(),
+            Some("rust".to_string())
+        );
+    }
+
     #[test]
     fn test_no_framework_detected() {
         let temp_dir = create_test_project(vec![("README.md", "# My Project")]);
@@ -328,12 +1053,130 @@ mod tests {
             FrameworkDetector::detect_language(Path::new("test.tsx")),
             Some("typescript".to_string())
         );
+        assert_eq!(
+            FrameworkDetector::detect_language(Path::new("main.rs")),
+            Some("rust".to_string())
+        );
+        assert_eq!(
+            FrameworkDetector::detect_language(Path::new("cmd/server/main.go")),
+            Some("go".to_string())
+        );
         assert_eq!(
             FrameworkDetector::detect_language(Path::new("test.unknown")),
             None
         );
     }
 
+    #[test]
+    fn test_detect_language_markdown_and_json() {
+        assert_eq!(
+            FrameworkDetector::detect_language(Path::new("README.md")),
+            Some("markdown".to_string())
+        );
+        assert_eq!(
+            FrameworkDetector::detect_language(Path::new("config/app.json")),
+            Some("json".to_string())
+        );
+        assert_eq!(
+            FrameworkDetector::detect_language(Path::new("src/main/resources/application.properties")),
+            Some("properties".to_string())
+        );
+        assert_eq!(
+            FrameworkDetector::detect_language(Path::new("scripts/deploy.sh")),
+            Some("shell".to_string())
+        );
+        assert_eq!(
+            FrameworkDetector::detect_language(Path::new("src/config.c")),
+            Some("c".to_string())
+        );
+        assert_eq!(
+            FrameworkDetector::detect_language(Path::new("infra/main.tf")),
+            Some("terraform".to_string())
+        );
+        assert_eq!(
+            FrameworkDetector::detect_language(Path::new("cloudformation/stack.yml")),
+            Some("yaml".to_string())
+        );
+    }
+
+    #[test]
+    fn test_is_project_doc_file() {
+        assert!(FrameworkDetector::is_project_doc_file(Path::new("README.md")));
+        assert!(FrameworkDetector::is_project_doc_file(Path::new("config.json")));
+        assert!(FrameworkDetector::is_project_doc_file(Path::new("docs/setup.md")));
+        assert!(FrameworkDetector::is_project_doc_file(Path::new("docs/api/auth.json")));
+
+        assert!(!FrameworkDetector::is_project_doc_file(Path::new("src/fixtures/data.json")));
+        assert!(!FrameworkDetector::is_project_doc_file(Path::new("app/README.md")));
+        assert!(!FrameworkDetector::is_project_doc_file(Path::new("package-lock.json")));
+        assert!(!FrameworkDetector::is_project_doc_file(Path::new(".ryn-baseline.json")));
+        assert!(!FrameworkDetector::is_project_doc_file(Path::new("settings.py")));
+    }
+
+    #[test]
+    fn test_cache_returns_cached_result_until_indicator_changes() {
+        let temp_dir = create_test_project(vec![(
+            "package.json",
+            r#"{"dependencies": {"express": "^4.0"}}"#,
+        )]);
+        let cache = FrameworkCache::new();
+
+        assert_eq!(cache.detect(temp_dir.path()).unwrap(), Some("express".to_string()));
+        assert_eq!(cache.len(), 1);
+
+        // Rewrite package.json with a newer mtime: cache entry is stale
+        let package_json = temp_dir.path().join("package.json");
+        fs::write(&package_json, r#"{"dependencies": {"react": "^18.0"}}"#).unwrap();
+        let newer = SystemTime::now() + std::time::Duration::from_secs(5);
+        fs::File::options().write(true).open(&package_json).unwrap().set_modified(newer).unwrap();
+
+        assert_eq!(cache.detect(temp_dir.path()).unwrap(), Some("react".to_string()));
+    }
+
+    #[test]
+    fn test_cache_hit_skips_detection() {
+        let temp_dir = create_test_project(vec![("README.md", "# none")]);
+        let cache = FrameworkCache::new();
+        let mtime = FrameworkCache::indicator_mtime(temp_dir.path());
+
+        // Seed an entry that detection would never produce; a hit must return it
+        cache.entries.insert(temp_dir.path().to_path_buf(), (Some("django".to_string()), mtime));
+        assert_eq!(cache.detect(temp_dir.path()).unwrap(), Some("django".to_string()));
+
+        cache.invalidate(temp_dir.path());
+        assert!(cache.is_empty());
+        assert_eq!(cache.detect(temp_dir.path()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_cache_invalidate_for_file() {
+        let temp_dir = create_test_project(vec![("manage.py", "")]);
+        let cache = FrameworkCache::new();
+        cache.detect(temp_dir.path()).unwrap();
+
+        // Non-indicator files leave the entry alone
+        cache.invalidate_for_file(&temp_dir.path().join("views.py"));
+        assert_eq!(cache.len(), 1);
+
+        // Nested indicator files invalidate the containing project
+        cache.invalidate_for_file(&temp_dir.path().join("app").join("requirements.txt"));
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_cache_propagates_missing_project_error() {
+        let cache = FrameworkCache::new();
+        assert!(cache.detect(Path::new("/nonexistent/path/to/project")).is_err());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_is_indicator_file() {
+        assert!(FrameworkCache::is_indicator_file(Path::new("/p/package.json")));
+        assert!(FrameworkCache::is_indicator_file(Path::new("pom.xml")));
+        assert!(!FrameworkCache::is_indicator_file(Path::new("/p/index.js")));
+    }
+
     #[test]
     fn test_framework_detection_priority() {
         // Django takes priority over Flask
@@ -346,4 +1189,296 @@ mod tests {
 
         assert_eq!(result.unwrap(), Some("django".to_string()));
     }
+
+    // ===== Lock file parsing =====
+
+    #[test]
+    fn test_parse_requirements_txt() {
+        let content = r#"# Generated by pip-compile
+-r base.txt
+--index-url https://pypi.org/simple
+Django==4.2.7 \
+    --hash=sha256:8e0f1c2c2786b5c0e39fe1afce24c926040fad47c8ea8ad30aaf1188df29fc41
+Flask_Login>=0.6.3  # session handling
+celery[redis]~=5.3
+gunicorn ; sys_platform != "win32"
+requests
+"#;
+        assert_eq!(
+            FrameworkDetector::parse_requirements_txt(content),
+            vec!["django", "flask-login", "celery", "gunicorn", "requests"]
+        );
+    }
+
+    #[test]
+    fn test_parse_package_lock_v3_uses_root_dependencies() {
+        let content = r#"{
+  "name": "shop",
+  "version": "1.0.0",
+  "lockfileVersion": 3,
+  "requires": true,
+  "packages": {
+    "": {
+      "name": "shop",
+      "version": "1.0.0",
+      "dependencies": { "express": "^4.18.2" },
+      "devDependencies": { "jest": "^29.7.0" }
+    },
+    "node_modules/react": { "version": "18.2.0" }
+  }
+}"#;
+        let deps = FrameworkDetector::parse_package_json_deps(content);
+        assert_eq!(deps, vec!["express", "jest"]);
+    }
+
+    #[test]
+    fn test_parse_package_lock_v1_skips_dev_dependencies() {
+        let content = r#"{
+  "name": "shop",
+  "lockfileVersion": 1,
+  "dependencies": {
+    "express": { "version": "4.17.1", "resolved": "https://registry.npmjs.org/express/-/express-4.17.1.tgz" },
+    "react": { "version": "17.0.2", "dev": true }
+  }
+}"#;
+        assert_eq!(FrameworkDetector::parse_package_json_deps(content), vec!["express"]);
+    }
+
+    #[test]
+    fn test_parse_package_json_deps_invalid_json() {
+        assert!(FrameworkDetector::parse_package_json_deps("{ not json").is_empty());
+    }
+
+    #[test]
+    fn test_parse_gemfile_lock() {
+        let content = r#"GEM
+  remote: https://rubygems.org/
+  specs:
+    actionpack (7.0.4)
+      rack (~> 2.0, >= 2.2.0)
+    rails (7.0.4)
+      actionpack (= 7.0.4)
+
+PLATFORMS
+  x86_64-linux
+
+DEPENDENCIES
+  pg (~> 1.1)
+  puma (~> 5.0)
+  rails (~> 7.0.4)
+  rubocop!
+
+BUNDLED WITH
+   2.3.26
+"#;
+        assert_eq!(
+            FrameworkDetector::parse_gemfile_lock(content),
+            vec!["pg", "puma", "rails", "rubocop"]
+        );
+    }
+
+    #[test]
+    fn test_parse_yarn_lock() {
+        let content = r#"# THIS IS AN AUTOGENERATED FILE. DO NOT EDIT THIS FILE DIRECTLY.
+# yarn lockfile v1
+
+
+"@babel/core@^7.0.0", "@babel/core@^7.12.3":
+  version "7.23.2"
+
+express@^4.18.2:
+  version "4.18.2"
+  resolved "https://registry.yarnpkg.com/express/-/express-4.18.2.tgz"
+"#;
+        assert_eq!(FrameworkDetector::parse_yarn_lock(content), vec!["@babel/core", "express"]);
+    }
+
+    #[test]
+    fn test_detect_fastapi_from_pipfile_lock() {
+        let pipfile_lock = r#"{
+    "_meta": { "hash": { "sha256": "4f2b" }, "pipfile-spec": 6 },
+    "default": {
+        "fastapi": { "hashes": ["sha256:97ec"], "version": "==0.104.1" },
+        "starlette": { "version": "==0.27.0" }
+    },
+    "develop": {
+        "flask": { "version": "==3.0.0" }
+    }
+}"#;
+        let temp_dir = create_test_project(vec![("Pipfile.lock", pipfile_lock)]);
+
+        let candidates = FrameworkDetector::detect_framework_candidates(temp_dir.path()).unwrap();
+        assert_eq!(
+            candidates,
+            vec![FrameworkCandidate {
+                framework: "fastapi".to_string(),
+                confidence: LOCK_FILE_CONFIDENCE,
+                source: "Pipfile.lock".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_detect_aiohttp_from_poetry_lock() {
+        let poetry_lock = r#"# This file is automatically @generated by Poetry 1.6.1 and should not be changed by hand.
+
+[[package]]
+name = "aiohttp"
+version = "3.8.6"
+description = "Async http client/server framework (asyncio)"
+optional = false
+python-versions = ">=3.6"
+
+[package.dependencies]
+yarl = ">=1.0,<2.0"
+
+[[package]]
+name = "yarl"
+version = "1.9.2"
+
+[metadata]
+lock-version = "2.0"
+"#;
+        let temp_dir = create_test_project(vec![("poetry.lock", poetry_lock)]);
+
+        let result = FrameworkDetector::detect_framework(temp_dir.path());
+        assert_eq!(result.unwrap(), Some("aiohttp".to_string()));
+    }
+
+    #[test]
+    fn test_detect_rails_from_gemfile_lock() {
+        let gemfile_lock = "GEM\n  specs:\n    rails (7.0.4)\n\nDEPENDENCIES\n  rails (~> 7.0.4)\n";
+        let temp_dir = create_test_project(vec![("Gemfile.lock", gemfile_lock)]);
+
+        let result = FrameworkDetector::detect_framework(temp_dir.path());
+        assert_eq!(result.unwrap(), Some("rails".to_string()));
+    }
+
+    #[test]
+    fn test_yarn_lock_ignores_transitive_packages() {
+        let yarn_lock = "react@^18.2.0:\n  version \"18.2.0\"\n\nexpress@^4.18.2:\n  version \"4.18.2\"\n";
+        let temp_dir = create_test_project(vec![
+            ("package.json", r#"{"dependencies": {"express": "^4.18.2"}}"#),
+            ("yarn.lock", yarn_lock),
+        ]);
+
+        let candidates = FrameworkDetector::detect_framework_candidates(temp_dir.path()).unwrap();
+        assert_eq!(candidates[0].framework, "express");
+        assert_eq!(candidates[0].source, "yarn.lock");
+        assert!(candidates.iter().all(|c| c.framework != "react"));
+    }
+
+    #[test]
+    fn test_lock_file_outweighs_indicator_files() {
+        // app.py alone suggests Flask, but the lock file pins FastAPI
+        let temp_dir = create_test_project(vec![
+            ("app.py", "from fastapi import FastAPI\napp = FastAPI()"),
+            ("poetry.lock", "[[package]]\nname = \"fastapi\"\nversion = \"0.104.1\"\n"),
+        ]);
+
+        let candidates = FrameworkDetector::detect_framework_candidates(temp_dir.path()).unwrap();
+        assert_eq!(candidates[0].framework, "fastapi");
+        assert_eq!(candidates[0].confidence, LOCK_FILE_CONFIDENCE);
+        assert_eq!(candidates[1].framework, "flask");
+        assert_eq!(candidates[1].confidence, INDICATOR_FILE_CONFIDENCE);
+        assert_eq!(
+            FrameworkDetector::detect_framework(temp_dir.path()).unwrap(),
+            Some("fastapi".to_string())
+        );
+    }
+
+    #[test]
+    fn test_candidates_keep_highest_confidence_per_framework() {
+        // requirements.txt and manage.py both point at Django
+        let temp_dir = create_test_project(vec![
+            ("manage.py", "#!/usr/bin/env python"),
+            ("requirements.txt", "Django==4.2.7\n"),
+        ]);
+
+        let candidates = FrameworkDetector::detect_framework_candidates(temp_dir.path()).unwrap();
+        let django: Vec<_> = candidates.iter().filter(|c| c.framework == "django").collect();
+        assert_eq!(django.len(), 1);
+        assert_eq!(django[0].confidence, LOCK_FILE_CONFIDENCE);
+        assert_eq!(django[0].source, "requirements.txt");
+    }
+
+    #[test]
+    fn test_parse_workspace_files() {
+        assert_eq!(
+            FrameworkDetector::parse_pnpm_workspace("packages:\n  - 'packages/*'\n  - '!packages/legacy'\n"),
+            vec!["packages/*", "!packages/legacy"]
+        );
+        assert_eq!(
+            FrameworkDetector::parse_package_json_workspaces(r#"{"workspaces": ["apps/*"]}"#),
+            vec!["apps/*"]
+        );
+        assert_eq!(
+            FrameworkDetector::parse_package_json_workspaces(r#"{"workspaces": {"packages": ["libs/*"]}}"#),
+            vec!["libs/*"]
+        );
+        assert_eq!(
+            FrameworkDetector::parse_cargo_workspace("[workspace]\nmembers = [\"crates/*\"]\nexclude = [\"crates/old\"]\n"),
+            vec!["crates/*", "!crates/old"]
+        );
+        assert_eq!(
+            FrameworkDetector::parse_go_work("go 1.22\n\nuse ./cmd/api\nuse (\n\t./svc/auth // auth service\n\t./svc/billing\n)\n"),
+            vec!["./cmd/api", "./svc/auth", "./svc/billing"]
+        );
+        assert!(FrameworkDetector::parse_package_json_workspaces(r#"{"name": "app"}"#).is_empty());
+        assert!(FrameworkDetector::parse_cargo_workspace("[package]\nname = \"app\"\n").is_empty());
+    }
+
+    #[test]
+    fn test_detect_pnpm_monorepo() {
+        let temp_dir = create_test_project(vec![
+            ("pnpm-workspace.yaml", "packages:\n  - 'packages/*'\n  - '!packages/legacy'\n"),
+            ("packages/api/package.json", r#"{"name": "@acme/api"}"#),
+            ("packages/web/package.json", "{}"),
+            ("packages/legacy/package.json", r#"{"name": "legacy"}"#),
+            ("packages/docs/README.md", "no manifest"),
+        ]);
+
+        let layout = FrameworkDetector::detect_monorepo(temp_dir.path());
+        let names: Vec<_> = layout.packages.iter().map(|p| (p.name.as_str(), p.path.as_str())).collect();
+        assert_eq!(names, vec![("@acme/api", "packages/api"), ("web", "packages/web")]);
+        assert_eq!(layout.packages[0].kind, WorkspaceKind::Pnpm);
+
+        assert_eq!(layout.package_for("packages/api/src/db.ts").unwrap().name, "@acme/api");
+        assert!(layout.package_for("packages/apiserver/index.ts").is_none());
+        assert!(layout.package_for("scripts/deploy.sh").is_none());
+    }
+
+    #[test]
+    fn test_detect_cargo_and_go_workspaces() {
+        let temp_dir = create_test_project(vec![
+            ("Cargo.toml", "[workspace]\nmembers = [\"crates/*\"]\n"),
+            ("crates/core/Cargo.toml", "[package]\nname = \"acme-core\"\n"),
+            ("go.work", "go 1.22\nuse ./services/auth\n"),
+            ("services/auth/go.mod", "module github.com/acme/auth\n\ngo 1.22\n"),
+        ]);
+
+        let layout = FrameworkDetector::detect_monorepo(temp_dir.path());
+        assert_eq!(layout.package_for("crates/core/src/lib.rs").unwrap().name, "acme-core");
+        let auth = layout.package_for("services/auth/main.go").unwrap();
+        assert_eq!((auth.name.as_str(), auth.kind), ("github.com/acme/auth", WorkspaceKind::Go));
+    }
+
+    #[test]
+    fn test_nested_package_wins() {
+        let temp_dir = create_test_project(vec![
+            ("package.json", r#"{"workspaces": ["apps/*", "apps/*/plugins/*"]}"#),
+            ("apps/site/package.json", r#"{"name": "site"}"#),
+            ("apps/site/plugins/auth/package.json", r#"{"name": "site-auth"}"#),
+        ]);
+
+        let layout = FrameworkDetector::detect_monorepo(temp_dir.path());
+        assert_eq!(layout.package_for("apps/site/plugins/auth/index.js").unwrap().name, "site-auth");
+        assert_eq!(layout.package_for("apps/site/index.js").unwrap().name, "site");
+    }
+
+    #[test]
+    fn test_not_a_monorepo() {
+        let temp_dir = create_test_project(vec![("package.json", r#"{"name": "app"}"#)]);
+        assert!(FrameworkDetector::detect_monorepo(temp_dir.path()).is_empty());
+    }
 }
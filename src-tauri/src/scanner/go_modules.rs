@@ -0,0 +1,287 @@
+//! Known-vulnerable Go dependencies
+//!
+//! Parses the `require` directives of a project's root `go.mod` and looks the
+//! modules up in the OSV database (<https://osv.dev>) with a single
+//! `querybatch` request. Each module with known advisories becomes a CC6.7
+//! violation on its `go.mod` line. The lookup can be turned off with the
+//! [`OSV_CHECK_SETTING`] setting; network failures never fail the scan.
+
+use anyhow::{Context, Result};
+use crate::models::{Severity, Violation};
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+/// OSV batch query endpoint
+pub const OSV_QUERYBATCH_URL: &str = "https://api.osv.dev/v1/querybatch";
+
+/// Settings key: "false" disables the OSV lookup
+pub const OSV_CHECK_SETTING: &str = "osv_dependency_check";
+
+/// A module required by `go.mod`
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoDependency {
+    /// Module path, e.g. "golang.org/x/net"
+    pub module: String,
+    /// Version as written in `go.mod`, e.g. "v0.7.0"
+    pub version: String,
+    /// 1-based line of the requirement in `go.mod`
+    pub line_number: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvBatchResponse {
+    #[serde(default)]
+    results: Vec<OsvBatchResult>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct OsvBatchResult {
+    #[serde(default)]
+    vulns: Vec<OsvVulnRef>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvVulnRef {
+    id: String,
+}
+
+/// Parse the `require` directives of a `go.mod` file
+///
+/// Handles single-line `require module v1.2.3` and `require ( ... )` blocks.
+/// `// indirect` requirements are kept (they are still compiled in);
+/// `replace`, `exclude` and `retract` directives are ignored.
+pub fn parse_go_mod(content: &str) -> Vec<GoDependency> {
+    let mut dependencies = Vec::new();
+    let mut in_require_block = false;
+
+    for (idx, raw_line) in content.lines().enumerate() {
+        let line = raw_line.split("//").next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let requirement = if in_require_block {
+            if line == ")" {
+                in_require_block = false;
+                continue;
+            }
+            line
+        } else if let Some(rest) = line.strip_prefix("require") {
+            let rest = rest.trim();
+            if rest == "(" {
+                in_require_block = true;
+                continue;
+            }
+            rest
+        } else {
+            continue;
+        };
+
+        let mut parts = requirement.split_whitespace();
+        if let (Some(module), Some(version)) = (parts.next(), parts.next()) {
+            dependencies.push(GoDependency {
+                module: module.trim_matches('"').to_string(),
+                version: version.to_string(),
+                line_number: (idx + 1) as i64,
+            });
+        }
+    }
+
+    dependencies
+}
+
+/// Body of an OSV `querybatch` request for `dependencies`, in order
+///
+/// OSV stores Go versions without the leading `v`.
+pub fn build_osv_query(dependencies: &[GoDependency]) -> Value {
+    let queries: Vec<Value> = dependencies
+        .iter()
+        .map(|dep| {
+            json!({
+                "package": { "name": dep.module, "ecosystem": "Go" },
+                "version": dep.version.trim_start_matches('v'),
+            })
+        })
+        .collect();
+
+    json!({ "queries": queries })
+}
+
+/// Turn an OSV `querybatch` response into CC6.7 violations on `go.mod`
+///
+/// OSV answers with one result per query, in query order; modules without
+/// advisories get an empty result.
+pub fn violations_from_osv_response(
+    response: &str,
+    dependencies: &[GoDependency],
+    scan_id: i64,
+) -> Result<Vec<Violation>> {
+    let parsed: OsvBatchResponse =
+        serde_json::from_str(response).context("Failed to parse OSV response")?;
+
+    let violations = dependencies
+        .iter()
+        .zip(parsed.results)
+        .filter(|(_, result)| !result.vulns.is_empty())
+        .map(|(dep, result)| {
+            let ids: Vec<String> = result.vulns.into_iter().map(|v| v.id).collect();
+            Violation::new(
+                scan_id,
+                "CC6.7".to_string(),
+                Severity::High,
+                format!(
+                    "Go dependency {} {} has known vulnerabilities: {}",
+                    dep.module,
+                    dep.version,
+                    ids.join(", ")
+                ),
+                "go.mod".to_string(),
+                dep.line_number,
+                format!("{} {}", dep.module, dep.version),
+            )
+        })
+        .collect();
+
+    Ok(violations)
+}
+
+/// Look up every dependency in `go_mod` against OSV at `url`
+///
+/// Returns no violations (without a request) when `go.mod` requires nothing.
+pub async fn check_go_mod_vulnerabilities(go_mod: &str, url: &str, scan_id: i64) -> Result<Vec<Violation>> {
+    let dependencies = parse_go_mod(go_mod);
+    if dependencies.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let client = Client::builder()
+        .timeout(Duration::from_secs(15))
+        .build()
+        .context("Failed to build OSV HTTP client")?;
+
+    let response = client
+        .post(url)
+        .json(&build_osv_query(&dependencies))
+        .send()
+        .await
+        .context("OSV request failed")?
+        .error_for_status()
+        .context("OSV returned an error status")?
+        .text()
+        .await
+        .context("Failed to read OSV response")?;
+
+    violations_from_osv_response(&response, &dependencies, scan_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GO_MOD: &str = r#"module example.com/shop
+
+go 1.21
+
+require github.com/gorilla/mux v1.8.0
+
+require (
+    golang.org/x/net v0.7.0 // indirect
+    github.com/golang-jwt/jwt/v4 v4.4.1
+
+    gopkg.in/yaml.v3 v3.0.0-20210107192922-496545a6307b
+)
+
+replace github.com/gorilla/mux => ../mux
+"#;
+
+    #[test]
+    fn test_parse_go_mod_single_and_block_requires() {
+        let deps = parse_go_mod(GO_MOD);
+
+        let modules: Vec<_> = deps.iter().map(|d| (d.module.as_str(), d.version.as_str(), d.line_number)).collect();
+        assert_eq!(
+            modules,
+            vec![
+                ("github.com/gorilla/mux", "v1.8.0", 5),
+                ("golang.org/x/net", "v0.7.0", 8),
+                ("github.com/golang-jwt/jwt/v4", "v4.4.1", 9),
+                ("gopkg.in/yaml.v3", "v3.0.0-20210107192922-496545a6307b", 11),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_go_mod_without_requirements() {
+        assert!(parse_go_mod("module example.com/tool\n\ngo 1.22\n").is_empty());
+        assert!(parse_go_mod("").is_empty());
+    }
+
+    #[test]
+    fn test_parse_go_mod_ignores_comments_and_other_directives() {
+        let content = "module example.com/app\n\n// require example.com/old v1.0.0\nexclude golang.org/x/text v0.3.0\nretract v1.0.1\nrequire \"example.com/quoted\" v1.2.3 // pinned\n";
+
+        let deps = parse_go_mod(content);
+
+        assert_eq!(
+            deps,
+            vec![GoDependency {
+                module: "example.com/quoted".to_string(),
+                version: "v1.2.3".to_string(),
+                line_number: 6,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_build_osv_query_strips_version_prefix() {
+        let deps = parse_go_mod(GO_MOD);
+        let query = build_osv_query(&deps);
+
+        let queries = query["queries"].as_array().unwrap();
+        assert_eq!(queries.len(), 4);
+        assert_eq!(queries[1]["package"]["name"], "golang.org/x/net");
+        assert_eq!(queries[1]["package"]["ecosystem"], "Go");
+        assert_eq!(queries[1]["version"], "0.7.0");
+    }
+
+    #[test]
+    fn test_violations_from_osv_response() {
+        let deps = parse_go_mod(GO_MOD);
+        let response = r#"{"results": [
+            {},
+            {"vulns": [{"id": "GO-2023-1571", "modified": "2023-06-12T18:45:41Z"}, {"id": "GO-2023-1988"}]},
+            {"vulns": []},
+            {"vulns": [{"id": "GO-2022-0603"}]}
+        ]}"#;
+
+        let violations = violations_from_osv_response(response, &deps, 7).unwrap();
+
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].scan_id, 7);
+        assert_eq!(violations[0].control_id, "CC6.7");
+        assert_eq!(violations[0].severity, "high");
+        assert_eq!(violations[0].file_path, "go.mod");
+        assert_eq!(violations[0].line_number, 8);
+        assert_eq!(violations[0].code_snippet, "golang.org/x/net v0.7.0");
+        assert!(violations[0].description.contains("GO-2023-1571, GO-2023-1988"));
+        assert_eq!(violations[1].line_number, 11);
+    }
+
+    #[test]
+    fn test_violations_from_malformed_osv_response() {
+        let deps = parse_go_mod(GO_MOD);
+        assert!(violations_from_osv_response("<html>rate limited</html>", &deps, 1).is_err());
+        assert!(violations_from_osv_response("{}", &deps, 1).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_check_go_mod_without_requirements_skips_request() {
+        // The URL is never contacted when there is nothing to look up
+        let violations = check_go_mod_vulnerabilities("module example.com/tool\n", "http://127.0.0.1:9/", 1)
+            .await
+            .unwrap();
+        assert!(violations.is_empty());
+    }
+}
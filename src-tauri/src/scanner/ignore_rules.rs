@@ -0,0 +1,220 @@
+//! Ignore rules with gitignore semantics
+//!
+//! A project's ignores are built from three sources, in order:
+//! 1. Built-in defaults: `SKIP_DIRECTORIES`, `SKIP_GLOB_PATTERNS` and hidden
+//!    files and directories (`.*`)
+//! 2. The project root's `.gitignore`
+//! 3. The project root's `.rynignore`, for paths that should be tracked by
+//!    git but not scanned
+//!
+//! Later sources take precedence, so a `.rynignore` can re-include a built-in
+//! default with a negated pattern (e.g. `!public/` or `!.github/`). Patterns
+//! are matched relative to the project root. Invalid lines are logged and
+//! skipped rather than failing the scan.
+
+use crate::scanner::constants::{SKIP_DIRECTORIES, SKIP_GLOB_PATTERNS};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+
+/// Project-level ignore file read alongside `.gitignore`
+pub const RYNIGNORE_FILE_NAME: &str = ".rynignore";
+
+/// Project's git ignore file
+pub const GITIGNORE_FILE_NAME: &str = ".gitignore";
+
+/// Source name for the built-in default patterns
+pub const BUILTIN_SOURCE: &str = "built-in";
+
+/// Patterns contributed by one ignore source
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IgnoreSource {
+    /// "built-in", ".gitignore" or ".rynignore"
+    pub name: String,
+    /// Patterns in file order, without comments and blank lines
+    pub patterns: Vec<String>,
+}
+
+/// The rule that ignores a path
+#[derive(Debug, Clone, PartialEq)]
+pub struct IgnoreMatch {
+    /// Source the pattern came from
+    pub source: String,
+    /// Pattern as written in its source
+    pub pattern: String,
+}
+
+/// Compiled ignore rules for one project
+#[derive(Debug, Clone)]
+pub struct ProjectIgnores {
+    root: PathBuf,
+    matcher: Gitignore,
+    sources: Vec<IgnoreSource>,
+}
+
+impl ProjectIgnores {
+    /// Built-in defaults only, for paths outside any project
+    pub fn builtin() -> Self {
+        Self::build(Path::new(""), &[])
+    }
+
+    /// Load the built-in defaults plus the project's `.gitignore` and `.rynignore`
+    ///
+    /// Missing or unreadable ignore files are treated as empty.
+    pub fn load(project_root: &Path) -> Self {
+        let files: Vec<(&str, String)> = [GITIGNORE_FILE_NAME, RYNIGNORE_FILE_NAME]
+            .into_iter()
+            .filter_map(|name| {
+                std::fs::read_to_string(project_root.join(name))
+                    .ok()
+                    .map(|content| (name, content))
+            })
+            .collect();
+        Self::build(project_root, &files)
+    }
+
+    /// Compile the built-in defaults followed by each (source name, file content)
+    fn build(root: &Path, files: &[(&str, String)]) -> Self {
+        let mut builder = GitignoreBuilder::new(root);
+        let mut sources = vec![IgnoreSource {
+            name: BUILTIN_SOURCE.to_string(),
+            patterns: builtin_patterns(),
+        }];
+
+        for pattern in &sources[0].patterns {
+            builder
+                .add_line(None, pattern)
+                .expect("built-in ignore patterns must be valid");
+        }
+
+        for (name, content) in files {
+            let mut patterns = Vec::new();
+            for line in content.lines() {
+                let trimmed = line.trim_end();
+                if trimmed.is_empty() || trimmed.starts_with('#') {
+                    continue;
+                }
+                match builder.add_line(Some(PathBuf::from(name)), trimmed) {
+                    Ok(_) => patterns.push(trimmed.to_string()),
+                    Err(e) => eprintln!("[ryn] Skipping invalid pattern in {}: {}", name, e),
+                }
+            }
+            sources.push(IgnoreSource { name: name.to_string(), patterns });
+        }
+
+        let matcher = builder.build().unwrap_or_else(|e| {
+            eprintln!("[ryn] Failed to build ignore rules, using none: {}", e);
+            Gitignore::empty()
+        });
+
+        ProjectIgnores { root: root.to_path_buf(), matcher, sources }
+    }
+
+    /// Sources in precedence order (later overrides earlier)
+    pub fn sources(&self) -> &[IgnoreSource] {
+        &self.sources
+    }
+
+    /// Whether `path` (absolute or project-relative) is ignored
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        self.matching_rule(path, is_dir).is_some()
+    }
+
+    /// The rule that ignores `path` or one of its parent directories, if any
+    ///
+    /// As with git, a path inside an ignored directory stays ignored even if
+    /// a later pattern would re-include it.
+    pub fn matching_rule(&self, path: &Path, is_dir: bool) -> Option<IgnoreMatch> {
+        let relative = path.strip_prefix(&self.root).unwrap_or(path);
+        let components: Vec<_> = relative
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(name) => Some(name),
+                _ => None,
+            })
+            .collect();
+
+        let mut prefix = PathBuf::new();
+        for (index, name) in components.iter().enumerate() {
+            prefix.push(name);
+            let prefix_is_dir = is_dir || index + 1 < components.len();
+            if let Match::Ignore(glob) = self.matcher.matched(&prefix, prefix_is_dir) {
+                let source = glob
+                    .from()
+                    .map(|from| from.to_string_lossy().to_string())
+                    .unwrap_or_else(|| BUILTIN_SOURCE.to_string());
+                return Some(IgnoreMatch { source, pattern: glob.original().to_string() });
+            }
+        }
+        None
+    }
+}
+
+/// Built-in defaults as gitignore patterns
+///
+/// No trailing slash, so like a `.gitignore` entry they match files and
+/// directories of that name at any depth.
+fn builtin_patterns() -> Vec<String> {
+    SKIP_DIRECTORIES
+        .iter()
+        .chain(SKIP_GLOB_PATTERNS)
+        .map(|pattern| pattern.to_string())
+        .chain(std::iter::once(".*".to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_builtin_skips_default_directories() {
+        let ignores = ProjectIgnores::builtin();
+        assert!(ignores.is_ignored(Path::new("/project/node_modules/lib/index.js"), false));
+        assert!(ignores.is_ignored(Path::new("src/mypkg.egg-info/PKG-INFO"), false));
+        assert!(ignores.is_ignored(Path::new(".github"), true));
+        assert!(!ignores.is_ignored(Path::new("/project/src/main.rs"), false));
+
+        let rule = ignores.matching_rule(Path::new("vendor/pkg/errors.go"), false).unwrap();
+        assert_eq!(rule, IgnoreMatch { source: BUILTIN_SOURCE.to_string(), pattern: "vendor".to_string() });
+    }
+
+    #[test]
+    fn test_load_reads_gitignore_and_rynignore() {
+        let dir = tempfile::Builder::new().prefix("ryntest").tempdir().unwrap();
+        fs::write(dir.path().join(GITIGNORE_FILE_NAME), "# generated\n/generated/\n*.log\n").unwrap();
+        fs::write(dir.path().join(RYNIGNORE_FILE_NAME), "docs/**/*.py\n!public/\n").unwrap();
+
+        let ignores = ProjectIgnores::load(dir.path());
+        let root = dir.path();
+
+        assert!(ignores.is_ignored(&root.join("generated/models.py"), false));
+        // Anchored to the root, unlike the built-in names
+        assert!(!ignores.is_ignored(&root.join("src/generated/models.py"), false));
+        assert!(ignores.is_ignored(&root.join("src/debug.log"), false));
+        assert!(ignores.is_ignored(&root.join("docs/examples/demo.py"), false));
+        // .rynignore re-includes a built-in default
+        assert!(!ignores.is_ignored(&root.join("public/app.js"), false));
+        // Components above the project root aren't matched
+        assert!(!ignores.is_ignored(&root.join("app.py"), false));
+
+        let rule = ignores.matching_rule(&root.join("docs/examples/demo.py"), false).unwrap();
+        assert_eq!(rule.source, RYNIGNORE_FILE_NAME);
+        assert_eq!(rule.pattern, "docs/**/*.py");
+
+        let names: Vec<_> = ignores.sources().iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec![BUILTIN_SOURCE, GITIGNORE_FILE_NAME, RYNIGNORE_FILE_NAME]);
+        assert_eq!(ignores.sources()[1].patterns, vec!["/generated/".to_string(), "*.log".to_string()]);
+    }
+
+    #[test]
+    fn test_negation_cannot_reinclude_inside_ignored_directory() {
+        let dir = tempfile::Builder::new().prefix("ryntest").tempdir().unwrap();
+        fs::write(dir.path().join(RYNIGNORE_FILE_NAME), "!node_modules/pkg/index.js\n").unwrap();
+
+        let ignores = ProjectIgnores::load(dir.path());
+        assert!(ignores.is_ignored(&dir.path().join("node_modules/pkg/index.js"), false));
+    }
+}
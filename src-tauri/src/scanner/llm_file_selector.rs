@@ -2,9 +2,50 @@
 //!
 //! Determines which files should be analyzed with LLM vs regex-only scanning.
 //! Smart mode analyzes ~30-40% of files by focusing on security-relevant code.
+//! Each file gets a relevance score (see [`score_file`]); smart mode selects files
+//! whose score reaches the configured threshold.
 
+use crate::scanner::FrameworkDetector;
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 
+/// Default minimum score for smart mode to send a file to the LLM
+pub const DEFAULT_SMART_THRESHOLD: u32 = 3;
+
+/// Settings key overriding [`DEFAULT_SMART_THRESHOLD`]
+pub const SMART_THRESHOLD_SETTING: &str = "llm_smart_threshold";
+
+/// Security keywords worth one point each (counted once per file)
+const SECURITY_KEYWORDS: [&str; 9] = [
+    "auth", "password", "secret", "token", "db", "sql", "request", "cookie", "session",
+];
+
+/// File name fragments that mark a file as security-relevant
+const SECURITY_FILE_NAMES: [&str; 6] = ["auth", "login", "payment", "user", "admin", "config"];
+
+// Score weights per signal category. Any strong pattern category alone reaches
+// the default threshold; weak signals (file I/O, logging), keywords and file
+// names only select files in combination.
+const AUTH_PATTERN_WEIGHT: u32 = 4;
+const DATABASE_PATTERN_WEIGHT: u32 = 3;
+const NETWORK_PATTERN_WEIGHT: u32 = 3;
+const ENDPOINT_PATTERN_WEIGHT: u32 = 3;
+const SECRETS_PATTERN_WEIGHT: u32 = 3;
+const FILE_IO_PATTERN_WEIGHT: u32 = 1;
+const LOGGING_PATTERN_WEIGHT: u32 = 1;
+const FILE_NAME_WEIGHT: u32 = 2;
+
+/// Breakdown of why smart mode did or did not select a file
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LlmFileSelectorScore {
+    pub file_path: String,
+    pub score: u32,
+    /// Human-readable contribution of each signal, e.g. "database call patterns (+2)"
+    pub reasons: Vec<String>,
+    pub threshold: u32,
+    pub selected: bool,
+}
+
 /// Determines if a file should be analyzed with LLM based on content
 ///
 /// # Arguments
@@ -17,17 +58,128 @@ use std::path::Path;
 ///
 /// # Modes
 /// - `regex_only`: Always returns false (no LLM analysis)
-/// - `smart`: Returns true if file contains security-relevant patterns
+/// - `smart`: Returns true if the file's relevance score reaches [`DEFAULT_SMART_THRESHOLD`],
+///   or for README/docs Markdown and root JSON config that mention secrets
 /// - `analyze_all`: Always returns true (analyze every file)
 pub fn should_analyze_with_llm(file_path: &str, code: &str, scan_mode: &str) -> bool {
+    should_analyze_with_llm_threshold(file_path, code, scan_mode, DEFAULT_SMART_THRESHOLD)
+}
+
+/// Same as [`should_analyze_with_llm`] with an explicit smart mode threshold
+pub fn should_analyze_with_llm_threshold(
+    file_path: &str,
+    code: &str,
+    scan_mode: &str,
+    threshold: u32,
+) -> bool {
     match scan_mode {
         "regex_only" => false,
         "analyze_all" => is_supported_language(file_path),
-        "smart" => is_supported_language(file_path) && is_security_relevant(code),
+        "smart" if FrameworkDetector::is_project_doc_file(Path::new(file_path)) => {
+            contains_secrets_patterns(&code.to_lowercase())
+        }
+        "smart" => is_supported_language(file_path) && score_file(file_path, code, threshold).selected,
         _ => false, // Unknown mode defaults to regex-only
     }
 }
 
+/// Pattern category scored by `score_file`: reason label, detector and weight
+type PatternCategory = (&'static str, fn(&str) -> bool, u32);
+
+/// Compute the smart mode relevance score for a file
+///
+/// Signals:
+/// - Unique security keywords (auth, password, secret, ...): +1 each
+/// - Authentication/authorization patterns: +4
+/// - Database, network, endpoint and secrets patterns: +3 each
+/// - File I/O and logging patterns: +1 each
+/// - Security-relevant file name (auth, login, payment, user, admin, config): +2
+///
+/// Unsupported languages are scored anyway but never selected.
+pub fn score_file(file_path: &str, code: &str, threshold: u32) -> LlmFileSelectorScore {
+    let code_lower = code.to_lowercase();
+    let mut score = 0;
+    let mut reasons = Vec::new();
+
+    let keywords = matched_security_keywords(&code_lower);
+    if !keywords.is_empty() {
+        score += keywords.len() as u32;
+        reasons.push(format!(
+            "security keywords: {} (+{})",
+            keywords.join(", "),
+            keywords.len()
+        ));
+    }
+
+    let categories: [PatternCategory; 7] = [
+        ("authentication patterns", contains_auth_patterns, AUTH_PATTERN_WEIGHT),
+        ("database call patterns", contains_database_patterns, DATABASE_PATTERN_WEIGHT),
+        ("network call patterns", contains_network_patterns, NETWORK_PATTERN_WEIGHT),
+        ("API endpoint patterns", contains_endpoint_patterns, ENDPOINT_PATTERN_WEIGHT),
+        ("secrets handling patterns", contains_secrets_patterns, SECRETS_PATTERN_WEIGHT),
+        ("file I/O patterns", contains_file_io_patterns, FILE_IO_PATTERN_WEIGHT),
+        ("logging patterns", contains_logging_patterns, LOGGING_PATTERN_WEIGHT),
+    ];
+    for (label, matches, weight) in categories {
+        if matches(&code_lower) {
+            score += weight;
+            reasons.push(format!("{} (+{})", label, weight));
+        }
+    }
+
+    if let Some(name) = security_file_name_match(file_path) {
+        score += FILE_NAME_WEIGHT;
+        reasons.push(format!("file name matches '{}' (+{})", name, FILE_NAME_WEIGHT));
+    }
+
+    let supported = is_supported_language(file_path);
+    if !supported {
+        reasons.push("unsupported language".to_string());
+    }
+
+    LlmFileSelectorScore {
+        file_path: file_path.to_string(),
+        score,
+        reasons,
+        threshold,
+        selected: supported && score >= threshold,
+    }
+}
+
+/// Security keywords present in already-lowercased code
+///
+/// Short keywords (auth, db, sql) must start an identifier segment so that
+/// e.g. "feedback" does not count as "db"; longer ones match anywhere.
+fn matched_security_keywords(code: &str) -> Vec<&'static str> {
+    let segments: Vec<&str> = code
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    SECURITY_KEYWORDS
+        .iter()
+        .copied()
+        .filter(|keyword| {
+            if keyword.len() >= 5 {
+                code.contains(keyword)
+            } else {
+                segments.iter().any(|segment| segment.starts_with(keyword))
+            }
+        })
+        .collect()
+}
+
+/// First security-relevant fragment found in the file's name (not its directories)
+fn security_file_name_match(file_path: &str) -> Option<&'static str> {
+    let name = Path::new(file_path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    SECURITY_FILE_NAMES.iter().copied().find(|fragment| name.contains(fragment))
+}
+
 /// Check if file extension is supported for LLM analysis
 fn is_supported_language(file_path: &str) -> bool {
     let path = Path::new(file_path);
@@ -222,6 +374,23 @@ fn contains_file_io_patterns(code: &str) -> bool {
     file_keywords.iter().any(|keyword| code.contains(keyword))
 }
 
+/// Check for logging patterns (weak on their own, but logs can leak secrets)
+fn contains_logging_patterns(code: &str) -> bool {
+    let logging_keywords = [
+        "logger.",
+        "logging.",
+        "console.log(",
+        "log.info(",
+        "log.debug(",
+        "log.warn",
+        "log.error(",
+        "log.printf(",
+        "winston",
+    ];
+
+    logging_keywords.iter().any(|keyword| code.contains(keyword))
+}
+
 /// Check for network operation patterns
 fn contains_network_patterns(code: &str) -> bool {
     let network_keywords = [
@@ -274,6 +443,21 @@ mod tests {
         assert!(!should_analyze_with_llm("README.md", code, "analyze_all"));
     }
 
+    #[test]
+    fn test_should_analyze_smart_mode_docs_and_config_with_secrets() {
+        let readme = "```\nexport DB_PASSWORD=hunter2\n```";
+        assert!(should_analyze_with_llm("README.md", readme, "smart"));
+        assert!(should_analyze_with_llm("config.json", r#"{"client_secret": "abc"}"#, "smart"));
+        assert!(should_analyze_with_llm("docs/setup.md", readme, "smart"));
+    }
+
+    #[test]
+    fn test_should_analyze_smart_mode_skips_other_docs_and_json() {
+        assert!(!should_analyze_with_llm("README.md", "# Project\nA small library.", "smart"));
+        assert!(!should_analyze_with_llm("src/fixtures/users.json", r#"{"password": "x"}"#, "smart"));
+        assert!(!should_analyze_with_llm("README.md", "password", "regex_only"));
+    }
+
     #[test]
     fn test_should_analyze_unknown_mode() {
         let code = "password = 'test'";
@@ -482,4 +666,194 @@ def delete_user(user_id):
         let code = "export const API_KEY = process.env.STRIPE_KEY;";
         assert!(should_analyze_with_llm("config.ts", code, "smart"));
     }
+
+    // ===== Smart mode scoring calibration =====
+
+    #[test]
+    fn test_score_empty_file_is_zero() {
+        let score = score_file("empty.py", "", DEFAULT_SMART_THRESHOLD);
+        assert_eq!(score.score, 0);
+        assert!(score.reasons.is_empty());
+        assert!(!score.selected);
+    }
+
+    #[test]
+    fn test_score_counts_unique_keywords_once() {
+        let code = "token = token + token";
+        let score = score_file("calc.py", code, DEFAULT_SMART_THRESHOLD);
+        assert_eq!(score.score, 1);
+        assert!(score.reasons[0].contains("token"));
+    }
+
+    #[test]
+    fn test_score_multiple_keywords_reach_threshold() {
+        let code = "def f(cookie, token, request): return cookie";
+        let score = score_file("handler.py", code, DEFAULT_SMART_THRESHOLD);
+        assert_eq!(score.score, 3);
+        assert!(score.selected);
+    }
+
+    #[test]
+    fn test_score_two_keywords_below_threshold() {
+        let code = "def f(cookie, token): return cookie";
+        let score = score_file("handler.py", code, DEFAULT_SMART_THRESHOLD);
+        assert_eq!(score.score, 2);
+        assert!(!score.selected);
+    }
+
+    #[test]
+    fn test_score_short_keywords_require_identifier_start() {
+        // "feedback" and "mysqlike" style substrings must not count as db/sql
+        let code = "feedback = collect_feedback(); nosqlish = 1";
+        let score = score_file("survey.py", code, DEFAULT_SMART_THRESHOLD);
+        assert_eq!(score.score, 0);
+    }
+
+    #[test]
+    fn test_score_short_keywords_match_identifier_segments() {
+        let code = "db_conn = make(); sql_text = build(); auth_header = h";
+        let keywords = matched_security_keywords(&code.to_lowercase());
+        assert_eq!(keywords, vec!["auth", "db", "sql"]);
+    }
+
+    #[test]
+    fn test_score_long_keywords_match_inside_camel_case() {
+        let keywords = matched_security_keywords(&"const userPassword = getSecretValue();".to_lowercase());
+        assert_eq!(keywords, vec!["password", "secret"]);
+    }
+
+    #[test]
+    fn test_score_network_pattern_weight() {
+        let code = "fetch(url).then(render)";
+        let score = score_file("client.js", code, DEFAULT_SMART_THRESHOLD);
+        assert_eq!(score.score, NETWORK_PATTERN_WEIGHT);
+        assert!(score.reasons.iter().any(|r| r.starts_with("network call patterns")));
+        assert!(score.selected);
+    }
+
+    #[test]
+    fn test_score_database_pattern_weight() {
+        let code = "cursor.execute(query_text)";
+        let score = score_file("report.py", code, DEFAULT_SMART_THRESHOLD);
+        assert_eq!(score.score, DATABASE_PATTERN_WEIGHT);
+        assert!(score.reasons.iter().any(|r| r.starts_with("database call patterns")));
+    }
+
+    #[test]
+    fn test_score_network_plus_database_selected() {
+        let code = "rows = cursor.execute(q)\nrequests.post(url, json=rows)";
+        let score = score_file("sync.py", code, DEFAULT_SMART_THRESHOLD);
+        // db (+3), network (+3), "request" keyword (+1)
+        assert_eq!(score.score, 7);
+        assert!(score.selected);
+    }
+
+    #[test]
+    fn test_score_auth_pattern_alone_selected() {
+        let code = "@login_required\ndef index(): pass";
+        let score = score_file("views.py", code, DEFAULT_SMART_THRESHOLD);
+        assert_eq!(score.score, AUTH_PATTERN_WEIGHT);
+        assert!(score.selected);
+    }
+
+    #[test]
+    fn test_score_file_name_alone_below_threshold() {
+        let score = score_file("src/payment.py", "x = 1", DEFAULT_SMART_THRESHOLD);
+        assert_eq!(score.score, FILE_NAME_WEIGHT);
+        assert!(score.reasons.iter().any(|r| r.contains("'payment'")));
+        assert!(!score.selected);
+    }
+
+    #[test]
+    fn test_score_file_name_plus_keyword_selected() {
+        let score = score_file("admin.py", "session_timeout = 30", DEFAULT_SMART_THRESHOLD);
+        assert_eq!(score.score, FILE_NAME_WEIGHT + 1);
+        assert!(score.selected);
+    }
+
+    #[test]
+    fn test_score_file_name_ignores_directories() {
+        assert_eq!(security_file_name_match("auth/helpers.py"), None);
+        assert_eq!(security_file_name_match("lib/UserService.ts"), Some("user"));
+        assert_eq!(security_file_name_match("LoginForm.tsx"), Some("login"));
+    }
+
+    #[test]
+    fn test_score_file_io_alone_not_selected() {
+        let code = "with open(path) as f: data = f.read()";
+        let score = score_file("loader.py", code, DEFAULT_SMART_THRESHOLD);
+        assert_eq!(score.score, FILE_IO_PATTERN_WEIGHT);
+        assert!(!score.selected);
+    }
+
+    #[test]
+    fn test_score_logging_alone_not_selected() {
+        let code = "logger.info('cache warmed in %s ms', elapsed)";
+        let score = score_file("cache.py", code, DEFAULT_SMART_THRESHOLD);
+        assert_eq!(score.score, LOGGING_PATTERN_WEIGHT);
+        assert!(score.reasons.iter().any(|r| r.starts_with("logging patterns")));
+        assert!(!score.selected);
+    }
+
+    #[test]
+    fn test_score_weak_signals_combine() {
+        let code = "logger.info('saving upload for %s', session_id)\nwith open(path, 'wb') as f: f.write(blob)";
+        let score = score_file("storage.py", code, DEFAULT_SMART_THRESHOLD);
+        // file I/O (+1), logging (+1), "session" keyword (+1)
+        assert_eq!(score.score, 3);
+        assert!(score.selected);
+    }
+
+    #[test]
+    fn test_score_custom_threshold() {
+        let code = "fetch(url)";
+        assert!(score_file("client.js", code, 3).selected);
+        assert!(!score_file("client.js", code, 4).selected);
+        assert!(should_analyze_with_llm_threshold("client.js", code, "smart", 3));
+        assert!(!should_analyze_with_llm_threshold("client.js", code, "smart", 4));
+    }
+
+    #[test]
+    fn test_score_zero_threshold_selects_any_supported_file() {
+        assert!(score_file("math.py", "x = 1", 0).selected);
+        assert!(!score_file("notes.md", "x = 1", 0).selected);
+    }
+
+    #[test]
+    fn test_score_unsupported_language_never_selected() {
+        let code = "password = 'x'; cursor.execute(q); requests.get(u)";
+        let score = score_file("auth.md", code, DEFAULT_SMART_THRESHOLD);
+        assert!(score.score >= DEFAULT_SMART_THRESHOLD);
+        assert!(!score.selected);
+        assert!(score.reasons.iter().any(|r| r == "unsupported language"));
+    }
+
+    #[test]
+    fn test_score_real_world_login_handler() {
+        let code = r#"
+@app.route('/login', methods=['POST'])
+def login():
+    user = User.query.filter(User.email == request.form['email']).first()
+    if user and check_password_hash(user.password, request.form['password']):
+        session['user_id'] = user.id
+        return redirect('/')
+"#;
+        let score = score_file("auth.py", code, DEFAULT_SMART_THRESHOLD);
+        // keywords password/request/session (+3), db (+3), endpoint (+3),
+        // secrets (+3), file name (+2)
+        assert_eq!(score.score, 14);
+        assert!(score.selected);
+    }
+
+    #[test]
+    fn test_score_real_world_presentational_component() {
+        let code = r#"
+export function Badge({ label, color }) {
+  return <span className={`badge badge-${color}`}>{label}</span>;
+}
+"#;
+        let score = score_file("Badge.tsx", code, DEFAULT_SMART_THRESHOLD);
+        assert_eq!(score.score, 0);
+        assert!(!should_analyze_with_llm("Badge.tsx", code, "smart"));
+    }
 }
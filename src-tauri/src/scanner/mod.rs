@@ -1,14 +1,30 @@
 //! Code scanning engine for SOC 2 compliance
 //!
-//! Provides framework detection, file watching, and AST parsing.
+//! Provides framework detection, file watching, auto-rescan, AST parsing, scan profiling, scan event delivery, test fixture handling, `.gitignore`/`.rynignore` rules, Go and package-manager dependency vulnerability lookups, baseline suppression files, and multi-root workspaces.
 
 pub mod constants;
 pub mod framework_detector;
 pub mod file_watcher;
+pub mod auto_rescan;
 pub mod tree_sitter_utils;
 pub mod llm_file_selector;
+pub mod go_modules;
+pub mod dependencies;
+pub mod scan_excludes;
+pub mod ignore_rules;
+pub mod scan_profiler;
+pub mod scan_events;
+pub mod test_fixtures;
+pub mod baseline;
+pub mod project_roots;
 
-pub use constants::SKIP_DIRECTORIES;
-pub use framework_detector::FrameworkDetector;
-pub use file_watcher::{FileWatcher, FileEvent, WatcherHandle};
+pub use constants::{SKIP_DIRECTORIES, SKIP_GLOB_PATTERNS, VIRTUAL_ENV_MARKERS};
+pub use framework_detector::{FrameworkDetector, FrameworkCache, FRAMEWORK_CACHE};
+pub use file_watcher::{FileWatcher, FileEvent, WatcherHandle, WatcherStats};
+pub use auto_rescan::AutoRescanConfig;
+pub use scan_events::{NullEventEmitter, ScanEventEmitter};
+pub use test_fixtures::FixtureSettings;
+pub use ignore_rules::{IgnoreMatch, IgnoreSource, ProjectIgnores, RYNIGNORE_FILE_NAME};
+pub use baseline::{Baseline, BaselineEntry, BASELINE_FILE_NAME};
+pub use project_roots::{ScanRoot, ScanRoots};
 pub use tree_sitter_utils::{CodeParser, ParseResult, ASTNode};
@@ -0,0 +1,154 @@
+//! Directories scanned together as one project (multi-root workspaces)
+//!
+//! A project's own path is its primary root and keeps plain relative
+//! violation paths. Extra roots from `project_roots` are walked by the same
+//! scan; their violation paths are prefixed with `@{label}/` so a path still
+//! names exactly one file, and `ScanRoots::resolve` maps it back to disk.
+
+use crate::models::{Project, ProjectRoot};
+use std::path::{Path, PathBuf};
+
+/// Starts the first component of a violation path in an extra root
+pub const ROOT_LABEL_PREFIX: char = '@';
+
+/// One directory a scan walks
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScanRoot {
+    /// None for the project's primary root
+    pub label: Option<String>,
+    pub path: PathBuf,
+}
+
+impl ScanRoot {
+    /// Path recorded on violations for `relative`, a path inside this root
+    pub fn violation_path(&self, relative: &Path) -> String {
+        let relative = relative.to_string_lossy();
+        match &self.label {
+            Some(label) => format!("{}{}/{}", ROOT_LABEL_PREFIX, label, relative),
+            None => relative.to_string(),
+        }
+    }
+}
+
+/// A project's primary root followed by its extra roots
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScanRoots {
+    roots: Vec<ScanRoot>,
+}
+
+impl ScanRoots {
+    pub fn new(project_path: &str, extra: &[ProjectRoot]) -> Self {
+        let primary = ScanRoot { label: None, path: PathBuf::from(project_path) };
+        let extra = extra.iter().map(|root| ScanRoot {
+            label: Some(root.label.clone()),
+            path: PathBuf::from(&root.path),
+        });
+        Self { roots: std::iter::once(primary).chain(extra).collect() }
+    }
+
+    /// Roots of `project` and its `project_roots` rows
+    pub fn for_project(project: &Project, extra: &[ProjectRoot]) -> Self {
+        Self::new(&project.path, extra)
+    }
+
+    pub fn primary(&self) -> &ScanRoot {
+        &self.roots[0]
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ScanRoot> {
+        self.roots.iter()
+    }
+
+    /// Whether the project has extra roots
+    pub fn is_multi_root(&self) -> bool {
+        self.roots.len() > 1
+    }
+
+    /// Find the root containing `path` and the path inside it
+    ///
+    /// Roots never overlap (`add_project_root` rejects nested directories).
+    pub fn locate(&self, path: &Path) -> Option<(&ScanRoot, PathBuf)> {
+        self.roots
+            .iter()
+            .find_map(|root| path.strip_prefix(&root.path).ok().map(|relative| (root, relative.to_path_buf())))
+    }
+
+    /// Whether `path` is, contains or is inside one of the roots
+    pub fn overlaps(&self, path: &Path) -> bool {
+        self.roots.iter().any(|root| path.starts_with(&root.path) || root.path.starts_with(path))
+    }
+
+    /// Violation path for a file on disk, or None if it's outside every root
+    pub fn violation_path(&self, path: &Path) -> Option<String> {
+        self.locate(path).map(|(root, relative)| root.violation_path(&relative))
+    }
+
+    /// Map a violation path back to its root and the path inside that root
+    ///
+    /// Paths without a known `@{label}/` prefix belong to the primary root.
+    pub fn resolve<'a>(&'a self, file_path: &'a str) -> (&'a ScanRoot, &'a str) {
+        if let Some((label, relative)) = file_path
+            .strip_prefix(ROOT_LABEL_PREFIX)
+            .and_then(|rest| rest.split_once('/'))
+        {
+            if let Some(root) = self.roots.iter().find(|root| root.label.as_deref() == Some(label)) {
+                return (root, relative);
+            }
+        }
+        (self.primary(), file_path)
+    }
+
+    /// Absolute path of the file a violation path names
+    pub fn full_path(&self, file_path: &str) -> PathBuf {
+        let (root, relative) = self.resolve(file_path);
+        root.path.join(relative)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roots() -> ScanRoots {
+        let web = ProjectRoot {
+            id: 1,
+            project_id: 1,
+            path: "/work/web".to_string(),
+            label: "web".to_string(),
+            framework: None,
+            created_at: String::new(),
+        };
+        ScanRoots::new("/work/api", &[web])
+    }
+
+    #[test]
+    fn test_violation_paths_round_trip() {
+        let roots = roots();
+        assert!(roots.is_multi_root());
+
+        let api_file = Path::new("/work/api/src/app.py");
+        let web_file = Path::new("/work/web/src/App.tsx");
+        assert_eq!(roots.violation_path(api_file).as_deref(), Some("src/app.py"));
+        assert_eq!(roots.violation_path(web_file).as_deref(), Some("@web/src/App.tsx"));
+        assert_eq!(roots.violation_path(Path::new("/elsewhere/x.py")), None);
+
+        assert_eq!(roots.full_path("src/app.py"), api_file);
+        assert_eq!(roots.full_path("@web/src/App.tsx"), web_file);
+        let (root, relative) = roots.resolve("@web/src/App.tsx");
+        assert_eq!((root.label.as_deref(), relative), (Some("web"), "src/App.tsx"));
+    }
+
+    #[test]
+    fn test_unknown_label_belongs_to_primary_root() {
+        let roots = roots();
+        assert_eq!(roots.full_path("@types/index.d.ts"), Path::new("/work/api/@types/index.d.ts"));
+    }
+
+    #[test]
+    fn test_overlaps() {
+        let roots = roots();
+        assert!(roots.overlaps(Path::new("/work/api/vendor")));
+        assert!(roots.overlaps(Path::new("/work")));
+        assert!(!roots.overlaps(Path::new("/work/jobs")));
+    }
+}
@@ -0,0 +1,61 @@
+//! Where scan progress events go
+//!
+//! The scan pipeline reports progress through [`ScanEventEmitter`] instead of
+//! a Tauri `AppHandle` directly, so it can also run headless (the `ryn` CLI).
+//! In the desktop app events go to the frontend; headless scans use
+//! [`NullEventEmitter`], which discards them.
+
+use serde::Serialize;
+use tauri::Emitter;
+
+/// Sink for scan events (`scan-progress`, `cost-limit-reached`, ...)
+pub trait ScanEventEmitter: Clone + Send + Sync + 'static {
+    /// Send `payload` as `event`; an error means nobody could receive it
+    fn emit_event<S: Serialize + Clone>(&self, event: &str, payload: S) -> Result<(), String>;
+}
+
+impl<R: tauri::Runtime> ScanEventEmitter for tauri::AppHandle<R> {
+    fn emit_event<S: Serialize + Clone>(&self, event: &str, payload: S) -> Result<(), String> {
+        self.emit(event, payload).map_err(|e| e.to_string())
+    }
+}
+
+/// Discards every event, for scans run without a frontend
+///
+/// Emitting always fails, so prompts that wait on a user answer (the cost
+/// limit) are treated as declined instead of waiting forever.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NullEventEmitter;
+
+impl ScanEventEmitter for NullEventEmitter {
+    fn emit_event<S: Serialize + Clone>(&self, event: &str, _payload: S) -> Result<(), String> {
+        Err(format!("No frontend to receive {}", event))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tauri::Listener;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_null_emitter_discards_events() {
+        let result = NullEventEmitter.emit_event("cost-limit-reached", 1);
+        assert_eq!(result, Err("No frontend to receive cost-limit-reached".to_string()));
+    }
+
+    #[test]
+    fn test_app_handle_emits_to_listeners() {
+        let app = tauri::test::mock_app();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let received_clone = received.clone();
+        app.listen("scan-progress", move |event| {
+            received_clone.lock().unwrap().push(event.payload().to_string());
+        });
+
+        app.handle().emit_event("scan-progress", 42).unwrap();
+
+        assert_eq!(*received.lock().unwrap(), vec!["42".to_string()]);
+    }
+}
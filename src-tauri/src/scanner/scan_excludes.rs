@@ -0,0 +1,116 @@
+//! Per-project scan exclusion patterns
+//!
+//! Users can exclude paths beyond `.gitignore`/`SKIP_DIRECTORIES`, e.g.
+//! `["tests/**", "fixtures/**", "*.min.js"]`. Patterns are stored as a JSON
+//! array in the settings table under [`setting_key`] and matched against paths
+//! relative to the project root. Excluded files are neither rule-scanned nor
+//! sent to the LLM.
+
+use anyhow::{anyhow, Context, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::path::Path;
+
+/// Settings key holding a project's exclusion patterns
+pub fn setting_key(project_id: i64) -> String {
+    format!("scan_exclude_patterns:{}", project_id)
+}
+
+/// Parse the stored JSON array of patterns (missing or malformed means none)
+pub fn parse_patterns(value: &str) -> Vec<String> {
+    serde_json::from_str(value).unwrap_or_default()
+}
+
+/// Check that every pattern compiles and none excludes the whole project
+///
+/// Patterns made only of wildcards and separators (`*`, `**`, `**/*`, `/`)
+/// or pointing at the root itself (`.`, `./`, empty) are rejected.
+pub fn validate_patterns(patterns: &[String]) -> Result<()> {
+    for pattern in patterns {
+        let trimmed = normalize(pattern);
+        if trimmed.is_empty() || trimmed == "." {
+            return Err(anyhow!("Pattern excludes the project root: {:?}", pattern));
+        }
+        if trimmed.chars().all(|c| c == '*' || c == '/') {
+            return Err(anyhow!("Pattern excludes every file in the project: {:?}", pattern));
+        }
+        Glob::new(trimmed).with_context(|| format!("Invalid glob pattern: {:?}", pattern))?;
+    }
+    Ok(())
+}
+
+/// Compile patterns into a matcher for project-relative paths
+pub fn build_exclude_set(patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(normalize(pattern))
+            .with_context(|| format!("Invalid glob pattern: {:?}", pattern))?;
+        builder.add(glob);
+    }
+    builder.build().context("Failed to build exclusion set")
+}
+
+/// Whether `path` (relative to the project root) matches an exclusion pattern
+pub fn is_excluded(exclude_set: &GlobSet, relative_path: &Path) -> bool {
+    !exclude_set.is_empty() && exclude_set.is_match(relative_path)
+}
+
+/// Strip surrounding whitespace and leading `./` or `/` so patterns are root-relative
+fn normalize(pattern: &str) -> &str {
+    let trimmed = pattern.trim();
+    let trimmed = trimmed.strip_prefix("./").unwrap_or(trimmed);
+    trimmed.trim_start_matches('/')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patterns(list: &[&str]) -> Vec<String> {
+        list.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn test_validate_accepts_typical_patterns() {
+        assert!(validate_patterns(&patterns(&["tests/**", "fixtures/**", "*.min.js"])).is_ok());
+        assert!(validate_patterns(&[]).is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_root_patterns() {
+        for pattern in ["**", "*", "**/*", "/**", "./", ".", "", "  "] {
+            assert!(
+                validate_patterns(&patterns(&[pattern])).is_err(),
+                "{:?} should be rejected",
+                pattern
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_glob() {
+        assert!(validate_patterns(&patterns(&["src/[a-"])).is_err());
+    }
+
+    #[test]
+    fn test_exclude_set_matches_relative_paths() {
+        let set = build_exclude_set(&patterns(&["tests/**", "./fixtures/**", "*.min.js"])).unwrap();
+        assert!(is_excluded(&set, Path::new("tests/test_app.py")));
+        assert!(is_excluded(&set, Path::new("tests/unit/test_models.py")));
+        assert!(is_excluded(&set, Path::new("fixtures/data.json")));
+        assert!(is_excluded(&set, Path::new("static/vendor/jquery.min.js")));
+        assert!(!is_excluded(&set, Path::new("src/app.py")));
+        assert!(!is_excluded(&set, Path::new("src/tests_helper.py")));
+    }
+
+    #[test]
+    fn test_empty_set_excludes_nothing() {
+        let set = build_exclude_set(&[]).unwrap();
+        assert!(!is_excluded(&set, Path::new("app.py")));
+    }
+
+    #[test]
+    fn test_parse_patterns() {
+        assert_eq!(parse_patterns(r#"["tests/**"]"#), vec!["tests/**"]);
+        assert!(parse_patterns("not json").is_empty());
+    }
+}
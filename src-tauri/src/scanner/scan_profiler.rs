@@ -0,0 +1,222 @@
+//! Scan pipeline profiling
+//!
+//! `scan_project_internal` is instrumented with `tracing` spans for each phase
+//! (`file_walk`, `rule_execution` per file, `llm_analysis`, `tree_sitter_enrichment`,
+//! `merge_violations`). Spans are no-ops unless a scan runs with
+//! `profile_scan`, in which case [`ScanProfiler`] records how long each span
+//! lived and writes `scan-{id}-profile.json` in Chrome trace format
+//! (load it in `chrome://tracing` or the DevTools Performance panel).
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread::ThreadId;
+use std::time::Instant;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::{Dispatch, Subscriber};
+use tracing_subscriber::layer::{Context as LayerContext, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+/// Directory (inside the data directory) holding scan profiles
+pub const PROFILE_DIR: &str = "profiles";
+
+/// One complete ("X" phase) event in Chrome trace format
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraceEvent {
+    pub name: String,
+    /// Event phase; always "X" (complete event with duration)
+    pub ph: String,
+    /// Start time in microseconds since the profile started
+    pub ts: u64,
+    /// Duration in microseconds
+    pub dur: u64,
+    pub pid: u32,
+    pub tid: u64,
+    /// Span fields, e.g. the file path of a `rule_execution` span
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub args: BTreeMap<String, String>,
+}
+
+/// Path of the profile written for a scan
+pub fn profile_path(data_dir: &Path, scan_id: i64) -> PathBuf {
+    data_dir
+        .join(PROFILE_DIR)
+        .join(format!("scan-{}-profile.json", scan_id))
+}
+
+/// Collects span timings for a single profiled scan
+#[derive(Clone)]
+pub struct ScanProfiler {
+    events: Arc<Mutex<Vec<TraceEvent>>>,
+    threads: Arc<Mutex<HashMap<ThreadId, u64>>>,
+    start: Instant,
+}
+
+impl Default for ScanProfiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScanProfiler {
+    pub fn new() -> Self {
+        Self {
+            events: Arc::new(Mutex::new(Vec::new())),
+            threads: Arc::new(Mutex::new(HashMap::new())),
+            start: Instant::now(),
+        }
+    }
+
+    /// Dispatcher recording into this profiler; attach it with `WithSubscriber::with_subscriber`
+    pub fn dispatch(&self) -> Dispatch {
+        Dispatch::new(tracing_subscriber::registry().with(self.clone()))
+    }
+
+    /// Recorded events ordered by start time (enclosing spans first)
+    pub fn events(&self) -> Vec<TraceEvent> {
+        let mut events = self.events.lock().unwrap().clone();
+        events.sort_by(|a, b| a.ts.cmp(&b.ts).then(b.dur.cmp(&a.dur)));
+        events
+    }
+
+    /// Write the recorded events as a Chrome trace JSON array
+    pub fn write(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create profile directory {:?}", parent))?;
+        }
+
+        let json = serde_json::to_string_pretty(&self.events())
+            .context("Failed to serialize scan profile")?;
+        std::fs::write(path, json)
+            .with_context(|| format!("Failed to write scan profile {:?}", path))?;
+
+        Ok(())
+    }
+
+    /// Small stable id for the current thread (Chrome traces need numeric tids)
+    fn thread_id(&self) -> u64 {
+        let mut threads = self.threads.lock().unwrap();
+        let next = threads.len() as u64 + 1;
+        *threads.entry(std::thread::current().id()).or_insert(next)
+    }
+}
+
+/// Per-span state stored in the registry's span extensions
+struct SpanTiming {
+    start: Instant,
+    args: BTreeMap<String, String>,
+}
+
+/// Records span fields as strings
+struct ArgsVisitor<'a>(&'a mut BTreeMap<String, String>);
+
+impl Visit for ArgsVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+impl<S> Layer<S> for ScanProfiler
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: LayerContext<'_, S>) {
+        let mut args = BTreeMap::new();
+        attrs.record(&mut ArgsVisitor(&mut args));
+
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(SpanTiming {
+                start: Instant::now(),
+                args,
+            });
+        }
+    }
+
+    /// A span's duration runs from creation to close (last handle dropped),
+    /// so phase spans don't need to be entered across `.await` points
+    fn on_close(&self, id: Id, ctx: LayerContext<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let extensions = span.extensions();
+        let Some(timing) = extensions.get::<SpanTiming>() else {
+            return;
+        };
+
+        let event = TraceEvent {
+            name: span.name().to_string(),
+            ph: "X".to_string(),
+            ts: timing.start.duration_since(self.start).as_micros() as u64,
+            dur: timing.start.elapsed().as_micros() as u64,
+            pid: std::process::id(),
+            tid: self.thread_id(),
+            args: timing.args.clone(),
+        };
+        self.events.lock().unwrap().push(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_profiler_records_spans_with_fields() {
+        let profiler = ScanProfiler::new();
+
+        tracing::dispatcher::with_default(&profiler.dispatch(), || {
+            let phase = tracing::info_span!("file_walk");
+            tracing::info_span!("rule_execution", file = "app.py").in_scope(|| {});
+            drop(phase);
+        });
+
+        let events = profiler.events();
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|e| e.ph == "X" && e.pid == std::process::id()));
+
+        let rule = events.iter().find(|e| e.name == "rule_execution").unwrap();
+        assert_eq!(rule.args.get("file").map(String::as_str), Some("app.py"));
+        assert!(events.iter().any(|e| e.name == "file_walk"));
+    }
+
+    #[test]
+    fn test_spans_outside_profiler_are_not_recorded() {
+        let profiler = ScanProfiler::new();
+        let _ = profiler.dispatch();
+
+        tracing::info_span!("file_walk").in_scope(|| {});
+
+        assert!(profiler.events().is_empty());
+    }
+
+    #[test]
+    fn test_write_produces_chrome_trace_array() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = profile_path(temp_dir.path(), 7);
+        let profiler = ScanProfiler::new();
+
+        tracing::dispatcher::with_default(&profiler.dispatch(), || {
+            tracing::info_span!("merge_violations").in_scope(|| {});
+        });
+        profiler.write(&path).unwrap();
+
+        assert!(path.ends_with("profiles/scan-7-profile.json"));
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        let event = &json.as_array().unwrap()[0];
+        for key in ["name", "ph", "ts", "dur", "pid", "tid"] {
+            assert!(event.get(key).is_some(), "missing {}", key);
+        }
+        assert_eq!(event["name"], "merge_violations");
+    }
+}
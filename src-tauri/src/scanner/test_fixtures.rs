@@ -0,0 +1,157 @@
+//! Test fixture directories
+//!
+//! Fixture files (`tests/fixtures/`, `test_data/`, ...) often hold secrets
+//! copied from production "just to test", so they are scanned by default.
+//! Findings in them are reported one severity level lower than elsewhere.
+//! Setting `scan_test_fixtures` to "false" skips fixture directories instead.
+//!
+//! `fixture_path_patterns` is stored as a JSON array of directory paths, e.g.
+//! `["tests/fixtures", "testdata"]`. A pattern matches when its components
+//! appear consecutively anywhere in a file's path.
+
+use crate::models::{Severity, Violation};
+use std::path::{Component, Path};
+
+/// Settings key for whether fixture directories are scanned ("true"/"false")
+pub const SCAN_SETTING_KEY: &str = "scan_test_fixtures";
+
+/// Settings key holding the fixture directory patterns
+pub const PATTERNS_SETTING_KEY: &str = "fixture_path_patterns";
+
+/// Fixture directories recognized when no patterns are configured
+pub const DEFAULT_PATTERNS: &[&str] = &["tests/fixtures", "test_data", "testdata", "__fixtures__"];
+
+/// How fixture directories are treated during a scan
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixtureSettings {
+    /// Scan fixture directories (at reduced severity) rather than skipping them
+    pub scan: bool,
+    pub patterns: Vec<String>,
+}
+
+impl Default for FixtureSettings {
+    fn default() -> Self {
+        Self {
+            scan: true,
+            patterns: DEFAULT_PATTERNS.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+}
+
+impl FixtureSettings {
+    /// Build from stored setting values; missing or malformed values take their defaults
+    pub fn from_values(scan: Option<&str>, patterns: Option<&str>) -> Self {
+        let defaults = Self::default();
+        Self {
+            scan: scan.map(|value| value != "false").unwrap_or(defaults.scan),
+            patterns: patterns
+                .and_then(|value| serde_json::from_str(value).ok())
+                .unwrap_or(defaults.patterns),
+        }
+    }
+
+    /// Whether `path` lies inside a fixture directory
+    pub fn is_fixture_path(&self, path: &Path) -> bool {
+        let components: Vec<&str> = path
+            .components()
+            .filter_map(|c| match c {
+                Component::Normal(name) => name.to_str(),
+                _ => None,
+            })
+            .collect();
+
+        self.patterns.iter().any(|pattern| {
+            let parts: Vec<&str> = pattern.split('/').filter(|p| !p.is_empty()).collect();
+            // The last component is the file itself, so only directories can match
+            !parts.is_empty()
+                && components.len() > parts.len()
+                && components[..components.len() - 1]
+                    .windows(parts.len())
+                    .any(|window| window == parts.as_slice())
+        })
+    }
+
+    /// Whether the scan should skip `path` as a fixture
+    pub fn should_skip(&self, path: &Path) -> bool {
+        !self.scan && self.is_fixture_path(path)
+    }
+
+    /// Lower the severity of violations found in fixture files by one level
+    pub fn downgrade_violations(&self, violations: &mut [Violation]) {
+        for violation in violations {
+            if !self.is_fixture_path(Path::new(&violation.file_path)) {
+                continue;
+            }
+            if let Some(severity) = Severity::from_str(&violation.severity) {
+                violation.severity = severity.downgrade().as_str().to_string();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn violation(file_path: &str, severity: Severity) -> Violation {
+        Violation::new(
+            1,
+            "CC6.7".to_string(),
+            severity,
+            "Hardcoded API key".to_string(),
+            file_path.to_string(),
+            1,
+            "API_KEY = \"sk_live_abc\"".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_from_values_defaults() {
+        assert_eq!(FixtureSettings::from_values(None, None), FixtureSettings::default());
+        assert_eq!(FixtureSettings::from_values(Some("true"), Some("not json")), FixtureSettings::default());
+
+        let settings = FixtureSettings::from_values(Some("false"), Some(r#"["spec/data"]"#));
+        assert!(!settings.scan);
+        assert_eq!(settings.patterns, vec!["spec/data".to_string()]);
+    }
+
+    #[test]
+    fn test_is_fixture_path_matches_directories() {
+        let settings = FixtureSettings::default();
+
+        assert!(settings.is_fixture_path(Path::new("tests/fixtures/config.json")));
+        assert!(settings.is_fixture_path(Path::new("/project/api/tests/fixtures/users/seed.py")));
+        assert!(settings.is_fixture_path(Path::new("pkg/testdata/keys.go")));
+        assert!(settings.is_fixture_path(Path::new("src/__fixtures__/token.ts")));
+
+        assert!(!settings.is_fixture_path(Path::new("tests/test_api.py")));
+        assert!(!settings.is_fixture_path(Path::new("src/fixtures/loader.py")));
+        // A file named like a fixture directory isn't inside one
+        assert!(!settings.is_fixture_path(Path::new("src/testdata")));
+    }
+
+    #[test]
+    fn test_should_skip_only_when_fixture_scanning_disabled() {
+        let path = Path::new("/project/test_data/secrets.env");
+        assert!(!FixtureSettings::default().should_skip(path));
+
+        let disabled = FixtureSettings { scan: false, ..FixtureSettings::default() };
+        assert!(disabled.should_skip(path));
+        assert!(!disabled.should_skip(Path::new("/project/src/app.py")));
+    }
+
+    #[test]
+    fn test_downgrade_violations_in_fixtures_only() {
+        let mut violations = vec![
+            violation("tests/fixtures/prod.py", Severity::Critical),
+            violation("testdata/config.js", Severity::Low),
+            violation("src/config.py", Severity::Critical),
+        ];
+
+        FixtureSettings::default().downgrade_violations(&mut violations);
+
+        assert_eq!(violations[0].severity, "high");
+        assert_eq!(violations[1].severity, "low");
+        assert_eq!(violations[2].severity, "critical");
+    }
+}
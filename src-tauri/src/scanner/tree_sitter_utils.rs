@@ -1,7 +1,12 @@
 //! Tree-Sitter AST parsing utilities
 //!
 //! Parses code into Abstract Syntax Trees (AST) for semantic analysis.
-//! Supports Python, JavaScript, and TypeScript.
+//! Supports Python, JavaScript, TypeScript, Rust and Go. TypeScript additionally
+//! extracts class methods, interface and abstract method signatures, and
+//! abstract classes and interfaces, along with their decorators. Rust treats
+//! structs, enums, traits and impl blocks as classes and attributes
+//! (`#[get("/admin")]`) as decorators. Go methods count as functions and
+//! type declarations as classes.
 
 use anyhow::{Context, Result};
 use std::str::Utf8Error;
@@ -22,6 +27,11 @@ pub struct ASTNode {
     pub end_row: usize,
     /// Text content of the node
     pub text: String,
+    /// Declared name (from the node's `name` field), if any
+    pub name: Option<String>,
+    /// Innermost decorator applied to this node, without the leading `@`
+    /// or Rust's `#[...]` (e.g. "Injectable()", "login_required", "get(\"/admin\")")
+    pub decorator_name: Option<String>,
 }
 
 /// Parse result containing extracted AST information
@@ -44,6 +54,8 @@ pub struct CodeParser {
     python_language: Language,
     javascript_language: Language,
     typescript_language: Language,
+    rust_language: Language,
+    go_language: Language,
 }
 
 impl CodeParser {
@@ -57,6 +69,8 @@ impl CodeParser {
             python_language: tree_sitter_python::language(),
             javascript_language: tree_sitter_javascript::language(),
             typescript_language: tree_sitter_typescript::language_typescript(),
+            rust_language: tree_sitter_rust::language(),
+            go_language: tree_sitter_go::language(),
         })
     }
 
@@ -96,11 +110,35 @@ impl CodeParser {
         self.parse_internal(code, "typescript", &self.typescript_language)
     }
 
+    /// Parse Rust code
+    ///
+    /// # Arguments
+    /// * `code` - Rust source code to parse
+    ///
+    /// # Returns
+    /// * `Ok(ParseResult)` containing AST information
+    /// * `Err(...)` if parsing fails
+    pub fn parse_rust(&self, code: &str) -> Result<ParseResult> {
+        self.parse_internal(code, "rust", &self.rust_language)
+    }
+
+    /// Parse Go code
+    ///
+    /// # Arguments
+    /// * `code` - Go source code to parse
+    ///
+    /// # Returns
+    /// * `Ok(ParseResult)` containing AST information
+    /// * `Err(...)` if parsing fails
+    pub fn parse_go(&self, code: &str) -> Result<ParseResult> {
+        self.parse_internal(code, "go", &self.go_language)
+    }
+
     /// Parse code with specified language
     ///
     /// # Arguments
     /// * `code` - Source code to parse
-    /// * `language_name` - Name of the language ("python", "javascript", "typescript")
+    /// * `language_name` - Name of the language ("python", "javascript", "typescript", "rust", "go")
     /// * `language` - Tree-sitter language object
     ///
     /// # Returns
@@ -114,7 +152,7 @@ impl CodeParser {
     ) -> Result<ParseResult> {
         let mut parser = Parser::new();
         parser
-            .set_language(&language)
+            .set_language(language)
             .context(format!("Failed to set {} language", language_name))?;
 
         let tree = parser
@@ -127,7 +165,7 @@ impl CodeParser {
         let mut imports = Vec::new();
 
         // Traverse AST recursively
-        Self::traverse_node(root, code, &mut functions, &mut classes, &mut imports)?;
+        Self::traverse_node(root, code, language_name, &mut functions, &mut classes, &mut imports)?;
 
         Ok(ParseResult {
             language: language_name.to_string(),
@@ -142,17 +180,47 @@ impl CodeParser {
     fn traverse_node(
         node: Node,
         code: &str,
+        language_name: &str,
         functions: &mut Vec<ASTNode>,
         classes: &mut Vec<ASTNode>,
         imports: &mut Vec<ASTNode>,
     ) -> Result<()> {
+        let typescript = language_name == "typescript";
+        let rust = language_name == "rust";
+        let go = language_name == "go";
+
         match node.kind() {
             "function_definition" | "function_declaration" => {
                 functions.push(Self::node_to_ast(node, code)?);
             }
+            // Class methods, interface methods and abstract methods
+            "method_definition" | "method_signature" | "abstract_method_signature" if typescript => {
+                functions.push(Self::node_to_ast(node, code)?);
+            }
             "class_definition" | "class_declaration" => {
                 classes.push(Self::node_to_ast(node, code)?);
             }
+            "abstract_class_declaration" | "interface_declaration" if typescript => {
+                classes.push(Self::node_to_ast(node, code)?);
+            }
+            // Free functions, methods and trait method signatures
+            "function_item" | "function_signature_item" if rust => {
+                functions.push(Self::node_to_ast(node, code)?);
+            }
+            "struct_item" | "enum_item" | "trait_item" | "impl_item" if rust => {
+                classes.push(Self::node_to_ast(node, code)?);
+            }
+            "use_declaration" if rust => {
+                imports.push(Self::node_to_ast(node, code)?);
+            }
+            // Methods with a receiver; plain functions are function_declaration
+            "method_declaration" if go => {
+                functions.push(Self::node_to_ast(node, code)?);
+            }
+            // Each name in a `type (...)` group is its own type_spec
+            "type_spec" if go => {
+                classes.push(Self::node_to_ast(node, code)?);
+            }
             "import_statement"
             | "from_import_statement"
             | "import_declaration"
@@ -165,7 +233,7 @@ impl CodeParser {
         // Traverse children
         for i in 0..node.child_count() {
             if let Some(child) = node.child(i) {
-                Self::traverse_node(child, code, functions, classes, imports)?;
+                Self::traverse_node(child, code, language_name, functions, classes, imports)?;
             }
         }
 
@@ -175,6 +243,11 @@ impl CodeParser {
     /// Convert a tree-sitter Node to ASTNode
     fn node_to_ast(node: Node, code: &str) -> Result<ASTNode> {
         let text = Self::get_node_text(node, code)?;
+        // Rust impl blocks have no name, only the type they implement
+        let name = node
+            .child_by_field_name("name")
+            .or_else(|| (node.kind() == "impl_item").then(|| node.child_by_field_name("type")).flatten())
+            .and_then(|name| Self::get_node_text(name, code).ok());
 
         Ok(ASTNode {
             kind: node.kind().to_string(),
@@ -183,9 +256,57 @@ impl CodeParser {
             start_row: node.start_position().row,
             end_row: node.end_position().row,
             text,
+            name,
+            decorator_name: Self::innermost_decorator(node, code),
         })
     }
 
+    /// Find the decorator closest to a definition
+    ///
+    /// Depending on the grammar, decorators are children of the definition
+    /// (TypeScript classes), preceding siblings (TypeScript class members), or
+    /// children of a wrapper (Python `decorated_definition`, TypeScript
+    /// `export_statement`). Rust attributes are preceding `attribute_item`
+    /// siblings. The innermost one is the last in source order.
+    fn innermost_decorator(node: Node, code: &str) -> Option<String> {
+        let mut decorators: Vec<Node> = Self::decorator_children(node);
+
+        let mut sibling = node.prev_named_sibling();
+        while let Some(prev) = sibling.filter(|prev| matches!(prev.kind(), "decorator" | "attribute_item")) {
+            decorators.push(prev);
+            sibling = prev.prev_named_sibling();
+        }
+
+        if let Some(parent) = node
+            .parent()
+            .filter(|parent| matches!(parent.kind(), "decorated_definition" | "export_statement"))
+        {
+            decorators.extend(Self::decorator_children(parent));
+        }
+
+        decorators
+            .into_iter()
+            .max_by_key(|decorator| decorator.start_byte())
+            .and_then(|decorator| Self::get_node_text(decorator, code).ok())
+            .map(|text| Self::strip_decorator_syntax(&text))
+    }
+
+    /// Drop the `@` of a decorator or the `#[...]` around a Rust attribute
+    fn strip_decorator_syntax(text: &str) -> String {
+        let text = text.trim();
+        match text.strip_prefix("#[").and_then(|inner| inner.strip_suffix(']')) {
+            Some(attribute) => attribute.trim().to_string(),
+            None => text.trim_start_matches('@').trim().to_string(),
+        }
+    }
+
+    fn decorator_children(node: Node) -> Vec<Node> {
+        (0..node.child_count())
+            .filter_map(|i| node.child(i))
+            .filter(|child| child.kind() == "decorator")
+            .collect()
+    }
+
     /// Extract text from a tree-sitter Node
     fn get_node_text(node: Node, code: &str) -> Result<String, Utf8Error> {
         Ok(node.utf8_text(code.as_bytes())?.to_string())
@@ -198,14 +319,17 @@ impl Default for CodeParser {
     }
 }
 
-/// Find function and class context for a specific line number
+/// Find function, class and decorator context for a specific line number
+///
+/// The decorator is the innermost one on the enclosing function (or method),
+/// falling back to the enclosing class when the function is undecorated.
 ///
 /// # Arguments
 /// * `parse_result` - The ParseResult from parsing the code
 /// * `line_number` - The line number to find context for (1-indexed, as used in violations)
 ///
 /// # Returns
-/// * `(Option<String>, Option<String>)` - (function_name, class_name)
+/// * `(Option<String>, Option<String>, Option<String>)` - (function_name, class_name, decorator_name)
 ///
 /// # Example
 /// ```no_run
@@ -215,26 +339,36 @@ impl Default for CodeParser {
 /// let parser = CodeParser::new().unwrap();
 /// let code = "def my_function():\n    pass\n";
 /// let result = parser.parse_python(code).unwrap();
-/// let (func_name, class_name) = find_context_at_line(&result, 1);
+/// let (func_name, class_name, decorator_name) = find_context_at_line(&result, 1);
 /// assert_eq!(func_name, Some("my_function".to_string()));
 /// ```
 pub fn find_context_at_line(
     parse_result: &ParseResult,
     line_number: i64,
-) -> (Option<String>, Option<String>) {
+) -> (Option<String>, Option<String>, Option<String>) {
     let target_row = (line_number - 1) as usize; // Convert to 0-indexed
+    let contains_line = |node: &&ASTNode| node.start_row <= target_row && target_row <= node.end_row;
 
     // Find containing function
-    let function_name = parse_result.functions.iter()
-        .find(|node| node.start_row <= target_row && target_row <= node.end_row)
-        .and_then(|node| extract_name_from_definition(&node.text));
+    let function = parse_result.functions.iter().find(contains_line);
+    let function_name = function.and_then(node_name);
 
     // Find containing class
-    let class_name = parse_result.classes.iter()
-        .find(|node| node.start_row <= target_row && target_row <= node.end_row)
-        .and_then(|node| extract_name_from_definition(&node.text));
+    let class = parse_result.classes.iter().find(contains_line);
+    let class_name = class.and_then(node_name);
+
+    let decorator_name = function
+        .and_then(|node| node.decorator_name.clone())
+        .or_else(|| class.and_then(|node| node.decorator_name.clone()));
 
-    (function_name, class_name)
+    (function_name, class_name, decorator_name)
+}
+
+/// Name of a function/class node, parsed from its text when the grammar has no name field
+fn node_name(node: &ASTNode) -> Option<String> {
+    node.name
+        .clone()
+        .or_else(|| extract_name_from_definition(&node.text))
 }
 
 /// Extract name from function/class definition text
@@ -260,7 +394,7 @@ fn extract_name_from_definition(text: &str) -> Option<String> {
     if trimmed.starts_with("class ") {
         return trimmed
             .strip_prefix("class ")?
-            .split(|c: char| c == '(' || c == ':')
+            .split(['(', ':'])
             .next()
             .map(|s| s.trim().to_string());
     }
@@ -487,4 +621,281 @@ def baz():
         let result = parser.parse_python(code).expect("Failed to parse");
         assert!(result.functions.len() >= 3);
     }
+
+    const NEST_CONTROLLER: &str = r#"@Controller('users')
+export class UsersController {
+    constructor(private readonly users: UsersService) {}
+
+    @UseGuards(AuthGuard('jwt'))
+    @Get(':id')
+    findOne(id: string) {
+        return this.users.find(id);
+    }
+
+    remove(id: string) {
+        return this.users.delete(id);
+    }
+}
+"#;
+
+    #[test]
+    fn test_typescript_class_decorator() {
+        let parser = CodeParser::new().expect("Failed to create parser");
+        let code = "@Injectable()\nclass UserService {\n    find() {}\n}\n";
+
+        let result = parser.parse_typescript(code).expect("Failed to parse");
+        assert_eq!(result.classes.len(), 1);
+        assert_eq!(result.classes[0].name.as_deref(), Some("UserService"));
+        assert_eq!(result.classes[0].decorator_name.as_deref(), Some("Injectable()"));
+    }
+
+    #[test]
+    fn test_typescript_exported_class_decorator() {
+        let parser = CodeParser::new().expect("Failed to create parser");
+        let code = "@Component({ selector: 'app-root' })\nexport class AppComponent {}\n";
+
+        let result = parser.parse_typescript(code).expect("Failed to parse");
+        assert_eq!(result.classes[0].name.as_deref(), Some("AppComponent"));
+        assert_eq!(
+            result.classes[0].decorator_name.as_deref(),
+            Some("Component({ selector: 'app-root' })")
+        );
+    }
+
+    #[test]
+    fn test_typescript_innermost_class_decorator() {
+        let parser = CodeParser::new().expect("Failed to create parser");
+        let code = "@Injectable()\n@Component()\nclass Widget {}\n";
+
+        let result = parser.parse_typescript(code).expect("Failed to parse");
+        assert_eq!(result.classes[0].decorator_name.as_deref(), Some("Component()"));
+    }
+
+    #[test]
+    fn test_typescript_class_methods_and_decorators() {
+        let parser = CodeParser::new().expect("Failed to create parser");
+
+        let result = parser.parse_typescript(NEST_CONTROLLER).expect("Failed to parse");
+        let find_one = result.functions.iter().find(|f| f.name.as_deref() == Some("findOne")).unwrap();
+        let remove = result.functions.iter().find(|f| f.name.as_deref() == Some("remove")).unwrap();
+
+        assert_eq!(find_one.kind, "method_definition");
+        assert_eq!(find_one.decorator_name.as_deref(), Some("Get(':id')"));
+        assert_eq!(remove.decorator_name, None);
+    }
+
+    #[test]
+    fn test_typescript_interface_methods() {
+        let parser = CodeParser::new().expect("Failed to create parser");
+        let code = "interface UserRepository {\n    find(id: string): User;\n    save(user: User): void;\n}\n";
+
+        let result = parser.parse_typescript(code).expect("Failed to parse");
+        assert_eq!(result.classes[0].kind, "interface_declaration");
+        assert_eq!(result.classes[0].name.as_deref(), Some("UserRepository"));
+
+        let names: Vec<_> = result.functions.iter().filter_map(|f| f.name.as_deref()).collect();
+        assert_eq!(names, vec!["find", "save"]);
+        assert!(result.functions.iter().all(|f| f.kind == "method_signature"));
+    }
+
+    #[test]
+    fn test_typescript_abstract_class_methods() {
+        let parser = CodeParser::new().expect("Failed to create parser");
+        let code = "abstract class BaseHandler {\n    abstract handle(event: Event): void;\n\n    run(event: Event) {\n        this.handle(event);\n    }\n}\n";
+
+        let result = parser.parse_typescript(code).expect("Failed to parse");
+        assert_eq!(result.classes[0].kind, "abstract_class_declaration");
+        assert_eq!(result.classes[0].name.as_deref(), Some("BaseHandler"));
+
+        let handle = result.functions.iter().find(|f| f.name.as_deref() == Some("handle")).unwrap();
+        assert_eq!(handle.kind, "abstract_method_signature");
+        assert!(result.functions.iter().any(|f| f.name.as_deref() == Some("run")));
+    }
+
+    #[test]
+    fn test_javascript_methods_not_extracted() {
+        let parser = CodeParser::new().expect("Failed to create parser");
+        let code = "class Service {\n    load() {\n        return 1;\n    }\n}\n";
+
+        let result = parser.parse_javascript(code).expect("Failed to parse");
+        assert!(result.functions.is_empty());
+        assert_eq!(result.classes[0].name.as_deref(), Some("Service"));
+    }
+
+    #[test]
+    fn test_find_context_typescript_method_decorator() {
+        let parser = CodeParser::new().expect("Failed to create parser");
+        let result = parser.parse_typescript(NEST_CONTROLLER).expect("Failed to parse");
+
+        // Line 8: return this.users.find(id) inside findOne
+        let (func, class, decorator) = find_context_at_line(&result, 8);
+        assert_eq!(func.as_deref(), Some("findOne"));
+        assert_eq!(class.as_deref(), Some("UsersController"));
+        assert_eq!(decorator.as_deref(), Some("Get(':id')"));
+    }
+
+    #[test]
+    fn test_find_context_falls_back_to_class_decorator() {
+        let parser = CodeParser::new().expect("Failed to create parser");
+        let result = parser.parse_typescript(NEST_CONTROLLER).expect("Failed to parse");
+
+        // Line 12: inside the undecorated remove()
+        let (func, class, decorator) = find_context_at_line(&result, 12);
+        assert_eq!(func.as_deref(), Some("remove"));
+        assert_eq!(class.as_deref(), Some("UsersController"));
+        assert_eq!(decorator.as_deref(), Some("Controller('users')"));
+    }
+
+    #[test]
+    fn test_find_context_abstract_method() {
+        let parser = CodeParser::new().expect("Failed to create parser");
+        let code = "abstract class Job {\n    abstract execute(): Promise<void>;\n}\n";
+        let result = parser.parse_typescript(code).expect("Failed to parse");
+
+        let (func, class, decorator) = find_context_at_line(&result, 2);
+        assert_eq!(func.as_deref(), Some("execute"));
+        assert_eq!(class.as_deref(), Some("Job"));
+        assert_eq!(decorator, None);
+    }
+
+    #[test]
+    fn test_find_context_without_decorators() {
+        let parser = CodeParser::new().expect("Failed to create parser");
+        let code = "function handler(req: Request) {\n    return req.body;\n}\n";
+        let result = parser.parse_typescript(code).expect("Failed to parse");
+
+        assert_eq!(find_context_at_line(&result, 2), (Some("handler".to_string()), None, None));
+    }
+
+    #[test]
+    fn test_find_context_python_decorator() {
+        let parser = CodeParser::new().expect("Failed to create parser");
+        let code = "@app.route('/profile')\n@login_required\ndef profile(request):\n    return render(request)\n";
+        let result = parser.parse_python(code).expect("Failed to parse");
+
+        let (func, class, decorator) = find_context_at_line(&result, 4);
+        assert_eq!(func.as_deref(), Some("profile"));
+        assert_eq!(class, None);
+        assert_eq!(decorator.as_deref(), Some("login_required"));
+    }
+
+    #[test]
+    fn test_find_context_python_async_function_name() {
+        let parser = CodeParser::new().expect("Failed to create parser");
+        let code = "async def fetch_user(user_id):\n    return await db.get(user_id)\n";
+        let result = parser.parse_python(code).expect("Failed to parse");
+
+        let (func, _, _) = find_context_at_line(&result, 2);
+        assert_eq!(func.as_deref(), Some("fetch_user"));
+    }
+
+    #[test]
+    fn test_find_context_module_level() {
+        let parser = CodeParser::new().expect("Failed to create parser");
+        let code = "SECRET = 'x'\n\ndef f():\n    pass\n";
+        let result = parser.parse_python(code).expect("Failed to parse");
+
+        assert_eq!(find_context_at_line(&result, 1), (None, None, None));
+    }
+
+    #[test]
+    fn test_parse_rust_items() {
+        let parser = CodeParser::new().expect("Failed to create parser");
+        let code = r#"use actix_web::{get, HttpResponse};
+
+pub struct Config {
+    port: u16,
+}
+
+pub trait Store {
+    fn load(&self) -> Config;
+}
+
+fn main() {}
+"#;
+        let result = parser.parse_rust(code).expect("Failed to parse");
+
+        assert_eq!(result.language, "rust");
+        assert_eq!(result.imports.len(), 1);
+        let functions: Vec<_> = result.functions.iter().filter_map(|f| f.name.as_deref()).collect();
+        assert_eq!(functions, vec!["load", "main"]);
+        let classes: Vec<_> = result.classes.iter().filter_map(|c| c.name.as_deref()).collect();
+        assert_eq!(classes, vec!["Config", "Store"]);
+    }
+
+    #[test]
+    fn test_find_context_rust_impl_method() {
+        let parser = CodeParser::new().expect("Failed to create parser");
+        let code = "impl UserService {\n    fn find(&self, id: i64) -> User {\n        self.db.get(id)\n    }\n}\n";
+        let result = parser.parse_rust(code).expect("Failed to parse");
+
+        let (func, class, decorator) = find_context_at_line(&result, 3);
+        assert_eq!(func.as_deref(), Some("find"));
+        assert_eq!(class.as_deref(), Some("UserService"));
+        assert_eq!(decorator, None);
+    }
+
+    #[test]
+    fn test_find_context_rust_route_attribute() {
+        let parser = CodeParser::new().expect("Failed to create parser");
+        let code = "#[get(\"/admin\")]\nasync fn admin_panel() -> impl Responder {\n    HttpResponse::Ok()\n}\n";
+        let result = parser.parse_rust(code).expect("Failed to parse");
+
+        let (func, _, decorator) = find_context_at_line(&result, 3);
+        assert_eq!(func.as_deref(), Some("admin_panel"));
+        assert_eq!(decorator.as_deref(), Some("get(\"/admin\")"));
+    }
+
+    #[test]
+    fn test_find_context_rust_innermost_attribute() {
+        let parser = CodeParser::new().expect("Failed to create parser");
+        let code = "#[get(\"/admin\")]\n#[has_permissions(\"admin\")]\nasync fn admin_panel() -> impl Responder {\n    HttpResponse::Ok()\n}\n";
+        let result = parser.parse_rust(code).expect("Failed to parse");
+
+        let (_, _, decorator) = find_context_at_line(&result, 4);
+        assert_eq!(decorator.as_deref(), Some("has_permissions(\"admin\")"));
+    }
+
+    #[test]
+    fn test_parse_go() {
+        let parser = CodeParser::new().expect("Failed to create parser");
+        let code = r#"package main
+
+import (
+    "log"
+    "net/http"
+)
+
+type Server struct {
+    addr string
+}
+
+func (s *Server) Start() error {
+    return http.ListenAndServe(s.addr, nil)
+}
+
+func main() {
+    log.Println("starting")
+}
+"#;
+        let result = parser.parse_go(code).expect("Failed to parse");
+
+        assert_eq!(result.language, "go");
+        assert_eq!(result.imports.len(), 1);
+        let functions: Vec<_> = result.functions.iter().filter_map(|f| f.name.as_deref()).collect();
+        assert_eq!(functions, vec!["Start", "main"]);
+        let classes: Vec<_> = result.classes.iter().filter_map(|c| c.name.as_deref()).collect();
+        assert_eq!(classes, vec!["Server"]);
+    }
+
+    #[test]
+    fn test_find_context_go_method() {
+        let parser = CodeParser::new().expect("Failed to create parser");
+        let code = "package main\n\ntype UserService struct{}\n\nfunc (s *UserService) Find(id int) User {\n    return s.db.Get(id)\n}\n";
+        let result = parser.parse_go(code).expect("Failed to parse");
+
+        let (func, _, decorator) = find_context_at_line(&result, 6);
+        assert_eq!(func.as_deref(), Some("Find"));
+        assert_eq!(decorator, None);
+    }
 }
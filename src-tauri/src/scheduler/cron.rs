@@ -0,0 +1,232 @@
+//! Cron expression parsing
+//!
+//! Standard five-field expressions (minute, hour, day of month, month, day of
+//! week) with `*`, lists, ranges and steps, plus the `@hourly`, `@daily`,
+//! `@weekly`, `@monthly` and `@yearly` shorthands. As in cron, when both the
+//! day of month and day of week are restricted a day matching either runs.
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, TimeZone, Timelike};
+
+/// How far ahead `next_after` looks before giving up (covers "29 2 *")
+const SEARCH_DAYS: i64 = 366 * 8;
+
+/// A parsed cron expression
+#[derive(Debug, Clone, PartialEq)]
+pub struct CronSchedule {
+    /// Bit n set if the field allows value n
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    /// Sunday is 0 (7 is accepted and folded into 0)
+    days_of_week: u64,
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+impl CronSchedule {
+    /// Parse a cron expression
+    ///
+    /// # Errors
+    /// Returns error naming the bad field if the expression is invalid
+    pub fn parse(expr: &str) -> Result<Self> {
+        let expr = expr.trim();
+        let expanded = match expr {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            other => other,
+        };
+
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        if fields.len() != 5 {
+            bail!(
+                "Invalid cron expression '{}': expected 5 fields (minute hour day-of-month month day-of-week), got {}",
+                expr,
+                fields.len()
+            );
+        }
+
+        let field = |index: usize, name: &str, min: u32, max: u32| {
+            parse_field(fields[index], min, max)
+                .with_context(|| format!("Invalid cron expression '{}': bad {} field '{}'", expr, name, fields[index]))
+        };
+
+        let mut days_of_week = field(4, "day-of-week", 0, 7)?;
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week | 1) & !(1 << 7);
+        }
+
+        Ok(Self {
+            minutes: field(0, "minute", 0, 59)?,
+            hours: field(1, "hour", 0, 23)?,
+            days_of_month: field(2, "day-of-month", 1, 31)?,
+            months: field(3, "month", 1, 12)?,
+            days_of_week,
+            day_of_month_restricted: !fields[2].starts_with('*'),
+            day_of_week_restricted: !fields[4].starts_with('*'),
+        })
+    }
+
+    /// First time strictly after `after` the expression matches, in `after`'s time zone
+    ///
+    /// Local times skipped by a DST change are skipped; ambiguous ones use the
+    /// earlier instant. Returns None if nothing matches within eight years
+    /// (e.g. "0 0 31 2 *").
+    pub fn next_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let timezone = after.timezone();
+        let start = after.naive_local().with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let end = start + Duration::days(SEARCH_DAYS);
+
+        let mut time = start;
+        while time < end {
+            if !bit(self.months, time.month()) {
+                time = first_of_next_month(time.date())?;
+                continue;
+            }
+            if !self.matches_day(time.date()) {
+                time = (time.date() + Duration::days(1)).and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if !bit(self.hours, time.hour()) {
+                time = time.with_minute(0)? + Duration::hours(1);
+                continue;
+            }
+            if !bit(self.minutes, time.minute()) {
+                time += Duration::minutes(1);
+                continue;
+            }
+            if let Some(matched) = timezone.from_local_datetime(&time).earliest() {
+                return Some(matched);
+            }
+            time += Duration::minutes(1);
+        }
+
+        None
+    }
+
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let day_of_month = bit(self.days_of_month, date.day());
+        let day_of_week = bit(self.days_of_week, date.weekday().num_days_from_sunday());
+        if self.day_of_month_restricted && self.day_of_week_restricted {
+            day_of_month || day_of_week
+        } else {
+            day_of_month && day_of_week
+        }
+    }
+}
+
+fn bit(bits: u64, value: u32) -> bool {
+    bits & (1 << value) != 0
+}
+
+fn first_of_next_month(date: NaiveDate) -> Option<NaiveDateTime> {
+    let (year, month) = if date.month() == 12 {
+        (date.year() + 1, 1)
+    } else {
+        (date.year(), date.month() + 1)
+    };
+    NaiveDate::from_ymd_opt(year, month, 1)?.and_hms_opt(0, 0, 0)
+}
+
+/// Parse one field into a bit set of the values in `min..=max` it allows
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64> {
+    let mut bits = 0u64;
+
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or_else(|| anyhow!("invalid step '{}'", step))?;
+                (range, step)
+            }
+            None => (part, 1),
+        };
+
+        let value = |text: &str| -> Result<u32> {
+            let value: u32 = text.parse().map_err(|_| anyhow!("invalid value '{}'", text))?;
+            if value < min || value > max {
+                bail!("{} is outside {}-{}", value, min, max);
+            }
+            Ok(value)
+        };
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (value(start)?, value(end)?)
+        } else {
+            // "5/15" means every 15 starting at 5
+            let start = value(range)?;
+            (start, if part.contains('/') { max } else { start })
+        };
+        if start > end {
+            bail!("range {}-{} is backwards", start, end);
+        }
+
+        for v in (start..=end).step_by(step as usize) {
+            bits |= 1 << v;
+        }
+    }
+
+    Ok(bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn at(text: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(text).unwrap().with_timezone(&Utc)
+    }
+
+    fn next(expr: &str, after: &str) -> String {
+        CronSchedule::parse(expr)
+            .unwrap()
+            .next_after(&at(after))
+            .unwrap()
+            .format("%Y-%m-%dT%H:%M")
+            .to_string()
+    }
+
+    #[test]
+    fn test_next_after_nightly_and_hourly() {
+        assert_eq!(next("0 2 * * *", "2026-03-10T01:30:00Z"), "2026-03-10T02:00");
+        // Strictly after: a schedule that just ran moves to the next day
+        assert_eq!(next("0 2 * * *", "2026-03-10T02:00:00Z"), "2026-03-11T02:00");
+        assert_eq!(next("@hourly", "2026-03-10T23:59:30Z"), "2026-03-11T00:00");
+        assert_eq!(next("*/15 * * * *", "2026-03-10T10:16:00Z"), "2026-03-10T10:30");
+    }
+
+    #[test]
+    fn test_next_after_days_and_months() {
+        // 2026-03-10 is a Tuesday; weekdays only at 09:30
+        assert_eq!(next("30 9 * * 1-5", "2026-03-13T10:00:00Z"), "2026-03-16T09:30");
+        // 7 is also Sunday
+        assert_eq!(next("0 0 * * 7", "2026-03-10T00:00:00Z"), "2026-03-15T00:00");
+        assert_eq!(next("@monthly", "2026-12-15T00:00:00Z"), "2027-01-01T00:00");
+        assert_eq!(next("0 0 29 2 *", "2026-03-01T00:00:00Z"), "2028-02-29T00:00");
+        // Day of month and day of week both restricted: either matches
+        assert_eq!(next("0 0 1 * 5", "2026-03-10T00:00:00Z"), "2026-03-13T00:00");
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_expressions() {
+        for expr in ["", "* * * *", "60 * * * *", "0 24 * * *", "0 0 0 * *", "0 0 * 13 *", "*/0 * * * *", "5-1 * * * *", "a * * * *"] {
+            assert!(CronSchedule::parse(expr).is_err(), "expected '{}' to be rejected", expr);
+        }
+        let error = CronSchedule::parse("0 25 * * *").unwrap_err();
+        assert!(format!("{:#}", error).contains("hour"));
+
+        // Never matches
+        let impossible = CronSchedule::parse("0 0 31 2 *").unwrap();
+        assert!(impossible.next_after(&at("2026-01-01T00:00:00Z")).is_none());
+    }
+}
@@ -0,0 +1,238 @@
+//! Scheduled scans
+//!
+//! Projects can be scanned on a cron schedule (`schedule_scan`), e.g. nightly
+//! at 02:00, without anyone opening the app and clicking scan. `spawn` starts
+//! a background task that checks the `scan_schedules` table once a minute and
+//! runs each due schedule's scan, one at a time, while the app is running.
+//!
+//! A schedule's next run is worked out from the time it was due, so runs
+//! missed while the app was closed collapse into a single catch-up scan at
+//! the next check. Schedules of every namespace run: each scan is scoped to
+//! its project's namespace (`queries::in_namespace`) without switching the
+//! namespace selected in the app.
+
+pub mod cron;
+
+pub use cron::CronSchedule;
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Local, SecondsFormat, Utc};
+use std::time::Duration;
+use tauri::Manager;
+
+use crate::commands::scan::{scan_project_internal, ScanResponseChannels};
+use crate::db::{self, queries};
+use crate::models::ScanSchedule;
+use crate::scanner::ScanEventEmitter;
+use crate::utils::create_audit_event;
+
+/// How often the background task looks for due schedules
+pub const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Format a schedule time the way `scan_schedules` stores it
+///
+/// Fixed-width UTC, so stored times compare correctly as text.
+pub fn format_schedule_time(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Next time `cron_expr` fires after `after`, in local time, formatted for storage
+///
+/// # Errors
+/// Returns error if the expression is invalid or never fires
+pub fn next_run_at(cron_expr: &str, after: DateTime<Utc>) -> Result<String> {
+    let schedule = CronSchedule::parse(cron_expr)?;
+    let next = schedule
+        .next_after(&after.with_timezone(&Local))
+        .ok_or_else(|| anyhow!("Cron expression '{}' never fires", cron_expr))?;
+    Ok(format_schedule_time(next.with_timezone(&Utc)))
+}
+
+/// Start the background task that runs due schedules
+pub fn spawn<R: tauri::Runtime>(app: tauri::AppHandle<R>) {
+    tauri::async_runtime::spawn(async move {
+        eprintln!("[ryn] Scan scheduler started, checking every {}s", CHECK_INTERVAL.as_secs());
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            let channels = app.state::<ScanResponseChannels>();
+            run_due_schedules(app.clone(), &channels, Utc::now()).await;
+        }
+    });
+}
+
+/// Run every schedule due at `now`, one after another
+///
+/// Returns: IDs of the schedules that were run
+pub async fn run_due_schedules<E: ScanEventEmitter>(
+    app: E,
+    channels: &ScanResponseChannels,
+    now: DateTime<Utc>,
+) -> Vec<i64> {
+    let due = {
+        let conn = db::get_connection();
+        match queries::select_due_scan_schedules(&conn, &format_schedule_time(now)) {
+            Ok(due) => due,
+            Err(e) => {
+                eprintln!("[ryn] Failed to fetch due scan schedules: {:#}", e);
+                return Vec::new();
+            }
+        }
+    }; // Connection dropped here
+
+    let mut ran = Vec::new();
+    for (schedule, namespace) in due {
+        queries::in_namespace(namespace, run_schedule(app.clone(), channels, &schedule, now)).await;
+        ran.push(schedule.id);
+    }
+    ran
+}
+
+/// Advance a schedule to its next run, then scan its project
+async fn run_schedule<E: ScanEventEmitter>(
+    app: E,
+    channels: &ScanResponseChannels,
+    schedule: &ScanSchedule,
+    now: DateTime<Utc>,
+) {
+    eprintln!(
+        "[ryn] Running scheduled scan: schedule_id={}, project_id={}, cron='{}'",
+        schedule.id, schedule.project_id, schedule.cron_expr
+    );
+
+    // Advance before scanning so a failing scan isn't retried every minute
+    {
+        let conn = db::get_connection();
+        let next = match next_run_at(&schedule.cron_expr, now) {
+            Ok(next) => next,
+            Err(e) => {
+                // Can't happen for schedules created through schedule_scan
+                eprintln!("[ryn] Disabling scan schedule {}: {:#}", schedule.id, e);
+                let _ = queries::set_scan_schedule_enabled(&conn, schedule.id, false);
+                return;
+            }
+        };
+        if let Err(e) = queries::update_scan_schedule_run(&conn, schedule.id, &format_schedule_time(now), &next) {
+            eprintln!("[ryn] Failed to update scan schedule {}: {:#}", schedule.id, e);
+            return;
+        }
+    } // Connection dropped here
+
+    let result = scan_project_internal(app, channels, schedule.project_id, None).await;
+
+    let conn = db::get_connection();
+    let description = match &result {
+        Ok(scan) => {
+            let _ = queries::update_scan_schedule_last_scan(&conn, schedule.id, scan.id);
+            format!("Scheduled scan {} ran for schedule {} ('{}')", scan.id, schedule.id, schedule.cron_expr)
+        }
+        Err(e) => {
+            eprintln!("[ryn] Scheduled scan failed: schedule_id={}, error={}", schedule.id, e);
+            format!("Scheduled scan for schedule {} ('{}') failed: {}", schedule.id, schedule.cron_expr, e)
+        }
+    };
+    if let Ok(event) = create_audit_event(&conn, "scheduled_scan", Some(schedule.project_id), None, None, &description) {
+        let _ = queries::insert_audit_event(&conn, &event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_helpers::TestDbGuard;
+    use crate::models::DEFAULT_NAMESPACE;
+    use crate::scanner::NullEventEmitter;
+
+    fn create_project(dir: &std::path::Path) -> i64 {
+        std::fs::write(dir.join("app.py"), "API_KEY = 'sk_live_1234567890abcdef'\n").unwrap();
+        let conn = db::get_connection();
+        queries::insert_project(&conn, "scheduled", &dir.to_string_lossy(), None).unwrap()
+    }
+
+    #[test]
+    fn test_format_and_next_run_at() {
+        let now = DateTime::parse_from_rfc3339("2026-03-10T10:16:42.5Z").unwrap().with_timezone(&Utc);
+        assert_eq!(format_schedule_time(now), "2026-03-10T10:16:42Z");
+        // Minute-level schedules don't depend on the local time zone offset
+        assert_eq!(next_run_at("* * * * *", now).unwrap(), "2026-03-10T10:17:00Z");
+        assert!(next_run_at("0 0 31 2 *", now).unwrap_err().to_string().contains("never fires"));
+        assert!(next_run_at("nightly", now).is_err());
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_run_due_schedules_scans_and_advances() {
+        let _guard = TestDbGuard::new();
+        let dir = tempfile::tempdir().unwrap();
+        let project_id = create_project(dir.path());
+        let now = Utc::now();
+
+        let (due_id, later_id) = {
+            let conn = db::get_connection();
+            let due = ScanSchedule::new(project_id, "*/5 * * * *".to_string(), format_schedule_time(now));
+            let later = ScanSchedule::new(
+                project_id,
+                "0 2 * * *".to_string(),
+                format_schedule_time(now + chrono::Duration::hours(1)),
+            );
+            (
+                queries::insert_scan_schedule(&conn, &due).unwrap(),
+                queries::insert_scan_schedule(&conn, &later).unwrap(),
+            )
+        };
+
+        let ran = run_due_schedules(NullEventEmitter, &ScanResponseChannels::default(), now).await;
+        assert_eq!(ran, vec![due_id]);
+
+        let conn = db::get_connection();
+        let schedules = queries::select_scan_schedules(&conn).unwrap();
+        let due = schedules.iter().find(|s| s.id == due_id).unwrap();
+        assert_eq!(due.last_run_at.as_deref(), Some(format_schedule_time(now).as_str()));
+        assert!(due.next_run_at > format_schedule_time(now));
+        let scan_id = due.last_scan_id.expect("scheduled run should record its scan");
+        assert!(queries::select_scan(&conn, scan_id).unwrap().is_some());
+
+        let later = schedules.iter().find(|s| s.id == later_id).unwrap();
+        assert!(later.last_run_at.is_none());
+
+        let events = queries::select_audit_events_by_project(&conn, project_id).unwrap();
+        assert_eq!(events.iter().filter(|e| e.event_type == "scheduled_scan").count(), 1);
+        drop(conn);
+
+        // Already advanced, so nothing is due at the same time again
+        let ran = run_due_schedules(NullEventEmitter, &ScanResponseChannels::default(), now).await;
+        assert!(ran.is_empty());
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_run_due_schedules_covers_other_namespaces() {
+        let _guard = TestDbGuard::new();
+        let dir = tempfile::tempdir().unwrap();
+        let now = Utc::now();
+
+        let (project_id, schedule_id) = {
+            let conn = db::get_connection();
+            queries::insert_namespace(&conn, "team-b").unwrap();
+            queries::insert_or_update_setting(&conn, "current_namespace", "team-b").unwrap();
+            drop(conn);
+            let project_id = create_project(dir.path());
+            let conn = db::get_connection();
+            let schedule = ScanSchedule::new(project_id, "0 2 * * *".to_string(), format_schedule_time(now));
+            let schedule_id = queries::insert_scan_schedule(&conn, &schedule).unwrap();
+            queries::insert_or_update_setting(&conn, "current_namespace", DEFAULT_NAMESPACE).unwrap();
+            (project_id, schedule_id)
+        };
+
+        let ran = run_due_schedules(NullEventEmitter, &ScanResponseChannels::default(), now).await;
+        assert_eq!(ran, vec![schedule_id]);
+
+        // The scan ran in team-b without switching the app away from default
+        let conn = db::get_connection();
+        assert_eq!(queries::current_namespace(&conn).unwrap(), DEFAULT_NAMESPACE);
+        queries::insert_or_update_setting(&conn, "current_namespace", "team-b").unwrap();
+        let scans = queries::select_scans(&conn, project_id).unwrap();
+        assert_eq!(scans.len(), 1);
+        assert!(scans[0].violations_found > 0);
+    }
+}
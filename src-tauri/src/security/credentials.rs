@@ -0,0 +1,250 @@
+//! API keys and other secrets in the OS keychain
+//!
+//! Keys are stored under the "ryn" service in the platform credential store
+//! (macOS Keychain, Windows Credential Manager/DPAPI, libsecret on Linux), so
+//! desktop users set them from the app instead of editing a .env file. The
+//! environment variables (`XAI_API_KEY`, `GITHUB_TOKEN`) still work as a
+//! fallback for CI and headless runs; a key in the keychain takes precedence.
+//!
+//! Tests use an in-memory store instead of the real keychain.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+/// Keychain service all of Ryn's secrets are stored under
+pub const KEYCHAIN_SERVICE: &str = "ryn";
+
+/// Service whose API key is stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CredentialProvider {
+    /// X.AI Grok API, used for LLM analysis and fix generation
+    Xai,
+    /// GitHub API token, used to open fix pull requests
+    Github,
+}
+
+impl CredentialProvider {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CredentialProvider::Xai => "xai",
+            CredentialProvider::Github => "github",
+        }
+    }
+
+    /// Parse a provider name; "grok" is accepted for X.AI
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "xai" | "grok" => Some(CredentialProvider::Xai),
+            "github" => Some(CredentialProvider::Github),
+            _ => None,
+        }
+    }
+
+    /// Environment variable read when the keychain has no key
+    pub fn env_var(&self) -> &'static str {
+        match self {
+            CredentialProvider::Xai => "XAI_API_KEY",
+            CredentialProvider::Github => "GITHUB_TOKEN",
+        }
+    }
+
+    /// Keychain account the key is stored under
+    pub fn account(&self) -> &'static str {
+        match self {
+            CredentialProvider::Xai => "xai_api_key",
+            CredentialProvider::Github => "github_token",
+        }
+    }
+
+    /// Check a key's format before storing it
+    ///
+    /// # Errors
+    /// Returns error if the key is empty, too short or (for X.AI) lacks the `xai-` prefix
+    pub fn validate(&self, key: &str) -> Result<()> {
+        crate::utils::validate_api_key(key)?;
+        if *self == CredentialProvider::Xai && !key.starts_with("xai-") {
+            return Err(anyhow!("API key must start with 'xai-'"));
+        }
+        Ok(())
+    }
+}
+
+/// Get a provider's key from the keychain, falling back to its environment variable
+///
+/// A keychain that can't be read (e.g. no Secret Service daemon on a headless
+/// Linux runner) is treated like one without the key.
+///
+/// # Errors
+/// Returns error if neither has a key
+pub fn get_api_key(provider: CredentialProvider) -> Result<String> {
+    match read_secret(provider.account()) {
+        Ok(Some(key)) => return Ok(key),
+        Ok(None) => {}
+        Err(e) => eprintln!("[ryn] Keychain unavailable, checking {}: {:#}", provider.env_var(), e),
+    }
+    std::env::var(provider.env_var())
+        .ok()
+        .map(|key| key.trim().to_string())
+        .filter(|key| !key.is_empty())
+        .ok_or_else(|| {
+            anyhow!(
+                "No {} key set. Add it in Settings or set the {} environment variable.",
+                provider.as_str(),
+                provider.env_var()
+            )
+        })
+}
+
+/// Whether a key is available from the keychain or the environment
+pub fn has_api_key(provider: CredentialProvider) -> bool {
+    get_api_key(provider).is_ok()
+}
+
+/// Validate and store a provider's key in the keychain
+///
+/// # Errors
+/// Returns error if the key is malformed or the keychain can't be written
+pub fn set_api_key(provider: CredentialProvider, key: &str) -> Result<()> {
+    let key = key.trim();
+    provider.validate(key)?;
+    store_secret(provider.account(), key)
+}
+
+/// Remove a provider's key from the keychain (the environment variable is unaffected)
+pub fn delete_api_key(provider: CredentialProvider) -> Result<()> {
+    delete_secret(provider.account())
+}
+
+#[cfg(not(test))]
+pub use keychain::{delete_secret, read_secret, store_secret};
+
+#[cfg(test)]
+pub use memory::{delete_secret, read_secret, store_secret};
+
+#[cfg(not(test))]
+mod keychain {
+    use super::*;
+    use anyhow::Context;
+
+    fn entry(account: &str) -> Result<keyring::Entry> {
+        keyring::Entry::new(KEYCHAIN_SERVICE, account)
+            .with_context(|| format!("Failed to open keychain entry {}", account))
+    }
+
+    /// Read a secret, or None if the keychain has no entry for `account`
+    pub fn read_secret(account: &str) -> Result<Option<String>> {
+        match entry(account)?.get_password() {
+            Ok(secret) => Ok(Some(secret)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {} from the keychain", account)),
+        }
+    }
+
+    /// Store a secret, replacing any existing one
+    pub fn store_secret(account: &str, secret: &str) -> Result<()> {
+        entry(account)?
+            .set_password(secret)
+            .with_context(|| format!("Failed to store {} in the keychain", account))
+    }
+
+    /// Delete a secret; deleting a missing entry is not an error
+    pub fn delete_secret(account: &str) -> Result<()> {
+        match entry(account)?.delete_password() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(e).with_context(|| format!("Failed to delete {} from the keychain", account)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod memory {
+    use super::*;
+    use once_cell::sync::Lazy;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Mutex;
+
+    static SECRETS: Lazy<Mutex<HashMap<String, String>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+    static UNAVAILABLE: AtomicBool = AtomicBool::new(false);
+
+    /// Make reads fail, like a keychain with no backing service
+    pub fn set_unavailable(unavailable: bool) {
+        UNAVAILABLE.store(unavailable, Ordering::SeqCst);
+    }
+
+    pub fn read_secret(account: &str) -> Result<Option<String>> {
+        if UNAVAILABLE.load(Ordering::SeqCst) {
+            return Err(anyhow!("Platform secure storage failure"));
+        }
+        Ok(SECRETS.lock().unwrap().get(account).cloned())
+    }
+
+    pub fn store_secret(account: &str, secret: &str) -> Result<()> {
+        SECRETS.lock().unwrap().insert(account.to_string(), secret.to_string());
+        Ok(())
+    }
+
+    pub fn delete_secret(account: &str) -> Result<()> {
+        SECRETS.lock().unwrap().remove(account);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_KEY: &str = "xai-1234567890123456789";
+
+    #[test]
+    fn test_provider_names() {
+        assert_eq!(CredentialProvider::from_str("grok"), Some(CredentialProvider::Xai));
+        assert_eq!(CredentialProvider::from_str(" GitHub "), Some(CredentialProvider::Github));
+        assert_eq!(CredentialProvider::from_str("openai"), None);
+        assert_eq!(CredentialProvider::Github.env_var(), "GITHUB_TOKEN");
+    }
+
+    #[test]
+    fn test_validate_rejects_malformed_keys() {
+        assert!(CredentialProvider::Xai.validate(TEST_KEY).is_ok());
+        assert!(CredentialProvider::Xai.validate("sk-1234567890123456789").is_err());
+        assert!(CredentialProvider::Github.validate("ghp_1234567890123456789").is_ok());
+        assert!(CredentialProvider::Github.validate("short").is_err());
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_keychain_takes_precedence_over_env() {
+        std::env::remove_var("GITHUB_TOKEN");
+        delete_api_key(CredentialProvider::Github).unwrap();
+        let error = get_api_key(CredentialProvider::Github).unwrap_err();
+        assert!(error.to_string().contains("GITHUB_TOKEN"));
+
+        std::env::set_var("GITHUB_TOKEN", "ghp_from_environment_000");
+        assert_eq!(get_api_key(CredentialProvider::Github).unwrap(), "ghp_from_environment_000");
+
+        set_api_key(CredentialProvider::Github, " ghp_from_keychain_00000 ").unwrap();
+        assert_eq!(get_api_key(CredentialProvider::Github).unwrap(), "ghp_from_keychain_00000");
+
+        delete_api_key(CredentialProvider::Github).unwrap();
+        std::env::remove_var("GITHUB_TOKEN");
+        assert!(!has_api_key(CredentialProvider::Github));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_unreadable_keychain_falls_back_to_env() {
+        set_api_key(CredentialProvider::Github, "ghp_from_keychain_00000").unwrap();
+        std::env::set_var("GITHUB_TOKEN", "ghp_from_environment_000");
+        memory::set_unavailable(true);
+
+        assert_eq!(get_api_key(CredentialProvider::Github).unwrap(), "ghp_from_environment_000");
+        std::env::remove_var("GITHUB_TOKEN");
+        let error = get_api_key(CredentialProvider::Github).unwrap_err();
+        assert!(error.to_string().contains("GITHUB_TOKEN"));
+
+        memory::set_unavailable(false);
+        delete_api_key(CredentialProvider::Github).unwrap();
+    }
+}
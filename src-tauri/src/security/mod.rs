@@ -1,6 +1,7 @@
 //! Security module
 //!
 //! Provides security functions for path validation, input sanitization,
-//! and other security-critical operations
+//! credential storage and other security-critical operations
 
+pub mod credentials;
 pub mod path_validation;
@@ -3,8 +3,50 @@
 //! Provides security functions to prevent directory traversal attacks
 //! and validate file paths before file system operations
 
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use anyhow::{Result, anyhow};
+use thiserror::Error;
+
+/// Longest output path accepted for exports (Linux PATH_MAX)
+pub const MAX_EXPORT_PATH_LEN: usize = 4096;
+
+/// Directories exports may never be written into
+const EXPORT_FORBIDDEN_DIRS: &[&str] = &[
+    "/etc",
+    "/private/etc",     // macOS: /etc symlinks to /private/etc
+    "/sys",
+    "/proc",
+    "/dev",
+    "/boot",
+    "/usr",
+    "/bin",
+    "/sbin",
+];
+
+/// Windows system directories, compared case-insensitively with `/` separators
+const EXPORT_FORBIDDEN_WINDOWS_DIRS: &[&str] = &["/windows", "c:/windows"];
+
+/// Why an export path was rejected
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SecurityError {
+    #[error("Export path must be absolute: {0}")]
+    RelativePath(PathBuf),
+
+    #[error("Path traversal detected in export path: {0}")]
+    PathTraversal(PathBuf),
+
+    #[error("Cannot export into system directory: {0}")]
+    SystemDirectory(PathBuf),
+
+    #[error("Export directory does not exist: {0}")]
+    ParentNotFound(PathBuf),
+
+    #[error("Export directory is not writable: {0}")]
+    ParentNotWritable(PathBuf),
+
+    #[error("Export path is {0} bytes, the limit is {max}", max = MAX_EXPORT_PATH_LEN)]
+    PathTooLong(usize),
+}
 
 /// Validate and canonicalize a file path to prevent directory traversal
 ///
@@ -111,8 +153,7 @@ pub fn validate_project_path(path: &Path) -> Result<()> {
 
     for forbidden_path in &forbidden {
         // Check if scanning exactly this directory or a shallow subdirectory
-        if path_str.starts_with(forbidden_path) {
-            let remainder = &path_str[forbidden_path.len()..];
+        if let Some(remainder) = path_str.strip_prefix(forbidden_path) {
             // Allow only if significantly nested (more than 10 chars deep)
             if remainder.is_empty() || remainder.len() <= 10 {
                 return Err(anyhow!(
@@ -126,6 +167,86 @@ pub fn validate_project_path(path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Validate a user-chosen output path before an export writes to it
+///
+/// This function ensures that:
+/// 1. The path is at most `MAX_EXPORT_PATH_LEN` bytes and absolute
+/// 2. The path has no ".." components and isn't itself a symlink
+/// 3. The parent directory exists and is writable
+/// 4. Neither the path nor its symlink-resolved location is inside a
+///    system directory (`/etc`, `/usr`, `C:\Windows`, ...)
+///
+/// # Arguments
+/// * `path` - The file the export will create or overwrite
+///
+/// # Returns
+/// * `Ok(())` - If the export may write to `path`
+/// * `Err(SecurityError)` - Which check failed
+pub fn validate_export_path(path: &Path) -> std::result::Result<(), SecurityError> {
+    let len = path.as_os_str().len();
+    if len > MAX_EXPORT_PATH_LEN {
+        return Err(SecurityError::PathTooLong(len));
+    }
+
+    if !path.is_absolute() {
+        return Err(SecurityError::RelativePath(path.to_path_buf()));
+    }
+
+    if path.components().any(|c| c == Component::ParentDir) {
+        return Err(SecurityError::PathTraversal(path.to_path_buf()));
+    }
+
+    if is_export_forbidden(path) {
+        return Err(SecurityError::SystemDirectory(path.to_path_buf()));
+    }
+
+    let (Some(parent), Some(file_name)) = (path.parent(), path.file_name()) else {
+        // "/" or a path ending in a root/prefix component
+        return Err(SecurityError::SystemDirectory(path.to_path_buf()));
+    };
+
+    // Writing through a symlink would overwrite whatever it points at
+    if path.symlink_metadata().map(|m| m.file_type().is_symlink()).unwrap_or(false) {
+        return Err(SecurityError::PathTraversal(path.to_path_buf()));
+    }
+
+    // Resolve symlinks in the parent so a link into /etc can't slip through
+    let canonical_parent = parent
+        .canonicalize()
+        .map_err(|_| SecurityError::ParentNotFound(parent.to_path_buf()))?;
+    if !canonical_parent.is_dir() {
+        return Err(SecurityError::ParentNotFound(parent.to_path_buf()));
+    }
+
+    if is_export_forbidden(&canonical_parent.join(file_name)) {
+        return Err(SecurityError::SystemDirectory(path.to_path_buf()));
+    }
+
+    let readonly = std::fs::metadata(&canonical_parent)
+        .map(|m| m.permissions().readonly())
+        .unwrap_or(true);
+    if readonly {
+        return Err(SecurityError::ParentNotWritable(parent.to_path_buf()));
+    }
+
+    Ok(())
+}
+
+/// Whether `path` lies inside one of the export-forbidden system directories
+fn is_export_forbidden(path: &Path) -> bool {
+    if EXPORT_FORBIDDEN_DIRS.iter().any(|dir| path.starts_with(dir)) {
+        return true;
+    }
+
+    let normalized = path.to_string_lossy().replace('\\', "/").to_lowercase();
+    EXPORT_FORBIDDEN_WINDOWS_DIRS.iter().any(|dir| {
+        normalized
+            .strip_prefix(dir)
+            .map(|rest| rest.is_empty() || rest.starts_with('/'))
+            .unwrap_or(false)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -253,4 +374,188 @@ mod tests {
             assert_eq!(validated, real_file.canonicalize().unwrap());
         }
     }
+
+    fn export_target(dir: &TempDir, name: &str) -> PathBuf {
+        dir.path().join(name)
+    }
+
+    #[test]
+    fn test_export_path_allows_file_in_writable_directory() {
+        let dir = TempDir::new().unwrap();
+        assert_eq!(validate_export_path(&export_target(&dir, "export.json")), Ok(()));
+    }
+
+    #[test]
+    fn test_export_path_allows_overwriting_existing_file() {
+        let dir = TempDir::new().unwrap();
+        let target = export_target(&dir, "export.csv");
+        fs::write(&target, "old").unwrap();
+        assert_eq!(validate_export_path(&target), Ok(()));
+    }
+
+    #[test]
+    fn test_export_path_rejects_relative_path() {
+        let result = validate_export_path(Path::new("exports/data.json"));
+        assert!(matches!(result, Err(SecurityError::RelativePath(_))));
+    }
+
+    #[test]
+    fn test_export_path_rejects_bare_file_name() {
+        let result = validate_export_path(Path::new("data.json"));
+        assert!(matches!(result, Err(SecurityError::RelativePath(_))));
+    }
+
+    #[test]
+    fn test_export_path_rejects_parent_components() {
+        let dir = TempDir::new().unwrap();
+        let target = dir.path().join("..").join("escape.json");
+        assert!(matches!(validate_export_path(&target), Err(SecurityError::PathTraversal(_))));
+    }
+
+    #[test]
+    fn test_export_path_rejects_parent_traversal_into_etc() {
+        let result = validate_export_path(Path::new("/home/user/../../etc/passwd"));
+        assert!(matches!(result, Err(SecurityError::PathTraversal(_))));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_export_path_rejects_symlinked_target() {
+        let dir = TempDir::new().unwrap();
+        let real = export_target(&dir, "real.json");
+        fs::write(&real, "{}").unwrap();
+        let link = export_target(&dir, "link.json");
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+
+        assert!(matches!(validate_export_path(&link), Err(SecurityError::PathTraversal(_))));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_export_path_rejects_dangling_symlink_target() {
+        let dir = TempDir::new().unwrap();
+        let link = export_target(&dir, "link.json");
+        std::os::unix::fs::symlink("/etc/ryn-export-test", &link).unwrap();
+
+        assert!(matches!(validate_export_path(&link), Err(SecurityError::PathTraversal(_))));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_export_path_rejects_symlinked_directory_into_system_dir() {
+        let dir = TempDir::new().unwrap();
+        let link = export_target(&dir, "config");
+        std::os::unix::fs::symlink("/etc", &link).unwrap();
+
+        let result = validate_export_path(&link.join("passwd"));
+        assert!(matches!(result, Err(SecurityError::SystemDirectory(_))));
+    }
+
+    #[test]
+    fn test_export_path_rejects_etc() {
+        let result = validate_export_path(Path::new("/etc/export.json"));
+        assert!(matches!(result, Err(SecurityError::SystemDirectory(_))));
+    }
+
+    #[test]
+    fn test_export_path_rejects_nested_system_directories() {
+        for path in ["/usr/local/share/export.json", "/sys/kernel/export.json", "/bin/export", "/sbin/export"] {
+            let result = validate_export_path(Path::new(path));
+            assert!(matches!(result, Err(SecurityError::SystemDirectory(_))), "{} was allowed", path);
+        }
+    }
+
+    #[test]
+    fn test_export_path_rejects_windows_directory() {
+        for path in ["/Windows/System32/export.json", "C:\\Windows\\export.json", "c:/windows/temp/x.csv"] {
+            assert!(is_export_forbidden(Path::new(path)), "{} was allowed", path);
+        }
+        let result = validate_export_path(Path::new("/Windows/export.json"));
+        assert!(matches!(result, Err(SecurityError::SystemDirectory(_))));
+    }
+
+    #[test]
+    fn test_export_path_allows_lookalike_directories() {
+        for path in ["/home/user/etc/export.json", "/srv/usr-data/export.json", "/home/user/windows-backup/x.csv"] {
+            assert!(!is_export_forbidden(Path::new(path)), "{} was rejected", path);
+        }
+        assert!(!is_export_forbidden(Path::new("/etcetera/export.json")));
+    }
+
+    #[test]
+    fn test_export_path_rejects_root() {
+        let result = validate_export_path(Path::new("/"));
+        assert!(matches!(result, Err(SecurityError::SystemDirectory(_))));
+    }
+
+    #[test]
+    fn test_export_path_rejects_missing_parent() {
+        let dir = TempDir::new().unwrap();
+        let target = dir.path().join("missing").join("export.json");
+        let result = validate_export_path(&target);
+        assert_eq!(result, Err(SecurityError::ParentNotFound(dir.path().join("missing"))));
+    }
+
+    #[test]
+    fn test_export_path_rejects_file_as_parent() {
+        let dir = TempDir::new().unwrap();
+        let file = export_target(&dir, "plain.txt");
+        fs::write(&file, "x").unwrap();
+
+        let result = validate_export_path(&file.join("export.json"));
+        assert!(matches!(result, Err(SecurityError::ParentNotFound(_))));
+    }
+
+    #[test]
+    fn test_export_path_rejects_read_only_parent() {
+        let dir = TempDir::new().unwrap();
+        let locked = dir.path().join("locked");
+        fs::create_dir(&locked).unwrap();
+        let original = fs::metadata(&locked).unwrap().permissions();
+        let mut permissions = original.clone();
+        permissions.set_readonly(true);
+        fs::set_permissions(&locked, permissions).unwrap();
+
+        let result = validate_export_path(&locked.join("export.json"));
+
+        fs::set_permissions(&locked, original).unwrap();
+        assert!(matches!(result, Err(SecurityError::ParentNotWritable(_))));
+    }
+
+    #[test]
+    fn test_export_path_rejects_overlong_path() {
+        let path = format!("/tmp/{}", "a".repeat(MAX_EXPORT_PATH_LEN));
+        let result = validate_export_path(Path::new(&path));
+        assert_eq!(result, Err(SecurityError::PathTooLong(path.len())));
+    }
+
+    #[test]
+    fn test_export_path_length_checked_before_other_rules() {
+        // A relative path that is also too long reports the length
+        let path = "a".repeat(MAX_EXPORT_PATH_LEN + 1);
+        assert_eq!(validate_export_path(Path::new(&path)), Err(SecurityError::PathTooLong(path.len())));
+    }
+
+    #[test]
+    fn test_export_path_at_length_limit_is_not_too_long() {
+        let dir = TempDir::new().unwrap();
+        let prefix = dir.path().to_string_lossy().len() + 1;
+        let path = dir.path().join("a".repeat(MAX_EXPORT_PATH_LEN - prefix));
+        assert_eq!(path.as_os_str().len(), MAX_EXPORT_PATH_LEN);
+
+        // The file name exceeds NAME_MAX, but that's for the write to report
+        assert!(!matches!(validate_export_path(&path), Err(SecurityError::PathTooLong(_))));
+    }
+
+    #[test]
+    fn test_security_error_messages() {
+        assert_eq!(
+            SecurityError::SystemDirectory(PathBuf::from("/etc/x")).to_string(),
+            "Cannot export into system directory: /etc/x"
+        );
+        assert_eq!(
+            SecurityError::PathTooLong(5000).to_string(),
+            "Export path is 5000 bytes, the limit is 4096"
+        );
+    }
 }
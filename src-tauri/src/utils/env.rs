@@ -1,9 +1,11 @@
 //! Environment variable management
 //!
 //! Handles loading and validation of environment variables for API keys and configuration.
+//! API keys themselves are read through `security::credentials`, which checks
+//! the OS keychain before the environment.
 
-use anyhow::{anyhow, Context, Result};
-use std::env;
+use crate::security::credentials::{self, CredentialProvider};
+use anyhow::{anyhow, Result};
 
 /// Load environment variables from .env file
 ///
@@ -14,13 +16,12 @@ pub fn load_env() -> Result<()> {
     Ok(())
 }
 
-/// Get XAI_API_KEY from environment
+/// Get the X.AI API key from the OS keychain, or else XAI_API_KEY
 ///
 /// # Errors
-/// Returns error if XAI_API_KEY environment variable is not set
+/// Returns error if neither the keychain nor the environment has a key
 pub fn get_xai_key() -> Result<String> {
-    env::var("XAI_API_KEY")
-        .context("XAI_API_KEY environment variable not set. Please set it in .env or your environment.")
+    credentials::get_api_key(CredentialProvider::Xai)
 }
 
 /// Validate API key format
@@ -0,0 +1,88 @@
+//! Search match highlighting
+//!
+//! Marks the parts of a text that match a search query with `**...**` markdown.
+//! Each whitespace-separated word of the query is matched independently,
+//! case-insensitively and literally (regex metacharacters have no meaning).
+
+use regex::Regex;
+
+/// Build a case-insensitive pattern matching any word of `query`
+///
+/// Longer words come first so "logging" wins over "log" at the same position.
+/// Returns None for a blank query.
+fn query_pattern(query: &str) -> Option<Regex> {
+    let mut words: Vec<&str> = query.split_whitespace().collect();
+    if words.is_empty() {
+        return None;
+    }
+    words.sort_by_key(|w| std::cmp::Reverse(w.len()));
+    words.dedup();
+
+    let alternatives: Vec<String> = words.iter().map(|w| regex::escape(w)).collect();
+    Regex::new(&format!("(?i){}", alternatives.join("|"))).ok()
+}
+
+/// Wrap every match of a query word in `text` with `**...**`
+///
+/// A blank query returns the text unchanged.
+pub fn highlight_matches(text: &str, query: &str) -> String {
+    match query_pattern(query) {
+        Some(pattern) => pattern.replace_all(text, "**${0}**").into_owned(),
+        None => text.to_string(),
+    }
+}
+
+/// Number of non-overlapping matches of query words in `text`
+pub fn count_matches(text: &str, query: &str) -> i32 {
+    query_pattern(query).map_or(0, |pattern| pattern.find_iter(text).count() as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight_is_case_insensitive() {
+        assert_eq!(
+            highlight_matches("Hardcoded PASSWORD in config", "password"),
+            "Hardcoded **PASSWORD** in config"
+        );
+        assert_eq!(highlight_matches("Password and password", "PASSWORD"), "**Password** and **password**");
+    }
+
+    #[test]
+    fn test_highlight_multi_word_query_matches_each_word() {
+        assert_eq!(
+            highlight_matches("Missing audit log on delete_user", "delete audit"),
+            "Missing **audit** log on **delete**_user"
+        );
+        assert_eq!(count_matches("Missing audit log on delete_user", "delete audit"), 2);
+    }
+
+    #[test]
+    fn test_highlight_prefers_longest_word() {
+        assert_eq!(highlight_matches("logging disabled", "log logging"), "**logging** disabled");
+    }
+
+    #[test]
+    fn test_highlight_special_characters_are_literal() {
+        assert_eq!(
+            highlight_matches("cursor.execute(f\"SELECT * FROM users\")", "execute( *"),
+            "cursor.**execute(**f\"SELECT ***** FROM users\")"
+        );
+        assert_eq!(highlight_matches("a+b and ab", "a+b"), "**a+b** and ab");
+        assert_eq!(count_matches("price is $5.00, not $5000", "$5.00"), 1);
+    }
+
+    #[test]
+    fn test_blank_query_leaves_text_unchanged() {
+        assert_eq!(highlight_matches("Hardcoded secret", "   "), "Hardcoded secret");
+        assert_eq!(count_matches("Hardcoded secret", ""), 0);
+    }
+
+    #[test]
+    fn test_no_match_leaves_text_unchanged() {
+        assert_eq!(highlight_matches("Hardcoded secret", "token"), "Hardcoded secret");
+        assert_eq!(count_matches("Hardcoded secret", "token"), 0);
+    }
+}
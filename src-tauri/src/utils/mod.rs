@@ -5,6 +5,7 @@
 pub mod audit;
 pub mod code_context;
 pub mod env;
+pub mod highlight;
 
 pub use audit::create_audit_event;
 pub use code_context::{
@@ -12,6 +13,7 @@ pub use code_context::{
     extract_code_block_plain,
     extract_context_from_string,
 };
+pub use highlight::{highlight_matches, count_matches};
 pub use env::{
     load_env, get_xai_key, validate_api_key, get_and_validate_api_key,
 };
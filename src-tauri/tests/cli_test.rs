@@ -0,0 +1,183 @@
+//! End-to-end tests for the headless `ryn` CLI and the `ryn-cli` binary
+//!
+//! Runs the built binaries against temporary projects, each with its own
+//! `RYN_DATA_DIR` so the developer's database is never touched.
+//!
+//! Run with `cargo test --features headless --test cli_test`.
+
+#![cfg(feature = "headless")]
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+fn ryn(data_dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_ryn"))
+        .args(args)
+        .env("RYN_DATA_DIR", data_dir)
+        .env_remove("ANTHROPIC_API_KEY")
+        .output()
+        .expect("failed to run ryn")
+}
+
+fn ryn_cli(data_dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_ryn-cli"))
+        .args(args)
+        .env("RYN_DATA_DIR", data_dir)
+        .env_remove("ANTHROPIC_API_KEY")
+        .output()
+        .expect("failed to run ryn-cli")
+}
+
+fn clean_project() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("app.py"), "def add(a, b):\n    return a + b\n").unwrap();
+    dir
+}
+
+fn leaky_project() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    // Built at runtime so the token never appears in this file
+    let token = ["ghp_", "R4nd0mCl4ss1cT0k3nV4lu3abcdefghijKL"].concat();
+    fs::write(dir.path().join("settings.py"), format!("GITHUB_TOKEN = '{}'\n", token)).unwrap();
+    dir
+}
+
+#[test]
+fn test_scan_clean_project_exits_zero() {
+    let data_dir = TempDir::new().unwrap();
+    let project = clean_project();
+
+    let output = ryn(data_dir.path(), &["scan", project.path().to_str().unwrap()]);
+
+    assert_eq!(output.status.code(), Some(0), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("0 violations"));
+}
+
+#[test]
+fn test_scan_with_critical_violation_exits_one() {
+    let data_dir = TempDir::new().unwrap();
+    let project = leaky_project();
+    let out_file = data_dir.path().join("result.json");
+
+    let output = ryn(
+        data_dir.path(),
+        &["scan", project.path().to_str().unwrap(), "--format", "json", "--output", out_file.to_str().unwrap()],
+    );
+
+    assert_eq!(output.status.code(), Some(1), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+
+    let result: serde_json::Value = serde_json::from_str(&fs::read_to_string(&out_file).unwrap()).unwrap();
+    assert!(result["scan"]["critical_count"].as_i64().unwrap() >= 1);
+    let violations = result["violations"].as_array().unwrap();
+    assert!(violations.iter().any(|v| v["control_id"] == "CC6.7" && v["severity"] == "critical"));
+}
+
+#[test]
+fn test_scan_json_on_stdout_is_only_the_result() {
+    let data_dir = TempDir::new().unwrap();
+    let project = leaky_project();
+
+    let output = ryn(data_dir.path(), &["scan", project.path().to_str().unwrap(), "--format", "json"]);
+
+    assert_eq!(output.status.code(), Some(1), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(result["scan"]["critical_count"].as_i64().unwrap() >= 1);
+}
+
+#[test]
+fn test_scan_sarif_output() {
+    let data_dir = TempDir::new().unwrap();
+    let project = leaky_project();
+    let out_file = data_dir.path().join("result.sarif");
+
+    ryn(
+        data_dir.path(),
+        &["scan", project.path().to_str().unwrap(), "--format", "sarif", "--output", out_file.to_str().unwrap()],
+    );
+
+    let sarif: serde_json::Value = serde_json::from_str(&fs::read_to_string(&out_file).unwrap()).unwrap();
+    assert_eq!(sarif["version"], "2.1.0");
+    assert!(!sarif["runs"][0]["results"].as_array().unwrap().is_empty());
+}
+
+#[test]
+fn test_detect_framework() {
+    let data_dir = TempDir::new().unwrap();
+    let project = TempDir::new().unwrap();
+    fs::write(project.path().join("manage.py"), "import django\n").unwrap();
+
+    let output = ryn(data_dir.path(), &["detect-framework", project.path().to_str().unwrap()]);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "django");
+}
+
+#[test]
+fn test_report_for_scan() {
+    let data_dir = TempDir::new().unwrap();
+    let project = leaky_project();
+    let scan_file = data_dir.path().join("result.json");
+    ryn(
+        data_dir.path(),
+        &["scan", project.path().to_str().unwrap(), "--format", "json", "--output", scan_file.to_str().unwrap()],
+    );
+    let result: serde_json::Value = serde_json::from_str(&fs::read_to_string(&scan_file).unwrap()).unwrap();
+    let scan_id = result["scan"]["id"].as_i64().unwrap().to_string();
+
+    let report_file = data_dir.path().join("report.md");
+    let output = ryn(data_dir.path(), &["report", &scan_id, "--output", report_file.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(0));
+    let report = fs::read_to_string(&report_file).unwrap();
+    assert!(report.starts_with("# Ryn Compliance Report"));
+    assert!(report.contains("### CC6.7"));
+
+    let pdf_file = data_dir.path().join("report.pdf");
+    let output = ryn(data_dir.path(), &["report", &scan_id, "--format", "pdf", "--output", pdf_file.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(fs::read(&pdf_file).unwrap().starts_with(b"%PDF-1.4"));
+}
+
+#[test]
+fn test_report_unknown_scan_exits_two() {
+    let data_dir = TempDir::new().unwrap();
+
+    let output = ryn(data_dir.path(), &["report", "999"]);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Scan not found: 999"));
+}
+
+#[test]
+fn test_ryn_cli_fail_on_threshold() {
+    let data_dir = TempDir::new().unwrap();
+    let project = leaky_project();
+    let path = project.path().to_str().unwrap();
+
+    let output = ryn_cli(data_dir.path(), &["scan", "--path", path, "--format", "json", "--fail-on", "high"]);
+    assert_eq!(output.status.code(), Some(1), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+
+    let output = ryn_cli(data_dir.path(), &["scan", "--path", path, "--fail-on", "never"]);
+    assert_eq!(output.status.code(), Some(0), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+}
+
+#[test]
+fn test_ryn_cli_clean_project_exits_zero_on_low() {
+    let data_dir = TempDir::new().unwrap();
+    let project = clean_project();
+
+    let output = ryn_cli(data_dir.path(), &["scan", "--path", project.path().to_str().unwrap(), "--fail-on", "low"]);
+
+    assert_eq!(output.status.code(), Some(0), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+}
+
+#[test]
+fn test_ryn_cli_help_uses_binary_name() {
+    let data_dir = TempDir::new().unwrap();
+
+    let output = ryn_cli(data_dir.path(), &["--help"]);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("ryn-cli"));
+}
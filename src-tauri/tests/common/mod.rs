@@ -2,6 +2,9 @@
 //!
 //! This module provides utilities for setting up isolated test environments,
 //! creating test data, and asserting database state across all integration tests.
+//! Each test binary uses a different subset of these helpers.
+
+#![allow(dead_code)]
 
 use rusqlite::Connection;
 use tempfile::TempDir;
@@ -17,7 +20,7 @@ use std::fs;
 ///
 /// # Examples
 ///
-/// ```
+/// ```ignore
 /// use common::TestProject;
 ///
 /// #[test]
@@ -52,7 +55,7 @@ impl TestProject {
     /// 1. Creates a temporary directory for the database
     /// 2. Sets RYN_DATA_DIR environment variable to point to temp dir
     /// 3. Opens a SQLite connection
-    /// 4. Runs all migrations (v1 and v2)
+    /// 4. Runs all migrations
     /// 5. Seeds SOC 2 controls
     /// 6. Creates a temporary project directory for file scanning tests
     pub fn new(test_name: &str) -> Result<Self> {
@@ -325,30 +328,15 @@ impl TestProject {
     }
 }
 
+/// Schema version `run_migrations` brings a fresh database to
+pub const LATEST_SCHEMA_VERSION: i64 = 33;
+
 /// Run migrations for test database
 ///
-/// This is a standalone version of the migrations from src/db/migrations.rs
-/// to avoid dependency on the main singleton connection.
+/// Calls the crate's own migrations on this connection directly, so test
+/// databases stay at the app's schema without the singleton connection.
 fn run_test_migrations(conn: &Connection) -> Result<()> {
-    let current_version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
-
-    // Migration v0 -> v1: Initial schema
-    if current_version < 1 {
-        migrate_test_to_v1(conn)?;
-        conn.execute("PRAGMA user_version = 1", [])?;
-    }
-
-    // Migration v1 -> v2: Hybrid scanning schema
-    if current_version < 2 {
-        migrate_test_to_v2(conn)?;
-        conn.execute("PRAGMA user_version = 2", [])?;
-    }
-
-    // Migration v2 -> v3: Tree-sitter context fields
-    if current_version < 3 {
-        migrate_test_to_v3(conn)?;
-        conn.execute("PRAGMA user_version = 3", [])?;
-    }
+    ryn::db::migrations::run_migrations(conn)?;
 
     // Seed default settings
     seed_test_settings(conn)?;
@@ -356,102 +344,6 @@ fn run_test_migrations(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
-/// Migrate to v1 (initial schema)
-fn migrate_test_to_v1(conn: &Connection) -> Result<()> {
-    // Create all 7 initial tables
-    conn.execute_batch(include_str!("../../src/db/schema.sql"))?;
-
-    // Create indexes
-    conn.execute_batch("
-        CREATE INDEX IF NOT EXISTS idx_violations_scan_id ON violations(scan_id);
-        CREATE INDEX IF NOT EXISTS idx_violations_status ON violations(status);
-        CREATE INDEX IF NOT EXISTS idx_fixes_violation_id ON fixes(violation_id);
-        CREATE INDEX IF NOT EXISTS idx_audit_events_type ON audit_events(event_type);
-        CREATE INDEX IF NOT EXISTS idx_scans_project_id ON scans(project_id);
-        CREATE INDEX IF NOT EXISTS idx_audit_events_project_id ON audit_events(project_id);
-        CREATE INDEX IF NOT EXISTS idx_violations_file_path ON violations(file_path);
-        CREATE INDEX IF NOT EXISTS idx_audit_events_created_at ON audit_events(created_at);
-    ")?;
-
-    Ok(())
-}
-
-/// Migrate to v2 (hybrid scanning schema)
-fn migrate_test_to_v2(conn: &Connection) -> Result<()> {
-    // Add detection_method column
-    conn.execute(
-        "ALTER TABLE violations ADD COLUMN detection_method TEXT NOT NULL DEFAULT 'regex'
-         CHECK(detection_method IN ('regex', 'llm', 'hybrid'))",
-        [],
-    )?;
-
-    // Add confidence_score column
-    conn.execute(
-        "ALTER TABLE violations ADD COLUMN confidence_score INTEGER
-         CHECK(confidence_score IS NULL OR (confidence_score >= 0 AND confidence_score <= 100))",
-        [],
-    )?;
-
-    // Add llm_reasoning column
-    conn.execute(
-        "ALTER TABLE violations ADD COLUMN llm_reasoning TEXT",
-        [],
-    )?;
-
-    // Add regex_reasoning column
-    conn.execute(
-        "ALTER TABLE violations ADD COLUMN regex_reasoning TEXT",
-        [],
-    )?;
-
-    // Create scan_costs table
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS scan_costs (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            scan_id INTEGER NOT NULL,
-            files_analyzed_with_llm INTEGER NOT NULL DEFAULT 0,
-            input_tokens INTEGER NOT NULL DEFAULT 0,
-            output_tokens INTEGER NOT NULL DEFAULT 0,
-            cache_read_tokens INTEGER NOT NULL DEFAULT 0,
-            cache_write_tokens INTEGER NOT NULL DEFAULT 0,
-            total_cost_usd REAL NOT NULL DEFAULT 0.0,
-            created_at TEXT NOT NULL DEFAULT (datetime('now')),
-            FOREIGN KEY (scan_id) REFERENCES scans(id) ON DELETE CASCADE
-        )",
-        [],
-    )?;
-
-    // Create scan_costs indexes
-    conn.execute(
-        "CREATE INDEX IF NOT EXISTS idx_scan_costs_created_at ON scan_costs(created_at)",
-        [],
-    )?;
-
-    conn.execute(
-        "CREATE INDEX IF NOT EXISTS idx_scan_costs_scan_id ON scan_costs(scan_id)",
-        [],
-    )?;
-
-    Ok(())
-}
-
-/// Migrate to v3 (tree-sitter context fields)
-fn migrate_test_to_v3(conn: &Connection) -> Result<()> {
-    // Add function_name column
-    conn.execute(
-        "ALTER TABLE violations ADD COLUMN function_name TEXT",
-        [],
-    )?;
-
-    // Add class_name column
-    conn.execute(
-        "ALTER TABLE violations ADD COLUMN class_name TEXT",
-        [],
-    )?;
-
-    Ok(())
-}
-
 /// Seed default settings for test database
 fn seed_test_settings(conn: &Connection) -> Result<()> {
     conn.execute(
@@ -549,9 +441,8 @@ mod tests {
     fn test_schema_version() {
         let project = TestProject::new("test_schema_version").unwrap();
 
-        // Schema should be at version 3 after migrations
         let version = project.get_schema_version().unwrap();
-        assert_eq!(version, 3);
+        assert_eq!(version, LATEST_SCHEMA_VERSION);
     }
 
     #[test]
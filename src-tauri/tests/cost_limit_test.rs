@@ -33,7 +33,7 @@ async fn test_scan_response_channels_create_and_respond() {
 
     // Receive decision
     match rx.await {
-        Ok(decision) => assert_eq!(decision, true, "Should receive 'continue' decision"),
+        Ok(decision) => assert!(decision, "Should receive 'continue' decision"),
         Err(e) => panic!("Failed to receive decision: {:?}", e),
     }
 }
@@ -66,7 +66,7 @@ async fn test_user_decision_stop() {
     channels.respond_to_cost_limit(scan_id, false).unwrap();
 
     match rx.await {
-        Ok(decision) => assert_eq!(decision, false, "Should receive 'stop' decision"),
+        Ok(decision) => assert!(!decision, "Should receive 'stop' decision"),
         Err(e) => panic!("Failed to receive decision: {:?}", e),
     }
 }
@@ -93,8 +93,8 @@ async fn test_multiple_scans_independent_prompts() {
     let decision1 = rx1.await.unwrap();
     let decision2 = rx2.await.unwrap();
 
-    assert_eq!(decision1, true, "Scan 1 should receive 'continue'");
-    assert_eq!(decision2, false, "Scan 2 should receive 'stop'");
+    assert!(decision1, "Scan 1 should receive 'continue'");
+    assert!(!decision2, "Scan 2 should receive 'stop'");
 }
 
 /// Test 5: Channel can only be responded to once
@@ -168,7 +168,7 @@ fn test_cost_limit_detection() {
     assert_eq!(cost_limit, 0.50, "Cost limit should be $0.50");
 
     // Simulate scan costs
-    let scan_costs = vec![
+    let scan_costs = [
         0.10, // Batch 1: $0.10 (cumulative: $0.10) - under limit
         0.15, // Batch 2: $0.15 (cumulative: $0.25) - under limit
         0.20, // Batch 3: $0.20 (cumulative: $0.45) - under limit
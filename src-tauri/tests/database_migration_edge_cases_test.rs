@@ -187,7 +187,7 @@ fn test_migration_recovery_after_failure() -> Result<()> {
     // Create test data to ensure no corruption
     seed_controls(&conn)?;
     let control_count = count_rows(&conn, "controls")?;
-    assert_eq!(control_count, 4, "Controls should be seeded correctly despite migration issue");
+    assert_eq!(control_count, 5, "Controls should be seeded correctly despite migration issue");
 
     Ok(())
 }
@@ -239,7 +239,7 @@ fn test_downgrade_protection() -> Result<()> {
 
     // Step 4: Verify existing data is intact despite migration failure
     let control_count = count_rows(&conn, "controls")?;
-    assert_eq!(control_count, 4, "Controls should still exist after failed re-migration");
+    assert_eq!(control_count, 5, "Controls should still exist after failed re-migration");
 
     println!("✓ Existing data intact despite migration failure");
 
@@ -1213,7 +1213,7 @@ fn test_migration_failure_on_disk_full() -> Result<()> {
     assert_eq!(project_count, 1, "Existing project should be intact");
 
     let control_count = count_rows(&conn, "controls")?;
-    assert_eq!(control_count, 4, "Controls should be intact");
+    assert_eq!(control_count, 5, "Controls should be intact");
 
     // Verify new columns exist
     assert!(column_exists(&conn, "violations", "detection_method")?);
@@ -336,7 +336,7 @@ fn test_large_file_timeout_enforcement() {
     // Write header
     writeln!(file, "# Large Python file for timeout testing").unwrap();
     writeln!(file, "API_KEY = 'sk_live_12345abcdef'  # Hardcoded secret").unwrap();
-    writeln!(file, "").unwrap();
+    writeln!(file).unwrap();
     writeln!(file, "# Massive data array").unwrap();
     writeln!(file, "data = [").unwrap();
 
@@ -379,7 +379,7 @@ fn test_large_file_timeout_enforcement() {
                 Ok(violations) => {
                     println!("[Test] Scan completed in {:.2}s", duration.as_secs_f64());
                     println!("[Test] Violations found: {}", violations.len());
-                    assert!(violations.len() > 0, "Should detect hardcoded secret");
+                    assert!(!violations.is_empty(), "Should detect hardcoded secret");
                 }
                 Err(e) => {
                     println!("[Test] Scan failed in {:.2}s: {}", duration.as_secs_f64(), e);
@@ -0,0 +1,101 @@
+//! Framework Cache Benchmark
+//!
+//! Compares cold `FrameworkDetector` runs against `FrameworkCache` hits on a
+//! large project. The project has no root-level Django marker, so cold detection
+//! falls through to the depth-limited directory walk.
+
+use ryn::scanner::framework_detector::FrameworkDetector;
+use ryn::scanner::FrameworkCache;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+
+const TOP_LEVEL_DIRS: usize = 40;
+const NESTED_DIRS: usize = 10;
+const FILES_PER_DIR: usize = 10;
+const ITERATIONS: u32 = 20;
+
+/// Create TOP_LEVEL_DIRS * NESTED_DIRS directories holding FILES_PER_DIR files each
+fn create_large_project() -> TempDir {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("requirements.txt"), "flask==2.0.0\n").unwrap();
+
+    for top in 0..TOP_LEVEL_DIRS {
+        for nested in 0..NESTED_DIRS {
+            let dir = temp_dir.path().join(format!("pkg_{}/module_{}/lib", top, nested));
+            fs::create_dir_all(&dir).unwrap();
+            for file in 0..FILES_PER_DIR {
+                fs::write(dir.join(format!("file_{}.py", file)), "x = 1\n").unwrap();
+            }
+        }
+    }
+
+    temp_dir
+}
+
+fn average(total: Duration) -> Duration {
+    total / ITERATIONS
+}
+
+fn time_iterations(mut f: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    average(start.elapsed())
+}
+
+fn assert_detects_flask(path: &Path, framework: Option<String>) {
+    assert_eq!(framework, Some("flask".to_string()), "unexpected framework for {:?}", path);
+}
+
+#[test]
+fn bench_framework_cache_hit_vs_cold_detection() {
+    let project = create_large_project();
+    let path = project.path();
+
+    let cold = time_iterations(|| {
+        assert_detects_flask(path, FrameworkDetector::detect_framework(path).unwrap());
+    });
+
+    let cache = FrameworkCache::new();
+    assert_detects_flask(path, cache.detect(path).unwrap());
+
+    let hit = time_iterations(|| {
+        assert_detects_flask(path, cache.detect(path).unwrap());
+    });
+
+    println!(
+        "Framework detection on {} files: cold {:?}/run, cache hit {:?}/run ({:.1}x faster)",
+        TOP_LEVEL_DIRS * NESTED_DIRS * FILES_PER_DIR,
+        cold,
+        hit,
+        cold.as_secs_f64() / hit.as_secs_f64().max(f64::EPSILON)
+    );
+
+    assert_eq!(cache.len(), 1);
+    assert!(
+        hit < cold,
+        "cache hits ({:?}) should be faster than cold detection ({:?})",
+        hit,
+        cold
+    );
+}
+
+#[test]
+fn bench_framework_cache_invalidation_redetects() {
+    let project = create_large_project();
+    let path = project.path();
+    let cache = FrameworkCache::new();
+
+    assert_detects_flask(path, cache.detect(path).unwrap());
+
+    cache.invalidate_for_file(&path.join("requirements.txt"));
+    assert!(cache.is_empty());
+
+    let start = Instant::now();
+    assert_detects_flask(path, cache.detect(path).unwrap());
+    println!("Re-detection after invalidation: {:?}", start.elapsed());
+    assert_eq!(cache.len(), 1);
+}
@@ -14,7 +14,7 @@
 use ryn::commands::fix::{generate_fix, apply_fix};
 use ryn::db;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tempfile::TempDir;
 use std::os::unix::fs::PermissionsExt; // For chmod on Unix
 
@@ -69,7 +69,7 @@ fn create_test_setup() -> (TempDir, PathBuf, i64, i64) {
 
 /// Helper: Create test file and violation in global database
 fn create_test_file_and_violation(
-    project_path: &PathBuf,
+    project_path: &Path,
     scan_id: i64,
     file_name: &str,
     content: &str,
@@ -115,7 +115,7 @@ async fn test_apply_fix_nonexistent_fix() {
     );
 
     // Try to apply fix that doesn't exist (ID 9999)
-    let result = apply_fix(9999).await;
+    let result = apply_fix(9999, false).await;
 
     assert!(
         result.is_err(),
@@ -159,7 +159,7 @@ async fn test_apply_fix_file_deleted_after_scan() {
     fs::remove_file(&test_file).expect("Failed to delete test file");
 
     // Try to apply fix - should fail because file doesn't exist
-    let result = apply_fix(fix_id).await;
+    let result = apply_fix(fix_id, true).await;
 
     assert!(
         result.is_err(),
@@ -208,7 +208,7 @@ async fn test_apply_fix_file_readonly() {
     fs::set_permissions(&test_file, permissions).expect("Failed to set readonly");
 
     // Try to apply fix - should fail due to permission denied
-    let result = apply_fix(fix_id).await;
+    let result = apply_fix(fix_id, true).await;
 
     assert!(
         result.is_err(),
@@ -241,7 +241,7 @@ async fn test_apply_fix_no_fix_generated() {
 
     // Try to apply fix WITHOUT generating it first
     // This should fail because no fix exists in the database
-    let result = apply_fix(violation_id).await;
+    let result = apply_fix(violation_id, true).await;
 
     assert!(
         result.is_err(),
@@ -292,7 +292,7 @@ async fn test_apply_fix_succeeds_with_backup() {
     let fix_id = fix_result.unwrap().id;
 
     // Apply fix - should succeed
-    let apply_result = apply_fix(fix_id).await;
+    let apply_result = apply_fix(fix_id, true).await;
 
     assert!(
         apply_result.is_ok(),
@@ -373,7 +373,7 @@ async fn test_apply_fix_updates_violation_status() {
     assert!(fix_result.is_ok());
     let fix_id = fix_result.unwrap().id;
 
-    let apply_result = apply_fix(fix_id).await;
+    let apply_result = apply_fix(fix_id, true).await;
     assert!(apply_result.is_ok());
 
     // Verify violation status was updated to 'fixed'
@@ -420,7 +420,7 @@ async fn test_apply_fix_records_applied_at_timestamp() {
     assert!(fix_result.is_ok());
     let fix_id = fix_result.unwrap().id;
 
-    let apply_result = apply_fix(fix_id).await;
+    let apply_result = apply_fix(fix_id, true).await;
     assert!(apply_result.is_ok());
 
     // Verify fix has applied_at timestamp set
@@ -160,9 +160,11 @@ def user_profile(request):
         "django",
         Some("user_profile"),
         None,
+        &[],
     )
     .await
-    .expect("Failed to generate fix");
+    .expect("Failed to generate fix")
+    .fixed_code;
 
     // Validation 1: Fixed code should be syntactically valid Python
     validate_python_syntax(&fixed_code)
@@ -215,9 +217,11 @@ class PaymentProcessor:
         "python",
         Some("__init__"),
         Some("PaymentProcessor"),
+        &[],
     )
     .await
-    .expect("Failed to generate fix");
+    .expect("Failed to generate fix")
+    .fixed_code;
 
     // Validation 1: Fixed code should be syntactically valid Python
     validate_python_syntax(&fixed_code)
@@ -281,9 +285,11 @@ def delete_user(user_id):
         "django",
         Some("delete_user"),
         None,
+        &[],
     )
     .await
-    .expect("Failed to generate fix");
+    .expect("Failed to generate fix")
+    .fixed_code;
 
     // Validation 1: Fixed code should be syntactically valid Python
     validate_python_syntax(&fixed_code)
@@ -338,9 +344,11 @@ def fetch_user_data(user_id):
         "python",
         Some("fetch_user_data"),
         None,
+        &[],
     )
     .await
-    .expect("Failed to generate fix");
+    .expect("Failed to generate fix")
+    .fixed_code;
 
     // Validation 1: Fixed code should be syntactically valid Python
     validate_python_syntax(&fixed_code)
@@ -392,9 +400,11 @@ app.get('/api/user/profile', (req, res) => {
         "express",
         None,
         None,
+        &[],
     )
     .await
-    .expect("Failed to generate fix");
+    .expect("Failed to generate fix")
+    .fixed_code;
 
     // Validation 1: Fixed code should be syntactically valid JavaScript
     validate_javascript_syntax(&fixed_code)
@@ -440,9 +450,11 @@ module.exports = config;
         "javascript",
         None,
         None,
+        &[],
     )
     .await
-    .expect("Failed to generate fix");
+    .expect("Failed to generate fix")
+    .fixed_code;
 
     // Validation 1: Fixed code should be syntactically valid JavaScript
     validate_javascript_syntax(&fixed_code)
@@ -494,9 +506,11 @@ async function fetchUserData(userId: string): Promise<User> {
         "typescript",
         Some("fetchUserData"),
         None,
+        &[],
     )
     .await
-    .expect("Failed to generate fix");
+    .expect("Failed to generate fix")
+    .fixed_code;
 
     // Validation 1: Fixed code should be syntactically valid TypeScript
     validate_typescript_syntax(&fixed_code)
@@ -555,9 +569,11 @@ class UserService:
         "python",
         Some("__init__"),
         Some("UserService"),
+        &[],
     )
     .await
-    .expect("Failed to generate fix");
+    .expect("Failed to generate fix")
+    .fixed_code;
 
     // Validation 1: Fixed code should be syntactically valid Python
     validate_python_syntax(&fixed_code)
@@ -613,9 +629,11 @@ def placeholder():
         "python",
         Some("placeholder"),
         None,
+        &[],
     )
     .await
-    .expect("Failed to generate fix");
+    .expect("Failed to generate fix")
+    .fixed_code;
 
     // Validation: Fixed code should be syntactically valid Python
     validate_python_syntax(&fixed_code)
@@ -648,9 +666,11 @@ class OuterClass:
         "python",
         Some("inner_method"),
         Some("InnerClass"),
+        &[],
     )
     .await
-    .expect("Failed to generate fix");
+    .expect("Failed to generate fix")
+    .fixed_code;
 
     // Validation 1: Fixed code should be syntactically valid Python
     validate_python_syntax(&fixed_code)
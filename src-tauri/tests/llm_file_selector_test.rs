@@ -86,7 +86,7 @@ fn test_smart_mode_selects_security_relevant_files() {
         ("database.py", "cursor.execute('INSERT INTO users VALUES (?, ?)', data)"),
         ("api.py", "@app.route('/api/users', methods=['POST'])"),
         ("secrets.py", "api_key = os.getenv('STRIPE_API_KEY')"),
-        ("uploads.py", "file.write(user_upload_data)"),
+        ("user_uploads.py", "file.write(user_upload_data)"),
         ("client.py", "response = requests.post('https://api.example.com')"),
     ];
 
@@ -202,7 +202,7 @@ fn test_smart_mode_realistic_selection_percentage() {
 
     // Smart mode should select ~30% of files (7 security files out of 23 total = 30.4%)
     assert!(
-        selection_percentage >= 25.0 && selection_percentage <= 40.0,
+        (25.0..=40.0).contains(&selection_percentage),
         "Smart mode should select ~30-40% of files, got {:.1}% ({}/{})",
         selection_percentage,
         selected_count,
@@ -375,21 +375,22 @@ fn test_secrets_pattern_detection() {
 }
 
 /// Test file I/O pattern detection
+///
+/// File I/O is a weak signal: it only selects a file together with other signals.
 #[test]
 fn test_file_io_pattern_detection() {
     let project = TestProject::new("file_io_patterns").unwrap();
 
     let test_cases = vec![
-        // Python file operations
-        ("file_handler.py", "with open(user_path, 'w') as f: f.write(data)", true),
-        ("uploads.py", "file.save(os.path.join(app.config['UPLOAD_FOLDER'], filename))", true),
-
-        // JavaScript/Node.js file operations
-        ("file_ops.js", "fs.readFile(path, 'utf8', callback)", true),
-        ("writer.js", "fs.writeFileSync(tempfile, data)", true),
+        // File I/O alone stays below the threshold
+        ("file_handler.py", "with open(user_path, 'w') as f: f.write(data)", false),
+        ("file_ops.js", "fs.readFile(path, 'utf8', callback)", false),
+        ("paths.py", "full_path = os.path.join(base_dir, user_input)", false),
 
-        // Path operations
-        ("paths.py", "full_path = os.path.join(base_dir, user_input)", true),
+        // File I/O combined with database, keyword or file name signals
+        ("uploads.py", "file.save(os.path.join(app.config['UPLOAD_FOLDER'], filename))", true),
+        ("writer.js", "fs.writeFileSync(tempfile, JSON.stringify({ token, session }))", true),
+        ("user_export.py", "with open(export_path, 'w') as f: f.write(rows)", true),
 
         // Non-file-I/O files
         ("calculator.py", "def multiply(a, b): return a * b", false),
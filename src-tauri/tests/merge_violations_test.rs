@@ -9,7 +9,6 @@
 mod common;
 
 use common::TestProject;
-use ryn::models::violation::{DetectionMethod, Severity};
 
 /// Test that identical line numbers produce hybrid violations
 #[test]
@@ -94,7 +93,7 @@ fn test_merge_within_tolerance() {
     let scan_id = project.insert_scan(project_id, "completed").unwrap();
 
     // Test cases: (regex_line, llm_line, should_merge)
-    let test_cases = vec![
+    let test_cases = [
         (42, 42, true),   // Exact match
         (42, 43, true),   // 1 line apart
         (42, 45, true),   // 3 lines apart (at tolerance)
@@ -103,7 +102,7 @@ fn test_merge_within_tolerance() {
         (42, 38, false),  // 4 lines before (exceeds tolerance)
     ];
 
-    for (idx, (regex_line, llm_line, should_merge)) in test_cases.iter().enumerate() {
+    for (idx, (regex_line, llm_line, _should_merge)) in test_cases.iter().enumerate() {
         let file = format!("test_{}.py", idx);
 
         // Insert regex violation
@@ -313,7 +312,7 @@ fn test_merge_severity_selection() {
     let scan_id = project.insert_scan(project_id, "completed").unwrap();
 
     // Test cases: (regex_severity, llm_severity, expected_highest)
-    let test_cases = vec![
+    let test_cases = [
         ("critical", "high", "critical"),
         ("high", "critical", "critical"),
         ("medium", "low", "medium"),
@@ -369,7 +368,7 @@ fn test_merge_severity_selection() {
         assert_eq!(severities.len(), 2);
 
         // Verify expected severity appears first (highest)
-        let severity_order = vec!["critical", "high", "medium", "low"];
+        let severity_order = ["critical", "high", "medium", "low"];
         let expected_pos = severity_order.iter().position(|&s| s == *expected).unwrap();
 
         for sev in &severities {
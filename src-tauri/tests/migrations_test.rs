@@ -9,7 +9,7 @@
 
 mod common;
 
-use common::TestProject;
+use common::{TestProject, LATEST_SCHEMA_VERSION};
 use rusqlite::Connection;
 use tempfile::TempDir;
 
@@ -18,9 +18,8 @@ use tempfile::TempDir;
 fn test_fresh_db_migrates_to_v2() {
     let project = TestProject::new("fresh_db_v2").unwrap();
 
-    // Verify schema version is 3 (latest)
     let version = project.get_schema_version().unwrap();
-    assert_eq!(version, 3, "Fresh database should be at schema version 3");
+    assert_eq!(version, LATEST_SCHEMA_VERSION, "Fresh database should be at the latest schema version");
 
     // Verify all v1 tables exist
     assert!(project.table_exists("projects").unwrap());
@@ -183,7 +182,7 @@ fn test_migrations_idempotent() {
     let table_count1 = project.list_tables().unwrap().len();
     let index_count1 = project.list_indexes().unwrap().len();
 
-    // Run migrations again (should be no-op since already at v3)
+    // Run migrations again (should be no-op since already at the latest version)
     // Note: TestProject runs migrations in constructor, so we manually verify idempotency
     let conn = project.connection();
 
@@ -192,7 +191,7 @@ fn test_migrations_idempotent() {
     // re-running migrations.
 
     let current_version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0)).unwrap();
-    assert_eq!(current_version, 3, "Should still be at version 3");
+    assert_eq!(current_version, LATEST_SCHEMA_VERSION, "Should still be at the latest version");
 
     // Migration logic should skip v1 and v2 if already at v2
     // (This is what run_migrations() does - checks current_version)
@@ -21,34 +21,6 @@ mod common;
 use common::TestProject;
 use std::path::{Path, PathBuf};
 use std::fs;
-use std::process::Command;
-
-/// Helper: Clone a vulnerable repo into tests/fixtures/ if not already present
-fn ensure_fixture(name: &str, url: &str) -> PathBuf {
-    let fixture_dir = Path::new("tests/fixtures").join(name);
-
-    if !fixture_dir.exists() {
-        println!("[Test Fixture] Cloning {} from {}", name, url);
-
-        // Create fixtures directory
-        fs::create_dir_all("tests/fixtures")
-            .expect("Failed to create tests/fixtures directory");
-
-        // Clone with --depth=1 for speed
-        let status = Command::new("git")
-            .args(["clone", "--depth=1", url, fixture_dir.to_str().unwrap()])
-            .status()
-            .expect(&format!("Failed to clone test fixture: {}", name));
-
-        if !status.success() {
-            panic!("Git clone failed for {}", name);
-        }
-
-        println!("[Test Fixture] Successfully cloned {}", name);
-    }
-
-    fixture_dir
-}
 
 /// Helper: Run all 4 rule engines on a file
 fn run_all_rules(code: &str, file_path: &str, scan_id: i64) -> Vec<ryn::models::Violation> {
@@ -91,7 +63,7 @@ fn scan_python_files(project_dir: &Path, scan_id: i64) -> Vec<ryn::models::Viola
 
         // Only scan Python files
         if path.extension().and_then(|s| s.to_str()) == Some("py") {
-            if let Ok(content) = fs::read_to_string(&path) {
+            if let Ok(content) = fs::read_to_string(path) {
                 // Get relative path from project root
                 let relative_path = path.strip_prefix(project_dir)
                     .unwrap_or(path)
@@ -132,7 +104,7 @@ fn scan_js_files(project_dir: &Path, scan_id: i64) -> Vec<ryn::models::Violation
                 continue;
             }
 
-            if let Ok(content) = fs::read_to_string(&path) {
+            if let Ok(content) = fs::read_to_string(path) {
                 // Get relative path from project root
                 let relative_path = path.strip_prefix(project_dir)
                     .unwrap_or(path)
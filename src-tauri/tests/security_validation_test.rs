@@ -4,7 +4,6 @@
 mod common;
 
 use common::TestProject;
-use rusqlite::Result;
 use ryn::db::queries;
 
 #[test]
@@ -16,14 +15,14 @@ fn test_sql_injection_in_project_name() {
     let malicious_name = "test'; DROP TABLE projects; --";
     let path = test_project.create_file("test.py", "print('test')").unwrap();
 
-    let project_id = queries::insert_project(&conn, malicious_name, path.to_str().unwrap(), None).unwrap();
+    let project_id = queries::insert_project(conn, malicious_name, path.to_str().unwrap(), None).unwrap();
 
     // Verify project was inserted with the malicious string as literal text
-    let project = queries::select_project(&conn, project_id).unwrap().unwrap();
+    let project = queries::select_project(conn, project_id).unwrap().unwrap();
     assert_eq!(project.name, malicious_name);
 
     // Verify projects table still exists
-    let projects = queries::select_projects(&conn).unwrap();
+    let projects = queries::select_projects(conn).unwrap();
     assert!(!projects.is_empty());
 }
 
@@ -72,7 +71,7 @@ fn test_xss_in_violation_description() {
     ).unwrap();
 
     // Verify XSS payload stored as literal text
-    let violation = queries::select_violation(&conn, violation_id).unwrap().unwrap();
+    let violation = queries::select_violation(conn, violation_id).unwrap().unwrap();
     assert_eq!(violation.description, xss_payload);
 }
 
@@ -83,12 +82,12 @@ fn test_path_traversal_in_project_path() {
 
     // Attempt path traversal
     let traversal_path = "../../../etc/passwd";
-    let result = queries::insert_project(&conn, "malicious", traversal_path, None);
+    let result = queries::insert_project(conn, "malicious", traversal_path, None);
 
     // Should either sanitize or store as-is (validation happens at command layer)
     match result {
         Ok(project_id) => {
-            let project = queries::select_project(&conn, project_id).unwrap().unwrap();
+            let project = queries::select_project(conn, project_id).unwrap().unwrap();
             assert_eq!(project.path, traversal_path); // Stored literally
         }
         Err(_) => {
@@ -100,7 +99,7 @@ fn test_path_traversal_in_project_path() {
 #[test]
 fn test_path_traversal_in_file_path() {
     let test_project = TestProject::new("test_path_traversal_in_file_path").unwrap();
-    let conn = test_project.connection();
+    let _conn = test_project.connection();
 
     let project_id = test_project.insert_project("test-project", "/tmp/test", None).unwrap();
     let scan_id = test_project.insert_scan(project_id, "completed").unwrap();
@@ -131,8 +130,8 @@ fn test_unicode_in_project_name() {
     let unicode_name = "项目测试 🚀 Тест مشروع";
     let path = test_project.create_file("test.py", "print('test')").unwrap();
 
-    let project_id = queries::insert_project(&conn, unicode_name, path.to_str().unwrap(), None).unwrap();
-    let project = queries::select_project(&conn, project_id).unwrap().unwrap();
+    let project_id = queries::insert_project(conn, unicode_name, path.to_str().unwrap(), None).unwrap();
+    let project = queries::select_project(conn, project_id).unwrap().unwrap();
 
     assert_eq!(project.name, unicode_name);
 }
@@ -146,11 +145,11 @@ fn test_null_byte_injection() {
     let path = test_project.create_file("test.py", "print('test')").unwrap();
 
     // Null bytes should be handled or rejected
-    let result = queries::insert_project(&conn, null_byte_name, path.to_str().unwrap(), None);
+    let result = queries::insert_project(conn, null_byte_name, path.to_str().unwrap(), None);
 
     match result {
         Ok(project_id) => {
-            let project = queries::select_project(&conn, project_id).unwrap().unwrap();
+            let project = queries::select_project(conn, project_id).unwrap().unwrap();
             // Verify null byte handling
             assert!(project.name.contains("test"));
         }
@@ -169,12 +168,12 @@ fn test_very_long_string_project_name() {
     let long_name = "a".repeat(10000);
     let path = test_project.create_file("test.py", "print('test')").unwrap();
 
-    let result = queries::insert_project(&conn, &long_name, path.to_str().unwrap(), None);
+    let result = queries::insert_project(conn, &long_name, path.to_str().unwrap(), None);
 
     // Should handle gracefully (truncate, error, or accept)
     match result {
         Ok(project_id) => {
-            let project = queries::select_project(&conn, project_id).unwrap().unwrap();
+            let project = queries::select_project(conn, project_id).unwrap().unwrap();
             assert!(!project.name.is_empty());
         }
         Err(e) => {
@@ -187,7 +186,7 @@ fn test_very_long_string_project_name() {
 #[test]
 fn test_control_characters_in_description() {
     let test_project = TestProject::new("test_control_characters_in_description").unwrap();
-    let conn = test_project.connection();
+    let _conn = test_project.connection();
 
     let project_id = test_project.insert_project("test-project", "/tmp/test", None).unwrap();
     let scan_id = test_project.insert_scan(project_id, "completed").unwrap();
@@ -236,7 +235,7 @@ fn test_special_characters_in_code_snippet() {
         None,
     ).unwrap();
 
-    let violation = queries::select_violation(&conn, violation_id).unwrap().unwrap();
+    let violation = queries::select_violation(conn, violation_id).unwrap().unwrap();
     assert_eq!(violation.code_snippet, special_code);
 }
 
@@ -265,7 +264,7 @@ fn test_negative_line_number() {
     // Should either reject or store (validation at command layer)
     match result {
         Ok(violation_id) => {
-            let violation = queries::select_violation(&conn, violation_id).unwrap().unwrap();
+            let violation = queries::select_violation(conn, violation_id).unwrap().unwrap();
             assert_eq!(violation.line_number, -1);
         }
         Err(_) => {
@@ -300,7 +299,7 @@ fn test_invalid_severity_value() {
     // Should store as-is or reject (no enum constraint at DB level)
     match result {
         Ok(violation_id) => {
-            let violation = queries::select_violation(&conn, violation_id).unwrap().unwrap();
+            let violation = queries::select_violation(conn, violation_id).unwrap().unwrap();
             assert_eq!(violation.severity, "invalid_severity");
         }
         Err(_) => {
@@ -312,7 +311,7 @@ fn test_invalid_severity_value() {
 #[test]
 fn test_invalid_detection_method() {
     let test_project = TestProject::new("test_invalid_detection_method").unwrap();
-    let conn = test_project.connection();
+    let _conn = test_project.connection();
 
     let project_id = test_project.insert_project("test-project", "/tmp/test", None).unwrap();
     let scan_id = test_project.insert_scan(project_id, "completed").unwrap();
@@ -369,7 +368,7 @@ fn test_empty_strings_in_required_fields() {
     // Should handle gracefully
     match result {
         Ok(violation_id) => {
-            let violation = queries::select_violation(&conn, violation_id).unwrap().unwrap();
+            let violation = queries::select_violation(conn, violation_id).unwrap().unwrap();
             assert_eq!(violation.control_id, "");
             assert_eq!(violation.description, "");
         }
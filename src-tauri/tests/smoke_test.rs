@@ -4,7 +4,7 @@
 
 mod common;
 
-use common::TestProject;
+use common::{TestProject, LATEST_SCHEMA_VERSION};
 
 #[test]
 fn test_common_module_works() {
@@ -12,7 +12,7 @@ fn test_common_module_works() {
 
     // Verify database is initialized
     let version = project.get_schema_version().unwrap();
-    assert_eq!(version, 3, "Schema should be at version 3");
+    assert_eq!(version, LATEST_SCHEMA_VERSION, "Schema should be at the latest version");
 
     // Verify tables exist
     assert!(project.table_exists("projects").unwrap());
@@ -0,0 +1,129 @@
+//! Violation Index Benchmark
+//!
+//! Times the common violation queries on a large database with and without the
+//! v9 composite indexes. "Before" drops the composite indexes, leaving only the
+//! single-column indexes that existed up to v8.
+
+use ryn::db::{queries, run_migrations, seed_controls};
+use rusqlite::{params, Connection};
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+
+const SCANS: i64 = 50;
+const VIOLATIONS_PER_SCAN: i64 = 2_000;
+const ITERATIONS: u32 = 50;
+
+const COMPOSITE_INDEXES: [&str; 4] = [
+    "idx_violations_scan_control",
+    "idx_violations_scan_severity",
+    "idx_violations_file_line",
+    "idx_violations_scan_status",
+];
+
+fn create_large_db(temp_dir: &TempDir) -> Connection {
+    let conn = Connection::open(temp_dir.path().join("bench.db")).unwrap();
+    run_migrations(&conn).unwrap();
+    seed_controls(&conn).unwrap();
+
+    let project_id = queries::insert_project(&conn, "bench", "/bench", None).unwrap();
+
+    conn.execute_batch("BEGIN").unwrap();
+    {
+        let mut insert = conn
+            .prepare(
+                "INSERT INTO violations (scan_id, control_id, severity, description, file_path, line_number, code_snippet, status)
+                 VALUES (?, ?, ?, 'bench', ?, ?, 'code', ?)",
+            )
+            .unwrap();
+        let controls = ["CC6.1", "CC6.7", "CC7.2", "A1.2"];
+        let severities = ["critical", "high", "medium", "low"];
+        let statuses = ["open", "open", "fixed", "dismissed"];
+
+        for _ in 0..SCANS {
+            let scan_id = queries::insert_scan(&conn, project_id, "regex_only").unwrap();
+            for i in 0..VIOLATIONS_PER_SCAN {
+                let n = i as usize;
+                insert
+                    .execute(params![
+                        scan_id,
+                        controls[n % 4],
+                        severities[(n / 4) % 4],
+                        format!("src/module_{}.py", i % 200),
+                        i % 500,
+                        statuses[(n / 16) % 4],
+                    ])
+                    .unwrap();
+            }
+        }
+    }
+    conn.execute_batch("COMMIT").unwrap();
+
+    conn
+}
+
+fn time_iterations(mut f: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    start.elapsed() / ITERATIONS
+}
+
+/// Run the list query and an open-violation filter for the middle scan
+fn run_common_queries(conn: &Connection) -> (usize, i64) {
+    let scan_id = SCANS / 2;
+    let listed = queries::select_violations(conn, scan_id).unwrap().len();
+    let open: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM violations WHERE scan_id = ? AND status = 'open'",
+            [scan_id],
+            |row| row.get(0),
+        )
+        .unwrap();
+    (listed, open)
+}
+
+#[test]
+fn bench_violation_queries_before_and_after_composite_indexes() {
+    let temp_dir = TempDir::new().unwrap();
+    let conn = create_large_db(&temp_dir);
+
+    let after_results = run_common_queries(&conn);
+    let after = time_iterations(|| {
+        run_common_queries(&conn);
+    });
+    let after_plan = queries::analyze_query_plan(
+        &conn,
+        "SELECT id FROM violations WHERE scan_id = ? AND status = ?",
+    )
+    .unwrap();
+
+    for index in COMPOSITE_INDEXES {
+        conn.execute_batch(&format!("DROP INDEX {}", index)).unwrap();
+    }
+
+    let before_results = run_common_queries(&conn);
+    let before = time_iterations(|| {
+        run_common_queries(&conn);
+    });
+    let before_plan = queries::analyze_query_plan(
+        &conn,
+        "SELECT id FROM violations WHERE scan_id = ? AND status = ?",
+    )
+    .unwrap();
+
+    println!(
+        "Violation queries over {} rows: before {:?}/run, after {:?}/run ({:.1}x)",
+        SCANS * VIOLATIONS_PER_SCAN,
+        before,
+        after,
+        before.as_secs_f64() / after.as_secs_f64().max(f64::EPSILON)
+    );
+    println!("Plan before: {}", before_plan);
+    println!("Plan after: {}", after_plan);
+
+    assert_eq!(before_results, after_results, "indexes must not change query results");
+    assert_eq!(after_results.0, VIOLATIONS_PER_SCAN as usize);
+    assert!(after_plan.contains("idx_violations_scan_status"));
+    assert!(!before_plan.contains("idx_violations_scan_status"));
+}